@@ -1,18 +1,20 @@
 use crate::error::{ConductorApiError, ConductorApiResult};
 use crate::util::AbortOnDropHandle;
-use holo_hash::{ActionHash, DnaHash};
+use holo_hash::{ActionHash, DhtOpHash, DnaHash};
 use holochain_conductor_api::{
-    AdminInterfaceConfig, AdminRequest, AdminResponse, AppAuthenticationToken,
-    AppAuthenticationTokenIssued, AppInfo, AppInterfaceInfo, AppStatusFilter, DhtOpsCursor,
-    FullStateDump, IssueAppAuthenticationTokenPayload, PeerMetaInfo, SourceChainCursor,
-    StorageInfo,
+    conductor::ZomeCallLimits, AbandonedOpsFilter, AdminInterfaceConfig, AdminRequest,
+    AdminResponse, AppAuthenticationToken, AppAuthenticationTokenIssued, AppInfo, AppInterfaceInfo,
+    AppStatusFilter, DhtOpsCursor, FullStateDump, IssueAppAuthenticationTokenPayload, OpTrace,
+    PeerMetaInfo, SourceChainCursor, StorageInfo, WorkflowStateDump,
 };
-use holochain_types::network::HolochainTransportStats;
+#[cfg(feature = "unstable-sharding")]
+use holochain_types::network::ArcPolicy;
+use holochain_types::network::{BandwidthLimits, HolochainTransportStats, PeerLatency};
 use holochain_types::websocket::AllowedOrigins;
 use holochain_types::{
     dna::AgentPubKey,
     prelude::{
-        AppCapGrantInfo, CellId, DeleteCloneCellPayload, InstallAppPayload,
+        AppCapGrantInfo, CellId, DeleteCloneCellPayload, InstallAppPayload, OutboxSignal,
         UpdateCoordinatorsPayload,
     },
 };
@@ -267,18 +269,23 @@ impl AdminWebsocket {
     /// installed app then you can provide the installed_app_id. The client will still need to
     /// authenticate with a valid token for the same app, but clients for other apps will not be
     /// able to connect. If you want to allow all apps to connect then set this to `None`.
+    ///
+    /// Zome calls over the interface can be limited by `zome_call_limits`. Any limit that is not
+    /// set there is taken from the conductor config.
     pub async fn attach_app_interface(
         &self,
         port: u16,
         danger_bind_addr: Option<String>,
         allowed_origins: AllowedOrigins,
         installed_app_id: Option<String>,
+        zome_call_limits: Option<ZomeCallLimits>,
     ) -> ConductorApiResult<u16> {
         let msg = AdminRequest::AttachAppInterface {
             port: Some(port),
             danger_bind_addr,
             allowed_origins,
             installed_app_id,
+            zome_call_limits,
         };
         let response = self.send(msg).await?;
         match response {
@@ -441,6 +448,17 @@ impl AdminWebsocket {
         }
     }
 
+    /// Move the committed data of all the databases of the conductor out of
+    /// their write-ahead logs and into the database files.
+    pub async fn checkpoint_databases(&self) -> ConductorApiResult<()> {
+        let msg = AdminRequest::CheckpointDatabases;
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::DatabasesCheckpointed => Ok(()),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
     pub async fn dump_network_stats(&self) -> ConductorApiResult<HolochainTransportStats> {
         let msg = AdminRequest::DumpNetworkStats;
         let response = self.send(msg).await?;
@@ -450,6 +468,132 @@ impl AdminWebsocket {
         }
     }
 
+    /// Dump the round-trip latency that has been measured for peer URLs.
+    pub async fn dump_peer_latency(&self) -> ConductorApiResult<Vec<PeerLatency>> {
+        let msg = AdminRequest::DumpPeerLatency;
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::PeerLatencyDumped(latency) => Ok(latency),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// Limit the background network traffic of an app, or with `None` remove its limits.
+    pub async fn set_bandwidth_limits(
+        &self,
+        installed_app_id: String,
+        limits: Option<BandwidthLimits>,
+    ) -> ConductorApiResult<()> {
+        let msg = AdminRequest::SetBandwidthLimits {
+            installed_app_id,
+            limits,
+        };
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::BandwidthLimitsSet => Ok(()),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// List the bandwidth limits that have been set for apps.
+    pub async fn list_bandwidth_limits(
+        &self,
+    ) -> ConductorApiResult<std::collections::HashMap<String, BandwidthLimits>> {
+        let msg = AdminRequest::ListBandwidthLimits;
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::BandwidthLimitsListed(limits) => Ok(limits),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// List the durable remote signals waiting in the outbox, optionally only those sent on one DNA.
+    pub async fn list_signal_outbox(
+        &self,
+        dna_hash: Option<DnaHash>,
+    ) -> ConductorApiResult<Vec<OutboxSignal>> {
+        let msg = AdminRequest::ListSignalOutbox { dna_hash };
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::SignalOutboxListed(outbox) => Ok(outbox),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// Set how much of the DHT the conductor holds for a DNA, or for every DNA of an app.
+    ///
+    /// Returns the DNAs that the policy was set for.
+    #[cfg(feature = "unstable-sharding")]
+    pub async fn set_arc_policy(
+        &self,
+        target: holochain_conductor_api::ArcPolicyTarget,
+        policy: Option<ArcPolicy>,
+    ) -> ConductorApiResult<Vec<DnaHash>> {
+        let msg = AdminRequest::SetArcPolicy { target, policy };
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::ArcPolicySet(dna_hashes) => Ok(dna_hashes),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// List the arc policies that have been set on the conductor.
+    #[cfg(feature = "unstable-sharding")]
+    pub async fn list_arc_policies(
+        &self,
+    ) -> ConductorApiResult<std::collections::HashMap<DnaHash, ArcPolicy>> {
+        let msg = AdminRequest::ListArcPolicies;
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::ArcPoliciesListed(policies) => Ok(policies),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// Dump the state of the validation and integration workflows of a DNA.
+    pub async fn dump_workflow_state(
+        &self,
+        dna_hash: DnaHash,
+    ) -> ConductorApiResult<WorkflowStateDump> {
+        let msg = AdminRequest::DumpWorkflowState { dna_hash };
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::WorkflowStateDumped(dump) => Ok(*dump),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// Re-queue the abandoned ops of a DNA that match `filter` for validation.
+    ///
+    /// Returns the number of ops that were re-queued.
+    pub async fn retry_abandoned_ops(
+        &self,
+        dna_hash: DnaHash,
+        filter: AbandonedOpsFilter,
+    ) -> ConductorApiResult<u64> {
+        let msg = AdminRequest::RetryAbandonedOps { dna_hash, filter };
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::AbandonedOpsRetried(retried) => Ok(retried),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// Trace a single DHT op through this conductor's databases, validation
+    /// queues and peer store.
+    pub async fn trace_op(
+        &self,
+        dna_hash: DnaHash,
+        op_hash: DhtOpHash,
+    ) -> ConductorApiResult<OpTrace> {
+        let msg = AdminRequest::TraceOp { dna_hash, op_hash };
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::OpTraced(trace) => Ok(*trace),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
     /// Dump one exclusive page of a cell's source-chain state.
     ///
     /// # Errors
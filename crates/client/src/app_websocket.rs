@@ -316,6 +316,26 @@ impl AppWebsocket {
         }
     }
 
+    /// Sends a pre-signed zome call and also returns the wasm metering points
+    /// the call consumed.
+    ///
+    /// The points are `None` if the call was not run by a metered wasm backend.
+    pub async fn signed_call_zome_metered(
+        &self,
+        signed_params: ZomeCallParamsSigned,
+    ) -> ConductorApiResult<(ExternIO, Option<u64>)> {
+        let app_request = AppRequest::CallZomeMetered(Box::new(signed_params));
+        let response = self.inner.send(app_request).await?;
+
+        match response {
+            AppResponse::ZomeCalledMetered {
+                output,
+                points_consumed,
+            } => Ok((*output, points_consumed)),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
+    }
+
     pub async fn provide_memproofs(&self, memproofs: MemproofMap) -> ConductorApiResult<()> {
         let app_request = AppRequest::ProvideMemproofs(memproofs);
         let response = self.inner.send(app_request).await?;
@@ -9,9 +9,9 @@ pub use admin_websocket::{AdminWebsocket, AuthorizeSigningCredentialsPayload, En
 pub use app_websocket::{AppWebsocket, CallZomeOptions, ZomeCallTarget};
 pub use error::{ConductorApiError, ConductorApiResult};
 pub use holochain_conductor_api::{
-    AdminRequest, AdminResponse, AppAuthenticationRequest, AppAuthenticationToken,
-    AppAuthenticationTokenIssued, AppInfo, AppRequest, AppResponse, AppStatusFilter, CellInfo,
-    IssueAppAuthenticationTokenPayload, PeerMetaInfo, ProvisionedCell,
+    conductor::ZomeCallLimits, AdminRequest, AdminResponse, AppAuthenticationRequest,
+    AppAuthenticationToken, AppAuthenticationTokenIssued, AppInfo, AppRequest, AppResponse,
+    AppStatusFilter, CellInfo, IssueAppAuthenticationTokenPayload, PeerMetaInfo, ProvisionedCell,
 };
 pub use holochain_serialized_bytes::prelude::SerializedBytes;
 pub use holochain_types::{
@@ -55,6 +55,7 @@ async fn signed_zome_call() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -62,7 +63,7 @@ async fn signed_zome_call() {
 
     // Connect app agent client
     let app_ws_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
     let issued_token = admin_ws
@@ -132,6 +133,7 @@ async fn storage_info() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -149,6 +151,34 @@ async fn storage_info() {
     assert_eq!(1, matched_storage_info.len());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn checkpoint_databases() {
+    let conductor = SweetConductor::standard().await;
+    let admin_port = conductor.get_arbitrary_admin_websocket_port().unwrap();
+    let admin_ws = AdminWebsocket::connect(format!("127.0.0.1:{admin_port}"), None)
+        .await
+        .unwrap();
+    let app_id: InstalledAppId = "test-app".into();
+    let agent_key = admin_ws.generate_agent_pub_key().await.unwrap();
+    admin_ws
+        .install_app(InstallAppPayload {
+            agent_key: Some(agent_key),
+            installed_app_id: Some(app_id.clone()),
+            network_seed: None,
+            roles_settings: None,
+            source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
+            ignore_genesis_failure: false,
+            restore_from_dht: false,
+            wasm_metering: None,
+        })
+        .await
+        .unwrap();
+    admin_ws.enable_app(app_id.clone()).await.unwrap();
+    admin_ws.disable_app(app_id).await.unwrap();
+
+    admin_ws.checkpoint_databases().await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn dump_network_stats() {
     let conductor = SweetConductor::standard().await;
@@ -167,6 +197,7 @@ async fn dump_network_stats() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -195,6 +226,7 @@ async fn agent_info() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -254,6 +286,7 @@ async fn peer_meta_info() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -284,6 +317,60 @@ async fn peer_meta_info() {
     assert_eq!(meta_infos.len(), 0);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn dump_workflow_state() {
+    let conductor = SweetConductor::standard().await;
+    let admin_port = conductor.get_arbitrary_admin_websocket_port().unwrap();
+    let admin_ws = AdminWebsocket::connect(format!("127.0.0.1:{admin_port}"), None)
+        .await
+        .unwrap();
+    let app_id: InstalledAppId = "test-app".into();
+    let agent_key = admin_ws.generate_agent_pub_key().await.unwrap();
+    admin_ws
+        .install_app(InstallAppPayload {
+            agent_key: Some(agent_key.clone()),
+            installed_app_id: Some(app_id.clone()),
+            network_seed: None,
+            roles_settings: None,
+            source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
+            ignore_genesis_failure: false,
+            restore_from_dht: false,
+            wasm_metering: None,
+        })
+        .await
+        .unwrap();
+    admin_ws.enable_app(app_id.clone()).await.unwrap();
+
+    let app_info = admin_ws
+        .list_apps(None)
+        .await
+        .unwrap()
+        .first()
+        .unwrap()
+        .clone();
+    let dna_hash = match app_info.cell_info.first().unwrap().1.first().unwrap() {
+        CellInfo::Provisioned(c) => c.cell_id.dna_hash().clone(),
+        _ => panic!("Wrong CellInfo type."),
+    };
+
+    let dump = admin_ws.dump_workflow_state(dna_hash).await.unwrap();
+
+    assert_eq!(dump.limbo.abandoned, 0);
+    assert!(dump.abandoned_ops.is_empty());
+    let workflows = dump
+        .queue_consumers
+        .iter()
+        .filter(|status| status.running)
+        .map(|status| status.workflow.as_str())
+        .collect::<Vec<_>>();
+    assert!(workflows.contains(&"sys_validation"), "{workflows:?}");
+    assert!(workflows.contains(&"app_validation"), "{workflows:?}");
+
+    // A DNA that is not installed is an error.
+    let unknown_dna = holochain::prelude::DnaHash::from_raw_36(vec![0xdb; 36]);
+    assert!(admin_ws.dump_workflow_state(unknown_dna).await.is_err());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn install_app_then_list_apps_and_list_cell_ids() {
     let conductor = SweetConductor::standard().await;
@@ -302,6 +389,7 @@ async fn install_app_then_list_apps_and_list_cell_ids() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -349,6 +437,7 @@ async fn install_app_with_roles_settings() {
             membrane_proof: Default::default(),
             modifiers: Some(custom_modifiers),
             init_properties: None,
+            network: None,
         },
     );
 
@@ -361,6 +450,7 @@ async fn install_app_with_roles_settings() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -51,6 +51,7 @@ async fn handle_signal() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -58,7 +59,7 @@ async fn handle_signal() {
 
     // Connect app agent client
     let app_ws_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
     let token_issued = admin_ws
@@ -148,6 +149,7 @@ async fn close_on_drop_is_clone_safe() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -155,7 +157,7 @@ async fn close_on_drop_is_clone_safe() {
 
     // Connect app client
     let app_ws_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
     let token_issued = admin_ws
@@ -220,13 +222,14 @@ async fn deferred_memproof_installation() {
             source: AppBundleSource::Bytes(app_bundle_bytes),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
 
     // Connect app client
     let app_ws_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
     let token_issued = admin_ws
@@ -286,7 +289,7 @@ async fn connect_multiple_addresses() {
         .await
         .unwrap();
     let app_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -301,6 +304,7 @@ async fn connect_multiple_addresses() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -348,6 +352,7 @@ async fn connect_with_custom_origin() {
             None,
             AllowedOrigins::from("my_cli_app".to_string()),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -363,6 +368,7 @@ async fn connect_with_custom_origin() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -415,6 +421,7 @@ async fn dump_network_stats() {
             None,
             AllowedOrigins::from("my_cli_app".to_string()),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -430,6 +437,7 @@ async fn dump_network_stats() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -474,6 +482,7 @@ async fn dump_network_metrics() {
             None,
             AllowedOrigins::from("my_cli_app".to_string()),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -489,6 +498,7 @@ async fn dump_network_metrics() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -538,6 +548,7 @@ async fn agent_info() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -545,7 +556,7 @@ async fn agent_info() {
 
     // Connect app client
     let app_ws_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
     let token_issued = admin_ws
@@ -613,6 +624,7 @@ async fn peer_meta_info() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -620,7 +632,7 @@ async fn peer_meta_info() {
 
     // Connect app client
     let app_ws_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
     let token_issued = admin_ws
@@ -681,13 +693,14 @@ async fn call_zome_with_options_custom_timeout() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
     admin_ws.enable_app(app_id.clone()).await.unwrap();
 
     let app_ws_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
     let token_issued = admin_ws
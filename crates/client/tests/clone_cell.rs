@@ -37,12 +37,13 @@ async fn clone_cell_management() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
     admin_ws.enable_app(app_id.clone()).await.unwrap();
     let app_api_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -190,6 +191,7 @@ pub async fn app_info_refresh() {
             source: AppBundleSource::Bytes(fixture::get_fixture_app_bundle()),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -199,7 +201,7 @@ pub async fn app_info_refresh() {
 
     // Create an app interface and connect an app agent to it
     let app_api_port = admin_ws
-        .attach_app_interface(0, None, AllowedOrigins::Any, None)
+        .attach_app_interface(0, None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -70,6 +70,7 @@ fn make_fixture_app_bundle() -> Bytes {
                 installed_hash: None,
                 clone_limit: 10,
             },
+            network: None,
         }],
         bootstrap_url: None,
         relay_url: None,
@@ -0,0 +1,424 @@
+//! Offline analysis of the report files that a conductor writes when
+//! `hc_report` is enabled.
+//!
+//! Each conductor writes the reports it receives from the peers that fetched
+//! ops from it to `<data-root>/reports/hc-report.YYYY-MM-DD.jsonl`. Every
+//! fetched ops entry is signed by all of the agents of the peer that received
+//! the ops, so the volumes in it can be attributed to that peer.
+//!
+//! `hc report` reads those files back, verifies the signatures on each entry
+//! and sums the op count and bytes of the valid entries by day, space and
+//! peer. Entries whose signatures don't verify are not counted.
+
+use chrono::DateTime;
+use clap::{Parser, ValueEnum};
+use holochain_keystore::ReportEntryFetchedOpsExt;
+use holochain_types::report::{ReportEntry, ReportEntryFetchedOps};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Aggregate the fetched op volumes in a directory of `hc-report.*.jsonl` files.
+#[derive(Debug, Parser)]
+pub struct HcReport {
+    /// The directory containing the report files, usually `<data-root>/reports`.
+    pub path: PathBuf,
+
+    /// What to aggregate the volumes by.
+    ///
+    /// May be given more than once, or as a comma separated list. Volumes
+    /// are aggregated by day, space and peer if not given.
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [GroupBy::Day, GroupBy::Space, GroupBy::Peer])]
+    pub group_by: Vec<GroupBy>,
+
+    /// Write the aggregated volumes as CSV to this file instead of printing
+    /// a table. Use `-` to write the CSV to stdout.
+    #[arg(long)]
+    pub csv: Option<PathBuf>,
+}
+
+/// A property of a fetched ops report to aggregate volumes by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum GroupBy {
+    /// The UTC day that the report was made on.
+    Day,
+    /// The space, i.e. the DNA, that the ops were fetched in.
+    Space,
+    /// The agents of the peer that fetched the ops.
+    Peer,
+}
+
+impl GroupBy {
+    fn column(&self) -> &'static str {
+        match self {
+            GroupBy::Day => "day",
+            GroupBy::Space => "space",
+            GroupBy::Peer => "peer",
+        }
+    }
+}
+
+/// The op volume of a single verified fetched ops report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedOps {
+    /// The UTC day of the report, as `YYYY-MM-DD`.
+    pub day: String,
+    /// The base64url space id.
+    pub space: String,
+    /// The agent keys of the peer that fetched the ops, sorted and separated by spaces.
+    pub peer: String,
+    /// The number of ops fetched.
+    pub op_count: u64,
+    /// The total size of the fetched ops in bytes.
+    pub total_bytes: u64,
+}
+
+impl FetchedOps {
+    fn try_from_entry(entry: &ReportEntryFetchedOps) -> Option<Self> {
+        let timestamp: i64 = entry.timestamp.parse().ok()?;
+        let day = DateTime::from_timestamp_micros(timestamp)?
+            .format("%Y-%m-%d")
+            .to_string();
+        let mut agents = entry.agent_pubkeys.clone();
+        agents.sort();
+
+        Some(Self {
+            day,
+            space: entry.space.clone(),
+            peer: agents.join(" "),
+            op_count: entry.op_count.parse().ok()?,
+            total_bytes: entry.total_bytes.parse().ok()?,
+        })
+    }
+
+    fn key(&self, group_by: &[GroupBy]) -> Vec<String> {
+        group_by
+            .iter()
+            .map(|g| match g {
+                GroupBy::Day => self.day.clone(),
+                GroupBy::Space => self.space.clone(),
+                GroupBy::Peer => self.peer.clone(),
+            })
+            .collect()
+    }
+}
+
+/// The fetched ops reports read from a directory of report files.
+#[derive(Debug, Default)]
+pub struct LoadedReports {
+    /// The reports whose signatures were verified.
+    pub fetched_ops: Vec<FetchedOps>,
+    /// The number of fetched ops reports whose signatures did not verify.
+    pub invalid_signatures: usize,
+    /// The number of lines that could not be parsed as a report entry.
+    pub unparsable: usize,
+}
+
+/// The summed volume of the reports that share a group key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Volume {
+    /// The values of the properties that were grouped by, in the order given.
+    pub key: Vec<String>,
+    /// The number of reports that were summed.
+    pub reports: u64,
+    /// The total number of ops fetched, saturating at `u64::MAX`.
+    pub op_count: u64,
+    /// The total size of the fetched ops in bytes, saturating at `u64::MAX`.
+    pub total_bytes: u64,
+}
+
+impl HcReport {
+    /// Run this command.
+    pub async fn run(self) -> anyhow::Result<()> {
+        let files = list_report_files(&self.path)?;
+        if files.is_empty() {
+            anyhow::bail!(
+                "No hc-report.*.jsonl files found in {}",
+                self.path.display()
+            );
+        }
+
+        let loaded = load_reports(&files).await?;
+        eprintln!(
+            "Read {} verified fetched ops reports from {} files",
+            loaded.fetched_ops.len(),
+            files.len()
+        );
+        if loaded.invalid_signatures > 0 {
+            eprintln!(
+                "Skipped {} reports with invalid signatures",
+                loaded.invalid_signatures
+            );
+        }
+        if loaded.unparsable > 0 {
+            eprintln!("Skipped {} unparsable lines", loaded.unparsable);
+        }
+
+        let volumes = aggregate(&loaded.fetched_ops, &self.group_by);
+
+        match self.csv {
+            Some(path) if path.as_os_str() == "-" => print!("{}", to_csv(&self.group_by, &volumes)),
+            Some(path) => {
+                std::fs::write(&path, to_csv(&self.group_by, &volumes))?;
+                eprintln!("Wrote {} rows to {}", volumes.len(), path.display());
+            }
+            None => print_table(&self.group_by, &volumes),
+        }
+
+        Ok(())
+    }
+}
+
+/// List the `hc-report.*.jsonl` files in a directory, in name and so date order.
+pub fn list_report_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("hc-report.")
+            && name.ends_with(".jsonl")
+            && entry.file_type()?.is_file()
+        {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Read the fetched ops reports from report files, verifying their signatures.
+pub async fn load_reports(files: &[PathBuf]) -> anyhow::Result<LoadedReports> {
+    let mut out = LoadedReports::default();
+    for file in files {
+        let data = std::fs::read_to_string(file)?;
+        for line in data.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let entry = match serde_json::from_str::<ReportEntry>(line) {
+                Ok(ReportEntry::FetchedOps(entry)) => entry,
+                Ok(ReportEntry::Start(_)) => continue,
+                Err(_) => {
+                    out.unparsable += 1;
+                    continue;
+                }
+            };
+
+            if !entry.verify().await {
+                out.invalid_signatures += 1;
+                continue;
+            }
+
+            match FetchedOps::try_from_entry(&entry) {
+                Some(fetched_ops) => out.fetched_ops.push(fetched_ops),
+                None => out.unparsable += 1,
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Sum the volumes of the reports that share the same values for `group_by`.
+///
+/// The volumes are sorted by their key.
+pub fn aggregate(fetched_ops: &[FetchedOps], group_by: &[GroupBy]) -> Vec<Volume> {
+    let mut volumes: BTreeMap<Vec<String>, Volume> = BTreeMap::new();
+    for report in fetched_ops {
+        let key = report.key(group_by);
+        let volume = volumes.entry(key.clone()).or_insert_with(|| Volume {
+            key,
+            reports: 0,
+            op_count: 0,
+            total_bytes: 0,
+        });
+        volume.reports += 1;
+        // Peers sign their own counts, so a report can hold any value.
+        volume.op_count = volume.op_count.saturating_add(report.op_count);
+        volume.total_bytes = volume.total_bytes.saturating_add(report.total_bytes);
+    }
+    volumes.into_values().collect()
+}
+
+fn header(group_by: &[GroupBy]) -> Vec<String> {
+    group_by
+        .iter()
+        .map(|g| g.column())
+        .chain(["reports", "op_count", "total_bytes"])
+        .map(String::from)
+        .collect()
+}
+
+fn row(volume: &Volume) -> Vec<String> {
+    volume
+        .key
+        .iter()
+        .cloned()
+        .chain([
+            volume.reports.to_string(),
+            volume.op_count.to_string(),
+            volume.total_bytes.to_string(),
+        ])
+        .collect()
+}
+
+/// Encode aggregated volumes as CSV, with a header row.
+pub fn to_csv(group_by: &[GroupBy], volumes: &[Volume]) -> String {
+    std::iter::once(header(group_by))
+        .chain(volumes.iter().map(row))
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|f| csv_field(f))
+                .collect::<Vec<_>>()
+                .join(",")
+                + "\n"
+        })
+        .collect()
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn print_table(group_by: &[GroupBy], volumes: &[Volume]) {
+    let rows: Vec<Vec<String>> = std::iter::once(header(group_by))
+        .chain(volumes.iter().map(row))
+        .collect();
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|i| rows.iter().map(|r| r[i].len()).max().unwrap_or_default())
+        .collect();
+    for r in rows {
+        let line = r
+            .iter()
+            .zip(&widths)
+            .map(|(field, width)| format!("{field:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::*;
+    use holo_hash::AgentPubKey;
+    use holochain_keystore::{AgentPubKeyExt, MetaLairClient};
+
+    async fn signed_entry(
+        keystore: &MetaLairClient,
+        agent: &AgentPubKey,
+        timestamp: &str,
+        space: &str,
+        op_count: u64,
+        total_bytes: u64,
+    ) -> ReportEntryFetchedOps {
+        let mut entry = ReportEntryFetchedOps {
+            timestamp: timestamp.to_string(),
+            space: space.to_string(),
+            op_count: op_count.to_string(),
+            total_bytes: total_bytes.to_string(),
+            agent_pubkeys: vec![agent.to_string()],
+            signatures: vec![],
+        };
+        let signature = agent
+            .sign_raw(keystore, entry.encode_for_verification().into())
+            .await
+            .unwrap();
+        entry
+            .signatures
+            .push(BASE64_URL_SAFE_NO_PAD.encode(signature.0));
+        entry
+    }
+
+    fn to_line(entry: ReportEntryFetchedOps) -> String {
+        serde_json::to_string(&ReportEntry::FetchedOps(entry)).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn aggregates_verified_reports() {
+        let keystore = holochain_keystore::spawn_mem_keystore().await.unwrap();
+        let alice = keystore.new_sign_keypair_random().await.unwrap();
+        let bob = keystore.new_sign_keypair_random().await.unwrap();
+
+        // 2025-01-01 and 2025-01-02, UTC
+        let day_1 = "1735732800000000";
+        let day_2 = "1735819200000000";
+
+        let mut tampered = signed_entry(&keystore, &bob, day_1, "space-a", 1, 1).await;
+        tampered.op_count = "1000".to_string();
+
+        let dir = tempfile::tempdir().unwrap();
+        let lines = [
+            serde_json::to_string(&ReportEntry::start()).unwrap(),
+            to_line(signed_entry(&keystore, &alice, day_1, "space-a", 2, 100).await),
+            to_line(signed_entry(&keystore, &alice, day_1, "space-a", 3, 50).await),
+            to_line(signed_entry(&keystore, &bob, day_1, "space-a", 1, 10).await),
+            to_line(tampered),
+            "not json".to_string(),
+        ];
+        std::fs::write(
+            dir.path().join("hc-report.2025-01-01.jsonl"),
+            lines.join("\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("hc-report.2025-01-02.jsonl"),
+            to_line(signed_entry(&keystore, &alice, day_2, "space-b", 4, 40).await),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("other.log"), "ignored").unwrap();
+
+        let files = list_report_files(dir.path()).unwrap();
+        assert_eq!(files.len(), 2);
+
+        let loaded = load_reports(&files).await.unwrap();
+        assert_eq!(loaded.fetched_ops.len(), 4);
+        assert_eq!(loaded.invalid_signatures, 1);
+        assert_eq!(loaded.unparsable, 1);
+
+        let by_peer = aggregate(&loaded.fetched_ops, &[GroupBy::Peer]);
+        let alice_volume = by_peer
+            .iter()
+            .find(|v| v.key == vec![alice.to_string()])
+            .unwrap();
+        assert_eq!(
+            (
+                alice_volume.reports,
+                alice_volume.op_count,
+                alice_volume.total_bytes
+            ),
+            (3, 9, 190)
+        );
+
+        let by_day_and_space = aggregate(&loaded.fetched_ops, &[GroupBy::Day, GroupBy::Space]);
+        assert_eq!(
+            to_csv(&[GroupBy::Day, GroupBy::Space], &by_day_and_space),
+            "day,space,reports,op_count,total_bytes\n\
+             2025-01-01,space-a,3,6,160\n\
+             2025-01-02,space-b,1,4,40\n"
+        );
+    }
+
+    #[test]
+    fn aggregate_saturates_totals() {
+        let report = |op_count, total_bytes| FetchedOps {
+            day: "2025-01-01".to_string(),
+            space: "space-a".to_string(),
+            peer: "peer".to_string(),
+            op_count,
+            total_bytes,
+        };
+
+        let volumes = aggregate(&[report(u64::MAX, u64::MAX), report(1, 1)], &[]);
+        assert_eq!(
+            volumes,
+            vec![Volume {
+                key: vec![],
+                reports: 2,
+                op_count: u64::MAX,
+                total_bytes: u64::MAX,
+            }]
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Static checks of DNA bundles, run by `hc dna check`.
+
+use anyhow::Context;
+use holochain::core::ribosome::dna_check::{check_dna_bundle, DnaCheckReport};
+use holochain::core::ribosome::real_ribosome::WasmBackend;
+use holochain_types::prelude::DnaManifest;
+use mr_bundle::FileSystemBundler;
+use std::path::Path;
+
+/// Check the DNA bundle at `path` and print a report.
+///
+/// Fails if the report contains any errors, so that it can be used in CI.
+pub async fn check_dna(path: &Path, json: bool) -> anyhow::Result<()> {
+    let report = check_dna_at(path).await?;
+
+    if json {
+        // Print without other text so it can be piped
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{report}");
+    }
+
+    if report.has_errors() {
+        anyhow::bail!("Found problems in DNA bundle {}", path.display());
+    }
+    Ok(())
+}
+
+/// Check the DNA bundle at `path`.
+pub(crate) async fn check_dna_at(path: &Path) -> anyhow::Result<DnaCheckReport> {
+    // The manifest is not validated on load so that all of its problems can be reported.
+    let bundle = FileSystemBundler::load_from::<DnaManifest>(path)
+        .await
+        .with_context(|| format!("Failed to read DNA bundle {}", path.display()))?;
+    Ok(check_dna_bundle(wasm_backend(), bundle).await?)
+}
+
+#[cfg(not(any(feature = "wasmer-sys-cranelift", feature = "wasmer-wasmi")))]
+compile_error!("`dna-check` needs one of the `wasmer-sys-cranelift` or `wasmer-wasmi` features.");
+
+pub(crate) fn wasm_backend() -> WasmBackend {
+    #[cfg(feature = "wasmer-sys-cranelift")]
+    return WasmBackend::Cranelift;
+
+    #[cfg(all(feature = "wasmer-wasmi", not(feature = "wasmer-sys-cranelift")))]
+    return WasmBackend::Wasmi;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::prelude::DnaWasm;
+    use holochain_wasm_test_utils::{TestWasm, TestWasmPair};
+
+    /// Pack a DNA with the integrity and coordinator zomes of `test_wasm`,
+    /// with the coordinator zome depending on `dependency`.
+    async fn pack_dna(dir: &Path, test_wasm: TestWasm, dependency: &str) -> std::path::PathBuf {
+        let TestWasmPair {
+            integrity,
+            coordinator,
+        } = TestWasmPair::<DnaWasm>::from(test_wasm);
+        std::fs::write(dir.join("integrity.wasm"), integrity.code()).unwrap();
+        std::fs::write(dir.join("coordinator.wasm"), coordinator.code()).unwrap();
+        let manifest = format!(
+            r#"---
+manifest_version: "0"
+name: test_dna
+integrity:
+  network_seed: 00000000-0000-0000-0000-000000000000
+  properties: ~
+  zomes:
+    - name: integrity
+      path: integrity.wasm
+coordinator:
+  zomes:
+    - name: coordinator
+      path: coordinator.wasm
+      dependencies:
+        - name: {dependency}
+"#
+        );
+        std::fs::write(dir.join("dna.yaml"), manifest).unwrap();
+
+        crate::packing::pack::<DnaManifest>(dir, None, "test_dna".to_string(), false)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_wasm_dna_passes_check() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dna_path = pack_dna(tmp_dir.path(), TestWasm::EntryDefs, "integrity").await;
+
+        let report = check_dna_at(&dna_path).await.unwrap();
+        assert!(!report.has_errors(), "{report}");
+        assert!(report.dna_hash.is_some());
+
+        let integrity = report.zomes.iter().find(|z| z.integrity).unwrap();
+        assert_eq!(integrity.entry_defs, vec!["post", "comment"]);
+        let coordinator = report.zomes.iter().find(|z| !z.integrity).unwrap();
+        assert!(!coordinator.host_fn_imports.is_empty());
+
+        check_dna(&dna_path, true).await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dangling_dependency_fails_check() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dna_path = pack_dna(tmp_dir.path(), TestWasm::EntryDefs, "missing").await;
+
+        let report = check_dna_at(&dna_path).await.unwrap();
+        assert!(report.has_errors());
+        assert!(report
+            .findings
+            .iter()
+            .any(|f| f.message == "Dependency 'missing' is not an integrity zome of this DNA"));
+
+        assert!(check_dna(&dna_path, true).await.is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! CLI definitions.
 
 use crate::error::HcBundleResult;
+use crate::sign::SignArgs;
 use anyhow::Context;
 use clap::{Parser, Subcommand};
 use holochain_types::dna::DnaBundle;
@@ -58,6 +59,13 @@ pub enum HcDnaBundleSubcommand {
         /// provided working directory.
         #[arg(short = 'o', long)]
         output: Option<PathBuf>,
+
+        /// Write the bundle in the streaming format, which can be packed and
+        /// installed one resource at a time instead of all at once, so large
+        /// bundles need much less memory. Only Holochain versions that support
+        /// the format can read it.
+        #[arg(long)]
+        streaming: bool,
     },
 
     /// Unpack parts of the `.dna` bundle file into a specific directory.
@@ -98,6 +106,64 @@ pub enum HcDnaBundleSubcommand {
         /// The path to the dna file.
         path: std::path::PathBuf,
     },
+
+    /// Check a `.dna` bundle for problems before it is installed.
+    ///
+    /// The manifest is checked, each zome's WASM is compiled and its imports
+    /// are compared with the host functions of this version of Holochain,
+    /// and the `entry_defs` and entry and link type callbacks of the
+    /// integrity zomes are called.
+    ///
+    /// e.g.:
+    ///
+    /// $ hc dna check ./some/dir/my-dna.dna
+    ///
+    /// prints a report and exits with an error if any problems were found
+    /// that would stop the DNA from being installed or its zomes from running.
+    #[cfg(feature = "dna-check")]
+    Check {
+        /// The path to the dna file.
+        path: PathBuf,
+
+        /// Print the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare two `.dna` bundles to find out whether the new one can
+    /// replace the old one.
+    ///
+    /// The modifiers, the WASM of the integrity and coordinator zomes and the
+    /// entry defs and link types of the integrity zomes are compared. The
+    /// result is one of:
+    ///
+    /// - network-compatible: the DNA hash and the coordinators are unchanged,
+    ///
+    /// - coordinator update only: the DNA hash is unchanged, and the new
+    ///   coordinators can be installed with `UpdateCoordinators`,
+    ///
+    /// - new network: the DNA hash changed, so cells of the new DNA will be
+    ///   on a different network. The old DNA hash is suggested as an entry of
+    ///   the top-level `lineage` of the new DNA's manifest, which is only
+    ///   available when `hc` is built with the `unstable-migration` feature.
+    ///
+    /// Entry defs and link types are only compared when `hc` is built with
+    /// the `dna-check` feature, which is enabled by default.
+    ///
+    /// e.g.:
+    ///
+    /// $ hc dna diff ./old/my-dna.dna ./new/my-dna.dna
+    Diff {
+        /// The path to the old dna file.
+        old: PathBuf,
+
+        /// The path to the new dna file.
+        new: PathBuf,
+
+        /// Print the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Work with Holochain hApp bundles.
@@ -145,6 +211,17 @@ pub enum HcAppBundleSubcommand {
         /// as each of the DNA files specified in the manifest.
         #[arg(short, long)]
         recursive: bool,
+
+        /// Write the bundle in the streaming format, which can be packed and
+        /// installed one resource at a time instead of all at once, so large
+        /// bundles need much less memory. Only Holochain versions that support
+        /// the format can read it.
+        #[arg(long)]
+        streaming: bool,
+
+        /// Options for signing the bundle as its publisher.
+        #[command(flatten)]
+        sign: SignArgs,
     },
 
     /// Unpack parts of the `.happ` bundle file into a specific directory.
@@ -229,6 +306,19 @@ pub enum HcWebAppBundleSubcommand {
         /// as each of the DNA files specified in the hApps' manifests.
         #[arg(short, long)]
         recursive: bool,
+
+        /// Write the bundle in the streaming format, which can be packed and
+        /// installed one resource at a time instead of all at once, so large
+        /// bundles need much less memory. Only Holochain versions that support
+        /// the format can read it.
+        #[arg(long)]
+        streaming: bool,
+
+        /// Options for signing the bundle as its publisher.
+        ///
+        /// The signature covers the UI and the hApp bundle.
+        #[command(flatten)]
+        sign: SignArgs,
     },
 
     /// Unpack parts of the `.webhapp` bundle file into a specific directory.
@@ -301,10 +391,15 @@ impl HcDnaBundleSubcommand {
             Self::Init { path } => {
                 crate::init::init_dna(path).await?;
             }
-            Self::Pack { path, output } => {
+            Self::Pack {
+                path,
+                output,
+                streaming,
+            } => {
                 let name = get_dna_name(&path).await?;
                 let bundle_path =
-                    crate::packing::pack::<ValidatedDnaManifest>(&path, output, name).await?;
+                    crate::packing::pack::<ValidatedDnaManifest>(&path, output, name, streaming)
+                        .await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
             }
             Self::Unpack {
@@ -342,6 +437,13 @@ impl HcDnaBundleSubcommand {
                 let dna_hash_b64 = bundle.to_dna_file().await?.0.dna_hash().to_string();
                 println!("{dna_hash_b64}");
             }
+            #[cfg(feature = "dna-check")]
+            Self::Check { path, json } => {
+                crate::check::check_dna(&path, json).await?;
+            }
+            Self::Diff { old, new, json } => {
+                crate::diff::diff_dna(&old, &new, json).await?;
+            }
         }
         Ok(())
     }
@@ -358,15 +460,22 @@ impl HcAppBundleSubcommand {
                 path,
                 output,
                 recursive,
+                streaming,
+                sign,
             } => {
                 let name = get_app_name(&path).await?;
 
                 if recursive {
-                    app_pack_recursive(&path).await?;
+                    app_pack_recursive(&path, streaming).await?;
                 }
 
-                let bundle_path = crate::packing::pack::<AppManifest>(&path, output, name).await?;
+                let bundle_path =
+                    crate::packing::pack::<AppManifest>(&path, output, name, streaming).await?;
+                sign.sign_bundle::<AppManifest>(&bundle_path).await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
+                if let Some(publisher) = sign.sign {
+                    println!("Signed bundle as publisher {publisher}");
+                }
             }
             Self::Unpack {
                 path,
@@ -411,16 +520,22 @@ impl HcWebAppBundleSubcommand {
                 path,
                 output,
                 recursive,
+                streaming,
+                sign,
             } => {
                 let name = get_web_app_name(&path).await?;
 
                 if recursive {
-                    web_app_pack_recursive(&path).await?;
+                    web_app_pack_recursive(&path, streaming).await?;
                 }
 
                 let bundle_path =
-                    crate::packing::pack::<WebAppManifest>(&path, output, name).await?;
+                    crate::packing::pack::<WebAppManifest>(&path, output, name, streaming).await?;
+                sign.sign_bundle::<WebAppManifest>(&bundle_path).await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
+                if let Some(publisher) = sign.sign {
+                    println!("Signed bundle as publisher {publisher}");
+                }
             }
             Self::Unpack {
                 path,
@@ -482,7 +597,10 @@ pub async fn get_web_app_name(manifest_path: &Path) -> HcBundleResult<String> {
 }
 
 /// Pack the app's manifest and all its DNAs if their location is bundled
-pub async fn web_app_pack_recursive(web_app_workdir_path: &PathBuf) -> anyhow::Result<()> {
+pub async fn web_app_pack_recursive(
+    web_app_workdir_path: &PathBuf,
+    streaming: bool,
+) -> anyhow::Result<()> {
     let canonical_web_app_workdir_path = ffs::canonicalize(web_app_workdir_path).await?;
 
     let web_app_manifest_path = canonical_web_app_workdir_path.join(WebAppManifest::file_name());
@@ -506,6 +624,8 @@ pub async fn web_app_pack_recursive(web_app_workdir_path: &PathBuf) -> anyhow::R
         path: ffs::canonicalize(app_workdir_location).await?,
         output: None,
         recursive: true,
+        streaming,
+        sign: SignArgs::default(),
     }
     .run()
     .await?;
@@ -514,7 +634,7 @@ pub async fn web_app_pack_recursive(web_app_workdir_path: &PathBuf) -> anyhow::R
 }
 
 /// Pack all the app's DNAs if their location is bundled
-pub async fn app_pack_recursive(app_workdir_path: &PathBuf) -> anyhow::Result<()> {
+pub async fn app_pack_recursive(app_workdir_path: &PathBuf, streaming: bool) -> anyhow::Result<()> {
     let app_workdir_path = ffs::canonicalize(app_workdir_path).await?;
 
     let app_manifest_path = app_workdir_path.join(AppManifest::file_name());
@@ -529,6 +649,7 @@ pub async fn app_pack_recursive(app_workdir_path: &PathBuf) -> anyhow::Result<()
         HcDnaBundleSubcommand::Pack {
             path: dna_workdir_location,
             output: None,
+            streaming,
         }
         .run()
         .await?;
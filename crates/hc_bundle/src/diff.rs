@@ -0,0 +1,403 @@
+//! Compatibility diffs between two DNA bundles, run by `hc dna diff`.
+
+use anyhow::Context;
+#[cfg(feature = "dna-check")]
+use holochain::core::ribosome::dna_check::DnaCheckReport;
+use holochain_types::prelude::*;
+use mr_bundle::FileSystemBundler;
+#[cfg(feature = "dna-check")]
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Whether a new version of a DNA can replace an old one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DnaCompatibility {
+    /// The DNA hash and the coordinator zomes are unchanged.
+    NetworkCompatible,
+    /// The DNA hash is unchanged, so the new coordinator zomes can be
+    /// installed on existing cells with `UpdateCoordinators`.
+    CoordinatorUpdateOnly,
+    /// The DNA hash changed, so the new DNA will be on a different network.
+    NewNetwork,
+}
+
+impl std::fmt::Display for DnaCompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NetworkCompatible => "network-compatible",
+            Self::CoordinatorUpdateOnly => "coordinator update only",
+            Self::NewNetwork => "new network",
+        })
+    }
+}
+
+/// Which part of a DNA a [`DnaChange`] affects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DnaChangeScope {
+    /// Changes the DNA hash.
+    Network,
+    /// Changes the coordinator zomes.
+    Coordinator,
+    /// Changes neither, such as the name of the DNA.
+    None,
+}
+
+/// A single difference between two DNAs.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct DnaChange {
+    /// What the change affects.
+    pub scope: DnaChangeScope,
+    /// The zome that changed, if the change is specific to one zome.
+    pub zome: Option<ZomeName>,
+    /// A description of the change.
+    pub message: String,
+}
+
+/// The result of comparing two DNA bundles.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct DnaDiffReport {
+    /// The hash of the old DNA.
+    pub old_dna_hash: String,
+    /// The hash of the new DNA.
+    pub new_dna_hash: String,
+    /// Whether the new DNA can replace the old one.
+    pub compatibility: DnaCompatibility,
+    /// Every difference found.
+    pub changes: Vec<DnaChange>,
+    /// DNA hashes that should be added to the `lineage` of the new DNA's manifest.
+    pub suggested_lineage: Vec<String>,
+    /// Things that could not be compared.
+    pub notes: Vec<String>,
+}
+
+impl DnaDiffReport {
+    fn change(&mut self, scope: DnaChangeScope, zome: Option<&ZomeName>, message: String) {
+        self.changes.push(DnaChange {
+            scope,
+            zome: zome.cloned(),
+            message,
+        });
+    }
+}
+
+impl std::fmt::Display for DnaDiffReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Old hash: {}", self.old_dna_hash)?;
+        writeln!(f, "New hash: {}", self.new_dna_hash)?;
+        if self.changes.is_empty() {
+            writeln!(f, "No changes")?;
+        }
+        for change in &self.changes {
+            let scope = match change.scope {
+                DnaChangeScope::Network => "network",
+                DnaChangeScope::Coordinator => "coordinator",
+                DnaChangeScope::None => "other",
+            };
+            match &change.zome {
+                Some(zome) => writeln!(f, "{scope} [{zome}]: {}", change.message)?,
+                None => writeln!(f, "{scope}: {}", change.message)?,
+            }
+        }
+        for note in &self.notes {
+            writeln!(f, "note: {note}")?;
+        }
+        writeln!(f, "Result: {}", self.compatibility)?;
+        if !self.suggested_lineage.is_empty() {
+            #[cfg(feature = "unstable-migration")]
+            {
+                writeln!(
+                    f,
+                    "To mark the new DNA as a successor of the old one, add to its dna.yaml:"
+                )?;
+                writeln!(f, "lineage:")?;
+                for hash in &self.suggested_lineage {
+                    writeln!(f, "  - {hash}")?;
+                }
+            }
+            #[cfg(not(feature = "unstable-migration"))]
+            {
+                writeln!(
+                    f,
+                    "note: the new DNA could list the old one in the `lineage` of its dna.yaml, \
+                    which needs the `unstable-migration` feature:"
+                )?;
+                for hash in &self.suggested_lineage {
+                    writeln!(f, "  {hash}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare the DNA bundles at `old_path` and `new_path` and print a report.
+pub async fn diff_dna(old_path: &Path, new_path: &Path, json: bool) -> anyhow::Result<()> {
+    let old_dna = load_dna(old_path).await?;
+    let new_dna = load_dna(new_path).await?;
+    let mut report = diff_dna_files(&old_dna, &new_dna);
+    // A change of entry or link types also changes the wasm and so the DNA
+    // hash, so it doesn't affect the compatibility.
+    diff_types_at(&mut report, old_path, new_path).await?;
+
+    if json {
+        // Print without other text so it can be piped
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{report}");
+    }
+    Ok(())
+}
+
+async fn load_dna(path: &Path) -> anyhow::Result<DnaFile> {
+    let bundle = FileSystemBundler::load_from::<ValidatedDnaManifest>(path)
+        .await
+        .with_context(|| format!("Failed to read DNA bundle {}", path.display()))?;
+    let (dna_file, _) = DnaBundle::from(bundle)
+        .into_dna_file(DnaModifiersOpt::none())
+        .await?;
+    Ok(dna_file)
+}
+
+fn diff_dna_files(old: &DnaFile, new: &DnaFile) -> DnaDiffReport {
+    let mut report = DnaDiffReport {
+        old_dna_hash: old.dna_hash().to_string(),
+        new_dna_hash: new.dna_hash().to_string(),
+        compatibility: DnaCompatibility::NetworkCompatible,
+        changes: Vec::new(),
+        suggested_lineage: Vec::new(),
+        notes: Vec::new(),
+    };
+    let (old_def, new_def) = (old.dna_def(), new.dna_def());
+
+    if old_def.name != new_def.name {
+        report.change(
+            DnaChangeScope::None,
+            None,
+            format!("Name changed from '{}' to '{}'", old_def.name, new_def.name),
+        );
+    }
+    if old_def.modifiers.network_seed != new_def.modifiers.network_seed {
+        report.change(
+            DnaChangeScope::Network,
+            None,
+            "The network seed changed".to_string(),
+        );
+    }
+    if old_def.modifiers.properties != new_def.modifiers.properties {
+        report.change(
+            DnaChangeScope::Network,
+            None,
+            "The properties changed".to_string(),
+        );
+    }
+
+    diff_zomes(
+        &mut report,
+        DnaChangeScope::Network,
+        &zome_defs(
+            old_def
+                .integrity_zomes
+                .iter()
+                .map(|(n, d)| (n, d.as_any_zome_def())),
+        ),
+        &zome_defs(
+            new_def
+                .integrity_zomes
+                .iter()
+                .map(|(n, d)| (n, d.as_any_zome_def())),
+        ),
+    );
+    diff_zomes(
+        &mut report,
+        DnaChangeScope::Coordinator,
+        &zome_defs(
+            old_def
+                .coordinator_zomes
+                .iter()
+                .map(|(n, d)| (n, d.as_any_zome_def())),
+        ),
+        &zome_defs(
+            new_def
+                .coordinator_zomes
+                .iter()
+                .map(|(n, d)| (n, d.as_any_zome_def())),
+        ),
+    );
+
+    report.compatibility = if old.dna_hash() != new.dna_hash() {
+        DnaCompatibility::NewNetwork
+    } else if report
+        .changes
+        .iter()
+        .any(|c| c.scope == DnaChangeScope::Coordinator)
+    {
+        DnaCompatibility::CoordinatorUpdateOnly
+    } else {
+        DnaCompatibility::NetworkCompatible
+    };
+
+    if report.compatibility == DnaCompatibility::NewNetwork {
+        #[cfg(feature = "unstable-migration")]
+        let already_listed = new_def.lineage.contains(old.dna_hash());
+        #[cfg(not(feature = "unstable-migration"))]
+        let already_listed = false;
+        if !already_listed {
+            report.suggested_lineage.push(old.dna_hash().to_string());
+        }
+    }
+
+    report
+}
+
+/// The name, wasm hash and dependencies of each zome, in order.
+fn zome_defs<'a>(
+    zomes: impl Iterator<Item = (&'a ZomeName, &'a ZomeDef)>,
+) -> Vec<(ZomeName, Option<WasmHash>, Vec<ZomeName>)> {
+    zomes
+        .map(|(name, def)| match def {
+            ZomeDef::Wasm(WasmZomeDef {
+                wasm_hash,
+                dependencies,
+            }) => (name.clone(), Some(wasm_hash.clone()), dependencies.clone()),
+            _ => (name.clone(), None, Vec::new()),
+        })
+        .collect()
+}
+
+fn diff_zomes(
+    report: &mut DnaDiffReport,
+    scope: DnaChangeScope,
+    old: &[(ZomeName, Option<WasmHash>, Vec<ZomeName>)],
+    new: &[(ZomeName, Option<WasmHash>, Vec<ZomeName>)],
+) {
+    let kind = match scope {
+        DnaChangeScope::Network => "Integrity",
+        _ => "Coordinator",
+    };
+    for (name, _, _) in old {
+        if !new.iter().any(|(n, _, _)| n == name) {
+            report.change(scope, Some(name), format!("{kind} zome removed"));
+        }
+    }
+    for (index, (name, wasm_hash, dependencies)) in new.iter().enumerate() {
+        let Some(old_index) = old.iter().position(|(n, _, _)| n == name) else {
+            report.change(scope, Some(name), format!("{kind} zome added"));
+            continue;
+        };
+        let (_, old_wasm_hash, old_dependencies) = &old[old_index];
+        if old_wasm_hash != wasm_hash {
+            let hash = |h: &Option<WasmHash>| {
+                h.as_ref()
+                    .map(|h| h.to_string())
+                    .unwrap_or_else(|| "<inline>".to_string())
+            };
+            report.change(
+                scope,
+                Some(name),
+                format!(
+                    "Wasm changed from {} to {}",
+                    hash(old_wasm_hash),
+                    hash(wasm_hash)
+                ),
+            );
+        }
+        if old_dependencies != dependencies {
+            report.change(scope, Some(name), "Dependencies changed".to_string());
+        }
+        // The index of an integrity zome is part of every entry and link type it defines.
+        if scope == DnaChangeScope::Network && old_index != index {
+            report.change(
+                scope,
+                Some(name),
+                format!("Moved from position {old_index} to {index}"),
+            );
+        }
+    }
+}
+
+/// Compare the entry defs and link types of the integrity zomes of the DNA
+/// bundles at `old_path` and `new_path`.
+#[cfg(feature = "dna-check")]
+async fn diff_types_at(
+    report: &mut DnaDiffReport,
+    old_path: &Path,
+    new_path: &Path,
+) -> anyhow::Result<()> {
+    // The check compiles the zomes, which is needed for their entry and link types.
+    let old_check = crate::check::check_dna_at(old_path).await?;
+    let new_check = crate::check::check_dna_at(new_path).await?;
+    diff_types(report, &old_check, &new_check);
+    Ok(())
+}
+
+#[cfg(not(feature = "dna-check"))]
+async fn diff_types_at(
+    report: &mut DnaDiffReport,
+    _old_path: &Path,
+    _new_path: &Path,
+) -> anyhow::Result<()> {
+    report.notes.push(
+        "Entry defs and link types were not compared because this build doesn't have the `dna-check` feature"
+            .to_string(),
+    );
+    Ok(())
+}
+
+/// Compare the entry defs and link types of the integrity zomes.
+#[cfg(feature = "dna-check")]
+fn diff_types(report: &mut DnaDiffReport, old: &DnaCheckReport, new: &DnaCheckReport) {
+    for (check, which) in [(old, "old"), (new, "new")] {
+        if check.has_errors() {
+            report.notes.push(format!(
+                "Entry defs and link types were not compared because the {which} DNA has problems, run `hc dna check` on it for details"
+            ));
+            return;
+        }
+    }
+
+    for zome in new.zomes.iter().filter(|z| z.integrity) {
+        let Some(old_zome) = old
+            .zomes
+            .iter()
+            .find(|z| z.integrity && z.name == zome.name)
+        else {
+            continue;
+        };
+        let old_defs: BTreeSet<_> = old_zome.entry_defs.iter().collect();
+        let new_defs: BTreeSet<_> = zome.entry_defs.iter().collect();
+        for removed in old_defs.difference(&new_defs) {
+            report.change(
+                DnaChangeScope::Network,
+                Some(&zome.name),
+                format!("Entry def '{removed}' removed"),
+            );
+        }
+        for added in new_defs.difference(&old_defs) {
+            report.change(
+                DnaChangeScope::Network,
+                Some(&zome.name),
+                format!("Entry def '{added}' added"),
+            );
+        }
+        if old_defs == new_defs && old_zome.entry_defs != zome.entry_defs {
+            report.change(
+                DnaChangeScope::Network,
+                Some(&zome.name),
+                "Entry defs reordered".to_string(),
+            );
+        }
+        if old_zome.num_link_types != zome.num_link_types {
+            report.change(
+                DnaChangeScope::Network,
+                Some(&zome.name),
+                format!(
+                    "Number of link types changed from {} to {}",
+                    old_zome.num_link_types.unwrap_or_default(),
+                    zome.num_link_types.unwrap_or_default()
+                ),
+            );
+        }
+    }
+}
@@ -2,13 +2,18 @@
 
 //! This crate provides a set of utilities for working with Holochain bundles.
 
+#[cfg(feature = "dna-check")]
+mod check;
 mod cli;
+mod diff;
 mod error;
 mod init;
 mod packing;
+mod sign;
 
 pub use cli::{
     app_pack_recursive, bundled_dnas_workdir_locations, get_app_name, get_dna_name,
     get_web_app_name, web_app_pack_recursive, HcAppBundle, HcDnaBundle, HcWebAppBundle,
 };
 pub use packing::{expand_bundle, expand_unknown_bundle, pack};
+pub use sign::{sign_bundle_with_keystore, SignArgs};
@@ -7,13 +7,12 @@ use std::path::Path;
 use std::path::PathBuf;
 
 /// Expand an existing bundle into a working directory, returning the directory path used.
-pub async fn expand_bundle<M: Manifest>(
+pub async fn expand_bundle<M: Manifest + Send + 'static>(
     bundle_path: &Path,
     target_dir: Option<PathBuf>,
     force: bool,
 ) -> HcBundleResult<PathBuf> {
     let bundle_path = ffs::canonicalize(bundle_path).await?;
-    let bundle = FileSystemBundler::load_from::<M>(&bundle_path).await?;
 
     let target_dir = if let Some(d) = target_dir {
         d
@@ -21,7 +20,7 @@ pub async fn expand_bundle<M: Manifest>(
         bundle_path_to_dir(&bundle_path, M::bundle_extension())?
     };
 
-    FileSystemBundler::expand_to(&bundle, &target_dir, force).await?;
+    FileSystemBundler::unpack_to::<M>(&bundle_path, &target_dir, force).await?;
 
     Ok(target_dir)
 }
@@ -66,10 +65,14 @@ fn bundle_path_to_dir(path: &Path, extension: &'static str) -> HcBundleResult<Pa
 
 /// Pack a directory containing a YAML manifest (DNA, hApp, Web hApp) into a bundle, returning
 /// the path to which the bundle file was written.
+///
+/// The bundle is written in the streaming format if `streaming` is true, see
+/// [`FileSystemBundler::bundle_streaming_to`].
 pub async fn pack<M: Manifest>(
     dir_path: &Path,
     target_path: Option<PathBuf>,
     name: String,
+    streaming: bool,
 ) -> HcBundleResult<PathBuf> {
     let dir_path = ffs::canonicalize(dir_path).await?;
     let manifest_path = dir_path.join(M::file_name());
@@ -85,7 +88,11 @@ pub async fn pack<M: Manifest>(
         None => dir_to_bundle_path(&dir_path, name, M::bundle_extension())?,
     };
 
-    FileSystemBundler::bundle_to::<M>(&manifest_path, &target_path).await?;
+    if streaming {
+        FileSystemBundler::bundle_streaming_to::<M>(&manifest_path, &target_path).await?;
+    } else {
+        FileSystemBundler::bundle_to::<M>(&manifest_path, &target_path).await?;
+    }
 
     Ok(target_path)
 }
@@ -139,7 +146,7 @@ integrity:
         // in the parent directory
         std::fs::write(tmpdir.path().join("zome-3.wasm"), [7, 8, 9]).unwrap();
 
-        let bundle_path = pack::<ValidatedDnaManifest>(&dir, None, "test_dna".to_string())
+        let bundle_path = pack::<ValidatedDnaManifest>(&dir, None, "test_dna".to_string(), false)
             .await
             .unwrap();
         let bundle = FileSystemBundler::load_from::<ValidatedDnaManifest>(&bundle_path)
@@ -179,6 +186,7 @@ integrity:
             &dir,
             Some(dir.parent().unwrap().to_path_buf()),
             "test_dna".to_string(),
+            false,
         )
         .await
         .unwrap();
@@ -199,12 +207,29 @@ integrity:
         assert_eq!(dir.read_dir().unwrap().collect::<Vec<_>>().len(), 4);
 
         // Ensure that we get the same bundle after the round trip
-        let bundle_path = pack::<ValidatedDnaManifest>(&dir, None, "test_dna".to_string())
+        let bundle_path = pack::<ValidatedDnaManifest>(&dir, None, "test_dna".to_string(), false)
             .await
             .unwrap();
         let bundle2 = FileSystemBundler::load_from::<ValidatedDnaManifest>(bundle_path)
             .await
             .unwrap();
         assert_eq!(bundle, bundle2);
+
+        // Ensure that the streaming format holds the same bundle
+        let bundle_path = pack::<ValidatedDnaManifest>(
+            &dir,
+            Some(tmpdir.path().join("streaming.dna")),
+            "test_dna".to_string(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert!(std::fs::read(&bundle_path)
+            .unwrap()
+            .starts_with(&mr_bundle::stream::MAGIC));
+        let bundle3 = FileSystemBundler::load_from::<ValidatedDnaManifest>(bundle_path)
+            .await
+            .unwrap();
+        assert_eq!(bundle, bundle3);
     }
 }
@@ -0,0 +1,86 @@
+//! Signing hApp and web hApp bundles as their publisher.
+
+use anyhow::Context;
+use clap::Args;
+use holochain_keystore::lair_keystore::spawn_lair_keystore;
+use holochain_keystore::{AgentPubKeyExt, MetaLairClient};
+use holochain_types::prelude::{with_publisher_signature, AgentPubKey, AgentPubKeyB64};
+use holochain_util::ffs;
+use mr_bundle::{Bundle, Manifest};
+use std::path::Path;
+use url2::Url2;
+
+/// Options for signing a bundle as its publisher.
+#[derive(Debug, Clone, Default, Args)]
+pub struct SignArgs {
+    /// Sign the bundle as its publisher, with this agent key.
+    ///
+    /// The key must be in the lair keystore at `--lair-url`. Conductors can be
+    /// configured to only install bundles signed by trusted publishers.
+    #[arg(long, value_name = "AGENT_KEY", requires = "lair_url")]
+    pub sign: Option<AgentPubKeyB64>,
+
+    /// The connection URL of the lair keystore that holds the signing key.
+    #[arg(long, value_parser = try_parse_url2, requires = "sign")]
+    pub lair_url: Option<Url2>,
+
+    /// Read the lair passphrase from stdin instead of prompting for it.
+    #[arg(long, requires = "sign")]
+    pub piped: bool,
+}
+
+impl SignArgs {
+    /// Sign the bundle at `bundle_path` in place, if a signing key was given.
+    ///
+    /// The signature covers the manifest and the hash of every resource, so
+    /// for a web hApp it covers both the UI and the hApp bundle. The signed
+    /// bundle is written in the same format as the original.
+    pub async fn sign_bundle<M: Manifest>(&self, bundle_path: &Path) -> anyhow::Result<()> {
+        let (Some(publisher), Some(lair_url)) = (&self.sign, &self.lair_url) else {
+            return Ok(());
+        };
+        let publisher = AgentPubKey::from(publisher.clone());
+
+        holochain_util::pw::pw_set_piped(self.piped);
+        if !self.piped {
+            eprintln!("Enter the lair passphrase to sign the bundle: ");
+        }
+        let passphrase = holochain_util::pw::pw_get().context("Failed to get passphrase")?;
+        let keystore = spawn_lair_keystore(lair_url.clone(), passphrase)
+            .await
+            .context("Failed to connect to lair")?;
+
+        sign_bundle_with_keystore::<M>(bundle_path, publisher, &keystore).await
+    }
+}
+
+/// Sign the bundle at `bundle_path` in place as `publisher`, whose key is in
+/// `keystore`.
+///
+/// See [`SignArgs::sign_bundle`].
+pub async fn sign_bundle_with_keystore<M: Manifest>(
+    bundle_path: &Path,
+    publisher: AgentPubKey,
+    keystore: &MetaLairClient,
+) -> anyhow::Result<()> {
+    let bytes = ffs::read(bundle_path).await?;
+    let bundle = Bundle::<M>::unpack(bytes.as_slice())?;
+    let (_, payload) = bundle.signing_payload()?;
+    let signature = publisher
+        .sign_raw(keystore, payload.into())
+        .await
+        .with_context(|| format!("Failed to sign with agent key {publisher}"))?;
+    let bundle = with_publisher_signature(bundle, publisher, signature)?;
+    let signed = if bytes.starts_with(&mr_bundle::stream::MAGIC) {
+        bundle.pack_streaming()?
+    } else {
+        bundle.pack()?
+    };
+    ffs::write(bundle_path, &signed[..]).await?;
+
+    Ok(())
+}
+
+fn try_parse_url2(arg: &str) -> url2::Url2Result<Url2> {
+    Url2::try_parse(arg)
+}
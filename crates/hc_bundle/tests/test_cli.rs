@@ -405,6 +405,272 @@ async fn test_hash_dna_function() {
     }
 }
 
+#[cfg(feature = "dna-check")]
+#[tokio::test]
+async fn test_check_dna_reports_invalid_wasm() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let dna_path = tmp_dir.path().join("dna1.dna");
+    {
+        let mut cmd = Command::new(assert_cmd::cargo_bin!("hc-dna"));
+        let cmd = cmd.args(["pack", "tests/fixtures/my-app/dnas/dna1", "-o"]);
+        cmd.arg(&dna_path).assert().success();
+    }
+    {
+        let mut cmd = Command::new(assert_cmd::cargo_bin!("hc-dna"));
+        let cmd = cmd.args(["check", "--json"]).arg(&dna_path);
+        let output = cmd.assert().failure().get_output().stdout.clone();
+        let report: Value = serde_json::from_slice(&output).unwrap();
+        let findings = report["findings"].as_array().unwrap();
+        assert_eq!(findings.len(), 2);
+        for finding in findings {
+            assert_eq!(finding["severity"], "error");
+            assert!(finding["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("The wasm can't be compiled"));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_diff_dna_compatibility() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let zomes_dir = std::fs::canonicalize("tests/fixtures/my-app/dnas/dna1/zomes").unwrap();
+    let zomes_dir = zomes_dir.display();
+
+    let pack = |name: &str, network_seed: &str, coordinator: bool| {
+        let work_dir = tmp_dir.path().join(name);
+        std::fs::create_dir_all(&work_dir).unwrap();
+        let mut manifest = format!(
+            r#"---
+manifest_version: "0"
+name: a dna
+integrity:
+  network_seed: {network_seed}
+  properties: ~
+  zomes:
+    - name: zome1
+      path: {zomes_dir}/zome11.wasm
+"#
+        );
+        if coordinator {
+            manifest.push_str(&format!(
+                r#"coordinator:
+  zomes:
+    - name: zome2
+      path: {zomes_dir}/zome12.wasm
+"#
+            ));
+        }
+        std::fs::write(work_dir.join("dna.yaml"), manifest).unwrap();
+
+        let dna_path = tmp_dir.path().join(format!("{name}.dna"));
+        let mut cmd = Command::new(assert_cmd::cargo_bin!("hc-dna"));
+        let cmd = cmd.arg("pack").arg(&work_dir).arg("-o").arg(&dna_path);
+        cmd.assert().success();
+        dna_path
+    };
+    let diff = |old: &Path, new: &Path| -> Value {
+        let mut cmd = Command::new(assert_cmd::cargo_bin!("hc-dna"));
+        let cmd = cmd.args(["diff", "--json"]).arg(old).arg(new);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        serde_json::from_slice(&output).unwrap()
+    };
+
+    let seed = "00000000-0000-0000-0000-000000000000";
+    let old = pack("old", seed, false);
+    let same = pack("same", seed, false);
+    let coordinator = pack("coordinator", seed, true);
+    let new_seed = pack("new-seed", "11111111-1111-1111-1111-111111111111", false);
+
+    let report = diff(&old, &same);
+    assert_eq!(report["compatibility"], "network-compatible");
+    assert_eq!(report["changes"].as_array().unwrap().len(), 0);
+
+    let report = diff(&old, &coordinator);
+    assert_eq!(report["compatibility"], "coordinator-update-only");
+    assert_eq!(report["changes"][0]["scope"], "coordinator");
+    assert_eq!(report["changes"][0]["zome"], "zome2");
+    assert_eq!(report["suggested_lineage"].as_array().unwrap().len(), 0);
+
+    let report = diff(&old, &new_seed);
+    assert_eq!(report["compatibility"], "new-network");
+    assert_eq!(report["changes"][0]["scope"], "network");
+    assert_eq!(report["suggested_lineage"][0], report["old_dna_hash"]);
+
+    // The suggestion is for the top-level `lineage` of the manifest, which
+    // only exists with the `unstable-migration` feature.
+    let mut cmd = Command::new(assert_cmd::cargo_bin!("hc-dna"));
+    let cmd = cmd.arg("diff").arg(&old).arg(&new_seed);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+    let old_hash = report["old_dna_hash"].as_str().unwrap();
+    if cfg!(feature = "unstable-migration") {
+        assert!(output.contains(&format!("\nlineage:\n  - {old_hash}\n")));
+    } else {
+        assert!(!output.contains("lineage:"));
+        assert!(output.contains("unstable-migration"));
+    }
+}
+
+#[cfg(feature = "dna-check")]
+#[tokio::test]
+async fn test_diff_dna_entry_and_link_types() {
+    use holochain_wasm_test_utils::{TestWasm, TestWasmPair};
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    // Both DNAs have an integrity zome of the same name, built from test wasms
+    // with different entry and link types.
+    let pack = |name: &str, test_wasm: TestWasm| {
+        let work_dir = tmp_dir.path().join(name);
+        std::fs::create_dir_all(&work_dir).unwrap();
+        let wasm = TestWasmPair::<DnaWasm>::from(test_wasm).integrity;
+        std::fs::write(work_dir.join("integrity.wasm"), wasm.code()).unwrap();
+        let manifest = r#"---
+manifest_version: "0"
+name: a dna
+integrity:
+  network_seed: 00000000-0000-0000-0000-000000000000
+  properties: ~
+  zomes:
+    - name: integrity
+      path: integrity.wasm
+"#;
+        std::fs::write(work_dir.join("dna.yaml"), manifest).unwrap();
+
+        let dna_path = tmp_dir.path().join(format!("{name}.dna"));
+        let mut cmd = Command::new(assert_cmd::cargo_bin!("hc-dna"));
+        let cmd = cmd.arg("pack").arg(&work_dir).arg("-o").arg(&dna_path);
+        cmd.assert().success();
+        dna_path
+    };
+
+    let old = pack("entry-defs", TestWasm::EntryDefs);
+    let new = pack("link", TestWasm::Link);
+
+    let mut cmd = Command::new(assert_cmd::cargo_bin!("hc-dna"));
+    let cmd = cmd.args(["diff", "--json"]).arg(&old).arg(&new);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let report: Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(report["compatibility"], "new-network");
+    assert_eq!(report["notes"].as_array().unwrap().len(), 0);
+    let messages = report["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|c| c["zome"] == "integrity")
+        .map(|c| c["message"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    for expected in [
+        "Entry def 'post' removed",
+        "Entry def 'comment' removed",
+        "Entry def 'test' added",
+        "Number of link types changed from 0 to 5",
+    ] {
+        assert!(
+            messages.iter().any(|m| m == expected),
+            "Missing '{expected}' in {messages:?}"
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn signed_web_app_installs_unless_its_ui_is_tampered_with() {
+    use holochain::conductor::config::AppPublishersConfig;
+    use holochain::conductor::error::ConductorError;
+    use holochain::sweettest::*;
+    use holochain_keystore::AgentPubKeyExt;
+    use holochain_wasm_test_utils::TestWasm;
+
+    let keystore = holochain_keystore::test_keystore();
+    let publisher = AgentPubKey::new_random(&keystore).await.unwrap();
+
+    let mut config = SweetConductorConfig::standard();
+    config.app_publishers = Some(AppPublishersConfig {
+        trusted: vec![publisher.clone().into()],
+        allow_unsigned: false,
+    });
+    let conductor = SweetConductor::create_with_defaults(
+        config,
+        Some(keystore.clone()),
+        Some(SweetLocalRendezvous::new().await),
+    )
+    .await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let app_bundle = app_bundle_from_dnas(&[("role".to_string(), dna)], false, None).await;
+    let manifest = WebAppManifest::current("web app".to_string());
+    let web_app_bundle = |ui: &'static [u8]| {
+        mr_bundle::Bundle::new(
+            manifest.clone(),
+            vec![
+                (manifest.web_ui_location(), ui.to_vec().into()),
+                (
+                    manifest.happ_bundle_location(),
+                    app_bundle.pack().unwrap().into(),
+                ),
+            ],
+        )
+        .unwrap()
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let signed_path = dir.path().join("signed.webhapp");
+    ffs::write(&signed_path, &web_app_bundle(b"ui").pack().unwrap()[..])
+        .await
+        .unwrap();
+    hc_bundle::sign_bundle_with_keystore::<WebAppManifest>(
+        &signed_path,
+        publisher.clone(),
+        &keystore,
+    )
+    .await
+    .unwrap();
+
+    // The signature is kept, but the UI it was made over is swapped for another
+    let signed = mr_bundle::Bundle::<WebAppManifest>::unpack(
+        ffs::read(&signed_path).await.unwrap().as_slice(),
+    )
+    .unwrap();
+    let tampered_path = dir.path().join("tampered.webhapp");
+    ffs::write(
+        &tampered_path,
+        &web_app_bundle(b"tampered ui")
+            .with_signature(signed.signature().cloned())
+            .pack()
+            .unwrap()[..],
+    )
+    .await
+    .unwrap();
+
+    let payload = |path: &Path, installed_app_id: &str| InstallAppPayload {
+        agent_key: None,
+        source: AppBundleSource::Path(path.to_path_buf()),
+        installed_app_id: Some(installed_app_id.into()),
+        network_seed: None,
+        roles_settings: Default::default(),
+        ignore_genesis_failure: false,
+        restore_from_dht: false,
+        wasm_metering: None,
+    };
+
+    matches::assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload(&tampered_path, "tampered"))
+            .await
+            .unwrap_err(),
+        ConductorError::UntrustedAppBundle(_)
+    );
+
+    conductor
+        .clone()
+        .install_app_bundle(payload(&signed_path, "signed"))
+        .await
+        .unwrap();
+}
+
 #[test]
 fn test_all_dna_manifests_match_schema() {
     let schema = get_schema::<DnaManifest>();
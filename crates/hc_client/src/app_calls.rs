@@ -0,0 +1,294 @@
+//! App API helpers for the `hc-client` CLI.
+//!
+//! These utilities make [`holochain_conductor_api::AppRequest`] calls for an
+//! installed app against a running conductor. An app interface is found, or
+//! attached, through the conductor's admin interface.
+
+use crate::calls::{app_info_to_base64_json, parse_clone_cell_id, parse_dna_hash, ListAgents};
+use crate::zome_call::{generate_signing_credentials, get_app_client, signing_credentials, Auth};
+use anyhow::{anyhow, Context};
+use clap::{Args, Parser, Subcommand};
+use holochain_client::{AdminWebsocket, AppWebsocket, ClientAgentSigner, ZomeCallTarget};
+use holochain_conductor_api::{AppInfo, CellInfo};
+use holochain_types::app::{CreateCloneCellPayload, DisableCloneCellPayload, MemproofMap};
+use holochain_types::prelude::{
+    CellId, CloneCellId, CloneId, DnaModifiersOpt, ExternIO, FunctionName, NetworkSeed, RoleName,
+    SerializedBytes, UnsafeBytes, YamlProperties, ZomeName,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[doc(hidden)]
+#[derive(Debug, Parser)]
+pub struct AppCall {
+    /// Port of the running conductor admin interface.
+    #[arg(short, long)]
+    pub port: u16,
+
+    /// Whether to pipe the passphrase for `call-zome` from stdin.
+    ///
+    /// By default, the passphrase is read interactively from the user.
+    #[arg(long)]
+    pub piped: bool,
+
+    /// The installed app id to make the request for.
+    pub app_id: String,
+
+    /// The app request you want to make.
+    #[command(subcommand)]
+    pub call: AppRequestCli,
+}
+
+/// Calls to the app API that can be made from the CLI.
+#[derive(Debug, Subcommand, Clone)]
+pub enum AppRequestCli {
+    /// Calls [`AppWebsocket::app_info`].
+    AppInfo,
+    /// Calls [`AppWebsocket::call_zome`].
+    CallZome(CallZome),
+    /// Calls [`AppWebsocket::create_clone_cell`].
+    CreateCloneCell(CreateCloneCell),
+    /// Calls [`AppWebsocket::disable_clone_cell`].
+    DisableCloneCell(CloneCell),
+    /// Calls [`AppWebsocket::enable_clone_cell`].
+    EnableCloneCell(CloneCell),
+    /// Calls [`AppWebsocket::enable_app`].
+    EnableApp,
+    /// Calls [`AppWebsocket::provide_memproofs`].
+    ProvideMemproofs(ProvideMemproofs),
+    /// Calls [`AppWebsocket::list_wasm_host_functions`].
+    ListWasmHostFunctions,
+    /// Calls [`AppWebsocket::dump_network_stats`].
+    DumpNetworkStats,
+    /// Calls [`AppWebsocket::agent_info`].
+    ListAgents(ListAgents),
+}
+
+/// Calls [`AppWebsocket::call_zome`]
+/// and prints the response as JSON.
+///
+/// Zome calls are signed with the credentials created by `zome-call-auth`.
+#[derive(Debug, Args, Clone)]
+pub struct CallZome {
+    /// The cell to call, as a DNA hash, role name or clone id.
+    pub target: String,
+
+    /// The zome to call.
+    pub zome_name: String,
+
+    /// The zome function to call.
+    pub function: String,
+
+    /// The zome call payload as JSON.
+    #[arg(default_value = "null")]
+    pub payload: String,
+}
+
+/// Calls [`AppWebsocket::create_clone_cell`]
+/// and prints the new clone cell.
+#[derive(Debug, Args, Clone)]
+pub struct CreateCloneCell {
+    /// The role name of the DNA to clone.
+    pub role_name: RoleName,
+
+    /// The network seed of the clone, which makes its DNA hash distinct.
+    #[arg(long)]
+    pub network_seed: NetworkSeed,
+
+    /// An optional name for the clone.
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+/// Identifies a clone cell of the app.
+#[derive(Debug, Args, Clone)]
+pub struct CloneCell {
+    /// The clone ID or DNA hash of the clone cell.
+    #[arg(value_parser = parse_clone_cell_id)]
+    pub clone_cell_id: CloneCellId,
+}
+
+/// Calls [`AppWebsocket::provide_memproofs`]
+/// and provides deferred membrane proofs for the app.
+#[derive(Debug, Args, Clone)]
+pub struct ProvideMemproofs {
+    /// A JSON object from role name to membrane proof.
+    ///
+    /// Each membrane proof is encoded from its JSON value to msgpack.
+    pub memproofs: String,
+}
+
+#[doc(hidden)]
+pub async fn app_call(req: AppCall) -> anyhow::Result<()> {
+    let AppCall {
+        port,
+        piped,
+        app_id,
+        call,
+    } = req;
+
+    let admin_client = AdminWebsocket::connect(format!("localhost:{port}"), None).await?;
+    let signer = ClientAgentSigner::new();
+    let mut client = get_app_client(&admin_client, app_id, signer.clone()).await?;
+
+    if let AppRequestCli::CallZome(_) = call {
+        holochain_util::pw::pw_set_piped(piped);
+        if !piped {
+            crate::msg!("Enter passphrase to authorize zome calls: ");
+        }
+        let passphrase = holochain_util::pw::pw_get().context("Failed to get passphrase")?;
+        let (auth, key) = generate_signing_credentials(passphrase).await?;
+        add_app_credentials(&signer, client.cached_app_info(), &auth, &key);
+    }
+
+    app_call_inner(&mut client, call).await
+}
+
+pub(crate) async fn app_call_inner(
+    client: &mut AppWebsocket,
+    call: AppRequestCli,
+) -> anyhow::Result<()> {
+    match call {
+        AppRequestCli::AppInfo => {
+            let app_info = client
+                .app_info()
+                .await?
+                .map(app_info_to_base64_json)
+                .transpose()?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&app_info)?);
+        }
+        AppRequestCli::CallZome(args) => {
+            let target = zome_call_target(client.cached_app_info(), args.target)?;
+            let payload = serde_json::from_str::<serde_json::Value>(&args.payload)
+                .map_err(|e| anyhow!("Invalid zome call payload: {e}"))?;
+            let response = client
+                .call_zome(
+                    target,
+                    ZomeName::from(args.zome_name),
+                    FunctionName(args.function),
+                    ExternIO::encode(payload)?,
+                )
+                .await?;
+            // Print without other text so it can be piped
+            println!(
+                "{}",
+                serde_json::to_string(&response.decode::<hc_serde_json::Value>()?)?
+            );
+        }
+        AppRequestCli::CreateCloneCell(args) => {
+            let clone_cell = client
+                .create_clone_cell(CreateCloneCellPayload {
+                    role_name: args.role_name,
+                    modifiers: DnaModifiersOpt::<YamlProperties>::none()
+                        .with_network_seed(args.network_seed),
+                    membrane_proof: None,
+                    name: args.name,
+                })
+                .await?;
+            client.refresh_app_info().await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&clone_cell)?);
+        }
+        AppRequestCli::DisableCloneCell(args) => {
+            client
+                .disable_clone_cell(DisableCloneCellPayload {
+                    clone_cell_id: args.clone_cell_id,
+                })
+                .await?;
+            crate::msg!("Disabled clone cell");
+        }
+        AppRequestCli::EnableCloneCell(args) => {
+            let clone_cell = client
+                .enable_clone_cell(DisableCloneCellPayload {
+                    clone_cell_id: args.clone_cell_id,
+                })
+                .await?;
+            client.refresh_app_info().await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&clone_cell)?);
+        }
+        AppRequestCli::EnableApp => {
+            client.enable_app().await?;
+            crate::msg!("Enabled app");
+        }
+        AppRequestCli::ProvideMemproofs(args) => {
+            let memproofs =
+                serde_json::from_str::<HashMap<RoleName, serde_json::Value>>(&args.memproofs)
+                    .map_err(|e| anyhow!("Invalid membrane proofs: {e}"))?;
+            let memproofs = memproofs
+                .into_iter()
+                .map(|(role_name, memproof)| {
+                    let bytes = ExternIO::encode(memproof)?.0;
+                    Ok((
+                        role_name,
+                        Arc::new(SerializedBytes::from(UnsafeBytes::from(bytes))),
+                    ))
+                })
+                .collect::<anyhow::Result<MemproofMap>>()?;
+            client.provide_memproofs(memproofs).await?;
+            crate::msg!("Provided membrane proofs");
+        }
+        AppRequestCli::ListWasmHostFunctions => {
+            let functions = client.list_wasm_host_functions().await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&functions)?);
+        }
+        AppRequestCli::DumpNetworkStats => {
+            let stats = client.dump_network_stats().await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&stats)?);
+        }
+        AppRequestCli::ListAgents(args) => {
+            let agent_infos = client
+                .agent_info(args.dna)
+                .await?
+                .iter()
+                .map(|info| serde_json::from_str(info))
+                .collect::<Result<Vec<serde_json::Value>, _>>()?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&agent_infos)?);
+        }
+    }
+    Ok(())
+}
+
+/// Add signing credentials for every cell of an app to `signer`.
+pub(crate) fn add_app_credentials(
+    signer: &ClientAgentSigner,
+    app_info: &AppInfo,
+    auth: &Auth,
+    key: &ed25519_dalek::SigningKey,
+) {
+    for (cell_id, credentials) in signing_credentials(auth, key, app_cell_ids(app_info)) {
+        signer.add_credentials(cell_id, credentials);
+    }
+}
+
+/// The IDs of the provisioned and cloned cells of an app.
+pub(crate) fn app_cell_ids(app_info: &AppInfo) -> Vec<CellId> {
+    app_info
+        .cell_info
+        .values()
+        .flatten()
+        .filter_map(|info| match info {
+            CellInfo::Provisioned(cell) => Some(cell.cell_id.clone()),
+            CellInfo::Cloned(cell) => Some(cell.cell_id.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn zome_call_target(app_info: &AppInfo, target: String) -> anyhow::Result<ZomeCallTarget> {
+    if let Ok(dna_hash) = parse_dna_hash(&target) {
+        return app_cell_ids(app_info)
+            .into_iter()
+            .find(|cell_id| cell_id.dna_hash() == &dna_hash)
+            .map(ZomeCallTarget::CellId)
+            .ok_or_else(|| anyhow!("No cell found for DNA hash {target}"));
+    }
+    Ok(match CloneId::try_from(target.clone()) {
+        Ok(clone_id) => ZomeCallTarget::CloneId(clone_id),
+        Err(_) => ZomeCallTarget::RoleName(target),
+    })
+}
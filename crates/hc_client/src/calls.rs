@@ -9,18 +9,25 @@ use chrono::{DateTime, Utc};
 use clap::{Args, Parser, Subcommand};
 use holo_hash::{ActionHash, AgentPubKeyB64, DhtOpHash, DnaHashB64};
 use holochain_client::AdminWebsocket;
+use holochain_conductor_api::conductor::ZomeCallLimits;
 use holochain_conductor_api::InterfaceDriver;
 use holochain_conductor_api::PeerMetaInfo;
 use holochain_conductor_api::{AdminInterfaceConfig, AppInfo};
+use holochain_conductor_api::{AppAuthenticationToken, IssueAppAuthenticationTokenPayload};
 use holochain_conductor_api::{AppStatusFilter, DhtOpsCursor, SourceChainCursor};
 use holochain_types::app::AppManifest;
 use holochain_types::app::RoleSettingsMap;
 use holochain_types::app::RoleSettingsMapYaml;
+use holochain_types::app::{CoordinatorSource, DeleteCloneCellPayload, UpdateCoordinatorsPayload};
+use holochain_types::network::BandwidthLimits;
 use holochain_types::prelude::NetworkSeed;
 use holochain_types::prelude::{AgentPubKey, AppBundleSource};
+use holochain_types::prelude::{CapAccess, CapSecret, GrantedFunctions, CAP_SECRET_BYTES};
 use holochain_types::prelude::{CellId, InstallAppPayload};
+use holochain_types::prelude::{CloneCellId, CloneId, FunctionName, ZomeName};
 use holochain_types::prelude::{Deserialize, Serialize};
 use holochain_types::prelude::{DnaHash, InstalledAppId};
+use holochain_types::prelude::{GrantZomeCallCapabilityPayload, ZomeCallCapGrant};
 use holochain_types::websocket::AllowedOrigins;
 use kitsune2_api::AgentInfoSigned;
 use kitsune2_api::Url;
@@ -82,6 +89,16 @@ pub enum AdminRequestCli {
     DumpNetworkMetrics(DumpNetworkMetrics),
     /// Calls [`AdminWebsocket::dump_network_stats`].
     DumpNetworkStats,
+    /// Calls [`AdminWebsocket::dump_peer_latency`].
+    DumpPeerLatency,
+    /// Calls [`AdminWebsocket::list_signal_outbox`].
+    ListSignalOutbox(ListSignalOutbox),
+    /// Calls [`AdminWebsocket::set_bandwidth_limits`].
+    SetBandwidthLimits(SetBandwidthLimits),
+    /// Calls [`AdminWebsocket::list_bandwidth_limits`].
+    ListBandwidthLimits,
+    /// Calls [`AdminWebsocket::trace_op`].
+    TraceOp(TraceOp),
     /// Calls [`AdminWebsocket::list_capability_grants`].
     ListCapabilityGrants(ListCapGrants),
     /// Calls [`AdminWebsocket::revoke_zome_call_capability`].
@@ -92,6 +109,20 @@ pub enum AdminRequestCli {
     ListAgents(ListAgents),
     /// Calls [`AdminWebsocket::peer_meta_info`].
     PeerMetaInfo(PeerMetaInfoArgs),
+    /// Calls [`AdminWebsocket::update_coordinators`].
+    UpdateCoordinators(UpdateCoordinators),
+    /// Calls [`AdminWebsocket::grant_zome_call_capability`].
+    GrantZomeCallCapability(GrantZomeCallCapability),
+    /// Calls [`AdminWebsocket::delete_clone_cell`].
+    DeleteCloneCell(DeleteCloneCell),
+    /// Calls [`AdminWebsocket::storage_info`].
+    StorageInfo,
+    /// Calls [`AdminWebsocket::issue_app_auth_token`].
+    IssueAppAuthToken(IssueAppAuthToken),
+    /// Calls [`AdminWebsocket::revoke_app_authentication_token`].
+    RevokeAppAuthToken(RevokeAppAuthToken),
+    /// Calls [`AdminWebsocket::get_dna_definition`].
+    GetDnaDefinition(GetDnaDefinition),
 }
 
 /// Calls [`AdminWebsocket::add_admin_interfaces`]
@@ -145,6 +176,18 @@ pub struct AddAppWs {
     /// will be allowed to connect to this interface.
     #[arg(long)]
     pub installed_app_id: Option<InstalledAppId>,
+
+    /// Optional maximum number of zome calls in progress at once over this interface.
+    ///
+    /// If not provided, the limit from the conductor config applies.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_concurrent_calls: Option<u32>,
+
+    /// Optional maximum number of zome calls started per second over this interface.
+    ///
+    /// If not provided, the limit from the conductor config applies.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_calls_per_second: Option<u32>,
 }
 
 /// Calls [`AdminWebsocket::install_app`]
@@ -268,6 +311,62 @@ pub struct DumpNetworkMetrics {
     pub include_dht_summary: bool,
 }
 
+/// Arguments for listing the signal outbox.
+#[derive(Debug, Args, Clone)]
+pub struct ListSignalOutbox {
+    /// Only list the signals sent on this DNA.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: Option<DnaHash>,
+}
+
+/// Calls [`AdminWebsocket::set_bandwidth_limits`] and limits the background
+/// network traffic of an app.
+///
+/// Passing neither limit removes the limits of the app.
+#[derive(Debug, Args, Clone)]
+pub struct SetBandwidthLimits {
+    /// The InstalledAppId to limit.
+    pub app_id: String,
+
+    /// The number of bytes of gossip and fetch traffic allowed per day.
+    #[arg(long)]
+    pub daily_bytes: Option<u64>,
+
+    /// The number of bytes of gossip and fetch traffic allowed per second, on average
+    /// over a minute.
+    #[arg(long)]
+    pub bytes_per_second: Option<u64>,
+}
+
+/// Calls [`AdminWebsocket::trace_op`] and prints where a DHT op is
+/// on this conductor and why.
+#[derive(Debug, Args, Clone)]
+pub struct TraceOp {
+    /// The DNA hash of the network the op belongs to.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The hash of the DHT op to trace.
+    #[arg(value_parser = parse_dht_op_hash)]
+    pub op: DhtOpHash,
+}
+
+/// Trace a DHT op through the conductor running on an admin port.
+#[derive(Debug, Args, Clone)]
+pub struct TraceOpCall {
+    /// Port of the running conductor admin interface.
+    #[arg(short, long)]
+    pub port: u16,
+
+    /// Optional origin header to send with each admin websocket message.
+    #[arg(long)]
+    pub origin: Option<String>,
+
+    /// (flattened)
+    #[command(flatten)]
+    pub trace_op: TraceOp,
+}
+
 /// Arguments for listing capability grants info.
 #[derive(Debug, Args, Clone)]
 pub struct ListCapGrants {
@@ -329,6 +428,119 @@ pub struct PeerMetaInfoArgs {
     pub dna: Option<Vec<DnaHash>>,
 }
 
+/// Calls [`AdminWebsocket::update_coordinators`]
+/// and replaces the coordinator zomes of a cell.
+#[derive(Debug, Args, Clone)]
+pub struct UpdateCoordinators {
+    /// The DNA hash half of the cell ID to update.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID to update.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// Location of the coordinator bundle file to install.
+    pub path: PathBuf,
+
+    /// Re-queue the abandoned ops of the DNA for validation once the
+    /// coordinators have been updated.
+    #[arg(long)]
+    pub retry_abandoned_ops: bool,
+}
+
+/// Calls [`AdminWebsocket::grant_zome_call_capability`]
+/// and prints the action hash of the grant and its secret, if any.
+///
+/// The grant is unrestricted unless `--transferable` or `--assignee` is given,
+/// in which case a random secret is generated for it.
+#[derive(Debug, Args, Clone)]
+pub struct GrantZomeCallCapability {
+    /// The DNA hash half of the cell ID to grant the capability for.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID to grant the capability for.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// A tag for the grant.
+    #[arg(long, default_value = "hc-client")]
+    pub tag: String,
+
+    /// A function to grant, as `zome:function`.
+    ///
+    /// Can be given several times. If not given, all functions are granted.
+    #[arg(long = "function", value_parser = parse_granted_function)]
+    pub functions: Vec<(ZomeName, FunctionName)>,
+
+    /// Make the grant transferable, so that anyone holding the secret can use it.
+    #[arg(long, conflicts_with = "assignees")]
+    pub transferable: bool,
+
+    /// An agent to assign the grant to.
+    ///
+    /// Can be given several times.
+    #[arg(long = "assignee", value_parser = parse_agent_key)]
+    pub assignees: Vec<AgentPubKey>,
+}
+
+/// Calls [`AdminWebsocket::delete_clone_cell`]
+/// and deletes a disabled clone cell.
+#[derive(Debug, Args, Clone)]
+pub struct DeleteCloneCell {
+    /// The InstalledAppId the clone cell belongs to.
+    pub app_id: String,
+
+    /// The clone ID or DNA hash of the clone cell.
+    #[arg(value_parser = parse_clone_cell_id)]
+    pub clone_cell_id: CloneCellId,
+}
+
+/// Calls [`AdminWebsocket::issue_app_auth_token`]
+/// and prints the issued token.
+#[derive(Debug, Args, Clone)]
+pub struct IssueAppAuthToken {
+    /// The InstalledAppId to issue the token for.
+    pub app_id: String,
+
+    /// The number of seconds the token is valid for, or 0 for a token that
+    /// does not expire.
+    #[arg(long, default_value_t = 30)]
+    pub expiry_seconds: u64,
+
+    /// Allow the token to be used more than once.
+    #[arg(long)]
+    pub multi_use: bool,
+}
+
+/// Calls [`AdminWebsocket::revoke_app_authentication_token`]
+/// and revokes a token.
+#[derive(Debug, Args, Clone)]
+pub struct RevokeAppAuthToken {
+    /// The token to revoke, as the JSON array printed by `issue-app-auth-token`.
+    pub token: String,
+}
+
+/// Calls [`AdminWebsocket::get_dna_definition`]
+/// and prints the definition of a cell's DNA.
+#[derive(Debug, Args, Clone)]
+pub struct GetDnaDefinition {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+}
+
+#[derive(Serialize)]
+struct GrantedCapability {
+    action_hash: String,
+    cap_secret: Option<CapSecret>,
+}
+
 #[doc(hidden)]
 pub async fn call(req: Call) -> anyhow::Result<()> {
     let Call { port, origin, call } = req;
@@ -338,7 +550,26 @@ pub async fn call(req: Call) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn call_inner(client: &mut AdminWebsocket, call: AdminRequestCli) -> anyhow::Result<()> {
+/// Print where a DHT op is on a running conductor and why, the same as
+/// `hc client call trace-op`.
+pub async fn trace_op(req: TraceOpCall) -> anyhow::Result<()> {
+    let TraceOpCall {
+        port,
+        origin,
+        trace_op,
+    } = req;
+    call(Call {
+        port,
+        origin,
+        call: AdminRequestCli::TraceOp(trace_op),
+    })
+    .await
+}
+
+pub(crate) async fn call_inner(
+    client: &mut AdminWebsocket,
+    call: AdminRequestCli,
+) -> anyhow::Result<()> {
     match call {
         AdminRequestCli::AddAdminWs(args) => {
             let port = args.port.unwrap_or(0);
@@ -355,12 +586,19 @@ async fn call_inner(client: &mut AdminWebsocket, call: AdminRequestCli) -> anyho
         }
         AdminRequestCli::AddAppWs(args) => {
             let port = args.port.unwrap_or(0);
+            let zome_call_limits = (args.max_concurrent_calls.is_some()
+                || args.max_calls_per_second.is_some())
+            .then_some(ZomeCallLimits {
+                max_concurrent_calls: args.max_concurrent_calls,
+                max_calls_per_second: args.max_calls_per_second,
+            });
             let port = client
                 .attach_app_interface(
                     port,
                     args.danger_bind_addr,
                     args.allowed_origins,
                     args.installed_app_id,
+                    zome_call_limits,
                 )
                 .await?;
             crate::msg!("Added app port {}", port);
@@ -456,6 +694,47 @@ async fn call_inner(client: &mut AdminWebsocket, call: AdminRequestCli) -> anyho
             // Print without other text so it can be piped
             println!("{}", serde_json::to_string(&stats)?);
         }
+        AdminRequestCli::DumpPeerLatency => {
+            let latency = client.dump_peer_latency().await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&latency)?);
+        }
+        AdminRequestCli::ListSignalOutbox(args) => {
+            let outbox = client.list_signal_outbox(args.dna).await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&outbox)?);
+        }
+        AdminRequestCli::SetBandwidthLimits(args) => {
+            let limits = (args.daily_bytes.is_some() || args.bytes_per_second.is_some()).then_some(
+                BandwidthLimits {
+                    daily_bytes: args.daily_bytes,
+                    bytes_per_second: args.bytes_per_second,
+                },
+            );
+            client
+                .set_bandwidth_limits(args.app_id.clone(), limits)
+                .await?;
+            match limits {
+                Some(limits) => {
+                    crate::msg!(
+                        "Set bandwidth limits of app \"{}\": {:?}",
+                        args.app_id,
+                        limits
+                    )
+                }
+                None => crate::msg!("Removed bandwidth limits of app \"{}\"", args.app_id),
+            }
+        }
+        AdminRequestCli::ListBandwidthLimits => {
+            let limits = client.list_bandwidth_limits().await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&limits)?);
+        }
+        AdminRequestCli::TraceOp(args) => {
+            let trace = client.trace_op(args.dna, args.op).await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&trace)?);
+        }
         AdminRequestCli::RevokeZomeCallCapability(args) => {
             let action_hash = ActionHash::try_from(&args.action_hash)
                 .map_err(|e| anyhow!("Invalid action hash: {e}"))?;
@@ -533,12 +812,98 @@ async fn call_inner(client: &mut AdminWebsocket, call: AdminRequestCli) -> anyho
                 .collect::<BTreeMap<String, BTreeMap<String, PeerMetaInfo>>>();
             println!("{}", serde_json::to_string(&string_key_info)?);
         }
+        AdminRequestCli::UpdateCoordinators(args) => {
+            client
+                .update_coordinators(UpdateCoordinatorsPayload {
+                    cell_id: CellId::new(args.dna, args.agent_key),
+                    source: CoordinatorSource::Path(args.path),
+                    retry_abandoned_ops: args.retry_abandoned_ops,
+                })
+                .await?;
+            crate::msg!("Updated coordinators");
+        }
+        AdminRequestCli::GrantZomeCallCapability(args) => {
+            let functions = if args.functions.is_empty() {
+                GrantedFunctions::All
+            } else {
+                GrantedFunctions::Listed(args.functions.into_iter().collect())
+            };
+            let cap_secret = if args.transferable || !args.assignees.is_empty() {
+                let mut secret = [0; CAP_SECRET_BYTES];
+                sodoken::random::randombytes_buf(&mut secret)?;
+                Some(CapSecret::from(secret))
+            } else {
+                None
+            };
+            let access = match cap_secret {
+                None => CapAccess::Unrestricted,
+                Some(secret) if args.transferable => CapAccess::Transferable { secret },
+                Some(secret) => CapAccess::Assigned {
+                    secret,
+                    assignees: args.assignees.into_iter().collect(),
+                },
+            };
+            let action_hash = client
+                .grant_zome_call_capability(GrantZomeCallCapabilityPayload {
+                    cell_id: CellId::new(args.dna, args.agent_key),
+                    cap_grant: ZomeCallCapGrant::new(args.tag, access, functions),
+                })
+                .await?;
+            // Print without other text so it can be piped
+            println!(
+                "{}",
+                serde_json::to_string(&GrantedCapability {
+                    action_hash: action_hash.to_string(),
+                    cap_secret,
+                })?
+            );
+        }
+        AdminRequestCli::DeleteCloneCell(args) => {
+            client
+                .delete_clone_cell(DeleteCloneCellPayload {
+                    app_id: args.app_id.clone(),
+                    clone_cell_id: args.clone_cell_id,
+                })
+                .await?;
+            crate::msg!("Deleted clone cell of app: \"{}\"", args.app_id);
+        }
+        AdminRequestCli::StorageInfo => {
+            let info = client.storage_info().await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&info)?);
+        }
+        AdminRequestCli::IssueAppAuthToken(args) => {
+            let issued = client
+                .issue_app_auth_token(
+                    IssueAppAuthenticationTokenPayload::for_installed_app_id(args.app_id)
+                        .expiry_seconds(args.expiry_seconds)
+                        .single_use(!args.multi_use),
+                )
+                .await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&issued)?);
+        }
+        AdminRequestCli::RevokeAppAuthToken(args) => {
+            let token: AppAuthenticationToken = serde_json::from_str(&args.token)
+                .map_err(|e| anyhow!("Invalid app authentication token: {e}"))?;
+            client.revoke_app_authentication_token(token).await?;
+            crate::msg!("Revoked app authentication token");
+        }
+        AdminRequestCli::GetDnaDefinition(args) => {
+            let dna_def = client
+                .get_dna_definition(CellId::new(args.dna, args.agent_key))
+                .await?;
+            // Print without other text so it can be piped
+            println!("{}", serde_json::to_string(&dna_def)?);
+        }
     }
     Ok(())
 }
 
 /// Convert an [`AppInfo`] to JSON with extra base64 conversion of `agent_pub_key` and `cell_id` fields.
-fn app_info_to_base64_json(app_info: AppInfo) -> Result<serde_json::Value, serde_json::Error> {
+pub(crate) fn app_info_to_base64_json(
+    app_info: AppInfo,
+) -> Result<serde_json::Value, serde_json::Error> {
     let value = serde_json::to_value(&app_info)?;
     let serde_json::Value::Object(mut app_info_map) = value else {
         return Err(serde::de::Error::custom(
@@ -688,6 +1053,7 @@ pub async fn install_app_bundle(
         network_seed,
         ignore_genesis_failure: false,
         restore_from_dht: false,
+        wasm_metering: None,
     };
 
     let installed_app = client.install_app(payload).await?;
@@ -733,14 +1099,34 @@ async fn request_agent_info(
     Ok(out)
 }
 
-fn parse_agent_key(arg: &str) -> anyhow::Result<AgentPubKey> {
+pub(crate) fn parse_agent_key(arg: &str) -> anyhow::Result<AgentPubKey> {
     AgentPubKey::try_from(arg).map_err(|e| anyhow::anyhow!("{e:?}"))
 }
 
-fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
+pub(crate) fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
     DnaHash::try_from(arg).map_err(|e| anyhow::anyhow!("{e:?}"))
 }
 
+fn parse_dht_op_hash(arg: &str) -> anyhow::Result<DhtOpHash> {
+    DhtOpHash::try_from(arg).map_err(|e| anyhow::anyhow!("{e:?}"))
+}
+
+pub(crate) fn parse_clone_cell_id(arg: &str) -> anyhow::Result<CloneCellId> {
+    match DnaHash::try_from(arg) {
+        Ok(dna_hash) => Ok(CloneCellId::DnaHash(dna_hash)),
+        Err(_) => CloneId::try_from(arg.to_string())
+            .map(CloneCellId::CloneId)
+            .map_err(|e| anyhow!("Invalid clone cell id, expected a clone id or DNA hash: {e}")),
+    }
+}
+
+fn parse_granted_function(arg: &str) -> anyhow::Result<(ZomeName, FunctionName)> {
+    let (zome, function) = arg
+        .split_once(':')
+        .ok_or_else(|| anyhow!("Expected a function as `zome:function`, got {arg}"))?;
+    Ok((ZomeName::from(zome), FunctionName::from(function)))
+}
+
 fn parse_source_chain_cursor(arg: &str) -> anyhow::Result<SourceChainCursor> {
     match arg.parse::<u32>() {
         Ok(sequence) => Ok(SourceChainCursor::Sequence(sequence)),
@@ -935,6 +1321,61 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn parses_grant_zome_call_capability_arguments() {
+        let dna = "uhC0kWCsAgoKkkfwyJAglj30xX_GLLV-3BXuFy436a2SqpcEwyBzm";
+        let agent = "uhCAkJCuynkgVdMn_bzZ2ZYaVfygkn0WCuzfFspczxFnZM1QAyXoo";
+        let call = Call::try_parse_from([
+            "hc-client",
+            "--port",
+            "1234",
+            "grant-zome-call-capability",
+            dna,
+            agent,
+            "--function",
+            "my_zome:my_fn",
+            "--assignee",
+            agent,
+        ])
+        .unwrap();
+
+        let AdminRequestCli::GrantZomeCallCapability(args) = call.call else {
+            panic!("expected grant-zome-call-capability arguments");
+        };
+        assert_eq!(
+            args.functions,
+            vec![(ZomeName::from("my_zome"), FunctionName::from("my_fn"))]
+        );
+        assert_eq!(args.assignees, vec![AgentPubKey::try_from(agent).unwrap()]);
+        assert!(!args.transferable);
+
+        assert!(Call::try_parse_from([
+            "hc-client",
+            "--port",
+            "1234",
+            "grant-zome-call-capability",
+            dna,
+            agent,
+            "--function",
+            "missing-separator",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parses_clone_cell_ids() {
+        let dna = "uhC0kWCsAgoKkkfwyJAglj30xX_GLLV-3BXuFy436a2SqpcEwyBzm";
+        assert!(matches!(
+            parse_clone_cell_id(dna).unwrap(),
+            CloneCellId::DnaHash(_)
+        ));
+        assert!(matches!(
+            parse_clone_cell_id("my-role.0").unwrap(),
+            CloneCellId::CloneId(clone_id) if clone_id == CloneId::new(&"my-role".to_string(), 0)
+        ));
+        assert!(parse_clone_cell_id("my-role").is_err());
+    }
+
     fn test_cell_id(dna: u8, agent: u8) -> CellId {
         CellId::new(test_dna_hash(dna), test_agent_key(agent))
     }
@@ -1,18 +1,28 @@
 use clap::{Parser, Subcommand};
 
-use crate::{calls, zome_call};
+use crate::{app_calls, calls, repl, signals, zome_call};
 
 /// Client commands that can be executed.
 #[derive(Debug, Subcommand)]
 pub enum ClientCommand {
     /// Invoke an admin API request via the conductor's admin interface.
     Call(calls::Call),
+    /// Invoke an app API request via an app interface of the conductor.
+    #[command(name = "app-call")]
+    AppCall(app_calls::AppCall),
     /// Generate signing credentials for zome calls and grant capabilities.
     #[command(name = "zome-call-auth")]
     ZomeCallAuth(zome_call::ZomeCallAuth),
     /// Make a zome call against a running conductor.
     #[command(name = "zome-call")]
     ZomeCall(zome_call::ZomeCall),
+    /// Start an interactive session with a running conductor.
+    Repl(repl::Repl),
+    /// Print the signals of an app as newline delimited JSON.
+    Signals(signals::Signals),
+    /// Print where a DHT op is on a running conductor and why.
+    #[command(name = "trace-op")]
+    TraceOp(calls::TraceOpCall),
 }
 
 /// Execution context for running CLI commands.
@@ -34,8 +44,12 @@ impl HcClient {
     pub async fn run(self) -> anyhow::Result<()> {
         match self.command {
             ClientCommand::Call(call) => calls::call(call).await,
+            ClientCommand::AppCall(call) => app_calls::app_call(call).await,
             ClientCommand::ZomeCallAuth(auth) => zome_call::zome_call_auth(auth).await,
             ClientCommand::ZomeCall(call) => zome_call::zome_call(call).await,
+            ClientCommand::Repl(repl) => repl::repl(repl).await,
+            ClientCommand::Signals(args) => signals::signals(args).await,
+            ClientCommand::TraceOp(args) => calls::trace_op(args).await,
         }
     }
 }
@@ -16,9 +16,12 @@ macro_rules! msg {
 
 pub(crate) use msg;
 
+pub mod app_calls;
 pub mod calls;
 /// CLI entry points for the `hc-client` binary.
 pub mod cli;
+pub mod repl;
+pub mod signals;
 pub mod zome_call;
 
 pub use cli::HcClient;
@@ -0,0 +1,380 @@
+//! An interactive session with a running conductor.
+//!
+//! `hc client repl` keeps an admin connection to the conductor open, along
+//! with an app connection for each app it has made requests for. Each line is
+//! either an admin request, written as for `hc client call`, or an app request
+//! for an installed app, written as for `hc client app-call`:
+//!
+//! ```shell
+//! hc-client> list-apps
+//! hc-client> app my-app app-info
+//! hc-client> app my-app call-zome my-role my-zome my-function '{"my": "payload"}'
+//! ```
+//!
+//! App IDs and cell IDs can be completed with tab, and are refreshed from the
+//! conductor after every request. A cell ID is completed as its DNA hash
+//! followed by the agent key of a cell with that DNA. Zome calls are signed
+//! with the credentials created by `zome-call-auth`, after prompting for their
+//! passphrase once per session.
+
+use crate::app_calls::{add_app_credentials, app_call_inner, AppRequestCli};
+use crate::calls::{call_inner, AdminRequestCli};
+use crate::zome_call::{generate_signing_credentials, get_app_client, Auth};
+use anyhow::Context;
+use clap::{CommandFactory, Parser, Subcommand};
+use holochain_client::{AdminWebsocket, AppWebsocket, ClientAgentSigner};
+use holochain_types::prelude::InstalledAppId;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::{Editor, Helper, Highlighter, Hinter, Validator};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+
+/// Start an interactive session with a running conductor.
+#[derive(Debug, Parser)]
+pub struct Repl {
+    /// Port of the running conductor admin interface.
+    #[arg(short, long)]
+    pub port: u16,
+
+    /// Optional origin header to send with each admin websocket message.
+    #[arg(long)]
+    pub origin: Option<String>,
+}
+
+/// A line entered in the REPL.
+#[derive(Debug, Parser)]
+#[command(name = "repl", no_binary_name = true, disable_version_flag = true)]
+struct ReplLine {
+    #[command(subcommand)]
+    command: ReplCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum ReplCommand {
+    #[command(flatten)]
+    Admin(AdminRequestCli),
+    /// Make an app API request for an installed app.
+    App {
+        /// The installed app id to make the request for.
+        app_id: InstalledAppId,
+
+        /// The app request you want to make.
+        #[command(subcommand)]
+        call: AppRequestCli,
+    },
+    /// Leave the REPL.
+    #[command(alias = "quit")]
+    Exit,
+}
+
+/// An open app connection and the signer its zome calls are signed with.
+struct AppConnection {
+    client: AppWebsocket,
+    signer: ClientAgentSigner,
+}
+
+struct Session {
+    admin: AdminWebsocket,
+    apps: HashMap<InstalledAppId, AppConnection>,
+    signing_key: Option<(Auth, ed25519_dalek::SigningKey)>,
+    completions: Arc<RwLock<Completions>>,
+}
+
+/// The words that can be completed.
+#[derive(Debug, Default)]
+struct Completions {
+    /// Command names and app IDs.
+    words: BTreeSet<String>,
+    /// The agent keys of the cells of each DNA, keyed by DNA hash.
+    cells: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Completions {
+    /// The start of the word that ends at the end of `line`, and the
+    /// completions for it.
+    ///
+    /// A word after the DNA hash of a cell is completed with the agent keys of
+    /// the cells with that DNA, so that together they form a cell ID.
+    fn complete(&self, line: &str) -> (usize, Vec<String>) {
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let prefix = &line[start..];
+        let previous = line[..start].split_whitespace().next_back();
+        let candidates: Vec<String> = match previous.and_then(|word| self.cells.get(word)) {
+            Some(agents) => agents
+                .iter()
+                .filter(|agent| agent.starts_with(prefix))
+                .cloned()
+                .collect(),
+            None => self
+                .words
+                .iter()
+                .chain(self.cells.keys())
+                .filter(|word| word.starts_with(prefix))
+                .cloned()
+                .collect(),
+        };
+        (start, candidates)
+    }
+}
+
+/// Completes command names, app IDs and cell IDs.
+#[derive(Helper, Highlighter, Hinter, Validator)]
+struct ReplHelper {
+    completions: Arc<RwLock<Completions>>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, candidates) = self
+            .completions
+            .read()
+            .expect("completions lock poisoned")
+            .complete(&line[..pos]);
+        let candidates = candidates
+            .into_iter()
+            .map(|word| Pair {
+                display: word.clone(),
+                replacement: word,
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+/// Run the REPL until the user exits it.
+pub async fn repl(repl: Repl) -> anyhow::Result<()> {
+    let admin = AdminWebsocket::connect(format!("localhost:{}", repl.port), repl.origin).await?;
+    let completions = Arc::new(RwLock::new(Completions::default()));
+    let mut session = Session {
+        admin,
+        apps: HashMap::new(),
+        signing_key: None,
+        completions: completions.clone(),
+    };
+    session.refresh_completions().await?;
+
+    let (prompt, mut lines) = spawn_line_reader(ReplHelper { completions });
+
+    crate::msg!(
+        "Connected to admin port {}. Type `help` for commands.",
+        repl.port
+    );
+    loop {
+        if prompt.send(()).is_err() {
+            break;
+        }
+        let line = match lines.recv().await {
+            Some(Ok(line)) => line,
+            Some(Err(ReadlineError::Interrupted)) => continue,
+            Some(Err(ReadlineError::Eof)) | None => break,
+            Some(Err(e)) => return Err(e.into()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let Some(args) = shlex::split(&line) else {
+            crate::msg!("Unbalanced quotes in: {line}");
+            continue;
+        };
+        let command = match ReplLine::try_parse_from(args) {
+            Ok(parsed) => parsed.command,
+            Err(e) => {
+                let _ = e.print();
+                continue;
+            }
+        };
+        if let ReplCommand::Exit = command {
+            break;
+        }
+        if let Err(e) = session.run(command).await {
+            crate::msg!("Error: {e:?}");
+        }
+        // Completions are best effort, the next request will report a lost connection.
+        let _ = session.refresh_completions().await;
+    }
+
+    Ok(())
+}
+
+/// Read lines on a thread of their own, one for each prompt sent.
+///
+/// Reading a line blocks until it is entered, so it is kept off the tokio
+/// runtime, whichever flavour it is.
+fn spawn_line_reader(
+    helper: ReplHelper,
+) -> (
+    std::sync::mpsc::Sender<()>,
+    tokio::sync::mpsc::Receiver<rustyline::Result<String>>,
+) {
+    let (prompt_tx, prompt_rx) = std::sync::mpsc::channel::<()>();
+    let (line_tx, line_rx) = tokio::sync::mpsc::channel(1);
+    std::thread::spawn(move || {
+        let mut editor = match Editor::<ReplHelper, DefaultHistory>::new() {
+            Ok(editor) => editor,
+            Err(e) => {
+                let _ = line_tx.blocking_send(Err(e));
+                return;
+            }
+        };
+        editor.set_helper(Some(helper));
+        while prompt_rx.recv().is_ok() {
+            let line = editor.readline("hc-client> ");
+            if let Ok(line) = &line {
+                if !line.trim().is_empty() {
+                    let _ = editor.add_history_entry(line.as_str());
+                }
+            }
+            if line_tx.blocking_send(line).is_err() {
+                break;
+            }
+        }
+    });
+    (prompt_tx, line_rx)
+}
+
+impl Session {
+    async fn run(&mut self, command: ReplCommand) -> anyhow::Result<()> {
+        match command {
+            ReplCommand::Admin(call) => call_inner(&mut self.admin, call).await,
+            ReplCommand::App { app_id, call } => {
+                if let AppRequestCli::CallZome(_) = call {
+                    self.ensure_signing_key().await?;
+                }
+                if !self.apps.contains_key(&app_id) {
+                    let signer = ClientAgentSigner::new();
+                    let client =
+                        get_app_client(&self.admin, app_id.clone(), signer.clone()).await?;
+                    self.apps
+                        .insert(app_id.clone(), AppConnection { client, signer });
+                }
+                let connection = self
+                    .apps
+                    .get_mut(&app_id)
+                    .expect("app connection was just inserted");
+
+                if let Some((auth, key)) = &self.signing_key {
+                    add_app_credentials(
+                        &connection.signer,
+                        connection.client.cached_app_info(),
+                        auth,
+                        key,
+                    );
+                }
+                app_call_inner(&mut connection.client, call).await
+            }
+            ReplCommand::Exit => Ok(()),
+        }
+    }
+
+    async fn ensure_signing_key(&mut self) -> anyhow::Result<()> {
+        if self.signing_key.is_none() {
+            crate::msg!("Enter passphrase to authorize zome calls: ");
+            let passphrase = tokio::task::spawn_blocking(holochain_util::pw::pw_get)
+                .await?
+                .context("Failed to get passphrase")?;
+            self.signing_key = Some(generate_signing_credentials(passphrase).await?);
+        }
+        Ok(())
+    }
+
+    async fn refresh_completions(&self) -> anyhow::Result<()> {
+        let mut words: BTreeSet<String> = ReplLine::command()
+            .get_subcommands()
+            .map(|command| command.get_name().to_string())
+            .collect();
+        words.extend(
+            AppRequestCli::augment_subcommands(clap::Command::new("app"))
+                .get_subcommands()
+                .map(|command| command.get_name().to_string()),
+        );
+        words.extend(
+            self.admin
+                .list_apps(None)
+                .await?
+                .into_iter()
+                .map(|app| app.installed_app_id),
+        );
+        let mut cells: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for cell_id in self.admin.list_cell_ids().await? {
+            cells
+                .entry(cell_id.dna_hash().to_string())
+                .or_default()
+                .insert(cell_id.agent_pubkey().to_string());
+        }
+
+        *self.completions.write().expect("completions lock poisoned") =
+            Completions { words, cells };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_admin_and_app_lines() {
+        let line = ReplLine::try_parse_from(shlex::split("list-apps").unwrap()).unwrap();
+        assert!(matches!(
+            line.command,
+            ReplCommand::Admin(AdminRequestCli::ListApps(_))
+        ));
+
+        let line = ReplLine::try_parse_from(
+            shlex::split(r#"app my-app call-zome my-role my-zome my_fn '{"a": 1}'"#).unwrap(),
+        )
+        .unwrap();
+        let ReplCommand::App {
+            app_id,
+            call: AppRequestCli::CallZome(call),
+        } = line.command
+        else {
+            panic!("expected an app call-zome line");
+        };
+        assert_eq!(app_id, "my-app");
+        assert_eq!(call.target, "my-role");
+        assert_eq!(call.payload, r#"{"a": 1}"#);
+    }
+
+    #[test]
+    fn completes_cell_ids() {
+        let completions = Completions {
+            words: ["dump-state".to_string(), "my-app".to_string()].into(),
+            cells: [
+                (
+                    "uhC0kdna1".to_string(),
+                    ["uhCAkalice".to_string(), "uhCAkbob".to_string()].into(),
+                ),
+                ("uhC0kdna2".to_string(), ["uhCAkcarol".to_string()].into()),
+            ]
+            .into(),
+        };
+
+        assert_eq!(
+            completions.complete("dump-state uhC0k"),
+            (11, vec!["uhC0kdna1".to_string(), "uhC0kdna2".to_string()])
+        );
+        // Only agents with a cell of the DNA complete its cell ID.
+        assert_eq!(
+            completions.complete("dump-state uhC0kdna1 uhCAk"),
+            (21, vec!["uhCAkalice".to_string(), "uhCAkbob".to_string()])
+        );
+        assert_eq!(
+            completions.complete("dump-state uhC0kdna2 "),
+            (21, vec!["uhCAkcarol".to_string()])
+        );
+        // Agent keys are not offered on their own.
+        assert_eq!(completions.complete("dump-state uhCAk"), (11, vec![]));
+        assert_eq!(completions.complete("my"), (0, vec!["my-app".to_string()]));
+    }
+}
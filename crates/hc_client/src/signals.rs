@@ -0,0 +1,271 @@
+//! Stream the signals of an app to stdout.
+//!
+//! `hc client signals` connects to an app interface of a running conductor and
+//! prints every [`Signal`] the app receives as a line of JSON, until it is
+//! interrupted. App and direct signal payloads are decoded from msgpack to
+//! JSON where possible, so the output can be piped to tools like `jq`.
+
+use crate::app_calls::app_cell_ids;
+use crate::calls::parse_dna_hash;
+use crate::zome_call::get_app_client;
+use chrono::Utc;
+use clap::Parser;
+use holochain_client::{AdminWebsocket, ClientAgentSigner};
+use holochain_conductor_api::{AppInfo, CellInfo};
+use holochain_types::prelude::{CellId, ExternIO, ZomeName};
+use holochain_types::signal::{Signal, SystemSignal};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Print the signals of an app as newline delimited JSON.
+///
+/// Signals can be filtered by the cell and zome they come from. By default
+/// only matching signals are printed, or with `--exclude` all signals except
+/// the matching ones are printed.
+#[derive(Debug, Parser)]
+pub struct Signals {
+    /// Port of the running conductor admin interface.
+    #[arg(short, long)]
+    pub port: u16,
+
+    /// The installed app id to stream signals for.
+    #[arg(long)]
+    pub app_id: String,
+
+    /// A cell to match signals from, as a DNA hash or role name.
+    ///
+    /// Can be given several times.
+    #[arg(long = "cell")]
+    pub cells: Vec<String>,
+
+    /// A zome to match app signals from.
+    ///
+    /// Can be given several times. Only signals emitted by a zome can match.
+    #[arg(long = "zome")]
+    pub zomes: Vec<String>,
+
+    /// Print all signals except the matching ones.
+    #[arg(long)]
+    pub exclude: bool,
+}
+
+/// Filters signals by cell and zome, as an include or exclude list.
+#[derive(Debug, Default)]
+struct SignalFilter {
+    cells: Option<HashSet<CellId>>,
+    zomes: Option<HashSet<ZomeName>>,
+    exclude: bool,
+}
+
+impl SignalFilter {
+    fn new(app_info: &AppInfo, args: &Signals) -> anyhow::Result<Self> {
+        let cells = if args.cells.is_empty() {
+            None
+        } else {
+            let mut cells = HashSet::new();
+            for cell in &args.cells {
+                let matching = resolve_cells(app_info, cell);
+                if matching.is_empty() {
+                    anyhow::bail!("No cell found for {cell} in app {}", args.app_id);
+                }
+                cells.extend(matching);
+            }
+            Some(cells)
+        };
+        let zomes = if args.zomes.is_empty() {
+            None
+        } else {
+            Some(
+                args.zomes
+                    .iter()
+                    .map(|z| ZomeName::from(z.as_str()))
+                    .collect(),
+            )
+        };
+
+        Ok(Self {
+            cells,
+            zomes,
+            exclude: args.exclude,
+        })
+    }
+
+    fn allows(&self, signal: &Signal) -> bool {
+        if self.cells.is_none() && self.zomes.is_none() {
+            return true;
+        }
+        let cell_matches = match &self.cells {
+            None => true,
+            Some(cells) => signal_cell_id(signal).is_some_and(|cell_id| cells.contains(cell_id)),
+        };
+        let zome_matches = match (&self.zomes, signal) {
+            (None, _) => true,
+            (Some(zomes), Signal::App { zome_name, .. }) => zomes.contains(zome_name),
+            (Some(_), _) => false,
+        };
+        (cell_matches && zome_matches) != self.exclude
+    }
+}
+
+/// Stream the signals of an app to stdout until interrupted.
+pub async fn signals(args: Signals) -> anyhow::Result<()> {
+    let admin_client = AdminWebsocket::connect(format!("localhost:{}", args.port), None).await?;
+    let app_client =
+        get_app_client(&admin_client, args.app_id.clone(), ClientAgentSigner::new()).await?;
+    let filter = SignalFilter::new(app_client.cached_app_info(), &args)?;
+
+    app_client
+        .on_signal(move |signal| {
+            if !filter.allows(&signal) {
+                return;
+            }
+            let mut stdout = std::io::stdout().lock();
+            // Print without other text so it can be piped
+            let _ = writeln!(stdout, "{}", signal_to_json(signal));
+            let _ = stdout.flush();
+        })
+        .await;
+
+    // On stderr, so that stdout only carries the signals
+    eprintln!("hc-client: Streaming signals for app \"{}\"", args.app_id);
+    tokio::signal::ctrl_c().await?;
+
+    Ok(())
+}
+
+/// The cells of an app that match a DNA hash or role name.
+fn resolve_cells(app_info: &AppInfo, cell: &str) -> Vec<CellId> {
+    if let Ok(dna_hash) = parse_dna_hash(cell) {
+        return app_cell_ids(app_info)
+            .into_iter()
+            .filter(|cell_id| cell_id.dna_hash() == &dna_hash)
+            .collect();
+    }
+    app_info
+        .cell_info
+        .get(cell)
+        .into_iter()
+        .flatten()
+        .filter_map(|info| match info {
+            CellInfo::Provisioned(cell) => Some(cell.cell_id.clone()),
+            CellInfo::Cloned(cell) => Some(cell.cell_id.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn signal_cell_id(signal: &Signal) -> Option<&CellId> {
+    match signal {
+        Signal::App { cell_id, .. } | Signal::AppDirect { cell_id, .. } => Some(cell_id),
+        Signal::System(SystemSignal::RestoreComplete { cell_id })
+        | Signal::System(SystemSignal::RestoreFailed { cell_id, .. }) => Some(cell_id),
+        Signal::System(_) => None,
+    }
+}
+
+fn cell_id_to_json(cell_id: &CellId) -> serde_json::Value {
+    serde_json::json!({
+        "dna_hash": cell_id.dna_hash().to_string(),
+        "agent_pub_key": cell_id.agent_pubkey().to_string(),
+    })
+}
+
+/// Decode a msgpack payload to JSON, or fall back to its raw bytes.
+fn payload_to_json(payload: ExternIO) -> serde_json::Value {
+    match payload.decode::<hc_serde_json::Value>() {
+        Ok(value) => serde_json::json!({ "payload": value }),
+        Err(_) => serde_json::json!({ "payload_bytes": payload.0 }),
+    }
+}
+
+fn signal_to_json(signal: Signal) -> serde_json::Value {
+    let cell_id = signal_cell_id(&signal).map(cell_id_to_json);
+    let mut json = match signal {
+        Signal::App {
+            zome_name, signal, ..
+        } => {
+            let mut json = payload_to_json(signal.into_inner());
+            json["type"] = "app".into();
+            json["zome_name"] = zome_name.to_string().into();
+            json
+        }
+        Signal::AppDirect { signal, .. } => {
+            let mut json = payload_to_json(ExternIO(signal));
+            json["type"] = "app_direct".into();
+            json
+        }
+        Signal::System(signal) => serde_json::json!({
+            "type": "system",
+            "signal": serde_json::to_value(&signal).unwrap_or_default(),
+        }),
+    };
+    if let Some(cell_id) = cell_id {
+        json["cell_id"] = cell_id;
+    }
+    json["received_at"] = Utc::now().to_rfc3339().into();
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holo_hash::{AgentPubKey, DnaHash};
+    use holochain_types::prelude::AppSignal;
+
+    fn test_cell_id(dna: u8) -> CellId {
+        CellId::new(
+            DnaHash::from_raw_36(vec![dna; 36]),
+            AgentPubKey::from_raw_36(vec![0; 36]),
+        )
+    }
+
+    fn app_signal(cell_id: CellId, zome_name: &str) -> Signal {
+        Signal::App {
+            cell_id,
+            zome_name: zome_name.into(),
+            signal: AppSignal::new(ExternIO::encode(serde_json::json!({"a": 1})).unwrap()),
+        }
+    }
+
+    #[test]
+    fn filter_matches_cell_and_zome() {
+        let mut filter = SignalFilter {
+            cells: Some([test_cell_id(1)].into_iter().collect()),
+            zomes: Some([ZomeName::from("chat")].into_iter().collect()),
+            exclude: false,
+        };
+
+        assert!(filter.allows(&app_signal(test_cell_id(1), "chat")));
+        assert!(!filter.allows(&app_signal(test_cell_id(1), "other")));
+        assert!(!filter.allows(&app_signal(test_cell_id(2), "chat")));
+        assert!(!filter.allows(&Signal::AppDirect {
+            cell_id: test_cell_id(1),
+            signal: vec![],
+        }));
+
+        filter.exclude = true;
+        assert!(!filter.allows(&app_signal(test_cell_id(1), "chat")));
+        assert!(filter.allows(&app_signal(test_cell_id(2), "chat")));
+
+        assert!(SignalFilter::default().allows(&app_signal(test_cell_id(2), "chat")));
+    }
+
+    #[test]
+    fn app_signal_payload_is_decoded_to_json() {
+        let json = signal_to_json(app_signal(test_cell_id(1), "chat"));
+        assert_eq!(json["type"], "app");
+        assert_eq!(json["zome_name"], "chat");
+        assert_eq!(json["payload"], serde_json::json!({"a": 1}));
+        assert_eq!(
+            json["cell_id"]["dna_hash"],
+            test_cell_id(1).dna_hash().to_string()
+        );
+
+        let json = signal_to_json(Signal::AppDirect {
+            cell_id: test_cell_id(1),
+            signal: vec![0xc1],
+        });
+        assert_eq!(json["type"], "app_direct");
+        assert_eq!(json["payload_bytes"], serde_json::json!([0xc1]));
+    }
+}
@@ -147,7 +147,12 @@ pub async fn zome_call_auth(zome_call_auth: ZomeCallAuth) -> anyhow::Result<()>
     let admin_port = zome_call_auth.connect_args.port;
 
     let admin_client = AdminWebsocket::connect(format!("localhost:{admin_port}"), None).await?;
-    let app_client = get_app_client(&admin_client, zome_call_auth.app_id.clone(), None).await?;
+    let app_client = get_app_client(
+        &admin_client,
+        zome_call_auth.app_id.clone(),
+        ClientAgentSigner::new(),
+    )
+    .await?;
     let app_info = app_client.app_info().await?;
     let info = match app_info {
         Some(info) => info,
@@ -202,7 +207,12 @@ pub async fn zome_call(zome_call: ZomeCall) -> anyhow::Result<()> {
 
     let admin_client = AdminWebsocket::connect(format!("localhost:{admin_port}"), None).await?;
 
-    let app_client = get_app_client(&admin_client, zome_call.app_id.clone(), None).await?;
+    let app_client = get_app_client(
+        &admin_client,
+        zome_call.app_id.clone(),
+        ClientAgentSigner::new(),
+    )
+    .await?;
 
     let app_info = app_client.app_info().await?;
     let info = match app_info {
@@ -240,25 +250,12 @@ pub async fn zome_call(zome_call: ZomeCall) -> anyhow::Result<()> {
 
     let (auth, key) = generate_signing_credentials(passphrase).await?;
 
-    let credentials: Vec<(CellId, SigningCredentials)> = cell_ids
-        .clone()
-        .into_iter()
-        .map(|cell_id| {
-            (
-                cell_id,
-                SigningCredentials {
-                    signing_agent_key: AgentPubKey::from_raw_32(
-                        key.verifying_key().as_bytes().to_vec(),
-                    ),
-                    keypair: key.clone(),
-                    cap_secret: auth.cap_secret,
-                },
-            )
-        })
-        .collect();
+    let signer = ClientAgentSigner::new();
+    for (cell_id, credentials) in signing_credentials(&auth, &key, cell_ids.clone()) {
+        signer.add_credentials(cell_id, credentials);
+    }
 
-    let app_client =
-        get_app_client(&admin_client, zome_call.app_id.clone(), Some(credentials)).await?;
+    let app_client = get_app_client(&admin_client, zome_call.app_id.clone(), signer).await?;
 
     let response = app_client
         .call_zome(
@@ -280,7 +277,9 @@ pub async fn zome_call(zome_call: ZomeCall) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn generate_signing_credentials(
+/// Derive the zome call signing key from a passphrase and the `.hc_auth`
+/// file, which is created if it doesn't exist yet.
+pub(crate) async fn generate_signing_credentials(
     passphrase: Arc<Mutex<sodoken::LockedArray>>,
 ) -> anyhow::Result<(Auth, ed25519_dalek::SigningKey)> {
     let auth = load_or_create_auth().await?;
@@ -306,10 +305,36 @@ async fn generate_signing_credentials(
     Ok((auth, ed25519_dalek::SigningKey::from_bytes(&hash)))
 }
 
-async fn get_app_client(
+/// Build the signing credentials for `cell_ids` from a key made by
+/// [`generate_signing_credentials`].
+pub(crate) fn signing_credentials(
+    auth: &Auth,
+    key: &ed25519_dalek::SigningKey,
+    cell_ids: impl IntoIterator<Item = CellId>,
+) -> Vec<(CellId, SigningCredentials)> {
+    cell_ids
+        .into_iter()
+        .map(|cell_id| {
+            (
+                cell_id,
+                SigningCredentials {
+                    signing_agent_key: AgentPubKey::from_raw_32(
+                        key.verifying_key().as_bytes().to_vec(),
+                    ),
+                    keypair: key.clone(),
+                    cap_secret: auth.cap_secret,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Connect to an app interface for `installed_app_id`, attaching one if there
+/// is no suitable interface yet.
+pub(crate) async fn get_app_client(
     admin_client: &AdminWebsocket,
     installed_app_id: InstalledAppId,
-    credentials: Option<Vec<(CellId, SigningCredentials)>>,
+    signer: ClientAgentSigner,
 ) -> anyhow::Result<AppWebsocket> {
     let app_interfaces = admin_client.list_app_interfaces().await?;
 
@@ -339,6 +364,7 @@ async fn get_app_client(
                     None,
                     AllowedOrigins::Origins(vec!["sandbox".to_string()].into_iter().collect()),
                     None,
+                    None,
                 )
                 .await?
         }
@@ -350,17 +376,6 @@ async fn get_app_client(
         ))
         .await?;
 
-    let signer = ClientAgentSigner::new();
-
-    match credentials {
-        None => (),
-        Some(c) => {
-            for (cell_id, creds) in c {
-                signer.add_credentials(cell_id, creds);
-            }
-        }
-    }
-
     Ok(AppWebsocket::connect(
         format!("localhost:{port}"),
         token.token,
@@ -371,7 +386,7 @@ async fn get_app_client(
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct Auth {
+pub(crate) struct Auth {
     salt: Vec<u8>,
     cap_secret: CapSecret,
 }
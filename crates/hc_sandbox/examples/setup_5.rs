@@ -52,6 +52,7 @@ async fn main() -> anyhow::Result<()> {
             network_seed: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         };
 
         let installed_app = admin_ws.install_app(payload).await?;
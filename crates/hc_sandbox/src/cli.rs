@@ -1,11 +1,13 @@
 //! Definitions of Parser options for use in the CLI
 
 use crate::cmds::*;
+use crate::local_network::{LocalNetwork, LocalNetworkServer};
 use clap::{ArgAction, Parser};
 use holochain_conductor_api::conductor::paths::ConfigRootPath;
 use holochain_trace::Output;
 use holochain_types::prelude::InstalledAppId;
 use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -90,6 +92,17 @@ pub enum HcSandboxSubcommand {
         /// for an example of such a yaml file.
         #[arg(long)]
         roles_settings: Option<PathBuf>,
+
+        /// Generate this many sandboxes on a self-contained local network.
+        ///
+        /// `hc sandbox` runs a bootstrap and relay server for the sandboxes
+        /// whenever they are run, so that they can network without any
+        /// external services. The server is forgotten by `hc sandbox clean`.
+        ///
+        /// This cannot be used with `--num-sandboxes` or with a network
+        /// config.
+        #[arg(long, value_name = "N", conflicts_with = "num_sandboxes")]
+        local_network: Option<NonZeroUsize>,
     },
 
     /// Run conductor(s) from existing sandbox(es).
@@ -129,6 +142,37 @@ pub enum HcSandboxSubcommand {
 
     /// Create a fresh sandbox with no apps installed.
     Create(Create),
+
+    /// Save a snapshot of existing sandbox(es) to `$(pwd)/.hc_snapshots`.
+    ///
+    /// The config, databases and keystore of each sandbox are copied, so that
+    /// the exact state of the sandboxes can be restored later with
+    /// `hc sandbox restore`. The apps of running sandboxes are disabled while
+    /// they are copied and enabled again afterwards.
+    Snapshot {
+        /// Name of the snapshot.
+        name: String,
+
+        /// (flattened)
+        #[command(flatten)]
+        existing: Existing,
+    },
+
+    /// Restore the sandboxes of a snapshot into new sandboxes.
+    ///
+    /// The new sandboxes are added to `$(pwd)/.hc` and get new admin ports
+    /// when they run. Apps that were disabled while the snapshot was taken
+    /// are enabled again the first time a new sandbox runs.
+    Restore {
+        /// Name of the snapshot.
+        name: String,
+
+        /// Set a root directory for the restored sandboxes to be placed into.
+        /// Defaults to the system's temp directory.
+        /// This directory must already exist.
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
 }
 
 /// Options for running a sandbox
@@ -154,12 +198,31 @@ impl HcSandbox {
         match self.subcommand {
             HcSandboxSubcommand::Generate {
                 app_id,
-                create,
+                mut create,
                 run,
                 happ,
                 network_seed,
                 roles_settings,
+                local_network,
             } => {
+                let hc_dir = std::env::current_dir()?;
+                let mut local_network_server = None;
+                if let Some(num_sandboxes) = local_network {
+                    if create.network.is_some() {
+                        anyhow::bail!("A local network cannot be used with a network config");
+                    }
+                    // Sandboxes generated earlier in this directory keep
+                    // their local network, so join it rather than start another.
+                    // The server is started now so that it picks its own port.
+                    let server = match LocalNetwork::load(&hc_dir)? {
+                        Some(local_network) => local_network.start().await?,
+                        None => LocalNetwork::start_new().await?,
+                    };
+                    server.local_network().save(&hc_dir)?;
+                    create.num_sandboxes = num_sandboxes;
+                    create.network = Some(server.local_network().network());
+                    local_network_server = Some(server);
+                }
                 let paths = generate(
                     &self.holochain_path,
                     happ,
@@ -170,6 +233,9 @@ impl HcSandbox {
                     self.structured.clone(),
                 )
                 .await?;
+                if local_network_server.is_some() {
+                    LocalNetwork::add_sandboxes(&hc_dir, &paths)?;
+                }
                 for (port, path) in self
                     .force_admin_ports
                     .clone()
@@ -179,6 +245,10 @@ impl HcSandbox {
                     crate::force_admin_port(path, port)?;
                 }
                 if let Some(ports) = run {
+                    let local_network_server = match local_network_server {
+                        Some(server) => Some(server),
+                        None => start_local_network(&paths).await?,
+                    };
                     let holochain_path = self.holochain_path.clone();
                     let force_admin_ports = self.force_admin_ports.clone();
                     let structured = self.structured.clone();
@@ -190,9 +260,15 @@ impl HcSandbox {
                         }
                         result = run_n(&holochain_path, paths, ports, force_admin_ports, structured) => result,
                     };
+                    if let Some(server) = local_network_server {
+                        server.shutdown().await;
+                    }
                     crate::save::release_ports(std::env::current_dir()?).await?;
                     return result;
                 }
+                if let Some(server) = local_network_server {
+                    server.shutdown().await;
+                }
             }
             HcSandboxSubcommand::Run(Run { ports, existing }) => {
                 let paths = existing.load(std::env::current_dir()?)?;
@@ -200,6 +276,9 @@ impl HcSandbox {
                     tracing::warn!("no paths available, exiting.");
                     return Ok(());
                 }
+                let paths: Vec<ConfigRootPath> =
+                    paths.into_iter().map(ConfigRootPath::from).collect();
+                let local_network_server = start_local_network(&paths).await?;
                 let holochain_path = self.holochain_path.clone();
                 let force_admin_ports = self.force_admin_ports.clone();
 
@@ -208,8 +287,11 @@ impl HcSandbox {
                         msg!("Received Ctrl-C, shutting down");
                         result.map_err(anyhow::Error::from)
                     }
-                    result = run_n(&holochain_path, paths.into_iter().map(ConfigRootPath::from).collect(), ports, force_admin_ports, self.structured) => result,
+                    result = run_n(&holochain_path, paths, ports, force_admin_ports, self.structured) => result,
                 };
+                if let Some(server) = local_network_server {
+                    server.shutdown().await;
+                }
                 crate::save::release_ports(std::env::current_dir()?).await?;
                 return result;
             }
@@ -283,12 +365,56 @@ impl HcSandbox {
                 }
                 save_and_print(std::env::current_dir()?, paths)?;
             }
+            HcSandboxSubcommand::Snapshot { name, existing } => {
+                let hc_dir = std::env::current_dir()?;
+                let paths = existing.load(hc_dir.clone())?;
+                let count = paths.len();
+                let snapshot_dir = crate::snapshot::snapshot(&hc_dir, &name, paths).await?;
+                msg!(
+                    "Saved {} sandbox(es) to snapshot '{}' at {}",
+                    count,
+                    name,
+                    snapshot_dir.display()
+                );
+            }
+            HcSandboxSubcommand::Restore { name, root } => {
+                let hc_dir = std::env::current_dir()?;
+                if !crate::snapshot::list(&hc_dir)?.contains(&name) {
+                    anyhow::bail!(
+                        "No snapshot named '{name}'. Available snapshots: {:?}",
+                        crate::snapshot::list(&hc_dir)?
+                    );
+                }
+                msg!("Restoring snapshot '{}'", name);
+                let paths = crate::snapshot::restore(&hc_dir, &name, root)?;
+                save_and_print(hc_dir, paths)?;
+            }
         }
 
         Ok(())
     }
 }
 
+/// Start the local network of the sandboxes in the current directory, if they use one.
+///
+/// The sandboxes at `paths` that were generated for the local network are
+/// pointed at the server, in case it had to move to a new address since they
+/// were generated.
+async fn start_local_network(
+    paths: &[ConfigRootPath],
+) -> anyhow::Result<Option<LocalNetworkServer>> {
+    let hc_dir = std::env::current_dir()?;
+    let Some(local_network) = LocalNetwork::load(&hc_dir)? else {
+        return Ok(None);
+    };
+    let server = local_network.start().await?;
+    if *server.local_network() != local_network {
+        server.local_network().save(&hc_dir)?;
+    }
+    server.local_network().configure_sandboxes(&hc_dir, paths)?;
+    Ok(Some(server))
+}
+
 /// Details about a conductor launched by the sandbox
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LaunchInfo {
@@ -34,9 +34,11 @@ pub mod bundles;
 pub mod cli;
 #[doc(hidden)]
 pub mod cmds;
+pub mod local_network;
 pub mod run;
 pub mod sandbox;
 pub mod save;
+pub mod snapshot;
 pub use cli::HcSandbox;
 
 mod ports;
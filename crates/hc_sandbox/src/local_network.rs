@@ -0,0 +1,318 @@
+//! # Self-contained local network
+//!
+//! Sandboxes generated with `hc sandbox generate --local-network` use a
+//! bootstrap and relay server that `hc sandbox` runs itself, so that several
+//! conductors can find and talk to each other without any external services.
+//!
+//! The address of the server and the sandboxes that use it are saved to a
+//! `.hc_local_network` file next to the `.hc` file. The server is started
+//! whenever the sandboxes are run and is forgotten by `hc sandbox clean`. If
+//! its address has been taken in the meantime, the server picks a new one and
+//! the sandboxes that use it are pointed at it. Other sandboxes in the same
+//! directory are left alone.
+
+use crate::cmds::{Network, NetworkCmd, NetworkType};
+use holochain_conductor_api::conductor::paths::ConfigRootPath;
+use holochain_conductor_config::config::{read_config, write_config};
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::path::{Path, PathBuf};
+use url2::Url2;
+
+const LOCAL_NETWORK_FILE: &str = ".hc_local_network";
+
+/// The bootstrap and relay server of a local network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalNetwork {
+    /// The address the server listens on.
+    pub addr: SocketAddr,
+}
+
+/// The contents of the `.hc_local_network` file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LocalNetworkFile {
+    #[serde(flatten)]
+    local_network: Option<LocalNetwork>,
+
+    /// The paths of the sandboxes that use the local network.
+    #[serde(default)]
+    sandboxes: Vec<PathBuf>,
+}
+
+/// A running local network server, which is shut down when dropped.
+pub struct LocalNetworkServer {
+    server: Option<kitsune2_bootstrap_srv::BootstrapSrv>,
+    local_network: LocalNetwork,
+}
+
+impl LocalNetwork {
+    /// Start the server of a new local network on a free local port.
+    pub async fn start_new() -> anyhow::Result<LocalNetworkServer> {
+        Self::start_at(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0).into()).await
+    }
+
+    /// The URL of the bootstrap and relay server.
+    pub fn url(&self) -> Url2 {
+        Url2::parse(format!("http://{}", self.addr))
+    }
+
+    /// The network config for sandboxes that use this local network.
+    pub fn network(&self) -> NetworkCmd {
+        NetworkCmd::Network(Network {
+            transport: NetworkType::QUIC {
+                relay_url: self.url(),
+            },
+            bootstrap: Some(self.url()),
+            target_arc_factor: None,
+        })
+    }
+
+    /// Save the local network to the `.hc_local_network` file in `hc_dir`.
+    ///
+    /// The sandboxes recorded in the file keep using the local network.
+    pub fn save(&self, hc_dir: &Path) -> anyhow::Result<()> {
+        let mut file = read_file(hc_dir)?;
+        file.local_network = Some(*self);
+        write_file(hc_dir, &file)
+    }
+
+    /// Load the local network from the `.hc_local_network` file in `hc_dir`, if there is one.
+    pub fn load(hc_dir: &Path) -> anyhow::Result<Option<Self>> {
+        Ok(read_file(hc_dir)?.local_network)
+    }
+
+    /// Record that the sandboxes at `paths` use the local network of `hc_dir`.
+    pub fn add_sandboxes(hc_dir: &Path, paths: &[ConfigRootPath]) -> anyhow::Result<()> {
+        let mut file = read_file(hc_dir)?;
+        for path in paths {
+            if !file.sandboxes.iter().any(|p| p == path.as_path()) {
+                file.sandboxes.push(path.to_path_buf());
+            }
+        }
+        write_file(hc_dir, &file)
+    }
+
+    /// Remove the `.hc_local_network` file from `hc_dir`, if there is one.
+    pub fn remove(hc_dir: &Path) -> std::io::Result<()> {
+        let path = file_path(hc_dir);
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|err| {
+                std::io::Error::new(
+                    err.kind(),
+                    format!(
+                        "Failed to remove '{LOCAL_NETWORK_FILE}' at {}\nReason: {}",
+                        hc_dir.display(),
+                        err
+                    ),
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Point the network config of the sandboxes at `paths` that use the local
+    /// network of `hc_dir` to this local network.
+    ///
+    /// Sandboxes that were not generated for the local network, and configs
+    /// that already use it, are left as they are.
+    pub fn configure_sandboxes(
+        &self,
+        hc_dir: &Path,
+        paths: &[ConfigRootPath],
+    ) -> anyhow::Result<()> {
+        let sandboxes = read_file(hc_dir)?.sandboxes;
+        for path in paths
+            .iter()
+            .filter(|path| sandboxes.iter().any(|p| p == path.as_path()))
+        {
+            let mut config = read_config(path.clone())?
+                .ok_or_else(|| anyhow::anyhow!("Failed to find config at {}", path.display()))?;
+            if config.network.bootstrap_url == self.url() && config.network.relay_url == self.url()
+            {
+                continue;
+            }
+            config.network.bootstrap_url = self.url();
+            config.network.relay_url = self.url();
+            write_config(path.clone(), &config)?;
+        }
+        Ok(())
+    }
+
+    /// Start the bootstrap and relay server.
+    ///
+    /// The server listens on the address of this local network if it is still
+    /// free, and on a new free local port otherwise.
+    pub async fn start(&self) -> anyhow::Result<LocalNetworkServer> {
+        match Self::start_at(self.addr).await {
+            Ok(server) => Ok(server),
+            Err(err) => {
+                tracing::warn!(?err, "Local network address is not available");
+                Self::start_new().await
+            }
+        }
+    }
+
+    async fn start_at(addr: SocketAddr) -> anyhow::Result<LocalNetworkServer> {
+        // The bootstrap server needs a crypto provider for its relay, and the
+        // features of its dependencies don't select a default one.
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let mut config = kitsune2_bootstrap_srv::Config::testing();
+        config.listen_address_list = vec![addr];
+
+        let server =
+            tokio::task::spawn_blocking(move || kitsune2_bootstrap_srv::BootstrapSrv::new(config))
+                .await?
+                .map_err(|e| anyhow::anyhow!("Failed to start local network at {addr}: {e}"))?;
+        // The server binds the port itself, so ask it which one it got.
+        let local_network = LocalNetwork {
+            addr: *server
+                .listen_addrs()
+                .first()
+                .ok_or_else(|| anyhow::anyhow!("Local network server is not listening"))?,
+        };
+
+        msg!(
+            "Local network bootstrap and relay server running at {}",
+            local_network.url()
+        );
+
+        Ok(LocalNetworkServer {
+            server: Some(server),
+            local_network,
+        })
+    }
+}
+
+impl LocalNetworkServer {
+    /// The local network that this server is running.
+    pub fn local_network(&self) -> &LocalNetwork {
+        &self.local_network
+    }
+
+    /// Stop the server and wait for it to shut down.
+    pub async fn shutdown(mut self) {
+        if let Some(mut server) = self.server.take() {
+            match tokio::task::spawn_blocking(move || server.shutdown()).await {
+                Ok(Ok(())) => (),
+                Ok(Err(err)) => tracing::error!(?err, "Failed to shut down local network server"),
+                Err(err) => tracing::error!(?err, "Failed to shut down local network server"),
+            }
+        }
+    }
+}
+
+impl Drop for LocalNetworkServer {
+    fn drop(&mut self) {
+        if let Some(mut server) = self.server.take() {
+            if let Err(err) = server.shutdown() {
+                tracing::error!(?err, "Failed to shut down local network server");
+            }
+        }
+    }
+}
+
+fn file_path(hc_dir: &Path) -> PathBuf {
+    hc_dir.join(LOCAL_NETWORK_FILE)
+}
+
+fn read_file(hc_dir: &Path) -> anyhow::Result<LocalNetworkFile> {
+    let path = file_path(hc_dir);
+    if !path.exists() {
+        return Ok(LocalNetworkFile::default());
+    }
+    let file = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&file)
+        .map_err(|e| anyhow::anyhow!("Failed to read local network from {}: {e}", path.display()))
+}
+
+fn write_file(hc_dir: &Path, file: &LocalNetworkFile) -> anyhow::Result<()> {
+    std::fs::create_dir_all(hc_dir)?;
+    std::fs::write(file_path(hc_dir), serde_json::to_string(file)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_conductor_config::config::create_config;
+
+    #[test]
+    fn local_network_round_trips_through_file() {
+        let hc_dir = tempfile::tempdir().unwrap();
+        assert_eq!(LocalNetwork::load(hc_dir.path()).unwrap(), None);
+
+        let local_network = LocalNetwork {
+            addr: SocketAddrV4::new(Ipv4Addr::LOCALHOST, 4321).into(),
+        };
+        local_network.save(hc_dir.path()).unwrap();
+        assert_eq!(
+            LocalNetwork::load(hc_dir.path()).unwrap(),
+            Some(local_network)
+        );
+
+        LocalNetwork::remove(hc_dir.path()).unwrap();
+        assert_eq!(LocalNetwork::load(hc_dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn only_local_network_sandboxes_are_configured() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hc_dir = tmp.path().join("hc");
+        let other_url = Url2::parse("https://bootstrap.example.com");
+        let sandboxes: Vec<ConfigRootPath> = ["local", "other"]
+            .into_iter()
+            .map(|name| {
+                let path = ConfigRootPath::from(tmp.path().join(name));
+                std::fs::create_dir_all(path.as_path()).unwrap();
+                let mut config = create_config(path.clone(), None).unwrap();
+                config.network.bootstrap_url = other_url.clone();
+                config.network.relay_url = other_url.clone();
+                write_config(path.clone(), &config).unwrap();
+                path
+            })
+            .collect();
+
+        let local_network = LocalNetwork {
+            addr: SocketAddrV4::new(Ipv4Addr::LOCALHOST, 4321).into(),
+        };
+        local_network.save(&hc_dir).unwrap();
+        LocalNetwork::add_sandboxes(&hc_dir, &sandboxes[..1]).unwrap();
+        // Saving a moved local network keeps the sandboxes that use it.
+        let local_network = LocalNetwork {
+            addr: SocketAddrV4::new(Ipv4Addr::LOCALHOST, 4322).into(),
+        };
+        local_network.save(&hc_dir).unwrap();
+
+        local_network
+            .configure_sandboxes(&hc_dir, &sandboxes)
+            .unwrap();
+
+        let config = read_config(sandboxes[0].clone()).unwrap().unwrap();
+        assert_eq!(config.network.bootstrap_url, local_network.url());
+        assert_eq!(config.network.relay_url, local_network.url());
+        let config = read_config(sandboxes[1].clone()).unwrap().unwrap();
+        assert_eq!(config.network.bootstrap_url, other_url);
+        assert_eq!(config.network.relay_url, other_url);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn server_moves_to_free_port_when_address_is_taken() {
+        let server = LocalNetwork::start_new().await.unwrap();
+        let local_network = *server.local_network();
+        assert_ne!(local_network.addr.port(), 0);
+
+        // The first server still holds the address.
+        let moved = local_network.start().await.unwrap();
+        assert_ne!(moved.local_network(), &local_network);
+        assert_ne!(moved.local_network().addr.port(), 0);
+
+        moved.shutdown().await;
+        server.shutdown().await;
+
+        // Once it has been shut down, the address is free to use again.
+        let restarted = local_network.start().await.unwrap();
+        assert_eq!(restarted.local_network(), &local_network);
+        restarted.shutdown().await;
+    }
+}
@@ -55,11 +55,12 @@ pub async fn run(
     for app_port in app_ports {
         let admin_ws = AdminWebsocket::connect(format!("localhost:{admin_port}"), None).await?;
         let port = admin_ws
-            .attach_app_interface(app_port, None, AllowedOrigins::Any, None)
+            .attach_app_interface(app_port, None, AllowedOrigins::Any, None, None)
             .await?;
         launch_info.app_ports.push(port);
     }
 
+    crate::snapshot::enable_restored_apps(&sandbox_path, admin_port).await?;
     crate::save::lock_live(std::env::current_dir()?, &sandbox_path, admin_port).await?;
     msg!("Connected successfully to a running holochain");
 
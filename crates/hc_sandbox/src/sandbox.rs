@@ -69,6 +69,7 @@ async fn install_app_bundle(
         network_seed,
         ignore_genesis_failure: false,
         restore_from_dht: false,
+        wasm_metering: None,
     };
 
     let installed_app = client.install_app(payload).await?;
@@ -42,8 +42,8 @@ pub fn save(hc_dir: PathBuf, paths: Vec<ConfigRootPath>) -> std::io::Result<()>
 
 /// Remove sandbox paths from the `.hc` file and attempt to delete the sandbox folders.
 ///
-/// If no sandbox paths remain in the `.hc` file, then the `.hc`, `.hc_auth`, and all `.hc_live*`
-/// files will be removed from `hc_dir`.
+/// If no sandbox paths remain in the `.hc` file, then the `.hc`, `.hc_auth`, `.hc_local_network`
+/// and all `.hc_live*` files will be removed from `hc_dir`.
 ///
 /// Returns the number of removed paths from `.hc`.
 pub fn remove(hc_dir: PathBuf, existing: Existing) -> std::io::Result<usize> {
@@ -143,6 +143,8 @@ pub fn remove(hc_dir: PathBuf, existing: Existing) -> std::io::Result<usize> {
                 }
             }
         }
+        // Forget the local network, if the sandboxes used one
+        crate::local_network::LocalNetwork::remove(&hc_dir)?;
         // Erase .hc_auth file
         let hc_auth = hc_dir.join(".hc_auth");
         if hc_auth.exists() {
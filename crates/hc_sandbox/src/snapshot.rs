@@ -0,0 +1,481 @@
+//! # Snapshots of sandboxes
+//!
+//! A snapshot is a copy of the data roots of some sandboxes, that is their
+//! conductor config, databases and keystore, saved under `.hc_snapshots` in
+//! the current directory. Restoring a snapshot copies the sandboxes into fresh
+//! directories, so that the same snapshot can be restored any number of times.
+//!
+//! The databases of a sandbox must not change while it is copied. The apps of
+//! running sandboxes are disabled through their admin port while they are
+//! snapshotted, which stops their cells from writing, and enabled again
+//! afterwards. The conductor then checkpoints its databases, so that the data
+//! it has committed is in the database files rather than only in their
+//! write-ahead logs. Their copies store these apps as disabled, so the snapshot
+//! records them and they are enabled again the first time a restored sandbox
+//! runs.
+
+use crate::local_network::LocalNetwork;
+use holochain_client::AdminWebsocket;
+use holochain_conductor_api::conductor::paths::ConfigRootPath;
+use holochain_conductor_api::conductor::KeystoreConfig;
+use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_config::config::{read_config, write_config};
+use holochain_conductor_config::ports::set_admin_port;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use url2::Url2;
+
+const SNAPSHOTS_DIRECTORY: &str = ".hc_snapshots";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LAIR_CONFIG_FILE: &str = "lair-keystore-config.yaml";
+const APPS_TO_ENABLE_FILE: &str = ".hc_apps_to_enable.json";
+
+/// The contents of a snapshot.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct SnapshotManifest {
+    /// The paths the sandboxes had when they were snapshotted, in order.
+    ///
+    /// Sandbox `i` is saved in the directory named `i` in the snapshot.
+    sandboxes: Vec<PathBuf>,
+
+    /// The local network the sandboxes used, if any.
+    #[serde(default)]
+    local_network: Option<LocalNetwork>,
+
+    /// The apps that were disabled while each sandbox was snapshotted, in the
+    /// order of `sandboxes`.
+    #[serde(default)]
+    paused_apps: Vec<Vec<String>>,
+}
+
+/// Snapshot the sandboxes at `paths` under `name`.
+///
+/// The apps of sandboxes that are running are disabled while the snapshot is
+/// taken and enabled again afterwards, and their databases are checkpointed
+/// before they are copied.
+///
+/// Fails if a snapshot with this name already exists.
+pub async fn snapshot(hc_dir: &Path, name: &str, paths: Vec<PathBuf>) -> anyhow::Result<PathBuf> {
+    let snapshot_dir = snapshot_path(hc_dir, name)?;
+    if snapshot_dir.exists() {
+        anyhow::bail!("A snapshot named '{name}' already exists");
+    }
+
+    let live_ports = crate::save::find_ports(hc_dir.to_path_buf(), &paths)?;
+    let mut paused = Vec::new();
+    let mut result = Ok(());
+    for (i, path, port) in paths
+        .iter()
+        .zip(live_ports)
+        .enumerate()
+        .filter_map(|(i, (path, port))| port.map(|port| (i, path, port)))
+    {
+        msg!(
+            "Disabling the apps of sandbox {} while it is snapshotted",
+            path.display()
+        );
+        match PausedSandbox::pause(port).await {
+            Ok(sandbox) => paused.push((i, sandbox)),
+            Err(err) => {
+                result = Err(err);
+                break;
+            }
+        }
+    }
+
+    let result = match result {
+        Ok(()) => {
+            let mut paused_apps = vec![Vec::new(); paths.len()];
+            for (i, sandbox) in &paused {
+                paused_apps[*i] = sandbox.app_ids.clone();
+            }
+            copy_sandboxes(hc_dir, &snapshot_dir, paths, paused_apps)
+        }
+        Err(err) => Err(err),
+    };
+
+    let mut resumed = Ok(());
+    for (_, sandbox) in paused {
+        let result = sandbox.resume().await;
+        if resumed.is_ok() {
+            resumed = result;
+        }
+    }
+
+    result?;
+    resumed?;
+    Ok(snapshot_dir)
+}
+
+fn copy_sandboxes(
+    hc_dir: &Path,
+    snapshot_dir: &Path,
+    paths: Vec<PathBuf>,
+    paused_apps: Vec<Vec<String>>,
+) -> anyhow::Result<()> {
+    std::fs::create_dir_all(snapshot_dir)?;
+
+    for (i, path) in paths.iter().enumerate() {
+        copy_dir(path, &snapshot_dir.join(i.to_string()))?;
+    }
+
+    let manifest = SnapshotManifest {
+        sandboxes: paths,
+        local_network: LocalNetwork::load(hc_dir)?,
+        paused_apps,
+    };
+    std::fs::write(
+        snapshot_dir.join(SNAPSHOT_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    Ok(())
+}
+
+/// A running sandbox whose enabled apps have been disabled and whose
+/// databases have been checkpointed.
+struct PausedSandbox {
+    admin_port: u16,
+    client: AdminWebsocket,
+    app_ids: Vec<String>,
+}
+
+impl PausedSandbox {
+    /// Disable the enabled apps of the sandbox running on `admin_port`, then
+    /// checkpoint its databases.
+    async fn pause(admin_port: u16) -> anyhow::Result<Self> {
+        let client = AdminWebsocket::connect(format!("localhost:{admin_port}"), None).await?;
+        let apps = client.list_apps(Some(AppStatusFilter::Enabled)).await?;
+        let mut sandbox = Self {
+            admin_port,
+            client,
+            app_ids: Vec::with_capacity(apps.len()),
+        };
+        for app in apps {
+            if let Err(err) = sandbox
+                .client
+                .disable_app(app.installed_app_id.clone())
+                .await
+            {
+                sandbox.resume().await?;
+                anyhow::bail!(
+                    "Failed to disable app '{}' on admin port {admin_port}: {err}",
+                    app.installed_app_id
+                );
+            }
+            sandbox.app_ids.push(app.installed_app_id);
+        }
+        if let Err(err) = sandbox.client.checkpoint_databases().await {
+            sandbox.resume().await?;
+            anyhow::bail!("Failed to checkpoint the databases on admin port {admin_port}: {err}");
+        }
+        Ok(sandbox)
+    }
+
+    /// Enable the apps that were disabled again.
+    async fn resume(self) -> anyhow::Result<()> {
+        for app_id in self.app_ids {
+            self.client
+                .enable_app(app_id.clone())
+                .await
+                .map_err(|err| {
+                    anyhow::anyhow!(
+                        "Failed to enable app '{app_id}' on admin port {} again: {err}",
+                        self.admin_port
+                    )
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// Restore the sandboxes of the snapshot `name` into new directories under
+/// `root`, which defaults to the system's temp directory.
+///
+/// The admin port of each restored sandbox is reset so that a free one is
+/// chosen when it runs. The apps that were disabled while the snapshot was
+/// taken are enabled by [`enable_restored_apps`] when it runs. If the
+/// sandboxes used a local network, they use the local network of `hc_dir`,
+/// which is set up from the snapshot if there is none.
+pub fn restore(
+    hc_dir: &Path,
+    name: &str,
+    root: Option<PathBuf>,
+) -> anyhow::Result<Vec<ConfigRootPath>> {
+    let snapshot_dir = snapshot_path(hc_dir, name)?;
+    let manifest = std::fs::read_to_string(snapshot_dir.join(SNAPSHOT_FILE))
+        .map_err(|e| anyhow::anyhow!("Failed to read snapshot '{name}': {e}"))?;
+    let manifest: SnapshotManifest = serde_json::from_str(&manifest)?;
+
+    let mut paths = Vec::with_capacity(manifest.sandboxes.len());
+    for (i, original_path) in manifest.sandboxes.iter().enumerate() {
+        let path =
+            holochain_conductor_config::generate::generate_config_directory(root.clone(), None)?;
+        copy_dir(&snapshot_dir.join(i.to_string()), path.as_path())?;
+        relocate(original_path, &path)?;
+        if let Some(app_ids) = manifest.paused_apps.get(i).filter(|ids| !ids.is_empty()) {
+            std::fs::write(
+                path.join(APPS_TO_ENABLE_FILE),
+                serde_json::to_string(app_ids)?,
+            )?;
+        }
+        paths.push(path);
+    }
+
+    if let Some(local_network) = manifest.local_network {
+        if LocalNetwork::load(hc_dir)?.is_none() {
+            local_network.save(hc_dir)?;
+        }
+        LocalNetwork::add_sandboxes(hc_dir, &paths)?;
+    }
+
+    Ok(paths)
+}
+
+/// Enable the apps of a restored sandbox that were disabled while it was
+/// snapshotted, now that it runs on `admin_port`.
+///
+/// Does nothing for sandboxes that have no such apps or whose apps have
+/// already been enabled.
+pub(crate) async fn enable_restored_apps(
+    sandbox_path: &Path,
+    admin_port: u16,
+) -> anyhow::Result<()> {
+    let apps_file = sandbox_path.join(APPS_TO_ENABLE_FILE);
+    if !apps_file.exists() {
+        return Ok(());
+    }
+    let app_ids: Vec<String> = serde_json::from_str(&std::fs::read_to_string(&apps_file)?)?;
+    let client = AdminWebsocket::connect(format!("localhost:{admin_port}"), None).await?;
+    for app_id in app_ids {
+        client
+            .enable_app(app_id.clone())
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed to enable restored app '{app_id}': {err}"))?;
+    }
+    std::fs::remove_file(apps_file)?;
+    Ok(())
+}
+
+/// List the names of the snapshots in `hc_dir`.
+pub fn list(hc_dir: &Path) -> std::io::Result<Vec<String>> {
+    let snapshots_dir = hc_dir.join(SNAPSHOTS_DIRECTORY);
+    if !snapshots_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(snapshots_dir)? {
+        let entry = entry?;
+        if entry.path().join(SNAPSHOT_FILE).exists() {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+fn snapshot_path(hc_dir: &Path, name: &str) -> anyhow::Result<PathBuf> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        anyhow::bail!("Invalid snapshot name '{name}'");
+    }
+    Ok(hc_dir.join(SNAPSHOTS_DIRECTORY).join(name))
+}
+
+/// Recursively copy the files of `from` to `to`.
+///
+/// Sockets and other special files, such as those a keystore leaves behind,
+/// are skipped.
+fn copy_dir(from: &Path, to: &Path) -> anyhow::Result<()> {
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry?;
+        let target = to.join(entry.path().strip_prefix(from)?);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if file_type.is_file() {
+            std::fs::copy(entry.path(), &target)
+                .map_err(|e| anyhow::anyhow!("Failed to copy {}: {e}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Point the config and keystore config of a sandbox copied from
+/// `original_path` at its new location, and reset its admin port.
+///
+/// Only the fields that hold paths into the sandbox are changed.
+fn relocate(original_path: &Path, path: &ConfigRootPath) -> anyhow::Result<()> {
+    let new_path = path.as_path();
+
+    let lair_config = walkdir::WalkDir::new(new_path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .find(|entry| entry.file_name() == LAIR_CONFIG_FILE);
+    if let Some(lair_config) = lair_config {
+        relocate_lair_config(lair_config.path(), original_path, new_path)?;
+    }
+
+    let mut config = read_config(path.clone())?
+        .ok_or_else(|| anyhow::anyhow!("Restored sandbox {} has no config", new_path.display()))?;
+    if let Some(data_root_path) = config
+        .data_root_path
+        .as_ref()
+        .and_then(|data_root_path| relocate_path(data_root_path, original_path, new_path))
+    {
+        config.data_root_path = Some(data_root_path.into());
+    }
+    match &mut config.keystore {
+        KeystoreConfig::LairServerInProc {
+            lair_root: Some(lair_root),
+        } => {
+            if let Some(new_root) = relocate_path(lair_root, original_path, new_path) {
+                *lair_root = new_root.into();
+            }
+        }
+        KeystoreConfig::LairServer { connection_url } => {
+            if let Some(url) = relocate_url(connection_url, original_path, new_path) {
+                *connection_url = url;
+            }
+        }
+        _ => (),
+    }
+    set_admin_port(&mut config, 0);
+    write_config(path.clone(), &config)?;
+
+    Ok(())
+}
+
+/// Point the paths in a lair keystore config at the new location of the
+/// sandbox it belongs to.
+fn relocate_lair_config(
+    lair_config: &Path,
+    original_path: &Path,
+    new_path: &Path,
+) -> anyhow::Result<()> {
+    let mut config: yaml_serde::Value =
+        yaml_serde::from_str(&std::fs::read_to_string(lair_config)?)?;
+    for key in ["pidFile", "storeFile"] {
+        if let Some(value) = config.get_mut(key) {
+            if let Some(path) = value
+                .as_str()
+                .and_then(|path| relocate_path(Path::new(path), original_path, new_path))
+            {
+                *value = path.to_string_lossy().into_owned().into();
+            }
+        }
+    }
+    if let Some(value) = config.get_mut("connectionUrl") {
+        if let Some(url) = value
+            .as_str()
+            .and_then(|url| Url2::try_parse(url).ok())
+            .and_then(|url| relocate_url(&url, original_path, new_path))
+        {
+            *value = url.to_string().into();
+        }
+    }
+    std::fs::write(lair_config, yaml_serde::to_string(&config)?)?;
+    Ok(())
+}
+
+/// The path at `new_path` that corresponds to `path` under `original_path`,
+/// if `path` is under `original_path`.
+fn relocate_path(path: &Path, original_path: &Path, new_path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(original_path).ok()?;
+    if relative.as_os_str().is_empty() {
+        Some(new_path.to_path_buf())
+    } else {
+        Some(new_path.join(relative))
+    }
+}
+
+/// The URL of a socket under `original_path` moved to `new_path`, if the URL
+/// points to one.
+fn relocate_url(url: &Url2, original_path: &Path, new_path: &Path) -> Option<Url2> {
+    let path = relocate_path(Path::new(url.path()), original_path, new_path)?;
+    let mut url = url.clone();
+    url.set_path(path.to_str()?);
+    Some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_conductor_api::InterfaceDriver;
+    use holochain_conductor_config::config::create_config;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn restore_relocates_snapshotted_sandbox() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hc_dir = tmp.path().join("hc");
+        let sandbox = ConfigRootPath::from(tmp.path().join("sandbox"));
+        std::fs::create_dir_all(sandbox.as_path()).unwrap();
+        let mut config = create_config(sandbox.clone(), None).unwrap();
+        set_admin_port(&mut config, 9000);
+        // Text that happens to contain the sandbox path is not a path to relocate.
+        let unrelated = format!("scope-for-{}", sandbox.display());
+        config.tracing_scope = Some(unrelated.clone());
+        write_config(sandbox.clone(), &config).unwrap();
+        let keystore_dir = sandbox.join("ks");
+        std::fs::write(
+            keystore_dir.join(LAIR_CONFIG_FILE),
+            format!(
+                "connectionUrl: unix://{0}/socket?k=key\npidFile: {0}/pid_file\nstoreFile: {0}/store_file\nsignatureFallback: none\n",
+                keystore_dir.display()
+            ),
+        )
+        .unwrap();
+        std::fs::create_dir_all(sandbox.join("databases")).unwrap();
+        std::fs::write(sandbox.join("databases").join("db"), b"data").unwrap();
+
+        snapshot(&hc_dir, "bug", vec![sandbox.to_path_buf()])
+            .await
+            .unwrap();
+        assert_eq!(list(&hc_dir).unwrap(), vec!["bug".to_string()]);
+        assert!(snapshot(&hc_dir, "bug", vec![sandbox.to_path_buf()])
+            .await
+            .is_err());
+
+        let restored = restore(&hc_dir, "bug", Some(tmp.path().to_path_buf())).unwrap();
+        assert_eq!(restored.len(), 1);
+        let restored = restored.into_iter().next().unwrap();
+        assert_ne!(restored, sandbox);
+        assert_eq!(
+            std::fs::read(restored.join("databases").join("db")).unwrap(),
+            b"data"
+        );
+
+        let config = read_config(restored.clone()).unwrap().unwrap();
+        assert_eq!(config.data_root_path.unwrap().as_path(), restored.as_path());
+        assert_eq!(config.tracing_scope, Some(unrelated));
+        // The sandbox wasn't running, so no apps were disabled.
+        assert!(!restored.join(APPS_TO_ENABLE_FILE).exists());
+        assert!(matches!(
+            config.keystore,
+            KeystoreConfig::LairServerInProc { lair_root: Some(lair_root) }
+                if lair_root.as_path() == restored.join("ks")
+        ));
+
+        let restored_keystore_dir = restored.join("ks");
+        let lair_config: yaml_serde::Value = yaml_serde::from_str(
+            &std::fs::read_to_string(restored_keystore_dir.join(LAIR_CONFIG_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            lair_config["connectionUrl"].as_str().unwrap(),
+            format!("unix://{}/socket?k=key", restored_keystore_dir.display())
+        );
+        assert_eq!(
+            lair_config["pidFile"].as_str().unwrap(),
+            restored_keystore_dir.join("pid_file").to_str().unwrap()
+        );
+        assert_eq!(
+            lair_config["storeFile"].as_str().unwrap(),
+            restored_keystore_dir.join("store_file").to_str().unwrap()
+        );
+        assert_eq!(lair_config["signatureFallback"].as_str().unwrap(), "none");
+        assert!(matches!(
+            config.admin_interfaces.unwrap()[0].driver,
+            InterfaceDriver::Websocket { port: 0, .. }
+        ));
+    }
+}
@@ -682,6 +682,130 @@ async fn generate_sandbox_and_get_admin_ports() {
     shutdown_sandbox(hc_generate).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn generate_local_network_conflicts_with_num_sandboxes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+
+    let output = get_sandbox_command()
+        .arg("generate")
+        .arg("--local-network=2")
+        .arg("--num-sandboxes=3")
+        .current_dir(temp_dir.path())
+        .output()
+        .await
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot be used with"),
+        "unexpected error: {stderr}"
+    );
+    // Nothing was generated.
+    assert!(!temp_dir.path().join(".hc").exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn snapshot_running_sandbox() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    package_fixture_if_not_packaged().await;
+    let app_path = std::env::current_dir()
+        .unwrap()
+        .join("tests/fixtures/my-app/");
+
+    holochain_trace::test_run();
+
+    let mut cmd = get_sandbox_command();
+    cmd.env("RUST_BACKTRACE", "1")
+        .arg(format!(
+            "--holochain-path={}",
+            get_holochain_bin_path().to_str().unwrap()
+        ))
+        .arg("--piped")
+        .arg("generate")
+        .arg("--in-process-lair")
+        .arg("--run=0")
+        .arg(app_path)
+        .current_dir(temp_dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true);
+
+    let mut hc_generate = input_piped_password(&mut cmd).await;
+    let launch_info = get_launch_info(&mut hc_generate).await;
+
+    let mut snapshot_cmd = get_sandbox_command();
+    snapshot_cmd
+        .env("RUST_BACKTRACE", "1")
+        .arg("snapshot")
+        .arg("running")
+        .arg("--all")
+        .current_dir(temp_dir.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+
+    let output = snapshot_cmd.output().await.unwrap();
+    assert!(output.status.success(), "snapshot command failed");
+    assert!(temp_dir
+        .path()
+        .join(".hc_snapshots/running/snapshot.json")
+        .exists());
+
+    // The apps that were disabled for the snapshot are enabled again
+    let admin_client = admin_client_from_launch(&launch_info).await;
+    let apps = admin_client.list_apps(None).await.unwrap();
+    assert!(!apps.is_empty());
+    let enabled = admin_client
+        .list_apps(Some(AppStatusFilter::Enabled))
+        .await
+        .unwrap();
+    assert_eq!(enabled.len(), apps.len());
+
+    shutdown_sandbox(hc_generate).await;
+
+    let mut restore_cmd = get_sandbox_command();
+    restore_cmd
+        .env("RUST_BACKTRACE", "1")
+        .arg("restore")
+        .arg("running")
+        .arg(format!("--root={}", temp_dir.path().display()))
+        .current_dir(temp_dir.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+
+    let output = restore_cmd.output().await.unwrap();
+    assert!(output.status.success(), "restore command failed");
+
+    // The restored sandbox runs with the apps that were disabled for the
+    // snapshot enabled again
+    let mut cmd = get_sandbox_command();
+    cmd.env("RUST_BACKTRACE", "1")
+        .arg("--piped")
+        .arg(format!(
+            "--holochain-path={}",
+            get_holochain_bin_path().to_str().unwrap()
+        ))
+        .arg("run")
+        .arg("1")
+        .current_dir(temp_dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true);
+
+    let mut hc_restored = input_piped_password(&mut cmd).await;
+    let launch_info = get_launch_info(&mut hc_restored).await;
+    let admin_client = admin_client_from_launch(&launch_info).await;
+    let enabled = admin_client
+        .list_apps(Some(AppStatusFilter::Enabled))
+        .await
+        .unwrap();
+    assert_eq!(enabled.len(), apps.len());
+
+    shutdown_sandbox(hc_restored).await;
+}
+
 include!(concat!(env!("OUT_DIR"), "/target.rs"));
 
 fn get_target(file: &str) -> std::path::PathBuf {
@@ -293,6 +293,25 @@ pub fn get_details<H: Into<AnyDhtHash>>(
         .unwrap())
 }
 
+/// Get the hashes of the actions whose validity the authorities of aggregated reads in this zome
+/// call disagreed on, see [`GetOptions::with_as_race`].
+///
+/// Reads that aggregate the responses of several authorities still return the merged data when
+/// they disagree, unless [`GetOptions::with_fail_on_disagreement`] is set. This lists the disputed
+/// actions of all the [`get`], [`get_details`], `get_links` and `get_agent_activity` calls made so
+/// far in the current zome call, so that the zome can decide how far to trust the data.
+///
+/// ### Usage
+/// ```ignore
+/// let record = get(action_hash.clone(), GetOptions::network().with_as_race(false))?;
+/// if get_authority_disagreements()?.contains(&action_hash) {
+///     // Some authorities hold the action as invalid.
+/// }
+/// ```
+pub fn get_authority_disagreements() -> ExternResult<Vec<ActionHash>> {
+    HDK.with(|h| h.borrow().get_authority_disagreements(()))
+}
+
 /// Implements a whole lot of sane defaults for a struct or enum that should behave as an entry.
 /// All the entry def fields are available as dedicated methods on the type and matching From impls
 /// are provided for each. This allows for both Foo::entry_types() and EntryDef::from(Foo::new())
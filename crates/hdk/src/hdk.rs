@@ -60,6 +60,7 @@ pub trait HdkT: HdiT {
     fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
     fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+    fn send_remote_signal_durable(&self, input: DurableRemoteSignal) -> ExternResult<()>;
     // Random
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
     // Time
@@ -105,6 +106,10 @@ pub trait HdkT: HdiT {
         &self,
         input: GetValidationReceiptsInput,
     ) -> ExternResult<Vec<ValidationReceiptSet>>;
+    // Agent presence
+    fn get_agent_presence(&self, input: GetAgentPresenceInput) -> ExternResult<Vec<AgentPresence>>;
+    // Authority disagreements
+    fn get_authority_disagreements(&self, input: ()) -> ExternResult<Vec<ActionHash>>;
 }
 
 #[cfg(feature = "mock")]
@@ -149,6 +154,7 @@ mockall::mock! {
         fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
         fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
         fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+        fn send_remote_signal_durable(&self, input: DurableRemoteSignal) -> ExternResult<()>;
         // Random
         fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
         // Time
@@ -188,6 +194,8 @@ mockall::mock! {
         fn open_chain(&self, input: OpenChainInput) -> ExternResult<ActionHash>;
         fn get_init_properties(&self, input: ()) -> ExternResult<Option<InitProperties>>;
         fn get_validation_receipts(&self, input: GetValidationReceiptsInput) -> ExternResult<Vec<ValidationReceiptSet>>;
+        fn get_agent_presence(&self, input: GetAgentPresenceInput) -> ExternResult<Vec<AgentPresence>>;
+        fn get_authority_disagreements(&self, input: ()) -> ExternResult<Vec<ActionHash>>;
     }
 
     impl HdiT for HdkT {
@@ -379,6 +387,9 @@ impl HdkT for ErrHdk {
     fn send_remote_signal(&self, _: RemoteSignal) -> ExternResult<()> {
         Self::err()
     }
+    fn send_remote_signal_durable(&self, _: DurableRemoteSignal) -> ExternResult<()> {
+        Self::err()
+    }
     // Random
     fn random_bytes(&self, _: u32) -> ExternResult<Bytes> {
         Self::err()
@@ -478,6 +489,19 @@ impl HdkT for ErrHdk {
     ) -> ExternResult<Vec<ValidationReceiptSet>> {
         Self::err()
     }
+
+    // Agent presence
+    fn get_agent_presence(
+        &self,
+        _input: GetAgentPresenceInput,
+    ) -> ExternResult<Vec<AgentPresence>> {
+        Self::err()
+    }
+
+    // Authority disagreements
+    fn get_authority_disagreements(&self, _input: ()) -> ExternResult<Vec<ActionHash>> {
+        Self::err()
+    }
 }
 
 /// The HDK implemented as externs provided by the host.
@@ -627,6 +651,9 @@ impl HdkT for HostHdk {
     fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()> {
         host_call::<RemoteSignal, ()>(__hc__send_remote_signal_1, remote_signal)
     }
+    fn send_remote_signal_durable(&self, input: DurableRemoteSignal) -> ExternResult<()> {
+        host_call::<DurableRemoteSignal, ()>(__hc__send_remote_signal_durable_1, input)
+    }
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
         host_call::<u32, Bytes>(__hc__random_bytes_1, number_of_bytes)
     }
@@ -738,6 +765,14 @@ impl HdkT for HostHdk {
             input,
         )
     }
+
+    fn get_agent_presence(&self, input: GetAgentPresenceInput) -> ExternResult<Vec<AgentPresence>> {
+        host_call::<GetAgentPresenceInput, Vec<AgentPresence>>(__hc__get_agent_presence_1, input)
+    }
+
+    fn get_authority_disagreements(&self, input: ()) -> ExternResult<Vec<ActionHash>> {
+        host_call::<(), Vec<ActionHash>>(__hc__get_authority_disagreements_1, input)
+    }
 }
 
 /// At any time the global HDK can be set to a different hdk.
@@ -156,3 +156,60 @@ where
         })
     })
 }
+
+/// ## Durable Remote Signal
+/// Send a signal to a list of other agents, like [`send_remote_signal`], but
+/// without losing it if an agent can't be reached.
+///
+/// Signals that can't be sent to an agent, for example because the agent is
+/// offline, are kept in the outbox of this conductor. They are retried with
+/// backoff whenever the agent is seen online again, until `ttl` has passed.
+///
+/// The outcome is reported to the sending agent's app interfaces as system
+/// signals. A signal that was put in the outbox is reported as
+/// `RemoteSignalQueued`, and then as `RemoteSignalDelivered` once it was sent or
+/// `RemoteSignalExpired` if it could not be sent before the `ttl` passed.
+pub fn send_remote_signal_durable<I>(
+    input: I,
+    agents: Vec<AgentPubKey>,
+    ttl: std::time::Duration,
+) -> ExternResult<()>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    HDK.with(|h| {
+        h.borrow().send_remote_signal_durable(DurableRemoteSignal {
+            remote_signal: RemoteSignal {
+                signal: ExternIO::encode(input).map_err(|e| wasm_error!(e))?,
+                agents,
+            },
+            ttl,
+        })
+    })
+}
+
+/// ## Agent Presence
+/// Get what this conductor knows about the reachability of a list of agents.
+///
+/// This is read from the local peer store and does not contact the agents, so
+/// it is cheap to call before choosing between [`call_remote`] and a fallback
+/// that goes through the DHT. The information may be out of date, a recently
+/// online agent can still be unreachable when it is called.
+///
+/// One [`AgentPresence`] is returned for each agent, in the order of `agents`.
+///
+/// ### Usage
+/// ```ignore
+/// let presence = get_agent_presence(vec![bob.clone()])?;
+/// if presence[0].recently_online {
+///     call_remote(bob, "foo_zome", "do_it".into(), None, payload)?;
+/// } else {
+///     create_link(bob, action_hash, LinkTypes::Inbox, ())?;
+/// }
+/// ```
+pub fn get_agent_presence(agents: Vec<AgentPubKey>) -> ExternResult<Vec<AgentPresence>> {
+    HDK.with(|h| {
+        h.borrow()
+            .get_agent_presence(GetAgentPresenceInput::new(agents))
+    })
+}
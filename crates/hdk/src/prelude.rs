@@ -22,6 +22,7 @@ pub use crate::entry::create_entry;
 pub use crate::entry::delete;
 pub use crate::entry::delete_entry;
 pub use crate::entry::get;
+pub use crate::entry::get_authority_disagreements;
 pub use crate::entry::get_details;
 pub use crate::entry::must_get_action;
 pub use crate::entry::must_get_entry;
@@ -57,7 +58,9 @@ pub use crate::migrate::*;
 pub use crate::p2p::call;
 pub use crate::p2p::call_remote;
 pub use crate::p2p::emit_signal;
+pub use crate::p2p::get_agent_presence;
 pub use crate::p2p::send_remote_signal;
+pub use crate::p2p::send_remote_signal_durable;
 pub use crate::random::*;
 pub use crate::time::schedule;
 pub use crate::time::sys_time;
@@ -150,6 +153,7 @@ macro_rules! holochain_externs {
             create:1,
             emit_signal:1,
             send_remote_signal:1,
+            send_remote_signal_durable:1,
             create_link:1,
             delete_link:1,
             update:1,
@@ -172,6 +176,8 @@ macro_rules! holochain_externs {
             open_chain:1,
             get_init_properties:1,
             get_validation_receipts:1,
+            get_agent_presence:1,
+            get_authority_disagreements:1,
             schedule:1
         );
 
@@ -80,6 +80,7 @@ pub fn wasm_call_n(c: &mut Criterion) {
                     provenance: AGENT_KEY.lock().unwrap().clone(),
                     expires_at: Timestamp::now(),
                     nonce: [0; 32].into(),
+                    points_consumed: Default::default(),
                 });
 
                 let ribosome = REAL_RIBOSOME.lock().unwrap().clone();
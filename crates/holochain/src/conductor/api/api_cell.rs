@@ -195,6 +195,15 @@ pub trait CellConductorReadHandleT: Send + Sync {
     /// Expose delete_clone_cell functionality to zomes.
     async fn delete_clone_cell(&self, payload: DeleteCloneCellPayload) -> ConductorResult<()>;
 
+    /// Put a remote signal from this cell in the outbox, for the agents it could
+    /// not be sent to.
+    async fn queue_outbox_signals(
+        &self,
+        to_agents: Vec<AgentPubKey>,
+        payload: OutboxSignalPayload,
+        ttl: std::time::Duration,
+    ) -> ConductorResult<()>;
+
     /// Accept a countersigning session.
     #[cfg(feature = "unstable-countersigning")]
     async fn accept_countersigning_session(
@@ -325,6 +334,23 @@ impl CellConductorReadHandleT for CellConductorApi {
             .await
     }
 
+    async fn queue_outbox_signals(
+        &self,
+        to_agents: Vec<AgentPubKey>,
+        payload: OutboxSignalPayload,
+        ttl: std::time::Duration,
+    ) -> ConductorResult<()> {
+        self.conductor_handle
+            .queue_outbox_signals(
+                self.cell_id.dna_hash().clone(),
+                self.cell_id.agent_pubkey().clone(),
+                to_agents,
+                payload,
+                ttl,
+            )
+            .await
+    }
+
     #[cfg(feature = "unstable-countersigning")]
     async fn accept_countersigning_session(
         &self,
@@ -79,7 +79,11 @@ impl AdminInterfaceApi {
                 Ok(AdminResponse::DnaDefinitionReturned(dna_def.content))
             }
             UpdateCoordinators(payload) => {
-                let UpdateCoordinatorsPayload { cell_id, source } = *payload;
+                let UpdateCoordinatorsPayload {
+                    cell_id,
+                    source,
+                    retry_abandoned_ops,
+                } = *payload;
                 let (coordinator_zomes, wasms) = match source {
                     CoordinatorSource::Path(ref path) => {
                         let bundle = FileSystemBundler::load_from::<CoordinatorManifest>(path)
@@ -91,9 +95,25 @@ impl AdminInterfaceApi {
                 };
 
                 self.conductor_handle
-                    .update_coordinators(cell_id, coordinator_zomes, wasms)
+                    .update_coordinators(cell_id.clone(), coordinator_zomes, wasms)
                     .await?;
 
+                // The coordinators are updated by now, so a failed retry must not
+                // fail the request and get the update applied again.
+                if retry_abandoned_ops {
+                    if let Err(e) = self
+                        .conductor_handle
+                        .retry_abandoned_ops(cell_id.dna_hash(), AbandonedOpsFilter::default())
+                        .await
+                    {
+                        warn!(
+                            ?e,
+                            ?cell_id,
+                            "Failed to retry abandoned ops after updating coordinators"
+                        );
+                    }
+                }
+
                 Ok(AdminResponse::CoordinatorsUpdated)
             }
             InstallApp(payload) => {
@@ -170,6 +190,7 @@ impl AdminInterfaceApi {
                 danger_bind_addr,
                 allowed_origins,
                 installed_app_id,
+                zome_call_limits,
             } => {
                 let port = port.unwrap_or(0);
                 let port = self
@@ -180,6 +201,7 @@ impl AdminInterfaceApi {
                         danger_bind_addr,
                         allowed_origins,
                         installed_app_id,
+                        zome_call_limits,
                     )
                     .await?;
                 Ok(AdminResponse::AppInterfaceAttached { port })
@@ -231,6 +253,42 @@ impl AdminInterfaceApi {
                 let stats = self.conductor_handle.dump_network_stats().await?;
                 Ok(AdminResponse::NetworkStatsDumped(stats))
             }
+            DumpPeerLatency => {
+                let latency = self.conductor_handle.dump_peer_latency().await?;
+                Ok(AdminResponse::PeerLatencyDumped(latency))
+            }
+            SetBandwidthLimits {
+                installed_app_id,
+                limits,
+            } => {
+                self.conductor_handle
+                    .set_bandwidth_limits(&installed_app_id, limits)
+                    .await?;
+                Ok(AdminResponse::BandwidthLimitsSet)
+            }
+            ListBandwidthLimits => {
+                let limits = self.conductor_handle.list_bandwidth_limits().await?;
+                Ok(AdminResponse::BandwidthLimitsListed(limits))
+            }
+            ListSignalOutbox { dna_hash } => {
+                let outbox = self.conductor_handle.list_signal_outbox(dna_hash).await?;
+                Ok(AdminResponse::SignalOutboxListed(outbox))
+            }
+            DumpWorkflowState { dna_hash } => {
+                let dump = self.conductor_handle.dump_workflow_state(&dna_hash).await?;
+                Ok(AdminResponse::WorkflowStateDumped(Box::new(dump)))
+            }
+            RetryAbandonedOps { dna_hash, filter } => {
+                let retried = self
+                    .conductor_handle
+                    .retry_abandoned_ops(&dna_hash, filter)
+                    .await?;
+                Ok(AdminResponse::AbandonedOpsRetried(retried))
+            }
+            TraceOp { dna_hash, op_hash } => {
+                let trace = self.conductor_handle.trace_op(&dna_hash, &op_hash).await?;
+                Ok(AdminResponse::OpTraced(Box::new(trace)))
+            }
             AddAgentInfo { agent_infos } => {
                 self.conductor_handle.add_agent_infos(agent_infos).await?;
                 Ok(AdminResponse::AgentInfoAdded)
@@ -288,6 +346,10 @@ impl AdminInterfaceApi {
             StorageInfo => Ok(AdminResponse::StorageInfo(
                 self.conductor_handle.storage_info().await?,
             )),
+            CheckpointDatabases => {
+                self.conductor_handle.checkpoint_databases().await?;
+                Ok(AdminResponse::DatabasesCheckpointed)
+            }
             IssueAppAuthenticationToken(payload) => {
                 Ok(AdminResponse::AppAuthenticationTokenIssued(
                     self.conductor_handle
@@ -305,6 +367,14 @@ impl AdminInterfaceApi {
                     .cells_by_dna_lineage(&dna_hash)
                     .await?,
             )),
+            #[cfg(feature = "unstable-sharding")]
+            SetArcPolicy { target, policy } => Ok(AdminResponse::ArcPolicySet(
+                self.conductor_handle.set_arc_policy(target, policy).await?,
+            )),
+            #[cfg(feature = "unstable-sharding")]
+            ListArcPolicies => Ok(AdminResponse::ArcPoliciesListed(
+                self.conductor_handle.list_arc_policies().await?,
+            )),
         }
     }
 }
@@ -93,29 +93,12 @@ impl AppInterfaceApi {
                 Ok(AppResponse::PeerMetaInfo(r))
             }
             AppRequest::CallZome(zome_call_params_signed) => {
-                match self.conductor_handle.handle_external_zome_call(*zome_call_params_signed).await? {
-                    Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCalled(Box::new(output))),
-                    Ok(ZomeCallResponse::AuthenticationFailed(signature, provenance)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::ZomeCallAuthenticationFailed(format!(
-                            "Authentication failure. Bad signature {signature:?} by provenance {provenance:?}.",
-                        )),
-                    )),
-                    Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, cap_secret, zome_name, fn_name)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::ZomeCallUnauthorized(format!(
-                            "Call was not authorized with reason {zome_call_authorization:?}, cap secret {cap_secret:?} to call the function {fn_name} in zome {zome_name}"
-                        )),
-                    )),
-                    Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
-                        "Interface zome calls should never be routed to the network. This is a bug. Got {}",
-                        e
-                    ),
-                    Ok(ZomeCallResponse::CountersigningSession(e)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::CountersigningSessionError(format!(
-                            "A countersigning session has failed to start on this zome call because: {e}"
-                        )),
-                    )),
-                    Err(e) => Ok(AppResponse::Error(e.into())),
-                }
+                self.call_zome(&installed_app_id, *zome_call_params_signed, false)
+                    .await
+            }
+            AppRequest::CallZomeMetered(zome_call_params_signed) => {
+                self.call_zome(&installed_app_id, *zome_call_params_signed, true)
+                    .await
             }
             #[cfg(feature = "unstable-countersigning")]
             AppRequest::GetCountersigningSessionState(payload) => {
@@ -225,16 +208,77 @@ impl AppInterfaceApi {
                 dna_hash,
                 agents,
                 signal,
+                durable_ttl_s,
+                acknowledged,
             } => {
+                let durable_ttl = durable_ttl_s.map(std::time::Duration::from_secs);
+                if acknowledged {
+                    let acks = self
+                        .conductor_handle
+                        .clone()
+                        .send_direct_signal_acked(
+                            installed_app_id,
+                            dna_hash,
+                            agents,
+                            signal,
+                            durable_ttl,
+                        )
+                        .await?;
+
+                    return Ok(AppResponse::DirectSignalAcknowledged(acks));
+                }
+
                 self.conductor_handle
                     .clone()
-                    .send_direct_signal(installed_app_id, dna_hash, agents, signal)
+                    .send_direct_signal(installed_app_id, dna_hash, agents, signal, durable_ttl)
                     .await?;
 
                 Ok(AppResponse::Ok)
             }
         }
     }
+
+    /// Call a zome function and wrap its result in an [AppResponse].
+    ///
+    /// The response includes the points the call consumed if it is `metered`.
+    async fn call_zome(
+        &self,
+        installed_app_id: &InstalledAppId,
+        zome_call_params_signed: ZomeCallParamsSigned,
+        metered: bool,
+    ) -> ConductorApiResult<AppResponse> {
+        let (result, points_consumed) = self
+            .conductor_handle
+            .handle_external_zome_call(installed_app_id, zome_call_params_signed)
+            .await?;
+        match result {
+            Ok(ZomeCallResponse::Ok(output)) if metered => Ok(AppResponse::ZomeCalledMetered {
+                output: Box::new(output),
+                points_consumed,
+            }),
+            Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCalled(Box::new(output))),
+            Ok(ZomeCallResponse::AuthenticationFailed(signature, provenance)) => Ok(AppResponse::Error(
+                ExternalApiWireError::ZomeCallAuthenticationFailed(format!(
+                    "Authentication failure. Bad signature {signature:?} by provenance {provenance:?}.",
+                )),
+            )),
+            Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, cap_secret, zome_name, fn_name)) => Ok(AppResponse::Error(
+                ExternalApiWireError::ZomeCallUnauthorized(format!(
+                    "Call was not authorized with reason {zome_call_authorization:?}, cap secret {cap_secret:?} to call the function {fn_name} in zome {zome_name}"
+                )),
+            )),
+            Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
+                "Interface zome calls should never be routed to the network. This is a bug. Got {}",
+                e
+            ),
+            Ok(ZomeCallResponse::CountersigningSession(e)) => Ok(AppResponse::Error(
+                ExternalApiWireError::CountersigningSessionError(format!(
+                    "A countersigning session has failed to start on this zome call because: {e}"
+                )),
+            )),
+            Err(e) => Ok(AppResponse::Error(e.into())),
+        }
+    }
 }
 
 /// The payload for authenticating an app interface connection
@@ -53,6 +53,10 @@ pub enum ConductorApiError {
     #[error("DnaError: {0}")]
     DnaError(#[from] holochain_types::dna::DnaError),
 
+    /// A zome call was rejected by admission control.
+    #[error("Zome call limit exceeded: {0}")]
+    ZomeCallLimitExceeded(String),
+
     /// The Dna file path provided was invalid
     #[error("The Dna file path provided was invalid")]
     DnaReadError(String),
@@ -140,6 +144,9 @@ impl From<ConductorApiError> for ExternalApiWireError {
     fn from(err: ConductorApiError) -> Self {
         match err {
             ConductorApiError::DnaReadError(e) => ExternalApiWireError::DnaReadError(e),
+            ConductorApiError::ZomeCallLimitExceeded(e) => {
+                ExternalApiWireError::ZomeCallLimitExceeded(e)
+            }
             e => ExternalApiWireError::internal(e),
         }
     }
@@ -53,6 +53,10 @@ use {
 
 pub const INIT_MUTEX_TIMEOUT_SECS: u64 = 30;
 
+/// How long the outcome of an acknowledged direct signal is remembered, so that
+/// retries of the signal by the sender are not passed on again.
+const DIRECT_SIGNAL_DEDUP_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
 pub mod error;
 
 #[cfg(test)]
@@ -104,6 +108,11 @@ pub struct Cell {
     /// We store the P2P config overrides used to create this cell here
     /// so we can later check for conflicts if we try to install a cell with the same dna
     overrides: CellConfigOverrides,
+    /// The outcome of recently received acknowledged direct signals, by sender and
+    /// idempotency key.
+    recent_direct_signals: parking_lot::Mutex<
+        std::collections::HashMap<(AgentPubKey, u64), (std::time::Instant, DirectSignalOutcome)>,
+    >,
 }
 
 impl Cell {
@@ -147,6 +156,7 @@ impl Cell {
                     signal_tx,
                     overrides,
                     init_mutex: Default::default(),
+                    recent_direct_signals: Default::default(),
                 },
                 initial_queue_triggers,
             ))
@@ -430,7 +440,8 @@ impl holochain_p2p::event::HcP2pHandler for Cell {
         signal: Vec<u8>,
         from_agent: AgentPubKey,
         signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
         Box::pin(async move {
             // Add 3 to allow for msgpack overhead for an "array 16"
             if signal.len() > DIRECT_SIGNAL_MAX_SIZE + 3 {
@@ -446,10 +457,23 @@ impl holochain_p2p::event::HcP2pHandler for Cell {
 
             let signal: DirectSignal = decode(&signal).map_err(HolochainP2pError::other)?;
 
-            let valid_sig = from_agent
-                .verify_signature(&signature, &signal)
-                .await
-                .map_err(HolochainP2pError::other)?;
+            // The idempotency key of an acknowledged signal is signed with it, so that
+            // a signal can't be passed on again by replaying it with a new key.
+            let valid_sig = match idempotency_key {
+                Some(idempotency_key) => {
+                    from_agent
+                        .verify_signature(
+                            &signature,
+                            &AckedDirectSignal {
+                                signal: signal.0.clone(),
+                                idempotency_key,
+                            },
+                        )
+                        .await
+                }
+                None => from_agent.verify_signature(&signature, &signal).await,
+            }
+            .map_err(HolochainP2pError::other)?;
             if !valid_sig {
                 warn!("Received signal payload with an invalid signature");
                 return Err(HolochainP2pError::other(
@@ -457,14 +481,44 @@ impl holochain_p2p::event::HcP2pHandler for Cell {
                 ));
             }
 
-            if let Err(e) = self.signal_tx.send(Signal::AppDirect {
+            let blocked = self
+                .conductor_handle
+                .is_blocked(
+                    BlockTargetId::Cell(CellId::new(dna_hash.clone(), from_agent.clone())),
+                    Timestamp::now(),
+                )
+                .await
+                .map_err(HolochainP2pError::other)?;
+
+            // A retried signal gets the outcome of the first attempt, without
+            // being passed on again.
+            let mut recent = self.recent_direct_signals.lock();
+            let now = std::time::Instant::now();
+            recent.retain(|_, (received_at, _)| {
+                now.duration_since(*received_at) < DIRECT_SIGNAL_DEDUP_WINDOW
+            });
+            let key = idempotency_key.map(|key| (from_agent, key));
+            if let Some((_, outcome)) = key.as_ref().and_then(|key| recent.get(key)) {
+                return Ok(*outcome);
+            }
+
+            let outcome = if blocked {
+                info!("Refused direct signal from a blocked agent");
+                DirectSignalOutcome::Blocked
+            } else if let Err(e) = self.signal_tx.send(Signal::AppDirect {
                 cell_id: CellId::new(dna_hash, to_agent),
                 signal: signal.0,
             }) {
-                info!(?e, "Failed to relay direct signal to app")
-            }
+                info!(?e, "Failed to relay direct signal to app");
+                DirectSignalOutcome::NoSubscriber
+            } else {
+                DirectSignalOutcome::Delivered
+            };
 
-            Ok(())
+            if let Some(key) = key {
+                recent.insert(key, (now, outcome));
+            }
+            Ok(outcome)
         })
     }
 
@@ -737,6 +791,21 @@ impl Cell {
         params: ZomeCallParams,
         workspace_lock: Option<SourceChainWorkspace>,
     ) -> CellResult<ZomeCallResult> {
+        let (result, _) = self.call_zome_metered(params, workspace_lock).await?;
+        Ok(result)
+    }
+
+    /// Call a zome function and also return the wasm metering points it
+    /// consumed.
+    ///
+    /// The points are `None` if the function was not run by a metered wasm
+    /// backend. Calls the function makes to other zomes or cells are metered
+    /// separately and not included.
+    pub async fn call_zome_metered(
+        &self,
+        params: ZomeCallParams,
+        workspace_lock: Option<SourceChainWorkspace>,
+    ) -> CellResult<(ZomeCallResult, Option<u64>)> {
         // Only check if init has run if this call is not coming from
         // an already running init call.
         if workspace_lock
@@ -752,9 +821,12 @@ impl Cell {
         // It is acceptable to call it to get the conductor to call it once but after that we will
         // just return a success with the same output that the init function would have returned.
         if params.fn_name.as_ref() == "init" {
-            return Ok(Ok(ZomeCallResponse::Ok(ExternIO::encode(
-                InitCallbackResult::Pass,
-            )?)));
+            return Ok((
+                Ok(ZomeCallResponse::Ok(ExternIO::encode(
+                    InitCallbackResult::Pass,
+                )?)),
+                None,
+            ));
         }
 
         let keystore = self.conductor_api.keystore().clone();
@@ -763,6 +835,7 @@ impl Cell {
         let ribosome = self.get_ribosome()?;
         let invocation =
             ZomeCallInvocation::try_from_params(self.conductor_api.clone(), params).await?;
+        let points_consumed = invocation.points_consumed.clone();
 
         // If there is no existing zome call then this is the root zome call
         let is_root_zome_call = workspace_lock.is_none();
@@ -785,7 +858,7 @@ impl Cell {
             conductor_handle,
             is_root_zome_call,
         };
-        Ok(call_zome_workflow(
+        let result = call_zome_workflow(
             workspace_lock,
             Arc::new(self.holochain_p2p_cell.clone()),
             keystore,
@@ -796,7 +869,9 @@ impl Cell {
             self.queue_triggers.countersigning.clone(),
         )
         .await
-        .map_err(Box::new)?)
+        .map_err(Box::new)?;
+        let points_consumed = *points_consumed.lock().unwrap_or_else(|i| i.into_inner());
+        Ok((result, points_consumed))
     }
 
     /// Check if each Zome's init callback has been run, and if not, run it.
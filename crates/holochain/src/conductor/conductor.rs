@@ -37,9 +37,12 @@ use super::api::error::ConductorApiError;
 use super::api::AppInterfaceApi;
 use super::config::AdminInterfaceConfig;
 use super::config::InterfaceDriver;
+use super::config::UiInterfaceConfig;
 use super::entry_def_store::get_entry_defs;
 use super::error::ConductorError;
+use super::interface::error::InterfaceError;
 use super::interface::error::InterfaceResult;
+use super::interface::web_ui::{app_origins, is_host_label, spawn_web_ui_interface};
 use super::interface::websocket::spawn_admin_interface_tasks;
 use super::interface::websocket::spawn_app_interface_task;
 use super::interface::websocket::spawn_websocket_listener;
@@ -52,6 +55,7 @@ use super::{api::AdminInterfaceApi, manager::TaskManagerClient};
 use crate::conductor::cell::Cell;
 use crate::conductor::conductor::app_auth_token_store::AppAuthTokenStore;
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
+use crate::conductor::conductor::zome_call_limiter::ZomeCallLimiter;
 use crate::conductor::config::ConductorConfig;
 use crate::conductor::error::ConductorResult;
 use crate::core::queue_consumer::InitialQueueTriggers;
@@ -74,6 +78,8 @@ use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
 use holo_hash::DnaHash;
 use holochain_conductor_api::conductor::KeystoreConfig;
+use holochain_conductor_api::conductor::WasmMeteringConfig;
+use holochain_conductor_api::conductor::ZomeCallLimits;
 use holochain_conductor_api::state::AppInterfaceConfig;
 use holochain_conductor_api::state::AppInterfaceId;
 use holochain_conductor_api::state::ConductorState;
@@ -85,6 +91,7 @@ use holochain_conductor_api::PeerMetaInfo;
 use holochain_conductor_api::{DhtOpsCursor, FullIntegrationStateDump, SourceChainCursor};
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::lair_keystore::spawn_lair_keystore_in_proc;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDnaT;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
@@ -119,6 +126,8 @@ pub(crate) mod zome_call_signature_verification;
 
 pub(crate) mod app_broadcast;
 
+pub(crate) mod zome_call_limiter;
+
 #[cfg(test)]
 pub(crate) mod tests;
 
@@ -190,6 +199,12 @@ pub struct Conductor {
 
     /// Container to connect app signals to app interfaces, by installed app id.
     app_broadcast: AppBroadcast,
+
+    /// Zome call admission control, by installed app id.
+    zome_call_limiters: RwShare<HashMap<InstalledAppId, Arc<ZomeCallLimiter>>>,
+
+    /// Stops the listener task of each running app interface, by interface id.
+    app_interface_stops: RwShare<HashMap<AppInterfaceId, tokio::sync::oneshot::Sender<()>>>,
 }
 
 impl std::fmt::Debug for Conductor {
@@ -288,6 +303,8 @@ mod startup_shutdown_impls {
                 wasmer_module_cache,
                 app_auth_token_store: RwShare::default(),
                 app_broadcast: AppBroadcast::default(),
+                zome_call_limiters: RwShare::default(),
+                app_interface_stops: RwShare::default(),
             }
         }
 
@@ -362,15 +379,28 @@ mod startup_shutdown_impls {
 
             info!("Conductor startup: app interfaces started.");
 
+            // After the app interfaces, so that the interfaces of web app UIs are
+            // already running and are not attached again.
+            self.clone()
+                .add_ui_interfaces(self.config.ui_interfaces.clone().unwrap_or_default())
+                .await?;
+
+            info!("Conductor startup: UI interface(s) added.");
+
+            self.apply_arc_policies().await?;
+
+            info!("Conductor startup: arc policies applied.");
+
+            self.apply_bandwidth_limits(None).await?;
+
+            info!("Conductor startup: bandwidth limits applied.");
+
             // Determine cells to create
             let state = self.get_state().await?;
             // create cells with their config override
             for (_, app) in state.enabled_apps() {
-                let config_override = Self::p2p_config_overrides(&app.manifest);
-                let cell_ids = app.all_enabled_cells();
-                self.clone()
-                    .create_cells_and_startup(cell_ids, config_override)
-                    .await?;
+                let cells = Self::cells_with_p2p_config_overrides(app, app.all_enabled_cells());
+                self.clone().create_cells_and_startup(cells).await?;
             }
 
             info!("Conductor startup: apps enabled.");
@@ -424,6 +454,9 @@ mod interface_impls {
 
                             InterfaceResult::Ok(port)
                         }
+                        InterfaceDriver::Http { .. } => Err(InterfaceError::Other(
+                            "HTTP interfaces can't be used as admin interfaces".to_string(),
+                        )),
                     }
                 }
             };
@@ -445,9 +478,72 @@ mod interface_impls {
             Ok(ports)
         }
 
+        /// Spawn the tasks serving web app UIs, based on the config passed in.
+        ///
+        /// Each installed web app is given the app interface its UI connects
+        /// to, if it doesn't have one yet.
+        ///
+        /// Returns the given or auto-chosen port of each interface.
+        #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+        pub async fn add_ui_interfaces(
+            self: Arc<Self>,
+            configs: Vec<UiInterfaceConfig>,
+        ) -> ConductorResult<Vec<u16>> {
+            let mut ports = Vec::with_capacity(configs.len());
+            for UiInterfaceConfig { driver } in &configs {
+                let port = match driver {
+                    InterfaceDriver::Http {
+                        port,
+                        danger_bind_addr,
+                        allowed_origins,
+                    } => spawn_web_ui_interface(
+                        self.task_manager(),
+                        self.clone(),
+                        *port,
+                        danger_bind_addr.clone(),
+                        allowed_origins.clone(),
+                    )
+                    .await
+                    .map_err(Box::new)?,
+                    InterfaceDriver::Websocket { .. } => {
+                        return Err(Box::new(InterfaceError::Other(
+                            "UI interfaces must use the HTTP driver".to_string(),
+                        ))
+                        .into());
+                    }
+                };
+                info!("UI interface added at port: {}", port);
+                ports.push(port);
+            }
+
+            let installed_app_ids = self
+                .get_state()
+                .await?
+                .installed_apps()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>();
+            for installed_app_id in installed_app_ids {
+                if self
+                    .web_ui_path(&installed_app_id)
+                    .is_some_and(|path| path.is_file())
+                {
+                    self.clone()
+                        .attach_web_ui_app_interface(&installed_app_id, &configs)
+                        .await?;
+                }
+            }
+
+            Ok(ports)
+        }
+
         /// Spawn a new app interface task, register it with the TaskManager,
         /// and modify the conductor accordingly, based on the config passed in.
         ///
+        /// Zome calls over the interface are limited by `zome_call_limits`,
+        /// with any limit not set there taken from the `interface` limits of
+        /// the conductor config.
+        ///
         /// Returns the given or auto-chosen port number if giving an Ok Result
         #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
         pub async fn add_app_interface(
@@ -456,6 +552,7 @@ mod interface_impls {
             danger_bind_addr: Option<String>,
             allowed_origins: AllowedOrigins,
             installed_app_id: Option<InstalledAppId>,
+            zome_call_limits: Option<ZomeCallLimits>,
         ) -> ConductorResult<u16> {
             let interface_id = match port {
                 either::Either::Left(port) => AppInterfaceId::new(port),
@@ -467,6 +564,17 @@ mod interface_impls {
 
             let tm = self.task_manager();
 
+            let global_limits = self
+                .config
+                .zome_call_limits
+                .as_ref()
+                .map(|limits| limits.interface)
+                .unwrap_or_default();
+            let zome_call_limiter = Arc::new(ZomeCallLimiter::new(
+                zome_call_limits.map_or(global_limits, |limits| limits.or(global_limits)),
+            ));
+
+            let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
             // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
             let port = spawn_app_interface_task(
                 tm.clone(),
@@ -476,16 +584,21 @@ mod interface_impls {
                 installed_app_id.clone(),
                 app_api,
                 self.app_broadcast.clone(),
+                zome_call_limiter,
+                stop_rx,
             )
             .await
             .map_err(Box::new)?;
+            self.app_interface_stops
+                .share_mut(|stops| stops.insert(interface_id.clone(), stop_tx));
 
             let config = AppInterfaceConfig::websocket(
                 port,
                 danger_bind_addr,
                 allowed_origins,
                 installed_app_id,
-            );
+            )
+            .with_zome_call_limits(zome_call_limits);
             self.update_state(|mut state| {
                 state.app_interfaces.insert(interface_id, config);
 
@@ -514,6 +627,7 @@ mod interface_impls {
                     port: config.driver.port(),
                     allowed_origins: config.driver.allowed_origins().clone(),
                     installed_app_id: config.installed_app_id.clone(),
+                    zome_call_limits: config.zome_call_limits,
                 })
                 .collect())
         }
@@ -532,6 +646,7 @@ mod interface_impls {
                         config.driver.danger_bind_addr().cloned(),
                         config.driver.allowed_origins().clone(),
                         config.installed_app_id.clone(),
+                        config.zome_call_limits,
                     )
                     .await?;
             }
@@ -613,6 +728,37 @@ mod dna_impls {
                 .share_mut(|d| d.add_entry_defs(entry_defs));
         }
 
+        /// The wasm metering budgets for the zomes of an app, or the conductor's
+        /// default budgets if there is no app.
+        ///
+        /// Budgets the app was installed with are limited by the conductor
+        /// config.
+        pub(crate) fn wasm_metering_for_app(
+            &self,
+            app: Option<&InstalledAppCommon>,
+        ) -> WasmMeteringBudgets {
+            match app {
+                Some(app) => self
+                    .wasm_metering_config()
+                    .budgets_for_app(&app.installed_app_id, app.wasm_metering.as_ref()),
+                None => self.wasm_metering_config().default,
+            }
+        }
+
+        /// The wasm metering config of the conductor.
+        ///
+        /// Default budgets that the config does not set are filled in with the
+        /// limit of the wasm host, so that apps cannot be installed with budgets
+        /// above it.
+        pub(crate) fn wasm_metering_config(&self) -> WasmMeteringConfig {
+            let mut config = self.config.wasm_metering.clone().unwrap_or_default();
+            if let Some(limit) = RealRibosome::default_metering_limit() {
+                config.default.zome_call.get_or_insert(limit);
+                config.default.validation.get_or_insert(limit);
+            }
+            config
+        }
+
         pub(crate) fn add_ribosome_to_store(&self, cell_id: CellId, ribosome: Ribosome) {
             self.ribosome_store
                 .share_mut(|d| d.add_ribosome(cell_id, ribosome));
@@ -647,6 +793,7 @@ mod dna_impls {
             }
 
             let entry_defs = self.spaces.entry_def_store.as_read().get_all().await?;
+            let wasm_metering = self.wasm_metering_for_app(Some(installed_app));
 
             // try to join all the tasks and return the list of dna files
             let ribosomes_with_cell_id_future =
@@ -655,6 +802,7 @@ mod dna_impls {
                     .map(|(cell_id, dna_def_hashed)| {
                         #[cfg(feature = "test_utils")]
                         let inline_zome_store = self.inline_zome_store.clone();
+                        let wasm_metering = wasm_metering.clone();
                         async move {
                             #[cfg(feature = "test_utils")]
                             {
@@ -681,7 +829,8 @@ mod dna_impls {
                                 dna_def_hashed.clone(),
                                 self.wasmer_module_cache.clone(),
                             )
-                            .await?;
+                            .await?
+                            .with_wasm_metering(wasm_metering);
                             let ribosome = Ribosome::new(dna_def_hashed, ribosome).await?;
 
                             ConductorResult::Ok((cell_id, ribosome))
@@ -770,6 +919,28 @@ mod dna_impls {
             &self,
             cell_id: CellId,
             dna_file: DnaFile,
+        ) -> ConductorResult<()> {
+            let state = self.get_state().await?;
+            let app = state
+                .installed_apps()
+                .values()
+                .find(|app| app.all_cells().any(|id| id == cell_id));
+            let wasm_metering = self.wasm_metering_for_app(app.map(|app| &**app));
+            self.register_dna_file_with_wasm_metering(cell_id, dna_file, wasm_metering)
+                .await
+        }
+
+        /// Install a [`DnaFile`] in this Conductor, with the wasm metering
+        /// budgets for the app it is installed for.
+        ///
+        /// Apps being installed are not in the state yet, so their budgets have
+        /// to be passed in for genesis to use them.
+        #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+        pub(crate) async fn register_dna_file_with_wasm_metering(
+            &self,
+            cell_id: CellId,
+            dna_file: DnaFile,
+            wasm_metering: WasmMeteringBudgets,
         ) -> ConductorResult<()> {
             if self.get_ribosome(&cell_id).is_ok() {
                 // ribosome for dna is already registered in store
@@ -817,7 +988,8 @@ mod dna_impls {
                     dna_file.dna_def_hashed().clone(),
                     self.wasmer_module_cache.clone(),
                 )
-                .await?;
+                .await?
+                .with_wasm_metering(wasm_metering);
                 Ribosome::new(dna_file.dna_def_hashed().clone(), ribosome).await?
             };
 
@@ -1012,6 +1184,13 @@ mod network_impls {
             })
         }
 
+        /// Move the committed data of all the databases of the conductor out of
+        /// their write-ahead logs and into the database files, so that the files
+        /// can be copied on their own.
+        pub(crate) async fn checkpoint_databases(&self) -> ConductorResult<()> {
+            self.spaces.checkpoint_databases().await
+        }
+
         async fn storage_info_for_dna(
             &self,
             dna_hash: &DnaHash,
@@ -1037,10 +1216,25 @@ mod network_impls {
         }
 
         /// Handle a zome call coming from outside of the conductor, e.g. through the ConductorApi.
+        ///
+        /// Also returns the wasm metering points the call consumed, if it was metered.
+        ///
+        /// The call is rejected with [`ConductorApiError::ZomeCallLimitExceeded`] if the
+        /// app it was made to is at its zome call limits.
         pub async fn handle_external_zome_call(
             &self,
+            installed_app_id: &InstalledAppId,
             zome_call_params_signed: ZomeCallParamsSigned,
-        ) -> ConductorApiResult<ZomeCallResult> {
+        ) -> ConductorApiResult<(ZomeCallResult, Option<u64>)> {
+            let _permit = self
+                .zome_call_limiter_for_app(installed_app_id)
+                .try_admit()
+                .map_err(|reason| {
+                    ConductorApiError::ZomeCallLimitExceeded(format!(
+                        "App {installed_app_id} rejected the call: {reason}"
+                    ))
+                })?;
+
             let zome_call_params = zome_call_params_signed
                 .bytes
                 .clone()
@@ -1053,13 +1247,39 @@ mod network_impls {
             )
             .await?
             {
-                return Ok(Ok(ZomeCallResponse::AuthenticationFailed(
-                    zome_call_params_signed.signature,
-                    zome_call_params.provenance,
-                )));
+                return Ok((
+                    Ok(ZomeCallResponse::AuthenticationFailed(
+                        zome_call_params_signed.signature,
+                        zome_call_params.provenance,
+                    )),
+                    None,
+                ));
             }
 
-            self.call_zome(zome_call_params.clone()).await
+            let cell = self.cell_by_id(&zome_call_params.cell_id).await?;
+            Ok(cell.call_zome_metered(zome_call_params, None).await?)
+        }
+
+        /// Get the zome call limiter for an app, creating it from the config
+        /// on first use.
+        fn zome_call_limiter_for_app(
+            &self,
+            installed_app_id: &InstalledAppId,
+        ) -> Arc<ZomeCallLimiter> {
+            self.zome_call_limiters.share_mut(|limiters| {
+                limiters
+                    .entry(installed_app_id.clone())
+                    .or_insert_with(|| {
+                        Arc::new(ZomeCallLimiter::new(
+                            self.config
+                                .zome_call_limits
+                                .as_ref()
+                                .map(|limits| limits.limits_for_app(installed_app_id))
+                                .unwrap_or_default(),
+                        ))
+                    })
+                    .clone()
+            })
         }
 
         /// Invoke a zome function on a Cell
@@ -1127,6 +1347,8 @@ pub struct InstallAppCommonFlags {
     pub defer_memproofs: bool,
     /// From [`InstallAppPayload::ignore_genesis_failure`]
     pub ignore_genesis_failure: bool,
+    /// From [`InstallAppPayload::wasm_metering`]
+    pub wasm_metering: Option<WasmMeteringBudgets>,
 }
 
 /// Methods related to app installation and management
@@ -1135,6 +1357,7 @@ pub struct InstallAppCommonFlags {
 mod app_impls {
     use super::*;
     use holochain_conductor_api::CellInfo;
+    use holochain_types::websocket::AllowedOrigins;
 
     impl Conductor {
         /// Install an app from minimal elements, without needing to construct a whole AppBundle.
@@ -1196,6 +1419,7 @@ mod app_impls {
                     flags.unwrap_or(InstallAppCommonFlags {
                         defer_memproofs: false,
                         ignore_genesis_failure: false,
+                        wasm_metering: None,
                     }),
                     InitPropertiesMap::new(),
                 )
@@ -1240,20 +1464,47 @@ mod app_impls {
                 ));
             };
 
+            let wasm_metering = self
+                .wasm_metering_config()
+                .budgets_for_app(&installed_app_id, flags.wasm_metering.as_ref());
+
+            // A cell used from another app runs with the budgets of the app
+            // that created it, so the budgets of this app must match them.
+            for (_, assignment) in &ops.role_assignments {
+                let AppRoleAssignment::Dependency(dependency) = assignment else {
+                    continue;
+                };
+                let owner = state
+                    .installed_apps()
+                    .values()
+                    .find(|app| app.role_name_for_cell(&dependency.cell_id).is_some());
+                if let Some(owner) = owner {
+                    if self.wasm_metering_for_app(Some(&**owner)) != wasm_metering {
+                        return Err(ConductorError::ConflictingWasmMetering(
+                            installed_app_id,
+                            owner.id().clone(),
+                        ));
+                    }
+                }
+            }
+
             for (dna, _) in ops.dnas_to_register {
                 let cell_id = CellId::new(dna.dna_hash().clone(), agent_key.clone());
-                self.clone().register_dna_file(cell_id, dna).await?;
+                self.clone()
+                    .register_dna_file_with_wasm_metering(cell_id, dna, wasm_metering.clone())
+                    .await?;
             }
 
             if flags.defer_memproofs {
                 let roles = ops.role_assignments;
-                let app = InstalledAppCommon::new(
+                let mut app = InstalledAppCommon::new(
                     installed_app_id.clone(),
                     agent_key.clone(),
                     roles,
                     manifest,
                     Timestamp::now(),
                 )?;
+                app.wasm_metering = flags.wasm_metering;
 
                 let (_, app) = self
                     .update_state_prime(move |mut state| {
@@ -1268,13 +1519,14 @@ mod app_impls {
 
                 if genesis_result.is_ok() || flags.ignore_genesis_failure {
                     let roles = ops.role_assignments;
-                    let app = InstalledAppCommon::new(
+                    let mut app = InstalledAppCommon::new(
                         installed_app_id.clone(),
                         agent_key.clone(),
                         roles,
                         manifest,
                         Timestamp::now(),
                     )?;
+                    app.wasm_metering = flags.wasm_metering;
 
                     for cell in app.all_cells() {
                         if let Ok(ribosome) = self.get_ribosome(&cell) {
@@ -1310,6 +1562,7 @@ mod app_impls {
                 roles_settings,
                 ignore_genesis_failure,
                 restore_from_dht,
+                wasm_metering,
             } = payload;
 
             if restore_from_dht && agent_key.is_none() {
@@ -1329,18 +1582,19 @@ mod app_impls {
             let membrane_proofs = get_memproof_map_from_role_settings(&roles_settings);
             let existing_cells = get_existing_cells_map_from_role_settings(&roles_settings);
             let init_properties = get_init_properties_map_from_role_settings(&roles_settings);
+            let network = get_network_map_from_role_settings(&roles_settings);
 
-            let bundle = {
-                let original_bundle = source.resolve().await?;
-                let mut manifest = original_bundle.manifest().to_owned();
-                if let Some(network_seed) = network_seed {
-                    manifest.set_network_seed(network_seed);
-                }
-                manifest.override_modifiers(modifiers)?;
-                AppBundle::from(original_bundle.into_inner().update_manifest(manifest)?)
-            };
-
-            let manifest = bundle.manifest().clone();
+            // The DNAs of the bundle are only read as its cells are resolved, below.
+            let (bundle, web_ui) = source
+                .open_web_app()
+                .await
+                .map_err(Self::invalid_publisher_signature_is_untrusted)?;
+            let mut manifest = bundle.manifest().to_owned();
+            if let Some(network_seed) = network_seed {
+                manifest.set_network_seed(network_seed);
+            }
+            manifest.override_modifiers(modifiers)?;
+            manifest.override_network(network)?;
 
             // Use deferred memproofs only if no memproofs are provided for any of the roles.
             // If a memproof is provided for any of the roles, it will override the app wide
@@ -1352,14 +1606,28 @@ mod app_impls {
             let flags = InstallAppCommonFlags {
                 defer_memproofs,
                 ignore_genesis_failure,
+                wasm_metering,
             };
 
             let installed_app_id =
                 installed_app_id.unwrap_or_else(|| manifest.app_name().to_owned());
 
-            let ops = bundle
-                .resolve_cells(membrane_proofs, existing_cells)
-                .await?;
+            // The UI is served under a host whose first label is the app ID.
+            if web_ui.is_some() && !is_host_label(&installed_app_id) {
+                return Err(ConductorError::InvalidWebAppId(installed_app_id));
+            }
+
+            let (ops, app_publisher_signature) = bundle
+                .resolve_cells(manifest.clone(), membrane_proofs, existing_cells)
+                .await
+                .map_err(Self::invalid_publisher_signature_is_untrusted)?;
+            // The signature of a web app bundle also covers its UI, and the hApp bundle in it
+            // by hash.
+            let publisher_signature = match &web_ui {
+                Some(web_ui) => web_ui.publisher_signature.clone(),
+                None => app_publisher_signature,
+            };
+            self.check_app_bundle_publisher(publisher_signature).await?;
 
             // Reject any init_properties whose role name is unknown or belongs to a
             // non-provisioned role (UseExisting / CloneOnly cells have no init callback).
@@ -1395,9 +1663,196 @@ mod app_impls {
                 )
                 .await?;
 
+            if let Some(web_ui) = web_ui {
+                if self.store_web_ui(&installed_app_id, web_ui.zip).await? {
+                    let configs = self.config.ui_interfaces.clone().unwrap_or_default();
+                    self.clone()
+                        .attach_web_ui_app_interface(&installed_app_id, &configs)
+                        .await?;
+                }
+            }
+
             Ok(app)
         }
 
+        /// The path where the UI of a web app is stored, if the conductor
+        /// has a data root path.
+        pub(crate) fn web_ui_path(&self, installed_app_id: &InstalledAppId) -> Option<PathBuf> {
+            use base64::prelude::*;
+
+            // App IDs can contain any characters, so encode them to get a valid file name.
+            let file_name = format!("{}.zip", BASE64_URL_SAFE_NO_PAD.encode(installed_app_id));
+            self.config.data_root_path.as_ref().map(|path| {
+                path.join(crate::conductor::paths::WEB_UI_DIRECTORY)
+                    .join(file_name)
+            })
+        }
+
+        /// Store the UI of a web app, returning whether it was stored.
+        async fn store_web_ui(
+            &self,
+            installed_app_id: &InstalledAppId,
+            web_ui: bytes::Bytes,
+        ) -> ConductorResult<bool> {
+            let Some(path) = self.web_ui_path(installed_app_id) else {
+                warn!(
+                    %installed_app_id,
+                    "The conductor has no data root path, so the UI of the web app won't be served"
+                );
+                return Ok(false);
+            };
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(path, web_ui).await?;
+            Ok(true)
+        }
+
+        /// Attach the app interface that the UI of a web app connects to, which
+        /// only accepts connections from the origins the UI is served under by
+        /// the given UI interfaces.
+        ///
+        /// Nothing is attached if the app already has an interface that accepts
+        /// all of these origins, so each app has at most one.
+        pub(crate) async fn attach_web_ui_app_interface(
+            self: Arc<Self>,
+            installed_app_id: &InstalledAppId,
+            configs: &[UiInterfaceConfig],
+        ) -> ConductorResult<()> {
+            let origins = configs
+                .iter()
+                .flat_map(|config| match config.driver.allowed_origins() {
+                    AllowedOrigins::Origins(origins) => app_origins(origins, installed_app_id),
+                    AllowedOrigins::Any => HashSet::new(),
+                })
+                .collect::<HashSet<_>>();
+            if origins.is_empty() {
+                return Ok(());
+            }
+
+            let attached = self
+                .list_app_interfaces()
+                .await?
+                .into_iter()
+                .any(|interface| {
+                    interface.installed_app_id.as_ref() == Some(installed_app_id)
+                        && matches!(
+                            &interface.allowed_origins,
+                            AllowedOrigins::Origins(allowed) if allowed.is_superset(&origins)
+                        )
+                });
+            if !attached {
+                self.add_app_interface(
+                    either::Either::Left(0),
+                    None,
+                    AllowedOrigins::Origins(origins),
+                    Some(installed_app_id.clone()),
+                    None,
+                )
+                .await?;
+            }
+            Ok(())
+        }
+
+        /// Remove the stored UI of a web app, and the app interface it connects to.
+        ///
+        /// The interface is recognized by only accepting connections from
+        /// origins the app's UI is served under. It is removed from the state,
+        /// so it is not started again, and can't be connected to since tokens
+        /// can't be issued for an app that isn't installed.
+        async fn remove_web_ui(&self, installed_app_id: &InstalledAppId) -> ConductorResult<()> {
+            if let Some(path) = self.web_ui_path(installed_app_id) {
+                match tokio::fs::remove_file(path).await {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                    _ => {}
+                }
+            }
+
+            let installed_app_id = installed_app_id.clone();
+            let (_, removed) = self
+                .update_state_prime(move |mut state| {
+                    let mut removed = Vec::new();
+                    state.app_interfaces.retain(|id, config| {
+                        let is_web_ui_interface = config.installed_app_id.as_ref()
+                            == Some(&installed_app_id)
+                            && matches!(
+                                config.driver.allowed_origins(),
+                                AllowedOrigins::Origins(origins)
+                                    if !origins.is_empty()
+                                        && app_origins(origins, &installed_app_id).len() == origins.len()
+                            );
+                        if is_web_ui_interface {
+                            removed.push(id.clone());
+                        }
+                        !is_web_ui_interface
+                    });
+                    Ok((state, removed))
+                })
+                .await?;
+
+            // Close the listeners of the removed interfaces, along with their connections.
+            self.app_interface_stops.share_mut(|stops| {
+                for id in removed {
+                    if let Some(stop) = stops.remove(&id) {
+                        let _ = stop.send(());
+                    }
+                }
+            });
+            Ok(())
+        }
+
+        /// Report a malformed publisher signature, or one that was made for another manifest, like
+        /// a signature that doesn't match the bundle.
+        fn invalid_publisher_signature_is_untrusted(e: AppBundleError) -> ConductorError {
+            match e {
+                AppBundleError::InvalidPublisherSignature(reason) => {
+                    ConductorError::UntrustedAppBundle(reason)
+                }
+                e => e.into(),
+            }
+        }
+
+        /// Check the publisher signature of an app bundle against the
+        /// trusted publishers in the conductor config.
+        ///
+        /// A signature that doesn't match the signing payload of the bundle
+        /// is always rejected, even if no trusted publishers are configured.
+        async fn check_app_bundle_publisher(
+            &self,
+            publisher_signature: Option<PublisherSignature>,
+        ) -> ConductorResult<()> {
+            let app_publishers = self.config.app_publishers.as_ref();
+            match publisher_signature {
+                Some(PublisherSignature {
+                    publisher,
+                    signature,
+                    payload,
+                }) => {
+                    if !publisher
+                        .verify_signature_raw(&signature, payload.into())
+                        .await?
+                    {
+                        return Err(ConductorError::UntrustedAppBundle(format!(
+                            "the signature by publisher {publisher} does not match the bundle"
+                        )));
+                    }
+                    if app_publishers.is_some_and(|config| !config.is_trusted(&publisher)) {
+                        return Err(ConductorError::UntrustedAppBundle(format!(
+                            "publisher {publisher} is not trusted"
+                        )));
+                    }
+                }
+                None => {
+                    if app_publishers.is_some_and(|config| !config.allow_unsigned) {
+                        return Err(ConductorError::UntrustedAppBundle(
+                            "the bundle is not signed".to_string(),
+                        ));
+                    }
+                }
+            }
+            Ok(())
+        }
+
         /// Uninstall an app, removing all traces of it including its cells.
         ///
         /// This will fail if the app is depended upon by other apps via the UseExisting
@@ -1443,6 +1898,17 @@ mod app_impls {
                     .collect::<HashSet<_>>();
                 self.app_broadcast.retain(installed_app_ids);
 
+                self.zome_call_limiters
+                    .share_mut(|limiters| limiters.remove(installed_app_id));
+
+                // The persisted limits are removed with the app, only the network
+                // needs to be told.
+                self.holochain_p2p
+                    .set_bandwidth_limits(installed_app_id.clone(), Vec::new(), None)
+                    .await?;
+
+                self.remove_web_ui(installed_app_id).await?;
+
                 Ok(())
             } else {
                 Err(ConductorError::AppHasDependents(
@@ -1794,12 +2260,12 @@ mod clone_cell_impls {
             crate::conductor::conductor::genesis_cells(self.clone(), cells).await?;
             let state = self.get_state().await?;
             let app = state.get_app(installed_app_id)?;
-            let p2p_config_override = Self::p2p_config_overrides(&app.manifest);
-            self.create_cells_and_startup(
+            let cells = Self::cells_with_p2p_config_overrides(
+                app,
                 [clone_cell.cell_id.clone()].into_iter(),
-                p2p_config_override,
-            )
-            .await?;
+            );
+            self.clone().create_cells_and_startup(cells).await?;
+            self.apply_bandwidth_limits(Some(installed_app_id)).await?;
             Ok(clone_cell)
         }
 
@@ -1865,13 +2331,12 @@ mod clone_cell_impls {
 
             let state = self.get_state().await?;
             let app = state.get_app(installed_app_id)?;
-            let p2p_config_override = Self::p2p_config_overrides(&app.manifest);
-
-            self.create_cells_and_startup(
+            let cells = Self::cells_with_p2p_config_overrides(
+                app,
                 [enabled_cell.cell_id.clone()].into_iter(),
-                p2p_config_override,
-            )
-            .await?;
+            );
+
+            self.create_cells_and_startup(cells).await?;
             Ok(enabled_cell)
         }
 
@@ -1918,14 +2383,14 @@ mod app_status_impls {
 
     impl Conductor {
         /// Instantiate cells, add them to the conductor's state, then initialize them.
+        ///
+        /// Each cell is created and joins the network with its own config overrides.
         pub(crate) async fn create_cells_and_startup(
             self: Arc<Self>,
-            cell_ids: impl Iterator<Item = CellId>,
-            config_override: Option<CellConfigOverrides>,
+            cells: Vec<(CellId, Option<CellConfigOverrides>)>,
         ) -> ConductorResult<()> {
-            let cells_to_create = cell_ids.map(|cell_id| {
+            let cells_to_create = cells.into_iter().map(|(cell_id, overrides)| {
                 let handle = self.clone();
-                let overrides = config_override.clone();
                 async move { handle.clone().create_cell(&cell_id, overrides).await }
             });
             // Create cells with bounded parallelism (max 5 concurrent)
@@ -1948,7 +2413,10 @@ mod app_status_impls {
                 .values()
                 .enumerate()
                 .map(|(i, cell)| {
-                    let config_override = config_override.clone();
+                    let config_override = cell
+                        .overrides()
+                        .is_overriding()
+                        .then(|| cell.overrides().clone());
                     let cell_id = cell.id().clone();
                     let agent_pubkey = cell_id.agent_pubkey().clone();
                     let holochain_p2p_dna = cell.holochain_p2p_dna().clone();
@@ -2057,14 +2525,9 @@ mod app_status_impls {
 
             self.load_wasms_into_ribosome_for_app(app).await?;
 
-            // Prepare module config overrides from app manifest
-            let config_override = Self::p2p_config_overrides(&app.manifest);
-
-            // Determine cells to create
-            let cell_ids_in_app = app.all_enabled_cells();
-            self.clone()
-                .create_cells_and_startup(cell_ids_in_app, config_override)
-                .await?;
+            // Determine cells to create, with their module config overrides from the app manifest
+            let cells = Self::cells_with_p2p_config_overrides(app, app.all_enabled_cells());
+            self.clone().create_cells_and_startup(cells).await?;
 
             // Set app status to enabled in conductor state.
             let (_, app) = self
@@ -2130,13 +2593,25 @@ mod app_status_impls {
             Ok(disabled_app)
         }
 
-        /// Get from [`AppManifest`] the [`CellConfigOverrides`] to apply when creating cells for the app.
-        pub(crate) fn p2p_config_overrides(manifest: &AppManifest) -> Option<CellConfigOverrides> {
+        /// Get from [`AppManifest`] the [`CellConfigOverrides`] to apply when creating cells for
+        /// the given role of the app.
+        pub(crate) fn p2p_config_overrides(
+            manifest: &AppManifest,
+            role_name: &RoleName,
+        ) -> Option<CellConfigOverrides> {
             let mut overrides = CellConfigOverrides::default();
             match manifest {
                 AppManifest::V0(manifest) => {
                     overrides.bootstrap_url = manifest.bootstrap_url.clone();
                     overrides.relay_url = manifest.relay_url.clone();
+                    if let Some(network) = manifest
+                        .roles
+                        .iter()
+                        .find(|role| &role.name == role_name)
+                        .and_then(|role| role.network.clone())
+                    {
+                        overrides.network = network;
+                    }
                 }
             }
             if overrides.is_overriding() {
@@ -2146,6 +2621,24 @@ mod app_status_impls {
             }
         }
 
+        /// Pair the given cells of an app with the [`CellConfigOverrides`] of the roles they belong to.
+        pub(crate) fn cells_with_p2p_config_overrides(
+            app: &InstalledApp,
+            cell_ids: impl Iterator<Item = CellId>,
+        ) -> Vec<(CellId, Option<CellConfigOverrides>)> {
+            cell_ids
+                .map(|cell_id| {
+                    // The cells of an app always belong to one of its roles.
+                    let role_name = app
+                        .role_name_for_cell(&cell_id)
+                        .cloned()
+                        .unwrap_or_default();
+                    let overrides = Self::p2p_config_overrides(&app.manifest, &role_name);
+                    (cell_id, overrides)
+                })
+                .collect()
+        }
+
         /// Check whether there is any already installed app with conflicting P2P overrides.
         ///
         /// If there is a conflicting app, return its [`CellId`].
@@ -2297,65 +2790,355 @@ mod scheduler_impls {
     }
 }
 
-/// Miscellaneous methods
-mod misc_impls {
-    use super::{state_dump_helpers::peer_store_dump, *};
-    use holochain_conductor_api::{CellInfo, JsonDump};
-    use holochain_zome_types::prelude::Entry;
-    use kitsune2_api::{SpaceId, TransportStats};
-    use std::sync::atomic::Ordering;
-
-    impl Conductor {
-        /// Grant a zome call capability for a cell
-        pub async fn grant_zome_call_capability(
-            &self,
-            payload: GrantZomeCallCapabilityPayload,
-        ) -> ConductorApiResult<ActionHash> {
-            let GrantZomeCallCapabilityPayload { cell_id, cap_grant } = payload;
-
-            // Must init before committing a grant
-            let cell = self.cell_by_id(&cell_id).await?;
-            cell.check_or_run_zome_init().await?;
+/// Methods related to the outbox of durable remote signals
+mod signal_outbox_impls {
+    use super::*;
+    use std::time::Duration;
 
-            let source_chain = SourceChain::new(
-                self.get_or_create_dht_store(cell_id.dna_hash())?,
-                self.keystore.clone(),
-                cell_id.agent_pubkey().clone(),
-            )
-            .await?;
+    /// How often the outbox is checked for signals that are due to be retried.
+    pub(crate) const SIGNAL_OUTBOX_INTERVAL: Duration = Duration::from_secs(1);
 
-            let cap_grant_entry = Entry::CapGrant(cap_grant);
-            let entry_hash = EntryHash::with_data_sync(&cap_grant_entry);
-            let action_data = ActionData::Create(CreateData {
-                entry_type: EntryType::CapGrant,
-                entry_hash,
-            });
+    /// How long to wait before the first retry of a signal in the outbox.
+    const SIGNAL_OUTBOX_INITIAL_BACKOFF: Duration = Duration::from_secs(2);
 
-            let action_hash = source_chain
-                .put(
-                    action_data,
-                    Some(cap_grant_entry),
-                    ChainTopOrdering::default(),
-                )
-                .await?;
+    /// The longest time to wait between two retries of a signal in the outbox.
+    const SIGNAL_OUTBOX_MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
 
-            source_chain
-                .flush(
-                    cell.holochain_p2p_dna()
-                        .target_arcs()
-                        .await
-                        .map_err(ConductorApiError::other)?,
-                )
-                .await?;
+    /// The most signals retried from the outbox in one round.
+    const SIGNAL_OUTBOX_BATCH_LIMIT: u32 = 256;
 
-            self.cell_by_id(&cell_id)
-                .await?
-                .notify_authored_ops_moved_to_limbo();
+    /// The most signals of one agent on one DNA retried from the outbox in one round.
+    const SIGNAL_OUTBOX_PER_SENDER_LIMIT: u32 = 32;
 
-            Ok(action_hash)
-        }
+    /// How many signals from the outbox are retried at the same time.
+    const SIGNAL_OUTBOX_CONCURRENCY: usize = 16;
 
-        /// Revoke a zome call capability for a cell identified by the [`ActionHash`] of the grant.
+    impl Conductor {
+        /// Put a signal in the outbox for each of the agents that it could not be
+        /// sent to, and tell the sender that it was queued.
+        pub(crate) async fn queue_outbox_signals(
+            &self,
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            to_agents: Vec<AgentPubKey>,
+            payload: OutboxSignalPayload,
+            ttl: Duration,
+        ) -> ConductorResult<()> {
+            if ttl > DURABLE_SIGNAL_MAX_TTL {
+                return Err(ConductorError::other(format!(
+                    "Durable signal TTL of {ttl:?} is longer than the maximum of {DURABLE_SIGNAL_MAX_TTL:?}"
+                )));
+            }
+
+            let now = Timestamp::now();
+            let expires_at = (now + ttl).unwrap_or(Timestamp::MAX);
+            for to_agent in to_agents {
+                let mut signal = OutboxSignal {
+                    id: 0,
+                    dna_hash: dna_hash.clone(),
+                    from_agent: from_agent.clone(),
+                    to_agent,
+                    payload: payload.clone(),
+                    created_at: now,
+                    expires_at,
+                    attempts: 0,
+                    next_attempt_at: next_attempt_at(now, 0, expires_at),
+                };
+                signal.id = self
+                    .spaces
+                    .conductor_store
+                    .insert_outbox_signal(&signal)
+                    .await?;
+                self.emit_outbox_signal(&signal, |cell_id, to_agent, outbox_id| {
+                    SystemSignal::RemoteSignalQueued {
+                        cell_id,
+                        to_agent,
+                        outbox_id,
+                    }
+                })
+                .await;
+            }
+            Ok(())
+        }
+
+        /// The signals in the outbox, optionally only those sent on one DNA.
+        pub async fn list_signal_outbox(
+            &self,
+            dna_hash: Option<DnaHash>,
+        ) -> ConductorApiResult<Vec<OutboxSignal>> {
+            Ok(self
+                .spaces
+                .conductor_store
+                .as_read()
+                .get_outbox_signals(dna_hash.as_ref())
+                .await?)
+        }
+
+        /// Start the task that retries the signals in the outbox.
+        pub(crate) fn start_signal_outbox(self: Arc<Self>, interval_period: Duration) {
+            let tm = self.task_manager();
+            tm.add_conductor_task_ignored("signal outbox", move || async move {
+                let mut interval = tokio::time::interval(interval_period);
+                loop {
+                    interval.tick().await;
+                    self.dispatch_outbox_signals(Timestamp::now()).await;
+                }
+            });
+        }
+
+        /// Retry the signals in the outbox that are due, and expire those whose
+        /// TTL has passed.
+        ///
+        /// A signal is only retried if its agent has recently been seen online,
+        /// otherwise it waits for the next retry. Each round retries a limited
+        /// number of signals, and of signals from each sender, so the rest wait
+        /// for the next round.
+        pub(crate) async fn dispatch_outbox_signals(&self, now: Timestamp) {
+            let due = match self
+                .spaces
+                .conductor_store
+                .as_read()
+                .get_due_outbox_signals(
+                    now,
+                    SIGNAL_OUTBOX_BATCH_LIMIT,
+                    SIGNAL_OUTBOX_PER_SENDER_LIMIT,
+                )
+                .await
+            {
+                Ok(due) => due,
+                Err(err) => {
+                    tracing::warn!(?err, "Could not read the signal outbox");
+                    return;
+                }
+            };
+
+            futures::stream::iter(due)
+                .for_each_concurrent(SIGNAL_OUTBOX_CONCURRENCY, |signal| async move {
+                    if let Err(err) = self.dispatch_outbox_signal(&signal, now).await {
+                        tracing::warn!(
+                            ?err,
+                            outbox_id = signal.id,
+                            "Could not retry outbox signal"
+                        );
+                    }
+                })
+                .await;
+        }
+
+        async fn dispatch_outbox_signal(
+            &self,
+            signal: &OutboxSignal,
+            now: Timestamp,
+        ) -> ConductorResult<()> {
+            if signal.expires_at <= now {
+                self.spaces
+                    .conductor_store
+                    .delete_outbox_signal(signal.id)
+                    .await?;
+                self.emit_outbox_signal(signal, |cell_id, to_agent, outbox_id| {
+                    SystemSignal::RemoteSignalExpired {
+                        cell_id,
+                        to_agent,
+                        outbox_id,
+                    }
+                })
+                .await;
+                return Ok(());
+            }
+
+            let online = self
+                .holochain_p2p
+                .was_agent_recently_online(signal.dna_hash.clone(), signal.to_agent.clone())
+                .await
+                .unwrap_or(false);
+            if online && self.send_outbox_signal(signal).await? {
+                self.spaces
+                    .conductor_store
+                    .delete_outbox_signal(signal.id)
+                    .await?;
+                self.emit_outbox_signal(signal, |cell_id, to_agent, outbox_id| {
+                    SystemSignal::RemoteSignalDelivered {
+                        cell_id,
+                        to_agent,
+                        outbox_id,
+                    }
+                })
+                .await;
+            } else {
+                let attempts = signal.attempts.saturating_add(1);
+                self.spaces
+                    .conductor_store
+                    .reschedule_outbox_signal(
+                        signal.id,
+                        attempts,
+                        next_attempt_at(now, attempts, signal.expires_at),
+                    )
+                    .await?;
+            }
+            Ok(())
+        }
+
+        /// Sign a signal from the outbox and send it, returning whether it could be sent.
+        async fn send_outbox_signal(&self, signal: &OutboxSignal) -> ConductorResult<bool> {
+            let undelivered = match &signal.payload {
+                OutboxSignalPayload::Zome {
+                    zome_name,
+                    signal: payload,
+                } => {
+                    let (nonce, expires_at) = holochain_nonce::fresh_nonce(Timestamp::now())
+                        .map_err(ConductorError::other)?;
+                    let zome_call_params = ZomeCallParams {
+                        provenance: signal.from_agent.clone(),
+                        cell_id: CellId::new(signal.dna_hash.clone(), signal.to_agent.clone()),
+                        zome_name: zome_name.clone(),
+                        fn_name: "recv_remote_signal".into(),
+                        cap_secret: None,
+                        payload: payload.clone(),
+                        nonce,
+                        expires_at,
+                    };
+                    let (bytes, bytes_hash) = zome_call_params.serialize_and_hash()?;
+                    let signature = signal
+                        .from_agent
+                        .sign_raw(self.keystore(), bytes_hash.into())
+                        .await?;
+                    self.holochain_p2p
+                        .send_remote_signal(
+                            signal.dna_hash.clone(),
+                            vec![(signal.to_agent.clone(), ExternIO(bytes), signature)],
+                        )
+                        .await?
+                }
+                OutboxSignalPayload::Direct { signal: payload } => {
+                    let signal_bytes =
+                        holochain_serialized_bytes::encode(&DirectSignal(payload.clone()))?;
+                    let signature = self
+                        .keystore()
+                        .sign(signal.from_agent.clone(), signal_bytes.clone().into())
+                        .await?;
+                    self.holochain_p2p
+                        .send_remote_signal_direct(
+                            signal.dna_hash.clone(),
+                            vec![signal.to_agent.clone()],
+                            signal_bytes,
+                            signal.from_agent.clone(),
+                            signature,
+                        )
+                        .await?
+                }
+            };
+            Ok(undelivered.is_empty())
+        }
+
+        /// Tell the sender of a signal in the outbox what happened to it.
+        async fn emit_outbox_signal(
+            &self,
+            signal: &OutboxSignal,
+            system_signal: impl FnOnce(CellId, AgentPubKey, u64) -> SystemSignal,
+        ) {
+            let cell_id = signal.cell_id();
+            match self.get_signal_tx(&cell_id).await {
+                Ok(signal_tx) => {
+                    // It's fine if no app interface is listening.
+                    let _ = signal_tx.send(Signal::System(system_signal(
+                        cell_id,
+                        signal.to_agent.clone(),
+                        signal.id,
+                    )));
+                }
+                Err(err) => {
+                    tracing::debug!(
+                        ?err,
+                        outbox_id = signal.id,
+                        "No app to signal outbox outcome to"
+                    );
+                }
+            }
+        }
+    }
+
+    /// When to next retry a signal that has already been retried `attempts` times,
+    /// with an exponential backoff that is never later than the signal expires.
+    fn next_attempt_at(now: Timestamp, attempts: u32, expires_at: Timestamp) -> Timestamp {
+        let backoff = SIGNAL_OUTBOX_INITIAL_BACKOFF
+            .saturating_mul(2u32.saturating_pow(attempts))
+            .min(SIGNAL_OUTBOX_MAX_BACKOFF);
+        (now + backoff).unwrap_or(Timestamp::MAX).min(expires_at)
+    }
+}
+
+/// Miscellaneous methods
+mod misc_impls {
+    use super::{state_dump_helpers::peer_store_dump, *};
+    use crate::core::workflow::sys_validation_workflow::get_dependency_hashes_from_actions;
+    use holochain_conductor_api::{
+        AbandonedOpsFilter, CellInfo, JsonDump, LimboStage, OpAuthority, OpTrace, WorkflowStateDump,
+    };
+    use holochain_zome_types::prelude::Entry;
+    use kitsune2_api::{SpaceId, TransportStats};
+    use std::sync::atomic::Ordering;
+
+    /// How many times sending an acknowledged direct signal to an agent is tried.
+    const DIRECT_SIGNAL_ACK_ATTEMPTS: u32 = 3;
+
+    /// How long to wait for an agent to acknowledge a direct signal, on each attempt.
+    const DIRECT_SIGNAL_ACK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+    /// How long to wait before the first retry of an unacknowledged direct signal.
+    const DIRECT_SIGNAL_ACK_RETRY_DELAY: std::time::Duration =
+        std::time::Duration::from_millis(500);
+
+    impl Conductor {
+        /// Grant a zome call capability for a cell
+        pub async fn grant_zome_call_capability(
+            &self,
+            payload: GrantZomeCallCapabilityPayload,
+        ) -> ConductorApiResult<ActionHash> {
+            let GrantZomeCallCapabilityPayload { cell_id, cap_grant } = payload;
+
+            // Must init before committing a grant
+            let cell = self.cell_by_id(&cell_id).await?;
+            cell.check_or_run_zome_init().await?;
+
+            let source_chain = SourceChain::new(
+                self.get_or_create_dht_store(cell_id.dna_hash())?,
+                self.keystore.clone(),
+                cell_id.agent_pubkey().clone(),
+            )
+            .await?;
+
+            let cap_grant_entry = Entry::CapGrant(cap_grant);
+            let entry_hash = EntryHash::with_data_sync(&cap_grant_entry);
+            let action_data = ActionData::Create(CreateData {
+                entry_type: EntryType::CapGrant,
+                entry_hash,
+            });
+
+            let action_hash = source_chain
+                .put(
+                    action_data,
+                    Some(cap_grant_entry),
+                    ChainTopOrdering::default(),
+                )
+                .await?;
+
+            source_chain
+                .flush(
+                    cell.holochain_p2p_dna()
+                        .target_arcs()
+                        .await
+                        .map_err(ConductorApiError::other)?,
+                )
+                .await?;
+
+            self.cell_by_id(&cell_id)
+                .await?
+                .notify_authored_ops_moved_to_limbo();
+
+            Ok(action_hash)
+        }
+
+        /// Revoke a zome call capability for a cell identified by the [`ActionHash`] of the grant.
         pub async fn revoke_zome_call_capability(
             &self,
             cell_id: CellId,
@@ -2607,6 +3390,150 @@ mod misc_impls {
             Ok(out)
         }
 
+        /// Dump the state of the validation and integration workflows of a DNA.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`ConductorApiError::DnaMissing`] if the DNA is not installed.
+        pub async fn dump_workflow_state(
+            &self,
+            dna_hash: &DnaHash,
+        ) -> ConductorApiResult<WorkflowStateDump> {
+            if !self.list_dna_hashes().await?.contains(dna_hash) {
+                return Err(ConductorApiError::DnaMissing(dna_hash.clone()));
+            }
+            let dht_store = self.get_or_create_dht_store(dna_hash)?.as_read();
+            let (sys_validation_attempts, app_validation_attempts) =
+                dht_store.validation_attempts_histograms().await?;
+            let pending_validation_receipts = dht_store
+                .pending_validation_receipts(Vec::new())
+                .await?
+                .len() as u64;
+
+            Ok(WorkflowStateDump {
+                limbo: dht_store.limbo_stage_counts().await?,
+                pending_validation_receipts,
+                oldest_pending_op: dht_store.oldest_pending_limbo_op().await?,
+                sys_validation_attempts,
+                app_validation_attempts,
+                abandoned_ops: dht_store
+                    .abandoned_limbo_ops(WorkflowStateDump::MAX_ABANDONED_OPS)
+                    .await?,
+                queue_consumers: self
+                    .get_queue_consumer_workflows()
+                    .consumer_status_for_dna(dna_hash),
+            })
+        }
+
+        /// Reset the attempt counters of the abandoned ops of a DNA that match
+        /// `filter`, and trigger the sys and app validation workflows so they
+        /// are validated again.
+        ///
+        /// Returns the number of ops that were re-queued.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`ConductorApiError::DnaMissing`] if the DNA is not installed.
+        pub async fn retry_abandoned_ops(
+            &self,
+            dna_hash: &DnaHash,
+            filter: AbandonedOpsFilter,
+        ) -> ConductorApiResult<u64> {
+            if !self.list_dna_hashes().await?.contains(dna_hash) {
+                return Err(ConductorApiError::DnaMissing(dna_hash.clone()));
+            }
+            let AbandonedOpsFilter {
+                op_type,
+                author,
+                basis,
+            } = filter;
+            let retried = self
+                .get_or_create_dht_store(dna_hash)?
+                .reset_abandoned_ops(op_type, author.as_ref(), basis.as_ref())
+                .await?;
+
+            if retried > 0 {
+                tracing::info!(?dna_hash, retried, "Re-queued abandoned ops for validation");
+                let queue_consumers = self.get_queue_consumer_workflows();
+                let dna_hash = Arc::new(dna_hash.clone());
+                if let Some(trigger) = queue_consumers.sys_validation_trigger(dna_hash.clone()) {
+                    trigger.trigger(&"retry_abandoned_ops");
+                }
+                if let Some(trigger) = queue_consumers.app_validation_trigger(dna_hash) {
+                    trigger.trigger(&"retry_abandoned_ops");
+                }
+            }
+
+            Ok(retried)
+        }
+
+        /// Trace an op through this conductor: where it is stored, how far its
+        /// validation has got, its publish state and which peers are
+        /// authorities for its basis.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`ConductorApiError::DnaMissing`] if the DNA is not installed.
+        pub async fn trace_op(
+            &self,
+            dna_hash: &DnaHash,
+            op_hash: &DhtOpHash,
+        ) -> ConductorApiResult<OpTrace> {
+            if !self.list_dna_hashes().await?.contains(dna_hash) {
+                return Err(ConductorApiError::DnaMissing(dna_hash.clone()));
+            }
+            let space = self.get_or_create_space(dna_hash)?;
+            let dht_store = space.dht_store.as_read();
+            let mut trace = dht_store.trace_op(op_hash).await?;
+
+            let pending_sys_validation = trace
+                .limbo
+                .as_ref()
+                .is_some_and(|limbo| limbo.stage == LimboStage::PendingSysValidation);
+            if let (true, Some(action_hash)) = (pending_sys_validation, &trace.action_hash) {
+                if let Some(action) = dht_store.retrieve_action(action_hash).await? {
+                    let deps = space.sys_validation_deps.lock().expect("poisoned");
+                    trace.missing_dependencies = get_dependency_hashes_from_actions(
+                        std::iter::once(action.action().clone()),
+                    )
+                    .into_iter()
+                    .map(|(hash, _)| hash)
+                    .filter(|hash| deps.is_missing(hash))
+                    .collect();
+                }
+            }
+
+            if let Some(basis) = trace.basis.clone() {
+                // A DNA that has not joined the network yet has no arc and no
+                // known peers; leave those fields empty rather than failing.
+                match self
+                    .holochain_p2p
+                    .authority_for_hash(dna_hash.clone(), basis.clone())
+                    .await
+                {
+                    Ok(in_arc) => trace.basis_in_local_arc = Some(in_arc),
+                    Err(holochain_p2p::HolochainP2pError::K2SpaceNotFound(_)) => {}
+                    Err(e) => return Err(e.into()),
+                }
+                match self
+                    .holochain_p2p
+                    .authorities_for_hash(dna_hash.clone(), basis)
+                    .await
+                {
+                    Ok(authorities) => {
+                        trace.authorities = authorities
+                            .into_iter()
+                            .map(|(agent, url)| OpAuthority { agent, url })
+                            .collect();
+                    }
+                    Err(holochain_p2p::HolochainP2pError::K2SpaceNotFound(_)) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+
+            Ok(trace)
+        }
+
         /// Dump of network metrics from Kitsune2.
         pub async fn dump_network_metrics(
             &self,
@@ -2699,6 +3626,198 @@ mod misc_impls {
             })
         }
 
+        /// The round-trip latency measured for each peer URL that is being pinged.
+        pub async fn dump_peer_latency(&self) -> ConductorApiResult<Vec<PeerLatency>> {
+            Ok(self.holochain_p2p.dump_peer_latency().await?)
+        }
+
+        /// Limit the background network traffic of an app, and persist the limits.
+        ///
+        /// The limits apply to the DNAs of all the app's cells. Passing no limits removes them.
+        pub async fn set_bandwidth_limits(
+            &self,
+            installed_app_id: &InstalledAppId,
+            limits: Option<BandwidthLimits>,
+        ) -> ConductorApiResult<()> {
+            let dna_hashes = self.app_dna_hashes(installed_app_id).await?;
+            self.holochain_p2p
+                .set_bandwidth_limits(installed_app_id.clone(), dna_hashes, limits)
+                .await?;
+            match &limits {
+                Some(limits) => {
+                    self.spaces
+                        .conductor_store
+                        .put_bandwidth_limits(installed_app_id, limits)
+                        .await?
+                }
+                None => {
+                    self.spaces
+                        .conductor_store
+                        .delete_bandwidth_limits(installed_app_id)
+                        .await?
+                }
+            }
+            Ok(())
+        }
+
+        /// The bandwidth limits that have been set with [`Conductor::set_bandwidth_limits`].
+        pub async fn list_bandwidth_limits(
+            &self,
+        ) -> ConductorApiResult<HashMap<InstalledAppId, BandwidthLimits>> {
+            Ok(self
+                .spaces
+                .conductor_store
+                .as_read()
+                .get_all_bandwidth_limits()
+                .await?
+                .into_iter()
+                .collect())
+        }
+
+        /// Apply the persisted bandwidth limits to the network, for all apps or for one app.
+        ///
+        /// This is done on startup, and when an app gets a new cell, so that the limits of
+        /// an app cover the DNAs of all its cells.
+        pub(crate) async fn apply_bandwidth_limits(
+            &self,
+            only_app: Option<&InstalledAppId>,
+        ) -> ConductorResult<()> {
+            let all_limits = self
+                .spaces
+                .conductor_store
+                .as_read()
+                .get_all_bandwidth_limits()
+                .await?;
+            for (installed_app_id, limits) in all_limits {
+                if only_app.is_some_and(|only_app| *only_app != installed_app_id) {
+                    continue;
+                }
+                let result = match self.app_dna_hashes(&installed_app_id).await {
+                    Ok(dna_hashes) => self
+                        .holochain_p2p
+                        .set_bandwidth_limits(installed_app_id.clone(), dna_hashes, Some(limits))
+                        .await
+                        .map_err(ConductorError::from),
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = result {
+                    tracing::warn!(
+                        ?err,
+                        %installed_app_id,
+                        ?limits,
+                        "Could not apply bandwidth limits"
+                    );
+                }
+            }
+            Ok(())
+        }
+
+        /// The DNAs of all the cells of an app, including clones.
+        async fn app_dna_hashes(
+            &self,
+            installed_app_id: &InstalledAppId,
+        ) -> ConductorResult<Vec<DnaHash>> {
+            let state = self.get_state().await?;
+            let mut dna_hashes = state
+                .get_app(installed_app_id)?
+                .all_cells()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect::<Vec<_>>();
+            dna_hashes.sort();
+            dna_hashes.dedup();
+            Ok(dna_hashes)
+        }
+
+        /// Set the arc policy for a DNA, or for every DNA of an app, and persist it.
+        ///
+        /// Passing no policy removes the persisted policy and goes back to the target
+        /// arc factor from the network config. Returns the DNAs that the policy was set for.
+        #[cfg(feature = "unstable-sharding")]
+        pub async fn set_arc_policy(
+            &self,
+            target: holochain_conductor_api::ArcPolicyTarget,
+            policy: Option<ArcPolicy>,
+        ) -> ConductorApiResult<Vec<DnaHash>> {
+            use holochain_conductor_api::ArcPolicyTarget;
+
+            let dna_hashes = match target {
+                ArcPolicyTarget::Dna(dna_hash) => vec![dna_hash],
+                ArcPolicyTarget::App(installed_app_id) => {
+                    self.app_dna_hashes(&installed_app_id).await?
+                }
+            };
+
+            // Validate the policy for every DNA before applying it to any, so that an
+            // app is never left with the policy applied to only some of its DNAs.
+            if let Some(policy) = &policy {
+                holochain_p2p::check_arc_policy(policy)?;
+            }
+            let installed_dna_hashes = self.list_dna_hashes().await?;
+            if let Some(dna_hash) = dna_hashes
+                .iter()
+                .find(|dna_hash| !installed_dna_hashes.contains(*dna_hash))
+            {
+                return Err(ConductorApiError::DnaMissing(dna_hash.clone()));
+            }
+
+            for dna_hash in &dna_hashes {
+                // Apply the policy before persisting it, so that a policy the network
+                // rejects is not applied again on the next startup.
+                self.holochain_p2p
+                    .set_arc_policy(dna_hash.clone(), policy)
+                    .await?;
+                match &policy {
+                    Some(policy) => {
+                        self.spaces
+                            .conductor_store
+                            .put_arc_policy(dna_hash, policy)
+                            .await?
+                    }
+                    None => {
+                        self.spaces
+                            .conductor_store
+                            .delete_arc_policy(dna_hash)
+                            .await?
+                    }
+                }
+            }
+
+            Ok(dna_hashes)
+        }
+
+        /// The arc policies that have been set with [`Conductor::set_arc_policy`].
+        #[cfg(feature = "unstable-sharding")]
+        pub async fn list_arc_policies(&self) -> ConductorApiResult<HashMap<DnaHash, ArcPolicy>> {
+            Ok(self
+                .spaces
+                .conductor_store
+                .as_read()
+                .get_all_arc_policies()
+                .await?
+                .into_iter()
+                .collect())
+        }
+
+        /// Apply the persisted arc policies to the network, before any cells join it.
+        pub(crate) async fn apply_arc_policies(&self) -> ConductorResult<()> {
+            let policies = self
+                .spaces
+                .conductor_store
+                .as_read()
+                .get_all_arc_policies()
+                .await?;
+            for (dna_hash, policy) in policies {
+                if let Err(err) = self
+                    .holochain_p2p
+                    .set_arc_policy(dna_hash.clone(), Some(policy))
+                    .await
+                {
+                    tracing::warn!(?err, ?dna_hash, ?policy, "Could not apply arc policy");
+                }
+            }
+            Ok(())
+        }
+
         /// Dump of backend network stats from the Kitsune2 network transport.
         ///
         /// This version of the function filters the stats to only include connections
@@ -2882,13 +4001,159 @@ mod misc_impls {
         }
 
         /// Send a signal directly to the specified agents, bypassing WASM execution
+        ///
+        /// If a `durable_ttl` is given, the signal is put in the outbox for any agents
+        /// that it could not be sent to, and retried until the TTL has passed.
         pub async fn send_direct_signal(
             &self,
             installed_app_id: InstalledAppId,
             dna_hash: DnaHash,
             agents: Vec<AgentPubKey>,
             signal: Vec<u8>,
+            durable_ttl: Option<std::time::Duration>,
         ) -> ConductorResult<()> {
+            let (from_agent, signal_bytes, sig) = self
+                .sign_direct_signal(&installed_app_id, &dna_hash, &agents, &signal, None)
+                .await?;
+
+            let undelivered = self
+                .holochain_p2p()
+                .send_remote_signal_direct(
+                    dna_hash.clone(),
+                    agents,
+                    signal_bytes,
+                    from_agent.clone(),
+                    sig,
+                )
+                .await?;
+
+            if let Some(ttl) = durable_ttl {
+                if !undelivered.is_empty() {
+                    self.queue_outbox_signals(
+                        dna_hash,
+                        from_agent,
+                        undelivered,
+                        OutboxSignalPayload::Direct { signal },
+                        ttl,
+                    )
+                    .await?;
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Send a signal directly to the specified agents, like [`Conductor::send_direct_signal`],
+        /// and wait for each agent's conductor to acknowledge it.
+        ///
+        /// Sending to an agent that does not answer is retried a few times before the agent
+        /// is reported as [`DirectSignalOutcome::Unreachable`]. If a `durable_ttl` is given,
+        /// the signal is put in the outbox for those agents.
+        ///
+        /// Every attempt carries the same idempotency key, so an agent whose
+        /// acknowledgement was lost receives the signal only once.
+        pub async fn send_direct_signal_acked(
+            &self,
+            installed_app_id: InstalledAppId,
+            dna_hash: DnaHash,
+            agents: Vec<AgentPubKey>,
+            signal: Vec<u8>,
+            durable_ttl: Option<std::time::Duration>,
+        ) -> ConductorResult<Vec<DirectSignalAck>> {
+            let idempotency_key = rand::random::<u64>();
+            let (from_agent, signal_bytes, sig) = self
+                .sign_direct_signal(
+                    &installed_app_id,
+                    &dna_hash,
+                    &agents,
+                    &signal,
+                    Some(idempotency_key),
+                )
+                .await?;
+
+            let acks = futures::future::join_all(agents.into_iter().map(|agent| {
+                let dna_hash = dna_hash.clone();
+                let signal_bytes = signal_bytes.clone();
+                let from_agent = from_agent.clone();
+                let sig = sig.clone();
+                async move {
+                    let mut retry_delay = DIRECT_SIGNAL_ACK_RETRY_DELAY;
+                    let mut outcome = DirectSignalOutcome::Unreachable;
+                    for attempt in 1..=DIRECT_SIGNAL_ACK_ATTEMPTS {
+                        match self
+                            .holochain_p2p()
+                            .send_remote_signal_direct_acked(
+                                dna_hash.clone(),
+                                agent.clone(),
+                                signal_bytes.clone(),
+                                from_agent.clone(),
+                                sig.clone(),
+                                idempotency_key,
+                                holochain_p2p::actor::NetworkRequestOptions {
+                                    timeout_ms: Some(DIRECT_SIGNAL_ACK_TIMEOUT.as_millis() as u64),
+                                    ..Default::default()
+                                },
+                            )
+                            .await
+                        {
+                            Ok(acked) => {
+                                outcome = acked;
+                                break;
+                            }
+                            Err(err) => {
+                                tracing::debug!(
+                                    ?err,
+                                    ?agent,
+                                    attempt,
+                                    "Direct signal not acknowledged"
+                                );
+                                if attempt < DIRECT_SIGNAL_ACK_ATTEMPTS {
+                                    tokio::time::sleep(retry_delay).await;
+                                    retry_delay *= 2;
+                                }
+                            }
+                        }
+                    }
+                    DirectSignalAck { agent, outcome }
+                }
+            }))
+            .await;
+
+            if let Some(ttl) = durable_ttl {
+                let unreachable = acks
+                    .iter()
+                    .filter(|ack| ack.outcome == DirectSignalOutcome::Unreachable)
+                    .map(|ack| ack.agent.clone())
+                    .collect::<Vec<_>>();
+                if !unreachable.is_empty() {
+                    self.queue_outbox_signals(
+                        dna_hash,
+                        from_agent,
+                        unreachable,
+                        OutboxSignalPayload::Direct { signal },
+                        ttl,
+                    )
+                    .await?;
+                }
+            }
+
+            Ok(acks)
+        }
+
+        /// Check that a direct signal can be sent by an app, and sign it as the app's agent.
+        ///
+        /// A signal that is sent with acknowledgement is signed along with its
+        /// `idempotency_key`, see [`AckedDirectSignal`].
+        ///
+        /// Returns the sending agent, the encoded signal and its signature.
+        async fn sign_direct_signal(
+            &self,
+            installed_app_id: &InstalledAppId,
+            dna_hash: &DnaHash,
+            agents: &[AgentPubKey],
+            signal: &[u8],
+            idempotency_key: Option<u64>,
+        ) -> ConductorResult<(AgentPubKey, Vec<u8>, Signature)> {
             if agents.is_empty() {
                 return Err(ConductorError::Other("No agents to signal".into()));
             }
@@ -2903,7 +4168,7 @@ mod misc_impls {
                 ));
             }
 
-            let app_info = self.get_app_info(&installed_app_id).await?.ok_or_else(|| {
+            let app_info = self.get_app_info(installed_app_id).await?.ok_or_else(|| {
                 ConductorError::other(format!("App not installed: {installed_app_id}"))
             })?;
 
@@ -2912,33 +4177,30 @@ mod misc_impls {
                 .values()
                 .flatten()
                 .find(|c| match c {
-                    CellInfo::Provisioned(cell) => cell.cell_id.dna_hash() == &dna_hash,
-                    CellInfo::Cloned(cell) => cell.cell_id.dna_hash() == &dna_hash,
-                    CellInfo::Stem(cell) => cell.original_dna_hash == dna_hash,
+                    CellInfo::Provisioned(cell) => cell.cell_id.dna_hash() == dna_hash,
+                    CellInfo::Cloned(cell) => cell.cell_id.dna_hash() == dna_hash,
+                    CellInfo::Stem(cell) => &cell.original_dna_hash == dna_hash,
                 })
                 .is_some();
             if !dna_belongs_to_app {
                 return Err(ConductorError::Other(format!("Attempted to send to DNA hash {dna_hash:?} but it was not found in app {installed_app_id}").into()));
             }
 
-            let signal_bytes = holochain_serialized_bytes::encode(&DirectSignal(signal))?;
+            let signal_bytes = holochain_serialized_bytes::encode(&DirectSignal(signal.to_vec()))?;
+            let signed_bytes = match idempotency_key {
+                Some(idempotency_key) => holochain_serialized_bytes::encode(&AckedDirectSignal {
+                    signal: signal.to_vec(),
+                    idempotency_key,
+                })?,
+                None => signal_bytes.clone(),
+            };
 
             let sig = self
                 .keystore()
-                .sign(app_info.agent_pub_key.clone(), signal_bytes.clone().into())
-                .await?;
-
-            self.holochain_p2p()
-                .send_remote_signal_direct(
-                    dna_hash,
-                    agents,
-                    signal_bytes,
-                    app_info.agent_pub_key,
-                    sig,
-                )
+                .sign(app_info.agent_pub_key.clone(), signed_bytes.into())
                 .await?;
 
-            Ok(())
+            Ok((app_info.agent_pub_key, signal_bytes, sig))
         }
     }
 }
@@ -3291,6 +4553,15 @@ impl Conductor {
             tracing::info!(?dnas_to_purge, "Purging DNAs");
         }
 
+        // Signals that the cells were still trying to send would have no one
+        // to report their outcome to.
+        for cell_id in &cell_ids {
+            self.spaces
+                .conductor_store
+                .delete_outbox_signals_from(cell_id.dna_hash(), cell_id.agent_pubkey())
+                .await?;
+        }
+
         // For any DNAs no longer represented in any installed app, delete the
         // per-DNA store so a reinstall doesn't inherit stale rows from the
         // previous installation.
@@ -3324,6 +4595,16 @@ impl Conductor {
             // of this DNA opens fresh, migrated databases rather than reusing
             // pools that point at the deleted files.
             self.spaces.remove_space(dna_hash);
+
+            // Arc policies are set for the DNAs of installed apps, so a DNA's
+            // policy goes with the last app that uses it.
+            self.spaces
+                .conductor_store
+                .delete_arc_policy(dna_hash)
+                .await?;
+            self.holochain_p2p
+                .set_arc_policy(dna_hash.clone(), None)
+                .await?;
         }
 
         Ok(())
@@ -3571,6 +4852,7 @@ pub fn app_manifest_from_dnas(
                     clone_limit,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
+                network: None,
             }
         })
         .collect();
@@ -3727,6 +5009,23 @@ fn get_init_properties_map_from_role_settings(
     }
 }
 
+/// Extract the network settings from the RoleSettingsMap into their own HashMap
+fn get_network_map_from_role_settings(roles_settings: &Option<RoleSettingsMap>) -> RoleNetworkMap {
+    match roles_settings {
+        Some(role_settings_map) => role_settings_map
+            .iter()
+            .filter_map(|(role_name, role_settings)| match role_settings {
+                #[allow(deprecated)]
+                RoleSettings::UseExisting { .. } => None,
+                RoleSettings::Provisioned { network, .. } => {
+                    network.as_ref().map(|n| (role_name.clone(), n.clone()))
+                }
+            })
+            .collect(),
+        None => HashMap::new(),
+    }
+}
+
 /// Extract the existing cells ids from the RoleSettingsMap into their own HashMap
 fn get_existing_cells_map_from_role_settings(
     roles_settings: &Option<RoleSettingsMap>,
@@ -38,6 +38,10 @@ pub struct ConductorBuilder {
     /// were installed on a previous run.
     #[cfg(feature = "test_utils")]
     pub inline_zome_store: Option<crate::core::ribosome::inline_ribosome::InlineZomeStore>,
+
+    /// A callback to wrap the network transport with, for example to inject faults.
+    #[cfg(feature = "test_utils")]
+    pub wrap_transport: Option<holochain_p2p::WrapTransportFactory>,
 }
 
 impl ConductorBuilder {
@@ -359,6 +363,10 @@ impl ConductorBuilder {
 
         info!("Conductor startup: scheduler task started.");
 
+        conductor
+            .clone()
+            .start_signal_outbox(signal_outbox_impls::SIGNAL_OUTBOX_INTERVAL);
+
         let tm = conductor.task_manager();
         let conductor2 = conductor.clone();
         tm.add_conductor_task_unrecoverable("post_commit_receiver", move |stop| {
@@ -395,6 +403,16 @@ impl ConductorBuilder {
         self
     }
 
+    /// Wrap the network transport
+    #[cfg(feature = "test_utils")]
+    pub fn with_wrap_transport(
+        mut self,
+        wrap_transport: holochain_p2p::WrapTransportFactory,
+    ) -> Self {
+        self.wrap_transport = Some(wrap_transport);
+        self
+    }
+
     /// Build a Conductor with a test environment
     #[cfg(any(test, feature = "test_utils"))]
     #[cfg_attr(feature = "instrument", tracing::instrument(skip_all, fields(scope = self.config.network.tracing_scope)))]
@@ -492,6 +510,8 @@ impl ConductorBuilder {
             disable_publish: config.network.disable_publish,
             #[cfg(feature = "test_utils")]
             disable_gossip: config.network.disable_gossip,
+            #[cfg(feature = "test_utils")]
+            wrap_transport: builder.wrap_transport,
             ..Default::default()
         };
 
@@ -38,11 +38,19 @@ impl holochain_p2p::event::HcP2pHandler for Conductor {
         signal: Vec<u8>,
         from_agent: AgentPubKey,
         signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
-        Box::pin(async {
+        idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
+        Box::pin(async move {
             self.cell_by_parts(&dna_hash, &to_agent)
                 .await?
-                .handle_remote_signal_direct(dna_hash, to_agent, signal, from_agent, signature)
+                .handle_remote_signal_direct(
+                    dna_hash,
+                    to_agent,
+                    signal,
+                    from_agent,
+                    signature,
+                    idempotency_key,
+                )
                 .await
         })
     }
@@ -450,6 +450,7 @@ async fn test_deferred_memproof_provisioning() {
             network_seed: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -567,6 +568,7 @@ async fn test_deferred_memproof_provisioning_uninstall() {
             network_seed: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -601,6 +603,7 @@ async fn init_properties_persist_and_clear_on_uninstall() {
             membrane_proof: None,
             modifiers: None,
             init_properties: Some(props.clone()),
+            network: None,
         },
     );
 
@@ -614,6 +617,7 @@ async fn init_properties_persist_and_clear_on_uninstall() {
             network_seed: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -654,6 +654,7 @@ async fn app_status_and_cell_state() {
             roles_settings: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -773,6 +774,7 @@ async fn app_status_and_cell_state() {
             roles_settings: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -912,6 +914,7 @@ async fn make_app(
                 clone_limit,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
+            network: None,
         };
         app_role_manifests.push(app_role_manifest);
         let dna_bundle = DnaBundle::from_dna_file(dna_file.clone()).unwrap();
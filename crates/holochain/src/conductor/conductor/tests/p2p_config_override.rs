@@ -1,10 +1,19 @@
+use holo_hash::{ActionHash, DnaHash};
 use holochain_conductor_api::conductor::ConductorConfig;
-use holochain_types::app::{AppManifest, AppManifestV0};
+use holochain_types::app::{
+    AppManifest, AppManifestV0, AppRoleDnaManifest, AppRoleManifest, RoleNetworkConfig,
+};
+use holochain_wasm_test_utils::TestWasm;
+use holochain_zome_types::prelude::{CellId, DnaModifiersOpt, Record};
 use kitsune2_test_utils::bootstrap::TestBootstrapSrv;
 
 use crate::{
     conductor::Conductor,
-    sweettest::{SweetConductor, SweetLocalRendezvous},
+    retry_until_timeout,
+    sweettest::{
+        SweetConductor, SweetConductorBatch, SweetConductorConfig, SweetDnaFile,
+        SweetLocalRendezvous,
+    },
 };
 
 #[tokio::test(flavor = "multi_thread")]
@@ -137,6 +146,246 @@ async fn should_override_space_config_with_relay_url() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn should_override_space_network_settings_per_role() {
+    holochain_trace::test_run();
+    let (private_dna, _, _) = crate::conductor::conductor::tests::mk_dna(
+        crate::test_utils::inline_zomes::simple_crud_zome(),
+    )
+    .await;
+    let (public_dna, _, _) = crate::conductor::conductor::tests::mk_dna(
+        crate::test_utils::inline_zomes::simple_crud_zome(),
+    )
+    .await;
+
+    let rendezvous = SweetLocalRendezvous::new().await;
+    let mut conductor =
+        SweetConductor::from_config_rendezvous(ConductorConfig::default(), rendezvous).await;
+
+    let private_network = RoleNetworkConfig {
+        disable_gossip: Some(true),
+        request_timeout_s: Some(5),
+        advanced: Some(serde_json::json!({
+            "k2Gossip": { "initiateIntervalMs": 60000 }
+        })),
+        ..Default::default()
+    };
+    let app_id = "app_id".to_string();
+    let manifest = AppManifest::V0(AppManifestV0 {
+        allow_deferred_memproofs: false,
+        description: None,
+        name: "dummy".to_string(),
+        roles: vec![AppRoleManifest {
+            name: "private".to_string(),
+            provisioning: None,
+            dna: AppRoleDnaManifest {
+                path: None,
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: None,
+                clone_limit: 0,
+            },
+            network: Some(private_network.clone()),
+        }],
+        bootstrap_url: None,
+        relay_url: None,
+    });
+
+    conductor
+        .install_app_with_manifest(
+            &app_id,
+            None,
+            [
+                &("private".to_string(), private_dna.clone()),
+                &("public".to_string(), public_dna.clone()),
+            ],
+            None,
+            manifest,
+        )
+        .await
+        .expect("failed to install app");
+
+    conductor
+        .enable_app(app_id.clone())
+        .await
+        .expect("failed to enable app");
+
+    let cell_for_dna = |dna_hash: &DnaHash| {
+        conductor
+            .running_cell_ids()
+            .into_iter()
+            .find(|cell_id| cell_id.dna_hash() == dna_hash)
+            .expect("should have cell")
+    };
+
+    let private_cell = conductor
+        .cell_by_id(&cell_for_dna(private_dna.dna_hash()))
+        .await
+        .expect("should get cell");
+    assert_eq!(
+        private_cell.overrides().network,
+        private_network,
+        "cell network settings should be overridden by its role in the app manifest"
+    );
+
+    let public_cell = conductor
+        .cell_by_id(&cell_for_dna(public_dna.dna_hash()))
+        .await
+        .expect("should get cell");
+    assert!(
+        !public_cell.overrides().is_overriding(),
+        "cell of a role without network settings should not be overridden"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn role_without_gossip_and_publish_does_not_sync() {
+    holochain_trace::test_run();
+    let (private_dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let (public_dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+
+    let mut conductors =
+        SweetConductorBatch::from_config_rendezvous(2, SweetConductorConfig::standard()).await;
+
+    let app_id = "app_id".to_string();
+    let manifest = AppManifest::V0(AppManifestV0 {
+        allow_deferred_memproofs: false,
+        description: None,
+        name: "dummy".to_string(),
+        roles: vec![AppRoleManifest {
+            name: "private".to_string(),
+            provisioning: None,
+            dna: AppRoleDnaManifest {
+                path: None,
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: None,
+                clone_limit: 0,
+            },
+            network: Some(RoleNetworkConfig {
+                disable_gossip: Some(true),
+                disable_publish: Some(true),
+                ..Default::default()
+            }),
+        }],
+        bootstrap_url: None,
+        relay_url: None,
+    });
+
+    let mut agents = Vec::new();
+    for i in 0..2 {
+        let agent = conductors[i]
+            .install_app_with_manifest(
+                &app_id,
+                None,
+                [
+                    &("private".to_string(), private_dna.clone()),
+                    &("public".to_string(), public_dna.clone()),
+                ],
+                None,
+                manifest.clone(),
+            )
+            .await
+            .expect("failed to install app");
+        conductors[i]
+            .enable_app(app_id.clone())
+            .await
+            .expect("failed to enable app");
+        agents.push(agent);
+    }
+
+    conductors.exchange_peer_info().await;
+    for conductor in conductors.iter() {
+        conductor
+            .declare_full_storage_arcs(private_dna.dna_hash())
+            .await;
+        conductor
+            .declare_full_storage_arcs(public_dna.dna_hash())
+            .await;
+    }
+
+    let cell = |i: usize, dna_hash: &DnaHash| {
+        conductors[i]
+            .get_sweet_cell(CellId::new(dna_hash.clone(), agents[i].clone()))
+            .expect("should get cell")
+    };
+    let alice_private = cell(0, private_dna.dna_hash());
+    let alice_public = cell(0, public_dna.dna_hash());
+    let bob_private = cell(1, private_dna.dna_hash());
+    let bob_public = cell(1, public_dna.dna_hash());
+
+    let private_hash: ActionHash = conductors[0]
+        .call(&alice_private.zome(TestWasm::Create), "create_entry", ())
+        .await;
+    let public_hash: ActionHash = conductors[0]
+        .call(&alice_public.zome(TestWasm::Create), "create_entry", ())
+        .await;
+
+    // The role without network settings publishes and gossips as usual.
+    retry_until_timeout!(30_000, {
+        let record: Option<Record> = conductors[1]
+            .call(
+                &bob_public.zome(TestWasm::Create),
+                "get_post",
+                public_hash.clone(),
+            )
+            .await;
+        if record.is_some() {
+            break;
+        }
+    });
+
+    // By now the private entry would have reached Bob too if it was published or gossiped.
+    let record: Option<Record> = conductors[1]
+        .call(
+            &bob_private.zome(TestWasm::Create),
+            "get_post",
+            private_hash,
+        )
+        .await;
+    assert!(
+        record.is_none(),
+        "role with gossip and publish disabled should not sync"
+    );
+}
+
+#[test]
+fn should_get_override_config_for_role_with_network_settings() {
+    let network = RoleNetworkConfig {
+        disable_publish: Some(true),
+        target_arc_factor: Some(0),
+        ..Default::default()
+    };
+    let manifest = AppManifest::V0(AppManifestV0 {
+        allow_deferred_memproofs: false,
+        description: None,
+        name: "dummy".to_string(),
+        roles: vec![AppRoleManifest {
+            name: "role".to_string(),
+            provisioning: None,
+            dna: AppRoleDnaManifest {
+                path: None,
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: None,
+                clone_limit: 0,
+            },
+            network: Some(network.clone()),
+        }],
+        bootstrap_url: Some("http://localhost:1234".to_string()),
+        relay_url: None,
+    });
+
+    let config = Conductor::p2p_config_overrides(&manifest, &"role".to_string())
+        .expect("no config override returned");
+    assert_eq!(config.network, network);
+    assert_eq!(
+        config.bootstrap_url.as_deref(),
+        Some("http://localhost:1234")
+    );
+
+    let config = Conductor::p2p_config_overrides(&manifest, &"other_role".to_string())
+        .expect("no config override returned");
+    assert_eq!(config.network, RoleNetworkConfig::default());
+}
+
 #[test]
 fn should_not_get_override_configuration_if_no_urls() {
     let manifest = AppManifest::V0(AppManifestV0 {
@@ -147,7 +396,7 @@ fn should_not_get_override_configuration_if_no_urls() {
         bootstrap_url: None,
         relay_url: None,
     });
-    let config = Conductor::p2p_config_overrides(&manifest);
+    let config = Conductor::p2p_config_overrides(&manifest, &"role".to_string());
 
     assert!(config.is_none());
 }
@@ -162,7 +411,8 @@ fn should_get_override_config_with_bootstrap_url() {
         bootstrap_url: Some("http://localhost:1234".to_string()),
         relay_url: None,
     });
-    let config = Conductor::p2p_config_overrides(&manifest).expect("no config override returned");
+    let config = Conductor::p2p_config_overrides(&manifest, &"role".to_string())
+        .expect("no config override returned");
 
     assert_eq!(
         config.bootstrap_url.as_deref(),
@@ -180,7 +430,8 @@ fn should_get_override_config_with_bootstrap_url_and_relay_url() {
         bootstrap_url: Some("http://localhost:1234".to_string()),
         relay_url: Some("http://localhost:5678".to_string()),
     });
-    let config = Conductor::p2p_config_overrides(&manifest).expect("no config override returned");
+    let config = Conductor::p2p_config_overrides(&manifest, &"role".to_string())
+        .expect("no config override returned");
 
     assert_eq!(
         config.bootstrap_url.as_deref(),
@@ -0,0 +1,124 @@
+//! Admission control for zome calls made through app interfaces.
+
+use crate::conductor::config::ZomeCallLimits;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Admits or rejects zome calls according to a set of [`ZomeCallLimits`].
+///
+/// Calls over a limit are rejected immediately instead of waiting for
+/// capacity, so that clients can back off.
+pub(crate) struct ZomeCallLimiter {
+    concurrency: Option<Arc<Semaphore>>,
+    rate: Option<parking_lot::Mutex<TokenBucket>>,
+}
+
+/// Held for the duration of an admitted zome call.
+///
+/// Dropping the permit frees its slot for another concurrent call.
+pub(crate) struct ZomeCallPermit(#[allow(dead_code)] Option<OwnedSemaphorePermit>);
+
+impl ZomeCallLimiter {
+    /// Create a limiter that enforces `limits`.
+    pub(crate) fn new(limits: ZomeCallLimits) -> Self {
+        Self {
+            concurrency: limits
+                .max_concurrent_calls
+                .map(|max| Arc::new(Semaphore::new(max as usize))),
+            rate: limits
+                .max_calls_per_second
+                .map(|max| parking_lot::Mutex::new(TokenBucket::new(max))),
+        }
+    }
+
+    /// Admit a zome call, or return the reason it was rejected.
+    pub(crate) fn try_admit(&self) -> Result<ZomeCallPermit, String> {
+        let permit = match &self.concurrency {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .try_acquire_owned()
+                    .map_err(|_| "too many concurrent zome calls".to_string())?,
+            ),
+            None => None,
+        };
+
+        if let Some(rate) = &self.rate {
+            if !rate.lock().try_take(Instant::now()) {
+                return Err("too many zome calls per second".to_string());
+            }
+        }
+
+        Ok(ZomeCallPermit(permit))
+    }
+}
+
+/// Token bucket that refills at `per_second` tokens per second, up to a
+/// burst of `per_second` tokens.
+struct TokenBucket {
+    per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(per_second: u32) -> Self {
+        Self {
+            per_second: per_second as f64,
+            tokens: per_second as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.per_second).min(self.per_second);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn concurrent_calls_over_limit_are_rejected() {
+        let limiter = ZomeCallLimiter::new(ZomeCallLimits {
+            max_concurrent_calls: Some(2),
+            max_calls_per_second: None,
+        });
+
+        let first = limiter.try_admit().unwrap();
+        let _second = limiter.try_admit().unwrap();
+        assert!(limiter.try_admit().is_err());
+
+        drop(first);
+        assert!(limiter.try_admit().is_ok());
+    }
+
+    #[test]
+    fn token_bucket_refills_over_time() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket {
+            per_second: 2.0,
+            tokens: 2.0,
+            last_refill: start,
+        };
+
+        assert!(bucket.try_take(start));
+        assert!(bucket.try_take(start));
+        assert!(!bucket.try_take(start));
+
+        assert!(bucket.try_take(start + Duration::from_millis(500)));
+        assert!(!bucket.try_take(start + Duration::from_millis(500)));
+    }
+}
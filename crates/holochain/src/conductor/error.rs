@@ -128,6 +128,15 @@ pub enum ConductorError {
     #[error("Agent key {0} is not present in the local Lair keystore")]
     AgentKeyNotInKeystore(holo_hash::AgentPubKey),
 
+    #[error("The app bundle was rejected by the trusted publishers config: {0}")]
+    UntrustedAppBundle(String),
+
+    #[error("App {0} can't use the cells of app {1}, because their wasm metering budgets differ. A cell runs with the budgets of the app that created it.")]
+    ConflictingWasmMetering(InstalledAppId, InstalledAppId),
+
+    #[error("The UI of web app {0} can't be served, because its ID isn't a valid host name label. Use only letters, digits and '-' in the IDs of web apps.")]
+    InvalidWebAppId(InstalledAppId),
+
     #[error("Authentication failed with reason: {0}")]
     FailedAuthenticationError(String),
 
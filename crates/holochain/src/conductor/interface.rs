@@ -2,10 +2,12 @@
 //! and dispatch them to the appropriate handlers within Holochain.
 //! They also allow emitting responses and one-way Signals.
 //!
-//! Currently, the only InterfaceDriver is a Websocket-based one, whose
-//! implementation can be found in the `websocket` module here.
+//! The Websocket-based InterfaceDriver implementation can be found in the
+//! `websocket` module here. The `web_ui` module implements the HTTP driver,
+//! which serves the UIs of installed web apps.
 
 pub mod error;
+pub mod web_ui;
 pub mod websocket;
 
 pub use holochain_conductor_api::config::InterfaceDriver;
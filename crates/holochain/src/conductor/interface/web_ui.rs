@@ -0,0 +1,508 @@
+//! Module for serving the UIs of web apps over HTTP,
+//! i.e. interfaces configured with `InterfaceDriver::Http`
+//!
+//! The UI of an app is served under its own origin, which has the app ID as the
+//! first label of the host, e.g. `http://my-app.localhost:8888`. Browsers resolve
+//! any `*.localhost` host to the local machine, so this works without any DNS
+//! setup when serving locally.
+//!
+//! The `Host` header a request is routed by is set by the client, so UIs are
+//! only served for origins listed explicitly in the interface's allowed
+//! origins, and the `Origin` and `Sec-Fetch-*` headers that browsers set
+//! themselves must not point to another site. This keeps pages on other sites
+//! from reading UIs, including through DNS rebinding.
+//!
+//! HTML pages get the connection details for the app injected as
+//! `window.__HC_LAUNCHER_ENV__`, which is where the Holochain client libraries
+//! look for them. Each app gets one app interface for its UI, which only
+//! accepts connections from the UI's origins, and the token is issued for a
+//! single use and expires shortly after. Tokens are only injected into pages
+//! requested from the local machine, since any client can set the headers a
+//! request is checked by, and a token gives access to the app API.
+//!
+//! Hosts and origins are compared in lower case, as browsers send them.
+
+use super::error::{InterfaceError, InterfaceResult};
+use super::websocket::determine_bind_socket_addrs;
+use crate::conductor::manager::TaskManagerClient;
+use crate::conductor::ConductorHandle;
+use axum::extract::{ConnectInfo, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
+use holochain_types::app::InstalledAppId;
+use holochain_types::websocket::AllowedOrigins;
+use std::collections::HashSet;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::*;
+
+/// The page served for paths that don't match a file in the UI zip.
+const INDEX_HTML: &str = "index.html";
+
+/// How long the token injected into a page can be used to connect for.
+const TOKEN_EXPIRY_SECONDS: u64 = 10;
+
+#[derive(Clone)]
+struct WebUiState {
+    conductor: ConductorHandle,
+    allowed_origins: HashSet<String>,
+}
+
+/// Bind an HTTP listener and spawn a task that serves web app UIs from it.
+///
+/// Fails if `allowed_origins` is [`AllowedOrigins::Any`], because the origin a
+/// UI is served under can't be trusted unless it is listed.
+///
+/// Returns the port that was bound, which is useful when `port` is 0.
+pub async fn spawn_web_ui_interface(
+    tm: TaskManagerClient,
+    conductor: ConductorHandle,
+    port: u16,
+    danger_bind_addr: Option<String>,
+    allowed_origins: AllowedOrigins,
+) -> InterfaceResult<u16> {
+    let AllowedOrigins::Origins(allowed_origins) = allowed_origins else {
+        return Err(InterfaceError::Other(
+            "UI interfaces must list their allowed origins, any origin (`*`) is not allowed"
+                .to_string(),
+        ));
+    };
+
+    let (ipv4_sock_addr, ipv6_sock_addr) = determine_bind_socket_addrs(port, danger_bind_addr)?;
+    let ipv4_listener = tokio::net::TcpListener::bind(ipv4_sock_addr).await?;
+    let port = ipv4_listener.local_addr()?.port();
+    let ipv6_listener = match tokio::net::TcpListener::bind((*ipv6_sock_addr.ip(), port)).await {
+        Ok(listener) => Some(listener),
+        Err(e) => {
+            warn!(
+                ?e,
+                "Failed to bind the UI interface to IPv6, serving on IPv4 only"
+            );
+            None
+        }
+    };
+    info!(
+        "Binding UI interface to IPv4: {}, IPv6: {}",
+        ipv4_sock_addr, ipv6_sock_addr
+    );
+
+    let allowed_origins = allowed_origins
+        .iter()
+        .map(|origin| origin.to_ascii_lowercase())
+        .collect();
+    let router = axum::Router::new()
+        .fallback(serve_web_ui)
+        .with_state(Arc::new(WebUiState {
+            conductor,
+            allowed_origins,
+        }))
+        .into_make_service_with_connect_info::<SocketAddr>();
+
+    tm.add_conductor_task_ignored(&format!("UI interface, port {port}"), move || async move {
+        let serve_ipv6 = async {
+            match ipv6_listener {
+                Some(listener) => axum::serve(listener, router.clone()).await,
+                None => std::future::pending().await,
+            }
+        };
+        let result = tokio::select! {
+            result = axum::serve(ipv4_listener, router.clone()) => result,
+            result = serve_ipv6 => result,
+        };
+        if let Err(e) = result {
+            warn!(?e, "UI interface stopped");
+        }
+        Ok(())
+    });
+
+    Ok(port)
+}
+
+async fn serve_web_ui(
+    State(state): State<Arc<WebUiState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    uri: Uri,
+) -> Response {
+    match try_serve_web_ui(&state, peer, &headers, &uri).await {
+        Ok(response) => response,
+        Err(status) => status.into_response(),
+    }
+}
+
+async fn try_serve_web_ui(
+    state: &WebUiState,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+    uri: &Uri,
+) -> Result<Response, StatusCode> {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?
+        .to_ascii_lowercase();
+    let origin = format!("http://{host}");
+    if !state.allowed_origins.contains(&origin) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    check_fetch_metadata(headers, &origin)?;
+
+    let installed_app_id = find_app_for_host(&state.conductor, &host)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let zip_path = state
+        .conductor
+        .web_ui_path(&installed_app_id)
+        .filter(|path| path.is_file())
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let file_name = match uri.path().trim_start_matches('/') {
+        "" => INDEX_HTML.to_string(),
+        path if path.ends_with('/') => format!("{path}{INDEX_HTML}"),
+        path => path.to_string(),
+    };
+    let (file_name, content) =
+        tokio::task::spawn_blocking(move || read_ui_file(zip_path, file_name))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map_err(|e| {
+                warn!(?e, %installed_app_id, "Failed to read web UI");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(StatusCode::NOT_FOUND)?;
+
+    let content_type = content_type(&file_name);
+    if content_type.starts_with("text/html") && !peer.ip().to_canonical().is_loopback() {
+        debug!(%peer, %installed_app_id, "Serving a web UI to a remote client without a token");
+    } else if content_type.starts_with("text/html") {
+        let env = launcher_env(&state.conductor, &installed_app_id, &origin)
+            .await
+            .map_err(|e| {
+                warn!(?e, %installed_app_id, "Failed to connect a web UI to its app");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        let html = inject_launcher_env(&String::from_utf8_lossy(&content), &env);
+        return Ok((
+            [
+                (header::CONTENT_TYPE, HeaderValue::from_static(content_type)),
+                (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+            ],
+            html,
+        )
+            .into_response());
+    }
+
+    Ok((
+        [(header::CONTENT_TYPE, HeaderValue::from_static(content_type))],
+        content,
+    )
+        .into_response())
+}
+
+/// Check the headers that browsers set on requests themselves, which pages
+/// can't change, against the origin the UI is served under.
+///
+/// Requests made by another site are refused, except for navigating to the UI,
+/// since the site can't read the page it navigates to. Clients other than
+/// browsers don't send these headers.
+fn check_fetch_metadata(headers: &HeaderMap, origin: &str) -> Result<(), StatusCode> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    if header("origin").is_some_and(|request_origin| !request_origin.eq_ignore_ascii_case(origin)) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    match (header("sec-fetch-site"), header("sec-fetch-mode")) {
+        (None | Some("same-origin" | "none"), _) | (Some(_), Some("navigate")) => Ok(()),
+        _ => Err(StatusCode::FORBIDDEN),
+    }
+}
+
+/// The origins among `allowed_origins` that the UI of an app is served under,
+/// i.e. those whose host has the app ID as its first label, in lower case.
+pub(crate) fn app_origins<'a>(
+    allowed_origins: impl IntoIterator<Item = &'a String>,
+    installed_app_id: &InstalledAppId,
+) -> HashSet<String> {
+    allowed_origins
+        .into_iter()
+        .filter(|origin| {
+            origin
+                .strip_prefix("http://")
+                .and_then(|host| host.split_once('.'))
+                .is_some_and(|(label, _)| label.eq_ignore_ascii_case(installed_app_id))
+        })
+        .map(|origin| origin.to_ascii_lowercase())
+        .collect()
+}
+
+/// Whether an app ID can be the first label of the host its UI is served
+/// under, i.e. 1 to 63 letters, digits and hyphens that don't start or end
+/// with a hyphen.
+pub(crate) fn is_host_label(installed_app_id: &str) -> bool {
+    (1..=63).contains(&installed_app_id.len())
+        && installed_app_id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        && !installed_app_id.starts_with('-')
+        && !installed_app_id.ends_with('-')
+}
+
+/// Find the installed app whose ID is the first label of `host`.
+///
+/// Browsers lower-case hosts, so the app ID is matched case-insensitively.
+async fn find_app_for_host(conductor: &ConductorHandle, host: &str) -> Option<InstalledAppId> {
+    let (label, _) = host.split_once('.')?;
+    conductor
+        .list_apps(None)
+        .await
+        .ok()?
+        .into_iter()
+        .map(|app| app.installed_app_id)
+        .find(|id| id.eq_ignore_ascii_case(label))
+}
+
+/// Read a file from a UI zip, falling back to `index.html` for paths without an
+/// extension so that UIs with client-side routing work.
+fn read_ui_file(
+    zip_path: PathBuf,
+    file_name: String,
+) -> InterfaceResult<Option<(String, Vec<u8>)>> {
+    let file = std::fs::File::open(zip_path)?;
+    let mut archive = zip::ZipArchive::new(std::io::BufReader::new(file))
+        .map_err(|e| InterfaceError::Other(e.to_string()))?;
+
+    let has_extension = file_name
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.contains('.'));
+    for name in [file_name.as_str(), INDEX_HTML] {
+        let mut entry = match archive.by_name(name) {
+            Ok(entry) if entry.is_file() => entry,
+            Ok(_) | Err(zip::result::ZipError::FileNotFound) => {
+                if has_extension {
+                    return Ok(None);
+                }
+                continue;
+            }
+            Err(e) => return Err(InterfaceError::Other(e.to_string())),
+        };
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        return Ok(Some((name.to_string(), content)));
+    }
+    Ok(None)
+}
+
+/// Find the app interface for the UI of an app, and issue a token for it.
+///
+/// The interface is attached when the web app is installed, or when the UI
+/// interface is added, so page loads never attach interfaces.
+async fn launcher_env(
+    conductor: &ConductorHandle,
+    installed_app_id: &InstalledAppId,
+    origin: &str,
+) -> InterfaceResult<serde_json::Value> {
+    let port = conductor
+        .list_app_interfaces()
+        .await
+        .map_err(|e| InterfaceError::RequestHandler(Box::new(e)))?
+        .into_iter()
+        .find(|interface| {
+            interface.installed_app_id.as_ref() == Some(installed_app_id)
+                && matches!(
+                    &interface.allowed_origins,
+                    AllowedOrigins::Origins(origins)
+                        if origins.iter().any(|o| o.eq_ignore_ascii_case(origin))
+                )
+        })
+        .map(|interface| interface.port)
+        .ok_or_else(|| {
+            InterfaceError::Other(format!(
+                "No app interface accepts connections from {origin}"
+            ))
+        })?;
+
+    let issued = conductor
+        .issue_app_authentication_token(
+            IssueAppAuthenticationTokenPayload::for_installed_app_id(installed_app_id.clone())
+                .expiry_seconds(TOKEN_EXPIRY_SECONDS),
+        )
+        .map_err(|e| InterfaceError::RequestHandler(Box::new(e)))?;
+
+    Ok(serde_json::json!({
+        "APP_INTERFACE_PORT": port,
+        "APP_INTERFACE_TOKEN": issued.token,
+        "INSTALLED_APP_ID": installed_app_id,
+    }))
+}
+
+/// Add a script that sets `window.__HC_LAUNCHER_ENV__` to the start of the
+/// page's `<head>`, or of the page if it has none.
+fn inject_launcher_env(html: &str, env: &serde_json::Value) -> String {
+    // Escape `<` so that the JSON can't close the script tag.
+    let env = env.to_string().replace('<', "\\u003c");
+    let script = format!("<script>window.__HC_LAUNCHER_ENV__ = {env};</script>");
+    let insert_at = html
+        .to_ascii_lowercase()
+        .find("<head")
+        .and_then(|start| html[start..].find('>').map(|end| start + end + 1))
+        .unwrap_or(0);
+    format!("{}{script}{}", &html[..insert_at], &html[insert_at..])
+}
+
+fn content_type(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" | "map" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "wasm" => "application/wasm",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "txt" => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launcher_env_is_injected_into_head() {
+        let env = serde_json::json!({ "INSTALLED_APP_ID": "</script>" });
+
+        let html = inject_launcher_env("<html><HEAD lang=\"en\"><title>x</title>", &env);
+        assert_eq!(
+            html,
+            "<html><HEAD lang=\"en\"><script>window.__HC_LAUNCHER_ENV__ = {\"INSTALLED_APP_ID\":\"\\u003c/script>\"};</script><title>x</title>"
+        );
+
+        let html = inject_launcher_env("<body></body>", &env);
+        assert!(html.starts_with("<script>window.__HC_LAUNCHER_ENV__"));
+        assert!(html.ends_with("<body></body>"));
+    }
+
+    #[test]
+    fn fetch_metadata_from_other_sites_is_refused() {
+        let origin = "http://my-app.localhost:8888";
+        let headers = |pairs: &[(&'static str, &'static str)]| {
+            pairs
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        header::HeaderName::from_static(name),
+                        HeaderValue::from_static(value),
+                    )
+                })
+                .collect::<HeaderMap>()
+        };
+
+        // Clients other than browsers
+        assert_eq!(Ok(()), check_fetch_metadata(&headers(&[]), origin));
+        // Typed into the address bar, or linked to from another site
+        assert_eq!(
+            Ok(()),
+            check_fetch_metadata(&headers(&[("sec-fetch-site", "none")]), origin)
+        );
+        assert_eq!(
+            Ok(()),
+            check_fetch_metadata(
+                &headers(&[
+                    ("sec-fetch-site", "cross-site"),
+                    ("sec-fetch-mode", "navigate")
+                ]),
+                origin
+            )
+        );
+        // Requested by the UI itself
+        assert_eq!(
+            Ok(()),
+            check_fetch_metadata(
+                &headers(&[
+                    ("origin", "http://my-app.localhost:8888"),
+                    ("sec-fetch-site", "same-origin"),
+                    ("sec-fetch-mode", "cors")
+                ]),
+                origin
+            )
+        );
+
+        // Fetched by a page on another site
+        assert_eq!(
+            Err(StatusCode::FORBIDDEN),
+            check_fetch_metadata(
+                &headers(&[
+                    ("sec-fetch-site", "cross-site"),
+                    ("sec-fetch-mode", "no-cors")
+                ]),
+                origin
+            )
+        );
+        assert_eq!(
+            Err(StatusCode::FORBIDDEN),
+            check_fetch_metadata(
+                &headers(&[
+                    ("origin", "http://other-app.localhost:8888"),
+                    ("sec-fetch-site", "same-site"),
+                    ("sec-fetch-mode", "cors")
+                ]),
+                origin
+            )
+        );
+    }
+
+    #[test]
+    fn app_origins_match_the_first_label() {
+        let allowed_origins = [
+            "http://my-app.localhost:8888".to_string(),
+            "http://My-App.example.org".to_string(),
+            "http://my-app-2.localhost:8888".to_string(),
+            "https://localhost".to_string(),
+        ];
+        assert_eq!(
+            app_origins(&allowed_origins, &"my-app".to_string()),
+            HashSet::from([
+                "http://my-app.localhost:8888".to_string(),
+                "http://my-app.example.org".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn app_ids_must_be_host_labels() {
+        assert!(is_host_label("my-app"));
+        assert!(is_host_label("MyApp2"));
+        assert!(!is_host_label(""));
+        assert!(!is_host_label("my.app"));
+        assert!(!is_host_label("my_app"));
+        assert!(!is_host_label("my app"));
+        assert!(!is_host_label("-my-app"));
+        assert!(!is_host_label("my-app-"));
+        assert!(!is_host_label(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn content_types() {
+        assert_eq!(content_type("index.html"), "text/html; charset=utf-8");
+        assert_eq!(
+            content_type("assets/app.JS"),
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(content_type("no-extension"), "application/octet-stream");
+    }
+}
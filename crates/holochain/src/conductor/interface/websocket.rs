@@ -4,6 +4,7 @@
 use super::error::InterfaceResult;
 use crate::conductor::api::{AdminInterfaceApi, AppAuthentication, AppInterfaceApi};
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
+use crate::conductor::conductor::zome_call_limiter::ZomeCallLimiter;
 use crate::conductor::manager::TaskManagerClient;
 use crate::conductor::metrics::dropped_signal_metric;
 use holochain_conductor_api::{
@@ -60,7 +61,7 @@ pub async fn spawn_websocket_listener(
     Ok(listener)
 }
 
-fn determine_bind_socket_addrs(
+pub(crate) fn determine_bind_socket_addrs(
     port: u16,
     danger_bind_addr: Option<String>,
 ) -> InterfaceResult<(SocketAddrV4, SocketAddrV6)> {
@@ -165,7 +166,14 @@ pub fn spawn_admin_interface_tasks(
 
 /// Create an App Interface, which includes the ability to receive signals
 /// from Cells via a broadcast channel
-pub async fn spawn_app_interface_task(
+///
+/// Zome calls made through all connections to the interface are admitted by
+/// the `zome_call_limiter`.
+///
+/// The listener and all its connections are closed once `stop` receives a
+/// value or its sender is dropped.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn spawn_app_interface_task(
     tm: TaskManagerClient,
     port: u16,
     danger_bind_addr: Option<String>,
@@ -173,6 +181,8 @@ pub async fn spawn_app_interface_task(
     installed_app_id: Option<InstalledAppId>,
     api: AppInterfaceApi,
     app_broadcast: AppBroadcast,
+    zome_call_limiter: Arc<ZomeCallLimiter>,
+    mut stop: tokio::sync::oneshot::Receiver<()>,
 ) -> InterfaceResult<u16> {
     trace!("Initializing App interface");
 
@@ -192,7 +202,11 @@ pub async fn spawn_app_interface_task(
             let task_list = TaskList::default();
             // establish a new connection to a client
             loop {
-                match listener.accept().await {
+                let accepted = tokio::select! {
+                    _ = &mut stop => break,
+                    accepted = listener.accept() => accepted,
+                };
+                match accepted {
                     Ok((tx_to_iface, rx_from_iface)) => {
                         authenticate_incoming_app_connection(
                             task_list.0.clone(),
@@ -202,6 +216,7 @@ pub async fn spawn_app_interface_task(
                             tx_to_iface,
                             installed_app_id.clone(),
                             port,
+                            zome_call_limiter.clone(),
                         );
                     }
                     Err(err) => {
@@ -209,6 +224,7 @@ pub async fn spawn_app_interface_task(
                     }
                 }
             }
+            Ok(())
         }
     });
     Ok(port)
@@ -269,6 +285,7 @@ fn authenticate_incoming_app_connection(
     tx_to_iface: WebsocketSender,
     installed_app_id: Option<InstalledAppId>,
     port: u16,
+    zome_call_limiter: Arc<ZomeCallLimiter>,
 ) {
     let join_handle = tokio::task::spawn({
         let task_list = task_list.clone();
@@ -334,6 +351,7 @@ fn authenticate_incoming_app_connection(
                                 api,
                                 rx_from_iface,
                                 installed_app_id,
+                                zome_call_limiter,
                             );
                         }
                         Err(e) => {
@@ -418,6 +436,7 @@ fn spawn_recv_incoming_app_msgs(
     api: AppInterfaceApi,
     rx_from_iface: WebsocketReceiver,
     installed_app_id: InstalledAppId,
+    zome_call_limiter: Arc<ZomeCallLimiter>,
 ) {
     use futures::stream::StreamExt;
 
@@ -452,8 +471,11 @@ fn spawn_recv_incoming_app_msgs(
             move |msg| {
                 let installed_app_id = installed_app_id.clone();
                 let api = api.clone();
+                let zome_call_limiter = zome_call_limiter.clone();
                 async move {
-                    if let Err(err) = handle_incoming_app_message(msg, installed_app_id, api).await
+                    if let Err(err) =
+                        handle_incoming_app_message(msg, installed_app_id, api, &zome_call_limiter)
+                            .await
                     {
                         error!(?err, "error handling app websocket message");
                     }
@@ -509,6 +531,7 @@ async fn handle_incoming_app_message(
     ws_msg: ReceiveMessage<AppRequest>,
     installed_app_id: InstalledAppId,
     api: AppInterfaceApi,
+    zome_call_limiter: &ZomeCallLimiter,
 ) -> InterfaceResult<()> {
     match ws_msg {
         ReceiveMessage::Signal(_) => {
@@ -521,6 +544,24 @@ async fn handle_incoming_app_message(
         }
         ReceiveMessage::Request(data, respond) => {
             use holochain_serialized_bytes::SerializedBytesError;
+            // Zome calls must be admitted by the interface before the app's
+            // own limits are checked.
+            let _permit = match &data {
+                AppRequest::CallZome(_) | AppRequest::CallZomeMetered(_) => {
+                    match zome_call_limiter.try_admit() {
+                        Ok(permit) => Some(permit),
+                        Err(reason) => {
+                            let result =
+                                AppResponse::Error(ExternalApiWireError::ZomeCallLimitExceeded(
+                                    format!("App interface rejected the call: {reason}"),
+                                ));
+                            respond.respond(result).await?;
+                            return Ok(());
+                        }
+                    }
+                }
+                _ => None,
+            };
             let result: AppResponse = api.handle_request(installed_app_id, Ok(data)).await?;
             // Have to jump through some hoops, because our response type
             // only implements try_into, but the responder needs try_from.
@@ -647,6 +688,7 @@ mod test {
             network_seed: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         }));
         let response: AdminResponse = admin_tx.request(request).await.unwrap();
         let app_info = match response {
@@ -676,6 +718,7 @@ mod test {
             danger_bind_addr: None,
             allowed_origins: AllowedOrigins::Any,
             installed_app_id: None,
+            zome_call_limits: None,
         };
         let response: AdminResponse = admin_tx.request(request).await.unwrap();
         let app_port = match response {
@@ -1140,6 +1183,7 @@ mod test {
             danger_bind_addr: None,
             allowed_origins: AllowedOrigins::Any,
             installed_app_id: None,
+            zome_call_limits: None,
         };
         let respond = |response: AdminResponse| {
             assert_matches!(response, AdminResponse::AppInterfaceAttached { .. });
@@ -1219,6 +1263,7 @@ mod test {
                 None,
                 AllowedOrigins::Any,
                 Some("test app".into()),
+                None,
             )
             .await
             .unwrap();
@@ -10,6 +10,7 @@ use crate::core::{
         incoming_dht_ops_workflow::{
             incoming_dht_ops_workflow, IncomingOpHashes, IncomingOpsBatch,
         },
+        sys_validation_workflow::validation_deps::SysValDeps,
         witnessing_workflow::{receive_incoming_countersigning_ops, WitnessingWorkspace},
     },
 };
@@ -77,6 +78,9 @@ pub struct Space {
 
     /// Incoming ops batch for this space.
     pub incoming_ops_batch: IncomingOpsBatch,
+
+    /// Sys validation dependencies cached across runs of the sys validation workflow.
+    pub sys_validation_deps: SysValDeps,
 }
 
 /// Test spaces
@@ -339,6 +343,26 @@ impl Spaces {
         self.queue_consumer_map.remove_all_for_dna(dna_hash);
     }
 
+    /// Move the committed data of every database of the conductor out of its
+    /// write-ahead log and into the database file.
+    ///
+    /// Afterwards the database files can be copied on their own, as long as
+    /// nothing writes to them in the meantime.
+    pub(crate) async fn checkpoint_databases(&self) -> ConductorResult<()> {
+        self.conductor_store.checkpoint().await?;
+        self.wasm_store.checkpoint().await?;
+        let stores =
+            self.get_from_spaces(|space| (space.dht_store.clone(), space.peer_meta_store.clone()));
+        for (dht_store, peer_meta_store) in stores {
+            dht_store
+                .checkpoint()
+                .await
+                .map_err(ConductorError::other)?;
+            peer_meta_store.checkpoint().await?;
+        }
+        Ok(())
+    }
+
     fn get_or_create_space_ref<F, R>(&self, dna_hash: &DnaHash, f: F) -> ConductorResult<R>
     where
         F: Fn(&Space) -> R,
@@ -479,6 +503,7 @@ impl Space {
                 witnessing_workspace: WitnessingWorkspace::default(),
                 incoming_op_hashes: IncomingOpHashes::default(),
                 incoming_ops_batch: IncomingOpsBatch::default(),
+                sys_validation_deps: SysValDeps::default(),
             })
         })
     }
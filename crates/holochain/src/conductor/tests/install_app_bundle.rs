@@ -33,6 +33,7 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
                 clone_limit,
             },
             provisioning: Some(CellProvisioning::CloneOnly),
+            network: None,
         }];
 
         let manifest = AppManifestCurrentBuilder::default()
@@ -53,6 +54,7 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
             network_seed: None,
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
+            wasm_metering: None,
         }
     }
 
@@ -151,6 +153,7 @@ async fn reject_duplicate_app_for_same_agent() {
             clone_limit: 0,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
+        network: None,
     }];
 
     let manifest = AppManifestCurrentBuilder::default()
@@ -172,6 +175,7 @@ async fn reject_duplicate_app_for_same_agent() {
             network_seed: None,
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -191,6 +195,7 @@ async fn reject_duplicate_app_for_same_agent() {
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
             network_seed: None,
+            wasm_metering: None,
         })
         .await;
     assert_matches!(
@@ -213,6 +218,7 @@ async fn reject_duplicate_app_for_same_agent() {
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
             network_seed: None,
+            wasm_metering: None,
         })
         .await;
     assert_matches!(
@@ -232,6 +238,7 @@ async fn reject_duplicate_app_for_same_agent() {
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
             network_seed: Some("network".into()),
+            wasm_metering: None,
         })
         .await;
     assert!(valid_install_of_second_app.is_ok());
@@ -349,6 +356,7 @@ async fn use_existing_integration() {
                 clone_limit: 0,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
+            network: None,
         }];
 
         let manifest = AppManifestCurrentBuilder::default()
@@ -388,6 +396,7 @@ async fn use_existing_integration() {
                         clone_limit: 0,
                     },
                     provisioning: Some(CellProvisioning::Create { deferred: false }),
+                    network: None,
                 },
                 AppRoleManifest {
                     name: "extant".into(),
@@ -399,6 +408,7 @@ async fn use_existing_integration() {
                     },
                     #[allow(deprecated)]
                     provisioning: Some(CellProvisioning::UseExisting { protected: true }),
+                    network: None,
                 },
             ];
 
@@ -430,6 +440,7 @@ async fn use_existing_integration() {
             network_seed: None,
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -445,6 +456,7 @@ async fn use_existing_integration() {
                 network_seed: None,
                 roles_settings: Default::default(),
                 ignore_genesis_failure: false,
+                wasm_metering: None,
             })
             .await
             .unwrap_err();
@@ -465,6 +477,7 @@ async fn use_existing_integration() {
                 network_seed: None,
                 roles_settings: Default::default(),
                 ignore_genesis_failure: false,
+                wasm_metering: None,
             })
             .await
             .unwrap_err();
@@ -485,6 +498,32 @@ async fn use_existing_integration() {
     #[allow(deprecated)]
     let role_settings = ("extant".into(), RoleSettings::UseExisting { cell_id });
 
+    {
+        // Fail to install the dependent app with other wasm metering budgets
+        // than the app whose cell it uses
+        let err = conductor
+            .clone()
+            .install_app_bundle(InstallAppPayload {
+                agent_key: None,
+                source: AppBundleSource::Bytes(bundle2(true).await),
+                installed_app_id: Some("app_2".into()),
+                network_seed: None,
+                roles_settings: Some(HashMap::from([role_settings.clone()])),
+                ignore_genesis_failure: false,
+                wasm_metering: Some(WasmMeteringBudgets {
+                    zome_call: Some(1),
+                    ..Default::default()
+                }),
+            })
+            .await
+            .unwrap_err();
+
+        assert_matches!(
+            err,
+            ConductorError::ConflictingWasmMetering(a, b) if a == *"app_2" && b == *"app_1"
+        );
+    }
+
     let app_2 = conductor
         .clone()
         .install_app_bundle(InstallAppPayload {
@@ -494,6 +533,7 @@ async fn use_existing_integration() {
             network_seed: None,
             roles_settings: Some(HashMap::from([role_settings])),
             ignore_genesis_failure: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -576,3 +616,276 @@ async fn use_existing_integration() {
         .await
         .unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn only_trusted_publishers_can_install() {
+    use holochain_conductor_api::conductor::AppPublishersConfig;
+    use holochain_keystore::AgentPubKeyExt;
+
+    holochain_trace::test_run();
+
+    let keystore = holochain_keystore::test_keystore();
+    let trusted = AgentPubKey::new_random(&keystore).await.unwrap();
+    let untrusted = AgentPubKey::new_random(&keystore).await.unwrap();
+
+    let mut config = SweetConductorConfig::standard();
+    config.app_publishers = Some(AppPublishersConfig {
+        trusted: vec![trusted.clone().into()],
+        allow_unsigned: false,
+    });
+    let mut conductor = SweetConductor::create_with_defaults(
+        config,
+        Some(keystore.clone()),
+        Some(SweetLocalRendezvous::new().await),
+    )
+    .await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let bundle = app_bundle_from_dnas(&[("role".to_string(), dna)], false, None).await;
+
+    let sign = |publisher: AgentPubKey| {
+        let bundle = bundle.clone();
+        let keystore = keystore.clone();
+        async move {
+            let payload = bundle.signing_payload().unwrap();
+            let signature = publisher.sign_raw(&keystore, payload.into()).await.unwrap();
+            bundle
+                .with_publisher_signature(publisher, signature)
+                .unwrap()
+        }
+    };
+
+    let payload = |bundle: AppBundle, installed_app_id: &str| InstallAppPayload {
+        agent_key: None,
+        source: AppBundleSource::Bytes(bundle.pack().unwrap()),
+        installed_app_id: Some(installed_app_id.into()),
+        network_seed: None,
+        roles_settings: Default::default(),
+        ignore_genesis_failure: false,
+        restore_from_dht: false,
+        wasm_metering: None,
+    };
+
+    // Unsigned bundles are rejected when `allow_unsigned` is not set
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload(bundle.clone(), "unsigned"))
+            .await
+            .unwrap_err(),
+        ConductorError::UntrustedAppBundle(_)
+    );
+
+    // A valid signature by a publisher that is not trusted is rejected
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload(sign(untrusted).await, "untrusted"))
+            .await
+            .unwrap_err(),
+        ConductorError::UntrustedAppBundle(_)
+    );
+
+    // A trusted publisher's signature that was made over a different bundle is rejected
+    let (other_dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let other_bundle = app_bundle_from_dnas(&[("role".to_string(), other_dna)], false, None).await;
+    let signature = sign(trusted.clone())
+        .await
+        .publisher_signature()
+        .unwrap()
+        .unwrap()
+        .signature;
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload(
+                other_bundle
+                    .with_publisher_signature(trusted.clone(), signature)
+                    .unwrap(),
+                "tampered"
+            ))
+            .await
+            .unwrap_err(),
+        ConductorError::UntrustedAppBundle(_)
+    );
+
+    // A bundle signed by a trusted publisher installs
+    conductor
+        .clone()
+        .install_app_bundle(payload(sign(trusted).await, "trusted"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn web_app_ui_is_served_from_ui_interface() {
+    use holochain_conductor_api::config::{InterfaceDriver, UiInterfaceConfig};
+    use holochain_types::web_app::*;
+    use holochain_types::websocket::AllowedOrigins;
+    use std::io::Write;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::standard().await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let app_bundle = app_bundle_from_dnas(&[("role".to_string(), dna)], false, None).await;
+
+    let mut ui = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    ui.start_file("index.html", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    ui.write_all(b"<html><head><title>web app</title></head></html>")
+        .unwrap();
+    let ui = ui.finish().unwrap().into_inner();
+
+    let manifest = WebAppManifest::current("web app".to_string());
+    let resources = vec![
+        (manifest.web_ui_location(), ui.into()),
+        (
+            manifest.happ_bundle_location(),
+            app_bundle.pack().unwrap().into(),
+        ),
+    ];
+    let web_app_bundle = WebAppBundle::from(mr_bundle::Bundle::new(manifest, resources).unwrap());
+    let payload = |installed_app_id: &str| InstallAppPayload {
+        agent_key: None,
+        source: AppBundleSource::Bytes(web_app_bundle.pack().unwrap()),
+        installed_app_id: Some(installed_app_id.into()),
+        network_seed: None,
+        roles_settings: Default::default(),
+        ignore_genesis_failure: false,
+        restore_from_dht: false,
+        wasm_metering: None,
+    };
+
+    // The app ID is the first label of the host the UI is served under
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload("web.app"))
+            .await
+            .unwrap_err(),
+        ConductorError::InvalidWebAppId(_)
+    );
+
+    conductor
+        .clone()
+        .install_app_bundle(payload("webapp"))
+        .await
+        .unwrap();
+
+    // UIs can only be served for origins that are listed
+    let any_origin = conductor
+        .raw_handle()
+        .add_ui_interfaces(vec![UiInterfaceConfig {
+            driver: InterfaceDriver::Http {
+                port: 0,
+                danger_bind_addr: None,
+                allowed_origins: AllowedOrigins::Any,
+            },
+        }])
+        .await;
+    assert!(any_origin.is_err());
+
+    // The port isn't known before the interface is bound, so the requests
+    // leave it out of the host, as they would for a UI served on port 80
+    let port = conductor
+        .raw_handle()
+        .add_ui_interfaces(vec![UiInterfaceConfig {
+            driver: InterfaceDriver::Http {
+                port: 0,
+                danger_bind_addr: None,
+                allowed_origins: AllowedOrigins::Origins(
+                    [
+                        "http://webapp.localhost".to_string(),
+                        "http://other.localhost".to_string(),
+                    ]
+                    .into(),
+                ),
+            },
+        }])
+        .await
+        .unwrap()[0];
+
+    let ui_app_interfaces = || async {
+        conductor
+            .list_app_interfaces()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|interface| interface.installed_app_id.as_deref() == Some("webapp"))
+            .collect::<Vec<_>>()
+    };
+    // The app interface for the UI is attached with the UI interface
+    let interfaces = ui_app_interfaces().await;
+    assert_eq!(interfaces.len(), 1);
+    let app_port = interfaces[0].port;
+    assert_eq!(
+        interfaces[0].allowed_origins,
+        AllowedOrigins::Origins(["http://webapp.localhost".to_string()].into())
+    );
+
+    let get = |host: &'static str, headers: &'static str| async move {
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        stream
+            .write_all(
+                format!("GET / HTTP/1.1\r\nHost: {host}\r\n{headers}Connection: close\r\n\r\n")
+                    .as_bytes(),
+            )
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    };
+
+    let response = get("webapp.localhost", "").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.contains("<head><script>window.__HC_LAUNCHER_ENV__ = {"));
+    assert!(response.contains("\"INSTALLED_APP_ID\":\"webapp\""));
+
+    // Page loads don't attach more app interfaces
+    get(
+        "webapp.localhost",
+        "Sec-Fetch-Site: none\r\nSec-Fetch-Mode: navigate\r\n",
+    )
+    .await;
+    assert_eq!(ui_app_interfaces().await.len(), 1);
+
+    // Hosts that aren't listed are refused, as a rebound domain would be
+    let response = get("evil.example", "").await;
+    assert!(response.starts_with("HTTP/1.1 403"), "{response}");
+
+    // Pages on other origins can't fetch the UI
+    let response = get(
+        "webapp.localhost",
+        "Origin: http://other.localhost\r\nSec-Fetch-Site: same-site\r\nSec-Fetch-Mode: cors\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 403"), "{response}");
+
+    let response = get("other.localhost", "").await;
+    assert!(response.starts_with("HTTP/1.1 404"), "{response}");
+
+    conductor
+        .clone()
+        .uninstall_app(&"webapp".to_string(), false)
+        .await
+        .unwrap();
+    let response = get("webapp.localhost", "").await;
+    assert!(response.starts_with("HTTP/1.1 404"), "{response}");
+    assert!(ui_app_interfaces().await.is_empty());
+
+    // The app interface of the UI stops listening
+    crate::retry_until_timeout!(5_000, 100, {
+        if tokio::net::TcpStream::connect(("127.0.0.1", app_port))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    });
+}
@@ -1,11 +1,15 @@
 use crate::prelude::fake_dna_hash;
+use crate::retry_until_timeout;
 use crate::sweettest::*;
 use hdk::prelude::CloneCellId;
 use hdk::prelude::DnaModifiersOpt;
+use hdk::prelude::{ActionHash, Record};
 use holochain_types::app::{CreateCloneCellPayload, EnableCloneCellPayload};
-use holochain_types::network::Kitsune2NetworkMetricsRequest;
+use holochain_types::network::{BandwidthLimits, Kitsune2NetworkMetricsRequest};
 use holochain_types::prelude::InstalledAppId;
 use holochain_wasm_test_utils::TestWasm;
+use std::collections::HashMap;
+use std::time::Duration;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn network_metrics() {
@@ -84,3 +88,107 @@ async fn network_metrics() {
         .unwrap()
         .contains_key(clone_cell.cell_id.dna_hash()));
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bandwidth_limits_pause_gossip() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let mut conductors = SweetConductorBatch::standard(2).await;
+    let app_id: InstalledAppId = "app".into();
+    let apps = conductors
+        .setup_app(&app_id, std::slice::from_ref(&dna))
+        .await
+        .unwrap();
+    let ((alice,), (bob,)) = apps.into_tuples();
+
+    // Any gossip or fetch traffic uses up this limit.
+    let limits = BandwidthLimits {
+        daily_bytes: Some(1),
+        bytes_per_second: None,
+    };
+    conductors[0]
+        .set_bandwidth_limits(&app_id, Some(limits))
+        .await
+        .unwrap();
+    assert_eq!(
+        conductors[0].list_bandwidth_limits().await.unwrap(),
+        HashMap::from([(app_id.clone(), limits)])
+    );
+
+    conductors.exchange_peer_info().await;
+
+    let request = Kitsune2NetworkMetricsRequest {
+        dna_hash: Some(dna.dna_hash().clone()),
+        include_dht_summary: false,
+    };
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let metrics = conductors[0]
+                .dump_network_metrics(request.clone())
+                .await
+                .unwrap();
+            let bandwidth = &metrics[dna.dna_hash()].bandwidth;
+            if bandwidth.gossip_paused {
+                let limited_bytes = bandwidth.gossip.sent
+                    + bandwidth.gossip.received
+                    + bandwidth.fetch.sent
+                    + bandwidth.fetch.received;
+                assert!(limited_bytes > 0);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await
+    .expect("gossip was not paused by the bandwidth limit");
+
+    // A record that alice creates is still published to bob.
+    let alice_hash: ActionHash = conductors[0]
+        .call(&alice.zome(TestWasm::Create), "create_entry", ())
+        .await;
+    retry_until_timeout!(30_000, {
+        let record: Option<Record> = conductors[1]
+            .call(&bob.zome(TestWasm::Create), "get_post", alice_hash.clone())
+            .await;
+        if record.is_some() {
+            break;
+        }
+    });
+
+    // And alice can still get a record that bob creates from the network.
+    let bob_hash: ActionHash = conductors[1]
+        .call(&bob.zome(TestWasm::Create), "create_entry", ())
+        .await;
+    retry_until_timeout!(30_000, {
+        let record: Option<Record> = conductors[0]
+            .call(
+                &alice.zome(TestWasm::Create),
+                "get_post_network",
+                bob_hash.clone(),
+            )
+            .await;
+        if record.is_some() {
+            break;
+        }
+    });
+
+    let metrics = conductors[0]
+        .dump_network_metrics(request.clone())
+        .await
+        .unwrap();
+    assert!(metrics[dna.dna_hash()].bandwidth.gossip_paused);
+
+    // Removing the limits resumes gossip.
+    conductors[0]
+        .set_bandwidth_limits(&app_id, None)
+        .await
+        .unwrap();
+    assert!(conductors[0]
+        .list_bandwidth_limits()
+        .await
+        .unwrap()
+        .is_empty());
+    let metrics = conductors[0].dump_network_metrics(request).await.unwrap();
+    assert!(!metrics[dna.dna_hash()].bandwidth.gossip_paused);
+}
@@ -36,6 +36,7 @@ async fn network_seed_regression() {
                 clone_limit: 0,
             },
             provisioning: None,
+            network: None,
         }];
 
         AppManifestCurrentBuilder::default()
@@ -69,6 +70,7 @@ async fn network_seed_regression() {
             network_seed: None,
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -82,6 +84,7 @@ async fn network_seed_regression() {
             network_seed: Some("seed".into()),
             roles_settings: Default::default(),
             ignore_genesis_failure: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -231,6 +234,7 @@ impl TestCase {
                 clone_limit: 10,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
+            network: None,
         }];
         let manifest = AppManifestCurrentBuilder::default()
             .name(case_str.clone())
@@ -261,6 +265,7 @@ impl TestCase {
                 network_seed,
                 roles_settings: Default::default(),
                 ignore_genesis_failure: false,
+                wasm_metering: None,
             })
             .await
             .unwrap();
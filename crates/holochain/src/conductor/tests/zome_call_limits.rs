@@ -0,0 +1,94 @@
+use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::config::{ZomeCallLimits, ZomeCallLimitsConfig};
+use crate::sweettest::*;
+use crate::test_utils::new_zome_call;
+use holochain_conductor_api::AppInterfaceInfo;
+use holochain_types::prelude::*;
+use holochain_types::websocket::AllowedOrigins;
+use holochain_wasm_test_utils::TestWasm;
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "test_utils")]
+async fn zome_calls_over_app_limit_are_rejected() {
+    holochain_trace::test_run();
+    let mut config = SweetConductorConfig::rendezvous(true);
+    config.zome_call_limits = Some(ZomeCallLimitsConfig {
+        apps: [(
+            "limited".to_string(),
+            ZomeCallLimits {
+                max_concurrent_calls: None,
+                max_calls_per_second: Some(1),
+            },
+        )]
+        .into(),
+        ..Default::default()
+    });
+    let mut conductor =
+        SweetConductor::from_config_rendezvous(config, SweetLocalRendezvous::new().await).await;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let app = conductor.setup_app("limited", [&dna]).await.unwrap();
+    let cell_id = app.cells()[0].cell_id().clone();
+
+    let call = || {
+        new_zome_call(
+            &conductor.keystore(),
+            &cell_id,
+            "foo",
+            (),
+            TestWasm::Foo.coordinator_zome_name(),
+        )
+    };
+    let first = conductor
+        .raw_handle()
+        .handle_external_zome_call(&"limited".to_string(), call().await.unwrap())
+        .await;
+    assert!(matches!(first, Ok((Ok(ZomeCallResponse::Ok(_)), _))));
+
+    // The burst of one call is used up.
+    let second = conductor
+        .raw_handle()
+        .handle_external_zome_call(&"limited".to_string(), call().await.unwrap())
+        .await;
+    assert!(matches!(
+        second,
+        Err(ConductorApiError::ZomeCallLimitExceeded(_))
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "test_utils")]
+async fn app_interface_zome_call_limits_survive_a_restart() {
+    holochain_trace::test_run();
+    let mut conductor = SweetConductor::from_config_rendezvous(
+        SweetConductorConfig::rendezvous(true),
+        SweetLocalRendezvous::new().await,
+    )
+    .await;
+    let limits = ZomeCallLimits {
+        max_concurrent_calls: None,
+        max_calls_per_second: Some(1),
+    };
+    conductor
+        .raw_handle()
+        .add_app_interface(
+            either::Either::Left(0),
+            None,
+            AllowedOrigins::Any,
+            None,
+            Some(limits),
+        )
+        .await
+        .unwrap();
+
+    let has_limits = |interfaces: Vec<AppInterfaceInfo>| {
+        interfaces
+            .iter()
+            .any(|info| info.zome_call_limits == Some(limits))
+    };
+    assert!(has_limits(conductor.list_app_interfaces().await.unwrap()));
+
+    // The limits are restored with the interface when the conductor restarts.
+    conductor.shutdown().await;
+    conductor.startup().await;
+    assert!(has_limits(conductor.list_app_interfaces().await.unwrap()));
+}
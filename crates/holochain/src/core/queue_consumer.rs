@@ -320,6 +320,28 @@ impl QueueConsumerMap {
         self.map
             .share_mut(|map| map.retain(|QueueEntry(dna, _), _| dna.as_ref() != dna_hash));
     }
+
+    /// Status of every queue consumer registered for the given DNA,
+    /// sorted by workflow name.
+    pub fn consumer_status_for_dna(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> Vec<holochain_conductor_api::QueueConsumerStatus> {
+        let mut out: Vec<_> = self.map.share_ref(|map| {
+            map.iter()
+                .filter(|(QueueEntry(dna, _), _)| dna.as_ref() == dna_hash)
+                .map(|(QueueEntry(_, queue_type), tx)| {
+                    holochain_conductor_api::QueueConsumerStatus {
+                        workflow: queue_type.name().to_string(),
+                        running: tx.is_consumer_running(),
+                        loop_paused: tx.is_loop_paused(),
+                    }
+                })
+                .collect()
+        });
+        out.sort_by(|a, b| a.workflow.cmp(&b.workflow));
+        out
+    }
 }
 
 #[derive(Hash, Eq, PartialEq, Clone, Debug)]
@@ -335,6 +357,19 @@ enum QueueType {
     Witnessing,
 }
 
+impl QueueType {
+    fn name(&self) -> &'static str {
+        match self {
+            QueueType::Receipt => "validation_receipt",
+            QueueType::Integration => "integrate_dht_ops",
+            QueueType::AppValidation => "app_validation",
+            QueueType::SysValidation => "sys_validation",
+            QueueType::Countersigning => "countersigning",
+            QueueType::Witnessing => "witnessing",
+        }
+    }
+}
+
 /// The entry points for kicking off a chain reaction of queue activity
 #[derive(Clone)]
 pub struct QueueTriggers {
@@ -485,6 +520,21 @@ impl TriggerSender {
         };
     }
 
+    /// Whether the consumer task is still listening for triggers.
+    ///
+    /// Returns `false` once the consumer has shut down or bailed on an error.
+    pub fn is_consumer_running(&self) -> bool {
+        self.trigger.receiver_count() > 0
+    }
+
+    /// Check whether the trigger loop is paused.
+    /// Always returns false if there is no loop.
+    pub fn is_loop_paused(&self) -> bool {
+        self.pause_back_off
+            .as_ref()
+            .is_some_and(|pause| pause.load(Ordering::Acquire))
+    }
+
     /// Reset the back off to the lowest duration.
     /// If no back off is set this is a no-op.
     pub fn reset_back_off(&self) {
@@ -1,7 +1,6 @@
 //! The workflow and queue consumer for sys validation
 
 use super::*;
-use crate::core::workflow::sys_validation_workflow::validation_deps::SysValDeps;
 use crate::core::workflow::sys_validation_workflow::SysValidationWorkspace;
 use crate::core::workflow::sys_validation_workflow::{
     get_representative_agent, sys_validation_workflow,
@@ -26,7 +25,7 @@ pub fn spawn_sys_validation_consumer(
     let workspace = Arc::new(workspace);
     let space = Arc::new(space);
 
-    let current_validation_dependencies = SysValDeps::default();
+    let current_validation_dependencies = space.sys_validation_deps.clone();
 
     super::queue_consumer_dna_bound(
         "sys_validation_consumer",
@@ -52,7 +52,7 @@ use holochain_wasmer_host::prelude::{wasm_error, WasmError, WasmErrorInner};
 use holochain_zome_types::block::BlockTargetId;
 use mockall::automock;
 use opentelemetry::KeyValue;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::iter::Iterator;
 use std::sync::Arc;
 use tokio::sync::broadcast;
@@ -107,6 +107,8 @@ pub mod guest_callback;
 pub mod host_fn;
 pub mod real_ribosome;
 
+pub mod dna_check;
+
 mod check_clone_access;
 
 #[cfg(feature = "test_utils")]
@@ -120,6 +122,9 @@ pub struct CallContext {
     pub(crate) function_name: FunctionName,
     pub(crate) auth: InvocationAuth,
     pub(crate) host_context: HostContext,
+    /// Actions that the authorities of aggregated reads made during this call
+    /// disagreed on, shared with every cascade the call's host fns build.
+    pub(crate) authority_disagreements: Arc<parking_lot::Mutex<HashSet<ActionHash>>>,
 }
 
 impl CallContext {
@@ -134,6 +139,7 @@ impl CallContext {
             function_name,
             host_context,
             auth,
+            authority_disagreements: Default::default(),
         }
     }
 
@@ -162,6 +168,7 @@ impl CallContext {
             function_name: self.function_name.clone(),
             host_context: transform(&self.host_context)?,
             auth: self.auth.clone(),
+            authority_disagreements: self.authority_disagreements.clone(),
         })
     }
 }
@@ -262,16 +269,25 @@ impl HostContext {
 
     /// Get the call zome handle, panics if none was provided
     pub fn call_zome_handle(&self) -> &CellConductorReadHandle {
+        self.maybe_call_zome_handle().expect(
+            "Gave access to a host function that uses the call zome handle without providing a call zome handle",
+        )
+    }
+
+    /// Get the call zome handle if it was provided.
+    pub fn maybe_call_zome_handle(&self) -> Option<&CellConductorReadHandle> {
         match self {
             Self::ZomeCall(ZomeCallHostAccess {
-                               call_zome_handle, ..
-                           })
-            | Self::Init(InitHostAccess { call_zome_handle, .. })
-            | Self::PostCommit(PostCommitHostAccess { call_zome_handle: Some(call_zome_handle), .. })
-            => call_zome_handle,
-            _ => panic!(
-                "Gave access to a host function that uses the call zome handle without providing a call zome handle"
-            ),
+                call_zome_handle, ..
+            })
+            | Self::Init(InitHostAccess {
+                call_zome_handle, ..
+            })
+            | Self::PostCommit(PostCommitHostAccess {
+                call_zome_handle: Some(call_zome_handle),
+                ..
+            }) => Some(call_zome_handle),
+            _ => None,
         }
     }
 }
@@ -380,6 +396,11 @@ pub trait Invocation: Send + Sync {
     /// shouldn't be retrieved multiple times.
     fn take_host_input(&self) -> Result<Option<ExternIO>, SerializedBytesError>;
     fn auth(&self) -> InvocationAuth;
+    /// Record the wasm metering points that a call for this invocation consumed.
+    ///
+    /// Only ribosomes that meter wasm execution report points, and only
+    /// zome call invocations keep them.
+    fn record_points_consumed(&self, _points: u64) {}
 }
 
 impl ZomeCallInvocation {
@@ -511,6 +532,9 @@ pub struct ZomeCallInvocation {
     pub nonce: Nonce256Bits,
     /// This call MUST NOT be respected after this time, in the opinion of the callee.
     pub expires_at: Timestamp,
+    /// The wasm metering points the call consumed, once it has run on a
+    /// metered wasm backend.
+    pub points_consumed: Arc<std::sync::Mutex<Option<u64>>>,
 }
 
 impl Invocation for ZomeCallInvocation {
@@ -533,6 +557,13 @@ impl Invocation for ZomeCallInvocation {
     fn auth(&self) -> InvocationAuth {
         InvocationAuth::Cap(self.provenance.clone(), self.cap_secret)
     }
+
+    fn record_points_consumed(&self, points: u64) {
+        *self
+            .points_consumed
+            .lock()
+            .unwrap_or_else(|i| i.into_inner()) = Some(points);
+    }
 }
 
 impl ZomeCallInvocation {
@@ -562,6 +593,7 @@ impl ZomeCallInvocation {
             provenance,
             nonce,
             expires_at,
+            points_consumed: Default::default(),
         })
     }
 }
@@ -908,6 +940,7 @@ impl Ribosome {
                 function_name: fn_name.clone(),
                 host_context,
                 auth: invocation.auth(),
+                authority_disagreements: Default::default(),
             };
 
             inner
@@ -0,0 +1,404 @@
+//! Static checks of a DNA bundle, run before it is installed.
+//!
+//! Many mistakes in a DNA only surface when it is installed or when a zome is
+//! first called. [`check_dna_bundle`] looks for them up front: it checks the
+//! manifest, compiles every zome's wasm, compares each wasm's imports with the
+//! host functions this conductor provides and calls the `entry_defs` and type
+//! count callbacks of the integrity zomes in a ribosome that is not part of
+//! any conductor.
+
+use super::guest_callback::entry_defs::{
+    EntryDefsHostAccess, EntryDefsInvocation, EntryDefsResult,
+};
+use super::real_ribosome::module_cache::make_module_cache;
+use super::real_ribosome::{RealRibosome, WasmBackend};
+use super::{Ribosome, RibosomeImplT};
+use crate::core::ribosome::error::{RibosomeError, RibosomeResult};
+use holochain_state::wasm::WasmStore;
+use holochain_types::prelude::*;
+use mr_bundle::Bundle;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// How serious a [`DnaCheckFinding`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DnaCheckSeverity {
+    /// The DNA will fail to install or its zomes will fail to run.
+    Error,
+    /// The DNA will install, but is likely not what was intended.
+    Warning,
+}
+
+/// A problem found while checking a DNA.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct DnaCheckFinding {
+    /// How serious the problem is.
+    pub severity: DnaCheckSeverity,
+    /// The zome the problem was found in, if it is specific to one zome.
+    pub zome: Option<ZomeName>,
+    /// A description of the problem.
+    pub message: String,
+}
+
+/// What was found out about a single zome while checking a DNA.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct ZomeCheckSummary {
+    /// The name of the zome.
+    pub name: ZomeName,
+    /// Whether this is an integrity zome.
+    pub integrity: bool,
+    /// The host functions the zome's wasm imports.
+    pub host_fn_imports: Vec<String>,
+    /// The number of entry types declared by an integrity zome.
+    pub num_entry_types: Option<u8>,
+    /// The number of link types declared by an integrity zome.
+    pub num_link_types: Option<u8>,
+    /// The entry defs returned by the `entry_defs` callback of an integrity zome.
+    pub entry_defs: Vec<String>,
+}
+
+/// The result of checking a DNA bundle.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize)]
+pub struct DnaCheckReport {
+    /// The name of the DNA, if the manifest could be read.
+    pub name: Option<String>,
+    /// The hash the DNA would be installed with, if it could be computed.
+    pub dna_hash: Option<String>,
+    /// A summary of each zome that could be compiled.
+    pub zomes: Vec<ZomeCheckSummary>,
+    /// Every problem found.
+    pub findings: Vec<DnaCheckFinding>,
+}
+
+impl DnaCheckReport {
+    /// Whether any finding is an error.
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|f| f.severity == DnaCheckSeverity::Error)
+    }
+
+    fn error(&mut self, zome: Option<&ZomeName>, message: impl Into<String>) {
+        self.findings.push(DnaCheckFinding {
+            severity: DnaCheckSeverity::Error,
+            zome: zome.cloned(),
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, zome: Option<&ZomeName>, message: impl Into<String>) {
+        self.findings.push(DnaCheckFinding {
+            severity: DnaCheckSeverity::Warning,
+            zome: zome.cloned(),
+            message: message.into(),
+        });
+    }
+
+    fn zome_mut(&mut self, zome_name: &ZomeName) -> Option<&mut ZomeCheckSummary> {
+        self.zomes.iter_mut().find(|z| &z.name == zome_name)
+    }
+}
+
+impl std::fmt::Display for DnaCheckReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "DNA: {}", self.name.as_deref().unwrap_or("<unknown>"))?;
+        if let Some(dna_hash) = &self.dna_hash {
+            writeln!(f, "Hash: {dna_hash}")?;
+        }
+        for zome in &self.zomes {
+            let kind = if zome.integrity {
+                "integrity"
+            } else {
+                "coordinator"
+            };
+            writeln!(f, "Zome {} ({kind})", zome.name)?;
+            writeln!(f, "  host functions: {}", zome.host_fn_imports.len())?;
+            if zome.integrity {
+                writeln!(
+                    f,
+                    "  entry types: {}, link types: {}",
+                    zome.num_entry_types.unwrap_or_default(),
+                    zome.num_link_types.unwrap_or_default()
+                )?;
+                if !zome.entry_defs.is_empty() {
+                    writeln!(f, "  entry defs: {}", zome.entry_defs.join(", "))?;
+                }
+            }
+        }
+        if self.findings.is_empty() {
+            return writeln!(f, "No problems found");
+        }
+        for finding in &self.findings {
+            let severity = match finding.severity {
+                DnaCheckSeverity::Error => "error",
+                DnaCheckSeverity::Warning => "warning",
+            };
+            match &finding.zome {
+                Some(zome) => writeln!(f, "{severity} [{zome}]: {}", finding.message)?,
+                None => writeln!(f, "{severity}: {}", finding.message)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check a DNA bundle for problems that would otherwise only show up at
+/// install time or when its zomes run.
+///
+/// The bundle is taken with an unvalidated manifest so that every manifest
+/// problem can be reported, rather than only the first one. Problems with the
+/// DNA are returned as findings in the report. An error is only returned if
+/// the check itself could not be run.
+pub async fn check_dna_bundle(
+    backend: WasmBackend,
+    bundle: Bundle<DnaManifest>,
+) -> RibosomeResult<DnaCheckReport> {
+    let mut report = DnaCheckReport::default();
+    let DnaManifest::V0(manifest) = bundle.manifest().clone();
+    report.name = Some(manifest.name.clone());
+
+    check_manifest(&manifest, &mut report);
+    if report.has_errors() {
+        // The manifest can't be turned into a DNA to check the zomes with.
+        return Ok(report);
+    }
+
+    let resources = bundle
+        .get_all_resources()
+        .into_iter()
+        .map(|(id, bytes)| (id.clone(), bytes.clone()))
+        .collect();
+    let dna_file = match ValidatedDnaManifest::try_from(DnaManifest::V0(manifest))
+        .and_then(|manifest| DnaBundle::new(manifest, resources))
+    {
+        Ok(dna_bundle) => match dna_bundle.to_dna_file().await {
+            Ok((dna_file, _)) => dna_file,
+            Err(e) => {
+                report.error(None, format!("The zomes could not be read: {e}"));
+                return Ok(report);
+            }
+        },
+        Err(e) => {
+            report.error(None, format!("The bundle is invalid: {e}"));
+            return Ok(report);
+        }
+    };
+    report.dna_hash = Some(dna_file.dna_hash().to_string());
+
+    check_zomes(backend, &dna_file, &mut report).await?;
+
+    Ok(report)
+}
+
+/// Check the manifest, reporting every problem instead of the first one.
+fn check_manifest(manifest: &DnaManifestV0, report: &mut DnaCheckReport) {
+    let integrity_zome_names: HashSet<_> = manifest
+        .integrity
+        .zomes
+        .iter()
+        .map(|z| z.name.clone())
+        .collect();
+    let mut names = HashSet::new();
+    for zome in manifest.all_zomes() {
+        if !names.insert(zome.name.clone()) {
+            report.error(Some(&zome.name), "Duplicate zome name");
+        }
+        for ZomeDependency { name } in zome.dependencies.iter().flatten() {
+            if *name == zome.name {
+                report.error(Some(&zome.name), "The zome depends on itself");
+            } else if !integrity_zome_names.contains(name) {
+                report.error(
+                    Some(&zome.name),
+                    format!("Dependency '{name}' is not an integrity zome of this DNA"),
+                );
+            }
+        }
+    }
+    for zome in &manifest.integrity.zomes {
+        if zome.dependencies.as_ref().is_some_and(|d| !d.is_empty()) {
+            report.warning(
+                Some(&zome.name),
+                "Integrity zomes should not have dependencies",
+            );
+        }
+    }
+
+    if let Some(properties) = &manifest.integrity.properties {
+        if let Err(e) = SerializedBytes::try_from(properties.clone()) {
+            report.error(None, format!("The DNA properties can't be encoded: {e}"));
+        }
+    }
+}
+
+/// Compile each zome and call the callbacks that are run when a DNA is installed.
+async fn check_zomes(
+    backend: WasmBackend,
+    dna_file: &DnaFile,
+    report: &mut DnaCheckReport,
+) -> RibosomeResult<()> {
+    // The ribosome loads wasm from a store, so use one that is thrown away
+    // after the check.
+    let db_dir = tempfile::tempdir()?;
+    let db = holochain_state::data::open_db(
+        db_dir.path(),
+        holochain_state::data::Wasm,
+        holochain_state::data::HolochainDataConfig::default(),
+    )
+    .await
+    .map_err(std::io::Error::other)?;
+    let wasm_store = WasmStore::new(db);
+    for (hash, code) in dna_file.code() {
+        wasm_store
+            .put(DnaWasmHashed::with_pre_hashed(code.clone(), hash.clone()))
+            .await
+            .map_err(std::io::Error::other)?;
+    }
+
+    let host_fns: HashSet<String> = RealRibosome::tooling_imports(backend, wasm_store.clone())
+        .await?
+        .into_iter()
+        .collect();
+    let dna_def_hashed = dna_file.dna_def_hashed().clone();
+    let real_ribosome = RealRibosome::new(
+        backend,
+        dna_def_hashed.clone(),
+        Arc::new(make_module_cache(backend, wasm_store)),
+    )
+    .await?;
+
+    let mut entry_defs_exported = HashSet::new();
+    for (zome_name, def) in dna_file.dna_def().all_zomes() {
+        let zome = Zome::new(zome_name.clone(), def.clone());
+        let module = match real_ribosome.get_module_for_zome(&zome).await {
+            Ok(module) => module,
+            Err(e) => {
+                report.error(Some(zome_name), format!("The wasm can't be compiled: {e}"));
+                continue;
+            }
+        };
+
+        let mut host_fn_imports = Vec::new();
+        for import in module.imports() {
+            let name = import.name().to_string();
+            if host_fns.contains(&name) {
+                host_fn_imports.push(name);
+            } else if name.starts_with("__hc__") {
+                report.error(
+                    Some(zome_name),
+                    format!(
+                        "Imports host function '{name}', which this conductor does not provide"
+                    ),
+                );
+            } else {
+                report.error(
+                    Some(zome_name),
+                    format!(
+                        "Imports '{}.{name}', which can't be resolved",
+                        import.module()
+                    ),
+                );
+            }
+        }
+        host_fn_imports.sort();
+
+        if module.exports().any(|export| export.name() == "entry_defs") {
+            entry_defs_exported.insert(zome_name.clone());
+        }
+
+        report.zomes.push(ZomeCheckSummary {
+            name: zome_name.clone(),
+            integrity: dna_file.dna_def().is_integrity_zome(zome_name),
+            host_fn_imports,
+            num_entry_types: None,
+            num_link_types: None,
+            entry_defs: Vec::new(),
+        });
+    }
+    if report.has_errors() {
+        // Callbacks can't be called on zomes that can't be instantiated.
+        return Ok(());
+    }
+
+    let ribosome = match Ribosome::new(dna_def_hashed, real_ribosome.clone()).await {
+        Ok(ribosome) => ribosome,
+        Err(e) => {
+            report.error(None, format!("The DNA can't be loaded: {e}"));
+            return Ok(());
+        }
+    };
+
+    for (zome_name, def) in dna_file.dna_def().integrity_zomes.iter() {
+        let zome = Zome::new(zome_name.clone(), def.clone().erase_type());
+        let mut counts = [None, None];
+        for (count, name) in counts
+            .iter_mut()
+            .zip(["__num_entry_types", "__num_link_types"])
+        {
+            match real_ribosome
+                .call_const_fn(Arc::new(ribosome.clone()), zome.clone(), name.to_string())
+                .await
+            {
+                Ok(n) => *count = Some(n.and_then(|n| u8::try_from(n).ok()).unwrap_or_default()),
+                Err(e) => report.error(Some(zome_name), format!("Calling {name} failed: {e}")),
+            }
+        }
+        let num_entry_types = counts[0];
+        if let Some(summary) = report.zome_mut(zome_name) {
+            summary.num_entry_types = counts[0];
+            summary.num_link_types = counts[1];
+        }
+
+        if !entry_defs_exported.contains(zome_name) {
+            if num_entry_types.unwrap_or_default() > 0 {
+                report.error(
+                    Some(zome_name),
+                    "Declares entry types but does not export `entry_defs`",
+                );
+            } else {
+                report.warning(
+                    Some(zome_name),
+                    "Integrity zome does not export `entry_defs`, so it can't define entry types",
+                );
+            }
+        }
+    }
+
+    match ribosome
+        .run_entry_defs(EntryDefsHostAccess, EntryDefsInvocation::new())
+        .await
+    {
+        Ok(EntryDefsResult::Defs(defs)) => {
+            for (zome_name, EntryDefs(entry_defs)) in defs {
+                let Some(summary) = report.zome_mut(&zome_name) else {
+                    continue;
+                };
+                summary.entry_defs = entry_defs
+                    .iter()
+                    .map(|def| match &def.id {
+                        EntryDefId::App(name) => name.to_string(),
+                        EntryDefId::CapClaim => "CapClaim".to_string(),
+                        EntryDefId::CapGrant => "CapGrant".to_string(),
+                    })
+                    .collect();
+                let declared = summary.num_entry_types.unwrap_or_default() as usize;
+                if entry_defs.len() != declared {
+                    let defined = entry_defs.len();
+                    report.error(
+                        Some(&zome_name),
+                        format!("`entry_defs` returns {defined} entry defs but the zome declares {declared} entry types"),
+                    );
+                }
+            }
+        }
+        Ok(EntryDefsResult::Err(zome_name, e)) => {
+            report.error(Some(&zome_name), format!("`entry_defs` failed: {e}"));
+        }
+        Err(RibosomeError::EntryDefs(zome_name, e)) => {
+            report.error(Some(&zome_name), format!("`entry_defs` failed: {e}"));
+        }
+        Err(e) => report.error(None, format!("Calling `entry_defs` failed: {e}")),
+    }
+
+    Ok(())
+}
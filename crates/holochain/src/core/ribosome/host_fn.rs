@@ -63,6 +63,7 @@ pub fn cascade_from_call_context(call_context: &CallContext) -> CascadeImpl {
         call_context.host_context.network().clone(),
     )
     .with_zome_call_origin(call_context.zome.zome_name(), call_context.function_name())
+    .with_authority_disagreements(call_context.authority_disagreements.clone())
 }
 
 // All host_fn_api_impls below rely on this import
@@ -192,6 +193,9 @@ host_fn_api_impls! {
     // Remotely signal many agents without waiting for responses
     fn send_remote_signal (zt::prelude::RemoteSignal) -> ();
 
+    // Remotely signal many agents, keeping the signal for agents that can't be reached
+    fn send_remote_signal_durable (zt::prelude::DurableRemoteSignal) -> ();
+
     // Schedule a schedulable function if it is not already scheduled.
     fn schedule (String) -> ();
 
@@ -242,4 +246,10 @@ host_fn_api_impls! {
 
     // Get validation receipts for an action
     fn get_validation_receipts(zt::prelude::GetValidationReceiptsInput) -> Vec<zt::prelude::ValidationReceiptSet>;
+
+    // Get what is locally known about the reachability of agents
+    fn get_agent_presence(zt::prelude::GetAgentPresenceInput) -> Vec<zt::prelude::AgentPresence>;
+
+    // Get the actions that the authorities of aggregated reads in this call disagreed on
+    fn get_authority_disagreements(()) -> Vec<holo_hash::ActionHash>;
 }
@@ -0,0 +1,145 @@
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::{CallContext, Ribosome};
+use holochain_types::access::{HostFnAccess, Permission};
+use holochain_util::tokio_helper;
+use holochain_wasmer_host::prelude::{wasm_error, WasmError, WasmErrorInner, WasmHostError};
+use holochain_zome_types::prelude::{AgentPresence, GetAgentPresenceInput};
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[cfg_attr(feature = "instrument", tracing::instrument(skip(_ribosome, call_context), fields(?call_context.zome, function = ?call_context.function_name)))]
+pub fn get_agent_presence(
+    _ribosome: Arc<Ribosome>,
+    call_context: Arc<CallContext>,
+    input: GetAgentPresenceInput,
+) -> Result<Vec<AgentPresence>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            non_determinism: Permission::Allow,
+            ..
+        } => {
+            let network = call_context.host_context().network();
+            // Only reads the local peer stores, no requests are sent to the agents.
+            let presence = tokio_helper::block_forever_on(async move {
+                network.get_agent_presence(input.agents).await
+            })
+            .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?;
+
+            Ok(presence)
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_agent_presence".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::ribosome::host_fn::get_agent_presence::get_agent_presence;
+    use crate::core::ribosome::mock_ribosome::MockRibosomeBuilder;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::{fixt, Predictable, Unpredictable};
+    use holochain_p2p::MockHolochainP2pDnaT;
+    use holochain_zome_types::prelude::*;
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn call_get_agent_presence() {
+        let ribosome = MockRibosomeBuilder::new().build().await.unwrap();
+
+        let online = fixt!(AgentPubKey);
+        let unknown = fixt!(AgentPubKey);
+        let now = Timestamp::now();
+
+        let mut network = MockHolochainP2pDnaT::new();
+        network
+            .expect_get_agent_presence()
+            .withf({
+                let agents = vec![online.clone(), unknown.clone()];
+                move |input| *input == agents
+            })
+            .return_once({
+                let online = online.clone();
+                let unknown = unknown.clone();
+                move |_| {
+                    Ok(vec![
+                        AgentPresence {
+                            agent: online,
+                            agent_info_created_at: Some(now),
+                            agent_info_expires_at: Some(now),
+                            last_contact_at: Some(now),
+                            unresponsive: false,
+                            recently_online: true,
+                        },
+                        AgentPresence::unknown(unknown),
+                    ])
+                }
+            });
+
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        let mut host_access = fixt!(ZomeCallHostAccess, Predictable);
+        host_access.network = Arc::new(network);
+        call_context.host_context = host_access.into();
+
+        let presence = get_agent_presence(
+            Arc::new(ribosome),
+            Arc::new(call_context),
+            GetAgentPresenceInput::new(vec![online.clone(), unknown.clone()]),
+        )
+        .unwrap();
+
+        assert_eq!(2, presence.len());
+        assert_eq!(online, presence[0].agent);
+        assert!(presence[0].recently_online);
+        assert_eq!(Some(now), presence[0].last_contact_at);
+        assert_eq!(AgentPresence::unknown(unknown), presence[1]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn get_agent_presence_of_peers() {
+        use crate::sweettest::*;
+
+        holochain_trace::test_run();
+
+        let zome = InlineIntegrityZome::new_unique(vec![], 0).function(
+            "get_agent_presence",
+            |api, agents: Vec<AgentPubKey>| {
+                api.get_agent_presence(GetAgentPresenceInput::new(agents))
+                    .map_err(Into::into)
+            },
+        );
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(("zome", zome)).await;
+
+        let config = SweetConductorConfig::rendezvous(true);
+        let mut conductors = SweetConductorBatch::from_config_rendezvous(2, config).await;
+        let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+        let ((alice,), (bob,)) = apps.into_tuples();
+
+        conductors.exchange_peer_info().await;
+
+        let unknown = fixt!(AgentPubKey);
+        let presence: Vec<AgentPresence> = conductors[0]
+            .call(
+                &alice.zome("zome"),
+                "get_agent_presence",
+                vec![bob.agent_pubkey().clone(), unknown.clone()],
+            )
+            .await;
+
+        assert_eq!(2, presence.len());
+        assert_eq!(bob.agent_pubkey(), &presence[0].agent);
+        assert!(presence[0].agent_info_created_at.is_some());
+        assert!(presence[0].agent_info_expires_at.is_some());
+        assert!(!presence[0].unresponsive);
+        assert!(presence[0].recently_online);
+        assert_eq!(AgentPresence::unknown(unknown), presence[1]);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::{CallContext, Ribosome};
+use holo_hash::ActionHash;
+use holochain_types::access::{HostFnAccess, Permission};
+use holochain_wasmer_host::prelude::{wasm_error, WasmError, WasmErrorInner, WasmHostError};
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[cfg_attr(feature = "instrument", tracing::instrument(skip(_ribosome, call_context), fields(?call_context.zome, function = ?call_context.function_name)))]
+pub fn get_authority_disagreements(
+    _ribosome: Arc<Ribosome>,
+    call_context: Arc<CallContext>,
+    _input: (),
+) -> Result<Vec<ActionHash>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            // Recorded by the cascades of the reads made earlier in this call.
+            let mut disagreements: Vec<ActionHash> = call_context
+                .authority_disagreements
+                .lock()
+                .iter()
+                .cloned()
+                .collect();
+            disagreements.sort();
+            Ok(disagreements)
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_authority_disagreements".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::ribosome::host_fn::cascade_from_call_context;
+    use crate::core::ribosome::host_fn::get_authority_disagreements::get_authority_disagreements;
+    use crate::core::ribosome::mock_ribosome::MockRibosomeBuilder;
+    use crate::fixt::ActionHashFixturator;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::{fixt, Predictable, Unpredictable};
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn call_get_authority_disagreements() {
+        let ribosome = Arc::new(MockRibosomeBuilder::new().build().await.unwrap());
+
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        call_context.host_context = fixt!(ZomeCallHostAccess, Predictable).into();
+        let call_context = Arc::new(call_context);
+
+        assert!(
+            get_authority_disagreements(ribosome.clone(), call_context.clone(), ())
+                .unwrap()
+                .is_empty()
+        );
+
+        // The cascades that host fns build for a call share its disagreements.
+        let cascade = cascade_from_call_context(&call_context);
+        let mut disputed = vec![fixt!(ActionHash), fixt!(ActionHash)];
+        call_context
+            .authority_disagreements
+            .lock()
+            .extend(disputed.iter().cloned());
+        assert_eq!(
+            cascade.authority_disagreements(),
+            disputed.iter().cloned().collect()
+        );
+
+        disputed.sort();
+        assert_eq!(
+            get_authority_disagreements(ribosome, call_context, ()).unwrap(),
+            disputed
+        );
+    }
+}
@@ -3,6 +3,7 @@ use crate::core::ribosome::{CallContext, Ribosome};
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
 use futures::StreamExt;
+use holochain_cascade::get_options_ext::GetOptionsExt;
 use holochain_p2p::actor::GetLinksRequestOptions;
 use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
@@ -50,8 +51,8 @@ pub fn get_links(
                                     .dht_get_links(
                                         key,
                                         GetLinksRequestOptions {
+                                            network_req_options: get_options.to_network_options(),
                                             get_options,
-                                            ..Default::default()
                                         },
                                     )
                                     .await?)
@@ -95,6 +96,108 @@ pub fn get_links(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::core::ribosome::host_fn::get_links::get_links;
+    use crate::core::ribosome::mock_ribosome::MockRibosomeBuilder;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::{fixt, Predictable, Unpredictable};
+    use holochain_cascade::error::CascadeError;
+    use holochain_keystore::AgentPubKeyExt;
+    use holochain_p2p::MockHolochainP2pDnaT;
+    use holochain_types::prelude::*;
+    use holochain_wasmer_host::prelude::*;
+    use std::sync::Arc;
+
+    /// A zome that asks to fail on disagreement gets the hashes of the links
+    /// that the authorities disagree on, instead of the merged links.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_links_fails_on_authority_disagreement_when_asked() {
+        let ribosome = Arc::new(MockRibosomeBuilder::new().build().await.unwrap());
+
+        let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        let base = AnyLinkableHash::from(fixt!(EntryHash));
+        let action = Action {
+            header: ActionHeader {
+                author: author.clone(),
+                timestamp: Timestamp::now(),
+                action_seq: 4,
+                prev_action: Some(fixt!(ActionHash)),
+            },
+            data: ActionData::CreateLink(CreateLinkData {
+                base_address: base.clone(),
+                target_address: fixt!(EntryHash).into(),
+                zome_index: ZomeIndex(0),
+                link_type: LinkType(0),
+                tag: LinkTag::new("tag"),
+            }),
+        };
+        let signature = author.sign(&keystore, &action).await.unwrap();
+        let create_link_hash = ActionHash::with_data_sync(&action);
+        let create_link = SignedAction::new(action, signature);
+        let responses = vec![
+            WireLinkOps {
+                creates: vec![Judged::valid(create_link.clone())],
+                ..Default::default()
+            },
+            WireLinkOps {
+                creates: vec![Judged::new(create_link, ValidationStatus::Rejected)],
+                ..Default::default()
+            },
+        ];
+
+        let mut network = MockHolochainP2pDnaT::new();
+        network.expect_authority_for_hash().returning(|_| Ok(false));
+        network
+            .expect_get_links()
+            .times(2)
+            .returning(move |_, _, _| Ok(responses.clone()));
+
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        let mut host_access = fixt!(ZomeCallHostAccess, Predictable);
+        host_access.network = Arc::new(network);
+        call_context.host_context = host_access.into();
+        let call_context = Arc::new(call_context);
+
+        let input = |get_options: GetOptions| {
+            GetLinksInput::from_query(
+                LinkQuery::new(
+                    base.clone(),
+                    LinkTypeFilter::Dependencies(vec![ZomeIndex(0)]),
+                ),
+                get_options,
+            )
+        };
+        let aggregated = GetOptions::network()
+            .with_remote_agent_count(2)
+            .with_as_race(false);
+
+        let links = get_links(
+            ribosome.clone(),
+            call_context.clone(),
+            vec![input(aggregated.clone())],
+        )
+        .unwrap();
+        assert_eq!(links[0].len(), 1);
+        assert_eq!(links[0][0].create_link_hash, create_link_hash);
+
+        let err = get_links(
+            ribosome,
+            call_context,
+            vec![input(aggregated.with_fail_on_disagreement(true))],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.downcast::<WasmError>().unwrap().error,
+            WasmErrorInner::Host(
+                CascadeError::AuthorityDisagreement(vec![create_link_hash]).to_string()
+            )
+        );
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 pub mod slow_tests {
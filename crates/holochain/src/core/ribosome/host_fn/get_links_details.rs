@@ -3,6 +3,7 @@ use crate::core::ribosome::{CallContext, Ribosome};
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
 use futures::future::join_all;
+use holochain_cascade::get_options_ext::GetOptionsExt;
 use holochain_p2p::actor::GetLinksRequestOptions;
 use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
@@ -42,8 +43,8 @@ pub fn get_links_details(
                             .get_links_details(
                                 key,
                                 GetLinksRequestOptions {
+                                    network_req_options: get_options.to_network_options(),
                                     get_options,
-                                    ..Default::default()
                                 },
                             )
                             .await?)
@@ -5,8 +5,11 @@ use crate::core::ribosome::RibosomeError;
 use holochain_keystore::AgentPubKeyExt;
 use holochain_nonce::fresh_nonce;
 use holochain_types::access::Permission;
+use holochain_types::prelude::AgentPubKey;
 use holochain_types::prelude::CellId;
 use holochain_types::prelude::ExternIO;
+use holochain_types::prelude::OutboxSignalPayload;
+use holochain_types::prelude::DURABLE_SIGNAL_MAX_TTL;
 use holochain_wasmer_host::prelude::*;
 use holochain_zome_types::prelude::{RemoteSignal, FunctionName, ZomeCallParams};
 use std::sync::Arc;
@@ -17,12 +20,24 @@ use holochain_timestamp::Timestamp;
 
 #[cfg_attr(
     feature = "instrument",
-    tracing::instrument(skip(_ribosome, call_context, input))
+    tracing::instrument(skip(ribosome, call_context, input))
 )]
 pub fn send_remote_signal(
     ribosome: Arc<Ribosome>,
     call_context: Arc<CallContext>,
     input: RemoteSignal,
+) -> Result<(), RuntimeError> {
+    send(ribosome, call_context, input, None, "send_remote_signal")
+}
+
+/// Send a remote signal, and queue it in the outbox for agents it can't be
+/// sent to if a `durable_ttl` is given.
+pub(super) fn send(
+    ribosome: Arc<Ribosome>,
+    call_context: Arc<CallContext>,
+    input: RemoteSignal,
+    durable_ttl: Option<std::time::Duration>,
+    host_fn_name: &str,
 ) -> Result<(), RuntimeError> {
     match HostFnAccess::from(&call_context.host_context()) {
         HostFnAccess {
@@ -36,8 +51,33 @@ pub fn send_remote_signal(
                     .agent_initial_pubkey;
             // Timeouts and errors are ignored,
             // this is a send and forget operation.
+            // Unless the signal is durable, then agents that can't be
+            // reached get the signal from the outbox later.
             let network = call_context.host_context().network().clone();
             let RemoteSignal { agents, signal } = input;
+            // Undelivered durable signals are queued through the call zome handle,
+            // so it must be resolved before the signal is sent off.
+            let outbox = match durable_ttl {
+                Some(ttl) if ttl > DURABLE_SIGNAL_MAX_TTL => {
+                    return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                        "Durable signal TTL of {ttl:?} is longer than the maximum of {DURABLE_SIGNAL_MAX_TTL:?}"
+                    )))
+                    .into());
+                }
+                Some(ttl) => {
+                    let call_zome_handle = call_context
+                        .host_context
+                        .maybe_call_zome_handle()
+                        .cloned()
+                        .ok_or_else(|| {
+                            RuntimeError::from(wasm_error!(WasmErrorInner::Host(format!(
+                                "{host_fn_name} can't queue undelivered signals without a call zome handle"
+                            ))))
+                        })?;
+                    Some((call_zome_handle, ttl))
+                }
+                None => None,
+            };
             let zome_name = call_context.zome().zome_name().clone();
             let fn_name: FunctionName = FN_NAME.into();
 
@@ -94,7 +134,35 @@ pub fn send_remote_signal(
                             };
                         }
 
-                        if let Err(e) = network.send_remote_signal(to_agent_list).await {
+                        let targets: Vec<AgentPubKey> = to_agent_list
+                            .iter()
+                            .map(|(agent, _, _)| agent.clone())
+                            .collect();
+                        let result = network.send_remote_signal(to_agent_list).await;
+
+                        if let Some((call_zome_handle, ttl)) = outbox {
+                            let undelivered = match &result {
+                                Ok(undelivered) => undelivered.clone(),
+                                Err(_) => targets,
+                            };
+                            if !undelivered.is_empty() {
+                                if let Err(e) = call_zome_handle
+                                    .queue_outbox_signals(
+                                        undelivered,
+                                        OutboxSignalPayload::Zome { zome_name, signal },
+                                        ttl,
+                                    )
+                                    .await
+                                {
+                                    tracing::warn!(
+                                        "Failed to queue remote signals because of {:?}",
+                                        e
+                                    );
+                                }
+                            }
+                        }
+
+                        if let Err(e) = result {
                             tracing::info!("Failed to send remote signals because of {:?}", e);
                         } else {
                             // Record sent remote signal
@@ -119,7 +187,7 @@ pub fn send_remote_signal(
             RibosomeError::HostFnPermissions(
                 call_context.zome.zome_name().clone(),
                 call_context.function_name().clone(),
-                "send_remote_signal".into(),
+                host_fn_name.into(),
             )
             .to_string(),
         ))
@@ -136,6 +204,7 @@ mod tests {
     use crate::sweettest::*;
     use futures::future;
     use hdk::prelude::*;
+    use holochain_types::signal::Signal;
 
     fn test_zome(agents: Vec<AgentPubKey>, num_signals: Arc<AtomicUsize>) -> InlineIntegrityZome {
         let entry_def = EntryDef::default_from_id("entrydef");
@@ -222,4 +291,62 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn durable_signal_fails_without_call_zome_handle() {
+        holochain_trace::test_run();
+
+        // Post commit after init has no call zome handle to queue undelivered
+        // signals with, so the durable send must fail in the zome.
+        let zomes = SweetInlineZomes::new(vec![], 0)
+            .function("init", move |api, ()| {
+                api.create(CreateInput::new(
+                    EntryDefLocation::CapGrant,
+                    EntryVisibility::Private,
+                    Entry::CapGrant(CapGrantEntry {
+                        tag: "".into(),
+                        access: ().into(),
+                        functions: GrantedFunctions::Listed(HashSet::new()),
+                    }),
+                    ChainTopOrdering::default(),
+                ))?;
+                Ok(InitCallbackResult::Pass)
+            })
+            .function("post_commit", move |api, _: Vec<SignedActionHashed>| {
+                let result = api.send_remote_signal_durable(DurableRemoteSignal {
+                    remote_signal: RemoteSignal {
+                        agents: vec![],
+                        signal: ExternIO::encode("Hey").unwrap(),
+                    },
+                    ttl: std::time::Duration::from_secs(60),
+                });
+                api.emit_signal(AppSignal::new(ExternIO::encode(result.is_err())?))?;
+                Ok(())
+            })
+            .function("noop", |_, ()| Ok(()))
+            .0;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+
+        let mut conductor = SweetConductor::standard().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let mut rx = conductor.subscribe_to_app_signals("app".into());
+
+        // The first zome call runs init
+        let _: () = conductor
+            .call(&cell.zome(SweetInlineZomes::COORDINATOR), "noop", ())
+            .await;
+
+        match rx.recv().await.unwrap() {
+            Signal::App { signal, .. } => {
+                let failed: bool = signal.into_inner().decode().unwrap();
+                assert!(
+                    failed,
+                    "durable signal should fail without a call zome handle"
+                );
+            }
+            s => unreachable!("unexpected signal: {:?}", s),
+        }
+    }
 }
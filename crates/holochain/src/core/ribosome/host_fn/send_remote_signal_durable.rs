@@ -0,0 +1,24 @@
+use super::send_remote_signal::send;
+use crate::core::ribosome::{CallContext, Ribosome};
+use holochain_zome_types::prelude::DurableRemoteSignal;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[cfg_attr(
+    feature = "instrument",
+    tracing::instrument(skip(ribosome, call_context, input))
+)]
+pub fn send_remote_signal_durable(
+    ribosome: Arc<Ribosome>,
+    call_context: Arc<CallContext>,
+    input: DurableRemoteSignal,
+) -> Result<(), RuntimeError> {
+    let DurableRemoteSignal { remote_signal, ttl } = input;
+    send(
+        ribosome,
+        call_context,
+        remote_signal,
+        Some(ttl),
+        "send_remote_signal_durable",
+    )
+}
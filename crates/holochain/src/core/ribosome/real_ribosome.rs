@@ -29,6 +29,8 @@ use crate::core::ribosome::host_fn::ed_25519_x_salsa20_poly1305_decrypt::ed_2551
 use crate::core::ribosome::host_fn::ed_25519_x_salsa20_poly1305_encrypt::ed_25519_x_salsa20_poly1305_encrypt;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
 use crate::core::ribosome::host_fn::get::get;
+use crate::core::ribosome::host_fn::get_agent_presence::get_agent_presence;
+use crate::core::ribosome::host_fn::get_authority_disagreements::get_authority_disagreements;
 use crate::core::ribosome::host_fn::get_details::get_details;
 use crate::core::ribosome::host_fn::get_init_properties::get_init_properties;
 use crate::core::ribosome::host_fn::get_links::get_links;
@@ -43,6 +45,7 @@ use crate::core::ribosome::host_fn::query::query;
 use crate::core::ribosome::host_fn::random_bytes::random_bytes;
 use crate::core::ribosome::host_fn::schedule::schedule;
 use crate::core::ribosome::host_fn::send_remote_signal::send_remote_signal;
+use crate::core::ribosome::host_fn::send_remote_signal_durable::send_remote_signal_durable;
 use crate::core::ribosome::host_fn::sign::sign;
 use crate::core::ribosome::host_fn::sign_ephemeral::sign_ephemeral;
 #[cfg(feature = "unstable-functions")]
@@ -127,6 +130,9 @@ pub struct RealRibosome {
 
     /// Database and in-memory cache for WASM modules.
     wasmer_module_cache: Arc<ModuleCache>,
+
+    /// Metering budgets for calls into the zomes of this ribosome.
+    wasm_metering: Arc<WasmMeteringBudgets>,
 }
 
 type ContextMap = Lazy<Arc<Mutex<HashMap<u64, Arc<CallContext>>>>>;
@@ -234,9 +240,51 @@ impl RealRibosome {
             backend,
             dna_def: Arc::new(Mutex::new(dna_def)),
             wasmer_module_cache,
+            wasm_metering: Default::default(),
         })
     }
 
+    /// Set the metering budgets for calls into the zomes of this ribosome.
+    ///
+    /// Calls without a budget are limited to [`WASM_METERING_LIMIT`] points.
+    ///
+    /// [`WASM_METERING_LIMIT`]: holochain_wasmer_host::module::sys::WASM_METERING_LIMIT
+    pub fn with_wasm_metering(mut self, wasm_metering: WasmMeteringBudgets) -> Self {
+        self.wasm_metering = Arc::new(wasm_metering);
+        self
+    }
+
+    /// The metering points available to calls without a budget, if this
+    /// build meters wasm execution.
+    pub fn default_metering_limit() -> Option<u64> {
+        #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
+        {
+            Some(wasmer_sys::WASM_METERING_LIMIT)
+        }
+        #[cfg(not(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm")))]
+        {
+            None
+        }
+    }
+
+    /// The metering points available to a call.
+    ///
+    /// Validation callbacks use the validation budget, every other call uses
+    /// the budget of the zome function it calls.
+    #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
+    fn metering_limit(&self, call_context: &CallContext) -> u64 {
+        match call_context.host_context {
+            HostContext::Validate(_)
+            | HostContext::GenesisSelfCheckV1(_)
+            | HostContext::GenesisSelfCheckV2(_) => self.wasm_metering.validation,
+            _ => self.wasm_metering.zome_fn_budget(
+                call_context.zome.zome_name().0.as_ref(),
+                call_context.function_name.as_ref(),
+            ),
+        }
+        .unwrap_or(wasmer_sys::WASM_METERING_LIMIT)
+    }
+
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self)))]
     async fn get_from_cache_or_build(&self, zome_name: &ZomeName) -> RibosomeResult<Arc<Module>> {
         let cache_key = self.get_module_cache_key(zome_name)?;
@@ -486,6 +534,11 @@ impl RealRibosome {
             )
             .with_host_function(&mut ns, "__hc__query_1", query)
             .with_host_function(&mut ns, "__hc__send_remote_signal_1", send_remote_signal)
+            .with_host_function(
+                &mut ns,
+                "__hc__send_remote_signal_durable_1",
+                send_remote_signal_durable,
+            )
             .with_host_function(&mut ns, "__hc__call_1", call)
             .with_host_function(&mut ns, "__hc__create_1", create)
             .with_host_function(&mut ns, "__hc__emit_signal_1", emit_signal)
@@ -504,6 +557,12 @@ impl RealRibosome {
                 &mut ns,
                 "__hc__get_validation_receipts_1",
                 get_validation_receipts,
+            )
+            .with_host_function(&mut ns, "__hc__get_agent_presence_1", get_agent_presence)
+            .with_host_function(
+                &mut ns,
+                "__hc__get_authority_disagreements_1",
+                get_authority_disagreements,
             );
 
         #[cfg(feature = "unstable-countersigning")]
@@ -589,6 +648,8 @@ impl RibosomeImplT for RealRibosome {
                 let context_key = Self::next_context_key();
                 let instance_with_store =
                     this.build_instance_with_store(ribosome, module, context_key, &zome.name.0)?;
+                #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
+                let metering_limit = this.metering_limit(&call_context);
                 // add call context to map for the following call
                 {
                     CONTEXT_MAP
@@ -596,9 +657,9 @@ impl RibosomeImplT for RealRibosome {
                         .insert(context_key, Arc::new(call_context));
                 }
 
-                // Reset available metering points to the maximum allowed per zome call
+                // Reset available metering points to the budget for this call
                 #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
-                wasmer_sys::reset_metering_points(instance_with_store.clone());
+                wasmer_sys::reset_metering_points(instance_with_store.clone(), metering_limit);
 
                 let input = invocation
                     .take_host_input()?
@@ -617,10 +678,13 @@ impl RibosomeImplT for RealRibosome {
                 #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
                 {
                     // Get metering points consumed in zome call and save to usage_meter
-                    let points_used =
-                        wasmer_sys::get_used_metering_points(instance_with_store.clone());
+                    let points_used = wasmer_sys::get_used_metering_points(
+                        instance_with_store.clone(),
+                        metering_limit,
+                    );
                     crate::core::metrics::ribosome_wasm_usage_metric()
                         .add(points_used, &attributes);
+                    invocation.record_points_consumed(points_used);
                 }
 
                 // remove context from map after call
@@ -660,6 +724,7 @@ impl RibosomeImplT for RealRibosome {
                             function_name: name.clone().into(),
                             host_context: HostContext::EntryDefs(EntryDefsHostAccess {}),
                             auth: super::InvocationAuth::LocalCallback,
+                            authority_disagreements: Default::default(),
                         };
 
                         // create a new key for the context map.
@@ -984,6 +1049,8 @@ mod test {
                 "__hc__enable_clone_cell_1",
                 "__hc__get_1",
                 "__hc__get_agent_activity_1",
+                "__hc__get_agent_presence_1",
+                "__hc__get_authority_disagreements_1",
                 "__hc__get_details_1",
                 "__hc__get_init_properties_1",
                 "__hc__get_links_1",
@@ -998,6 +1065,7 @@ mod test {
                 "__hc__random_bytes_1",
                 "__hc__schedule_1",
                 "__hc__send_remote_signal_1",
+                "__hc__send_remote_signal_durable_1",
                 "__hc__sign_1",
                 "__hc__sign_ephemeral_1",
                 #[cfg(feature = "unstable-functions")]
@@ -1037,6 +1105,119 @@ mod test {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
+    async fn zome_fn_metering_budget_traps_call() {
+        use holochain_conductor_api::conductor::WasmMeteringConfig;
+
+        holochain_trace::test_run();
+        let zome_name = TestWasm::TheIncredibleHalt.coordinator_zome_name();
+        let mut config = SweetConductorConfig::rendezvous(true);
+        config.wasm_metering = Some(WasmMeteringConfig {
+            default: WasmMeteringBudgets {
+                zome_fns: [(
+                    zome_name.to_string(),
+                    [("smash".to_string(), 1_000_000)].into(),
+                )]
+                .into(),
+                ..Default::default()
+            },
+            apps: Default::default(),
+        });
+        let mut conductor =
+            SweetConductor::from_config_rendezvous(config, SweetLocalRendezvous::new().await).await;
+        let (dna, _, _) =
+            SweetDnaFile::unique_from_test_wasms(vec![TestWasm::TheIncredibleHalt]).await;
+        let app = conductor.setup_app("", [&dna]).await.unwrap();
+        let zome = app.cells()[0].zome(zome_name);
+
+        // Without the budget this would loop until the much larger default
+        // limit is reached.
+        let result: Result<Result<(), _>, _> = tokio::time::timeout(
+            Duration::from_secs(10),
+            conductor.call_fallible(&zome, "smash", ()),
+        )
+        .await;
+        assert!(result.unwrap().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
+    async fn external_zome_call_reports_points_consumed() {
+        holochain_trace::test_run();
+        let conductor = SweetConductor::standard().await;
+        let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+        let app = conductor.setup_app("app", [&dna]).await.unwrap();
+        let cell_id = app.cells()[0].cell_id().clone();
+
+        let call = crate::test_utils::new_zome_call(
+            &conductor.keystore(),
+            &cell_id,
+            "foo",
+            (),
+            TestWasm::Foo.coordinator_zome_name(),
+        )
+        .await
+        .unwrap();
+        let (result, points_consumed) = conductor
+            .raw_handle()
+            .handle_external_zome_call(&"app".to_string(), call)
+            .await
+            .unwrap();
+
+        assert!(matches!(result, Ok(ZomeCallResponse::Ok(_))));
+        let points_consumed = points_consumed.unwrap();
+        assert!(points_consumed > 0);
+        assert!(points_consumed < wasmer_sys::WASM_METERING_LIMIT);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(any(feature = "wasmer-sys-cranelift", feature = "wasmer-sys-llvm"))]
+    async fn install_budgets_apply_to_genesis() {
+        use crate::conductor::conductor::InstallAppCommonFlags;
+
+        holochain_trace::test_run();
+        let conductor = SweetConductor::standard().await;
+        let (dna, _, _) =
+            SweetDnaFile::unique_from_test_wasms(vec![TestWasm::GenesisSelfCheckValid]).await;
+        let flags = |validation| {
+            Some(InstallAppCommonFlags {
+                defer_memproofs: false,
+                ignore_genesis_failure: false,
+                wasm_metering: Some(WasmMeteringBudgets {
+                    validation: Some(validation),
+                    ..Default::default()
+                }),
+            })
+        };
+
+        // The genesis self check cannot run within a budget of one point.
+        let result = conductor
+            .raw_handle()
+            .install_app_minimal(
+                "starved".to_string(),
+                None,
+                &[(dna.clone(), None)],
+                None,
+                flags(1),
+            )
+            .await;
+        assert!(result.is_err());
+
+        // Budgets above the limit of the wasm host are lowered to it.
+        conductor
+            .raw_handle()
+            .install_app_minimal(
+                "generous".to_string(),
+                None,
+                &[(dna.clone(), None)],
+                None,
+                flags(u64::MAX),
+            )
+            .await
+            .unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[ignore]
     async fn the_incredible_halt_test() {
@@ -1,25 +1,22 @@
-use holochain_wasmer_host::module::sys::WASM_METERING_LIMIT;
 use holochain_wasmer_host::module::InstanceWithStore;
 use std::sync::Arc;
 use wasmer::AsStoreMut;
 use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
 
-pub fn reset_metering_points(instance_with_store: Arc<InstanceWithStore>) {
+pub use holochain_wasmer_host::module::sys::WASM_METERING_LIMIT;
+
+pub fn reset_metering_points(instance_with_store: Arc<InstanceWithStore>, limit: u64) {
     let mut store_lock = instance_with_store.store.lock();
     let mut store_mut = store_lock.as_store_mut();
-    set_remaining_points(
-        &mut store_mut,
-        instance_with_store.instance.as_ref(),
-        WASM_METERING_LIMIT,
-    );
+    set_remaining_points(&mut store_mut, instance_with_store.instance.as_ref(), limit);
 }
 
-pub fn get_used_metering_points(instance_with_store: Arc<InstanceWithStore>) -> u64 {
+pub fn get_used_metering_points(instance_with_store: Arc<InstanceWithStore>, limit: u64) -> u64 {
     let mut store_lock = instance_with_store.store.lock();
     let mut store_mut = store_lock.as_store_mut();
 
     match get_remaining_points(&mut store_mut, instance_with_store.instance.as_ref()) {
-        MeteringPoints::Remaining(points) => WASM_METERING_LIMIT - points,
-        MeteringPoints::Exhausted => WASM_METERING_LIMIT,
+        MeteringPoints::Remaining(points) => limit.saturating_sub(points),
+        MeteringPoints::Exhausted => limit,
     }
 }
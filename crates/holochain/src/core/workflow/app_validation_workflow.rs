@@ -215,6 +215,8 @@ async fn app_validation_workflow_inner(
     // Locally-validated warrant ops, self-published into the DhtStore.
     let mut warrant_ops_vec: Vec<DhtOpHashed> = vec![];
     let mut app_validation_outcomes: Vec<(DhtOpHash, AppOutcome)> = vec![];
+    // Why validation of an op did not complete, shown to operators.
+    let mut validation_errors: Vec<(DhtOpHash, String)> = vec![];
     // Track action hashes already warranted in this batch to avoid creating duplicate
     // warrants for the same action. Multiple op types (CreateRecord, CreateEntry,
     // AgentActivity) can share the same action, and without this deduplication
@@ -343,9 +345,11 @@ async fn app_validation_workflow_inner(
                         accepted_ops.fetch_add(1, Ordering::SeqCst);
                         app_validation_outcomes.push((dht_op_hash, AppOutcome::Accepted));
                     }
-                    Outcome::AwaitingDeps(_) => {
-                        // Status stays NULL; nothing to record.
+                    Outcome::AwaitingDeps(deps) => {
+                        // Status stays NULL; only the missing deps are recorded.
                         awaiting_ops.fetch_add(1, Ordering::SeqCst);
+                        validation_errors
+                            .push((dht_op_hash, format!("Awaiting dependencies {deps:?}")));
                     }
                     Outcome::Rejected(_) => {
                         rejected_ops.fetch_add(1, Ordering::SeqCst);
@@ -362,6 +366,7 @@ async fn app_validation_workflow_inner(
                     ?err,
                     "App validation error when validating dht op."
                 );
+                validation_errors.push((dht_op_hash.clone(), err.to_string()));
                 failed_ops.lock().insert(dht_op_hash);
             }
         }
@@ -384,6 +389,12 @@ async fn app_validation_workflow_inner(
             .record_app_validation_outcomes(app_validation_outcomes)
             .await?;
     }
+    if !validation_errors.is_empty() {
+        workspace
+            .dht_store
+            .record_validation_errors(validation_errors)
+            .await?;
+    }
 
     // "self-publish" locally-validated warrant ops into the DhtStore as if they
     // were published to us by another node.
@@ -283,6 +283,8 @@ async fn sys_validation_workflow_inner(
     .await;
 
     let mut validation_outcomes = Vec::with_capacity(sorted_ops.len());
+    // Why validation of an op did not complete, shown to operators.
+    let mut validation_errors: Vec<(DhtOpHash, String)> = Vec::new();
     for hashed_op in sorted_ops {
         // Note that this is async only because of the signature checks done during countersigning.
         // In most cases this will be a fast synchronous call.
@@ -297,6 +299,7 @@ async fn sys_validation_workflow_inner(
             Ok(outcome) => validation_outcomes.push((hashed_op, outcome)),
             Err(e) => {
                 tracing::error!(error = ?e, "Error validating op");
+                validation_errors.push((hashed_op.as_hash().clone(), e.to_string()));
             }
         }
     }
@@ -339,6 +342,10 @@ async fn sys_validation_workflow_inner(
             Outcome::MissingDhtDep => {
                 // Awaiting dependencies — status stays NULL; no outcome to record.
                 summary.missing += 1;
+                validation_errors.push((
+                    op_hash.clone(),
+                    "A dependency of the op could not be found on the DHT".to_string(),
+                ));
             }
             Outcome::Rejected(reason) => {
                 invalid_ops.push((op_hash.clone(), op.clone(), reason));
@@ -389,6 +396,12 @@ async fn sys_validation_workflow_inner(
         .dht_store
         .record_warrant_sys_validation_outcomes(warrant_sys_outcomes)
         .await?;
+    if !validation_errors.is_empty() {
+        workspace
+            .dht_store
+            .record_validation_errors(validation_errors)
+            .await?;
+    }
 
     {
         let mut warrants = vec![];
@@ -746,7 +759,7 @@ async fn retrieve_dependencies(
         .merge(new_deps);
 }
 
-fn get_dependency_hashes_from_actions(
+pub(crate) fn get_dependency_hashes_from_actions(
     actions: impl Iterator<Item = Action>,
 ) -> Vec<(ActionHash, ValidationDependencyType)> {
     actions
@@ -69,6 +69,16 @@ impl ValidationDependencies {
             .unwrap_or(false)
     }
 
+    /// Check whether a dependency has been looked for but not found yet.
+    ///
+    /// Unlike [`Self::has`], this does not mark the dependency as retained, so it is safe to call
+    /// from outside the validation workflow.
+    pub fn is_missing(&self, hash: &ActionHash) -> bool {
+        self.states
+            .get(hash)
+            .is_some_and(|state| state.dependency.is_none())
+    }
+
     /// Get the state of a given dependency.
     ///
     /// This should always return a value because we should know about the dependency by examining
@@ -416,7 +426,7 @@ pub(super) struct ValidationDependency {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub(super) enum ValidationDependencyType {
+pub(crate) enum ValidationDependencyType {
     Action,
     Warranted(ChainOpType),
 }
@@ -11,6 +11,10 @@
 //! A handy collection of cells installed under the same app.
 //! Makes it easy to destructure the result of a SweetConductor::setup_app call
 //! into a collection of SweetCells which can be used for zome calls.
+//!
+//! ### SweetNetworkController
+//! A handle for dropping, delaying, duplicating and partitioning the network
+//! traffic between SweetConductors while a test is running.
 
 mod sweet_agents;
 mod sweet_app;
@@ -22,6 +26,7 @@ mod sweet_conductor_config;
 mod sweet_conductor_handle;
 pub mod sweet_consistency;
 mod sweet_dna;
+mod sweet_network;
 mod sweet_rendezvous;
 mod sweet_zome;
 
@@ -35,5 +40,6 @@ pub use sweet_conductor_config::*;
 pub use sweet_conductor_handle::*;
 pub use sweet_consistency::*;
 pub use sweet_dna::*;
+pub use sweet_network::*;
 pub use sweet_rendezvous::*;
 pub use sweet_zome::*;
@@ -24,6 +24,7 @@ pub async fn app_bundle_from_dnas(
                     clone_limit: 255,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
+                network: None,
             };
             let bundle = DnaBundle::from_dna_file(dna.clone()).unwrap();
             (manifest, (path, bundle))
@@ -53,6 +53,7 @@ pub struct SweetConductor {
     config: Arc<ConductorConfig>,
     inline_zome_store_ref: InlineZomeStore,
     rendezvous: Option<DynSweetRendezvous>,
+    network_controller: Option<SweetNetworkController>,
 }
 
 /// ID based equality is good for SweetConductors so we can track them
@@ -82,6 +83,7 @@ impl SweetConductor {
         env_dir: TestDir,
         config: Arc<ConductorConfig>,
         rendezvous: Option<DynSweetRendezvous>,
+        network_controller: Option<SweetNetworkController>,
     ) -> SweetConductor {
         let keystore = handle.keystore().clone();
 
@@ -92,6 +94,7 @@ impl SweetConductor {
             keystore,
             config,
             rendezvous,
+            network_controller,
         }
     }
 
@@ -161,6 +164,7 @@ impl SweetConductor {
         }
 
         let config: SweetConductorConfig = config.into();
+        let network_controller = config.network_controller().cloned();
         let mut config: ConductorConfig = if let Some(r) = rendezvous.clone() {
             config.apply_rendezvous(&r).into()
         } else {
@@ -192,11 +196,24 @@ impl SweetConductor {
 
         let keystore = keystore.unwrap_or_else(holochain_keystore::test_keystore);
 
-        let handle = Self::handle_from_existing(keystore, &config, Default::default()).await;
+        let handle = Self::handle_with_network_controller(
+            keystore,
+            &config,
+            Default::default(),
+            network_controller.as_ref(),
+        )
+        .await;
 
         info!("Starting with config: {:?}", config);
 
-        Self::new(handle, dir, Arc::new(config), rendezvous).await
+        Self::new(
+            handle,
+            dir,
+            Arc::new(config),
+            rendezvous,
+            network_controller,
+        )
+        .await
     }
 
     /// Create a handle from an existing environment and config
@@ -204,17 +221,39 @@ impl SweetConductor {
         keystore: MetaLairClient,
         config: &ConductorConfig,
         inline_zome_store_ref: InlineZomeStore,
+    ) -> ConductorHandle {
+        Self::handle_with_network_controller(keystore, config, inline_zome_store_ref, None).await
+    }
+
+    /// Create a handle from an existing environment and config, with the network
+    /// traffic going through the faults of the given controller.
+    async fn handle_with_network_controller(
+        keystore: MetaLairClient,
+        config: &ConductorConfig,
+        inline_zome_store_ref: InlineZomeStore,
+        network_controller: Option<&SweetNetworkController>,
     ) -> ConductorHandle {
         NUM_CREATED.fetch_add(1, Ordering::SeqCst);
 
-        Conductor::builder()
+        let mut builder = Conductor::builder()
             .config(config.clone())
             .with_keystore(keystore)
             .with_inline_zome_store(inline_zome_store_ref)
-            .no_print_setup()
-            .test()
-            .await
-            .unwrap()
+            .no_print_setup();
+
+        if let Some(controller) = network_controller {
+            let name = config
+                .tracing_scope()
+                .expect("SweetConductor must have a tracing scope set");
+            builder = builder.with_wrap_transport(controller.wrap_transport(name));
+        }
+
+        builder.test().await.unwrap()
+    }
+
+    /// The network controller that this conductor's traffic is going through, if any.
+    pub fn network_controller(&self) -> Option<&SweetNetworkController> {
+        self.network_controller.as_ref()
     }
 
     /// Get the rendezvous config that this conductor is using, if any
@@ -588,7 +627,13 @@ impl SweetConductor {
     {
         let port = self
             .raw_handle()
-            .add_app_interface(either::Either::Left(0), None, AllowedOrigins::Any, None)
+            .add_app_interface(
+                either::Either::Left(0),
+                None,
+                AllowedOrigins::Any,
+                None,
+                None,
+            )
             .await
             .expect("Couldn't create app interface");
         let (tx, rx) = websocket_client_by_port(port).await.unwrap();
@@ -646,10 +691,11 @@ impl SweetConductor {
             );
 
             self.handle = Some(SweetConductorHandle(
-                Self::handle_from_existing(
+                Self::handle_with_network_controller(
                     self.keystore.clone(),
                     &self.config,
                     self.inline_zome_store_ref.clone(),
+                    self.network_controller.as_ref(),
                 )
                 .await,
             ));
@@ -1,4 +1,4 @@
-use super::DynSweetRendezvous;
+use super::{DynSweetRendezvous, SweetNetworkController};
 use holochain_conductor_api::{
     conductor::{ConductorConfig, ConductorTuningParams, NetworkConfig},
     AdminInterfaceConfig, InterfaceDriver,
@@ -10,17 +10,23 @@ pub(crate) static NUM_CREATED: AtomicUsize = AtomicUsize::new(0);
 
 /// Wrapper around [`ConductorConfig`] with some helpful builder methods, setting
 /// default values for testing.
-#[derive(Clone, derive_more::Deref, derive_more::DerefMut, derive_more::Into)]
+#[derive(Clone, derive_more::Deref, derive_more::DerefMut)]
 pub struct SweetConductorConfig(
     #[deref]
     #[deref_mut]
-    #[into]
     ConductorConfig,
+    Option<SweetNetworkController>,
 );
 
 impl From<ConductorConfig> for SweetConductorConfig {
     fn from(config: ConductorConfig) -> Self {
-        Self(config)
+        Self(config, None)
+    }
+}
+
+impl From<SweetConductorConfig> for ConductorConfig {
+    fn from(config: SweetConductorConfig) -> Self {
+        config.0
     }
 }
 
@@ -111,4 +117,16 @@ impl SweetConductorConfig {
         f(&mut self.network);
         self
     }
+
+    /// Send the network traffic of the conductor through a transport that
+    /// injects the faults set on the given controller.
+    pub fn with_network_controller(mut self, controller: &SweetNetworkController) -> Self {
+        self.1 = Some(controller.clone());
+        self
+    }
+
+    /// The network controller that this config was given, if any.
+    pub fn network_controller(&self) -> Option<&SweetNetworkController> {
+        self.1.as_ref()
+    }
 }
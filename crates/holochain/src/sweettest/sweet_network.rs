@@ -0,0 +1,443 @@
+//! A fault-injecting wrapper around the network transport of SweetConductors.
+//!
+//! Conductors whose config was given a [`SweetNetworkController`] send all of
+//! their outgoing Kitsune2 traffic through a fault-injecting wrapper around
+//! their transport. The controller can then drop, delay, duplicate and
+//! partition the traffic between them while the test is running.
+//!
+//! Conductors are named by their [`SweetConductor::id`](super::SweetConductor::id).
+//! Faults are applied by the sending conductor, so traffic to and from
+//! conductors that don't share the controller is not affected.
+
+use bytes::Bytes;
+use kitsune2_api::{
+    ApiTransportStats, BoxFut, Builder, Config, DynTransport, DynTransportFactory, DynTxHandler,
+    DynTxModuleHandler, DynTxSpaceHandler, K2Error, K2Result, SpaceId, Transport, TransportFactory,
+    Url,
+};
+use parking_lot::Mutex;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The faults to inject into the traffic sent from one conductor to another.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkFaults {
+    /// The probability, between 0 and 1, that a message is silently dropped.
+    pub drop_probability: f64,
+
+    /// The probability, between 0 and 1, that a message is delivered twice.
+    pub duplicate_probability: f64,
+
+    /// How long messages are held back before they are delivered.
+    pub delay: Duration,
+
+    /// A random extra delay of up to this duration, added to each message.
+    ///
+    /// Messages with different delays can be delivered out of order.
+    pub jitter: Duration,
+}
+
+impl LinkFaults {
+    /// Drop every message.
+    pub fn drop_all() -> Self {
+        Self {
+            drop_probability: 1.0,
+            ..Default::default()
+        }
+    }
+
+    /// Drop messages with the given probability.
+    pub fn lossy(drop_probability: f64) -> Self {
+        Self {
+            drop_probability,
+            ..Default::default()
+        }
+    }
+
+    /// Delay every message by the given duration.
+    pub fn delayed(delay: Duration) -> Self {
+        Self {
+            delay,
+            ..Default::default()
+        }
+    }
+
+    /// Deliver messages twice with the given probability.
+    pub fn duplicating(duplicate_probability: f64) -> Self {
+        Self {
+            duplicate_probability,
+            ..Default::default()
+        }
+    }
+
+    fn is_none(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How many of the messages sent from one conductor to another have had faults
+/// injected into them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LinkFaultCounts {
+    /// Messages that were silently dropped.
+    pub dropped: u64,
+
+    /// Messages that could not be sent because the conductors were in
+    /// different partitions.
+    pub partitioned: u64,
+
+    /// Messages that were held back before being delivered.
+    pub delayed: u64,
+
+    /// Messages that were delivered twice.
+    pub duplicated: u64,
+}
+
+#[derive(Debug, Default)]
+struct NetworkState {
+    /// The conductor name that each transport URL belongs to
+    names: HashMap<Url, String>,
+    /// The faults on each link, keyed by sending and receiving conductor
+    links: HashMap<(String, String), LinkFaults>,
+    /// The faults on links that have no faults of their own
+    default_faults: LinkFaults,
+    /// The partition that each conductor is in, if the network is partitioned
+    partitions: HashMap<String, usize>,
+    /// The faults injected so far on each link, keyed by sending and receiving conductor
+    counts: HashMap<(String, String), LinkFaultCounts>,
+}
+
+/// What should happen to a message sent from one conductor to another.
+enum Verdict {
+    Deliver { copies: usize, delay: Duration },
+    Drop,
+    Partitioned,
+}
+
+/// A handle for controlling the faults injected into the network traffic
+/// between SweetConductors, at runtime.
+///
+/// Select the controller for a conductor with
+/// [`SweetConductorConfig::with_network_controller`](super::SweetConductorConfig::with_network_controller).
+/// All conductors that should have faults injected between them must share the
+/// same controller.
+#[derive(Clone, Debug, Default)]
+pub struct SweetNetworkController(Arc<Mutex<NetworkState>>);
+
+impl SweetNetworkController {
+    /// Create a controller that does not inject any faults until told to.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the faults on the traffic sent from one conductor to another.
+    ///
+    /// Only that direction is affected, use [`Self::set_link_faults`] to set
+    /// both directions.
+    pub fn set_faults(&self, from: &str, to: &str, faults: LinkFaults) {
+        let mut state = self.0.lock();
+        let key = (from.to_string(), to.to_string());
+        if faults.is_none() {
+            state.links.remove(&key);
+        } else {
+            state.links.insert(key, faults);
+        }
+    }
+
+    /// Set the faults on the traffic between two conductors, in both directions.
+    pub fn set_link_faults(&self, a: &str, b: &str, faults: LinkFaults) {
+        self.set_faults(a, b, faults.clone());
+        self.set_faults(b, a, faults);
+    }
+
+    /// Set the faults on the traffic between all conductors that have no faults
+    /// of their own set with [`Self::set_faults`].
+    pub fn set_default_faults(&self, faults: LinkFaults) {
+        self.0.lock().default_faults = faults;
+    }
+
+    /// Split the network into partitions.
+    ///
+    /// Conductors in different partitions can't reach each other. Conductors
+    /// that aren't in any of the partitions can still reach every conductor.
+    /// This replaces any previous partitioning.
+    pub fn partition(&self, partitions: &[&[&str]]) {
+        self.0.lock().partitions = partitions
+            .iter()
+            .enumerate()
+            .flat_map(|(index, names)| names.iter().map(move |name| (name.to_string(), index)))
+            .collect();
+    }
+
+    /// Cut one conductor off from all other conductors.
+    ///
+    /// Only conductors that have already joined a space are cut off. This
+    /// replaces any previous partitioning.
+    pub fn isolate(&self, name: &str) {
+        let mut state = self.0.lock();
+        let others: HashSet<String> = state
+            .names
+            .values()
+            .filter(|other| *other != name)
+            .cloned()
+            .collect();
+        state.partitions = others.into_iter().map(|other| (other, 1)).collect();
+        state.partitions.insert(name.to_string(), 0);
+    }
+
+    /// Remove all partitions, so that all conductors can reach each other again.
+    pub fn heal(&self) {
+        self.0.lock().partitions.clear();
+    }
+
+    /// How many of the messages sent from one conductor to another have had
+    /// faults injected into them so far.
+    ///
+    /// Tests can wait for these to change to know that traffic has been
+    /// affected, rather than sleeping for a while.
+    pub fn fault_counts(&self, from: &str, to: &str) -> LinkFaultCounts {
+        self.0
+            .lock()
+            .counts
+            .get(&(from.to_string(), to.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Remove all faults and partitions.
+    ///
+    /// The counts of faults injected so far are kept.
+    pub fn clear(&self) {
+        let mut state = self.0.lock();
+        state.links.clear();
+        state.default_faults = LinkFaults::default();
+        state.partitions.clear();
+    }
+
+    /// Create the transport wrapper for the conductor with the given name.
+    pub(crate) fn wrap_transport(&self, name: String) -> holochain_p2p::WrapTransportFactory {
+        let controller = self.clone();
+        Arc::new(move |orig| {
+            let factory: DynTransportFactory = Arc::new(FaultTransportFactory {
+                name: name.clone(),
+                controller: controller.clone(),
+                orig,
+            });
+            factory
+        })
+    }
+
+    fn register_url(&self, url: Url, name: &str) {
+        self.0.lock().names.insert(url, name.to_string());
+    }
+
+    fn verdict(&self, from: &str, to: &Url) -> Verdict {
+        let mut state = self.0.lock();
+        let Some(to) = state.names.get(to).cloned() else {
+            return Verdict::Deliver {
+                copies: 1,
+                delay: Duration::ZERO,
+            };
+        };
+        let link = (from.to_string(), to);
+
+        if let (Some(from_partition), Some(to_partition)) =
+            (state.partitions.get(&link.0), state.partitions.get(&link.1))
+        {
+            if from_partition != to_partition {
+                state.counts.entry(link).or_default().partitioned += 1;
+                return Verdict::Partitioned;
+            }
+        }
+
+        let faults = state
+            .links
+            .get(&link)
+            .unwrap_or(&state.default_faults)
+            .clone();
+
+        let mut rng = rand::rng();
+        if faults.drop_probability > 0.0 && rng.random_bool(faults.drop_probability.min(1.0)) {
+            state.counts.entry(link).or_default().dropped += 1;
+            return Verdict::Drop;
+        }
+        let copies = if faults.duplicate_probability > 0.0
+            && rng.random_bool(faults.duplicate_probability.min(1.0))
+        {
+            2
+        } else {
+            1
+        };
+        let jitter = if faults.jitter.is_zero() {
+            Duration::ZERO
+        } else {
+            rng.random_range(Duration::ZERO..=faults.jitter)
+        };
+        let delay = faults.delay + jitter;
+
+        let counts = state.counts.entry(link).or_default();
+        if copies > 1 {
+            counts.duplicated += 1;
+        }
+        if !delay.is_zero() {
+            counts.delayed += 1;
+        }
+
+        Verdict::Deliver { copies, delay }
+    }
+}
+
+/// A transport factory that wraps the transports it creates in a [`FaultTransport`].
+#[derive(Debug)]
+struct FaultTransportFactory {
+    name: String,
+    controller: SweetNetworkController,
+    orig: DynTransportFactory,
+}
+
+impl TransportFactory for FaultTransportFactory {
+    fn default_config(&self, config: &mut Config) -> K2Result<()> {
+        self.orig.default_config(config)
+    }
+
+    fn validate_config(&self, config: &Config) -> K2Result<()> {
+        self.orig.validate_config(config)
+    }
+
+    fn create(
+        &self,
+        builder: Arc<Builder>,
+        handler: DynTxHandler,
+    ) -> BoxFut<'static, K2Result<DynTransport>> {
+        let name = self.name.clone();
+        let controller = self.controller.clone();
+        let orig_fut = self.orig.create(builder, handler);
+        Box::pin(async move {
+            let orig = orig_fut.await?;
+            let out: DynTransport = Arc::new(FaultTransport {
+                name,
+                controller,
+                orig,
+            });
+            Ok(out)
+        })
+    }
+}
+
+/// A transport that injects the faults set on a [`SweetNetworkController`]
+/// into the messages it sends.
+#[derive(Debug)]
+struct FaultTransport {
+    name: String,
+    controller: SweetNetworkController,
+    orig: DynTransport,
+}
+
+impl FaultTransport {
+    /// Send a message, or as many copies of it as the controller decides, after
+    /// the delay that the controller decides.
+    ///
+    /// Delayed messages are sent in the background, so that the sender does not
+    /// wait for them like it wouldn't wait for a message in flight.
+    fn send<F>(&self, peer: Url, send: F) -> BoxFut<'_, K2Result<()>>
+    where
+        F: Fn(DynTransport) -> BoxFut<'static, K2Result<()>> + Send + 'static,
+    {
+        let verdict = self.controller.verdict(&self.name, &peer);
+        let orig = self.orig.clone();
+        Box::pin(async move {
+            match verdict {
+                Verdict::Drop => Ok(()),
+                Verdict::Partitioned => Err(K2Error::other(format!(
+                    "peer {peer} is unreachable in a partitioned network"
+                ))),
+                Verdict::Deliver { copies, delay } if delay.is_zero() => {
+                    for _ in 0..copies {
+                        send(orig.clone()).await?;
+                    }
+                    Ok(())
+                }
+                Verdict::Deliver { copies, delay } => {
+                    tokio::spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        for _ in 0..copies {
+                            if let Err(err) = send(orig.clone()).await {
+                                tracing::debug!(?err, %peer, "Failed to send delayed message");
+                            }
+                        }
+                    });
+                    Ok(())
+                }
+            }
+        })
+    }
+}
+
+impl Transport for FaultTransport {
+    fn register_space_handler(&self, space_id: SpaceId, handler: DynTxSpaceHandler) -> Option<Url> {
+        let url = self.orig.register_space_handler(space_id, handler);
+        if let Some(url) = &url {
+            self.controller.register_url(url.clone(), &self.name);
+        }
+        url
+    }
+
+    fn register_module_handler(
+        &self,
+        space_id: SpaceId,
+        module: String,
+        handler: DynTxModuleHandler,
+    ) {
+        self.orig.register_module_handler(space_id, module, handler)
+    }
+
+    fn disconnect(&self, peer: Url, reason: Option<String>) -> BoxFut<'_, ()> {
+        self.orig.disconnect(peer, reason)
+    }
+
+    fn send_space_notify(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        data: Bytes,
+    ) -> BoxFut<'_, K2Result<()>> {
+        let to = peer.clone();
+        self.send(peer, move |orig| {
+            let to = to.clone();
+            let space_id = space_id.clone();
+            let data = data.clone();
+            Box::pin(async move { orig.send_space_notify(to, space_id, data).await })
+        })
+    }
+
+    fn send_module(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        module: String,
+        data: Bytes,
+    ) -> BoxFut<'_, K2Result<()>> {
+        let to = peer.clone();
+        self.send(peer, move |orig| {
+            let to = to.clone();
+            let space_id = space_id.clone();
+            let module = module.clone();
+            let data = data.clone();
+            Box::pin(async move { orig.send_module(to, space_id, module, data).await })
+        })
+    }
+
+    fn unregister_space(&self, space_id: SpaceId) -> BoxFut<'_, ()> {
+        self.orig.unregister_space(space_id)
+    }
+
+    fn get_connected_peers(&self) -> BoxFut<'_, K2Result<Vec<Url>>> {
+        self.orig.get_connected_peers()
+    }
+
+    fn dump_network_stats(&self) -> BoxFut<'_, K2Result<ApiTransportStats>> {
+        self.orig.dump_network_stats()
+    }
+}
@@ -459,6 +459,7 @@ where
         provenance,
         nonce,
         expires_at,
+        points_consumed: Default::default(),
     })
 }
 
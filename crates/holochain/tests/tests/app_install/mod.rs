@@ -35,6 +35,7 @@ async fn can_install_app_with_custom_modifiers_overridden_correctly() {
                 clone_limit: 0,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
+            network: None,
         },
         AppRoleManifest {
             name: role_name_2.clone(),
@@ -46,6 +47,7 @@ async fn can_install_app_with_custom_modifiers_overridden_correctly() {
                 clone_limit: 0,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
+            network: None,
         },
     ];
 
@@ -77,6 +79,7 @@ async fn can_install_app_with_custom_modifiers_overridden_correctly() {
             membrane_proof: Default::default(),
             modifiers: Some(custom_modifiers),
             init_properties: None,
+            network: None,
         },
     );
 
@@ -93,6 +96,7 @@ async fn can_install_app_with_custom_modifiers_overridden_correctly() {
             roles_settings: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -107,6 +111,7 @@ async fn can_install_app_with_custom_modifiers_overridden_correctly() {
             roles_settings: Some(HashMap::from([role_settings])),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -214,6 +219,7 @@ async fn install_app_with_custom_modifier_fields_none_does_not_override_existing
             clone_limit: 0,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
+        network: None,
     }];
 
     let manifest = AppManifestCurrentBuilder::default()
@@ -237,6 +243,7 @@ async fn install_app_with_custom_modifier_fields_none_does_not_override_existing
             membrane_proof: Default::default(),
             modifiers: Some(custom_modifiers.clone()),
             init_properties: None,
+            network: None,
         },
     );
 
@@ -251,6 +258,7 @@ async fn install_app_with_custom_modifier_fields_none_does_not_override_existing
             roles_settings: Some(HashMap::from([role_settings])),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -292,6 +300,7 @@ async fn installing_with_modifiers_for_non_existing_role_fails() {
             membrane_proof: Default::default(),
             modifiers: Some(DnaModifiersOpt::default()),
             init_properties: None,
+            network: None,
         },
     );
 
@@ -306,6 +315,7 @@ async fn installing_with_modifiers_for_non_existing_role_fails() {
             roles_settings: Some(HashMap::from([role_settings])),
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await;
 
@@ -329,6 +339,7 @@ async fn providing_membrane_proof_overrides_deferred_provisioning() {
             membrane_proof: Some(MembraneProof::new(fixt!(SerializedBytes))),
             modifiers: None,
             init_properties: None,
+            network: None,
         },
     );
 
@@ -344,6 +355,7 @@ async fn providing_membrane_proof_overrides_deferred_provisioning() {
             network_seed: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -385,6 +397,7 @@ async fn restore_requires_agent_key() {
                     clone_limit: 0,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
+                network: None,
             }])
             .build()
             .unwrap()
@@ -406,6 +419,7 @@ async fn restore_requires_agent_key() {
             roles_settings: None,
             ignore_genesis_failure: false,
             restore_from_dht: true,
+            wasm_metering: None,
         })
         .await;
 
@@ -441,6 +455,7 @@ async fn install_with_agent_key_not_in_lair_is_rejected() {
                     clone_limit: 0,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
+                network: None,
             }])
             .build()
             .unwrap()
@@ -467,6 +482,7 @@ async fn install_with_agent_key_not_in_lair_is_rejected() {
             roles_settings: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await;
     assert!(
@@ -488,6 +504,7 @@ async fn install_with_agent_key_not_in_lair_is_rejected() {
             roles_settings: None,
             ignore_genesis_failure: false,
             restore_from_dht: true,
+            wasm_metering: None,
         })
         .await;
     assert!(
@@ -518,6 +535,7 @@ async fn install_with_existing_agent_key_in_lair_is_accepted() {
                     clone_limit: 0,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
+                network: None,
             }])
             .build()
             .unwrap()
@@ -547,6 +565,7 @@ async fn install_with_existing_agent_key_in_lair_is_accepted() {
             roles_settings: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await;
 
@@ -27,6 +27,7 @@ async fn app_allowed_origins() {
             None,
             "http://localhost:3000".to_string().into(),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -64,6 +65,7 @@ async fn app_allowed_origins_independence() {
             None,
             "http://localhost:3001".to_string().into(),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -75,6 +77,7 @@ async fn app_allowed_origins_independence() {
             None,
             "http://localhost:3002".to_string().into(),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -126,7 +129,7 @@ async fn app_interface_requires_auth() {
     // App interface with no restrictions, but should still require auth
     let app_port = conductor
         .clone()
-        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -178,7 +181,7 @@ async fn app_interface_can_handle_bad_auth_payload() {
 
     let app_port = conductor
         .clone()
-        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -234,6 +237,7 @@ async fn app_interfaces_can_be_bound_to_apps() {
             None,
             AllowedOrigins::Any,
             Some("test-app".to_string()),
+            None,
         )
         .await
         .unwrap();
@@ -314,6 +318,7 @@ async fn signals_are_not_sent_until_after_auth() {
             None,
             AllowedOrigins::Any,
             Some("test-app".to_string()),
+            None,
         )
         .await
         .unwrap();
@@ -394,6 +399,7 @@ async fn signals_are_restricted_by_app() {
             None,
             AllowedOrigins::Any,
             Some("test-app-1".to_string()),
+            None,
         )
         .await
         .unwrap();
@@ -406,6 +412,7 @@ async fn signals_are_restricted_by_app() {
             None,
             AllowedOrigins::Any,
             Some("test-app-2".to_string()),
+            None,
         )
         .await
         .unwrap();
@@ -413,7 +420,7 @@ async fn signals_are_restricted_by_app() {
     // App interface without an app restriction
     let app_3_port = conductor
         .clone()
-        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -515,7 +522,7 @@ async fn revoke_app_auth_token() {
 
     let app_port = conductor
         .clone()
-        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -255,6 +255,119 @@ async fn test_update_admin_interface() {
 
     let admin_api = AdminInterfaceApi::new(conductor.clone());
 
+    let source = coordinator_update_bundle();
+
+    println!("Bundle: {source:?}");
+
+    let req = UpdateCoordinatorsPayload {
+        cell_id: cells[0].cell_id().clone(),
+        source: CoordinatorSource::Bundle(Box::new(source)),
+        retry_abandoned_ops: false,
+    };
+    let req = AdminRequest::UpdateCoordinators(Box::new(req));
+    let r = admin_api.handle_request(Ok(req)).await.unwrap();
+    assert!(matches!(r, AdminResponse::CoordinatorsUpdated));
+
+    let record: Option<Record> = conductor
+        .call(
+            &cells[0].zome(TestCoordinatorWasm::CoordinatorZomeUpdate),
+            "get_entry",
+            hash,
+        )
+        .await;
+
+    assert!(record.is_some());
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn abandoned_ops_are_integrated_after_a_retry() {
+    use holochain::conductor::api::error::ConductorApiError;
+    use holochain::retry_until_timeout;
+
+    let mut conductor = SweetConductor::standard().await;
+    let (dna, _, _) = SweetDnaFile::unique_from_zomes(
+        vec![TestIntegrityWasm::IntegrityZome],
+        vec![TestCoordinatorWasm::CoordinatorZome],
+        vec![
+            DnaWasm::from(TestIntegrityWasm::IntegrityZome),
+            DnaWasm::from(TestCoordinatorWasm::CoordinatorZome),
+        ],
+    )
+    .await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let cells = app.into_cells();
+    let dht_store = cells[0].dht_store().clone();
+    let admin_api = AdminInterfaceApi::new(conductor.clone());
+
+    let hash: ActionHash = conductor
+        .call(
+            &cells[0].zome(TestCoordinatorWasm::CoordinatorZome),
+            "create_entry",
+            (),
+        )
+        .await;
+    await_consistency([&cells[0]]).await.unwrap();
+
+    let err = conductor
+        .retry_abandoned_ops(&DnaHash::from_raw_36(vec![0; 36]), Default::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ConductorApiError::DnaMissing(_)));
+
+    // Retried on request, with the count of re-queued ops returned.
+    let abandoned = dht_store
+        .test_abandon_integrated_chain_ops(&hash)
+        .await
+        .unwrap();
+    assert!(!abandoned.is_empty());
+    let req = AdminRequest::RetryAbandonedOps {
+        dna_hash: dna.dna_hash().clone(),
+        filter: Default::default(),
+    };
+    let r = admin_api.handle_request(Ok(req)).await.unwrap();
+    assert!(matches!(r, AdminResponse::AbandonedOpsRetried(n) if n == abandoned.len() as u64));
+    retry_until_timeout!(30_000, 100, {
+        if all_integrated(&dht_store, &abandoned).await {
+            break;
+        }
+    });
+
+    // Retried along with a coordinator update.
+    let abandoned = dht_store
+        .test_abandon_integrated_chain_ops(&hash)
+        .await
+        .unwrap();
+    let req = UpdateCoordinatorsPayload {
+        cell_id: cells[0].cell_id().clone(),
+        source: CoordinatorSource::Bundle(Box::new(coordinator_update_bundle())),
+        retry_abandoned_ops: true,
+    };
+    let req = AdminRequest::UpdateCoordinators(Box::new(req));
+    let r = admin_api.handle_request(Ok(req)).await.unwrap();
+    assert!(matches!(r, AdminResponse::CoordinatorsUpdated));
+    retry_until_timeout!(30_000, 100, {
+        if all_integrated(&dht_store, &abandoned).await {
+            break;
+        }
+    });
+}
+
+#[cfg(feature = "test_utils")]
+async fn all_integrated(
+    dht_store: &holochain_state::dht_store::DhtStore,
+    op_hashes: &[DhtOpHash],
+) -> bool {
+    for op_hash in op_hashes {
+        if dht_store.when_integrated(op_hash).await.unwrap().is_none() {
+            return false;
+        }
+    }
+    true
+}
+
+/// A bundle of the [`TestCoordinatorWasm::CoordinatorZomeUpdate`] coordinator zome.
+fn coordinator_update_bundle() -> CoordinatorBundle {
     let path: PathBuf = TestCoordinatorWasm::CoordinatorZomeUpdate.into();
     let manifest = CoordinatorManifest {
         zomes: vec![ZomeManifest {
@@ -272,7 +385,7 @@ async fn test_update_admin_interface() {
         .to_vec()
         .into();
 
-    let source: CoordinatorBundle = Bundle::new(
+    Bundle::new(
         manifest,
         [(
             path.file_name().unwrap().to_str().unwrap().to_string(),
@@ -280,27 +393,7 @@ async fn test_update_admin_interface() {
         )],
     )
     .unwrap()
-    .into();
-
-    println!("Bundle: {source:?}");
-
-    let req = UpdateCoordinatorsPayload {
-        cell_id: cells[0].cell_id().clone(),
-        source: CoordinatorSource::Bundle(Box::new(source)),
-    };
-    let req = AdminRequest::UpdateCoordinators(Box::new(req));
-    let r = admin_api.handle_request(Ok(req)).await.unwrap();
-    assert!(matches!(r, AdminResponse::CoordinatorsUpdated));
-
-    let record: Option<Record> = conductor
-        .call(
-            &cells[0].zome(TestCoordinatorWasm::CoordinatorZomeUpdate),
-            "get_entry",
-            hash,
-        )
-        .await;
-
-    assert!(record.is_some());
+    .into()
 }
 
 #[tokio::test(flavor = "multi_thread")]
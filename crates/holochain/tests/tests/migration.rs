@@ -90,6 +90,7 @@ fn pack_bundle(dna: &DnaFile) -> bytes::Bytes {
                     clone_limit: 0,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
+                network: None,
             }])
             .build()
             .unwrap()
@@ -120,6 +121,7 @@ async fn install_and_enable(
             roles_settings,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -204,6 +206,7 @@ async fn chain_switch_migration() {
             membrane_proof: None,
             modifiers: None,
             init_properties: Some(init_props),
+            network: None,
         },
     )]);
 
@@ -310,6 +313,7 @@ async fn chain_switch_migration_rejects_untrusted_signer() {
             membrane_proof: None,
             modifiers: None,
             init_properties: Some(init_props),
+            network: None,
         },
     )]);
 
@@ -20,6 +20,7 @@ mod lair_in_proc_restart;
 mod metrics;
 mod migration;
 mod multi_conductor;
+mod network_faults;
 mod new_lair;
 mod paths;
 mod publish;
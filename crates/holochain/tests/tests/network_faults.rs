@@ -0,0 +1,212 @@
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::{Duration, Instant};
+
+/// Two conductors sharing a network controller, holding the full DHT of an app
+/// with the simple create and read zome.
+#[cfg(feature = "test_utils")]
+async fn setup(network: &SweetNetworkController) -> (SweetConductorBatch, SweetCell, SweetCell) {
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+
+    let config = SweetConductorConfig::rendezvous(false).with_network_controller(network);
+
+    let mut conductors = SweetConductorBatch::from_config_rendezvous(2, config).await;
+    let dna_file = SweetDnaFile::unique_from_inline_zomes(("simple", simple_create_read_zome()))
+        .await
+        .0;
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    conductors[0]
+        .declare_full_storage_arcs(alice.dna_hash())
+        .await;
+    conductors[1]
+        .declare_full_storage_arcs(bobbo.dna_hash())
+        .await;
+    conductors.exchange_peer_info().await;
+
+    (conductors, alice, bobbo)
+}
+
+/// Wait until faults have been injected into more messages from one conductor
+/// to another than the given count.
+#[cfg(feature = "test_utils")]
+async fn await_faults(
+    network: &SweetNetworkController,
+    from: &str,
+    to: &str,
+    count: impl Fn(LinkFaultCounts) -> u64,
+    more_than: u64,
+) {
+    use holochain::retry_until_timeout;
+
+    retry_until_timeout!(30_000, 100, {
+        if count(network.fault_counts(from, to)) > more_than {
+            break;
+        }
+    });
+}
+
+/// Test that conductors in different partitions of the network can't get each
+/// other's data, and that the data is synced once the partition is healed.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn partitioned_conductors_sync_after_heal() {
+    use holochain::retry_until_timeout;
+
+    holochain_trace::test_run();
+
+    let network = SweetNetworkController::new();
+    let (conductors, alice, bobbo) = setup(&network).await;
+    let (alice_id, bobbo_id) = (conductors[0].id(), conductors[1].id());
+
+    network.partition(&[&[&alice_id], &[&bobbo_id]]);
+    let blocked = network.fault_counts(&alice_id, &bobbo_id).partitioned;
+
+    let hash: ActionHash = conductors[0]
+        .call(&alice.zome("simple"), "create", ())
+        .await;
+
+    // Wait for alice to have tried to reach bobbo since creating the record,
+    // which must have failed.
+    await_faults(&network, &alice_id, &bobbo_id, |c| c.partitioned, blocked).await;
+    let maybe_record: Option<Record> = conductors[1]
+        .call(&bobbo.zome("simple"), "read", hash.clone())
+        .await;
+    assert!(maybe_record.is_none());
+
+    network.heal();
+
+    retry_until_timeout!(30_000, 1_000, {
+        let maybe_record: Option<Record> = conductors[1]
+            .call(&bobbo.zome("simple"), "read", hash.clone())
+            .await;
+        if maybe_record.is_some() {
+            break;
+        }
+    });
+}
+
+/// Test that data doesn't reach a conductor while all messages to it are
+/// dropped, and that it is synced once the messages get through again.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn dropped_messages_sync_after_clear() {
+    use holochain::retry_until_timeout;
+
+    holochain_trace::test_run();
+
+    let network = SweetNetworkController::new();
+    let (conductors, alice, bobbo) = setup(&network).await;
+    let (alice_id, bobbo_id) = (conductors[0].id(), conductors[1].id());
+
+    network.set_link_faults(&alice_id, &bobbo_id, LinkFaults::drop_all());
+    let dropped = network.fault_counts(&alice_id, &bobbo_id).dropped;
+
+    let hash: ActionHash = conductors[0]
+        .call(&alice.zome("simple"), "create", ())
+        .await;
+
+    // Wait for a message from alice to bobbo to be dropped since creating the
+    // record, so that she has tried to send it.
+    await_faults(&network, &alice_id, &bobbo_id, |c| c.dropped, dropped).await;
+    let maybe_record: Option<Record> = conductors[1]
+        .call(&bobbo.zome("simple"), "read", hash.clone())
+        .await;
+    assert!(maybe_record.is_none());
+
+    network.clear();
+
+    retry_until_timeout!(30_000, 1_000, {
+        let maybe_record: Option<Record> = conductors[1]
+            .call(&bobbo.zome("simple"), "read", hash.clone())
+            .await;
+        if maybe_record.is_some() {
+            break;
+        }
+    });
+}
+
+/// Test that delayed messages are still delivered, but not before the delay.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn delayed_messages_are_delivered_after_the_delay() {
+    use holochain::retry_until_timeout;
+
+    holochain_trace::test_run();
+
+    let network = SweetNetworkController::new();
+    let (conductors, alice, bobbo) = setup(&network).await;
+    let (alice_id, bobbo_id) = (conductors[0].id(), conductors[1].id());
+
+    let delay = Duration::from_secs(2);
+    network.set_link_faults(&alice_id, &bobbo_id, LinkFaults::delayed(delay));
+
+    let created_at = Instant::now();
+    let hash: ActionHash = conductors[0]
+        .call(&alice.zome("simple"), "create", ())
+        .await;
+
+    retry_until_timeout!(30_000, 100, {
+        let maybe_record: Option<Record> = conductors[1]
+            .call(&bobbo.zome("simple"), "read", hash.clone())
+            .await;
+        if maybe_record.is_some() {
+            break;
+        }
+    });
+
+    // Every message from alice to bobbo is held back, so the record can't
+    // have reached bobbo any sooner.
+    assert!(created_at.elapsed() >= delay);
+    assert!(network.fault_counts(&alice_id, &bobbo_id).delayed > 0);
+}
+
+/// Test that data is synced correctly when every message is delivered twice.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn duplicated_messages_sync_once() {
+    use holochain::retry_until_timeout;
+
+    holochain_trace::test_run();
+
+    let network = SweetNetworkController::new();
+    let (conductors, alice, bobbo) = setup(&network).await;
+    let (alice_id, bobbo_id) = (conductors[0].id(), conductors[1].id());
+
+    network.set_link_faults(&alice_id, &bobbo_id, LinkFaults::duplicating(1.0));
+    let duplicated = network.fault_counts(&alice_id, &bobbo_id).duplicated;
+
+    let hash: ActionHash = conductors[0]
+        .call(&alice.zome("simple"), "create", ())
+        .await;
+    let record: Option<Record> = conductors[0]
+        .call(&alice.zome("simple"), "read", hash.clone())
+        .await;
+    let record = record.unwrap();
+
+    retry_until_timeout!(30_000, 1_000, {
+        let maybe_record: Option<Record> = conductors[1]
+            .call(&bobbo.zome("simple"), "read", hash.clone())
+            .await;
+        if maybe_record.is_some() {
+            assert_eq!(maybe_record, Some(record.clone()));
+            break;
+        }
+    });
+    assert!(network.fault_counts(&alice_id, &bobbo_id).duplicated > duplicated);
+
+    // Receiving the same ops twice doesn't add a second copy of the record's
+    // action to bobbo's view of the DHT.
+    let details: Option<Details> = conductors[1]
+        .call(&bobbo.zome("simple"), "read_details", hash)
+        .await;
+    match details.unwrap() {
+        Details::Record(details) => {
+            assert_eq!(details.record, record);
+            assert!(details.deletes.is_empty());
+            assert!(details.updates.is_empty());
+        }
+        Details::Entry(_) => panic!("expected record details"),
+    }
+}
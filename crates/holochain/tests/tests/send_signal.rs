@@ -37,7 +37,13 @@ async fn send_signal_after_conductor_restart() {
     // add app interface
     let app_interface_port_1 = (*conductor)
         .clone()
-        .add_app_interface(either::Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(
+            either::Either::Left(0),
+            None,
+            AllowedOrigins::Any,
+            None,
+            None,
+        )
         .await
         .unwrap();
 
@@ -141,7 +147,13 @@ async fn send_signal_after_conductor_restart() {
     // add a second app interface without websocket connection
     let _ = (*conductor)
         .clone()
-        .add_app_interface(either::Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(
+            either::Either::Left(0),
+            None,
+            AllowedOrigins::Any,
+            None,
+            None,
+        )
         .await
         .unwrap();
 
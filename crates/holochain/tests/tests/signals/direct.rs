@@ -8,10 +8,11 @@
 use std::time::Duration;
 
 use holochain::sweettest::{
-    authenticate_app_ws_client, websocket_client_by_port, SweetCell, SweetConductor,
+    authenticate_app_ws_client, websocket_client_by_port, SweetAgents, SweetCell, SweetConductor,
     SweetConductorBatch, SweetConductorConfig, SweetDnaFile, WsPollRecv,
 };
 use holochain_conductor_api::{AppRequest, AppResponse, ExternalApiWireError};
+use holochain_timestamp::InclusiveTimestampInterval;
 use holochain_types::prelude::*;
 use holochain_types::signal::DIRECT_SIGNAL_MAX_SIZE;
 use holochain_types::websocket::AllowedOrigins;
@@ -29,7 +30,13 @@ async fn connect_app_ws(
 ) -> (WebsocketSender, WebsocketReceiver) {
     let app_port = conductor
         .raw_handle()
-        .add_app_interface(either::Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(
+            either::Either::Left(0),
+            None,
+            AllowedOrigins::Any,
+            None,
+            None,
+        )
         .await
         .unwrap();
 
@@ -54,11 +61,36 @@ async fn send_direct_signal(
         dna_hash,
         agents,
         signal,
+        durable_ttl_s: None,
+        acknowledged: false,
     })
     .await
     .unwrap()
 }
 
+/// Send a direct signal request in acknowledged mode and return the acknowledgements.
+async fn send_acknowledged_direct_signal(
+    tx: &WebsocketSender,
+    dna_hash: DnaHash,
+    agents: Vec<AgentPubKey>,
+    signal: Vec<u8>,
+) -> Vec<DirectSignalAck> {
+    let response: AppResponse = tx
+        .request(AppRequest::SendDirectSignal {
+            dna_hash,
+            agents,
+            signal,
+            durable_ttl_s: None,
+            acknowledged: true,
+        })
+        .await
+        .unwrap();
+    match response {
+        AppResponse::DirectSignalAcknowledged(acks) => acks,
+        other => panic!("expected acknowledgements, got {other:?}"),
+    }
+}
+
 /// Wait for the next direct (`Signal::AppDirect`) signal on this socket, returning the target cell
 /// and payload. Other signal kinds are ignored. Returns `None` if `timeout` elapses first.
 async fn try_recv_direct_signal(
@@ -309,6 +341,90 @@ async fn direct_signal_to_unknown_agent_is_dropped() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn acknowledged_direct_signal_to_another_conductor_is_delivered() {
+    holochain_trace::test_run();
+
+    let mut conductors =
+        SweetConductorBatch::from_config_rendezvous(2, SweetConductorConfig::rendezvous(true))
+            .await;
+    let dna = SweetDnaFile::unique_empty().await;
+    let app_batch = conductors
+        .setup_app("app", std::slice::from_ref(&dna))
+        .await
+        .unwrap();
+    let ((alice,), (bob,)): ((SweetCell,), (SweetCell,)) = app_batch.into_tuples();
+
+    let dna_hash = dna.dna_hash().clone();
+
+    conductors[0]
+        .require_initial_gossip_activity_for_cell(&alice, 1, Duration::from_secs(90))
+        .await
+        .unwrap();
+
+    let (alice_tx, alice_rx) = connect_app_ws(&conductors[0], "app").await;
+    let _alice_rx = WsPollRecv::new::<AppResponse>(alice_rx);
+
+    // Bob must be subscribed to the app's signals for the signal to count as delivered.
+    let (_bob_tx, mut bob_rx) = connect_app_ws(&conductors[1], "app").await;
+
+    let payload = b"did you get this?".to_vec();
+    let acks = send_acknowledged_direct_signal(
+        &alice_tx,
+        dna_hash,
+        vec![bob.agent_pubkey().clone()],
+        payload.clone(),
+    )
+    .await;
+    assert_eq!(
+        acks,
+        vec![DirectSignalAck {
+            agent: bob.agent_pubkey().clone(),
+            outcome: DirectSignalOutcome::Delivered,
+        }]
+    );
+
+    let (cell_id, signal) = try_recv_direct_signal(&mut bob_rx, Duration::from_secs(10))
+        .await
+        .expect("Bob did not receive the direct signal");
+    assert_eq!(cell_id, *bob.cell_id());
+    assert_eq!(signal, payload);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn acknowledged_direct_signal_to_unknown_agent_is_unreachable() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::standard().await;
+    let dna = SweetDnaFile::unique_empty().await;
+    let _app = conductor
+        .setup_app("app", std::slice::from_ref(&dna))
+        .await
+        .unwrap();
+    let dna_hash = dna.dna_hash().clone();
+
+    let (alice_tx, alice_rx) = connect_app_ws(&conductor, "app").await;
+    let _alice_rx = WsPollRecv::new::<AppResponse>(alice_rx);
+
+    let unknown_agent = AgentPubKey::from_raw_36(vec![0; 36]);
+
+    // Unlike a fire-and-forget send, the sender is told that the agent could not be reached.
+    let acks = send_acknowledged_direct_signal(
+        &alice_tx,
+        dna_hash,
+        vec![unknown_agent.clone()],
+        b"nobody home".to_vec(),
+    )
+    .await;
+    assert_eq!(
+        acks,
+        vec![DirectSignalAck {
+            agent: unknown_agent,
+            outcome: DirectSignalOutcome::Unreachable,
+        }]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn direct_signal_with_no_agents_is_rejected() {
     holochain_trace::test_run();
@@ -371,3 +487,231 @@ async fn direct_signal_to_dna_not_in_app_is_rejected() {
     let response = send_direct_signal(&tx, other_dna_hash, vec![agent], b"payload".to_vec()).await;
     assert_error_contains(&response, "was not found in app");
 }
+
+/// Sign a direct signal from `from_agent` along with the given idempotency key, as the
+/// conductor of `from_agent` would.
+///
+/// Returns the encoded signal and its signature.
+async fn sign_direct_signal(
+    conductor: &SweetConductor,
+    from_agent: &AgentPubKey,
+    payload: Vec<u8>,
+    idempotency_key: Option<u64>,
+) -> (Vec<u8>, Signature) {
+    let signal = holochain_serialized_bytes::encode(&DirectSignal(payload.clone())).unwrap();
+    let signed = match idempotency_key {
+        Some(idempotency_key) => holochain_serialized_bytes::encode(&AckedDirectSignal {
+            signal: payload,
+            idempotency_key,
+        })
+        .unwrap(),
+        None => signal.clone(),
+    };
+    let signature = conductor
+        .keystore()
+        .sign(from_agent.clone(), signed.into())
+        .await
+        .unwrap();
+    (signal, signature)
+}
+
+/// Hand a signed direct signal to the conductor as if it had been received from the network,
+/// with the given idempotency key.
+async fn receive_signed_direct_signal(
+    conductor: &SweetConductor,
+    dna_hash: &DnaHash,
+    to_agent: &AgentPubKey,
+    from_agent: &AgentPubKey,
+    (signal, signature): (Vec<u8>, Signature),
+    idempotency_key: Option<u64>,
+) -> holochain_p2p::HolochainP2pResult<DirectSignalOutcome> {
+    use holochain_p2p::event::HcP2pHandler;
+
+    conductor
+        .raw_handle()
+        .handle_remote_signal_direct(
+            dna_hash.clone(),
+            to_agent.clone(),
+            signal,
+            from_agent.clone(),
+            signature,
+            idempotency_key,
+        )
+        .await
+}
+
+/// Sign a direct signal from `from_agent` and hand it to the conductor as if it had been
+/// received from the network, with the given idempotency key.
+async fn receive_direct_signal(
+    conductor: &SweetConductor,
+    dna_hash: &DnaHash,
+    to_agent: &AgentPubKey,
+    from_agent: &AgentPubKey,
+    payload: Vec<u8>,
+    idempotency_key: Option<u64>,
+) -> DirectSignalOutcome {
+    let signed = sign_direct_signal(conductor, from_agent, payload, idempotency_key).await;
+    receive_signed_direct_signal(
+        conductor,
+        dna_hash,
+        to_agent,
+        from_agent,
+        signed,
+        idempotency_key,
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn retried_acknowledged_direct_signal_is_delivered_once() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::standard().await;
+    let dna = SweetDnaFile::unique_empty().await;
+    let app = conductor
+        .setup_app("app", std::slice::from_ref(&dna))
+        .await
+        .unwrap();
+    let bob = app.agent().clone();
+    let dna_hash = dna.dna_hash().clone();
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (_bob_tx, mut bob_rx) = connect_app_ws(&conductor, "app").await;
+
+    // The second attempt has the key of the first, as if the first
+    // acknowledgement had been lost, and gets the same outcome.
+    for _ in 0..2 {
+        let outcome = receive_direct_signal(
+            &conductor,
+            &dna_hash,
+            &bob,
+            &alice,
+            b"once".to_vec(),
+            Some(1),
+        )
+        .await;
+        assert_eq!(outcome, DirectSignalOutcome::Delivered);
+    }
+
+    let (_, signal) = try_recv_direct_signal(&mut bob_rx, Duration::from_secs(10))
+        .await
+        .expect("Bob did not receive the direct signal");
+    assert_eq!(signal, b"once".to_vec());
+    assert!(
+        try_recv_direct_signal(&mut bob_rx, Duration::from_secs(2))
+            .await
+            .is_none(),
+        "a retried direct signal was delivered twice"
+    );
+
+    // A signal with a new key is a new signal.
+    let outcome = receive_direct_signal(
+        &conductor,
+        &dna_hash,
+        &bob,
+        &alice,
+        b"twice".to_vec(),
+        Some(2),
+    )
+    .await;
+    assert_eq!(outcome, DirectSignalOutcome::Delivered);
+    let (_, signal) = try_recv_direct_signal(&mut bob_rx, Duration::from_secs(10))
+        .await
+        .expect("Bob did not receive the second direct signal");
+    assert_eq!(signal, b"twice".to_vec());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn acknowledged_direct_signal_replayed_with_new_key_is_rejected() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::standard().await;
+    let dna = SweetDnaFile::unique_empty().await;
+    let app = conductor
+        .setup_app("app", std::slice::from_ref(&dna))
+        .await
+        .unwrap();
+    let bob = app.agent().clone();
+    let dna_hash = dna.dna_hash().clone();
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (_bob_tx, mut bob_rx) = connect_app_ws(&conductor, "app").await;
+
+    let signed = sign_direct_signal(&conductor, &alice, b"once".to_vec(), Some(1)).await;
+    let outcome =
+        receive_signed_direct_signal(&conductor, &dna_hash, &bob, &alice, signed.clone(), Some(1))
+            .await
+            .unwrap();
+    assert_eq!(outcome, DirectSignalOutcome::Delivered);
+    let (_, signal) = try_recv_direct_signal(&mut bob_rx, Duration::from_secs(10))
+        .await
+        .expect("Bob did not receive the direct signal");
+    assert_eq!(signal, b"once".to_vec());
+
+    // The key is covered by the signature, so the same signal with another key is refused.
+    for idempotency_key in [Some(2), None] {
+        assert!(receive_signed_direct_signal(
+            &conductor,
+            &dna_hash,
+            &bob,
+            &alice,
+            signed.clone(),
+            idempotency_key,
+        )
+        .await
+        .is_err());
+    }
+    assert!(
+        try_recv_direct_signal(&mut bob_rx, Duration::from_secs(2))
+            .await
+            .is_none(),
+        "a replayed direct signal was delivered again"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn acknowledged_direct_signal_from_blocked_agent_is_reported_as_blocked() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::standard().await;
+    let dna = SweetDnaFile::unique_empty().await;
+    let app = conductor
+        .setup_app("app", std::slice::from_ref(&dna))
+        .await
+        .unwrap();
+    let bob = app.agent().clone();
+    let dna_hash = dna.dna_hash().clone();
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    conductor
+        .holochain_p2p()
+        .block(Block::new(
+            BlockTarget::Cell(
+                CellId::new(dna_hash.clone(), alice.clone()),
+                CellBlockReason::BadCrypto,
+            ),
+            InclusiveTimestampInterval::try_new(Timestamp::now(), Timestamp::max()).unwrap(),
+        ))
+        .await
+        .unwrap();
+
+    let (_bob_tx, mut bob_rx) = connect_app_ws(&conductor, "app").await;
+
+    let outcome = receive_direct_signal(
+        &conductor,
+        &dna_hash,
+        &bob,
+        &alice,
+        b"let me in".to_vec(),
+        Some(1),
+    )
+    .await;
+    assert_eq!(outcome, DirectSignalOutcome::Blocked);
+    assert!(
+        try_recv_direct_signal(&mut bob_rx, Duration::from_secs(2))
+            .await
+            .is_none(),
+        "a direct signal from a blocked agent was delivered"
+    );
+}
@@ -219,3 +219,172 @@ async fn remote_signals_batch() {
     .await
     .unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn durable_remote_signal_to_unreachable_agent_expires() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::standard().await;
+
+    let dna_file = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::EmitSignal])
+        .await
+        .0;
+
+    let (alice,) = conductor
+        .setup_app("app", &[dna_file])
+        .await
+        .unwrap()
+        .into_tuple();
+    let unreachable = AgentPubKey::from_raw_32(vec![0xdb; 32]);
+
+    let mut signal_rx = conductor.subscribe_to_app_signals("app".to_string());
+
+    let _: () = conductor
+        .call(
+            &alice.zome(TestWasm::EmitSignal),
+            "signal_others_durable",
+            DurableRemoteSignal {
+                remote_signal: RemoteSignal {
+                    agents: vec![unreachable.clone()],
+                    signal: ExternIO::encode(SignalMessage {
+                        value: "hello".to_string(),
+                    })
+                    .unwrap(),
+                },
+                ttl: Duration::from_secs(3),
+            },
+        )
+        .await;
+
+    let queued_id = match tokio::time::timeout(Duration::from_secs(10), signal_rx.recv())
+        .await
+        .unwrap()
+        .unwrap()
+    {
+        Signal::System(SystemSignal::RemoteSignalQueued {
+            cell_id,
+            to_agent,
+            outbox_id,
+        }) => {
+            assert_eq!(alice.cell_id(), &cell_id);
+            assert_eq!(unreachable, to_agent);
+            outbox_id
+        }
+        other => panic!("Expected the signal to be queued, got {other:?}"),
+    };
+
+    let outbox = conductor
+        .list_signal_outbox(Some(alice.dna_hash().clone()))
+        .await
+        .unwrap();
+    assert_eq!(1, outbox.len());
+    assert_eq!(queued_id, outbox[0].id);
+
+    match tokio::time::timeout(Duration::from_secs(10), signal_rx.recv())
+        .await
+        .unwrap()
+        .unwrap()
+    {
+        Signal::System(SystemSignal::RemoteSignalExpired { outbox_id, .. }) => {
+            assert_eq!(queued_id, outbox_id);
+        }
+        other => panic!("Expected the signal to expire, got {other:?}"),
+    }
+
+    assert!(conductor.list_signal_outbox(None).await.unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "slow_tests")]
+async fn durable_remote_signal_is_delivered_when_agent_comes_online() {
+    holochain_trace::test_run();
+
+    let mut conductors =
+        SweetConductorBatch::from_config_rendezvous(2, SweetConductorConfig::rendezvous(true))
+            .await;
+
+    let dna_file = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::EmitSignal])
+        .await
+        .0;
+
+    let ((alice,), (bob,)) = conductors
+        .setup_app("app", &[dna_file])
+        .await
+        .unwrap()
+        .into_tuples();
+    conductors.exchange_peer_info().await;
+
+    // Take Bob offline, so the signal can't be sent to him.
+    conductors[1].shutdown().await;
+
+    let mut alice_signal_rx = conductors[0].subscribe_to_app_signals("app".to_string());
+
+    let _: () = conductors[0]
+        .call(
+            &alice.zome(TestWasm::EmitSignal),
+            "signal_others_durable",
+            DurableRemoteSignal {
+                remote_signal: RemoteSignal {
+                    agents: vec![bob.agent_pubkey().clone()],
+                    signal: ExternIO::encode(SignalMessage {
+                        value: "hello".to_string(),
+                    })
+                    .unwrap(),
+                },
+                ttl: Duration::from_secs(5 * 60),
+            },
+        )
+        .await;
+
+    let queued_id = tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            if let Signal::System(SystemSignal::RemoteSignalQueued {
+                to_agent,
+                outbox_id,
+                ..
+            }) = alice_signal_rx.recv().await.unwrap()
+            {
+                assert_eq!(bob.agent_pubkey(), &to_agent);
+                break outbox_id;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    // Bring Bob back online, the signal is sent to him from the outbox.
+    conductors[1].startup().await;
+    let mut bob_signal_rx = conductors[1].subscribe_to_app_signals("app".to_string());
+    conductors.exchange_peer_info().await;
+
+    tokio::time::timeout(Duration::from_secs(120), async {
+        loop {
+            if let Signal::System(SystemSignal::RemoteSignalDelivered {
+                cell_id,
+                to_agent,
+                outbox_id,
+            }) = alice_signal_rx.recv().await.unwrap()
+            {
+                assert_eq!(alice.cell_id(), &cell_id);
+                assert_eq!(bob.agent_pubkey(), &to_agent);
+                assert_eq!(queued_id, outbox_id);
+                break;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    let received = tokio::time::timeout(Duration::from_secs(10), bob_signal_rx.recv())
+        .await
+        .unwrap()
+        .map(to_signal_message)
+        .unwrap();
+    assert_eq!("hello", received.value);
+
+    assert!(conductors[0]
+        .list_signal_outbox(None)
+        .await
+        .unwrap()
+        .is_empty());
+}
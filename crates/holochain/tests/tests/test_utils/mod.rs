@@ -198,6 +198,7 @@ pub async fn attach_app_interface(client: &WebsocketSender, port: Option<u16>) -
         danger_bind_addr: None,
         allowed_origins: AllowedOrigins::Any,
         installed_app_id: None,
+        zome_call_limits: None,
     };
     let response = client.request(request);
     let response = check_timeout(response, 3000).await.unwrap();
@@ -297,6 +298,7 @@ pub async fn register_and_install_dna_named(
             clone_limit: 0,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
+        network: None,
     }];
 
     let manifest = AppManifestCurrentBuilder::default()
@@ -321,6 +323,7 @@ pub async fn register_and_install_dna_named(
         roles_settings: Default::default(),
         ignore_genesis_failure: false,
         restore_from_dht: false,
+        wasm_metering: None,
     };
     let request = AdminRequest::InstallApp(Box::new(payload));
     let response = client.request(request);
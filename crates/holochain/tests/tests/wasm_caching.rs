@@ -117,6 +117,7 @@ async fn wasm_is_not_memory_cached_after_deferred_memproofs() {
             network_seed: None,
             ignore_genesis_failure: false,
             restore_from_dht: false,
+            wasm_metering: None,
         })
         .await
         .unwrap();
@@ -789,6 +789,53 @@ async fn network_stats() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn peer_latency() {
+    holochain_trace::test_run();
+
+    let mut batch =
+        SweetConductorBatch::from_config_rendezvous(2, SweetConductorConfig::rendezvous(true))
+            .await;
+
+    let dna_file = SweetDnaFile::unique_empty().await;
+
+    let _ = batch.setup_app("app", &[dna_file]).await.unwrap();
+
+    let (client, _rx) = batch
+        .get(0)
+        .unwrap()
+        .admin_ws_client::<AdminResponse>()
+        .await;
+
+    // The other conductor is pinged once it has connected to gossip.
+    let latency = tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let res: AdminResponse = client.request(AdminRequest::DumpPeerLatency).await.unwrap();
+            match res {
+                AdminResponse::PeerLatencyDumped(latency)
+                    if latency.iter().any(|peer| peer.average_us.is_some()) =>
+                {
+                    break latency;
+                }
+                AdminResponse::PeerLatencyDumped(_) => {
+                    tokio::time::sleep(Duration::from_millis(500)).await
+                }
+                _ => panic!("unexpected"),
+            }
+        }
+    })
+    .await
+    .expect("no peer latency was measured");
+
+    let peer = latency
+        .iter()
+        .find(|peer| peer.average_us.is_some())
+        .unwrap();
+    assert!(!peer.samples_us.is_empty());
+    assert!(!peer.failed);
+    assert!(peer.expires_in_ms.unwrap() > 0);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn full_state_dump_returns_all_ops() {
     holochain_trace::test_run();
@@ -938,7 +985,7 @@ async fn holochain_websockets_listen_on_ipv4_and_ipv6() {
 
     let app_port = conductor
         .clone()
-        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None, None)
         .await
         .unwrap();
 
@@ -994,7 +1041,7 @@ async fn emit_signal_after_app_connection_closed() {
     // Connect to the app interface
     let port = conductor
         .clone()
-        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None)
+        .add_app_interface(Either::Left(0), None, AllowedOrigins::Any, None, None)
         .await
         .expect("Couldn't create app interface");
     let (tx, mut rx) = websocket_client_by_port(port).await.unwrap();
@@ -1153,6 +1200,7 @@ async fn bind_ipv6_unspecified() {
             Some("::".to_string()),
             AllowedOrigins::Any,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -1232,6 +1280,7 @@ async fn bind_invalid_addrs() {
             Some("some-hostname".to_string()),
             AllowedOrigins::Any,
             None,
+            None,
         )
         .await
         .unwrap_err();
@@ -1259,6 +1308,7 @@ async fn bind_invalid_addrs() {
             Some("2a0a:ef40:406:4f01:7819:603e:151c:b6d2".to_string()),
             AllowedOrigins::Any,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -0,0 +1,151 @@
+//! Tests for aggregated (non-race) reads whose authorities disagree on the
+//! validation status of an action.
+
+use super::*;
+use holochain_keystore::{spawn_test_keystore, AgentPubKeyExt};
+use holochain_p2p::MockHolochainP2pDnaT;
+
+async fn empty_store() -> holochain_state::dht_store::DhtStore {
+    let dna_hash = holo_hash::DnaHash::from_raw_36(vec![42u8; 36]);
+    holochain_state::test_utils::test_dht_store(dna_hash).await
+}
+
+fn aggregated_options() -> GetLinksRequestOptions {
+    GetLinksRequestOptions {
+        network_req_options: NetworkRequestOptions {
+            as_race: false,
+            ..Default::default()
+        },
+        get_options: GetOptions::network(),
+    }
+}
+
+fn link_key(base: AnyLinkableHash) -> WireLinkKey {
+    WireLinkKey {
+        base,
+        type_query: LinkTypeFilter::Dependencies(vec![ZomeIndex(0)]),
+        tag: None,
+        after: None,
+        before: None,
+        author: None,
+    }
+}
+
+/// A cascade whose network serves a link that one authority judged valid and
+/// another rejected, along with the link's base and hash.
+async fn disagreeing_links_cascade() -> (CascadeImpl, AnyLinkableHash, ActionHash) {
+    let keystore = spawn_test_keystore().await.unwrap();
+    let author = keystore.new_sign_keypair_random().await.unwrap();
+    let base = AnyLinkableHash::from(EntryHash::from_raw_36(vec![1; 36]));
+
+    let action = Action {
+        header: ActionHeader {
+            author: author.clone(),
+            timestamp: Timestamp::now(),
+            action_seq: 4,
+            prev_action: Some(ActionHash::from_raw_36(vec![2; 36])),
+        },
+        data: ActionData::CreateLink(CreateLinkData {
+            base_address: base.clone(),
+            target_address: EntryHash::from_raw_36(vec![3; 36]).into(),
+            zome_index: ZomeIndex(0),
+            link_type: LinkType(0),
+            tag: LinkTag::new("tag"),
+        }),
+    };
+    let signature = author.sign(&keystore, &action).await.unwrap();
+    let create_link_hash = ActionHash::with_data_sync(&action);
+    let create_link = SignedAction::new(action, signature);
+
+    let responses = vec![
+        WireLinkOps {
+            creates: vec![Judged::valid(create_link.clone())],
+            ..Default::default()
+        },
+        // The rejection has no warrant to prove it, so this response is not
+        // cached, but it still disagrees with the first authority.
+        WireLinkOps {
+            creates: vec![Judged::new(create_link, ValidationStatus::Rejected)],
+            ..Default::default()
+        },
+    ];
+
+    let mut network = MockHolochainP2pDnaT::new();
+    network.expect_authority_for_hash().returning(|_| Ok(false));
+    network
+        .expect_get_links()
+        .times(1)
+        .returning(move |_, _, _| Ok(responses.clone()));
+
+    let cascade = CascadeImpl::empty(empty_store().await).with_network(Arc::new(network));
+
+    (cascade, base, create_link_hash)
+}
+
+/// A link that one authority serves as valid and another as rejected is still
+/// returned, and the disagreement is reported rather than failing the read.
+#[tokio::test]
+async fn get_links_returns_merged_links_when_authorities_disagree() {
+    let (cascade, base, create_link_hash) = disagreeing_links_cascade().await;
+
+    let links = cascade
+        .dht_get_links(link_key(base), aggregated_options())
+        .await
+        .unwrap();
+
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].create_link_hash, create_link_hash);
+    assert_eq!(
+        cascade.authority_disagreements(),
+        HashSet::from([create_link_hash])
+    );
+}
+
+/// Authorities that agree leave nothing to report, and nothing to fail on.
+#[tokio::test]
+async fn get_links_reports_nothing_when_authorities_agree() {
+    let mut network = MockHolochainP2pDnaT::new();
+    network.expect_authority_for_hash().returning(|_| Ok(false));
+    network
+        .expect_get_links()
+        .times(1)
+        .returning(|_, _, _| Ok(vec![WireLinkOps::new(), WireLinkOps::new()]));
+
+    let cascade = CascadeImpl::empty(empty_store().await).with_network(Arc::new(network));
+
+    let mut options = aggregated_options();
+    options.get_options = options.get_options.with_fail_on_disagreement(true);
+    let links = cascade
+        .dht_get_links(
+            link_key(EntryHash::from_raw_36(vec![1; 36]).into()),
+            options,
+        )
+        .await
+        .unwrap();
+
+    assert!(links.is_empty());
+    assert!(cascade.authority_disagreements().is_empty());
+}
+
+/// A caller that asked to fail on disagreement gets the disagreeing actions
+/// as an error instead of the merged links.
+#[tokio::test]
+async fn get_links_fails_on_disagreement_when_asked() {
+    let (cascade, base, create_link_hash) = disagreeing_links_cascade().await;
+
+    let mut options = aggregated_options();
+    options.get_options = options.get_options.with_fail_on_disagreement(true);
+    let err = cascade
+        .dht_get_links(link_key(base), options)
+        .await
+        .unwrap_err();
+
+    let CascadeError::AuthorityDisagreement(hashes) = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(hashes, vec![create_link_hash.clone()]);
+    assert_eq!(
+        cascade.authority_disagreements(),
+        HashSet::from([create_link_hash])
+    );
+}
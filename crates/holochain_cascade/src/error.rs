@@ -26,6 +26,9 @@ pub enum CascadeError {
 
     #[error("Network not initialized")]
     NetworkNotInitialized,
+
+    #[error("Authorities disagree on the validation status of actions: {0:?}")]
+    AuthorityDisagreement(Vec<ActionHash>),
 }
 
 pub type CascadeResult<T> = Result<T, CascadeError>;
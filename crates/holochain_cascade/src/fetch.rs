@@ -156,6 +156,21 @@ impl CascadeImpl {
         }
     }
 
+    /// Keep the actions that authorities disagreed on, to be reported by
+    /// [`CascadeImpl::authority_disagreements`].
+    pub(crate) fn record_authority_disagreements(&self, disagreements: &HashSet<ActionHash>) {
+        if disagreements.is_empty() {
+            return;
+        }
+        tracing::warn!(
+            ?disagreements,
+            "Authorities disagree on the validation status of actions"
+        );
+        self.authority_disagreements
+            .lock()
+            .extend(disagreements.iter().cloned());
+    }
+
     fn record_fetch_error(&self, fetch_type: &'static str) {
         let mut attrs = vec![opentelemetry::KeyValue::new("fetch_type", fetch_type)];
         if let Some((zome, fn_name)) = &self.zome_call_origin {
@@ -174,14 +189,15 @@ impl CascadeImpl {
         }
     }
 
-    /// Fetch a Record from the network, caching and returning the results
+    /// Fetch a Record from the network and cache the results, returning the
+    /// actions that the authorities disagreed on.
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, options)))]
     pub async fn fetch_record(
         &self,
         hash: AnyDhtHash,
         options: NetworkRequestOptions,
-    ) -> CascadeResult<()> {
-        let network = some_or_return!(self.network.as_ref());
+    ) -> CascadeResult<HashSet<ActionHash>> {
+        let network = some_or_return!(self.network.as_ref(), HashSet::new());
         let results = match network
             .get(hash, options, self.zome_call_origin.clone())
             .instrument(debug_span!("fetch_record::network_get"))
@@ -197,9 +213,11 @@ impl CascadeImpl {
                 return Err(e.into());
             }
         };
+        let disagreements = quorum::wire_ops_disagreements(&results);
+        self.record_authority_disagreements(&disagreements);
 
         self.merge_ops_into_cache(results).await?;
-        Ok(())
+        Ok(disagreements)
     }
 
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, options)))]
@@ -207,8 +225,8 @@ impl CascadeImpl {
         &self,
         link_key: WireLinkKey,
         options: GetLinksRequestOptions,
-    ) -> CascadeResult<()> {
-        let network = some_or_return!(self.network.as_ref());
+    ) -> CascadeResult<HashSet<ActionHash>> {
+        let network = some_or_return!(self.network.as_ref(), HashSet::new());
         let results = match network
             .get_links(link_key.clone(), options, self.zome_call_origin.clone())
             .await
@@ -223,9 +241,11 @@ impl CascadeImpl {
                 return Err(e.into());
             }
         };
+        let disagreements = quorum::wire_link_ops_disagreements(&results);
+        self.record_authority_disagreements(&disagreements);
 
         self.merge_link_ops_into_cache(results).await?;
-        Ok(())
+        Ok(disagreements)
     }
 
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, options)))]
@@ -51,5 +51,10 @@ mod tests {
         assert_eq!(network_options.remote_agent_count, 3);
         assert_eq!(network_options.timeout_ms, None);
         assert!(network_options.as_race);
+
+        // Test aggregating responses
+        let options = GetOptions::network().with_as_race(false);
+        let network_options = options.to_network_options();
+        assert!(!network_options.as_race);
     }
 }
@@ -15,6 +15,14 @@
 //! to. That means that if the requested data is available locally, the cascade won't attempt to
 //! fetch it again.
 //!
+//! Network requests race the authorities that are queried by default, using the first response
+//! that has data. When a request is not a race, see [`GetOptions::with_as_race`], the responses
+//! of all authorities are merged instead. If the authorities disagree on the validation status of
+//! an action, the merged data is still returned, and the action is reported by
+//! [`CascadeImpl::authority_disagreements`], which zomes read with the
+//! `get_authority_disagreements` host fn. With [`GetOptions::with_fail_on_disagreement`], the
+//! read fails with [`CascadeError::AuthorityDisagreement`] instead.
+//!
 //! ## Retrieve vs Get
 //!
 //! There are two words used in cascade functions: "get", and "retrieve".
@@ -77,6 +85,7 @@ pub mod get_options_ext;
 mod agent_activity;
 mod fetch;
 mod metrics;
+mod quorum;
 mod verify;
 
 #[cfg(feature = "test_utils")]
@@ -119,6 +128,10 @@ pub struct CascadeImpl {
     private_data: Option<Arc<AgentPubKey>>,
     /// Optional zome call origin for metrics attribution.
     zome_call_origin: Option<(ZomeName, FunctionName)>,
+    /// Actions whose validation status the authorities of an aggregated network request
+    /// disagreed on. Shared between clones of the cascade, see
+    /// [`CascadeImpl::authority_disagreements`].
+    authority_disagreements: Arc<parking_lot::Mutex<HashSet<ActionHash>>>,
 }
 
 /// Times a cascade query and records `hc.cascade.duration` on drop, so every
@@ -157,6 +170,18 @@ impl CascadeImpl {
         }
     }
 
+    /// Record the actions that authorities disagree on in a set that is shared with other
+    /// cascades, such as the cascades of the host fns of one zome call.
+    pub fn with_authority_disagreements(
+        self,
+        authority_disagreements: Arc<parking_lot::Mutex<HashSet<ActionHash>>>,
+    ) -> Self {
+        Self {
+            authority_disagreements,
+            ..self
+        }
+    }
+
     /// Add the ability to access private entries for this agent.
     pub fn with_private_data(self, author: Arc<AgentPubKey>) -> Self {
         Self {
@@ -182,6 +207,7 @@ impl CascadeImpl {
             dht_store: self.dht_store,
 
             zome_call_origin: self.zome_call_origin,
+            authority_disagreements: self.authority_disagreements,
         }
     }
 
@@ -194,6 +220,7 @@ impl CascadeImpl {
             dht_store,
 
             zome_call_origin: None,
+            authority_disagreements: Default::default(),
         }
     }
 
@@ -218,6 +245,7 @@ impl CascadeImpl {
             network: Some(network),
             dht_store,
             zome_call_origin: None,
+            authority_disagreements: Default::default(),
         }
     }
 
@@ -232,9 +260,20 @@ impl CascadeImpl {
             private_data: author,
             dht_store,
             zome_call_origin: None,
+            authority_disagreements: Default::default(),
         }
     }
 
+    /// The actions whose validation status the authorities of an aggregated network request,
+    /// made through this cascade or one of its clones, disagreed on.
+    ///
+    /// The validation status that an authority serves is not signed, so a disagreement only fails
+    /// the read when [`GetOptions::with_fail_on_disagreement`] is set. Otherwise the data of all
+    /// authorities is merged and returned as usual, and it is up to the caller whether to trust it.
+    pub fn authority_disagreements(&self) -> HashSet<ActionHash> {
+        self.authority_disagreements.lock().clone()
+    }
+
     /// Get an [`EntryDetails`], by its [`EntryHash`], which contains entry data along with all CRUD
     /// actions associated with it.
     ///
@@ -259,7 +298,9 @@ impl CascadeImpl {
                     .fetch_record(entry_hash.clone().into(), options.network_request_options)
                     .await
                 {
-                    Ok(_) => (),
+                    Ok(disagreements) => {
+                        quorum::check_disagreements(&options.get_options, disagreements)?
+                    }
                     Err(CascadeError::NetworkError(
                         e @ HolochainP2pError::NoPeersForLocation(_, _),
                     )) => {
@@ -298,7 +339,9 @@ impl CascadeImpl {
                     .fetch_record(action_hash.clone().into(), options.network_request_options)
                     .await
                 {
-                    Ok(_) => (),
+                    Ok(disagreements) => {
+                        quorum::check_disagreements(&options.get_options, disagreements)?
+                    }
                     Err(CascadeError::NetworkError(
                         e @ HolochainP2pError::NoPeersForLocation(_, _),
                     )) => {
@@ -360,7 +403,7 @@ impl CascadeImpl {
                     .fetch_record(action_hash.clone().into(), options.to_network_options())
                     .await
                 {
-                    Ok(_) => (),
+                    Ok(disagreements) => quorum::check_disagreements(&options, disagreements)?,
                     Err(CascadeError::NetworkError(
                         e @ HolochainP2pError::NoPeersForLocation(_, _),
                     )) => {
@@ -407,7 +450,7 @@ impl CascadeImpl {
                     .fetch_record(entry_hash.clone().into(), options.to_network_options())
                     .await
                 {
-                    Ok(_) => (),
+                    Ok(disagreements) => quorum::check_disagreements(&options, disagreements)?,
                     Err(CascadeError::NetworkError(
                         e @ HolochainP2pError::NoPeersForLocation(_, _),
                     )) => {
@@ -509,8 +552,9 @@ impl CascadeImpl {
         if let GetStrategy::Network = options.get_options.strategy() {
             let authority = self.am_i_an_authority(key.base.clone()).await?;
             if !authority {
+                let get_options = options.get_options.clone();
                 match self.fetch_links(key.clone(), options).await {
-                    Ok(_) => (),
+                    Ok(disagreements) => quorum::check_disagreements(&get_options, disagreements)?,
                     Err(CascadeError::NetworkError(
                         e @ HolochainP2pError::NoPeersForLocation(_, _),
                     )) => {
@@ -556,8 +600,9 @@ impl CascadeImpl {
         if let GetStrategy::Network = options.get_options.strategy() {
             let authority = self.am_i_an_authority(key.base.clone()).await?;
             if !authority {
+                let get_options = options.get_options.clone();
                 match self.fetch_links(key.clone(), options).await {
-                    Ok(_) => (),
+                    Ok(disagreements) => quorum::check_disagreements(&get_options, disagreements)?,
                     Err(CascadeError::NetworkError(
                         e @ HolochainP2pError::NoPeersForLocation(_, _),
                     )) => {
@@ -739,6 +784,9 @@ impl CascadeImpl {
             let results = self
                 .fetch_agent_activity(agent.clone(), query.clone(), options.clone())
                 .await?;
+            let disagreements = quorum::activity_disagreements(&results);
+            self.record_authority_disagreements(&disagreements);
+            quorum::check_disagreements(&options.get_options, disagreements)?;
             let merged_response: AgentActivityResponse =
                 agent_activity::merge_activities(agent.clone(), &options, results)?;
 
@@ -1038,3 +1086,7 @@ mod dht_store_scratch_overlay_tests;
 /// Tests for the `get_agent_activity_multi` network passthrough.
 #[cfg(all(test, feature = "test_utils"))]
 mod agent_activity_multi_tests;
+
+/// Tests for aggregated reads where the authorities disagree.
+#[cfg(all(test, feature = "test_utils"))]
+mod authority_disagreement_tests;
@@ -0,0 +1,200 @@
+//! Reconciliation of the responses gathered from several authorities by an
+//! aggregated (non-race) network request.
+//!
+//! Responses are merged by the cascade as a union, so the actions, deletes,
+//! updates and links held by any authority are all kept. What authorities
+//! must agree on is the validation status of each action they served. Where
+//! two authorities have judged the same action differently, there is no
+//! agreed status to use. The statuses served are not signed by the
+//! authorities, so by default a disagreement does not fail the read: the
+//! merged data is still returned, and the disagreement is recorded on the
+//! cascade for the caller to act on. A caller that would rather not use the
+//! merged data can ask for the read to fail instead, see
+//! [`GetOptions::with_fail_on_disagreement`].
+//!
+//! An authority that has not yet judged an action does not disagree with
+//! one that has.
+
+use super::*;
+
+/// Find the actions whose validation status the authorities of a get disagree on.
+pub(crate) fn wire_ops_disagreements(responses: &[WireOps]) -> HashSet<ActionHash> {
+    find_disagreements(responses.iter().flat_map(|response| {
+        let judged: Vec<&Judged<SignedAction>> = match response {
+            WireOps::Entry(ops) => ops
+                .creates
+                .iter()
+                .chain(&ops.deletes)
+                .chain(&ops.updates)
+                .collect(),
+            WireOps::Record(ops) => ops
+                .action
+                .iter()
+                .chain(&ops.deletes)
+                .chain(&ops.updates)
+                .collect(),
+            WireOps::Warrant(_) => Vec::new(),
+        };
+        judged.into_iter().filter_map(judged_status)
+    }))
+}
+
+/// Find the links whose validation status the authorities of a get links disagree on.
+pub(crate) fn wire_link_ops_disagreements(responses: &[WireLinkOps]) -> HashSet<ActionHash> {
+    find_disagreements(responses.iter().flat_map(|response| {
+        response
+            .creates
+            .iter()
+            .chain(&response.deletes)
+            .filter_map(judged_status)
+    }))
+}
+
+/// Find the actions that one authority of a get agent activity returned as valid
+/// activity and another as rejected activity.
+pub(crate) fn activity_disagreements(responses: &[AgentActivityResponse]) -> HashSet<ActionHash> {
+    find_disagreements(responses.iter().flat_map(|response| {
+        chain_item_hashes(&response.valid_activity)
+            .map(|hash| (hash, ValidationStatus::Valid))
+            .chain(
+                chain_item_hashes(&response.rejected_activity)
+                    .map(|hash| (hash, ValidationStatus::Rejected)),
+            )
+    }))
+}
+
+/// Fail the read with the actions the authorities disagreed on, if there are any
+/// and the caller asked to fail on disagreement.
+pub(crate) fn check_disagreements(
+    get_options: &GetOptions,
+    disagreements: HashSet<ActionHash>,
+) -> CascadeResult<()> {
+    if get_options.fail_on_disagreement() != Some(true) || disagreements.is_empty() {
+        return Ok(());
+    }
+    let mut disagreements: Vec<ActionHash> = disagreements.into_iter().collect();
+    disagreements.sort();
+    Err(CascadeError::AuthorityDisagreement(disagreements))
+}
+
+fn find_disagreements(
+    statuses: impl Iterator<Item = (ActionHash, ValidationStatus)>,
+) -> HashSet<ActionHash> {
+    let mut agreed = HashMap::new();
+    let mut disagreements = HashSet::new();
+    for (hash, status) in statuses {
+        match agreed.get(&hash) {
+            Some(agreed_status) if *agreed_status != status => {
+                disagreements.insert(hash);
+            }
+            Some(_) => {}
+            None => {
+                agreed.insert(hash, status);
+            }
+        }
+    }
+    disagreements
+}
+
+fn judged_status(judged: &Judged<SignedAction>) -> Option<(ActionHash, ValidationStatus)> {
+    judged
+        .status
+        .map(|status| (ActionHash::with_data_sync(judged.data.data()), status))
+}
+
+fn chain_item_hashes(items: &ChainItems) -> Box<dyn Iterator<Item = ActionHash> + '_> {
+    match items {
+        ChainItems::Full(records) => {
+            Box::new(records.iter().map(|record| record.action_address().clone()))
+        }
+        ChainItems::Hashes(hashes) => Box::new(hashes.iter().map(|(_, hash)| hash.clone())),
+        ChainItems::NotRequested => Box::new(std::iter::empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::fixt;
+    use holo_hash::fixt::{ActionHashFixturator, AgentPubKeyFixturator};
+    use holochain_zome_types::fixt::ActionFixturator;
+
+    fn link_ops(judged: Vec<Judged<SignedAction>>) -> WireLinkOps {
+        WireLinkOps {
+            creates: judged,
+            ..Default::default()
+        }
+    }
+
+    fn activity(valid: Vec<ActionHash>, rejected: Vec<ActionHash>) -> AgentActivityResponse {
+        let to_items = |hashes: Vec<ActionHash>| {
+            ChainItems::Hashes(
+                hashes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, h)| (i as u32, h))
+                    .collect(),
+            )
+        };
+        AgentActivityResponse {
+            agent: fixt!(AgentPubKey),
+            valid_activity: to_items(valid),
+            rejected_activity: to_items(rejected),
+            status: ChainStatus::Empty,
+            highest_observed: None,
+            warrants: vec![],
+        }
+    }
+
+    #[test]
+    fn links_with_the_same_status_agree() {
+        let link = SignedAction::new(fixt!(Action), fixt!(Signature));
+        let other_link = SignedAction::new(fixt!(Action), fixt!(Signature));
+
+        let responses = vec![
+            link_ops(vec![Judged::valid(link.clone())]),
+            // An authority that has not judged the link yet, or that holds
+            // other links, does not disagree.
+            link_ops(vec![Judged::none(link.clone()), Judged::valid(other_link)]),
+            link_ops(vec![Judged::valid(link)]),
+        ];
+
+        assert!(wire_link_ops_disagreements(&responses).is_empty());
+    }
+
+    #[test]
+    fn links_with_different_statuses_disagree() {
+        let link = SignedAction::new(fixt!(Action), fixt!(Signature));
+        let link_hash = ActionHash::with_data_sync(link.data());
+
+        let responses = vec![
+            link_ops(vec![Judged::valid(link.clone())]),
+            link_ops(vec![Judged::new(link, ValidationStatus::Rejected)]),
+        ];
+
+        assert_eq!(
+            wire_link_ops_disagreements(&responses),
+            HashSet::from([link_hash])
+        );
+    }
+
+    #[test]
+    fn activity_valid_for_one_authority_and_rejected_for_another_disagrees() {
+        let action_hash = fixt!(ActionHash);
+
+        let agreeing = vec![
+            activity(vec![action_hash.clone()], vec![]),
+            activity(vec![action_hash.clone(), fixt!(ActionHash)], vec![]),
+        ];
+        assert!(activity_disagreements(&agreeing).is_empty());
+
+        let disagreeing = vec![
+            activity(vec![action_hash.clone()], vec![]),
+            activity(vec![], vec![action_hash.clone()]),
+        ];
+        assert_eq!(
+            activity_disagreements(&disagreeing),
+            HashSet::from([action_hash])
+        );
+    }
+}
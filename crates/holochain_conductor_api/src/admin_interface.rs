@@ -1,5 +1,6 @@
+use crate::conductor::ZomeCallLimits;
 use crate::peer_meta::PeerMetaInfo;
-use crate::{AppInfo, FullStateDump, StorageInfo};
+use crate::{AbandonedOpsFilter, AppInfo, FullStateDump, OpTrace, StorageInfo, WorkflowStateDump};
 use holo_hash::*;
 use holochain_types::prelude::*;
 use holochain_types::websocket::AllowedOrigins;
@@ -207,6 +208,13 @@ pub enum AdminRequest {
         /// If this is `Some` then the interface will only accept connections for the specified app.
         /// Those connections will only be able to make calls to and receive signals from that app.
         installed_app_id: Option<InstalledAppId>,
+
+        /// Optional limits on the zome calls made over this app interface.
+        ///
+        /// These take the place of the `interface` zome call limits of the conductor config.
+        /// Any limit that is not set here is taken from the conductor config.
+        #[serde(default)]
+        zome_call_limits: Option<ZomeCallLimits>,
     },
 
     /// List all the app interfaces currently attached with [`AttachAppInterface`].
@@ -298,6 +306,112 @@ pub enum AdminRequest {
     /// [`AdminResponse::NetworkStatsDumped`]
     DumpNetworkStats,
 
+    /// Dump the round-trip latency that has been measured for peer URLs.
+    ///
+    /// Peers are pinged to estimate their latency, which is used to prefer lower latency
+    /// peers when selecting authorities, and peers whose pings keep failing are avoided.
+    /// This returns the samples, failure streak and expiry of each peer URL.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::PeerLatencyDumped`]
+    DumpPeerLatency,
+
+    /// Limit the background network traffic of an app.
+    ///
+    /// The gossip and fetch traffic of the DNAs of all the app's cells, including clones, counts
+    /// towards its limits. When a limit is reached, gossip for those DNAs is paused until the
+    /// day or rate window is over, while gets and publishes continue. Passing no limits removes
+    /// them. The limits are persisted across restarts, and removed when the app is uninstalled.
+    ///
+    /// The bytes sent and received for each DNA can be seen with
+    /// [`AdminRequest::DumpNetworkMetrics`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::BandwidthLimitsSet`]
+    SetBandwidthLimits {
+        /// The app to limit.
+        installed_app_id: InstalledAppId,
+        /// The limits to apply, or `None` to remove them.
+        limits: Option<BandwidthLimits>,
+    },
+
+    /// List the bandwidth limits that have been set with [`AdminRequest::SetBandwidthLimits`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::BandwidthLimitsListed`]
+    ListBandwidthLimits,
+
+    /// List the durable remote signals in the outbox of this conductor.
+    ///
+    /// Durable signals that could not be sent to an agent are kept in the outbox and
+    /// retried until they are sent or their TTL passes.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::SignalOutboxListed`]
+    ListSignalOutbox {
+        /// Only list the signals sent on this DNA.
+        dna_hash: Option<DnaHash>,
+    },
+
+    /// Dump the state of the validation and integration workflows for a DNA.
+    ///
+    /// Reports how many ops are in each limbo stage, the oldest op still waiting
+    /// for validation, histograms of validation attempts, abandoned ops and the
+    /// status of the queue consumers that drive the workflows.
+    ///
+    /// This is a good first place to look when an app does not seem to sync.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::WorkflowStateDumped`]
+    DumpWorkflowState {
+        /// The DNA for which to dump the workflow state.
+        dna_hash: DnaHash,
+    },
+
+    /// Re-queue abandoned ops of a DNA for validation.
+    ///
+    /// Ops that exhausted their validation attempts are marked as abandoned
+    /// and are not retried. This resets the attempt counters of the abandoned
+    /// ops that match `filter` and triggers the sys and app validation
+    /// workflows, which is useful after a coordinator fix or when a
+    /// previously unreachable peer becomes available.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AbandonedOpsRetried`] with the number of ops re-queued.
+    RetryAbandonedOps {
+        /// The DNA whose abandoned ops should be retried.
+        dna_hash: DnaHash,
+        /// Restricts which abandoned ops are retried. The default retries all
+        /// of them.
+        #[serde(default)]
+        filter: AbandonedOpsFilter,
+    },
+
+    /// Trace a single op through this conductor.
+    ///
+    /// Reports whether the op is held in limbo, integrated or cached, and
+    /// whether it was authored here; its validation status and attempts,
+    /// including dependencies sys validation is still missing; the publish
+    /// state and validation receipts of authored ops; whether the op's basis
+    /// is covered by a local agent's storage arc; and which known peers are
+    /// authorities for the basis.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpTraced`]
+    TraceOp {
+        /// The DNA the op belongs to.
+        dna_hash: DnaHash,
+        /// The hash of the op to trace.
+        op_hash: DhtOpHash,
+    },
+
     /// Add a list of agents to this conductor's peer store.
     ///
     /// This is a way of shortcutting peer discovery and is useful for testing.
@@ -390,6 +504,18 @@ pub enum AdminRequest {
     /// [`AdminResponse::StorageInfo`]
     StorageInfo,
 
+    /// Move the committed data of all the databases of the conductor out of their
+    /// write-ahead logs and into the database files.
+    ///
+    /// Afterwards the database files hold all the data that has been committed, so that
+    /// they can be copied, for example to back up the conductor. Data that is written
+    /// after the call goes to the write-ahead logs again.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DatabasesCheckpointed`]
+    CheckpointDatabases,
+
     /// Connecting to an app over an app websocket requires an authentication token. This endpoint
     /// is used to issue those tokens for use by app clients.
     ///
@@ -409,6 +535,36 @@ pub enum AdminRequest {
     /// Namely, this finds cells with DNAs whose manifest lists the given DNA hash in its `lineage` field.
     #[cfg(feature = "unstable-migration")]
     GetCompatibleCells(DnaHash),
+
+    /// Set how much of the DHT this conductor holds for a DNA, or for every DNA of an app.
+    ///
+    /// The policy pins the target arc of the conductor's agents to the full DHT or to nothing,
+    /// or sets the target arc factor to use in place of the one in the network config.
+    /// Passing no policy goes back to the network config. The agents don't need to rejoin
+    /// the network for the change to apply, and it is persisted across restarts.
+    ///
+    /// For an app, the policy is set for the DNAs of all its cells, including clones, at
+    /// the time of the call. Nothing is set unless the policy is valid and all the DNAs are
+    /// installed. A DNA's policy is removed when the last app that uses the DNA is uninstalled.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ArcPolicySet`]
+    #[cfg(feature = "unstable-sharding")]
+    SetArcPolicy {
+        /// The DNA or app to set the policy for.
+        target: ArcPolicyTarget,
+        /// The policy to apply, or `None` to use the network config.
+        policy: Option<ArcPolicy>,
+    },
+
+    /// List the arc policies that have been set with [`AdminRequest::SetArcPolicy`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ArcPoliciesListed`]
+    #[cfg(feature = "unstable-sharding")]
+    ListArcPolicies,
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -521,6 +677,29 @@ pub enum AdminResponse {
     /// The successful result of a call to [`AdminRequest::DumpNetworkStats`].
     NetworkStatsDumped(HolochainTransportStats),
 
+    /// The successful result of a call to [`AdminRequest::DumpPeerLatency`].
+    PeerLatencyDumped(Vec<PeerLatency>),
+
+    /// The successful result of a call to [`AdminRequest::SetBandwidthLimits`].
+    BandwidthLimitsSet,
+
+    /// The successful result of a call to [`AdminRequest::ListBandwidthLimits`].
+    BandwidthLimitsListed(HashMap<InstalledAppId, BandwidthLimits>),
+
+    /// The successful result of a call to [`AdminRequest::ListSignalOutbox`].
+    SignalOutboxListed(Vec<OutboxSignal>),
+
+    /// The successful result of a call to [`AdminRequest::DumpWorkflowState`].
+    WorkflowStateDumped(Box<WorkflowStateDump>),
+
+    /// The successful result of a call to [`AdminRequest::RetryAbandonedOps`].
+    ///
+    /// Contains the number of ops that were re-queued for validation.
+    AbandonedOpsRetried(u64),
+
+    /// The successful result of a call to [`AdminRequest::TraceOp`].
+    OpTraced(Box<OpTrace>),
+
     /// The successful response to an [`AdminRequest::AddAgentInfo`].
     ///
     /// This means the agent info was successfully added to the peer store.
@@ -553,6 +732,9 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::StorageInfo`].
     StorageInfo(StorageInfo),
 
+    /// The successful response to an [`AdminRequest::CheckpointDatabases`].
+    DatabasesCheckpointed,
+
     /// The successful response to an [`AdminRequest::IssueAppAuthenticationToken`].
     AppAuthenticationTokenIssued(AppAuthenticationTokenIssued),
 
@@ -562,12 +744,33 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::GetCompatibleCells`].
     #[cfg(feature = "unstable-migration")]
     CompatibleCells(CompatibleCells),
+
+    /// The successful response to an [`AdminRequest::SetArcPolicy`].
+    ///
+    /// Contains the DNAs that the policy was set for.
+    #[cfg(feature = "unstable-sharding")]
+    ArcPolicySet(Vec<DnaHash>),
+
+    /// The successful response to an [`AdminRequest::ListArcPolicies`].
+    #[cfg(feature = "unstable-sharding")]
+    ArcPoliciesListed(HashMap<DnaHash, ArcPolicy>),
 }
 
 #[cfg(feature = "unstable-migration")]
 pub type CompatibleCells =
     std::collections::BTreeSet<(InstalledAppId, std::collections::BTreeSet<CellId>)>;
 
+/// What an [`AdminRequest::SetArcPolicy`] applies to.
+#[cfg(feature = "unstable-sharding")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ArcPolicyTarget {
+    /// A single DNA.
+    Dna(DnaHash),
+    /// Every DNA of an installed app.
+    App(InstalledAppId),
+}
+
 /// Error type that goes over the websocket wire.
 /// This intends to be application developer facing
 /// so it should be readable and relevant
@@ -593,6 +796,11 @@ pub enum ExternalApiWireError {
     ZomeCallUnauthorized(String),
     /// A countersigning session has failed.
     CountersigningSessionError(String),
+    /// The zome call was rejected because the app or interface it was made
+    /// to is at its zome call concurrency or rate limit.
+    ///
+    /// Clients should back off before retrying the call.
+    ZomeCallLimitExceeded(String),
 }
 
 impl ExternalApiWireError {
@@ -633,6 +841,10 @@ pub struct AppInterfaceInfo {
 
     /// The optional association with a specific installed app.
     pub installed_app_id: Option<InstalledAppId>,
+
+    /// The zome call limits set for this app interface, if any.
+    #[serde(default)]
+    pub zome_call_limits: Option<ZomeCallLimits>,
 }
 
 /// Request payload for [AdminRequest::IssueAppAuthenticationToken].
@@ -76,6 +76,19 @@ pub enum AppRequest {
     /// to the expected [`ZomeCallParams`].
     CallZome(Box<ZomeCallParamsSigned>),
 
+    /// Call a zome function and report the wasm metering points it consumed.
+    ///
+    /// The payload is signed in the same way as for [`AppRequest::CallZome`].
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCalledMetered`]
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`AppRequest::CallZome`].
+    CallZomeMetered(Box<ZomeCallParamsSigned>),
+
     /// Get the state of a countersigning session.
     ///
     /// # Returns
@@ -262,6 +275,11 @@ pub enum AppRequest {
     ///
     /// Note that this bypasses the usual security mechanism where zomes must create a capability
     /// grant to permit `recv_remote_signal` to be invoked without restriction.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::Ok`], or [`AppResponse::DirectSignalAcknowledged`] if the signal was sent
+    /// with `acknowledged` set.
     SendDirectSignal {
         /// The app network to send messages on.
         dna_hash: DnaHash,
@@ -276,6 +294,22 @@ pub enum AppRequest {
         /// Treated as opaque by Holochain, it is up to the application to decide how to serialize,
         /// deserialize and process payloads.
         signal: Vec<u8>,
+
+        /// If set, the signal is kept in the outbox of this conductor and retried for up to
+        /// this many seconds for any agent it could not be sent to.
+        ///
+        /// The outcome is reported with `RemoteSignalQueued`, `RemoteSignalDelivered` and
+        /// `RemoteSignalExpired` system signals.
+        #[serde(default)]
+        durable_ttl_s: Option<u64>,
+
+        /// If set, wait for the conductor of each agent to acknowledge the signal.
+        ///
+        /// The acknowledgement says whether the signal was delivered to a subscribed app
+        /// interface, had no subscriber or was blocked by the receiver. Agents that don't answer are retried a few times
+        /// before being reported as unreachable, a retried signal is passed on only once.
+        #[serde(default)]
+        acknowledged: bool,
     },
 }
 
@@ -309,6 +343,17 @@ pub enum AppResponse {
     /// [msgpack]: https://msgpack.org/
     ZomeCalled(Box<ExternIO>),
 
+    /// The successful response to an [`AppRequest::CallZomeMetered`].
+    ZomeCalledMetered {
+        /// The return value of the zome function.
+        output: Box<ExternIO>,
+        /// The wasm metering points the zome function consumed.
+        ///
+        /// `None` if the function was not run by a metered wasm backend. Calls the
+        /// function made to other zomes or cells are metered separately and not included.
+        points_consumed: Option<u64>,
+    },
+
     /// The successful response to an [`AppRequest::GetCountersigningSessionState`].
     #[cfg(feature = "unstable-countersigning")]
     CountersigningSessionState(Box<Option<CountersigningSessionState>>),
@@ -346,6 +391,10 @@ pub enum AppResponse {
     /// All the wasm host functions supported by this conductor.
     ListWasmHostFunctions(Vec<String>),
 
+    /// The successful result of a call to [`AppRequest::SendDirectSignal`] with
+    /// `acknowledged` set, with the acknowledgement of each agent.
+    DirectSignalAcknowledged(Vec<DirectSignalAck>),
+
     /// Operation successful, no payload.
     Ok,
 }
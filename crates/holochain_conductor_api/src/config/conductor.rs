@@ -37,12 +37,15 @@
 
 use crate::conductor::process::ERROR_CODE;
 use crate::config::conductor::paths::DataRootPath;
+use holo_hash::{AgentPubKey, AgentPubKeyB64};
+use holochain_types::app::{InstalledAppId, WasmMeteringBudgets};
 use schemars::JsonSchema;
 #[cfg(feature = "schema")]
 use schemars::Schema;
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 mod admin_interface_config;
@@ -143,6 +146,32 @@ pub struct ConductorConfig {
 
     /// Tracing scope.
     pub tracing_scope: Option<String>,
+
+    /// Wasm metering budgets for zome calls and callbacks.
+    ///
+    /// Budgets set when an app is installed can only lower these.
+    #[serde(default)]
+    pub wasm_metering: Option<WasmMeteringConfig>,
+
+    /// Limits on the zome calls that clients may make through app interfaces.
+    ///
+    /// Calls over a limit are rejected rather than queued, so that a single
+    /// client flooding an interface cannot starve other apps on the conductor.
+    #[serde(default)]
+    pub zome_call_limits: Option<ZomeCallLimitsConfig>,
+
+    /// The publishers whose app bundles this conductor trusts.
+    ///
+    /// If not set, any app bundle can be installed. A signed bundle whose
+    /// signature doesn't match its contents is always rejected.
+    #[serde(default)]
+    pub app_publishers: Option<AppPublishersConfig>,
+
+    /// Setup interfaces that serve the UIs of installed web apps over HTTP.
+    ///
+    /// These must use [`InterfaceDriver::Http`].
+    #[serde(default)]
+    pub ui_interfaces: Option<Vec<UiInterfaceConfig>>,
 }
 
 /// Database synchronous level configuration.
@@ -209,6 +238,10 @@ impl Default for ConductorConfig {
             restore_chain_quorum: default_restore_chain_quorum(),
             tuning_params: None,
             tracing_scope: None,
+            wasm_metering: None,
+            zome_call_limits: None,
+            app_publishers: None,
+            ui_interfaces: None,
         }
     }
 }
@@ -268,6 +301,139 @@ impl ConductorConfig {
     }
 }
 
+/// Wasm metering budgets for the apps of a conductor.
+#[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WasmMeteringConfig {
+    /// Budgets for apps that have none of their own here.
+    #[serde(default)]
+    pub default: WasmMeteringBudgets,
+
+    /// Budgets for specific apps, keyed by installed app id.
+    ///
+    /// Budgets that are not set for an app fall back to `default`.
+    #[serde(default)]
+    pub apps: BTreeMap<InstalledAppId, WasmMeteringBudgets>,
+}
+
+impl WasmMeteringConfig {
+    /// The budgets for an app, given the budgets it was installed with.
+    ///
+    /// The budgets configured for the app are a ceiling for the ones it was
+    /// installed with.
+    pub fn budgets_for_app(
+        &self,
+        installed_app_id: &InstalledAppId,
+        installed: Option<&WasmMeteringBudgets>,
+    ) -> WasmMeteringBudgets {
+        let configured = match self.apps.get(installed_app_id) {
+            Some(budgets) => budgets.clone().or(&self.default),
+            None => self.default.clone(),
+        };
+        match installed {
+            Some(budgets) => budgets.clamp_to(&configured),
+            None => configured,
+        }
+    }
+}
+
+/// Limits on the rate and concurrency of zome calls.
+#[derive(Clone, Copy, Default, Deserialize, Serialize, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ZomeCallLimits {
+    /// The maximum number of zome calls that may be in progress at once.
+    ///
+    /// Must be greater than 0. Leave it unset to not limit concurrent calls.
+    #[serde(default, deserialize_with = "deserialize_zome_call_limit")]
+    pub max_concurrent_calls: Option<u32>,
+
+    /// The maximum number of zome calls that may be started per second.
+    ///
+    /// Short bursts of up to this many calls are allowed. Must be greater
+    /// than 0. Leave it unset to not limit the rate of calls.
+    #[serde(default, deserialize_with = "deserialize_zome_call_limit")]
+    pub max_calls_per_second: Option<u32>,
+}
+
+fn deserialize_zome_call_limit<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<u32>::deserialize(deserializer)?;
+    if value == Some(0) {
+        return Err(serde::de::Error::custom(
+            "zome call limits must be greater than 0",
+        ));
+    }
+    Ok(value)
+}
+
+impl ZomeCallLimits {
+    /// Fill in any limit that is not set here from `fallback`.
+    pub fn or(self, fallback: ZomeCallLimits) -> Self {
+        Self {
+            max_concurrent_calls: self.max_concurrent_calls.or(fallback.max_concurrent_calls),
+            max_calls_per_second: self.max_calls_per_second.or(fallback.max_calls_per_second),
+        }
+    }
+}
+
+/// Zome call limits for the app interfaces and apps of a conductor.
+#[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ZomeCallLimitsConfig {
+    /// Limits shared by all connections to a single app interface.
+    #[serde(default)]
+    pub interface: ZomeCallLimits,
+
+    /// Limits shared by all calls to a single app, through any interface,
+    /// for apps that have none of their own here.
+    #[serde(default)]
+    pub app: ZomeCallLimits,
+
+    /// Limits for specific apps, keyed by installed app id.
+    ///
+    /// Limits that are not set for an app fall back to `app`.
+    #[serde(default)]
+    pub apps: BTreeMap<InstalledAppId, ZomeCallLimits>,
+}
+
+impl ZomeCallLimitsConfig {
+    /// The limits for calls to an app.
+    pub fn limits_for_app(&self, installed_app_id: &InstalledAppId) -> ZomeCallLimits {
+        match self.apps.get(installed_app_id) {
+            Some(limits) => limits.or(self.app),
+            None => self.app,
+        }
+    }
+}
+
+/// The publishers whose app bundles a conductor trusts.
+///
+/// When this is configured, a signed app bundle can only be installed if its
+/// publisher is trusted, and unsigned bundles are rejected unless
+/// `allow_unsigned` is set.
+#[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AppPublishersConfig {
+    /// The agent keys of trusted publishers.
+    #[serde(default)]
+    pub trusted: Vec<AgentPubKeyB64>,
+
+    /// Whether app bundles without a publisher signature can be installed.
+    #[serde(default)]
+    pub allow_unsigned: bool,
+}
+
+impl AppPublishersConfig {
+    /// Whether bundles signed by `publisher` can be installed.
+    pub fn is_trusted(&self, publisher: &AgentPubKey) -> bool {
+        self.trusted
+            .iter()
+            .any(|trusted| AgentPubKey::from(trusted.clone()) == *publisher)
+    }
+}
+
 /// The WASM backend to use.
 ///
 /// Note that the backend must be available in the Holochain binary, otherwise it will reject the
@@ -814,6 +980,10 @@ mod tests {
                 tracing_scope: None,
                 incoming_request_concurrency_limit: default_incoming_request_concurrency_limit(),
                 restore_chain_quorum: default_restore_chain_quorum(),
+                wasm_metering: None,
+                zome_call_limits: None,
+                app_publishers: None,
+                ui_interfaces: None,
             }
         );
     }
@@ -988,6 +1158,10 @@ admin_interfaces:
                 restore_chain_quorum: default_restore_chain_quorum(),
                 tuning_params: None,
                 tracing_scope: None,
+                wasm_metering: None,
+                zome_call_limits: None,
+                app_publishers: None,
+                ui_interfaces: None,
             }
         );
     }
@@ -1018,6 +1192,10 @@ admin_interfaces:
                 tracing_scope: None,
                 incoming_request_concurrency_limit: default_incoming_request_concurrency_limit(),
                 restore_chain_quorum: default_restore_chain_quorum(),
+                wasm_metering: None,
+                zome_call_limits: None,
+                app_publishers: None,
+                ui_interfaces: None,
             }
         );
     }
@@ -1220,4 +1398,88 @@ admin_interfaces:
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         assert_matches!(result, Err(ConductorConfigError::SerializationError(_)));
     }
+
+    #[test]
+    fn config_zome_call_limits_reject_zero() {
+        let yaml = r#"---
+    data_root_path: /path/to/env
+    keystore:
+      type: danger_test_keystore
+    zome_call_limits:
+      interface:
+        max_calls_per_second: 10
+      apps:
+        my_app:
+          max_concurrent_calls: 0
+    "#;
+        let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
+        assert_matches!(result, Err(ConductorConfigError::SerializationError(_)));
+
+        let yaml = r#"---
+    data_root_path: /path/to/env
+    keystore:
+      type: danger_test_keystore
+    zome_call_limits:
+      interface:
+        max_calls_per_second: 10
+      apps:
+        my_app:
+          max_concurrent_calls: 2
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        let limits = result.zome_call_limits.unwrap();
+        assert_eq!(limits.interface.max_calls_per_second, Some(10));
+        assert_eq!(
+            limits.limits_for_app(&"my_app".to_string()),
+            ZomeCallLimits {
+                max_concurrent_calls: Some(2),
+                max_calls_per_second: None,
+            }
+        );
+    }
+
+    #[test]
+    fn config_app_publishers() {
+        let publisher = AgentPubKey::from_raw_32(vec![1; 32]);
+        let yaml = format!(
+            r#"---
+    data_root_path: /path/to/env
+    keystore:
+      type: danger_test_keystore
+    app_publishers:
+      trusted:
+        - {publisher}
+    "#
+        );
+        let result: ConductorConfig = config_from_yaml(&yaml).unwrap();
+        let app_publishers = result.app_publishers.unwrap();
+        assert!(!app_publishers.allow_unsigned);
+        assert!(app_publishers.is_trusted(&publisher));
+        assert!(!app_publishers.is_trusted(&AgentPubKey::from_raw_32(vec![2; 32])));
+    }
+
+    #[test]
+    fn config_ui_interfaces() {
+        let yaml = r#"---
+    data_root_path: /path/to/env
+    keystore:
+      type: danger_test_keystore
+    ui_interfaces:
+      - driver:
+          type: http
+          port: 8888
+          allowed_origins: "http://my-app.localhost:8888"
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.ui_interfaces,
+            Some(vec![UiInterfaceConfig {
+                driver: InterfaceDriver::Http {
+                    port: 8888,
+                    danger_bind_addr: None,
+                    allowed_origins: "http://my-app.localhost:8888".to_string().into(),
+                },
+            }])
+        );
+    }
 }
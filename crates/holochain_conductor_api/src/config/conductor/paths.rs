@@ -14,6 +14,10 @@ pub const WASM_DIRECTORY: &str = "wasm";
 /// generated reports.
 pub const REPORTS_DIRECTORY: &str = "reports";
 
+/// Subdirectory of the data directory where the conductor stores the UIs
+/// of installed web apps.
+pub const WEB_UI_DIRECTORY: &str = "web_ui";
+
 /// Name of the file that conductor config is written to.
 pub const CONDUCTOR_CONFIG: &str = "conductor-config.yaml";
 
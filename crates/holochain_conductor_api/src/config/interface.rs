@@ -13,6 +13,16 @@ pub struct AdminInterfaceConfig {
     pub driver: InterfaceDriver,
 }
 
+/// Information needed to spawn an interface that serves the UIs of web apps
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UiInterfaceConfig {
+    /// By what means the interface will be exposed.
+    ///
+    /// This must be [`InterfaceDriver::Http`].
+    pub driver: InterfaceDriver,
+}
+
 /// Configuration for interfaces, specifying the means by which an interface
 /// should be opened.
 ///
@@ -50,13 +60,38 @@ pub enum InterfaceDriver {
         /// Connections from any origin which is not permitted by this config will be rejected.
         allowed_origins: AllowedOrigins,
     },
+
+    /// An HTTP interface that serves the UI of each installed web app.
+    ///
+    /// The UI of an app is served under its own origin, with the app ID as the first label of
+    /// the host, e.g. `http://my-app.localhost:8888` for the app `my-app`. HTML pages are served
+    /// with the port of the app interface for the app's UI and a freshly issued, short-lived app
+    /// authentication token, in `window.__HC_LAUNCHER_ENV__`.
+    Http {
+        /// The port on which to serve the UIs.
+        port: u16,
+
+        /// An optional address to bind the interface to.
+        ///
+        /// If no value is set then the interface will bind to `localhost`. Each page load issues
+        /// an app authentication token, so anyone who can reach this interface can connect to
+        /// the apps whose UIs it serves.
+        danger_bind_addr: Option<String>,
+
+        /// Allowed origins for this interface.
+        ///
+        /// Only UIs whose origin is listed are served, and the app interface a UI is given
+        /// only accepts connections from the UI's origins. The origins must be listed
+        /// explicitly, the interface can't be started if any origin (`*`) is allowed.
+        allowed_origins: AllowedOrigins,
+    },
 }
 
 impl InterfaceDriver {
     /// Get the port for this driver.
     pub fn port(&self) -> u16 {
         match self {
-            InterfaceDriver::Websocket { port, .. } => *port,
+            InterfaceDriver::Websocket { port, .. } | InterfaceDriver::Http { port, .. } => *port,
         }
     }
 
@@ -65,6 +100,9 @@ impl InterfaceDriver {
         match self {
             InterfaceDriver::Websocket {
                 danger_bind_addr, ..
+            }
+            | InterfaceDriver::Http {
+                danger_bind_addr, ..
             } => danger_bind_addr.as_ref(),
         }
     }
@@ -74,6 +112,9 @@ impl InterfaceDriver {
         match self {
             InterfaceDriver::Websocket {
                 allowed_origins, ..
+            }
+            | InterfaceDriver::Http {
+                allowed_origins, ..
             } => allowed_origins,
         }
     }
@@ -33,15 +33,19 @@ pub mod docs;
 mod admin_interface;
 mod app_interface;
 pub mod config;
+pub mod op_trace;
 pub mod peer_meta;
 pub mod signal_subscription;
 pub mod state;
 pub mod state_dump;
 pub mod storage_info;
+pub mod workflow_state;
 
 pub use admin_interface::*;
 pub use app_interface::*;
 pub use config::*;
+pub use op_trace::*;
 pub use peer_meta::*;
 pub use state_dump::*;
 pub use storage_info::*;
+pub use workflow_state::*;
@@ -0,0 +1,107 @@
+//! Types describing where a single DHT op is on this conductor and why,
+//! returned by [`AdminRequest::TraceOp`](crate::AdminRequest::TraceOp).
+
+use crate::LimboStage;
+use holo_hash::{ActionHash, AgentPubKey, AnyLinkableHash, DhtOpHash};
+use holochain_types::prelude::{ChainOpType, SignedValidationReceipt, Timestamp, ValidationStatus};
+use kitsune2_api::Url;
+use serde::{Deserialize, Serialize};
+
+/// Everything this conductor knows about one op.
+///
+/// Apart from [`OpTrace::authorities`], which reads the local peer store,
+/// everything here is read from the DNA's database; no network calls are made.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OpTrace {
+    /// Hash of the traced op.
+    pub op_hash: DhtOpHash,
+    /// Where the op is stored.
+    pub state: OpState,
+    /// Whether the op was authored by an agent on this conductor.
+    pub authored: bool,
+    /// Type of the chain op, or `None` for warrants and absent ops.
+    pub op_type: Option<ChainOpType>,
+    /// Hash of the action carried by a chain op.
+    pub action_hash: Option<ActionHash>,
+    /// Author of the action or warrant.
+    pub author: Option<AgentPubKey>,
+    /// DHT basis of the op. For warrants, this is the warrantee.
+    pub basis: Option<AnyLinkableHash>,
+    /// When this node received the op.
+    pub when_received: Option<Timestamp>,
+    /// When the op was integrated, for integrated and cached ops.
+    pub when_integrated: Option<Timestamp>,
+    /// The validation outcome, once there is one.
+    pub validation_status: Option<ValidationStatus>,
+    /// Validation progress while the op is in limbo.
+    pub limbo: Option<OpLimboTrace>,
+    /// Actions that sys validation is waiting to find before it can validate
+    /// this op.
+    ///
+    /// Only known while the op is pending sys validation and the workflow
+    /// has already looked for its dependencies.
+    pub missing_dependencies: Vec<ActionHash>,
+    /// Publish state of an op authored on this conductor.
+    pub publish: Option<OpPublishState>,
+    /// Validation receipts received for an op authored on this conductor.
+    pub validation_receipts: Vec<SignedValidationReceipt>,
+    /// Whether the storage arc of any local agent covers the basis.
+    ///
+    /// `None` if the basis is unknown or the DNA has not joined the network.
+    pub basis_in_local_arc: Option<bool>,
+    /// Remote peers in the local peer store whose storage arc covers the
+    /// basis. These are the candidates that gets and publishes for the basis
+    /// are sent to.
+    pub authorities: Vec<OpAuthority>,
+}
+
+/// Where a traced op is stored.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpState {
+    /// Not held by this conductor.
+    Absent,
+    /// Received and waiting for validation or integration.
+    Limbo,
+    /// Validated by this conductor and integrated.
+    Integrated,
+    /// Held as a cached copy from a get, without local validation.
+    Cached,
+}
+
+/// Validation progress of an op in limbo.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OpLimboTrace {
+    /// The stage the op is in.
+    pub stage: LimboStage,
+    /// Number of sys validation attempts.
+    pub sys_validation_attempts: u32,
+    /// Number of app validation attempts. Always `0` for warrants.
+    pub app_validation_attempts: u32,
+    /// When validation was last attempted.
+    pub last_validation_attempt: Option<Timestamp>,
+    /// When validation was abandoned, if it was.
+    pub abandoned_at: Option<Timestamp>,
+}
+
+/// Publish state of an authored op.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OpPublishState {
+    /// When the op was last published.
+    pub last_publish_time: Option<Timestamp>,
+    /// Whether enough validation receipts have been received to stop
+    /// publishing. Always `false` for warrants.
+    pub receipts_complete: bool,
+    /// Whether publishing is withheld, as for ops of an unfinished
+    /// countersigning session.
+    pub withhold_publish: bool,
+}
+
+/// A remote peer that is an authority for the basis of a traced op.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OpAuthority {
+    /// The peer's agent.
+    pub agent: AgentPubKey,
+    /// The URL the peer is reachable at.
+    pub url: Url,
+}
@@ -1,6 +1,7 @@
 //! Structs which allow the Conductor's state to be persisted across
 //! startups and shutdowns
 
+use crate::config::conductor::ZomeCallLimits;
 use crate::config::InterfaceDriver;
 use crate::signal_subscription::SignalSubscription;
 use holochain_types::prelude::*;
@@ -294,6 +295,11 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// Zome call limits for this interface, in place of the `interface` limits of the
+    /// conductor config. Limits that are not set here fall back to those.
+    #[serde(default)]
+    pub zome_call_limits: Option<ZomeCallLimits>,
 }
 
 impl AppInterfaceConfig {
@@ -312,8 +318,15 @@ impl AppInterfaceConfig {
                 danger_bind_addr,
                 allowed_origins,
             },
+            zome_call_limits: None,
         }
     }
+
+    /// Set the zome call limits of this interface.
+    pub fn with_zome_call_limits(mut self, zome_call_limits: Option<ZomeCallLimits>) -> Self {
+        self.zome_call_limits = zome_call_limits;
+        self
+    }
 }
 
 #[cfg(test)]
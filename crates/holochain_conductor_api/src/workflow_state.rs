@@ -0,0 +1,133 @@
+//! Types describing the validation and integration pipeline of a DNA, used by
+//! [`AdminRequest::DumpWorkflowState`](crate::AdminRequest::DumpWorkflowState)
+//! and [`AdminRequest::RetryAbandonedOps`](crate::AdminRequest::RetryAbandonedOps).
+
+use holo_hash::{AgentPubKey, AnyLinkableHash, DhtOpHash};
+use holochain_types::prelude::{ChainOpType, Timestamp};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Snapshot of the validation and integration queues of one DNA on this
+/// conductor.
+///
+/// Everything here is read locally; no network calls are made.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowStateDump {
+    /// Number of ops in each limbo stage.
+    pub limbo: LimboStageCounts,
+
+    /// Number of integrated ops for which a validation receipt still has to be
+    /// sent to the author.
+    pub pending_validation_receipts: u64,
+
+    /// The longest-waiting op that is still pending sys or app validation.
+    pub oldest_pending_op: Option<LimboOpInfo>,
+
+    /// Number of ops awaiting sys validation, keyed by the number of
+    /// sys validation attempts made so far. Abandoned ops are not counted.
+    pub sys_validation_attempts: BTreeMap<u32, u64>,
+
+    /// Number of ops awaiting app validation, keyed by the number of
+    /// app validation attempts made so far. Abandoned ops are not counted.
+    pub app_validation_attempts: BTreeMap<u32, u64>,
+
+    /// Abandoned ops, most recently abandoned first.
+    ///
+    /// Capped at [`WorkflowStateDump::MAX_ABANDONED_OPS`]; the total is
+    /// available in [`LimboStageCounts::abandoned`].
+    pub abandoned_ops: Vec<LimboOpInfo>,
+
+    /// Status of the queue consumers that drive this DNA's workflows.
+    pub queue_consumers: Vec<QueueConsumerStatus>,
+}
+
+impl WorkflowStateDump {
+    /// Maximum number of entries returned in [`WorkflowStateDump::abandoned_ops`].
+    pub const MAX_ABANDONED_OPS: u32 = 100;
+}
+
+/// Number of ops in each stage of the validation limbo.
+///
+/// Abandoned ops are only counted in `abandoned`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LimboStageCounts {
+    /// Chain ops and warrants awaiting sys validation.
+    pub pending_sys_validation: u64,
+    /// Chain ops that passed sys validation and await app validation.
+    pub pending_app_validation: u64,
+    /// Ops with a final validation outcome that await integration.
+    pub awaiting_integration: u64,
+    /// Ops whose validation was abandoned.
+    pub abandoned: u64,
+}
+
+/// The validation stage an op in limbo is waiting on.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LimboStage {
+    /// Waiting for sys validation.
+    PendingSysValidation,
+    /// Passed sys validation and waiting for app validation.
+    PendingAppValidation,
+    /// Validated and waiting for integration.
+    AwaitingIntegration,
+}
+
+/// Validation bookkeeping for a single op in limbo.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LimboOpInfo {
+    /// Hash of the op.
+    pub op_hash: DhtOpHash,
+    /// Type of the chain op, or `None` for a warrant.
+    pub op_type: Option<ChainOpType>,
+    /// DHT basis of the op. For warrants, this is the warrantee.
+    pub basis: AnyLinkableHash,
+    /// Author of the action or warrant.
+    pub author: AgentPubKey,
+    /// The stage the op is in.
+    pub stage: LimboStage,
+    /// When this node received the op.
+    pub when_received: Timestamp,
+    /// Number of sys validation attempts.
+    pub sys_validation_attempts: u32,
+    /// Number of app validation attempts. Always `0` for warrants.
+    pub app_validation_attempts: u32,
+    /// When validation was last attempted.
+    pub last_validation_attempt: Option<Timestamp>,
+    /// When validation was abandoned, if it was.
+    pub abandoned_at: Option<Timestamp>,
+    /// Why the last validation attempt did not complete, such as a missing
+    /// dependency or an error in the validation workflow. `None` if no
+    /// attempt has failed since the op last moved on to a new stage.
+    pub last_validation_error: Option<String>,
+}
+
+/// Status of a queue consumer workflow.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QueueConsumerStatus {
+    /// Name of the workflow, e.g. `sys_validation`.
+    pub workflow: String,
+    /// Whether the consumer task is still listening for triggers.
+    pub running: bool,
+    /// Whether the consumer's retry loop is paused, so that it only runs
+    /// when explicitly triggered.
+    pub loop_paused: bool,
+}
+
+/// Selects the abandoned ops retried by
+/// [`AdminRequest::RetryAbandonedOps`](crate::AdminRequest::RetryAbandonedOps).
+///
+/// Every field that is set must match; the default matches all abandoned ops.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AbandonedOpsFilter {
+    /// Only retry chain ops of this type. Warrants never match an op type.
+    #[serde(default)]
+    pub op_type: Option<ChainOpType>,
+    /// Only retry ops whose action or warrant was authored by this agent.
+    #[serde(default)]
+    pub author: Option<AgentPubKey>,
+    /// Only retry ops with this DHT basis. For warrants, this is matched
+    /// against the warrantee.
+    #[serde(default)]
+    pub basis: Option<AnyLinkableHash>,
+}
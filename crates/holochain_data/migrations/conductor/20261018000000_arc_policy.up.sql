@@ -0,0 +1,7 @@
+-- Per-DNA arc policies set at runtime through the admin API.
+-- The policy is stored msgpack encoded. DNAs without a row use the target
+-- arc factor from the conductor config.
+CREATE TABLE IF NOT EXISTS ArcPolicy (
+    dna_hash BLOB PRIMARY KEY,
+    policy_blob BLOB NOT NULL
+) STRICT;
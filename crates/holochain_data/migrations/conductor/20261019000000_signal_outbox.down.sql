@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS SignalOutbox_next_attempt_at;
+DROP TABLE IF EXISTS SignalOutbox;
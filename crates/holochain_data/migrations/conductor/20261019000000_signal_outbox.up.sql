@@ -0,0 +1,15 @@
+-- Durable remote signals that could not be sent to their agent yet.
+-- The payload is stored msgpack encoded. Timestamps are in microseconds.
+CREATE TABLE IF NOT EXISTS SignalOutbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    dna_hash BLOB NOT NULL,
+    from_agent BLOB NOT NULL,
+    to_agent BLOB NOT NULL,
+    payload_blob BLOB NOT NULL,
+    created_at INTEGER NOT NULL,
+    expires_at INTEGER NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at INTEGER NOT NULL
+) STRICT;
+
+CREATE INDEX IF NOT EXISTS SignalOutbox_next_attempt_at ON SignalOutbox (next_attempt_at);
@@ -0,0 +1,14 @@
+-- Per-app bandwidth limits set at runtime through the admin API.
+-- The limits are stored msgpack encoded. Removed automatically when the app
+-- is uninstalled, via the cascade on InstalledApp.
+--
+-- The traffic counted towards the daily limit is stored alongside the limits,
+-- so that a restart doesn't start a new day. It is reset when the limits change.
+CREATE TABLE IF NOT EXISTS BandwidthLimits (
+    app_id TEXT PRIMARY KEY,
+    limits_blob BLOB NOT NULL,
+    -- Microseconds since the UNIX epoch, NULL until traffic has been counted.
+    day_start INTEGER,
+    day_bytes INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (app_id) REFERENCES InstalledApp(app_id) ON DELETE CASCADE
+) STRICT;
@@ -0,0 +1,2 @@
+ALTER TABLE AppInterface DROP COLUMN max_calls_per_second;
+ALTER TABLE AppInterface DROP COLUMN max_concurrent_calls;
@@ -0,0 +1,5 @@
+-- Zome call limits set for a single app interface when it is attached, in
+-- place of the interface limits of the conductor config. NULL where a limit
+-- is not set for the interface.
+ALTER TABLE AppInterface ADD COLUMN max_concurrent_calls INTEGER;
+ALTER TABLE AppInterface ADD COLUMN max_calls_per_second INTEGER;
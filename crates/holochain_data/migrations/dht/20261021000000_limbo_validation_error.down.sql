@@ -0,0 +1,2 @@
+ALTER TABLE LimboWarrantOp DROP COLUMN last_validation_error;
+ALTER TABLE LimboChainOp DROP COLUMN last_validation_error;
@@ -0,0 +1,4 @@
+-- The error of the last failed validation attempt of an op in limbo, shown
+-- to operators through the admin API.
+ALTER TABLE LimboChainOp ADD COLUMN last_validation_error TEXT;
+ALTER TABLE LimboWarrantOp ADD COLUMN last_validation_error TEXT;
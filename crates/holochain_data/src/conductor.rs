@@ -38,7 +38,9 @@ where
     E: Executor<'e, Database = Sqlite>,
 {
     let models: Vec<AppInterfaceModel> = sqlx::query_as(
-        "SELECT port, id, driver_type, websocket_port, danger_bind_addr, allowed_origins_blob, installed_app_id FROM AppInterface",
+        "SELECT port, id, driver_type, websocket_port, danger_bind_addr, allowed_origins_blob, installed_app_id,
+                max_concurrent_calls, max_calls_per_second
+         FROM AppInterface",
     )
     .fetch_all(executor)
     .await?;
@@ -151,14 +153,17 @@ where
     E: Executor<'e, Database = Sqlite>,
 {
     sqlx::query(
-        "INSERT INTO AppInterface (port, id, driver_type, websocket_port, danger_bind_addr, allowed_origins_blob, installed_app_id)
-         VALUES (?, ?, ?, ?, ?, ?, ?)
+        "INSERT INTO AppInterface (port, id, driver_type, websocket_port, danger_bind_addr, allowed_origins_blob, installed_app_id,
+                                   max_concurrent_calls, max_calls_per_second)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
          ON CONFLICT(port, id) DO UPDATE SET
             driver_type = excluded.driver_type,
             websocket_port = excluded.websocket_port,
             danger_bind_addr = excluded.danger_bind_addr,
             allowed_origins_blob = excluded.allowed_origins_blob,
-            installed_app_id = excluded.installed_app_id",
+            installed_app_id = excluded.installed_app_id,
+            max_concurrent_calls = excluded.max_concurrent_calls,
+            max_calls_per_second = excluded.max_calls_per_second",
     )
     .bind(port)
     .bind(id)
@@ -167,6 +172,8 @@ where
     .bind(&model.danger_bind_addr)
     .bind(&model.allowed_origins_blob)
     .bind(&model.installed_app_id)
+    .bind(model.max_concurrent_calls)
+    .bind(model.max_calls_per_second)
     .execute(executor)
     .await?;
     Ok(())
@@ -341,6 +348,347 @@ where
     Ok(())
 }
 
+// ============================================================================
+// Arc Policy Operations
+// ============================================================================
+
+/// Set the arc policy for a DNA, replacing any previous policy.
+async fn put_arc_policy<'e, E>(
+    executor: E,
+    dna_hash: &DnaHash,
+    policy: &ArcPolicy,
+) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let policy_blob: Vec<u8> = holochain_serialized_bytes::encode(policy)
+        .map_err(|e| sqlx::Error::Protocol(format!("Serialization error: {}", e)))?;
+
+    sqlx::query(
+        "INSERT INTO ArcPolicy (dna_hash, policy_blob) VALUES (?, ?)
+         ON CONFLICT(dna_hash) DO UPDATE SET policy_blob = excluded.policy_blob",
+    )
+    .bind(dna_hash.get_raw_39())
+    .bind(policy_blob)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Get the arc policies of all DNAs that have one.
+async fn get_all_arc_policies<'e, E>(executor: E) -> sqlx::Result<Vec<(DnaHash, ArcPolicy)>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let rows: Vec<(Vec<u8>, Vec<u8>)> =
+        sqlx::query_as("SELECT dna_hash, policy_blob FROM ArcPolicy")
+            .fetch_all(executor)
+            .await?;
+
+    rows.into_iter()
+        .map(|(dna_hash, policy_blob)| {
+            let policy = holochain_serialized_bytes::decode(&policy_blob)
+                .map_err(|e| sqlx::Error::Protocol(format!("Deserialization error: {}", e)))?;
+            Ok((DnaHash::from_raw_39(dna_hash), policy))
+        })
+        .collect()
+}
+
+/// Delete the arc policy for a DNA.
+async fn delete_arc_policy<'e, E>(executor: E, dna_hash: &DnaHash) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM ArcPolicy WHERE dna_hash = ?")
+        .bind(dna_hash.get_raw_39())
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+// ============================================================================
+// Bandwidth Limit Operations
+// ============================================================================
+
+/// Set the bandwidth limits of an installed app, replacing any previous limits.
+///
+/// Setting different limits resets the traffic counted towards them.
+async fn put_bandwidth_limits<'e, E>(
+    executor: E,
+    app_id: &str,
+    limits: &BandwidthLimits,
+) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let limits_blob: Vec<u8> = holochain_serialized_bytes::encode(limits)
+        .map_err(|e| sqlx::Error::Protocol(format!("Serialization error: {}", e)))?;
+
+    sqlx::query(
+        "INSERT INTO BandwidthLimits (app_id, limits_blob) VALUES (?, ?)
+         ON CONFLICT(app_id) DO UPDATE SET
+            day_start = CASE WHEN limits_blob = excluded.limits_blob THEN day_start END,
+            day_bytes = CASE WHEN limits_blob = excluded.limits_blob THEN day_bytes ELSE 0 END,
+            limits_blob = excluded.limits_blob",
+    )
+    .bind(app_id)
+    .bind(limits_blob)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Get the bandwidth limits of all installed apps that have them.
+async fn get_all_bandwidth_limits<'e, E>(
+    executor: E,
+) -> sqlx::Result<Vec<(InstalledAppId, BandwidthLimits)>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let rows: Vec<(String, Vec<u8>)> =
+        sqlx::query_as("SELECT app_id, limits_blob FROM BandwidthLimits")
+            .fetch_all(executor)
+            .await?;
+
+    rows.into_iter()
+        .map(|(app_id, limits_blob)| {
+            let limits = holochain_serialized_bytes::decode(&limits_blob)
+                .map_err(|e| sqlx::Error::Protocol(format!("Deserialization error: {}", e)))?;
+            Ok((app_id, limits))
+        })
+        .collect()
+}
+
+/// Get the traffic counted towards the daily limit of an installed app, if any has been counted.
+async fn get_bandwidth_usage<'e, E>(
+    executor: E,
+    app_id: &str,
+) -> sqlx::Result<Option<BandwidthUsage>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let row: Option<(Option<i64>, i64)> =
+        sqlx::query_as("SELECT day_start, day_bytes FROM BandwidthLimits WHERE app_id = ?")
+            .bind(app_id)
+            .fetch_optional(executor)
+            .await?;
+
+    Ok(row.and_then(|(day_start, day_bytes)| {
+        Some(BandwidthUsage {
+            day_start: Timestamp::from_micros(day_start?),
+            day_bytes: day_bytes as u64,
+        })
+    }))
+}
+
+/// Store the traffic counted towards the daily limit of an installed app.
+///
+/// Does nothing if the app has no bandwidth limits.
+async fn put_bandwidth_usage<'e, E>(
+    executor: E,
+    app_id: &str,
+    usage: &BandwidthUsage,
+) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query("UPDATE BandwidthLimits SET day_start = ?, day_bytes = ? WHERE app_id = ?")
+        .bind(usage.day_start.as_micros())
+        .bind(usage.day_bytes as i64)
+        .bind(app_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Delete the bandwidth limits of an installed app.
+async fn delete_bandwidth_limits<'e, E>(executor: E, app_id: &str) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM BandwidthLimits WHERE app_id = ?")
+        .bind(app_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+// ============================================================================
+// Signal Outbox Operations
+// ============================================================================
+
+type OutboxSignalRow = (i64, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, i64, i64, i64, i64);
+
+const OUTBOX_SIGNAL_COLUMNS: &str = "id, dna_hash, from_agent, to_agent, payload_blob, \
+     created_at, expires_at, attempts, next_attempt_at";
+
+fn outbox_signal_from_row(row: OutboxSignalRow) -> sqlx::Result<OutboxSignal> {
+    let (
+        id,
+        dna_hash,
+        from_agent,
+        to_agent,
+        payload_blob,
+        created_at,
+        expires_at,
+        attempts,
+        next_attempt_at,
+    ) = row;
+    let payload = holochain_serialized_bytes::decode(&payload_blob)
+        .map_err(|e| sqlx::Error::Protocol(format!("Deserialization error: {}", e)))?;
+    Ok(OutboxSignal {
+        id: id as u64,
+        dna_hash: DnaHash::from_raw_39(dna_hash),
+        from_agent: AgentPubKey::from_raw_39(from_agent),
+        to_agent: AgentPubKey::from_raw_39(to_agent),
+        payload,
+        created_at: Timestamp::from_micros(created_at),
+        expires_at: Timestamp::from_micros(expires_at),
+        attempts: attempts as u32,
+        next_attempt_at: Timestamp::from_micros(next_attempt_at),
+    })
+}
+
+/// Put a signal in the outbox.
+///
+/// The `id` of the signal is ignored, the ID assigned to it is returned.
+async fn insert_outbox_signal<'e, E>(executor: E, signal: &OutboxSignal) -> sqlx::Result<u64>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let payload_blob: Vec<u8> = holochain_serialized_bytes::encode(&signal.payload)
+        .map_err(|e| sqlx::Error::Protocol(format!("Serialization error: {}", e)))?;
+
+    let result = sqlx::query(
+        "INSERT INTO SignalOutbox
+         (dna_hash, from_agent, to_agent, payload_blob, created_at, expires_at, attempts, next_attempt_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(signal.dna_hash.get_raw_39())
+    .bind(signal.from_agent.get_raw_39())
+    .bind(signal.to_agent.get_raw_39())
+    .bind(payload_blob)
+    .bind(signal.created_at.as_micros())
+    .bind(signal.expires_at.as_micros())
+    .bind(signal.attempts as i64)
+    .bind(signal.next_attempt_at.as_micros())
+    .execute(executor)
+    .await?;
+    Ok(result.last_insert_rowid() as u64)
+}
+
+/// Get the signals in the outbox, optionally only those sent on one DNA.
+async fn get_outbox_signals<'e, E>(
+    executor: E,
+    dna_hash: Option<&DnaHash>,
+) -> sqlx::Result<Vec<OutboxSignal>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let rows: Vec<OutboxSignalRow> = match dna_hash {
+        Some(dna_hash) => {
+            sqlx::query_as(sqlx::AssertSqlSafe(format!(
+                "SELECT {OUTBOX_SIGNAL_COLUMNS} FROM SignalOutbox WHERE dna_hash = ? ORDER BY id"
+            )))
+            .bind(dna_hash.get_raw_39())
+            .fetch_all(executor)
+            .await?
+        }
+        None => {
+            sqlx::query_as(sqlx::AssertSqlSafe(format!(
+                "SELECT {OUTBOX_SIGNAL_COLUMNS} FROM SignalOutbox ORDER BY id"
+            )))
+            .fetch_all(executor)
+            .await?
+        }
+    };
+
+    rows.into_iter().map(outbox_signal_from_row).collect()
+}
+
+/// Get the signals in the outbox that are due to be retried at the given time,
+/// oldest first.
+///
+/// At most `limit` signals are returned, and at most `per_sender_limit` of them
+/// from the same agent on the same DNA, so that one sender with a full outbox
+/// can't hold up the signals of the others.
+async fn get_due_outbox_signals<'e, E>(
+    executor: E,
+    now: Timestamp,
+    limit: u32,
+    per_sender_limit: u32,
+) -> sqlx::Result<Vec<OutboxSignal>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let rows: Vec<OutboxSignalRow> = sqlx::query_as(sqlx::AssertSqlSafe(format!(
+        "SELECT {OUTBOX_SIGNAL_COLUMNS} FROM (
+             SELECT {OUTBOX_SIGNAL_COLUMNS},
+                 ROW_NUMBER() OVER (PARTITION BY dna_hash, from_agent ORDER BY id) AS sender_rank
+             FROM SignalOutbox
+             WHERE next_attempt_at <= ?
+         )
+         WHERE sender_rank <= ?
+         ORDER BY id
+         LIMIT ?"
+    )))
+    .bind(now.as_micros())
+    .bind(per_sender_limit as i64)
+    .bind(limit as i64)
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter().map(outbox_signal_from_row).collect()
+}
+
+/// Record a failed attempt to send a signal from the outbox, and when to retry.
+async fn reschedule_outbox_signal<'e, E>(
+    executor: E,
+    id: u64,
+    attempts: u32,
+    next_attempt_at: Timestamp,
+) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query("UPDATE SignalOutbox SET attempts = ?, next_attempt_at = ? WHERE id = ?")
+        .bind(attempts as i64)
+        .bind(next_attempt_at.as_micros())
+        .bind(id as i64)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Remove a signal from the outbox.
+async fn delete_outbox_signal<'e, E>(executor: E, id: u64) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM SignalOutbox WHERE id = ?")
+        .bind(id as i64)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Remove all signals that an agent sent on a DNA from the outbox.
+async fn delete_outbox_signals_from<'e, E>(
+    executor: E,
+    dna_hash: &DnaHash,
+    from_agent: &AgentPubKey,
+) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM SignalOutbox WHERE dna_hash = ? AND from_agent = ?")
+        .bind(dna_hash.get_raw_39())
+        .bind(from_agent.get_raw_39())
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
 // ============================================================================
 // Nonce Witnessing Operations
 // ============================================================================
@@ -650,6 +998,43 @@ impl DbRead<Conductor> {
     pub async fn get_all_blocks(&self) -> Result<Vec<Block>, sqlx::Error> {
         get_all_blocks(self.pool()).await
     }
+
+    /// Get the arc policies of all DNAs that have one.
+    pub async fn get_all_arc_policies(&self) -> sqlx::Result<Vec<(DnaHash, ArcPolicy)>> {
+        get_all_arc_policies(self.pool()).await
+    }
+
+    /// Get the bandwidth limits of all installed apps that have them.
+    pub async fn get_all_bandwidth_limits(
+        &self,
+    ) -> sqlx::Result<Vec<(InstalledAppId, BandwidthLimits)>> {
+        get_all_bandwidth_limits(self.pool()).await
+    }
+
+    /// Get the traffic counted towards the daily limit of an installed app, if any has been
+    /// counted.
+    pub async fn get_bandwidth_usage(&self, app_id: &str) -> sqlx::Result<Option<BandwidthUsage>> {
+        get_bandwidth_usage(self.pool(), app_id).await
+    }
+
+    /// Get the signals in the outbox, optionally only those sent on one DNA.
+    pub async fn get_outbox_signals(
+        &self,
+        dna_hash: Option<&DnaHash>,
+    ) -> sqlx::Result<Vec<OutboxSignal>> {
+        get_outbox_signals(self.pool(), dna_hash).await
+    }
+
+    /// Get the signals in the outbox that are due to be retried at the given time,
+    /// at most `limit` in total and `per_sender_limit` per sending agent and DNA.
+    pub async fn get_due_outbox_signals(
+        &self,
+        now: Timestamp,
+        limit: u32,
+        per_sender_limit: u32,
+    ) -> sqlx::Result<Vec<OutboxSignal>> {
+        get_due_outbox_signals(self.pool(), now, limit, per_sender_limit).await
+    }
 }
 
 impl DbWrite<Conductor> {
@@ -730,6 +1115,70 @@ impl DbWrite<Conductor> {
         delete_init_properties(self.pool(), app_id, role_name).await
     }
 
+    /// Set the arc policy for a DNA, replacing any previous policy.
+    pub async fn put_arc_policy(&self, dna_hash: &DnaHash, policy: &ArcPolicy) -> sqlx::Result<()> {
+        put_arc_policy(self.pool(), dna_hash, policy).await
+    }
+
+    /// Delete the arc policy for a DNA.
+    pub async fn delete_arc_policy(&self, dna_hash: &DnaHash) -> sqlx::Result<()> {
+        delete_arc_policy(self.pool(), dna_hash).await
+    }
+
+    /// Set the bandwidth limits of an installed app, replacing any previous limits.
+    pub async fn put_bandwidth_limits(
+        &self,
+        app_id: &str,
+        limits: &BandwidthLimits,
+    ) -> sqlx::Result<()> {
+        put_bandwidth_limits(self.pool(), app_id, limits).await
+    }
+
+    /// Store the traffic counted towards the daily limit of an installed app.
+    pub async fn put_bandwidth_usage(
+        &self,
+        app_id: &str,
+        usage: &BandwidthUsage,
+    ) -> sqlx::Result<()> {
+        put_bandwidth_usage(self.pool(), app_id, usage).await
+    }
+
+    /// Delete the bandwidth limits of an installed app.
+    pub async fn delete_bandwidth_limits(&self, app_id: &str) -> sqlx::Result<()> {
+        delete_bandwidth_limits(self.pool(), app_id).await
+    }
+
+    /// Put a signal in the outbox.
+    ///
+    /// The `id` of the signal is ignored, the ID assigned to it is returned.
+    pub async fn insert_outbox_signal(&self, signal: &OutboxSignal) -> sqlx::Result<u64> {
+        insert_outbox_signal(self.pool(), signal).await
+    }
+
+    /// Record a failed attempt to send a signal from the outbox, and when to retry.
+    pub async fn reschedule_outbox_signal(
+        &self,
+        id: u64,
+        attempts: u32,
+        next_attempt_at: Timestamp,
+    ) -> sqlx::Result<()> {
+        reschedule_outbox_signal(self.pool(), id, attempts, next_attempt_at).await
+    }
+
+    /// Remove a signal from the outbox.
+    pub async fn delete_outbox_signal(&self, id: u64) -> sqlx::Result<()> {
+        delete_outbox_signal(self.pool(), id).await
+    }
+
+    /// Remove all signals that an agent sent on a DNA from the outbox.
+    pub async fn delete_outbox_signals_from(
+        &self,
+        dna_hash: &DnaHash,
+        from_agent: &AgentPubKey,
+    ) -> sqlx::Result<()> {
+        delete_outbox_signals_from(self.pool(), dna_hash, from_agent).await
+    }
+
     /// Witness a nonce (check if it's fresh and record it)
     pub async fn witness_nonce(
         &self,
@@ -823,6 +1272,46 @@ impl TxRead<Conductor> {
     pub async fn get_all_blocks(&mut self) -> Result<Vec<Block>, sqlx::Error> {
         get_all_blocks(self.conn_mut()).await
     }
+
+    /// Get the arc policies of all DNAs that have one.
+    pub async fn get_all_arc_policies(&mut self) -> sqlx::Result<Vec<(DnaHash, ArcPolicy)>> {
+        get_all_arc_policies(self.conn_mut()).await
+    }
+
+    /// Get the bandwidth limits of all installed apps that have them.
+    pub async fn get_all_bandwidth_limits(
+        &mut self,
+    ) -> sqlx::Result<Vec<(InstalledAppId, BandwidthLimits)>> {
+        get_all_bandwidth_limits(self.conn_mut()).await
+    }
+
+    /// Get the traffic counted towards the daily limit of an installed app, if any has been
+    /// counted.
+    pub async fn get_bandwidth_usage(
+        &mut self,
+        app_id: &str,
+    ) -> sqlx::Result<Option<BandwidthUsage>> {
+        get_bandwidth_usage(self.conn_mut(), app_id).await
+    }
+
+    /// Get the signals in the outbox, optionally only those sent on one DNA.
+    pub async fn get_outbox_signals(
+        &mut self,
+        dna_hash: Option<&DnaHash>,
+    ) -> sqlx::Result<Vec<OutboxSignal>> {
+        get_outbox_signals(self.conn_mut(), dna_hash).await
+    }
+
+    /// Get the signals in the outbox that are due to be retried at the given time,
+    /// at most `limit` in total and `per_sender_limit` per sending agent and DNA.
+    pub async fn get_due_outbox_signals(
+        &mut self,
+        now: Timestamp,
+        limit: u32,
+        per_sender_limit: u32,
+    ) -> sqlx::Result<Vec<OutboxSignal>> {
+        get_due_outbox_signals(self.conn_mut(), now, limit, per_sender_limit).await
+    }
 }
 
 impl TxWrite<Conductor> {
@@ -907,6 +1396,74 @@ impl TxWrite<Conductor> {
         delete_init_properties(self.conn_mut(), app_id, role_name).await
     }
 
+    /// Set the arc policy for a DNA, replacing any previous policy.
+    pub async fn put_arc_policy(
+        &mut self,
+        dna_hash: &DnaHash,
+        policy: &ArcPolicy,
+    ) -> sqlx::Result<()> {
+        put_arc_policy(self.conn_mut(), dna_hash, policy).await
+    }
+
+    /// Delete the arc policy for a DNA.
+    pub async fn delete_arc_policy(&mut self, dna_hash: &DnaHash) -> sqlx::Result<()> {
+        delete_arc_policy(self.conn_mut(), dna_hash).await
+    }
+
+    /// Set the bandwidth limits of an installed app, replacing any previous limits.
+    pub async fn put_bandwidth_limits(
+        &mut self,
+        app_id: &str,
+        limits: &BandwidthLimits,
+    ) -> sqlx::Result<()> {
+        put_bandwidth_limits(self.conn_mut(), app_id, limits).await
+    }
+
+    /// Store the traffic counted towards the daily limit of an installed app.
+    pub async fn put_bandwidth_usage(
+        &mut self,
+        app_id: &str,
+        usage: &BandwidthUsage,
+    ) -> sqlx::Result<()> {
+        put_bandwidth_usage(self.conn_mut(), app_id, usage).await
+    }
+
+    /// Delete the bandwidth limits of an installed app.
+    pub async fn delete_bandwidth_limits(&mut self, app_id: &str) -> sqlx::Result<()> {
+        delete_bandwidth_limits(self.conn_mut(), app_id).await
+    }
+
+    /// Put a signal in the outbox.
+    ///
+    /// The `id` of the signal is ignored, the ID assigned to it is returned.
+    pub async fn insert_outbox_signal(&mut self, signal: &OutboxSignal) -> sqlx::Result<u64> {
+        insert_outbox_signal(self.conn_mut(), signal).await
+    }
+
+    /// Record a failed attempt to send a signal from the outbox, and when to retry.
+    pub async fn reschedule_outbox_signal(
+        &mut self,
+        id: u64,
+        attempts: u32,
+        next_attempt_at: Timestamp,
+    ) -> sqlx::Result<()> {
+        reschedule_outbox_signal(self.conn_mut(), id, attempts, next_attempt_at).await
+    }
+
+    /// Remove a signal from the outbox.
+    pub async fn delete_outbox_signal(&mut self, id: u64) -> sqlx::Result<()> {
+        delete_outbox_signal(self.conn_mut(), id).await
+    }
+
+    /// Remove all signals that an agent sent on a DNA from the outbox.
+    pub async fn delete_outbox_signals_from(
+        &mut self,
+        dna_hash: &DnaHash,
+        from_agent: &AgentPubKey,
+    ) -> sqlx::Result<()> {
+        delete_outbox_signals_from(self.conn_mut(), dna_hash, from_agent).await
+    }
+
     /// Witness a nonce (check if it's fresh and record it).
     pub async fn witness_nonce(
         &mut self,
@@ -1334,6 +1891,267 @@ mod tests {
     // Conductor tag operations
     // ========================================================================
 
+    #[tokio::test]
+    async fn arc_policy_roundtrip() {
+        let db = test_open_db(Conductor).await.unwrap();
+        let dna_1 = DnaHash::from_raw_36(vec![1u8; 36]);
+        let dna_2 = DnaHash::from_raw_36(vec![2u8; 36]);
+
+        assert!(db.as_ref().get_all_arc_policies().await.unwrap().is_empty());
+
+        db.put_arc_policy(&dna_1, &ArcPolicy::Full).await.unwrap();
+        db.put_arc_policy(&dna_2, &ArcPolicy::TargetArcFactor(0))
+            .await
+            .unwrap();
+        // Setting a policy again replaces it
+        db.put_arc_policy(&dna_1, &ArcPolicy::Empty).await.unwrap();
+
+        let mut policies = db.as_ref().get_all_arc_policies().await.unwrap();
+        policies.sort_by_key(|(dna_hash, _)| dna_hash.clone());
+        assert_eq!(
+            policies,
+            vec![
+                (dna_1.clone(), ArcPolicy::Empty),
+                (dna_2.clone(), ArcPolicy::TargetArcFactor(0))
+            ]
+        );
+
+        db.delete_arc_policy(&dna_1).await.unwrap();
+        assert_eq!(
+            db.as_ref().get_all_arc_policies().await.unwrap(),
+            vec![(dna_2, ArcPolicy::TargetArcFactor(0))]
+        );
+    }
+
+    #[tokio::test]
+    async fn bandwidth_limits_roundtrip() {
+        let db = test_open_db(Conductor).await.unwrap();
+        insert_test_app(&db, "app-1").await;
+        insert_test_app(&db, "app-2").await;
+        let daily = BandwidthLimits {
+            daily_bytes: Some(1_000_000),
+            bytes_per_second: None,
+        };
+        let rate = BandwidthLimits {
+            daily_bytes: None,
+            bytes_per_second: Some(1_000),
+        };
+
+        assert!(db
+            .as_ref()
+            .get_all_bandwidth_limits()
+            .await
+            .unwrap()
+            .is_empty());
+
+        db.put_bandwidth_limits("app-1", &rate).await.unwrap();
+        db.put_bandwidth_limits("app-2", &rate).await.unwrap();
+        // Setting limits again replaces them
+        db.put_bandwidth_limits("app-1", &daily).await.unwrap();
+
+        let mut limits = db.as_ref().get_all_bandwidth_limits().await.unwrap();
+        limits.sort_by_key(|(app_id, _)| app_id.clone());
+        assert_eq!(
+            limits,
+            vec![("app-1".to_string(), daily), ("app-2".to_string(), rate)]
+        );
+
+        db.delete_bandwidth_limits("app-1").await.unwrap();
+        assert_eq!(
+            db.as_ref().get_all_bandwidth_limits().await.unwrap(),
+            vec![("app-2".to_string(), rate)]
+        );
+
+        // Deleting the app cascades to its limits.
+        db.delete_installed_app("app-2").await.unwrap();
+        assert!(db
+            .as_ref()
+            .get_all_bandwidth_limits()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn bandwidth_usage_is_kept_until_the_limits_change() {
+        let db = test_open_db(Conductor).await.unwrap();
+        insert_test_app(&db, "app-1").await;
+        let limits = BandwidthLimits {
+            daily_bytes: Some(1_000_000),
+            bytes_per_second: None,
+        };
+        let usage = BandwidthUsage {
+            day_start: Timestamp::from_micros(1_000),
+            day_bytes: 500,
+        };
+
+        // Usage isn't stored for apps without limits.
+        db.put_bandwidth_usage("app-1", &usage).await.unwrap();
+        db.put_bandwidth_limits("app-1", &limits).await.unwrap();
+        assert_eq!(
+            db.as_ref().get_bandwidth_usage("app-1").await.unwrap(),
+            None
+        );
+
+        db.put_bandwidth_usage("app-1", &usage).await.unwrap();
+        assert_eq!(
+            db.as_ref().get_bandwidth_usage("app-1").await.unwrap(),
+            Some(usage)
+        );
+
+        // Setting the same limits again keeps the usage.
+        db.put_bandwidth_limits("app-1", &limits).await.unwrap();
+        assert_eq!(
+            db.as_ref().get_bandwidth_usage("app-1").await.unwrap(),
+            Some(usage)
+        );
+
+        // Setting different limits resets it.
+        db.put_bandwidth_limits(
+            "app-1",
+            &BandwidthLimits {
+                daily_bytes: Some(2_000_000),
+                bytes_per_second: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            db.as_ref().get_bandwidth_usage("app-1").await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn signal_outbox_roundtrip() {
+        let db = test_open_db(Conductor).await.unwrap();
+        let dna_1 = DnaHash::from_raw_36(vec![1u8; 36]);
+        let dna_2 = DnaHash::from_raw_36(vec![2u8; 36]);
+        let signal = |dna_hash: &DnaHash, next_attempt_at: i64| OutboxSignal {
+            id: 0,
+            dna_hash: dna_hash.clone(),
+            from_agent: AgentPubKey::from_raw_36(vec![3u8; 36]),
+            to_agent: AgentPubKey::from_raw_36(vec![4u8; 36]),
+            payload: OutboxSignalPayload::Direct {
+                signal: vec![1, 2, 3],
+            },
+            created_at: Timestamp::from_micros(0),
+            expires_at: Timestamp::from_micros(1_000),
+            attempts: 0,
+            next_attempt_at: Timestamp::from_micros(next_attempt_at),
+        };
+
+        let id_1 = db.insert_outbox_signal(&signal(&dna_1, 100)).await.unwrap();
+        let id_2 = db.insert_outbox_signal(&signal(&dna_2, 200)).await.unwrap();
+        assert_ne!(id_1, id_2);
+
+        let all = db.as_ref().get_outbox_signals(None).await.unwrap();
+        assert_eq!(
+            all,
+            vec![
+                OutboxSignal {
+                    id: id_1,
+                    ..signal(&dna_1, 100)
+                },
+                OutboxSignal {
+                    id: id_2,
+                    ..signal(&dna_2, 200)
+                }
+            ]
+        );
+        let for_dna_2 = db.as_ref().get_outbox_signals(Some(&dna_2)).await.unwrap();
+        assert_eq!(for_dna_2.len(), 1);
+        assert_eq!(for_dna_2[0].id, id_2);
+
+        // Only the first signal is due
+        let due = db
+            .as_ref()
+            .get_due_outbox_signals(Timestamp::from_micros(150), 10, 10)
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, id_1);
+
+        // Rescheduling the first signal makes it not due anymore
+        db.reschedule_outbox_signal(id_1, 1, Timestamp::from_micros(300))
+            .await
+            .unwrap();
+        let due = db
+            .as_ref()
+            .get_due_outbox_signals(Timestamp::from_micros(250), 10, 10)
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, id_2);
+
+        db.delete_outbox_signal(id_2).await.unwrap();
+        let all = db.as_ref().get_outbox_signals(None).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, id_1);
+        assert_eq!(all[0].attempts, 1);
+        assert_eq!(all[0].next_attempt_at, Timestamp::from_micros(300));
+    }
+
+    #[tokio::test]
+    async fn due_outbox_signals_are_limited_per_sender() {
+        let db = test_open_db(Conductor).await.unwrap();
+        let dna_hash = DnaHash::from_raw_36(vec![1u8; 36]);
+        let busy_agent = AgentPubKey::from_raw_36(vec![2u8; 36]);
+        let quiet_agent = AgentPubKey::from_raw_36(vec![3u8; 36]);
+        let signal = |from_agent: &AgentPubKey| OutboxSignal {
+            id: 0,
+            dna_hash: dna_hash.clone(),
+            from_agent: from_agent.clone(),
+            to_agent: AgentPubKey::from_raw_36(vec![4u8; 36]),
+            payload: OutboxSignalPayload::Direct {
+                signal: vec![1, 2, 3],
+            },
+            created_at: Timestamp::from_micros(0),
+            expires_at: Timestamp::from_micros(1_000),
+            attempts: 0,
+            next_attempt_at: Timestamp::from_micros(100),
+        };
+
+        let mut busy_ids = Vec::new();
+        for _ in 0..5 {
+            busy_ids.push(db.insert_outbox_signal(&signal(&busy_agent)).await.unwrap());
+        }
+        let quiet_id = db
+            .insert_outbox_signal(&signal(&quiet_agent))
+            .await
+            .unwrap();
+
+        // The busy agent's signals don't hold up the quiet agent's signal.
+        let due = db
+            .as_ref()
+            .get_due_outbox_signals(Timestamp::from_micros(100), 10, 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            due.iter().map(|s| s.id).collect::<Vec<_>>(),
+            vec![busy_ids[0], busy_ids[1], quiet_id]
+        );
+
+        // The total is limited too, oldest signals first.
+        let due = db
+            .as_ref()
+            .get_due_outbox_signals(Timestamp::from_micros(100), 3, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            due.iter().map(|s| s.id).collect::<Vec<_>>(),
+            busy_ids[..3].to_vec()
+        );
+
+        // Removing the busy agent's signals leaves the quiet agent's.
+        db.delete_outbox_signals_from(&dna_hash, &busy_agent)
+            .await
+            .unwrap();
+        let all = db.as_ref().get_outbox_signals(None).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, quiet_id);
+    }
+
     #[tokio::test]
     async fn conductor_tag_roundtrip() {
         let db = test_open_db(Conductor).await.unwrap();
@@ -1372,6 +2190,8 @@ mod tests {
             danger_bind_addr: None,
             allowed_origins_blob: None,
             installed_app_id: None,
+            max_concurrent_calls: Some(4),
+            max_calls_per_second: None,
         };
 
         db.put_app_interface(8080, "iface-1", &model).await.unwrap();
@@ -1380,6 +2200,8 @@ mod tests {
         assert_eq!(interfaces.len(), 1);
         assert_eq!(interfaces[0].port, 8080);
         assert_eq!(interfaces[0].id, "iface-1");
+        assert_eq!(interfaces[0].max_concurrent_calls, Some(4));
+        assert_eq!(interfaces[0].max_calls_per_second, None);
 
         // Delete it
         db.delete_app_interface(8080, "iface-1").await.unwrap();
@@ -1420,6 +2242,8 @@ mod tests {
             danger_bind_addr: None,
             allowed_origins_blob: None,
             installed_app_id: None,
+            max_concurrent_calls: None,
+            max_calls_per_second: None,
         };
         db.put_app_interface(9090, "", &iface).await.unwrap();
 
@@ -26,6 +26,7 @@ pub use inner::limbo_chain_op::LimboChainOpJoinedRow;
 pub use inner::limbo_warrant::InsertLimboWarrant;
 pub use inner::link::InsertLink;
 pub use inner::remove_countersigning_session::RemoveCountersigningSessionOutcome;
+pub use inner::retry_abandoned::AbandonedLimboOpFilter;
 pub use inner::scheduled_function::InsertScheduledFunction;
 pub use inner::updated_record::InsertUpdatedRecord;
 pub use inner::warrant::InsertWarrant;
@@ -1146,6 +1147,302 @@ mod tests {
         assert_eq!(integrated, 1);
     }
 
+    #[tokio::test]
+    async fn limbo_stats_split_stages_and_report_abandoned_ops() {
+        let db = test_open_db(dht_db_id()).await.unwrap();
+
+        // Three chain ops: pending sys (oldest), pending app, abandoned.
+        let mut op_hashes = Vec::new();
+        for seed in 1..=3u8 {
+            let action_hash = seed_action_for_op(&db, seed).await;
+            let op_hash = DhtOpHash::from_raw_36(vec![0x50 + seed; 36]);
+            db.insert_limbo_chain_op(InsertLimboChainOp {
+                op_hash: &op_hash,
+                action_hash: &action_hash,
+                op_type: 1,
+                basis_hash: &sample_basis(seed),
+                storage_center_loc: 0,
+                require_receipt: false,
+                when_received: Timestamp::from_micros(seed as i64 * 10),
+                serialized_size: 0,
+            })
+            .await
+            .unwrap();
+            op_hashes.push(op_hash);
+        }
+        db.set_limbo_chain_op_sys_validation_status(&op_hashes[1], Some(1))
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE LimboChainOp
+             SET abandoned_at = 500, sys_validation_attempts = 4
+             WHERE hash = ?",
+        )
+        .bind(op_hashes[2].get_raw_36())
+        .execute(db.pool())
+        .await
+        .unwrap();
+        assert_eq!(
+            db.set_limbo_chain_op_validation_error(&op_hashes[2], "dependency missing")
+                .await
+                .unwrap(),
+            1
+        );
+
+        // One warrant pending sys validation.
+        let warrant_hash = DhtOpHash::from_raw_36(vec![0x60; 36]);
+        db.insert_limbo_warrant(InsertLimboWarrant {
+            hash: &warrant_hash,
+            author: &AgentPubKey::from_raw_36(vec![1u8; 36]),
+            timestamp: Timestamp::from_micros(10),
+            warrantee: &AgentPubKey::from_raw_36(vec![2u8; 36]),
+            proof: &[0u8; 64],
+            signature: &[7u8; 64],
+            reason: None,
+            storage_center_loc: 0,
+            when_received: Timestamp::from_micros(40),
+            serialized_size: 0,
+        })
+        .await
+        .unwrap();
+
+        let counts = db.as_ref().limbo_stage_counts().await.unwrap();
+        assert_eq!(
+            counts,
+            crate::models::dht::LimboStageCountsRow {
+                pending_sys_validation: 2,
+                pending_app_validation: 1,
+                awaiting_integration: 0,
+                abandoned: 1,
+            }
+        );
+
+        let oldest = db
+            .as_ref()
+            .oldest_pending_limbo_op()
+            .await
+            .unwrap()
+            .expect("pending op");
+        assert_eq!(oldest.hash, op_hashes[0].get_raw_36().to_vec());
+        assert_eq!(oldest.op_type, Some(1));
+
+        let abandoned = db.as_ref().abandoned_limbo_ops(10).await.unwrap();
+        assert_eq!(abandoned.len(), 1);
+        assert_eq!(abandoned[0].hash, op_hashes[2].get_raw_36().to_vec());
+        assert_eq!(abandoned[0].abandoned_at, Some(500));
+        assert_eq!(
+            abandoned[0].last_validation_error.as_deref(),
+            Some("dependency missing")
+        );
+
+        db.set_limbo_warrant_validation_error(&warrant_hash, "warrant error")
+            .await
+            .unwrap();
+        let warrant_error: Option<String> =
+            sqlx::query_scalar("SELECT last_validation_error FROM LimboWarrantOp WHERE hash = ?")
+                .bind(warrant_hash.get_raw_36())
+                .fetch_one(db.pool())
+                .await
+                .unwrap();
+        assert_eq!(warrant_error.as_deref(), Some("warrant error"));
+
+        let sys = db
+            .as_ref()
+            .sys_validation_attempts_histogram()
+            .await
+            .unwrap();
+        let sys: Vec<_> = sys.into_iter().map(|b| (b.attempts, b.op_count)).collect();
+        // The abandoned op is not counted.
+        assert_eq!(sys, vec![(0, 2)]);
+        let app = db
+            .as_ref()
+            .app_validation_attempts_histogram()
+            .await
+            .unwrap();
+        let app: Vec<_> = app.into_iter().map(|b| (b.attempts, b.op_count)).collect();
+        assert_eq!(app, vec![(0, 1)]);
+    }
+
+    #[tokio::test]
+    async fn reset_abandoned_limbo_ops_applies_filter_and_clears_attempts() {
+        let db = test_open_db(dht_db_id()).await.unwrap();
+        // seed_action_for_op author is all-ones.
+        let author_a = AgentPubKey::from_raw_36(vec![1u8; 36]);
+        let author_b = AgentPubKey::from_raw_36(vec![0xB2; 36]);
+
+        let action_a = seed_action_for_op(&db, 1).await;
+        let action_b = seed_action_for_op_with_author(&db, 2, &author_b).await;
+        let op_a = DhtOpHash::from_raw_36(vec![0x71; 36]);
+        let op_b = DhtOpHash::from_raw_36(vec![0x72; 36]);
+        for (op_hash, action_hash, op_type) in [(&op_a, &action_a, 1), (&op_b, &action_b, 2)] {
+            db.insert_limbo_chain_op(InsertLimboChainOp {
+                op_hash,
+                action_hash,
+                op_type,
+                basis_hash: &sample_basis(op_type as u8),
+                storage_center_loc: 0,
+                require_receipt: false,
+                when_received: Timestamp::from_micros(10),
+                serialized_size: 0,
+            })
+            .await
+            .unwrap();
+        }
+        let warrant_op = DhtOpHash::from_raw_36(vec![0x73; 36]);
+        db.insert_limbo_warrant(InsertLimboWarrant {
+            hash: &warrant_op,
+            author: &author_a,
+            timestamp: Timestamp::from_micros(10),
+            warrantee: &author_b,
+            proof: &[0u8; 64],
+            signature: &[7u8; 64],
+            reason: None,
+            storage_center_loc: 0,
+            when_received: Timestamp::from_micros(10),
+            serialized_size: 0,
+        })
+        .await
+        .unwrap();
+        sqlx::query(
+            "UPDATE LimboChainOp
+             SET abandoned_at = 500, sys_validation_attempts = 4,
+                 last_validation_attempt = 400",
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+        sqlx::query(
+            "UPDATE LimboWarrantOp
+             SET abandoned_at = 500, sys_validation_attempts = 4,
+                 last_validation_attempt = 400",
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        // Author filter matches the chain op by author_b only; the warrant
+        // is authored by author_a.
+        let reset = db
+            .reset_abandoned_limbo_ops(AbandonedLimboOpFilter {
+                author: Some(&author_b),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(reset, 1);
+        let row = db.as_ref().get_limbo_chain_op(op_b).await.unwrap().unwrap();
+        assert_eq!(row.abandoned_at, None);
+        assert_eq!(row.sys_validation_attempts, 0);
+        assert_eq!(row.last_validation_attempt, None);
+
+        // An op type filter never matches warrants.
+        let reset = db
+            .reset_abandoned_limbo_ops(AbandonedLimboOpFilter {
+                op_type: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(reset, 1);
+
+        // A basis filter matches warrants by warrantee.
+        let warrantee_basis: AnyLinkableHash = author_b.clone().into();
+        let reset = db
+            .reset_abandoned_limbo_ops(AbandonedLimboOpFilter {
+                basis: Some(&warrantee_basis),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(reset, 1);
+
+        let counts = db.as_ref().limbo_stage_counts().await.unwrap();
+        assert_eq!(counts.abandoned, 0);
+        assert_eq!(counts.pending_sys_validation, 3);
+    }
+
+    #[tokio::test]
+    async fn validation_error_is_cleared_when_op_advances_a_stage() {
+        let db = test_open_db(dht_db_id()).await.unwrap();
+
+        let action_hash = seed_action_for_op(&db, 1).await;
+        let op_hash = DhtOpHash::from_raw_36(vec![0x81; 36]);
+        db.insert_limbo_chain_op(InsertLimboChainOp {
+            op_hash: &op_hash,
+            action_hash: &action_hash,
+            op_type: 1,
+            basis_hash: &sample_basis(1),
+            storage_center_loc: 0,
+            require_receipt: false,
+            when_received: Timestamp::from_micros(10),
+            serialized_size: 0,
+        })
+        .await
+        .unwrap();
+        let warrant_hash = DhtOpHash::from_raw_36(vec![0x82; 36]);
+        db.insert_limbo_warrant(InsertLimboWarrant {
+            hash: &warrant_hash,
+            author: &AgentPubKey::from_raw_36(vec![1u8; 36]),
+            timestamp: Timestamp::from_micros(10),
+            warrantee: &AgentPubKey::from_raw_36(vec![2u8; 36]),
+            proof: &[0u8; 64],
+            signature: &[7u8; 64],
+            reason: None,
+            storage_center_loc: 0,
+            when_received: Timestamp::from_micros(10),
+            serialized_size: 0,
+        })
+        .await
+        .unwrap();
+
+        async fn op_error(
+            db: &crate::handles::DbWrite<Dht>,
+            op_hash: &DhtOpHash,
+        ) -> Option<String> {
+            sqlx::query_scalar("SELECT last_validation_error FROM LimboChainOp WHERE hash = ?")
+                .bind(op_hash.get_raw_36())
+                .fetch_one(db.pool())
+                .await
+                .unwrap()
+        }
+
+        // Leaving the status unset doesn't advance the op.
+        db.set_limbo_chain_op_validation_error(&op_hash, "sys error")
+            .await
+            .unwrap();
+        db.set_limbo_chain_op_sys_validation_status(&op_hash, None)
+            .await
+            .unwrap();
+        assert_eq!(op_error(&db, &op_hash).await.as_deref(), Some("sys error"));
+
+        db.set_limbo_chain_op_sys_validation_status(&op_hash, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(op_error(&db, &op_hash).await, None);
+
+        db.set_limbo_chain_op_validation_error(&op_hash, "app error")
+            .await
+            .unwrap();
+        db.set_limbo_chain_op_app_validation_status(&op_hash, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(op_error(&db, &op_hash).await, None);
+
+        db.set_limbo_warrant_validation_error(&warrant_hash, "warrant error")
+            .await
+            .unwrap();
+        db.set_limbo_warrant_sys_validation_status(&warrant_hash, Some(1))
+            .await
+            .unwrap();
+        let warrant_error: Option<String> =
+            sqlx::query_scalar("SELECT last_validation_error FROM LimboWarrantOp WHERE hash = ?")
+                .bind(warrant_hash.get_raw_36())
+                .fetch_one(db.pool())
+                .await
+                .unwrap();
+        assert_eq!(warrant_error, None);
+    }
+
     #[tokio::test]
     async fn count_valid_integrated_ops_counts_only_locally_validated_accepted_chain_ops() {
         let db = test_open_db(dht_db_id()).await.unwrap();
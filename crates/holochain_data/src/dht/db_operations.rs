@@ -17,11 +17,13 @@ mod deleted_link;
 mod deleted_record;
 mod entry;
 mod limbo_chain_op;
+mod limbo_stats;
 mod limbo_warrant;
 mod link;
 mod move_to_limbo;
 mod op_exists;
 mod remove_countersigning_session;
+mod retry_abandoned;
 mod scheduled_function;
 mod slice_hash;
 mod sync_queries;
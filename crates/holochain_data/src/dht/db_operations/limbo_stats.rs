@@ -0,0 +1,42 @@
+//! `DbRead<Dht>` API for the validation-pipeline statistics.
+
+use super::super::inner::limbo_stats;
+use crate::handles::DbRead;
+use crate::kind::Dht;
+use crate::models::dht::{LimboOpSummaryRow, LimboStageCountsRow, ValidationAttemptsBucketRow};
+
+impl DbRead<Dht> {
+    /// Per-stage op counts across `LimboChainOp` and `LimboWarrantOp`.
+    pub async fn limbo_stage_counts(&self) -> sqlx::Result<LimboStageCountsRow> {
+        let mut conn = self.timed_conn().await?;
+        limbo_stats::limbo_stage_counts(&mut *conn).await
+    }
+
+    /// The longest-waiting op still pending sys- or app-validation.
+    pub async fn oldest_pending_limbo_op(&self) -> sqlx::Result<Option<LimboOpSummaryRow>> {
+        let mut conn = self.timed_conn().await?;
+        limbo_stats::oldest_pending_limbo_op(&mut *conn).await
+    }
+
+    /// Up to `limit` abandoned limbo ops, most recently abandoned first.
+    pub async fn abandoned_limbo_ops(&self, limit: u32) -> sqlx::Result<Vec<LimboOpSummaryRow>> {
+        let mut conn = self.timed_conn().await?;
+        limbo_stats::abandoned_limbo_ops(&mut *conn, limit).await
+    }
+
+    /// Histogram of sys-validation attempts over ops awaiting sys validation.
+    pub async fn sys_validation_attempts_histogram(
+        &self,
+    ) -> sqlx::Result<Vec<ValidationAttemptsBucketRow>> {
+        let mut conn = self.timed_conn().await?;
+        limbo_stats::sys_validation_attempts_histogram(&mut *conn).await
+    }
+
+    /// Histogram of app-validation attempts over ops awaiting app validation.
+    pub async fn app_validation_attempts_histogram(
+        &self,
+    ) -> sqlx::Result<Vec<ValidationAttemptsBucketRow>> {
+        let mut conn = self.timed_conn().await?;
+        limbo_stats::app_validation_attempts_histogram(&mut *conn).await
+    }
+}
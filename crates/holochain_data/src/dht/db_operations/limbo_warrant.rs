@@ -33,6 +33,16 @@ impl DbWrite<Dht> {
         limbo_warrant::set_sys_validation_status(self.pool(), hash, status).await
     }
 
+    /// Record the error of the last failed validation attempt of the given
+    /// warrant. Returns the number of rows updated.
+    pub async fn set_limbo_warrant_validation_error(
+        &self,
+        hash: &DhtOpHash,
+        error: &str,
+    ) -> sqlx::Result<u64> {
+        limbo_warrant::set_validation_error(self.pool(), hash, error).await
+    }
+
     /// Atomically promote a limbo warrant: move metadata from `LimboWarrantOp`
     /// to `WarrantOp`, stamping `when_integrated`. `Warrant` content stays put.
     ///
@@ -0,0 +1,19 @@
+//! `DbWrite<Dht>` API for re-queueing abandoned limbo ops.
+
+use super::super::inner::retry_abandoned::{self, AbandonedLimboOpFilter};
+use crate::handles::DbWrite;
+use crate::kind::Dht;
+
+impl DbWrite<Dht> {
+    /// Reset the abandoned limbo chain ops and warrants matching `filter`,
+    /// wrapped in a transaction. Returns the number of ops reset.
+    pub async fn reset_abandoned_limbo_ops(
+        &self,
+        filter: AbandonedLimboOpFilter<'_>,
+    ) -> sqlx::Result<u64> {
+        let mut tx = self.begin().await?;
+        let reset = retry_abandoned::reset_abandoned_limbo_ops(tx.conn_mut(), filter).await?;
+        tx.commit().await?;
+        Ok(reset)
+    }
+}
@@ -17,11 +17,13 @@ pub(crate) mod deleted_link;
 pub(crate) mod deleted_record;
 pub(crate) mod entry;
 pub(crate) mod limbo_chain_op;
+pub(crate) mod limbo_stats;
 pub(crate) mod limbo_warrant;
 pub(crate) mod link;
 pub(crate) mod move_to_limbo;
 pub(crate) mod op_exists;
 pub(crate) mod remove_countersigning_session;
+pub(crate) mod retry_abandoned;
 pub(crate) mod scheduled_function;
 pub(crate) mod slice_hash;
 pub(crate) mod sync_queries;
@@ -260,6 +260,10 @@ where
     .await
 }
 
+/// Set the sys validation status of an op that has none yet.
+///
+/// Setting a status moves the op on to the next stage, so the error of its
+/// last failed validation attempt is cleared.
 pub(crate) async fn set_sys_validation_status<'e, E>(
     executor: E,
     op_hash: &DhtOpHash,
@@ -269,8 +273,9 @@ where
     E: Executor<'e, Database = Sqlite>,
 {
     let result = sqlx::query(
-        "UPDATE LimboChainOp SET sys_validation_status = ?
-         WHERE hash = ? AND sys_validation_status IS NULL",
+        "UPDATE LimboChainOp SET sys_validation_status = ?1,
+             last_validation_error = CASE WHEN ?1 IS NULL THEN last_validation_error END
+         WHERE hash = ?2 AND sys_validation_status IS NULL",
     )
     .bind(status)
     .bind(op_hash.get_raw_36())
@@ -279,6 +284,27 @@ where
     Ok(result.rows_affected())
 }
 
+/// Record the error of the last failed validation attempt of an op.
+pub(crate) async fn set_validation_error<'e, E>(
+    executor: E,
+    op_hash: &DhtOpHash,
+    error: &str,
+) -> sqlx::Result<u64>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let result = sqlx::query("UPDATE LimboChainOp SET last_validation_error = ? WHERE hash = ?")
+        .bind(error)
+        .bind(op_hash.get_raw_36())
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Set the app validation status of a sys validated op that has none yet.
+///
+/// Setting a status moves the op on to integration, so the error of its last
+/// failed validation attempt is cleared.
 pub(crate) async fn set_app_validation_status<'e, E>(
     executor: E,
     op_hash: &DhtOpHash,
@@ -288,8 +314,9 @@ where
     E: Executor<'e, Database = Sqlite>,
 {
     let result = sqlx::query(
-        "UPDATE LimboChainOp SET app_validation_status = ?
-         WHERE hash = ? AND sys_validation_status IS NOT NULL AND app_validation_status IS NULL",
+        "UPDATE LimboChainOp SET app_validation_status = ?1,
+             last_validation_error = CASE WHEN ?1 IS NULL THEN last_validation_error END
+         WHERE hash = ?2 AND sys_validation_status IS NOT NULL AND app_validation_status IS NULL",
     )
     .bind(status)
     .bind(op_hash.get_raw_36())
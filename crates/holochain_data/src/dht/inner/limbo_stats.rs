@@ -0,0 +1,160 @@
+//! Validation-pipeline statistics spanning `LimboChainOp` and `LimboWarrantOp`.
+//!
+//! These reads back operator introspection of the sys-validation,
+//! app-validation and integration queues. Chain ops and warrants are
+//! projected onto a common [`LimboOpSummaryRow`] column list (see
+//! [`LIMBO_OP_SUMMARY_UNION`]) so they can be ordered and filtered together.
+
+use super::sync_queries::LIMBO_CHAIN_OP_READY_PRED;
+use crate::models::dht::{LimboOpSummaryRow, LimboStageCountsRow, ValidationAttemptsBucketRow};
+use sqlx::{Executor, Sqlite};
+
+/// `LimboChainOp` and `LimboWarrantOp` rows projected onto the
+/// [`LimboOpSummaryRow`] columns. Warrants have no op type and no
+/// app-validation stage, so those columns are `NULL` / `0`.
+pub(crate) const LIMBO_OP_SUMMARY_UNION: &str = "
+    SELECT l.hash AS hash, l.op_type AS op_type, l.basis_hash AS basis_hash,
+           a.author AS author, l.sys_validation_status AS sys_validation_status,
+           l.app_validation_status AS app_validation_status,
+           l.abandoned_at AS abandoned_at, l.when_received AS when_received,
+           l.sys_validation_attempts AS sys_validation_attempts,
+           l.app_validation_attempts AS app_validation_attempts,
+           l.last_validation_attempt AS last_validation_attempt,
+           l.last_validation_error AS last_validation_error
+    FROM LimboChainOp l
+    JOIN Action a ON a.hash = l.action_hash
+    UNION ALL
+    SELECT w.hash, NULL, w.warrantee, w.author, op.sys_validation_status, NULL,
+           op.abandoned_at, op.when_received, op.sys_validation_attempts, 0,
+           op.last_validation_attempt, op.last_validation_error
+    FROM LimboWarrantOp op
+    JOIN Warrant w ON w.hash = op.hash";
+
+/// Matches [`LIMBO_OP_SUMMARY_UNION`] rows that are still waiting on a
+/// validation workflow: not abandoned, and either sys-validation is pending
+/// or (for chain ops only) sys-validation accepted the op and app-validation
+/// is pending.
+const LIMBO_OP_PENDING_PRED: &str = "abandoned_at IS NULL
+    AND (sys_validation_status IS NULL
+         OR (op_type IS NOT NULL AND sys_validation_status = 1
+             AND app_validation_status IS NULL))";
+
+/// Per-stage counts across both limbo tables. See [`LimboStageCountsRow`].
+pub(crate) async fn limbo_stage_counts<'e, E>(executor: E) -> sqlx::Result<LimboStageCountsRow>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let sql = format!(
+        "SELECT
+            (
+                (SELECT COUNT(*) FROM LimboChainOp
+                 WHERE abandoned_at IS NULL AND sys_validation_status IS NULL)
+                +
+                (SELECT COUNT(*) FROM LimboWarrantOp
+                 WHERE abandoned_at IS NULL AND sys_validation_status IS NULL)
+            ) AS pending_sys_validation,
+            (SELECT COUNT(*) FROM LimboChainOp
+             WHERE abandoned_at IS NULL AND sys_validation_status = 1
+               AND app_validation_status IS NULL) AS pending_app_validation,
+            (
+                (SELECT COUNT(*) FROM LimboChainOp WHERE {ready})
+                +
+                (SELECT COUNT(*) FROM LimboWarrantOp WHERE sys_validation_status IN (1, 2))
+            ) AS awaiting_integration,
+            (
+                (SELECT COUNT(*) FROM LimboChainOp WHERE abandoned_at IS NOT NULL)
+                +
+                (SELECT COUNT(*) FROM LimboWarrantOp WHERE abandoned_at IS NOT NULL)
+            ) AS abandoned",
+        ready = LIMBO_CHAIN_OP_READY_PRED,
+    );
+    // SQL is assembled from a compile-time-constant predicate (no user
+    // input), so asserting it is safe.
+    sqlx::query_as(sqlx::AssertSqlSafe(sql))
+        .fetch_one(executor)
+        .await
+}
+
+/// The longest-waiting op that is still pending sys- or app-validation, if any.
+pub(crate) async fn oldest_pending_limbo_op<'e, E>(
+    executor: E,
+) -> sqlx::Result<Option<LimboOpSummaryRow>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let sql = format!(
+        "SELECT * FROM ({LIMBO_OP_SUMMARY_UNION})
+         WHERE {LIMBO_OP_PENDING_PRED}
+         ORDER BY when_received, hash
+         LIMIT 1"
+    );
+    // Compile-time-constant SQL, see `limbo_stage_counts`.
+    sqlx::query_as(sqlx::AssertSqlSafe(sql))
+        .fetch_optional(executor)
+        .await
+}
+
+/// Abandoned ops across both limbo tables, most recently abandoned first.
+pub(crate) async fn abandoned_limbo_ops<'e, E>(
+    executor: E,
+    limit: u32,
+) -> sqlx::Result<Vec<LimboOpSummaryRow>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let sql = format!(
+        "SELECT * FROM ({LIMBO_OP_SUMMARY_UNION})
+         WHERE abandoned_at IS NOT NULL
+         ORDER BY abandoned_at DESC, hash
+         LIMIT ?"
+    );
+    // Compile-time-constant SQL, see `limbo_stage_counts`.
+    sqlx::query_as(sqlx::AssertSqlSafe(sql))
+        .bind(limit as i64)
+        .fetch_all(executor)
+        .await
+}
+
+/// Histogram of `sys_validation_attempts` over chain ops and warrants still
+/// awaiting system validation, ordered by attempts. Abandoned ops are not
+/// counted.
+pub(crate) async fn sys_validation_attempts_histogram<'e, E>(
+    executor: E,
+) -> sqlx::Result<Vec<ValidationAttemptsBucketRow>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query_as(
+        "SELECT attempts, COUNT(*) AS op_count FROM (
+             SELECT sys_validation_attempts AS attempts FROM LimboChainOp
+             WHERE sys_validation_status IS NULL AND abandoned_at IS NULL
+             UNION ALL
+             SELECT sys_validation_attempts AS attempts FROM LimboWarrantOp
+             WHERE sys_validation_status IS NULL AND abandoned_at IS NULL
+         )
+         GROUP BY attempts
+         ORDER BY attempts",
+    )
+    .fetch_all(executor)
+    .await
+}
+
+/// Histogram of `app_validation_attempts` over chain ops awaiting app
+/// validation, ordered by attempts. Abandoned ops are not counted.
+pub(crate) async fn app_validation_attempts_histogram<'e, E>(
+    executor: E,
+) -> sqlx::Result<Vec<ValidationAttemptsBucketRow>>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query_as(
+        "SELECT app_validation_attempts AS attempts, COUNT(*) AS op_count
+         FROM LimboChainOp
+         WHERE sys_validation_status = 1 AND app_validation_status IS NULL
+           AND abandoned_at IS NULL
+         GROUP BY app_validation_attempts
+         ORDER BY attempts",
+    )
+    .fetch_all(executor)
+    .await
+}
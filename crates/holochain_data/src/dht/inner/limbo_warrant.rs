@@ -139,6 +139,10 @@ where
     .await
 }
 
+/// Set the sys validation status of a warrant that has none yet.
+///
+/// Setting a status moves the warrant on to integration, so the error of its
+/// last failed validation attempt is cleared.
 pub(crate) async fn set_sys_validation_status<'e, E>(
     executor: E,
     hash: &DhtOpHash,
@@ -148,8 +152,9 @@ where
     E: Executor<'e, Database = Sqlite>,
 {
     let result = sqlx::query(
-        "UPDATE LimboWarrantOp SET sys_validation_status = ?
-         WHERE hash = ? AND sys_validation_status IS NULL",
+        "UPDATE LimboWarrantOp SET sys_validation_status = ?1,
+             last_validation_error = CASE WHEN ?1 IS NULL THEN last_validation_error END
+         WHERE hash = ?2 AND sys_validation_status IS NULL",
     )
     .bind(status)
     .bind(hash.get_raw_36())
@@ -158,6 +163,23 @@ where
     Ok(result.rows_affected())
 }
 
+/// Record the error of the last failed validation attempt of a warrant.
+pub(crate) async fn set_validation_error<'e, E>(
+    executor: E,
+    hash: &DhtOpHash,
+    error: &str,
+) -> sqlx::Result<u64>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let result = sqlx::query("UPDATE LimboWarrantOp SET last_validation_error = ? WHERE hash = ?")
+        .bind(error)
+        .bind(hash.get_raw_36())
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
+
 /// Delete a warrant in limbo: removes both the `LimboWarrantOp` row and the
 /// underlying `Warrant` content (the content was inserted alongside the
 /// limbo row and isn't useful without it).
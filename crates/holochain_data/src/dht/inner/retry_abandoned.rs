@@ -0,0 +1,72 @@
+//! Reset abandoned `LimboChainOp` / `LimboWarrantOp` rows so that the
+//! validation workflows pick them up again.
+
+use holo_hash::{AgentPubKey, AnyLinkableHash};
+use sqlx::SqliteConnection;
+
+/// Selects which abandoned limbo ops to reset. Unset fields match every op.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AbandonedLimboOpFilter<'a> {
+    /// `ChainOpType` discriminant. Warrants have no op type, so setting this
+    /// restricts the reset to chain ops.
+    pub op_type: Option<i64>,
+    /// Author of the action, or of the warrant.
+    pub author: Option<&'a AgentPubKey>,
+    /// DHT basis of the op. For warrants this is matched against the
+    /// warrantee.
+    pub basis: Option<&'a AnyLinkableHash>,
+}
+
+/// Clear `abandoned_at`, the attempt counters, `last_validation_attempt` and
+/// `last_validation_error` on every abandoned limbo op matching `filter`. Validation statuses are
+/// left untouched, so each op re-enters the stage it was abandoned in.
+///
+/// Returns the number of ops reset. **Caller must wrap in a transaction**
+/// so that chain ops and warrants are reset together.
+pub(crate) async fn reset_abandoned_limbo_ops(
+    conn: &mut SqliteConnection,
+    filter: AbandonedLimboOpFilter<'_>,
+) -> sqlx::Result<u64> {
+    let author = filter.author.map(|a| a.get_raw_36());
+    let basis = filter.basis.map(|b| b.get_raw_36());
+
+    let chain_ops = sqlx::query(
+        "UPDATE LimboChainOp
+         SET abandoned_at = NULL, sys_validation_attempts = 0,
+             app_validation_attempts = 0, last_validation_attempt = NULL,
+             last_validation_error = NULL
+         WHERE abandoned_at IS NOT NULL
+           AND (?1 IS NULL OR op_type = ?1)
+           AND (?2 IS NULL OR action_hash IN (SELECT hash FROM Action WHERE author = ?2))
+           AND (?3 IS NULL OR basis_hash = ?3)",
+    )
+    .bind(filter.op_type)
+    .bind(author)
+    .bind(basis)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    if filter.op_type.is_some() {
+        return Ok(chain_ops);
+    }
+
+    let warrants = sqlx::query(
+        "UPDATE LimboWarrantOp
+         SET abandoned_at = NULL, sys_validation_attempts = 0,
+             last_validation_attempt = NULL, last_validation_error = NULL
+         WHERE abandoned_at IS NOT NULL
+           AND hash IN (
+               SELECT hash FROM Warrant
+               WHERE (?1 IS NULL OR author = ?1)
+                 AND (?2 IS NULL OR warrantee = ?2)
+           )",
+    )
+    .bind(author)
+    .bind(basis)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    Ok(chain_ops + warrants)
+}
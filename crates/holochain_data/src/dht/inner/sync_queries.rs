@@ -819,7 +819,7 @@ where
 /// `inner/limbo_chain_op.rs`): a row is ready once sys-validation rejected it
 /// outright, or sys-validation accepted it and app-validation reached a
 /// terminal state.
-const LIMBO_CHAIN_OP_READY_PRED: &str =
+pub(crate) const LIMBO_CHAIN_OP_READY_PRED: &str =
     "sys_validation_status = 2 OR (sys_validation_status = 1 AND app_validation_status IN (1, 2))";
 
 /// Negation of [`LIMBO_CHAIN_OP_READY_PRED`]. The ready predicate evaluates
@@ -26,6 +26,16 @@ impl TxWrite<Dht> {
         limbo_chain_op::set_sys_validation_status(self.conn_mut(), op_hash, status).await
     }
 
+    /// Record the error of the last failed validation attempt of the given op.
+    /// Returns the number of rows updated.
+    pub async fn set_limbo_chain_op_validation_error(
+        &mut self,
+        op_hash: &DhtOpHash,
+        error: &str,
+    ) -> sqlx::Result<u64> {
+        limbo_chain_op::set_validation_error(self.conn_mut(), op_hash, error).await
+    }
+
     /// Set the app-validation status for the given op. Returns the number of rows updated.
     pub async fn set_limbo_chain_op_app_validation_status(
         &mut self,
@@ -25,6 +25,16 @@ impl TxWrite<Dht> {
         limbo_warrant::set_sys_validation_status(self.conn_mut(), hash, status).await
     }
 
+    /// Record the error of the last failed validation attempt of the given
+    /// warrant. Returns the number of rows updated.
+    pub async fn set_limbo_warrant_validation_error(
+        &mut self,
+        hash: &DhtOpHash,
+        error: &str,
+    ) -> sqlx::Result<u64> {
+        limbo_warrant::set_validation_error(self.conn_mut(), hash, error).await
+    }
+
     /// Atomically promote a `LimboWarrant` row to the `Warrant` table using
     /// the current transaction. `when_integrated` is stamped on the new
     /// `Warrant` row.
@@ -123,6 +123,27 @@ impl<I: DatabaseIdentifier> DbWrite<I> {
             write_txn_metric: self.0.write_txn_metric.clone(),
         })
     }
+
+    /// Copy every committed transaction from the write-ahead log into the
+    /// database file and truncate the log.
+    ///
+    /// Afterwards the database file alone holds all the committed data, so
+    /// it can be copied without its `-wal` file.
+    ///
+    /// Fails if a reader or writer kept the log from being checkpointed.
+    pub async fn checkpoint(&self) -> sqlx::Result<()> {
+        let mut conn = self.0.timed_conn().await?;
+        let (busy, _, _): (i64, i64, i64) = sqlx::query_as("PRAGMA wal_checkpoint(TRUNCATE)")
+            .fetch_one(&mut *conn)
+            .await?;
+        if busy != 0 {
+            return Err(sqlx::Error::Protocol(format!(
+                "Database {} is busy, its write-ahead log could not be checkpointed",
+                self.identifier().database_id()
+            )));
+        }
+        Ok(())
+    }
 }
 
 /// A read-only database transaction handle.
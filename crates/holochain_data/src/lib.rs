@@ -385,4 +385,32 @@ mod tests {
         assert!(!tables.contains(&"Conductor".to_string()));
         assert!(!tables.contains(&"Wasm".to_string()));
     }
+
+    #[tokio::test]
+    async fn checkpoint_empties_write_ahead_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(dir.path(), TestWasmDbId, HolochainDataConfig::default())
+            .await
+            .expect("Failed to open test database");
+
+        sqlx::query("CREATE TABLE Checkpointed (value INTEGER)")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO Checkpointed (value) VALUES (1)")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        let wal = dir.path().join("test_wasm_db-wal");
+        assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+
+        db.checkpoint().await.unwrap();
+
+        assert_eq!(std::fs::metadata(&wal).unwrap().len(), 0);
+        let value: i64 = sqlx::query_scalar("SELECT value FROM Checkpointed")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+    }
 }
@@ -163,6 +163,8 @@ pub struct AppInterfaceModel {
     pub danger_bind_addr: Option<String>,
     pub allowed_origins_blob: Option<Vec<u8>>,
     pub installed_app_id: Option<String>,
+    pub max_concurrent_calls: Option<i64>,
+    pub max_calls_per_second: Option<i64>,
 }
 
 impl AppInterfaceModel {
@@ -189,8 +191,13 @@ impl AppInterfaceModel {
                     danger_bind_addr: danger_bind_addr.clone(),
                     allowed_origins_blob: Some(allowed_origins_blob),
                     installed_app_id,
+                    max_concurrent_calls: None,
+                    max_calls_per_second: None,
                 })
             }
+            InterfaceDriver::Http { .. } => {
+                Err("HTTP interfaces can't be used as app interfaces".to_string())
+            }
         }
     }
 
@@ -169,6 +169,65 @@ pub struct LimboChainOpRow {
     pub serialized_size: i64,
 }
 
+/// Per-stage op counts across `LimboChainOp` and `LimboWarrantOp`.
+///
+/// Abandoned ops are counted only in `abandoned`, never in a pending stage.
+#[derive(Debug, Clone, Default, sqlx::FromRow, PartialEq, Eq)]
+pub struct LimboStageCountsRow {
+    /// Chain ops and warrants awaiting system validation.
+    pub pending_sys_validation: i64,
+    /// Chain ops that passed system validation and await app validation.
+    pub pending_app_validation: i64,
+    /// Chain ops and warrants with a terminal validation outcome awaiting
+    /// integration.
+    pub awaiting_integration: i64,
+    /// Chain ops and warrants whose validation was abandoned.
+    pub abandoned: i64,
+}
+
+/// Validation bookkeeping for one op in `LimboChainOp` or `LimboWarrantOp`.
+///
+/// Chain ops and warrants are selected through the same column list so that
+/// both can be ordered and filtered together.
+#[derive(Debug, Clone, sqlx::FromRow, PartialEq, Eq)]
+pub struct LimboOpSummaryRow {
+    /// DHT op hash (chain-op hash or warrant hash).
+    pub hash: Vec<u8>,
+    /// Encoded [`ChainOpType`](holochain_zome_types::op::ChainOpType);
+    /// `NULL` for warrants.
+    pub op_type: Option<i64>,
+    /// DHT basis hash; the warrantee for warrants.
+    pub basis_hash: Vec<u8>,
+    /// Agent pub key of the action or warrant author.
+    pub author: Vec<u8>,
+    /// Encoded [`RecordValidity`]; `NULL` represents pending.
+    pub sys_validation_status: Option<i64>,
+    /// Encoded [`RecordValidity`]; `NULL` represents pending, and is always
+    /// `NULL` for warrants.
+    pub app_validation_status: Option<i64>,
+    /// Microsecond timestamp at which validation was abandoned; `NULL` if not abandoned.
+    pub abandoned_at: Option<i64>,
+    /// Microsecond timestamp at which the op was received.
+    pub when_received: i64,
+    /// Number of system-validation attempts so far.
+    pub sys_validation_attempts: i64,
+    /// Number of app-validation attempts so far; always `0` for warrants.
+    pub app_validation_attempts: i64,
+    /// Microsecond timestamp of the last validation attempt.
+    pub last_validation_attempt: Option<i64>,
+    /// Error of the last failed validation attempt.
+    pub last_validation_error: Option<String>,
+}
+
+/// One bucket of a validation-attempts histogram.
+#[derive(Debug, Clone, sqlx::FromRow, PartialEq, Eq)]
+pub struct ValidationAttemptsBucketRow {
+    /// Number of attempts made.
+    pub attempts: i64,
+    /// Number of ops that have made exactly `attempts` attempts.
+    pub op_count: i64,
+}
+
 /// Joined `Warrant` + `LimboWarrantOp` row (a warrant awaiting validation,
 /// with op metadata). The split lives on disk; callers see content and op
 /// fields bundled together for ergonomics.
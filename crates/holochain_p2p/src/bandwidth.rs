@@ -0,0 +1,1280 @@
+//! Accounting and limits for the network traffic of each space.
+//!
+//! All outgoing traffic goes through a [`BandwidthTransport`], which wraps the
+//! Kitsune2 transport and counts the bytes sent for each space and kind of
+//! traffic. Incoming module messages are counted by the module handlers it
+//! registers, and incoming holochain_p2p messages are counted by the actor.
+//!
+//! Apps can be given [`BandwidthLimits`]. The gossip and fetch traffic of the
+//! spaces that an app is in counts towards its limits. While a limit is
+//! exceeded, the gossip module of those spaces is stopped by the
+//! [`BandwidthGossip`] that wraps it, so that no gossip rounds are started.
+//! The gossip module is started again once the limit period is over. The
+//! gossip module is never started in the spaces that have gossip disabled in
+//! their [`HcGossipModConfig`].
+//!
+//! An app with a rate limit has a [`TokenBucket`] that the gossip messages of
+//! all of its spaces are sent through, so that rounds that are already running
+//! keep to the rate of the app however many spaces its cells are in. Fetches
+//! are never held back, because a publish only sends op IDs and the receiver
+//! fetches the published ops.
+
+use crate::metrics::p2p_bandwidth_metric;
+use bytes::Bytes;
+use holo_hash::DnaHash;
+use holochain_timestamp::Timestamp;
+use holochain_types::network::{BandwidthLimits, BandwidthUsage, SpaceBandwidth};
+use holochain_types::prelude::InstalledAppId;
+use kitsune2_api::{
+    ApiTransportStats, BoxFut, Builder, Config, DynFetch, DynGossip, DynGossipFactory,
+    DynLocalAgentStore, DynOpStore, DynPeerMetaStore, DynPeerStore, DynTransport,
+    DynTransportFactory, DynTxHandler, DynTxModuleHandler, DynTxSpaceHandler, Gossip,
+    GossipFactory, GossipStateSummary, GossipStateSummaryRequest, K2Result, SpaceId, StoredOp,
+    Transport, TransportFactory, TxBaseHandler, TxModuleHandler, Url,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tokio::task::AbortHandle;
+
+/// The period of a daily limit.
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The window over which the rate of traffic is averaged for a rate limit.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How often a [`BandwidthGossip`] checks whether its gossip module should be
+/// stopped or started again.
+const GOSSIP_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The gossip configuration of a space, which is read by the [`BandwidthGossipFactory`].
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HcGossipConfig {
+    /// Whether gossip is disabled, so that the gossip module of the space is never started.
+    pub disabled: bool,
+}
+
+/// Module-level configuration for the gossip of a space.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HcGossipModConfig {
+    /// Gossip configuration.
+    pub hc_gossip: HcGossipConfig,
+}
+
+/// The kinds of traffic that bandwidth is counted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrafficKind {
+    Gossip,
+    Fetch,
+    Publish,
+    Other,
+}
+
+impl TrafficKind {
+    /// The kind of traffic of a Kitsune2 module.
+    fn from_module(module: &str) -> Self {
+        if module.eq_ignore_ascii_case("gossip") {
+            Self::Gossip
+        } else if module.eq_ignore_ascii_case("fetch") {
+            Self::Fetch
+        } else if module.eq_ignore_ascii_case("publish") {
+            Self::Publish
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Whether this kind of traffic counts towards bandwidth limits.
+    fn is_limited(&self) -> bool {
+        matches!(self, Self::Gossip | Self::Fetch)
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gossip => "gossip",
+            Self::Fetch => "fetch",
+            Self::Publish => "publish",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Whether traffic was sent to or received from a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Sent,
+    Received,
+}
+
+impl Direction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Received => "received",
+        }
+    }
+}
+
+/// The limits of one app and the traffic counted towards them.
+#[derive(Debug)]
+struct LimitState {
+    limits: BandwidthLimits,
+    spaces: HashSet<SpaceId>,
+    day_start: Timestamp,
+    day_bytes: u64,
+    window_start: Timestamp,
+    window_bytes: u64,
+    over_daily: bool,
+    over_rate: bool,
+    /// Whether the usage of the day has changed since it was last taken to be persisted.
+    usage_changed: bool,
+    /// The bucket that the gossip messages of the spaces are sent through, if there is a rate
+    /// limit.
+    gossip_bucket: Option<TokenBucket>,
+}
+
+impl LimitState {
+    fn new(
+        limits: BandwidthLimits,
+        spaces: HashSet<SpaceId>,
+        usage: Option<BandwidthUsage>,
+        now: Timestamp,
+    ) -> Self {
+        let usage = usage.unwrap_or(BandwidthUsage {
+            day_start: now,
+            day_bytes: 0,
+        });
+        let gossip_bucket = limits
+            .bytes_per_second
+            .map(|bytes_per_second| TokenBucket::new(bytes_per_second, now));
+        let mut state = Self {
+            limits,
+            spaces,
+            day_start: usage.day_start,
+            day_bytes: usage.day_bytes,
+            window_start: now,
+            window_bytes: 0,
+            over_daily: false,
+            over_rate: false,
+            usage_changed: true,
+            gossip_bucket,
+        };
+        state.roll(now);
+        state
+    }
+
+    /// Start a new day or rate window if the current one is over.
+    fn roll(&mut self, now: Timestamp) {
+        if now >= self.day_start.saturating_add(&DAY) {
+            self.day_start = now;
+            self.day_bytes = 0;
+            self.usage_changed = true;
+        }
+        if now >= self.window_start.saturating_add(&RATE_WINDOW) {
+            self.window_start = now;
+            self.window_bytes = 0;
+        }
+    }
+
+    fn record(&mut self, bytes: u64, now: Timestamp) {
+        self.roll(now);
+        self.day_bytes = self.day_bytes.saturating_add(bytes);
+        self.window_bytes = self.window_bytes.saturating_add(bytes);
+        self.usage_changed = true;
+    }
+
+    /// Check whether a limit is exceeded, logging when that changes.
+    fn check(&mut self, app_id: &str, now: Timestamp) -> bool {
+        self.roll(now);
+        let was_exceeded = self.is_exceeded();
+        self.over_daily = self
+            .limits
+            .daily_bytes
+            .is_some_and(|daily_bytes| self.day_bytes >= daily_bytes);
+        self.over_rate = self
+            .limits
+            .bytes_per_second
+            .is_some_and(|bytes_per_second| {
+                self.window_bytes >= bytes_per_second.saturating_mul(RATE_WINDOW.as_secs())
+            });
+        let exceeded = self.is_exceeded();
+        if exceeded != was_exceeded {
+            if exceeded {
+                tracing::info!(
+                    %app_id,
+                    over_daily = self.over_daily,
+                    over_rate = self.over_rate,
+                    "Bandwidth limit reached, pausing gossip"
+                );
+            } else {
+                tracing::info!(%app_id, "Bandwidth limit period is over, resuming gossip");
+            }
+        }
+        exceeded
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.over_daily || self.over_rate
+    }
+
+    fn usage(&self) -> BandwidthUsage {
+        BandwidthUsage {
+            day_start: self.day_start,
+            day_bytes: self.day_bytes,
+        }
+    }
+}
+
+/// A token bucket that the gossip messages of the spaces of an app are sent through.
+///
+/// It holds up to a second of traffic at its rate. Messages take their bytes
+/// from it even when it doesn't hold enough, so that concurrent messages queue
+/// up behind each other instead of each waiting for its own size.
+#[derive(Debug)]
+struct TokenBucket {
+    bytes_per_second: u64,
+    tokens: f64,
+    last_refill: Timestamp,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_second: u64, now: Timestamp) -> Self {
+        Self {
+            bytes_per_second,
+            tokens: bytes_per_second as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Take the bytes of a message, returning how long to hold it back until the bucket has
+    /// refilled enough to cover it.
+    fn take(&mut self, bytes: u64, now: Timestamp) -> Duration {
+        let rate = self.bytes_per_second.max(1) as f64;
+        let elapsed_micros = now
+            .as_micros()
+            .saturating_sub(self.last_refill.as_micros())
+            .max(0);
+        if elapsed_micros > 0 {
+            self.tokens = (self.tokens + elapsed_micros as f64 / 1_000_000.0 * rate).min(rate);
+            self.last_refill = now;
+        }
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / rate)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BandwidthData {
+    spaces: HashMap<SpaceId, SpaceBandwidth>,
+    limits: HashMap<InstalledAppId, LimitState>,
+}
+
+/// Counts the traffic of each space and tracks the bandwidth limits of apps.
+#[derive(Debug, Default)]
+pub(crate) struct BandwidthTracker(Mutex<BandwidthData>);
+
+impl BandwidthTracker {
+    /// Count traffic sent to or received from a peer in a space.
+    pub(crate) fn record(
+        &self,
+        space_id: &SpaceId,
+        kind: TrafficKind,
+        direction: Direction,
+        bytes: usize,
+    ) {
+        self.record_at(space_id, kind, direction, bytes as u64, Timestamp::now());
+
+        p2p_bandwidth_metric().add(
+            bytes as u64,
+            &[
+                opentelemetry::KeyValue::new(
+                    "dna_hash",
+                    format!("{:?}", DnaHash::from_k2_space(space_id)),
+                ),
+                opentelemetry::KeyValue::new("kind", kind.as_str()),
+                opentelemetry::KeyValue::new("direction", direction.as_str()),
+            ],
+        );
+    }
+
+    fn record_at(
+        &self,
+        space_id: &SpaceId,
+        kind: TrafficKind,
+        direction: Direction,
+        bytes: u64,
+        now: Timestamp,
+    ) {
+        let mut data = self.0.lock().expect("bandwidth data lock poisoned");
+
+        let space = data.spaces.entry(space_id.clone()).or_default();
+        let traffic = match kind {
+            TrafficKind::Gossip => &mut space.gossip,
+            TrafficKind::Fetch => &mut space.fetch,
+            TrafficKind::Publish => &mut space.publish,
+            TrafficKind::Other => &mut space.other,
+        };
+        match direction {
+            Direction::Sent => traffic.sent = traffic.sent.saturating_add(bytes),
+            Direction::Received => traffic.received = traffic.received.saturating_add(bytes),
+        }
+
+        if kind.is_limited() {
+            for state in data.limits.values_mut() {
+                if state.spaces.contains(space_id) {
+                    state.record(bytes, now);
+                }
+            }
+        }
+    }
+
+    /// Whether gossip in a space is paused, because an app that is in the space has exceeded
+    /// one of its limits.
+    pub(crate) fn is_gossip_paused(&self, space_id: &SpaceId) -> bool {
+        self.is_gossip_paused_at(space_id, Timestamp::now())
+    }
+
+    fn is_gossip_paused_at(&self, space_id: &SpaceId, now: Timestamp) -> bool {
+        let mut data = self.0.lock().expect("bandwidth data lock poisoned");
+        let mut paused = false;
+        for (app_id, state) in data.limits.iter_mut() {
+            // Check every app, so that each one logs when its limit is reached.
+            if state.spaces.contains(space_id) && state.check(app_id, now) {
+                paused = true;
+            }
+        }
+        paused
+    }
+
+    /// How long to hold back a gossip message of the given size in a space, if an app that is
+    /// in the space has a rate limit.
+    ///
+    /// The message takes its bytes from the bucket of every such app, and is held back until
+    /// all of them have refilled enough to cover it.
+    fn gossip_delay(&self, space_id: &SpaceId, bytes: usize) -> Option<Duration> {
+        self.gossip_delay_at(space_id, bytes as u64, Timestamp::now())
+    }
+
+    fn gossip_delay_at(&self, space_id: &SpaceId, bytes: u64, now: Timestamp) -> Option<Duration> {
+        let mut data = self.0.lock().expect("bandwidth data lock poisoned");
+        let delay = data
+            .limits
+            .values_mut()
+            .filter(|state| state.spaces.contains(space_id))
+            .filter_map(|state| state.gossip_bucket.as_mut())
+            .map(|bucket| bucket.take(bytes, now))
+            .max()
+            .unwrap_or_default();
+        (!delay.is_zero()).then_some(delay)
+    }
+
+    /// Set the limits of an app, whose cells are in the given spaces, or with `None` remove them.
+    ///
+    /// Setting different limits starts a new day and rate window for the app. Setting the same
+    /// limits again only updates the spaces that they apply to. The given usage continues the
+    /// day that it was counted in, if the tracker doesn't have limits for the app yet.
+    pub(crate) fn set_limits(
+        &self,
+        installed_app_id: InstalledAppId,
+        space_ids: impl IntoIterator<Item = SpaceId>,
+        limits: Option<BandwidthLimits>,
+        usage: Option<BandwidthUsage>,
+    ) {
+        let mut data = self.0.lock().expect("bandwidth data lock poisoned");
+        match limits {
+            Some(limits) => {
+                let spaces = space_ids.into_iter().collect();
+                match data.limits.get_mut(&installed_app_id) {
+                    Some(state) if state.limits == limits => state.spaces = spaces,
+                    Some(_) => {
+                        data.limits.insert(
+                            installed_app_id,
+                            LimitState::new(limits, spaces, None, Timestamp::now()),
+                        );
+                    }
+                    None => {
+                        data.limits.insert(
+                            installed_app_id,
+                            LimitState::new(limits, spaces, usage, Timestamp::now()),
+                        );
+                    }
+                }
+            }
+            None => {
+                data.limits.remove(&installed_app_id);
+            }
+        }
+    }
+
+    /// Take the usage of the apps whose usage has changed since it was last taken, to persist it.
+    pub(crate) fn take_changed_usage(&self) -> Vec<(InstalledAppId, BandwidthUsage)> {
+        let mut data = self.0.lock().expect("bandwidth data lock poisoned");
+        data.limits
+            .iter_mut()
+            .filter(|(_, state)| state.usage_changed)
+            .map(|(app_id, state)| {
+                state.usage_changed = false;
+                (app_id.clone(), state.usage())
+            })
+            .collect()
+    }
+
+    /// The traffic counted for a space so far.
+    pub(crate) fn space_bandwidth(&self, space_id: &SpaceId) -> SpaceBandwidth {
+        let gossip_paused = self.is_gossip_paused(space_id);
+        let data = self.0.lock().expect("bandwidth data lock poisoned");
+        SpaceBandwidth {
+            gossip_paused,
+            ..data.spaces.get(space_id).cloned().unwrap_or_default()
+        }
+    }
+}
+
+/// A transport factory that wraps the transports it creates in a [`BandwidthTransport`].
+#[derive(Debug)]
+pub(crate) struct BandwidthTransportFactory {
+    tracker: Arc<BandwidthTracker>,
+    orig: DynTransportFactory,
+}
+
+impl BandwidthTransportFactory {
+    pub(crate) fn create(
+        tracker: Arc<BandwidthTracker>,
+        orig: DynTransportFactory,
+    ) -> DynTransportFactory {
+        Arc::new(Self { tracker, orig })
+    }
+}
+
+impl TransportFactory for BandwidthTransportFactory {
+    fn default_config(&self, config: &mut Config) -> K2Result<()> {
+        self.orig.default_config(config)
+    }
+
+    fn validate_config(&self, config: &Config) -> K2Result<()> {
+        self.orig.validate_config(config)
+    }
+
+    fn create(
+        &self,
+        builder: Arc<Builder>,
+        handler: DynTxHandler,
+    ) -> BoxFut<'static, K2Result<DynTransport>> {
+        let tracker = self.tracker.clone();
+        let orig_fut = self.orig.create(builder, handler);
+        Box::pin(async move {
+            let orig = orig_fut.await?;
+            let out: DynTransport = Arc::new(BandwidthTransport { tracker, orig });
+            Ok(out)
+        })
+    }
+}
+
+/// A transport that counts the traffic of each space, and holds back gossip
+/// messages in the spaces of apps that have a rate limit.
+#[derive(Debug)]
+struct BandwidthTransport {
+    tracker: Arc<BandwidthTracker>,
+    orig: DynTransport,
+}
+
+impl Transport for BandwidthTransport {
+    fn register_space_handler(&self, space_id: SpaceId, handler: DynTxSpaceHandler) -> Option<Url> {
+        self.orig.register_space_handler(space_id, handler)
+    }
+
+    fn register_module_handler(
+        &self,
+        space_id: SpaceId,
+        module: String,
+        handler: DynTxModuleHandler,
+    ) {
+        let handler: DynTxModuleHandler = Arc::new(BandwidthModuleHandler {
+            kind: TrafficKind::from_module(&module),
+            tracker: self.tracker.clone(),
+            orig: handler,
+        });
+        self.orig.register_module_handler(space_id, module, handler)
+    }
+
+    fn disconnect(&self, peer: Url, reason: Option<String>) -> BoxFut<'_, ()> {
+        self.orig.disconnect(peer, reason)
+    }
+
+    fn send_space_notify(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        data: Bytes,
+    ) -> BoxFut<'_, K2Result<()>> {
+        self.tracker
+            .record(&space_id, TrafficKind::Other, Direction::Sent, data.len());
+        self.orig.send_space_notify(peer, space_id, data)
+    }
+
+    fn send_module(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        module: String,
+        data: Bytes,
+    ) -> BoxFut<'_, K2Result<()>> {
+        let kind = TrafficKind::from_module(&module);
+        let delay = match kind {
+            TrafficKind::Gossip => self.tracker.gossip_delay(&space_id, data.len()),
+            _ => None,
+        };
+        self.tracker
+            .record(&space_id, kind, Direction::Sent, data.len());
+        Box::pin(async move {
+            if let Some(delay) = delay {
+                tracing::trace!(%peer, ?space_id, ?delay, "Holding back outgoing gossip message");
+                tokio::time::sleep(delay).await;
+            }
+            self.orig.send_module(peer, space_id, module, data).await
+        })
+    }
+
+    fn unregister_space(&self, space_id: SpaceId) -> BoxFut<'_, ()> {
+        self.orig.unregister_space(space_id)
+    }
+
+    fn get_connected_peers(&self) -> BoxFut<'_, K2Result<Vec<Url>>> {
+        self.orig.get_connected_peers()
+    }
+
+    fn dump_network_stats(&self) -> BoxFut<'_, K2Result<ApiTransportStats>> {
+        self.orig.dump_network_stats()
+    }
+}
+
+/// A module handler that counts the incoming traffic of a module.
+#[derive(Debug)]
+struct BandwidthModuleHandler {
+    kind: TrafficKind,
+    tracker: Arc<BandwidthTracker>,
+    orig: DynTxModuleHandler,
+}
+
+impl TxBaseHandler for BandwidthModuleHandler {
+    fn new_listening_address(&self, this_url: Url) -> BoxFut<'static, ()> {
+        self.orig.new_listening_address(this_url)
+    }
+
+    fn peer_connect(&self, peer: Url) -> K2Result<()> {
+        self.orig.peer_connect(peer)
+    }
+
+    fn peer_disconnect(&self, peer: Url, reason: Option<String>) {
+        self.orig.peer_disconnect(peer, reason)
+    }
+}
+
+impl TxModuleHandler for BandwidthModuleHandler {
+    fn recv_module_msg(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        module: String,
+        data: Bytes,
+    ) -> K2Result<()> {
+        self.tracker
+            .record(&space_id, self.kind, Direction::Received, data.len());
+        self.orig.recv_module_msg(peer, space_id, module, data)
+    }
+}
+
+/// A gossip factory that wraps the gossip modules it creates in a [`BandwidthGossip`].
+#[derive(Debug)]
+pub(crate) struct BandwidthGossipFactory {
+    tracker: Arc<BandwidthTracker>,
+    orig: DynGossipFactory,
+}
+
+impl BandwidthGossipFactory {
+    pub(crate) fn create(
+        tracker: Arc<BandwidthTracker>,
+        orig: DynGossipFactory,
+    ) -> DynGossipFactory {
+        Arc::new(Self { tracker, orig })
+    }
+}
+
+impl GossipFactory for BandwidthGossipFactory {
+    fn default_config(&self, config: &mut Config) -> K2Result<()> {
+        config.set_module_config(&HcGossipModConfig::default())?;
+        self.orig.default_config(config)
+    }
+
+    fn validate_config(&self, config: &Config) -> K2Result<()> {
+        self.orig.validate_config(config)
+    }
+
+    fn create(
+        &self,
+        builder: Arc<Builder>,
+        space_id: SpaceId,
+        peer_store: DynPeerStore,
+        local_agent_store: DynLocalAgentStore,
+        peer_meta_store: DynPeerMetaStore,
+        op_store: DynOpStore,
+        transport: DynTransport,
+        fetch: DynFetch,
+    ) -> BoxFut<'static, K2Result<DynGossip>> {
+        let disabled = match builder.config.get_module_config::<HcGossipModConfig>() {
+            Ok(config) => config.hc_gossip.disabled,
+            Err(err) => return Box::pin(async move { Err(err) }),
+        };
+        let handler = Arc::new(GossipHandlerSlot::default());
+        let transport: DynTransport = Arc::new(GossipModuleTransport {
+            handler: handler.clone(),
+            orig: transport,
+        });
+        let orig = self.orig.clone();
+        let module_space_id = space_id.clone();
+        let create_module: CreateGossipModule = Arc::new(move || {
+            orig.create(
+                builder.clone(),
+                module_space_id.clone(),
+                peer_store.clone(),
+                local_agent_store.clone(),
+                peer_meta_store.clone(),
+                op_store.clone(),
+                transport.clone(),
+                fetch.clone(),
+            )
+        });
+        BandwidthGossip::create(
+            self.tracker.clone(),
+            space_id,
+            disabled,
+            handler,
+            create_module,
+        )
+    }
+}
+
+type CreateGossipModule = Arc<dyn Fn() -> BoxFut<'static, K2Result<DynGossip>> + Send + Sync>;
+
+/// A gossip module that runs the Kitsune2 gossip module of a space only while
+/// gossip is neither disabled in the configuration of the space nor paused by a
+/// bandwidth limit.
+///
+/// Stopping the gossip module, rather than dropping its messages, means that no
+/// gossip rounds are started with peers, and that no peers are marked as
+/// unresponsive because their rounds time out. When the module is started
+/// again, it picks up the ops that were stored in the meantime from the op
+/// store.
+struct BandwidthGossip {
+    space_id: SpaceId,
+    disabled: bool,
+    tracker: Arc<BandwidthTracker>,
+    handler: Arc<GossipHandlerSlot>,
+    create_module: CreateGossipModule,
+    module: Mutex<Option<DynGossip>>,
+    check_task: AbortHandle,
+}
+
+impl std::fmt::Debug for BandwidthGossip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BandwidthGossip")
+            .field("space_id", &self.space_id)
+            .field("disabled", &self.disabled)
+            .finish()
+    }
+}
+
+impl BandwidthGossip {
+    fn create(
+        tracker: Arc<BandwidthTracker>,
+        space_id: SpaceId,
+        disabled: bool,
+        handler: Arc<GossipHandlerSlot>,
+        create_module: CreateGossipModule,
+    ) -> BoxFut<'static, K2Result<DynGossip>> {
+        Box::pin(async move {
+            if disabled {
+                tracing::info!(?space_id, "Gossip is disabled in the space");
+            }
+            let module = if disabled || tracker.is_gossip_paused(&space_id) {
+                None
+            } else {
+                Some(create_module().await?)
+            };
+            let out: DynGossip = Arc::new_cyclic(|this: &Weak<Self>| {
+                let this = this.clone();
+                let check_task = tokio::spawn(async move {
+                    loop {
+                        tokio::time::sleep(GOSSIP_CHECK_INTERVAL).await;
+                        let Some(this) = this.upgrade() else {
+                            break;
+                        };
+                        this.stop_or_start_module().await;
+                    }
+                })
+                .abort_handle();
+                Self {
+                    space_id,
+                    disabled,
+                    tracker,
+                    handler,
+                    create_module,
+                    module: Mutex::new(module),
+                    check_task,
+                }
+            });
+            Ok(out)
+        })
+    }
+
+    fn module(&self) -> Option<DynGossip> {
+        self.module
+            .lock()
+            .expect("gossip module lock poisoned")
+            .clone()
+    }
+
+    /// Stop the gossip module if gossip has been paused, or start it if gossip has been resumed.
+    async fn stop_or_start_module(&self) {
+        let stopped = self.disabled || self.tracker.is_gossip_paused(&self.space_id);
+        let running = self.module().is_some();
+        if stopped && running {
+            tracing::debug!(space_id = ?self.space_id, "Stopping gossip module");
+            // Release the module's message handler too, so that nothing keeps the module alive.
+            self.handler.clear();
+            let module = self
+                .module
+                .lock()
+                .expect("gossip module lock poisoned")
+                .take();
+            drop(module);
+        } else if !stopped && !running {
+            tracing::debug!(space_id = ?self.space_id, "Starting gossip module");
+            match (self.create_module)().await {
+                Ok(module) => {
+                    *self.module.lock().expect("gossip module lock poisoned") = Some(module);
+                }
+                Err(err) => {
+                    tracing::warn!(?err, space_id = ?self.space_id, "Failed to start gossip module");
+                }
+            }
+        }
+    }
+}
+
+impl Drop for BandwidthGossip {
+    fn drop(&mut self) {
+        self.check_task.abort();
+    }
+}
+
+impl Gossip for BandwidthGossip {
+    fn get_state_summary(
+        &self,
+        request: GossipStateSummaryRequest,
+    ) -> BoxFut<'_, K2Result<GossipStateSummary>> {
+        let module = self.module();
+        Box::pin(async move {
+            match module {
+                Some(module) => module.get_state_summary(request).await,
+                None => Ok(GossipStateSummary {
+                    accepted_rounds: Default::default(),
+                    dht_summary: Default::default(),
+                    initiated_round: Default::default(),
+                    local_op_count: Default::default(),
+                    peer_meta: Default::default(),
+                }),
+            }
+        })
+    }
+
+    fn inform_ops_stored(&self, ops: Vec<StoredOp>) -> BoxFut<'_, K2Result<()>> {
+        let module = self.module();
+        Box::pin(async move {
+            match module {
+                Some(module) => module.inform_ops_stored(ops).await,
+                // The module finds the ops in the op store when it is started again.
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+/// The transport that a gossip module is given, which registers the module's
+/// message handler in a [`GossipHandlerSlot`] that can be cleared when the
+/// module is stopped.
+#[derive(Debug)]
+struct GossipModuleTransport {
+    handler: Arc<GossipHandlerSlot>,
+    orig: DynTransport,
+}
+
+impl Transport for GossipModuleTransport {
+    fn register_space_handler(&self, space_id: SpaceId, handler: DynTxSpaceHandler) -> Option<Url> {
+        self.orig.register_space_handler(space_id, handler)
+    }
+
+    fn register_module_handler(
+        &self,
+        space_id: SpaceId,
+        module: String,
+        handler: DynTxModuleHandler,
+    ) {
+        self.handler.set(handler);
+        self.orig
+            .register_module_handler(space_id, module, self.handler.clone())
+    }
+
+    fn disconnect(&self, peer: Url, reason: Option<String>) -> BoxFut<'_, ()> {
+        self.orig.disconnect(peer, reason)
+    }
+
+    fn send_space_notify(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        data: Bytes,
+    ) -> BoxFut<'_, K2Result<()>> {
+        self.orig.send_space_notify(peer, space_id, data)
+    }
+
+    fn send_module(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        module: String,
+        data: Bytes,
+    ) -> BoxFut<'_, K2Result<()>> {
+        self.orig.send_module(peer, space_id, module, data)
+    }
+
+    fn unregister_space(&self, space_id: SpaceId) -> BoxFut<'_, ()> {
+        self.orig.unregister_space(space_id)
+    }
+
+    fn get_connected_peers(&self) -> BoxFut<'_, K2Result<Vec<Url>>> {
+        self.orig.get_connected_peers()
+    }
+
+    fn dump_network_stats(&self) -> BoxFut<'_, K2Result<ApiTransportStats>> {
+        self.orig.dump_network_stats()
+    }
+}
+
+/// Passes gossip messages to the handler of the running gossip module of a
+/// space, if there is one.
+#[derive(Debug, Default)]
+struct GossipHandlerSlot(Mutex<Option<DynTxModuleHandler>>);
+
+impl GossipHandlerSlot {
+    fn get(&self) -> Option<DynTxModuleHandler> {
+        self.0.lock().expect("gossip handler lock poisoned").clone()
+    }
+
+    fn set(&self, handler: DynTxModuleHandler) {
+        *self.0.lock().expect("gossip handler lock poisoned") = Some(handler);
+    }
+
+    fn clear(&self) {
+        self.0.lock().expect("gossip handler lock poisoned").take();
+    }
+}
+
+impl TxBaseHandler for GossipHandlerSlot {
+    fn new_listening_address(&self, this_url: Url) -> BoxFut<'static, ()> {
+        match self.get() {
+            Some(handler) => handler.new_listening_address(this_url),
+            None => Box::pin(async {}),
+        }
+    }
+
+    fn peer_connect(&self, peer: Url) -> K2Result<()> {
+        match self.get() {
+            Some(handler) => handler.peer_connect(peer),
+            None => Ok(()),
+        }
+    }
+
+    fn peer_disconnect(&self, peer: Url, reason: Option<String>) {
+        if let Some(handler) = self.get() {
+            handler.peer_disconnect(peer, reason)
+        }
+    }
+}
+
+impl TxModuleHandler for GossipHandlerSlot {
+    fn recv_module_msg(
+        &self,
+        peer: Url,
+        space_id: SpaceId,
+        module: String,
+        data: Bytes,
+    ) -> K2Result<()> {
+        match self.get() {
+            Some(handler) => handler.recv_module_msg(peer, space_id, module, data),
+            None => {
+                // There is no gossip module to take part in the peer's round.
+                tracing::trace!(%peer, ?space_id, "Gossip module is stopped, ignoring message");
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::network::TrafficBytes;
+
+    fn space(n: u8) -> SpaceId {
+        DnaHash::from_raw_36(vec![n; 36]).to_k2_space()
+    }
+
+    fn tracker_with_limits(limits: BandwidthLimits, spaces: &[SpaceId]) -> BandwidthTracker {
+        let tracker = BandwidthTracker::default();
+        tracker.set_limits("app".to_string(), spaces.to_vec(), Some(limits), None);
+        tracker
+    }
+
+    fn later(start: Timestamp, duration: Duration) -> Timestamp {
+        (start + duration).unwrap()
+    }
+
+    #[test]
+    fn traffic_is_counted_by_kind_and_direction() {
+        let tracker = BandwidthTracker::default();
+        let space_id = space(1);
+        let now = Timestamp::now();
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 10, now);
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Received, 20, now);
+        tracker.record_at(&space_id, TrafficKind::Fetch, Direction::Received, 30, now);
+        tracker.record_at(&space_id, TrafficKind::Other, Direction::Sent, 40, now);
+        tracker.record_at(&space(2), TrafficKind::Publish, Direction::Sent, 50, now);
+
+        assert_eq!(
+            tracker.space_bandwidth(&space_id),
+            SpaceBandwidth {
+                gossip: TrafficBytes {
+                    sent: 10,
+                    received: 20
+                },
+                fetch: TrafficBytes {
+                    sent: 0,
+                    received: 30
+                },
+                publish: TrafficBytes::default(),
+                other: TrafficBytes {
+                    sent: 40,
+                    received: 0
+                },
+                gossip_paused: false,
+            }
+        );
+    }
+
+    #[test]
+    fn daily_limit_pauses_gossip_until_the_next_day() {
+        let space_id = space(1);
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: Some(100),
+                bytes_per_second: None,
+            },
+            std::slice::from_ref(&space_id),
+        );
+        let start = Timestamp::now();
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 60, start);
+        assert!(!tracker.is_gossip_paused_at(&space_id, start));
+
+        // Traffic that isn't gossip or fetch doesn't count towards the limit.
+        tracker.record_at(&space_id, TrafficKind::Publish, Direction::Sent, 60, start);
+        tracker.record_at(&space_id, TrafficKind::Other, Direction::Sent, 60, start);
+        assert!(!tracker.is_gossip_paused_at(&space_id, start));
+
+        tracker.record_at(
+            &space_id,
+            TrafficKind::Fetch,
+            Direction::Received,
+            40,
+            start,
+        );
+        assert!(tracker.is_gossip_paused_at(&space_id, start));
+        assert!(tracker.is_gossip_paused_at(&space_id, later(start, Duration::from_secs(60 * 60))));
+
+        assert!(!tracker.is_gossip_paused_at(&space_id, later(start, DAY)));
+    }
+
+    #[test]
+    fn rate_limit_pauses_gossip_until_the_next_window() {
+        let space_id = space(1);
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: None,
+                bytes_per_second: Some(10),
+            },
+            std::slice::from_ref(&space_id),
+        );
+        let start = Timestamp::now();
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 599, start);
+        assert!(!tracker.is_gossip_paused_at(&space_id, start));
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 1, start);
+        assert!(tracker.is_gossip_paused_at(&space_id, start));
+
+        assert!(!tracker.is_gossip_paused_at(&space_id, later(start, RATE_WINDOW)));
+    }
+
+    #[test]
+    fn limits_only_apply_to_the_spaces_of_the_app() {
+        let limited_space = space(1);
+        let other_space = space(2);
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: Some(100),
+                bytes_per_second: None,
+            },
+            std::slice::from_ref(&limited_space),
+        );
+        let now = Timestamp::now();
+
+        tracker.record_at(
+            &other_space,
+            TrafficKind::Gossip,
+            Direction::Sent,
+            1000,
+            now,
+        );
+        assert!(!tracker.is_gossip_paused_at(&limited_space, now));
+        assert!(!tracker.is_gossip_paused_at(&other_space, now));
+
+        tracker.record_at(
+            &limited_space,
+            TrafficKind::Gossip,
+            Direction::Sent,
+            100,
+            now,
+        );
+        assert!(tracker.is_gossip_paused_at(&limited_space, now));
+        assert!(!tracker.is_gossip_paused_at(&other_space, now));
+    }
+
+    #[test]
+    fn gossip_is_held_back_by_the_token_bucket_of_the_app() {
+        let space_id = space(1);
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: None,
+                bytes_per_second: Some(1_000),
+            },
+            std::slice::from_ref(&space_id),
+        );
+        let start = Timestamp::now();
+
+        assert_eq!(tracker.gossip_delay_at(&space_id, 1_000, start), None);
+
+        // Messages sent at the same time queue up behind each other.
+        assert_eq!(
+            tracker.gossip_delay_at(&space_id, 500, start),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            tracker.gossip_delay_at(&space_id, 500, start),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(tracker.gossip_delay_at(&space(2), 500, start), None);
+
+        assert_eq!(
+            tracker.gossip_delay_at(&space_id, 500, later(start, Duration::from_secs(2))),
+            None
+        );
+    }
+
+    #[test]
+    fn gossip_of_all_spaces_of_an_app_shares_its_token_bucket() {
+        let spaces = [space(1), space(2), space(3)];
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: None,
+                bytes_per_second: Some(1_000),
+            },
+            &spaces,
+        );
+        let start = Timestamp::now();
+
+        // The spaces of the app together keep to its rate.
+        assert_eq!(tracker.gossip_delay_at(&spaces[0], 1_000, start), None);
+        assert_eq!(
+            tracker.gossip_delay_at(&spaces[1], 1_000, start),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            tracker.gossip_delay_at(&spaces[2], 1_000, start),
+            Some(Duration::from_secs(2))
+        );
+
+        // Another app with its own rate limit in one of the spaces has its own bucket, and
+        // gossip in that space is held back by both.
+        tracker.set_limits(
+            "other app".to_string(),
+            [spaces[0].clone()],
+            Some(BandwidthLimits {
+                daily_bytes: None,
+                bytes_per_second: Some(10_000),
+            }),
+            None,
+        );
+        assert_eq!(
+            tracker.gossip_delay_at(&spaces[0], 1_000, start),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(tracker.gossip_delay_at(&space(4), 1_000, start), None);
+    }
+
+    #[test]
+    fn gossip_is_not_held_back_without_a_rate_limit() {
+        let space_id = space(1);
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: Some(100),
+                bytes_per_second: None,
+            },
+            std::slice::from_ref(&space_id),
+        );
+        let start = Timestamp::now();
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 100, start);
+        assert_eq!(tracker.gossip_delay_at(&space_id, 10_000_000, start), None);
+    }
+
+    #[test]
+    fn setting_the_same_limits_keeps_the_counted_traffic() {
+        let space_id = space(1);
+        let new_space_id = space(2);
+        let limits = BandwidthLimits {
+            daily_bytes: Some(100),
+            bytes_per_second: None,
+        };
+        let tracker = tracker_with_limits(limits, std::slice::from_ref(&space_id));
+        let now = Timestamp::now();
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 100, now);
+        assert!(tracker.is_gossip_paused_at(&space_id, now));
+
+        // Adding a space, as when a clone cell is created, doesn't reset the day.
+        tracker.set_limits(
+            "app".to_string(),
+            [space_id.clone(), new_space_id.clone()],
+            Some(limits),
+            None,
+        );
+        assert!(tracker.is_gossip_paused_at(&space_id, now));
+        assert!(tracker.is_gossip_paused_at(&new_space_id, now));
+
+        // Changing the limits does, even if usage is given.
+        tracker.set_limits(
+            "app".to_string(),
+            [space_id.clone()],
+            Some(BandwidthLimits {
+                daily_bytes: Some(200),
+                bytes_per_second: None,
+            }),
+            Some(BandwidthUsage {
+                day_start: now,
+                day_bytes: 200,
+            }),
+        );
+        assert!(!tracker.is_gossip_paused_at(&space_id, now));
+    }
+
+    #[test]
+    fn persisted_usage_continues_its_day() {
+        let space_id = space(1);
+        let limits = BandwidthLimits {
+            daily_bytes: Some(100),
+            bytes_per_second: None,
+        };
+        let tracker = BandwidthTracker::default();
+        let day_start = Timestamp::now();
+
+        tracker.set_limits(
+            "app".to_string(),
+            [space_id.clone()],
+            Some(limits),
+            Some(BandwidthUsage {
+                day_start,
+                day_bytes: 100,
+            }),
+        );
+        assert!(tracker.is_gossip_paused_at(&space_id, later(day_start, Duration::from_secs(1))));
+        assert!(!tracker.is_gossip_paused_at(&space_id, later(day_start, DAY)));
+
+        // A day that is already over is not continued.
+        tracker.set_limits("app".to_string(), [], None, None);
+        tracker.set_limits(
+            "app".to_string(),
+            [space_id.clone()],
+            Some(limits),
+            Some(BandwidthUsage {
+                day_start: Timestamp::from_micros(0),
+                day_bytes: 100,
+            }),
+        );
+        assert!(!tracker.is_gossip_paused_at(&space_id, Timestamp::now()));
+    }
+
+    #[test]
+    fn changed_usage_is_taken_once() {
+        let space_id = space(1);
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: Some(100),
+                bytes_per_second: None,
+            },
+            std::slice::from_ref(&space_id),
+        );
+        let now = Timestamp::now();
+
+        // New limits have usage to persist.
+        assert_eq!(tracker.take_changed_usage().len(), 1);
+        assert!(tracker.take_changed_usage().is_empty());
+
+        // Traffic that doesn't count towards the limits doesn't change the usage.
+        tracker.record_at(&space_id, TrafficKind::Publish, Direction::Sent, 10, now);
+        assert!(tracker.take_changed_usage().is_empty());
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 10, now);
+        let usage = tracker.take_changed_usage();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].0, "app");
+        assert_eq!(usage[0].1.day_bytes, 10);
+        assert!(tracker.take_changed_usage().is_empty());
+    }
+
+    #[test]
+    fn removing_limits_resumes_gossip() {
+        let space_id = space(1);
+        let tracker = tracker_with_limits(
+            BandwidthLimits {
+                daily_bytes: Some(100),
+                bytes_per_second: None,
+            },
+            std::slice::from_ref(&space_id),
+        );
+        let now = Timestamp::now();
+
+        tracker.record_at(&space_id, TrafficKind::Gossip, Direction::Sent, 100, now);
+        assert!(tracker.is_gossip_paused_at(&space_id, now));
+
+        tracker.set_limits("app".to_string(), [], None, None);
+        assert!(!tracker.is_gossip_paused_at(&space_id, now));
+    }
+}
@@ -41,6 +41,8 @@ pub use blocks::*;
 
 mod metrics;
 
+mod bandwidth;
+
 #[allow(unused)]
 #[cfg(any(test, feature = "test_utils"))]
 mod test;
@@ -135,10 +137,13 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
     /// This is a fire-and-forget operation, a best effort will be made
     /// to forward the signal, but if the conductor network is overworked
     /// it may decide not to deliver some of the signals.
+    ///
+    /// Returns the agents that the signal could not be sent to, because no URL
+    /// is known for them or sending to their URL failed.
     async fn send_remote_signal(
         &self,
         to_agent_list: Vec<(AgentPubKey, ExternIO, Signature)>,
-    ) -> HolochainP2pResult<()>;
+    ) -> HolochainP2pResult<Vec<AgentPubKey>>;
 
     /// Publish data to the correct neighborhood.
     #[allow(clippy::ptr_arg)]
@@ -214,6 +219,16 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
     /// unresponsive in the peer meta store.
     async fn was_agent_recently_online(&self, agent: AgentPubKey) -> HolochainP2pResult<bool>;
 
+    /// Get what is locally known about the reachability of agents in this network.
+    ///
+    /// Combines the agent info in the peer store with the unresponsive marker in the peer meta
+    /// store and the time of the last successful ping to the agent's URL. No requests are sent to
+    /// the agents.
+    async fn get_agent_presence(
+        &self,
+        agents: Vec<AgentPubKey>,
+    ) -> HolochainP2pResult<Vec<AgentPresence>>;
+
     /// Send a validation receipt to a remote node.
     async fn send_validation_receipts(
         &self,
@@ -314,10 +329,13 @@ impl HolochainP2pDnaT for HolochainP2pDna {
     /// This is a fire-and-forget operation, a best effort will be made
     /// to forward the signal, but if the conductor network is overworked
     /// it may decide not to deliver some of the signals.
+    ///
+    /// Returns the agents that the signal could not be sent to, because no URL
+    /// is known for them or sending to their URL failed.
     async fn send_remote_signal(
         &self,
         to_agent_list: Vec<(AgentPubKey, ExternIO, Signature)>,
-    ) -> HolochainP2pResult<()> {
+    ) -> HolochainP2pResult<Vec<AgentPubKey>> {
         self.sender
             .send_remote_signal(self.dna_hash(), to_agent_list)
             .await
@@ -435,6 +453,15 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    async fn get_agent_presence(
+        &self,
+        agents: Vec<AgentPubKey>,
+    ) -> HolochainP2pResult<Vec<AgentPresence>> {
+        self.sender
+            .get_agent_presence(self.dna_hash(), agents)
+            .await
+    }
+
     /// Send a validation receipt to a remote node.
     async fn send_validation_receipts(
         &self,
@@ -1,6 +1,7 @@
 use bytes::Bytes;
 use holo_hash::AgentPubKey;
 use holochain_keystore::MetaLairClient;
+use holochain_types::network::ArcPolicy;
 use kitsune2_api::{AgentId, AgentInfo, BoxFut, DhtArc, K2Error, K2Result, LocalAgent, Signer};
 use parking_lot::Mutex;
 use std::fmt::{Debug, Formatter};
@@ -30,12 +31,28 @@ fn apply_arc_factor(arc: DhtArc, factor: u32) -> DhtArc {
     }
 }
 
+/// An [ArcPolicy] shared by the local agents of a space, so that changing it
+/// applies to all of them.
+pub type SharedArcPolicy = Arc<Mutex<ArcPolicy>>;
+
+/// Check that an [ArcPolicy] can be applied to a space.
+///
+/// A target arc factor > 1 is not yet allowed until sharding is implemented.
+pub fn check_arc_policy(arc_policy: &ArcPolicy) -> crate::HolochainP2pResult<()> {
+    if matches!(arc_policy, ArcPolicy::TargetArcFactor(factor) if *factor > 1) {
+        return Err(crate::HolochainP2pError::other(
+            "A target arc factor > 1 is not yet allowed until sharding is implemented",
+        ));
+    }
+    Ok(())
+}
+
 struct LocalAgentInner {
     callback: Option<Arc<dyn Fn() + 'static + Send + Sync>>,
     /// The storage arc that the agent is currently claiming authority over.
     storage_arc: DhtArc,
-    /// The storage arc that the agent is trying to cover.
-    target_arc: DhtArc,
+    /// The most recent target arc hint, before the arc policy is applied.
+    target_arc_hint: DhtArc,
 }
 
 /// Holochain implementation of a Kitsune2 [LocalAgent] and [Signer].
@@ -48,8 +65,8 @@ pub struct HolochainP2pLocalAgent {
     keystore_client: MetaLairClient,
     /// The inner state that can be modified during the lifecycle of the agent
     inner: Mutex<LocalAgentInner>,
-    /// The target arc factor to apply to hints.
-    target_arc_factor: u32,
+    /// The arc policy to apply to hints.
+    arc_policy: SharedArcPolicy,
 }
 
 impl HolochainP2pLocalAgent {
@@ -68,11 +85,26 @@ impl HolochainP2pLocalAgent {
             inner: Mutex::new(LocalAgentInner {
                 callback: None,
                 storage_arc: DhtArc::Empty,
-                target_arc: apply_arc_factor(initial_target_arc, initial_target_arc_factor),
+                target_arc_hint: initial_target_arc,
             }),
-            target_arc_factor: initial_target_arc_factor,
+            arc_policy: Arc::new(Mutex::new(ArcPolicy::TargetArcFactor(
+                initial_target_arc_factor,
+            ))),
         }
     }
+
+    /// Use an arc policy that is shared with other agents, in place of the
+    /// initial target arc factor.
+    ///
+    /// Changes to the policy take effect the next time the target arc is read.
+    pub fn with_arc_policy(mut self, arc_policy: SharedArcPolicy) -> Self {
+        self.arc_policy = arc_policy;
+        self
+    }
+
+    fn arc_policy(&self) -> ArcPolicy {
+        *self.arc_policy.lock()
+    }
 }
 
 impl Debug for HolochainP2pLocalAgent {
@@ -117,6 +149,10 @@ impl LocalAgent for HolochainP2pLocalAgent {
     }
 
     fn get_cur_storage_arc(&self) -> DhtArc {
+        if self.arc_policy() == ArcPolicy::Empty {
+            return DhtArc::Empty;
+        }
+
         self.inner.lock().storage_arc
     }
 
@@ -125,18 +161,21 @@ impl LocalAgent for HolochainP2pLocalAgent {
     }
 
     fn get_tgt_storage_arc(&self) -> DhtArc {
-        self.inner.lock().target_arc
+        match self.arc_policy() {
+            ArcPolicy::Full => DhtArc::FULL,
+            ArcPolicy::Empty => DhtArc::Empty,
+            ArcPolicy::TargetArcFactor(factor) => {
+                apply_arc_factor(self.inner.lock().target_arc_hint, factor.min(1))
+            }
+        }
     }
 
     fn set_tgt_storage_arc_hint(&self, arc: DhtArc) {
-        let factor = if self.target_arc_factor > 1 {
+        if matches!(self.arc_policy(), ArcPolicy::TargetArcFactor(factor) if factor > 1) {
             tracing::error!("Received target arc factor > 1, this is not yet allowed until sharding is implemented!");
-            1
-        } else {
-            self.target_arc_factor
-        };
+        }
 
-        self.inner.lock().target_arc = apply_arc_factor(arc, factor);
+        self.inner.lock().target_arc_hint = arc;
     }
 }
 
@@ -171,4 +210,32 @@ mod tests {
             assert_eq!(expect, apply_arc_factor(orig, factor));
         }
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shared_arc_policy_overrides_hints() {
+        let keystore = holochain_keystore::test_keystore();
+        let agent = keystore.new_sign_keypair_random().await.unwrap();
+        let arc_policy: SharedArcPolicy = Arc::new(Mutex::new(ArcPolicy::TargetArcFactor(1)));
+        let local_agent = HolochainP2pLocalAgent::new(agent, DhtArc::FULL, 1, keystore)
+            .with_arc_policy(arc_policy.clone());
+
+        local_agent.set_cur_storage_arc(DhtArc::Arc(0, 100));
+        local_agent.set_tgt_storage_arc_hint(DhtArc::Arc(0, 200));
+        assert_eq!(DhtArc::Arc(0, 200), local_agent.get_tgt_storage_arc());
+
+        *arc_policy.lock() = ArcPolicy::Full;
+        assert_eq!(DhtArc::FULL, local_agent.get_tgt_storage_arc());
+        assert_eq!(DhtArc::Arc(0, 100), local_agent.get_cur_storage_arc());
+
+        *arc_policy.lock() = ArcPolicy::Empty;
+        assert_eq!(DhtArc::Empty, local_agent.get_tgt_storage_arc());
+        assert_eq!(DhtArc::Empty, local_agent.get_cur_storage_arc());
+
+        // Hints received while pinned are used again once the pin is removed
+        local_agent.set_tgt_storage_arc_hint(DhtArc::Arc(0, 300));
+        *arc_policy.lock() = ArcPolicy::TargetArcFactor(0);
+        assert_eq!(DhtArc::Empty, local_agent.get_tgt_storage_arc());
+        *arc_policy.lock() = ArcPolicy::TargetArcFactor(1);
+        assert_eq!(DhtArc::Arc(0, 300), local_agent.get_tgt_storage_arc());
+    }
 }
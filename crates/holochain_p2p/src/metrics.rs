@@ -81,3 +81,19 @@ pub fn p2p_recv_remote_signal_direct_metric() -> &'static P2pRecvRemoteSignalDir
             .build()
     })
 }
+
+/// A counter metric for counting the bytes sent and received in each space.
+pub type P2pBandwidthMetric = Counter<u64>;
+
+static P2P_BANDWIDTH_METRIC: OnceLock<P2pBandwidthMetric> = OnceLock::new();
+
+/// Metric for counting the bytes sent and received in each space, by kind of traffic.
+pub fn p2p_bandwidth_metric() -> &'static P2pBandwidthMetric {
+    P2P_BANDWIDTH_METRIC.get_or_init(|| {
+        meter("hc.holochain_p2p")
+            .u64_counter("hc.holochain_p2p.bandwidth")
+            .with_unit("By")
+            .with_description("The number of bytes sent to and received from peers in a space.")
+            .build()
+    })
+}
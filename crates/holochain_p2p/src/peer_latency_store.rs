@@ -1,5 +1,6 @@
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
+use holochain_types::network::PeerLatency;
 use kitsune2_api::{DynSpace, Url};
 use std::collections::{HashMap, VecDeque};
 use std::future::Future;
@@ -170,6 +171,20 @@ impl LatencyData {
         self.estimates.get(url).map(|entry| entry.weight())
     }
 
+    /// Returns how long ago the last successful ping to the given URL was
+    /// recorded, or `None` if there is no estimate for the URL.
+    pub(crate) fn last_sampled_ago(&self, url: &Url) -> Option<Duration> {
+        self.last_sampled_ago_at(url, Instant::now())
+    }
+
+    /// Returns how long before the given reference instant the last
+    /// successful ping to the given URL was recorded.
+    fn last_sampled_ago_at(&self, url: &Url, now: Instant) -> Option<Duration> {
+        self.estimates
+            .get(url)
+            .map(|entry| now.saturating_duration_since(entry.recorded_at))
+    }
+
     /// Records a full-round ping failure for a URL, incrementing the
     /// consecutive failure counter.
     pub(crate) fn record_failure(&mut self, url: &Url) {
@@ -207,6 +222,49 @@ impl LatencyData {
             .is_none_or(|e| e.is_expired_at(now) || e.is_due_for_refresh(now))
     }
 
+    /// Returns the estimate and failure bookkeeping of every tracked URL,
+    /// ordered by URL.
+    pub(crate) fn dump(&self) -> Vec<PeerLatency> {
+        self.dump_at(Instant::now())
+    }
+
+    /// Returns the estimate and failure bookkeeping of every tracked URL,
+    /// relative to the given reference instant.
+    fn dump_at(&self, now: Instant) -> Vec<PeerLatency> {
+        let mut urls: Vec<&Url> = self
+            .estimates
+            .keys()
+            .chain(
+                self.consecutive_failures
+                    .keys()
+                    .filter(|url| !self.estimates.contains_key(*url)),
+            )
+            .collect();
+        urls.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        urls.into_iter()
+            .map(|url| {
+                let estimate = self.estimates.get(url);
+                PeerLatency {
+                    url: url.clone(),
+                    samples_us: estimate
+                        .map(|e| e.samples.iter().map(|s| s.as_micros() as u64).collect())
+                        .unwrap_or_default(),
+                    average_us: estimate.map(|e| e.average.as_micros() as u64),
+                    consecutive_failures: self.consecutive_failures.get(url).copied().unwrap_or(0),
+                    failed: self.has_failed_pings(url),
+                    sampled_ms_ago: estimate
+                        .map(|e| now.saturating_duration_since(e.recorded_at).as_millis() as u64),
+                    expires_in_ms: estimate.map(|e| {
+                        (e.recorded_at + EXPIRY_DURATION)
+                            .saturating_duration_since(now)
+                            .as_millis() as u64
+                    }),
+                }
+            })
+            .collect()
+    }
+
     /// Removes entries whose `recorded_at` is older than [`EVICTION_DURATION`],
     /// bounding memory growth under peer churn.
     fn evict_stale(&mut self) {
@@ -591,6 +649,23 @@ mod tests {
         assert!(data.get_weight(&test_url("a")).is_none());
     }
 
+    #[test]
+    fn last_sampled_ago_tracks_latest_sample() {
+        let mut data = LatencyData::default();
+        let url = test_url("a");
+        assert!(data.last_sampled_ago(&url).is_none());
+
+        data.record_sample(url.clone(), Duration::from_millis(50));
+
+        let future = future_instant(Duration::from_secs(5 * 60));
+        let ago = data.last_sampled_ago_at(&url, future).unwrap();
+        assert!(ago >= Duration::from_secs(5 * 60), "ago was {ago:?}");
+
+        // A failed ping round does not count as contact.
+        data.record_failure(&url);
+        assert_eq!(data.last_sampled_ago_at(&url, future), Some(ago));
+    }
+
     #[test]
     fn evict_stale_removes_entries_older_than_eviction_duration() {
         let mut data = LatencyData::default();
@@ -626,6 +701,38 @@ mod tests {
         assert!(data.get_latency_including_stale(&url).is_some());
     }
 
+    #[test]
+    fn dump_reports_samples_failures_and_expiry() {
+        let mut data = LatencyData::default();
+        let sampled_url = test_url("a");
+        let failing_url = test_url("b");
+
+        data.record_sample(sampled_url.clone(), Duration::from_millis(100));
+        data.record_sample(sampled_url.clone(), Duration::from_millis(200));
+        for _ in 0..CONSECUTIVE_FAILURE_THRESHOLD {
+            data.record_failure(&failing_url);
+        }
+
+        let dump = data.dump_at(future_instant(Duration::from_secs(30 * 60)));
+        assert_eq!(dump.len(), 2);
+
+        assert_eq!(dump[0].url, sampled_url);
+        assert_eq!(dump[0].samples_us, vec![100_000, 200_000]);
+        assert_eq!(dump[0].average_us, Some(150_000));
+        assert_eq!(dump[0].consecutive_failures, 0);
+        assert!(!dump[0].failed);
+        // 30 minutes after sampling, with some slack for the test run time.
+        let expires_in_ms = dump[0].expires_in_ms.unwrap();
+        assert!(expires_in_ms <= 30 * 60 * 1000 && expires_in_ms > 29 * 60 * 1000);
+
+        assert_eq!(dump[1].url, failing_url);
+        assert!(dump[1].samples_us.is_empty());
+        assert_eq!(dump[1].average_us, None);
+        assert_eq!(dump[1].consecutive_failures, CONSECUTIVE_FAILURE_THRESHOLD);
+        assert!(dump[1].failed);
+        assert_eq!(dump[1].expires_in_ms, None);
+    }
+
     #[test]
     fn needs_ping_returns_true_for_unknown_peer() {
         let data = LatencyData::default();
@@ -47,6 +47,17 @@ pub type GetConductorStore = Arc<
     dyn Fn() -> BoxFut<'static, holochain_state::conductor::ConductorStore> + 'static + Send + Sync,
 >;
 
+/// Callback function to wrap the Kitsune2 transport factory.
+///
+/// This is used in tests to inject faults into the traffic between conductors.
+#[cfg(feature = "test_utils")]
+pub type WrapTransportFactory = Arc<
+    dyn Fn(kitsune2_api::DynTransportFactory) -> kitsune2_api::DynTransportFactory
+        + 'static
+        + Send
+        + Sync,
+>;
+
 /// Configure reporting.
 #[derive(Default)]
 pub enum ReportConfig {
@@ -161,6 +172,13 @@ pub struct HolochainP2pConfig {
     /// This flag is only used in tests.
     #[cfg(feature = "test_utils")]
     pub disable_gossip: bool,
+
+    /// If set, the transport factory of the Kitsune2 builder is passed through this
+    /// callback, which may wrap it.
+    ///
+    /// This is only used in tests.
+    #[cfg(feature = "test_utils")]
+    pub wrap_transport: Option<WrapTransportFactory>,
 }
 
 impl std::fmt::Debug for HolochainP2pConfig {
@@ -183,7 +201,11 @@ impl std::fmt::Debug for HolochainP2pConfig {
         {
             dbg.field("disable_bootstrap", &self.disable_bootstrap)
                 .field("disable_publish", &self.disable_publish)
-                .field("disable_gossip", &self.disable_gossip);
+                .field("disable_gossip", &self.disable_gossip)
+                .field(
+                    "wrap_transport",
+                    &self.wrap_transport.as_ref().map(|_| "<callback>"),
+                );
         }
 
         dbg.finish()
@@ -225,6 +247,8 @@ impl Default for HolochainP2pConfig {
             disable_publish: false,
             #[cfg(feature = "test_utils")]
             disable_gossip: false,
+            #[cfg(feature = "test_utils")]
+            wrap_transport: None,
         }
     }
 }
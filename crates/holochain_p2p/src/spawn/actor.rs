@@ -1,6 +1,10 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::actor::{GetLinksRequestOptions, NetworkRequestOptions};
+use crate::bandwidth::{
+    BandwidthGossipFactory, BandwidthTracker, BandwidthTransportFactory, Direction, HcGossipConfig,
+    HcGossipModConfig, TrafficKind,
+};
 use crate::metrics::{
     p2p_handle_incoming_request_duration_metric, p2p_handle_incoming_request_ignored_metric,
     p2p_outgoing_request_duration_metric, p2p_recv_remote_signal_direct_metric,
@@ -8,10 +12,11 @@ use crate::metrics::{
 };
 use crate::peer_latency_store::{PeerLatencyService, PingFn};
 use crate::*;
+use holochain_types::app::RoleNetworkConfig;
 use holochain_types::cell_config_overrides::CellConfigOverrides;
 use kitsune2_api::*;
 use kitsune2_core::get_responsive_remote_agents_near_location;
-use std::collections::HashMap;
+use std::collections::{hash_map, HashMap};
 use std::future::Future;
 use std::sync::{Mutex, Weak};
 use std::time::Duration;
@@ -78,13 +83,20 @@ impl event::HcP2pHandler for WrapEvtSender {
         signal: Vec<u8>,
         from_agent: AgentPubKey,
         signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
         let byte_count = signal.len();
         timing_trace!(
             true,
             {
-                self.0
-                    .handle_remote_signal_direct(dna_hash, to_agent, signal, from_agent, signature)
+                self.0.handle_remote_signal_direct(
+                    dna_hash,
+                    to_agent,
+                    signal,
+                    from_agent,
+                    signature,
+                    idempotency_key,
+                )
             },
             byte_count,
             a = "recv_remote_signal_direct",
@@ -280,19 +292,32 @@ impl Pending {
     }
 }
 
+/// The arc policy of a space, shared by its local agents.
+struct SpaceArcPolicy {
+    policy: SharedArcPolicy,
+    /// Whether the policy was set through [`actor::HcP2p::set_arc_policy`], rather than being the
+    /// default policy of the space, which follows its target arc factor.
+    explicit: bool,
+}
+
 pub(crate) struct HolochainP2pActor {
     this: Weak<Self>,
     target_arc_factor: u32,
+    arc_policies: Mutex<HashMap<DnaHash, SpaceArcPolicy>>,
     compat: NetworkCompatParams,
     preflight: Arc<Mutex<bytes::Bytes>>,
     evt_sender: Arc<std::sync::OnceLock<WrapEvtSender>>,
     lair_client: holochain_keystore::MetaLairClient,
     kitsune: DynKitsune,
     space_overridable_kitsune2_config: Config,
+    kitsune2_config: Option<serde_json::Value>,
+    space_networks: Mutex<HashMap<DnaHash, RoleNetworkConfig>>,
     get_conductor_store: GetConductorStore,
     pending: Arc<Mutex<Pending>>,
     latency_service: PeerLatencyService,
+    bandwidth: Arc<BandwidthTracker>,
     pruning_task_abort_handle: AbortHandle,
+    bandwidth_usage_task_abort_handle: AbortHandle,
     request_timeout: Duration,
     incoming_request_concurrency_limit_semaphore: Arc<Semaphore>,
 }
@@ -306,9 +331,14 @@ impl std::fmt::Debug for HolochainP2pActor {
 const EVT_REG_ERR: &str = "event handler not registered";
 /// Timeout for each individual ping request.
 const PING_TIMEOUT: Duration = Duration::from_secs(5);
+/// How often the traffic counted towards the daily bandwidth limits of apps is persisted.
+const BANDWIDTH_USAGE_SAVE_INTERVAL: Duration = Duration::from_secs(30);
 
 impl SpaceHandler for HolochainP2pActor {
     fn recv_notify(&self, from_peer: Url, space: SpaceId, data: bytes::Bytes) -> K2Result<()> {
+        self.bandwidth
+            .record(&space, TrafficKind::Other, Direction::Received, data.len());
+
         for msg in WireMessage::decode_batch(&data).map_err(|err| {
             K2Error::other_src("decode incoming holochain_p2p wire message batch", err)
         })? {
@@ -541,6 +571,7 @@ impl kitsune2_api::BootstrapFactory for BootWrapFact {
 impl Drop for HolochainP2pActor {
     fn drop(&mut self) {
         self.pruning_task_abort_handle.abort();
+        self.bandwidth_usage_task_abort_handle.abort();
     }
 }
 
@@ -569,8 +600,18 @@ impl HolochainP2pActor {
                 tracing::info!("Running with publish disabled");
                 builder.publish = Arc::new(test::NoopPublishFactory);
             }
+            if let Some(wrap_transport) = &config.wrap_transport {
+                tracing::info!("Running with a wrapped transport");
+                builder.transport = wrap_transport(builder.transport);
+            }
         }
 
+        // Count the traffic of each space, and pause or hold back gossip in the
+        // spaces of apps that have bandwidth limits.
+        let bandwidth = Arc::new(BandwidthTracker::default());
+        builder.transport = BandwidthTransportFactory::create(bandwidth.clone(), builder.transport);
+        builder.gossip = BandwidthGossipFactory::create(bandwidth.clone(), builder.gossip);
+
         builder.auth_material_bootstrap = config.auth_material_bootstrap;
         builder.auth_material_relay = config.auth_material_relay;
 
@@ -636,6 +677,7 @@ impl HolochainP2pActor {
 
         // Then override any configuration values provided by the user and set kitsune2_config if `network_config` is `Some`.
         let mut space_overridable_kitsune2_config = Config::default();
+        let kitsune2_config = config.network_config.clone();
         if let Some(network_config) = config.network_config {
             builder.config.set_module_config(&network_config)?;
             Self::kitsune2_overridable_params_from_value(
@@ -661,6 +703,10 @@ impl HolochainP2pActor {
             kitsune2,
             db_getter,
         );
+        let bandwidth_usage_task_abort_handle = HolochainP2pActor::spawn_bandwidth_usage_task(
+            bandwidth.clone(),
+            config.get_conductor_store.clone(),
+        );
         let ping_pending = pending.clone();
         let ping_fn: PingFn = Arc::new(move |space: DynSpace, url: Url| {
             let pending = Arc::clone(&ping_pending);
@@ -671,6 +717,7 @@ impl HolochainP2pActor {
         Ok(Arc::new_cyclic(|this| Self {
             this: this.clone(),
             target_arc_factor: config.target_arc_factor,
+            arc_policies: Mutex::new(HashMap::new()),
             compat: config.compat,
             preflight,
             evt_sender,
@@ -679,8 +726,12 @@ impl HolochainP2pActor {
             get_conductor_store: config.get_conductor_store.clone(),
             pending,
             latency_service,
+            bandwidth,
             space_overridable_kitsune2_config,
+            kitsune2_config,
+            space_networks: Mutex::new(HashMap::new()),
             pruning_task_abort_handle,
+            bandwidth_usage_task_abort_handle,
             request_timeout: config.request_timeout,
             incoming_request_concurrency_limit_semaphore: Arc::new(Semaphore::new(
                 config.incoming_request_concurrency_limit as usize,
@@ -786,6 +837,31 @@ impl HolochainP2pActor {
         .abort_handle()
     }
 
+    // Persists the traffic counted towards the daily bandwidth limits of apps at an interval, so
+    // that a restart doesn't start a new day.
+    fn spawn_bandwidth_usage_task(
+        bandwidth: Arc<BandwidthTracker>,
+        get_conductor_store: GetConductorStore,
+    ) -> AbortHandle {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(BANDWIDTH_USAGE_SAVE_INTERVAL).await;
+
+                let changed_usage = bandwidth.take_changed_usage();
+                if changed_usage.is_empty() {
+                    continue;
+                }
+                let store = get_conductor_store().await;
+                for (installed_app_id, usage) in changed_usage {
+                    if let Err(err) = store.put_bandwidth_usage(&installed_app_id, &usage).await {
+                        tracing::warn!(?err, %installed_app_id, "Could not persist bandwidth usage");
+                    }
+                }
+            }
+        })
+        .abort_handle()
+    }
+
     async fn get_peers_for_location(
         &self,
         space: &DynSpace,
@@ -881,6 +957,139 @@ impl HolochainP2pActor {
         Ok(selected)
     }
 
+    /// The arc policy shared by the local agents of a space.
+    ///
+    /// Spaces without a policy set use the target arc factor of the space.
+    fn arc_policy(&self, dna_hash: &DnaHash) -> SharedArcPolicy {
+        let target_arc_factor = self.target_arc_factor_for(dna_hash);
+        self.arc_policies
+            .lock()
+            .expect("arc policies lock poisoned")
+            .entry(dna_hash.clone())
+            .or_insert_with(|| SpaceArcPolicy {
+                policy: Arc::new(parking_lot::Mutex::new(ArcPolicy::TargetArcFactor(
+                    target_arc_factor,
+                ))),
+                explicit: false,
+            })
+            .policy
+            .clone()
+    }
+
+    /// Forget the default arc policy of a space, which has no local agents, so that it follows
+    /// the target arc factor the space has when it is next joined.
+    ///
+    /// Policies set through [`actor::HcP2p::set_arc_policy`] are kept.
+    fn forget_default_arc_policy(&self, dna_hash: &DnaHash) {
+        let mut arc_policies = self
+            .arc_policies
+            .lock()
+            .expect("arc policies lock poisoned");
+        if arc_policies
+            .get(dna_hash)
+            .is_some_and(|arc_policy| !arc_policy.explicit)
+        {
+            arc_policies.remove(dna_hash);
+        }
+    }
+
+    /// The network settings of a space, from the role of the cells that joined it.
+    fn space_network(&self, dna_hash: &DnaHash) -> RoleNetworkConfig {
+        self.space_networks
+            .lock()
+            .expect("space networks lock poisoned")
+            .get(dna_hash)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The target arc factor of a space, from its network settings or otherwise the conductor
+    /// settings.
+    fn target_arc_factor_for(&self, dna_hash: &DnaHash) -> u32 {
+        self.space_network(dna_hash)
+            .target_arc_factor
+            .unwrap_or(self.target_arc_factor)
+    }
+
+    /// The timeout for a request, from the request options, the network settings of the space
+    /// or otherwise the conductor settings.
+    fn timeout_for(&self, dna_hash: &DnaHash, options: &NetworkRequestOptions) -> Duration {
+        options
+            .timeout_ms
+            .map(Duration::from_millis)
+            .or_else(|| {
+                self.space_network(dna_hash)
+                    .request_timeout_s
+                    .map(Duration::from_secs)
+            })
+            .unwrap_or(self.request_timeout)
+    }
+
+    /// Find the URL to send a request addressed to a single agent to.
+    ///
+    /// An agent that has joined several spaces publishes an agent info to each of them, and
+    /// those don't necessarily carry the same URL, for example while the infos of a peer whose
+    /// address changed are being refreshed. When this space knows the agent but its URL has
+    /// failed pings, the URLs of the agent's live infos in the other spaces are considered too,
+    /// and the healthiest one is used. An agent that this space doesn't know is never reached
+    /// through another space.
+    ///
+    /// The chosen URL is touched so that the latency service tracks it.
+    async fn url_for_agent(
+        &self,
+        space: &DynSpace,
+        agent: &AgentPubKey,
+    ) -> HolochainP2pResult<Option<Url>> {
+        let agent_id = agent.to_k2_agent();
+        let Some(space_url) = space
+            .peer_store()
+            .get(agent_id.clone())
+            .await?
+            .and_then(|i| i.url.clone())
+        else {
+            return Ok(None);
+        };
+
+        let has_failed_pings = self
+            .latency_service
+            .store()
+            .lock()
+            .expect("latency data lock poisoned")
+            .has_failed_pings(&space_url);
+        let mut urls = vec![space_url];
+        if has_failed_pings {
+            let now = kitsune2_api::Timestamp::now();
+            for space_id in self.kitsune.list_spaces() {
+                let Some(other_space) = self.kitsune.space_if_exists(space_id).await else {
+                    continue;
+                };
+                // Only spaces where the agent is currently known, not ones it has left.
+                if let Some(url) = other_space
+                    .peer_store()
+                    .get(agent_id.clone())
+                    .await?
+                    .filter(|i| !i.is_tombstone && i.expires_at > now)
+                    .and_then(|i| i.url.clone())
+                {
+                    if !urls.contains(&url) {
+                        urls.push(url);
+                    }
+                }
+            }
+        }
+
+        let url = {
+            let store = self.latency_service.store();
+            let data = store.lock().expect("latency data lock poisoned");
+            crate::weighted_selection::select_healthy_url(&data, &urls)
+        };
+        if let Some(url) = &url {
+            self.latency_service.touch(url.clone(), space.clone());
+        }
+
+        Ok(url)
+    }
+
     /// Check whether a message should be bridged locally to some other agent on this node.
     ///
     /// Checks whether this message is destined for our own URL.
@@ -945,10 +1154,7 @@ impl HolochainP2pActor {
     {
         let req = WireMessage::encode_batch(&[&req])?;
 
-        let timeout = match options.timeout_ms {
-            Some(ms) => Duration::from_millis(ms),
-            None => self.request_timeout,
-        };
+        let timeout = self.timeout_for(&dna_hash, &options);
 
         let (s, r) = tokio::sync::oneshot::channel();
         self.pending.lock().unwrap().register(msg_id, s, timeout);
@@ -1047,6 +1253,38 @@ impl HolochainP2pActor {
     ) -> HolochainP2pResult<Option<Config>> {
         let mut override_needed = false;
         let config = self.space_overridable_kitsune2_config.clone();
+        if let Some(advanced) = space_overrides.network.advanced {
+            let serde_json::Value::Object(modules) = advanced else {
+                return Err(HolochainP2pError::other(
+                    "Advanced network settings of a role must be an object of module configs",
+                ));
+            };
+            // Merge the module configs of the role over those of the conductor, so that only
+            // the fields set by the role are overridden.
+            let mut merged = serde_json::Map::new();
+            for (module, module_config) in modules {
+                let mut conductor_module_config = self
+                    .kitsune2_config
+                    .as_ref()
+                    .and_then(|config| config.get(&module))
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null);
+                merge_json(&mut conductor_module_config, module_config);
+                merged.insert(module, conductor_module_config);
+            }
+            config.set_module_config(&serde_json::Value::Object(merged))?;
+            override_needed = true;
+        }
+
+        if let Some(disable_gossip) = space_overrides.network.disable_gossip {
+            config.set_module_config(&HcGossipModConfig {
+                hc_gossip: HcGossipConfig {
+                    disabled: disable_gossip,
+                },
+            })?;
+            override_needed = true;
+        }
+
         if let Some(bootstrap_url) = space_overrides.bootstrap_url.as_ref() {
             // get current bootstrap config and override server_url
             let mut core_bootstrap_config: kitsune2_core::factories::CoreBootstrapModConfig =
@@ -1119,7 +1357,8 @@ impl HolochainP2pActor {
                 | CountLinksRes { msg_id, .. }
                 | GetAgentActivityRes { msg_id, .. }
                 | MustGetAgentActivityRes { msg_id, .. }
-                | SendValidationReceiptsRes { msg_id } => {
+                | SendValidationReceiptsRes { msg_id }
+                | RemoteSignalDirectRes { msg_id, .. } => {
                     if let Some(resp) = pending.lock().unwrap().respond(msg_id) {
                         let _ = resp.send(msg);
                     }
@@ -1438,6 +1677,7 @@ impl HolochainP2pActor {
                             signal,
                             from_agent,
                             signature,
+                            None,
                         )
                         .await;
                     record_incoming_request_duration(&[opentelemetry::KeyValue::new(
@@ -1452,6 +1692,58 @@ impl HolochainP2pActor {
                         )],
                     );
                 }
+                RemoteSignalDirectReq {
+                    msg_id,
+                    to_agent,
+                    signal,
+                    from_agent,
+                    signature,
+                    idempotency_key,
+                } => {
+                    let resp = match evt_sender
+                        .get()
+                        .ok_or_else(|| HolochainP2pError::other(EVT_REG_ERR))?
+                        .handle_remote_signal_direct(
+                            dna_hash.clone(),
+                            to_agent.clone(),
+                            signal,
+                            from_agent,
+                            signature,
+                            Some(idempotency_key),
+                        )
+                        .await
+                    {
+                        Ok(outcome) => RemoteSignalDirectRes { msg_id, outcome },
+                        Err(err) => ErrorRes {
+                            msg_id,
+                            error: format!("{err:?}"),
+                        },
+                    };
+
+                    // Direct signals can be sent between agents on the same conductor,
+                    // so the response may need to be bridged.
+                    if let Some(this) = this.upgrade() {
+                        if let Err(err) = this
+                            .send_notify_response(space_id, from_peer, msg_id, resp)
+                            .await
+                        {
+                            tracing::debug!(?err, "Error sending remote signal direct response");
+                        }
+                    } else {
+                        tracing::debug!("HolochainP2pActor has been dropped");
+                    }
+                    record_incoming_request_duration(&[opentelemetry::KeyValue::new(
+                        "to_agent",
+                        format!("{to_agent:?}"),
+                    )]);
+                    p2p_recv_remote_signal_direct_metric().add(
+                        1,
+                        &[opentelemetry::KeyValue::new(
+                            "dna_hash",
+                            dna_hash.to_string(),
+                        )],
+                    );
+                }
                 PublishCountersignEvt { op } => {
                     evt_sender
                         .get()
@@ -1551,6 +1843,77 @@ where
     }
 }
 
+/// Await all of the futures and return every valid, non-empty response.
+///
+/// This is the aggregating counterpart of [`select_ok_non_empty`], used for requests that are not
+/// a race. Responses are collected until all futures have completed or `timeout` elapses, whichever
+/// comes first, so that the caller can merge what each authority holds.
+///
+/// If no future produced a non-empty response then, like [`select_ok_non_empty`], the last valid but
+/// empty response is returned, and failing that the last error.
+async fn gather_all_responses<I, O>(
+    tag: &'static str,
+    futures: I,
+    timeout: Duration,
+    is_empty: fn(&O) -> bool,
+) -> HolochainP2pResult<Vec<O>>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = HolochainP2pResult<O>> + Unpin,
+{
+    let mut futures = futures
+        .into_iter()
+        .collect::<futures::stream::FuturesUnordered<_>>();
+    let mut responses = Vec::new();
+    let mut empty_response = None;
+    let mut last_error = None;
+
+    let gather = async {
+        while let Some(out) = futures::StreamExt::next(&mut futures).await {
+            match out {
+                Ok(response) if !is_empty(&response) => responses.push(response),
+                Ok(response) => empty_response = Some(response),
+                Err(err) => {
+                    tracing::debug!(?err, tag, "peer request failed during aggregated request");
+                    last_error = Some(err);
+                }
+            }
+        }
+    };
+    if tokio::time::timeout(timeout, gather).await.is_err() {
+        tracing::debug!(
+            tag,
+            non_empty = responses.len(),
+            pending = futures.len(),
+            "timed out while aggregating responses"
+        );
+    }
+
+    if !responses.is_empty() {
+        return Ok(responses);
+    }
+    match (empty_response, last_error) {
+        (Some(response), _) => Ok(vec![response]),
+        (None, Some(err)) => Err(err),
+        (None, None) => Err(HolochainP2pError::other(format!(
+            "{tag} timed out before any response arrived"
+        ))),
+    }
+}
+
+/// Merge a JSON value over another one, recursing into objects so that only
+/// the fields that are set in `over` replace those in `base`.
+fn merge_json(base: &mut serde_json::Value, over: serde_json::Value) {
+    match (base, over) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(over)) => {
+            for (key, value) in over {
+                merge_json(base.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, over) => *base = over,
+    }
+}
+
 /// Collects non-empty responses from `futures` until `required_responses`
 /// have arrived, returning as soon as that threshold is met so one slow
 /// peer cannot stall an operation whose threshold is already reached.
@@ -1685,26 +2048,87 @@ impl actor::HcP2p for HolochainP2pActor {
         config_override: Option<CellConfigOverrides>,
     ) -> BoxFut<'_, HolochainP2pResult<()>> {
         Box::pin(async move {
+            let network = config_override
+                .as_ref()
+                .map(|overrides| overrides.network.clone())
+                .unwrap_or_default();
+            if network.target_arc_factor.is_some_and(|factor| factor > 1) {
+                return Err(HolochainP2pError::other(
+                    "A target arc factor > 1 is not yet allowed until sharding is implemented",
+                ));
+            }
             let config_override = match config_override {
                 Some(overrides) => self.space_config_override(overrides)?,
                 None => None,
             };
+            // The advanced settings and whether gossip is disabled only go into the Kitsune2
+            // config of the space.
+            let network = RoleNetworkConfig {
+                advanced: None,
+                ..network
+            };
+            // The Kitsune2 config of a space is only set when the space is created, by the first
+            // cell that joins it, so the settings of later cells would only partly apply.
+            let creates_space = match self
+                .space_networks
+                .lock()
+                .expect("space networks lock poisoned")
+                .entry(dna_hash.clone())
+            {
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(network);
+                    true
+                }
+                hash_map::Entry::Occupied(entry) => {
+                    if *entry.get() != network {
+                        tracing::warn!(
+                            ?dna_hash,
+                            ?agent_pub_key,
+                            space_network = ?entry.get(),
+                            ignored_network = ?network,
+                            "Ignoring network settings that differ from those the space was created with"
+                        );
+                    }
+                    false
+                }
+            };
 
             // Create k2 space with config override.
             //
             // This is the only time the space is created,
             // all other calls to get a space should use `space_if_exists`.
-            let space = self
+            let space = match self
                 .kitsune
                 .space(dna_hash.to_k2_space(), config_override)
-                .await?;
+                .await
+            {
+                Ok(space) => space,
+                Err(err) => {
+                    if creates_space {
+                        self.space_networks
+                            .lock()
+                            .expect("space networks lock poisoned")
+                            .remove(&dna_hash);
+                    }
+                    return Err(err.into());
+                }
+            };
 
-            let local_agent: DynLocalAgent = Arc::new(HolochainP2pLocalAgent::new(
-                agent_pub_key,
-                DhtArc::FULL,
-                self.target_arc_factor,
-                self.lair_client.clone(),
-            ));
+            if creates_space {
+                // A default policy from before the space was created may be for another target
+                // arc factor.
+                self.forget_default_arc_policy(&dna_hash);
+            }
+
+            let local_agent: DynLocalAgent = Arc::new(
+                HolochainP2pLocalAgent::new(
+                    agent_pub_key,
+                    DhtArc::FULL,
+                    self.target_arc_factor_for(&dna_hash),
+                    self.lair_client.clone(),
+                )
+                .with_arc_policy(self.arc_policy(&dna_hash)),
+            );
 
             space.local_agent_join(local_agent).await?;
             Ok(())
@@ -1729,8 +2153,17 @@ impl actor::HcP2p for HolochainP2pActor {
                     .is_ok_and(|agents| agents.is_empty())
                 {
                     drop(space);
-                    if let Err(err) = self.kitsune.remove_space(space_id).await {
-                        tracing::warn!(?err, "Failed to remove space after last agent left");
+                    match self.kitsune.remove_space(space_id).await {
+                        Ok(_) => {
+                            self.space_networks
+                                .lock()
+                                .expect("space networks lock poisoned")
+                                .remove(&dna_hash);
+                            self.forget_default_arc_policy(&dna_hash);
+                        }
+                        Err(err) => {
+                            tracing::warn!(?err, "Failed to remove space after last agent left");
+                        }
                     }
                 }
             } else {
@@ -1767,11 +2200,9 @@ impl actor::HcP2p for HolochainP2pActor {
 
             let byte_count = zome_call_params_serialized.0.len();
 
-            let to_url = space
-                .peer_store()
-                .get(to_agent.to_k2_agent())
+            let to_url = self
+                .url_for_agent(&space, &to_agent)
                 .await?
-                .and_then(|i| i.url.clone())
                 .ok_or_else(|| HolochainP2pError::other("call_remote: no url for peer"))?;
 
             let (msg_id, req) =
@@ -1808,7 +2239,7 @@ impl actor::HcP2p for HolochainP2pActor {
         &self,
         dna_hash: DnaHash,
         target_payload_list: Vec<(AgentPubKey, ExternIO, Signature)>,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<AgentPubKey>>> {
         Box::pin(async move {
             let space_id = dna_hash.to_k2_space();
             let space = self
@@ -1820,17 +2251,15 @@ impl actor::HcP2p for HolochainP2pActor {
             let byte_count: usize = target_payload_list.iter().map(|(_, p, _)| p.0.len()).sum();
 
             let mut all = Vec::with_capacity(target_payload_list.len());
+            let mut undelivered = Vec::new();
 
             for (to_agent, payload, signature) in target_payload_list {
-                let to_agent_id = to_agent.to_k2_agent();
-                let to_url = match space
-                    .peer_store()
-                    .get(to_agent_id)
-                    .await?
-                    .and_then(|i| i.url.clone())
-                {
+                let to_url = match self.url_for_agent(&space, &to_agent).await? {
                     Some(to_url) => to_url,
-                    None => continue,
+                    None => {
+                        undelivered.push(to_agent);
+                        continue;
+                    }
                 };
 
                 let req = WireMessage::remote_signal_evt(to_agent.clone(), payload, signature);
@@ -1842,12 +2271,15 @@ impl actor::HcP2p for HolochainP2pActor {
                 if self.should_bridge(&space, to_url.clone()) {
                     if let Err(err) = self.recv_notify(to_url, space_id.clone(), wire_msg) {
                         tracing::debug!(?err, "send_remote_signal failed to bridge call");
+                        undelivered.push(to_agent);
                     }
                 } else {
                     all.push(async {
                         if let Err(err) = self.send_notify(&space, to_url, req).await {
                             tracing::debug!(?err, "send_remote_signal failed");
+                            return Some(to_agent);
                         }
+                        None
                     });
                 }
             }
@@ -1856,10 +2288,10 @@ impl actor::HcP2p for HolochainP2pActor {
 
             if !all.is_empty() {
                 // errors handled in individual futures
-                let _ = futures::future::join_all(all).await;
+                undelivered.extend(futures::future::join_all(all).await.into_iter().flatten());
             }
 
-            let out = Ok(());
+            let out = Ok(undelivered);
 
             timing_trace_out!(out, start, byte_count, a = "send_remote_signal");
 
@@ -1874,7 +2306,7 @@ impl actor::HcP2p for HolochainP2pActor {
         signal: Vec<u8>,
         from_agent: AgentPubKey,
         signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<AgentPubKey>>> {
         Box::pin(async move {
             let space_id = dna_hash.to_k2_space();
             let space = self
@@ -1884,17 +2316,15 @@ impl actor::HcP2p for HolochainP2pActor {
                 .ok_or(HolochainP2pError::K2SpaceNotFound(space_id.clone()))?;
 
             let mut all = Vec::with_capacity(agents.len());
+            let mut undelivered = Vec::new();
 
             for agent in agents {
-                let agent_id = agent.to_k2_agent();
-                let to_url = match space
-                    .peer_store()
-                    .get(agent_id)
-                    .await?
-                    .and_then(|i| i.url.clone())
-                {
+                let to_url = match self.url_for_agent(&space, &agent).await? {
                     Some(to_url) => to_url,
-                    None => continue,
+                    None => {
+                        undelivered.push(agent);
+                        continue;
+                    }
                 };
 
                 let req = WireMessage::remote_signal_direct_evt(
@@ -1914,12 +2344,15 @@ impl actor::HcP2p for HolochainP2pActor {
 
                     if let Err(err) = self.recv_notify(to_url, space_id.clone(), wire_msg) {
                         tracing::debug!(?err, "send_remote_signal_direct failed to bridge call");
+                        undelivered.push(agent);
                     }
                 } else {
                     all.push(async {
                         if let Err(err) = self.send_notify(&space, to_url, req).await {
                             tracing::debug!(?err, "send_remote_signal_direct failed");
+                            return Some(agent);
                         }
+                        None
                     });
                 }
             }
@@ -1928,10 +2361,10 @@ impl actor::HcP2p for HolochainP2pActor {
 
             if !all.is_empty() {
                 // errors handled in individual futures
-                let _ = futures::future::join_all(all).await;
+                undelivered.extend(futures::future::join_all(all).await.into_iter().flatten());
             }
 
-            let out = Ok(());
+            let out = Ok(undelivered);
 
             timing_trace_out!(
                 out,
@@ -1944,55 +2377,127 @@ impl actor::HcP2p for HolochainP2pActor {
         })
     }
 
-    fn publish(
+    fn send_remote_signal_direct_acked(
         &self,
         dna_hash: DnaHash,
-        basis_hash: OpBasis,
-        _source: AgentPubKey,
-        op_hash_list: Vec<DhtOpHash>,
-        _timeout_ms: Option<u64>,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        to_agent: AgentPubKey,
+        signal: Vec<u8>,
+        from_agent: AgentPubKey,
+        signature: Signature,
+        idempotency_key: u64,
+        options: NetworkRequestOptions,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
         Box::pin(async move {
             let space_id = dna_hash.to_k2_space();
-
             let space = self
                 .kitsune
                 .space_if_exists(space_id.clone())
                 .await
                 .ok_or(HolochainP2pError::K2SpaceNotFound(space_id))?;
 
-            // -- actually publish the op hashes -- //
+            let byte_count = signal.len();
 
-            let op_hash_list: Vec<PublishOp> = op_hash_list
-                .into_iter()
-                .map(|h| PublishOp {
-                    op_id: h.to_located_k2_op_id(&basis_hash),
-                    // Published ops request a receipt from the
-                    // receiving validator.
-                    metadata: crate::publish_metadata::encode_publish_metadata(true),
-                })
-                .collect();
+            let to_url = self
+                .url_for_agent(&space, &to_agent)
+                .await?
+                .ok_or_else(|| {
+                    HolochainP2pError::other("send_remote_signal_direct_acked: no url for peer")
+                })?;
 
-            let urls: std::collections::HashSet<Url> = get_responsive_remote_agents_near_location(
-                space.peer_store().clone(),
-                space.local_agent_store().clone(),
-                space.peer_meta_store().clone(),
-                basis_hash.get_loc(),
-                usize::MAX,
-            )
-            .await?
-            .into_iter()
-            .filter_map(|info| {
-                if info.is_tombstone {
-                    return None;
-                }
-                info.url.clone()
-            })
-            .collect();
+            let (msg_id, req) = WireMessage::remote_signal_direct_req(
+                to_agent,
+                signal,
+                from_agent,
+                signature,
+                idempotency_key,
+            );
 
-            for url in urls {
-                space
-                    .publish()
+            let start = std::time::Instant::now();
+
+            let out = self
+                .send_request(
+                    "send_remote_signal_direct_acked",
+                    &space,
+                    to_url,
+                    msg_id,
+                    req,
+                    dna_hash,
+                    options,
+                    None,
+                    |res| match res {
+                        WireMessage::RemoteSignalDirectRes { outcome, .. } => Ok(outcome),
+                        _ => Err(HolochainP2pError::other(format!(
+                            "invalid response to send_remote_signal_direct_acked: {res:?}"
+                        ))),
+                    },
+                )
+                .await;
+
+            timing_trace_out!(
+                out,
+                start,
+                byte_count,
+                a = "send_remote_signal_direct_acked"
+            );
+
+            out
+        })
+    }
+
+    fn publish(
+        &self,
+        dna_hash: DnaHash,
+        basis_hash: OpBasis,
+        _source: AgentPubKey,
+        op_hash_list: Vec<DhtOpHash>,
+        _timeout_ms: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        Box::pin(async move {
+            if self.space_network(&dna_hash).disable_publish == Some(true) {
+                tracing::trace!(?dna_hash, "Publish is disabled, not publishing ops");
+                return Ok(());
+            }
+
+            let space_id = dna_hash.to_k2_space();
+
+            let space = self
+                .kitsune
+                .space_if_exists(space_id.clone())
+                .await
+                .ok_or(HolochainP2pError::K2SpaceNotFound(space_id))?;
+
+            // -- actually publish the op hashes -- //
+
+            let op_hash_list: Vec<PublishOp> = op_hash_list
+                .into_iter()
+                .map(|h| PublishOp {
+                    op_id: h.to_located_k2_op_id(&basis_hash),
+                    // Published ops request a receipt from the
+                    // receiving validator.
+                    metadata: crate::publish_metadata::encode_publish_metadata(true),
+                })
+                .collect();
+
+            let urls: std::collections::HashSet<Url> = get_responsive_remote_agents_near_location(
+                space.peer_store().clone(),
+                space.local_agent_store().clone(),
+                space.peer_meta_store().clone(),
+                basis_hash.get_loc(),
+                usize::MAX,
+            )
+            .await?
+            .into_iter()
+            .filter_map(|info| {
+                if info.is_tombstone {
+                    return None;
+                }
+                info.url.clone()
+            })
+            .collect();
+
+            for url in urls {
+                space
+                    .publish()
                     .publish_ops(op_hash_list.clone(), url)
                     .await?;
             }
@@ -2078,8 +2583,9 @@ impl actor::HcP2p for HolochainP2pActor {
 
             let start = std::time::Instant::now();
 
-            let out = select_ok_non_empty(
-                agents.into_iter().map(|(to_agent, to_url)| {
+            let requests = agents
+                .into_iter()
+                .map(|(to_agent, to_url)| {
                     Box::pin(async {
                         let (msg_id, req) = WireMessage::get_req(to_agent, dht_hash.clone());
 
@@ -2101,43 +2607,56 @@ impl actor::HcP2p for HolochainP2pActor {
                         )
                         .await
                     })
-                }),
-                |wire_ops| match wire_ops {
-                    WireOps::Entry(WireEntryOps {
-                        creates,
-                        deletes,
-                        updates,
-                        entry,
-                        warrants,
-                    }) if creates.is_empty()
-                        && deletes.is_empty()
-                        && updates.is_empty()
-                        && entry.is_none()
-                        && warrants.is_empty() =>
-                    {
-                        true
-                    }
-                    WireOps::Record(WireRecordOps {
-                        action,
-                        deletes,
-                        updates,
-                        entry,
-                        warrants,
-                    }) if action.is_none()
-                        && deletes.is_empty()
-                        && updates.is_empty()
-                        && entry.is_none()
-                        && warrants.is_empty() =>
-                    {
-                        true
-                    }
-                    _ => false,
-                },
-            )
-            .await;
+                })
+                .collect::<Vec<_>>();
+            let is_empty: fn(&WireOps) -> bool = |wire_ops| match wire_ops {
+                WireOps::Entry(WireEntryOps {
+                    creates,
+                    deletes,
+                    updates,
+                    entry,
+                    warrants,
+                }) if creates.is_empty()
+                    && deletes.is_empty()
+                    && updates.is_empty()
+                    && entry.is_none()
+                    && warrants.is_empty() =>
+                {
+                    true
+                }
+                WireOps::Record(WireRecordOps {
+                    action,
+                    deletes,
+                    updates,
+                    entry,
+                    warrants,
+                }) if action.is_none()
+                    && deletes.is_empty()
+                    && updates.is_empty()
+                    && entry.is_none()
+                    && warrants.is_empty() =>
+                {
+                    true
+                }
+                _ => false,
+            };
+
+            let out = if options.as_race {
+                select_ok_non_empty(requests, is_empty)
+                    .await
+                    .map(|x| vec![x])
+            } else {
+                gather_all_responses(
+                    "get",
+                    requests,
+                    self.timeout_for(&dna_hash, &options),
+                    is_empty,
+                )
+                .await
+            };
 
             timing_trace_out!(out, start, a = "send_get");
-            out.map(|x| vec![x])
+            out
         })
     }
 
@@ -2167,8 +2686,9 @@ impl actor::HcP2p for HolochainP2pActor {
 
             let start = std::time::Instant::now();
 
-            let out = select_ok_non_empty(
-                agents.into_iter().map(|(to_agent, to_url)| {
+            let requests = agents
+                .into_iter()
+                .map(|(to_agent, to_url)| {
                     Box::pin(async {
                         let r_options: event::GetLinksOptions = (&options).into();
 
@@ -2198,14 +2718,32 @@ impl actor::HcP2p for HolochainP2pActor {
                         )
                         .await
                     })
-                }),
-                |wire_link_ops| wire_link_ops.creates.is_empty(),
-            )
-            .await;
+                })
+                .collect::<Vec<_>>();
+            let out = if options.network_req_options.as_race {
+                // A response with only deletes must not win the race over
+                // authorities that hold the creates.
+                select_ok_non_empty(requests, |wire_link_ops: &WireLinkOps| {
+                    wire_link_ops.creates.is_empty()
+                })
+                .await
+                .map(|x| vec![x])
+            } else {
+                // Deletes are data when responses are reconciled.
+                gather_all_responses(
+                    "get_links",
+                    requests,
+                    self.timeout_for(&dna_hash, &options.network_req_options),
+                    |wire_link_ops: &WireLinkOps| {
+                        wire_link_ops.creates.is_empty() && wire_link_ops.deletes.is_empty()
+                    },
+                )
+                .await
+            };
 
             timing_trace_out!(out, start, a = "send_get_links");
 
-            out.map(|x| vec![x])
+            out
         })
     }
 
@@ -2293,8 +2831,9 @@ impl actor::HcP2p for HolochainP2pActor {
 
             let start = std::time::Instant::now();
 
-            let out = select_ok_non_empty(
-                agents.into_iter().map(|(to_agent, to_url)| {
+            let requests = agents
+                .into_iter()
+                .map(|(to_agent, to_url)| {
                     Box::pin(async {
                         let r_options: event::GetActivityOptions = (&options).into();
 
@@ -2323,14 +2862,26 @@ impl actor::HcP2p for HolochainP2pActor {
                         )
                         .await
                     })
-                }),
-                AgentActivityResponse::is_empty,
-            )
-            .await;
+                })
+                .collect::<Vec<_>>();
+
+            let out = if options.network_req_options.as_race {
+                select_ok_non_empty(requests, AgentActivityResponse::is_empty)
+                    .await
+                    .map(|x| vec![x])
+            } else {
+                gather_all_responses(
+                    "get_agent_activity",
+                    requests,
+                    self.timeout_for(&dna_hash, &options.network_req_options),
+                    AgentActivityResponse::is_empty,
+                )
+                .await
+            };
 
             timing_trace_out!(out, start, a = "send_get_agent_activity");
 
-            out.map(|x| vec![x])
+            out
         })
     }
 
@@ -2375,10 +2926,7 @@ impl actor::HcP2p for HolochainP2pActor {
                 )
                 .await?;
 
-            let timeout = match options.timeout_ms {
-                Some(ms) => Duration::from_millis(ms),
-                None => self.request_timeout,
-            };
+            let timeout = self.timeout_for(&dna_hash, &network_req_options);
 
             let start = std::time::Instant::now();
 
@@ -2535,6 +3083,65 @@ impl actor::HcP2p for HolochainP2pActor {
         })
     }
 
+    fn get_agent_presence(
+        &self,
+        dna_hash: DnaHash,
+        agents: Vec<AgentPubKey>,
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<AgentPresence>>> {
+        Box::pin(async move {
+            let space_id = dna_hash.to_k2_space();
+            let space = self
+                .kitsune
+                .space_if_exists(space_id.clone())
+                .await
+                .ok_or(HolochainP2pError::K2SpaceNotFound(space_id))?;
+
+            let now = holochain_timestamp::Timestamp::now();
+            let mut out = Vec::with_capacity(agents.len());
+            for agent in agents {
+                let Some(info) = space.peer_store().get(agent.to_k2_agent()).await? else {
+                    out.push(AgentPresence::unknown(agent));
+                    continue;
+                };
+
+                let (unresponsive, last_contact_at) = match &info.url {
+                    Some(url) => {
+                        let unresponsive = space
+                            .peer_meta_store()
+                            .get_unresponsive(url.clone())
+                            .await?
+                            .is_some();
+                        let last_contact_at = self
+                            .latency_service
+                            .store()
+                            .lock()
+                            .expect("latency data lock poisoned")
+                            .last_sampled_ago(url)
+                            .map(|ago| now.saturating_sub(&ago));
+                        (unresponsive, last_contact_at)
+                    }
+                    None => (false, None),
+                };
+
+                out.push(AgentPresence {
+                    agent,
+                    agent_info_created_at: Some(holochain_timestamp::Timestamp::from_micros(
+                        info.created_at.as_micros(),
+                    )),
+                    agent_info_expires_at: Some(holochain_timestamp::Timestamp::from_micros(
+                        info.expires_at.as_micros(),
+                    )),
+                    last_contact_at,
+                    unresponsive,
+                    // Same rule as `was_agent_recently_online`.
+                    recently_online: info.url.is_some() && !unresponsive,
+                });
+            }
+
+            Ok(out)
+        })
+    }
+
     fn send_validation_receipts(
         &self,
         dna_hash: DnaHash,
@@ -2626,6 +3233,23 @@ impl actor::HcP2p for HolochainP2pActor {
         })
     }
 
+    fn authorities_for_hash(
+        &self,
+        dna_hash: DnaHash,
+        basis: OpBasis,
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<(AgentPubKey, Url)>>> {
+        Box::pin(async move {
+            let space_id = dna_hash.to_k2_space();
+            let space = self
+                .kitsune
+                .space_if_exists(space_id.clone())
+                .await
+                .ok_or(HolochainP2pError::K2SpaceNotFound(space_id))?;
+
+            self.get_peers_for_location(&space, basis.get_loc()).await
+        })
+    }
+
     fn countersigning_session_negotiation(
         &self,
         dna_hash: DnaHash,
@@ -2770,6 +3394,7 @@ impl actor::HcP2p for HolochainP2pActor {
                                 fetch_state_summary,
                                 gossip_state_summary,
                                 local_agents,
+                                bandwidth: self.bandwidth.space_bandwidth(&space_id),
                             },
                         ))
                     })
@@ -2785,6 +3410,91 @@ impl actor::HcP2p for HolochainP2pActor {
         Box::pin(async move { Ok(self.kitsune.transport().await?.dump_network_stats().await?) })
     }
 
+    fn dump_peer_latency(&self) -> BoxFut<'_, HolochainP2pResult<Vec<PeerLatency>>> {
+        Box::pin(async move {
+            Ok(self
+                .latency_service
+                .store()
+                .lock()
+                .expect("latency data lock poisoned")
+                .dump())
+        })
+    }
+
+    fn set_bandwidth_limits(
+        &self,
+        installed_app_id: InstalledAppId,
+        dna_hashes: Vec<DnaHash>,
+        limits: Option<BandwidthLimits>,
+    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        Box::pin(async move {
+            // Continue the day of the persisted usage, in case the limits are being applied
+            // on startup.
+            let usage = match limits {
+                Some(_) => {
+                    let store = self.conductor_store_getter()().await;
+                    store
+                        .as_read()
+                        .get_bandwidth_usage(&installed_app_id)
+                        .await
+                        .map_err(|err| {
+                            HolochainP2pError::other(format!(
+                                "Could not read bandwidth usage from database: {err}"
+                            ))
+                        })?
+                }
+                None => None,
+            };
+            self.bandwidth.set_limits(
+                installed_app_id,
+                dna_hashes.iter().map(|dna_hash| dna_hash.to_k2_space()),
+                limits,
+                usage,
+            );
+            Ok(())
+        })
+    }
+
+    fn set_arc_policy(
+        &self,
+        dna_hash: DnaHash,
+        arc_policy: Option<ArcPolicy>,
+    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        Box::pin(async move {
+            let explicit = arc_policy.is_some();
+            let arc_policy = arc_policy.unwrap_or(ArcPolicy::TargetArcFactor(
+                self.target_arc_factor_for(&dna_hash),
+            ));
+            crate::check_arc_policy(&arc_policy)?;
+
+            {
+                let mut arc_policies = self
+                    .arc_policies
+                    .lock()
+                    .expect("arc policies lock poisoned");
+                let space_arc_policy =
+                    arc_policies
+                        .entry(dna_hash.clone())
+                        .or_insert_with(|| SpaceArcPolicy {
+                            policy: Arc::new(parking_lot::Mutex::new(arc_policy)),
+                            explicit,
+                        });
+                *space_arc_policy.policy.lock() = arc_policy;
+                space_arc_policy.explicit = explicit;
+            }
+
+            // Have the local agents that have already joined the space
+            // publish their agent info with the new arcs.
+            if let Some(space) = self.kitsune.space_if_exists(dna_hash.to_k2_space()).await {
+                for local_agent in space.local_agent_store().get_all().await? {
+                    local_agent.invoke_cb();
+                }
+            }
+
+            Ok(())
+        })
+    }
+
     fn target_arcs(
         &self,
         dna_hash: DnaHash,
@@ -2987,6 +3697,7 @@ mod tests {
         let space_overrides = CellConfigOverrides {
             bootstrap_url: Some("http://override:1234".to_string()),
             relay_url: Some("wss://override:5678".to_string()),
+            ..Default::default()
         };
         let overrides = actor_p2p
             .space_config_override(space_overrides)
@@ -3021,6 +3732,7 @@ mod tests {
         let space_overrides = CellConfigOverrides {
             bootstrap_url: Some("http://override:1234".to_string()),
             relay_url: Some("wss://override:5678".to_string()),
+            ..Default::default()
         };
         let overrides = actor_p2p
             .space_config_override(space_overrides)
@@ -3046,6 +3758,226 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_disable_gossip_in_space_config() {
+        let actor = test_p2p_actor().await;
+        let actor_p2p: Arc<HolochainP2pActor> =
+            Arc::downcast(actor).expect("failed to downcast actor");
+
+        let space_overrides = CellConfigOverrides {
+            network: RoleNetworkConfig {
+                disable_gossip: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let overrides = actor_p2p
+            .space_config_override(space_overrides)
+            .expect("failed to get overrides")
+            .expect("overrides should be some");
+
+        let gossip_config: HcGossipModConfig = overrides
+            .get_module_config()
+            .expect("failed to get gossip config");
+        assert!(gossip_config.hc_gossip.disabled);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_merge_role_advanced_config_over_conductor_config() {
+        let actor = test_p2p_actor().await;
+        let actor_p2p: Arc<HolochainP2pActor> =
+            Arc::downcast(actor).expect("failed to downcast actor");
+
+        let space_overrides = CellConfigOverrides {
+            network: RoleNetworkConfig {
+                advanced: Some(serde_json::json!({
+                    "coreBootstrap": { "backoffMinMs": 200 }
+                })),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let overrides = actor_p2p
+            .space_config_override(space_overrides)
+            .expect("failed to get overrides")
+            .expect("overrides should be some");
+
+        let bootstrap_config: kitsune2_core::factories::CoreBootstrapModConfig = overrides
+            .get_module_config()
+            .expect("failed to get bootstrap config");
+        assert_eq!(
+            bootstrap_config.core_bootstrap.backoff_min_ms, 200,
+            "backoff_min_ms should be overridden by the role"
+        );
+        assert_eq!(
+            bootstrap_config.core_bootstrap.backoff_max_ms, 5_000,
+            "backoff_max_ms should be kept from the conductor config"
+        );
+
+        // Advanced settings that aren't module configs are rejected.
+        let space_overrides = CellConfigOverrides {
+            network: RoleNetworkConfig {
+                advanced: Some(serde_json::json!(["coreBootstrap"])),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(actor_p2p.space_config_override(space_overrides).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_use_network_settings_of_space() {
+        let actor = test_p2p_actor().await;
+        let actor_p2p: Arc<HolochainP2pActor> =
+            Arc::downcast(actor).expect("failed to downcast actor");
+
+        let dna_hash = DnaHash::from_raw_32(vec![0xdb; 32]);
+        let other_dna_hash = DnaHash::from_raw_32(vec![0xdc; 32]);
+        actor_p2p.space_networks.lock().unwrap().insert(
+            dna_hash.clone(),
+            RoleNetworkConfig {
+                target_arc_factor: Some(0),
+                request_timeout_s: Some(5),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(actor_p2p.target_arc_factor_for(&dna_hash), 0);
+        assert_eq!(
+            actor_p2p.target_arc_factor_for(&other_dna_hash),
+            actor_p2p.target_arc_factor
+        );
+
+        assert_eq!(
+            actor_p2p.timeout_for(&dna_hash, &NetworkRequestOptions::default()),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            actor_p2p.timeout_for(&other_dna_hash, &NetworkRequestOptions::default()),
+            actor_p2p.request_timeout
+        );
+
+        // A timeout in the request options takes precedence.
+        let options = NetworkRequestOptions {
+            timeout_ms: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(
+            actor_p2p.timeout_for(&dna_hash, &options),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_keep_network_settings_of_first_cell_in_space() {
+        let dna_hash = DnaHash::from_raw_36(vec![0; 36]);
+        let actor = test_p2p_actor_with_stores(&dna_hash).await;
+
+        let network = RoleNetworkConfig {
+            target_arc_factor: Some(0),
+            request_timeout_s: Some(5),
+            ..Default::default()
+        };
+        let alice = actor.lair_client.new_sign_keypair_random().await.unwrap();
+        actor
+            .join(
+                dna_hash.clone(),
+                alice.clone(),
+                None,
+                Some(CellConfigOverrides {
+                    network: network.clone(),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+
+        // A cell joining with other settings doesn't change the settings of the space.
+        let bob = actor.lair_client.new_sign_keypair_random().await.unwrap();
+        actor
+            .join(
+                dna_hash.clone(),
+                bob.clone(),
+                None,
+                Some(CellConfigOverrides {
+                    network: RoleNetworkConfig {
+                        request_timeout_s: Some(30),
+                        disable_publish: Some(true),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(network, actor.space_network(&dna_hash));
+
+        // Nor does a cell joining without settings.
+        let carol = actor.lair_client.new_sign_keypair_random().await.unwrap();
+        actor
+            .join(dna_hash.clone(), carol.clone(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(network, actor.space_network(&dna_hash));
+
+        // Once the space is removed, the next cell to join sets the settings again.
+        for agent in [alice.clone(), bob, carol] {
+            actor.leave(dna_hash.clone(), agent).await.unwrap();
+        }
+        actor
+            .join(dna_hash.clone(), alice, None, None)
+            .await
+            .unwrap();
+        assert_eq!(RoleNetworkConfig::default(), actor.space_network(&dna_hash));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_forget_default_arc_policy_with_space() {
+        let dna_hash = DnaHash::from_raw_36(vec![0; 36]);
+        let actor = test_p2p_actor_with_stores(&dna_hash).await;
+        let agent = actor.lair_client.new_sign_keypair_random().await.unwrap();
+        let join_with_factor = |target_arc_factor| {
+            actor.join(
+                dna_hash.clone(),
+                agent.clone(),
+                None,
+                Some(CellConfigOverrides {
+                    network: RoleNetworkConfig {
+                        target_arc_factor,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            )
+        };
+
+        join_with_factor(Some(0)).await.unwrap();
+        assert_eq!(
+            ArcPolicy::TargetArcFactor(0),
+            *actor.arc_policy(&dna_hash).lock()
+        );
+
+        // The default policy of a removed space follows the settings it is rejoined with.
+        actor.leave(dna_hash.clone(), agent.clone()).await.unwrap();
+        join_with_factor(None).await.unwrap();
+        assert_eq!(
+            ArcPolicy::TargetArcFactor(actor.target_arc_factor),
+            *actor.arc_policy(&dna_hash).lock()
+        );
+
+        // A policy that was set is kept.
+        actor
+            .set_arc_policy(dna_hash.clone(), Some(ArcPolicy::TargetArcFactor(0)))
+            .await
+            .unwrap();
+        actor.leave(dna_hash.clone(), agent.clone()).await.unwrap();
+        join_with_factor(None).await.unwrap();
+        assert_eq!(
+            ArcPolicy::TargetArcFactor(0),
+            *actor.arc_policy(&dna_hash).lock()
+        );
+    }
+
     async fn test_p2p_actor() -> Arc<dyn HcP2p> {
         use kitsune2_core::factories::{CoreBootstrapConfig, CoreBootstrapModConfig};
 
@@ -3086,6 +4018,55 @@ mod tests {
             .expect("failed to create actor")
     }
 
+    /// A test actor with databases for the space of `dna_hash`, so that agents can join it.
+    async fn test_p2p_actor_with_stores(dna_hash: &DnaHash) -> Arc<HolochainP2pActor> {
+        use holochain_state::data::PeerMetaStore;
+
+        let dht_store = holochain_state::DhtStore::new_test(holochain_state::data::Dht::new(
+            Arc::new(dna_hash.clone()),
+        ))
+        .await
+        .unwrap();
+        let conductor_store = holochain_state::conductor::ConductorStore::new_test()
+            .await
+            .unwrap();
+        let peer_meta_db = holochain_state::peer_metadata_store::PeerMetaStore::new(
+            holochain_state::data::test_open_db(PeerMetaStore::new(Arc::new(dna_hash.clone())))
+                .await
+                .unwrap(),
+        );
+
+        let config = HolochainP2pConfig {
+            network_config: Some(serde_json::json!({
+                "coreBootstrap": {
+                    "serverUrl": "https://not_a_host"
+                }
+            })),
+            get_dht_store: Arc::new(move |_space| {
+                let dht_store = dht_store.clone();
+                Box::pin(async move { Ok(dht_store) })
+            }),
+            get_conductor_store: Arc::new(move || {
+                let conductor_store = conductor_store.clone();
+                Box::pin(async move { conductor_store })
+            }),
+            get_db_peer_meta: Arc::new(move |_space| {
+                let peer_meta_db = peer_meta_db.clone();
+                Box::pin(async move { Ok(peer_meta_db) })
+            }),
+            ..Default::default()
+        };
+        let actor = HolochainP2pActor::create(config, holochain_keystore::test_keystore())
+            .await
+            .unwrap();
+        let actor: Arc<HolochainP2pActor> = Arc::downcast(actor).unwrap();
+        actor
+            .register_handler(Arc::new(BlockingEventHandler::new()))
+            .await
+            .unwrap();
+        actor
+    }
+
     async fn test_p2p_actor_iroh() -> Arc<dyn HcP2p> {
         use kitsune2_core::factories::{CoreBootstrapConfig, CoreBootstrapModConfig};
 
@@ -3177,7 +4158,8 @@ mod tests {
             _signal: Vec<u8>,
             _from_agent: AgentPubKey,
             _signature: Signature,
-        ) -> BoxFut<'_, HolochainP2pResult<()>> {
+            _idempotency_key: Option<u64>,
+        ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
             // Increment counter
             let mut count = self.handle_remote_signal_direct_count.lock().unwrap();
             *count += 1;
@@ -4165,6 +5147,61 @@ mod tests {
         assert_eq!(out, 0u8);
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn gather_all_responses_waits_for_every_authority() {
+        // Unlike a race, a slower peer with data must still be included.
+        let futures = vec![
+            Box::pin(async { Ok::<_, HolochainP2pError>(1u8) }) as BoxFut<'static, _>,
+            Box::pin(async { Err(HolochainP2pError::other("boom")) }),
+            Box::pin(async { Ok(0u8) }),
+            Box::pin(async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok(3u8)
+            }),
+        ];
+
+        let mut out = gather_all_responses("t", futures, Duration::from_secs(5), |v| *v == 0)
+            .await
+            .unwrap();
+        out.sort();
+        assert_eq!(out, vec![1u8, 3]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gather_all_responses_keeps_what_arrived_before_timeout() {
+        let futures = vec![
+            Box::pin(async { Ok::<_, HolochainP2pError>(1u8) }) as BoxFut<'static, _>,
+            Box::pin(std::future::pending()),
+        ];
+
+        let start = tokio::time::Instant::now();
+        let out = gather_all_responses("t", futures, Duration::from_secs(5), never_empty)
+            .await
+            .unwrap();
+        assert_eq!(out, vec![1u8]);
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gather_all_responses_falls_back_to_empty_then_error() {
+        let futures = vec![
+            Box::pin(async { Ok::<_, HolochainP2pError>(0u8) }) as BoxFut<'static, _>,
+            Box::pin(async { Err(HolochainP2pError::other("boom")) }),
+        ];
+        let out = gather_all_responses("t", futures, Duration::from_secs(5), |v: &u8| *v == 0)
+            .await
+            .unwrap();
+        assert_eq!(out, vec![0u8]);
+
+        let futures = vec![
+            Box::pin(async { Err::<u8, _>(HolochainP2pError::other("boom")) })
+                as BoxFut<'static, _>,
+        ];
+        gather_all_responses("t", futures, Duration::from_secs(5), never_empty)
+            .await
+            .unwrap_err();
+    }
+
     #[tokio::test(start_paused = true)]
     async fn gather_required_responses_stops_at_threshold() {
         let futures = vec![
@@ -6,7 +6,7 @@ use holochain_types::prelude::ValidationReceiptBundle;
 use holochain_types::{
     activity::AgentActivityResponse, cell_config_overrides::CellConfigOverrides,
 };
-use kitsune2_api::{SpaceId, StoredOp};
+use kitsune2_api::{SpaceId, StoredOp, Url};
 use std::any::Any;
 use std::collections::HashMap;
 
@@ -16,8 +16,8 @@ pub struct NetworkRequestOptions {
     /// Make requests to this number of remote agents in parallel.
     ///
     /// When `GetOptions::as_race` is `true`, the first response received will be returned.
-    /// When `GetOptions::as_race` is `false`, responses will be aggregated until the timeout is
-    /// reached. This is not implemented.
+    /// When `GetOptions::as_race` is `false`, the responses of all agents that answer before the
+    /// timeout is reached will be returned, for the caller to merge.
     ///
     /// Defaults to `3`.
     pub remote_agent_count: u8,
@@ -29,6 +29,9 @@ pub struct NetworkRequestOptions {
 
     /// Whether to treat the get as a race, returning the first response received.
     ///
+    /// Only `get`, `get_links` and `get_agent_activity` aggregate responses when this is `false`,
+    /// other requests are always treated as a race.
+    ///
     /// Defaults to `true`.
     pub as_race: bool,
 }
@@ -216,6 +219,9 @@ pub trait HcP2p: 'static + Send + Sync + std::fmt::Debug + Any {
     ) -> BoxFut<'_, HolochainP2pResult<()>>;
 
     /// The p2p module must be informed at runtime which dna/agent pairs it should be tracking.
+    ///
+    /// The config override, including the network settings of the role of the agent's cell,
+    /// applies to the network space of the dna.
     fn join(
         &self,
         dna_hash: DnaHash,
@@ -254,14 +260,19 @@ pub trait HcP2p: 'static + Send + Sync + std::fmt::Debug + Any {
     /// This is a fire-and-forget operation, a best effort will be made
     /// to forward the signal, but if the conductor network is overworked
     /// it may decide not to deliver some of the signals.
+    ///
+    /// Returns the agents that the signal could not be sent to, because no URL
+    /// is known for them or sending to their URL failed.
     fn send_remote_signal(
         &self,
         dna_hash: DnaHash,
         to_agent_list: Vec<(AgentPubKey, ExternIO, Signature)>,
-    ) -> BoxFut<'_, HolochainP2pResult<()>>;
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<AgentPubKey>>>;
 
     /// Similar to `send_remote_signal` but bypasses the WASM when receiving the signal on the
     /// target conductors.
+    ///
+    /// Returns the agents that the signal could not be sent to.
     fn send_remote_signal_direct(
         &self,
         dna_hash: DnaHash,
@@ -269,7 +280,25 @@ pub trait HcP2p: 'static + Send + Sync + std::fmt::Debug + Any {
         signal: Vec<u8>,
         from_agent: AgentPubKey,
         signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>>;
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<AgentPubKey>>>;
+
+    /// Send a direct signal to one agent and wait for its conductor to acknowledge it.
+    ///
+    /// The acknowledgement says whether the signal was delivered to a subscribed app
+    /// interface, had no subscriber or was blocked by the receiver. Retries of the same
+    /// signal must use the same `idempotency_key`, so that the receiver passes the signal
+    /// on only once. The `signature` must be over the signal along with the key, as an
+    /// `AckedDirectSignal`.
+    fn send_remote_signal_direct_acked(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        signal: Vec<u8>,
+        from_agent: AgentPubKey,
+        signature: Signature,
+        idempotency_key: u64,
+        options: NetworkRequestOptions,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>>;
 
     /// Publish data to the correct neighborhood.
     fn publish(
@@ -368,6 +397,15 @@ pub trait HcP2p: 'static + Send + Sync + std::fmt::Debug + Any {
         agent: AgentPubKey,
     ) -> BoxFut<'_, HolochainP2pResult<bool>>;
 
+    /// Get what is locally known about the reachability of agents in this network.
+    ///
+    /// This only reads local stores and does not contact the agents.
+    fn get_agent_presence(
+        &self,
+        dna_hash: DnaHash,
+        agents: Vec<AgentPubKey>,
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<AgentPresence>>>;
+
     /// Send a validation receipt to a remote node.
     fn send_validation_receipts(
         &self,
@@ -383,6 +421,16 @@ pub trait HcP2p: 'static + Send + Sync + std::fmt::Debug + Any {
         basis: OpBasis,
     ) -> BoxFut<'_, HolochainP2pResult<bool>>;
 
+    /// List the responsive remote peers in this space whose storage arc
+    /// contains a hash, as they would be considered for a get or publish.
+    ///
+    /// Only the local peer store is consulted; no network calls are made.
+    fn authorities_for_hash(
+        &self,
+        dna_hash: DnaHash,
+        basis: OpBasis,
+    ) -> BoxFut<'_, HolochainP2pResult<Vec<(AgentPubKey, Url)>>>;
+
     /// Messages between agents negotiation a countersigning session.
     fn countersigning_session_negotiation(
         &self,
@@ -403,6 +451,35 @@ pub trait HcP2p: 'static + Send + Sync + std::fmt::Debug + Any {
     fn dump_network_stats(&self)
         -> BoxFut<'_, HolochainP2pResult<kitsune2_api::ApiTransportStats>>;
 
+    /// Get the latency measured for each peer URL that is being pinged.
+    fn dump_peer_latency(&self) -> BoxFut<'_, HolochainP2pResult<Vec<PeerLatency>>>;
+
+    /// Set the bandwidth limits of an app whose cells are in the spaces of the given DNAs, or
+    /// with `None` remove them.
+    ///
+    /// While the gossip and fetch traffic of those spaces exceeds a limit, gossip in them is
+    /// paused, and while an app has a rate limit its gossip messages are held back to that rate.
+    /// Setting different limits starts a new limit period.
+    /// The traffic counted towards a daily limit is read from the conductor store, and saved
+    /// to it periodically.
+    fn set_bandwidth_limits(
+        &self,
+        installed_app_id: InstalledAppId,
+        dna_hashes: Vec<DnaHash>,
+        limits: Option<BandwidthLimits>,
+    ) -> BoxFut<'_, HolochainP2pResult<()>>;
+
+    /// Set how much of a space's DHT the local agents hold, or with `None` go back to the
+    /// target arc factor from the config.
+    ///
+    /// Agents that have already joined the space republish their agent info with the new
+    /// arcs, without rejoining. The policy also applies to agents that join later.
+    fn set_arc_policy(
+        &self,
+        dna_hash: DnaHash,
+        arc_policy: Option<ArcPolicy>,
+    ) -> BoxFut<'_, HolochainP2pResult<()>>;
+
     /// Get the target arcs of the agents currently in this space.
     fn target_arcs(
         &self,
@@ -73,6 +73,10 @@ pub trait HcP2pHandler: 'static + Send + Sync + std::fmt::Debug {
     ) -> BoxFut<'_, HolochainP2pResult<SerializedBytes>>;
 
     /// A remote node is sending us a direct signal.
+    ///
+    /// Acknowledged signals carry an `idempotency_key` that stays the same when the
+    /// sender retries, so that a retried signal is only passed on once. Their
+    /// signature covers the key, see `AckedDirectSignal`.
     fn handle_remote_signal_direct(
         &self,
         dna_hash: DnaHash,
@@ -80,7 +84,8 @@ pub trait HcP2pHandler: 'static + Send + Sync + std::fmt::Debug {
         signal: Vec<u8>,
         from_agent: AgentPubKey,
         signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>>;
+        idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>>;
 
     /// A remote node is publishing data in a range we claim to be holding.
     ///
@@ -110,6 +110,21 @@ pub enum WireMessage {
         from_agent: AgentPubKey,
         signature: Signature,
     },
+    /// A direct signal that the receiver acknowledges with a
+    /// [`RemoteSignalDirectRes`](Self::RemoteSignalDirectRes).
+    RemoteSignalDirectReq {
+        msg_id: u64,
+        to_agent: AgentPubKey,
+        signal: Vec<u8>,
+        from_agent: AgentPubKey,
+        signature: Signature,
+        /// The same for every retry of a signal.
+        idempotency_key: u64,
+    },
+    RemoteSignalDirectRes {
+        msg_id: u64,
+        outcome: DirectSignalOutcome,
+    },
     PublishCountersignEvt {
         op: ChainOp,
     },
@@ -161,6 +176,8 @@ impl WireMessage {
             WireMessage::MustGetAgentActivityRes { msg_id, .. } => Some(*msg_id),
             WireMessage::SendValidationReceiptsReq { msg_id, .. } => Some(*msg_id),
             WireMessage::SendValidationReceiptsRes { msg_id, .. } => Some(*msg_id),
+            WireMessage::RemoteSignalDirectReq { msg_id, .. } => Some(*msg_id),
+            WireMessage::RemoteSignalDirectRes { msg_id, .. } => Some(*msg_id),
             WireMessage::PingReq { msg_id, .. } => Some(*msg_id),
             WireMessage::PingRes { msg_id, .. } => Some(*msg_id),
             _ => None,
@@ -349,6 +366,33 @@ impl WireMessage {
         }
     }
 
+    /// Outgoing "RemoteSignalDirect" request, acknowledged by the receiver.
+    pub fn remote_signal_direct_req(
+        to_agent: AgentPubKey,
+        signal: Vec<u8>,
+        from_agent: AgentPubKey,
+        signature: Signature,
+        idempotency_key: u64,
+    ) -> (u64, WireMessage) {
+        let msg_id = next_msg_id();
+        (
+            msg_id,
+            Self::RemoteSignalDirectReq {
+                msg_id,
+                to_agent,
+                signal,
+                from_agent,
+                signature,
+                idempotency_key,
+            },
+        )
+    }
+
+    /// Incoming "RemoteSignalDirect" response.
+    pub fn remote_signal_direct_res(msg_id: u64, outcome: DirectSignalOutcome) -> WireMessage {
+        Self::RemoteSignalDirectRes { msg_id, outcome }
+    }
+
     /// Outgoing "PublishCountersign" notify event.
     pub fn publish_countersign_evt(op: ChainOp) -> WireMessage {
         Self::PublishCountersignEvt { op }
@@ -61,6 +61,24 @@ pub(crate) fn select_weighted_urls(store: &LatencyData, urls: &[Url], count: usi
         })
 }
 
+/// Selects the URL to use out of several URLs that reach the same agent.
+///
+/// URLs without failed pings are preferred, and out of those the one with the
+/// lowest latency. URLs that haven't been measured yet come after measured ones,
+/// in the order they were given. A URL with failed pings is only returned when
+/// every URL has failed pings, so the request can still be attempted.
+pub(crate) fn select_healthy_url(store: &LatencyData, urls: &[Url]) -> Option<Url> {
+    let healthy = || urls.iter().filter(|url| !store.has_failed_pings(url));
+
+    healthy()
+        .filter_map(|url| store.get_weight(url).map(|weight| (url, weight)))
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+        .map(|(url, _)| url)
+        .or_else(|| healthy().next())
+        .or_else(|| urls.first())
+        .cloned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,4 +246,45 @@ mod tests {
         // Only 2 selectable URLs remain after filtering the dead one.
         assert_eq!(selected.len(), 2);
     }
+
+    #[test]
+    fn healthy_url_prefers_lowest_latency() {
+        let mut store = LatencyData::default();
+        let unknown_url = test_url("unknown");
+        let fast_url = test_url("fast");
+        let slow_url = test_url("slow");
+
+        store.record_sample(fast_url.clone(), Duration::from_millis(10));
+        store.record_sample(slow_url.clone(), Duration::from_millis(1000));
+
+        let urls = vec![unknown_url, slow_url, fast_url.clone()];
+        assert_eq!(select_healthy_url(&store, &urls), Some(fast_url));
+    }
+
+    #[test]
+    fn healthy_url_skips_failed_urls() {
+        let mut store = LatencyData::default();
+        let failed_url = test_url("failed");
+        let unknown_url = test_url("unknown");
+
+        store.record_sample(failed_url.clone(), Duration::from_millis(10));
+        mark_ping_failed(&mut store, &failed_url);
+
+        let urls = vec![failed_url, unknown_url.clone()];
+        assert_eq!(select_healthy_url(&store, &urls), Some(unknown_url));
+    }
+
+    #[test]
+    fn healthy_url_falls_back_to_first_when_all_failed() {
+        let mut store = LatencyData::default();
+        let first_url = test_url("1");
+        let second_url = test_url("2");
+
+        mark_ping_failed(&mut store, &first_url);
+        mark_ping_failed(&mut store, &second_url);
+
+        let urls = vec![first_url.clone(), second_url];
+        assert_eq!(select_healthy_url(&store, &urls), Some(first_url));
+        assert_eq!(select_healthy_url(&store, &[]), None);
+    }
 }
@@ -62,14 +62,15 @@ impl HcP2pHandler for Handler {
         signal: Vec<u8>,
         _from_agent: AgentPubKey,
         _signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        _idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
         Box::pin(async move {
             let respond = format!(
                 "got_remote_signal_direct: {}",
                 String::from_utf8_lossy(&signal),
             );
             self.calls.lock().unwrap().push(respond.clone());
-            Ok(())
+            Ok(DirectSignalOutcome::Delivered)
         })
     }
 
@@ -67,7 +67,8 @@ impl HcP2pHandler for UnresponsiveHandler {
         _signal: Vec<u8>,
         _from_agent: AgentPubKey,
         _signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        _idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
         Box::pin(std::future::pending())
     }
 
@@ -179,7 +180,8 @@ impl HcP2pHandler for EchoingActivityHandler {
         _signal: Vec<u8>,
         _from_agent: AgentPubKey,
         _signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        _idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
         Box::pin(std::future::pending())
     }
 
@@ -392,12 +394,14 @@ async fn test_remote_signal() {
     // Wait for hc2 to discover agent1 via the bootstrap before sending.
     wait_for_peers(&hc2, dna_hash.clone(), 2).await;
 
-    hc2.send_remote_signal(
-        dna_hash,
-        vec![(agent1, ExternIO(b"hello".to_vec()), Signature([0; 64]))],
-    )
-    .await
-    .unwrap();
+    let undelivered = hc2
+        .send_remote_signal(
+            dna_hash,
+            vec![(agent1, ExternIO(b"hello".to_vec()), Signature([0; 64]))],
+        )
+        .await
+        .unwrap();
+    assert!(undelivered.is_empty());
 
     tokio::time::timeout(UNRESPONSIVE_TIMEOUT, async {
         loop {
@@ -412,6 +416,30 @@ async fn test_remote_signal() {
     .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_remote_signal_to_unknown_agent_is_undelivered() {
+    let dna_hash = DnaHash::from_raw_36(vec![0; 36]);
+    let handler = Arc::new(Handler::default());
+
+    let (_bootstrap_srv, addr) = spawn_test_bootstrap().await.unwrap();
+    let (_agent1, hc1, _) = spawn_test(dna_hash.clone(), handler.clone(), &addr).await;
+    let unknown_agent = AgentPubKey::from_raw_36(vec![0xdb; 36]);
+
+    let undelivered = hc1
+        .send_remote_signal(
+            dna_hash,
+            vec![(
+                unknown_agent.clone(),
+                ExternIO(b"hello".to_vec()),
+                Signature([0; 64]),
+            )],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(vec![unknown_agent], undelivered);
+}
+
 fn test_dht_op(
     authored_timestamp: holochain_types::prelude::Timestamp,
 ) -> holochain_types::op::DhtOp {
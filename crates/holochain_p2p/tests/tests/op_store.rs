@@ -16,6 +16,7 @@ use holochain_types::chain::MustGetAgentActivityResponse;
 use holochain_types::link::{CountLinksResponse, WireLinkKey, WireLinkOps, WireLinkQuery};
 use holochain_types::op::{ChainOp, DhtOp};
 use holochain_types::prelude::ValidationReceiptBundle;
+use holochain_types::signal::DirectSignalOutcome;
 use holochain_types::wire_ops::WireOps;
 use holochain_zome_types::fixt::{
     ActionFixturator, CreateAction, EntryFixturator, SignatureFixturator,
@@ -50,7 +51,8 @@ impl HcP2pHandler for StubHost {
         _signal: Vec<u8>,
         _from_agent: AgentPubKey,
         _signature: Signature,
-    ) -> BoxFut<'_, HolochainP2pResult<()>> {
+        _idempotency_key: Option<u64>,
+    ) -> BoxFut<'_, HolochainP2pResult<DirectSignalOutcome>> {
         unimplemented!()
     }
 
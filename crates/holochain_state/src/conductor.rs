@@ -6,7 +6,8 @@
 //! [`AppInterfaceModel`], [`WitnessNonceResult`]) is fine — they are the
 //! storage representation of this data.
 
-use holo_hash::AgentPubKey;
+use holo_hash::{AgentPubKey, DnaHash};
+use holochain_conductor_api::conductor::ZomeCallLimits;
 use holochain_conductor_api::signal_subscription::SignalSubscription;
 use holochain_conductor_api::state::{
     AppInterfaceConfig, AppInterfaceId, ConductorState, ConductorStateTag,
@@ -15,8 +16,8 @@ use holochain_data::conductor::{AppInterfaceModel, Block, BlockTargetId, Nonce25
 use holochain_data::kind::Conductor;
 use holochain_data::{TxRead, TxWrite};
 use holochain_types::prelude::{
-    AppStatus, InitProperties, InitPropertiesMap, InstalledApp, InstalledAppCommon, InstalledAppId,
-    InstalledAppMap, Timestamp,
+    AppStatus, ArcPolicy, BandwidthLimits, BandwidthUsage, InitProperties, InitPropertiesMap,
+    InstalledApp, InstalledAppCommon, InstalledAppId, InstalledAppMap, OutboxSignal, Timestamp,
 };
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -165,6 +166,49 @@ impl ConductorStore<holochain_data::DbRead<Conductor>> {
     pub async fn get_all_blocks(&self) -> StateQueryResult<Vec<Block>> {
         Ok(self.db.get_all_blocks().await?)
     }
+
+    /// Get the arc policies of all DNAs that have one.
+    pub async fn get_all_arc_policies(&self) -> StateQueryResult<Vec<(DnaHash, ArcPolicy)>> {
+        Ok(self.db.get_all_arc_policies().await?)
+    }
+
+    /// Get the bandwidth limits of all installed apps that have them.
+    pub async fn get_all_bandwidth_limits(
+        &self,
+    ) -> StateQueryResult<Vec<(InstalledAppId, BandwidthLimits)>> {
+        Ok(self.db.get_all_bandwidth_limits().await?)
+    }
+
+    /// Get the traffic counted towards the daily limit of an installed app, if any has been
+    /// counted.
+    pub async fn get_bandwidth_usage(
+        &self,
+        app_id: &str,
+    ) -> StateQueryResult<Option<BandwidthUsage>> {
+        Ok(self.db.get_bandwidth_usage(app_id).await?)
+    }
+
+    /// Get the signals in the outbox, optionally only those sent on one DNA.
+    pub async fn get_outbox_signals(
+        &self,
+        dna_hash: Option<&DnaHash>,
+    ) -> StateQueryResult<Vec<OutboxSignal>> {
+        Ok(self.db.get_outbox_signals(dna_hash).await?)
+    }
+
+    /// Get the signals in the outbox that are due to be retried at the given time,
+    /// at most `limit` in total and `per_sender_limit` per sending agent and DNA.
+    pub async fn get_due_outbox_signals(
+        &self,
+        now: Timestamp,
+        limit: u32,
+        per_sender_limit: u32,
+    ) -> StateQueryResult<Vec<OutboxSignal>> {
+        Ok(self
+            .db
+            .get_due_outbox_signals(now, limit, per_sender_limit)
+            .await?)
+    }
 }
 
 impl ConductorStore<holochain_data::DbWrite<Conductor>> {
@@ -250,6 +294,84 @@ impl ConductorStore<holochain_data::DbWrite<Conductor>> {
         Ok(self.db.delete_init_properties(app_id, role_name).await?)
     }
 
+    /// Set the arc policy for a DNA, replacing any previous policy.
+    pub async fn put_arc_policy(
+        &self,
+        dna_hash: &DnaHash,
+        policy: &ArcPolicy,
+    ) -> StateMutationResult<()> {
+        Ok(self.db.put_arc_policy(dna_hash, policy).await?)
+    }
+
+    /// Delete the arc policy for a DNA.
+    pub async fn delete_arc_policy(&self, dna_hash: &DnaHash) -> StateMutationResult<()> {
+        Ok(self.db.delete_arc_policy(dna_hash).await?)
+    }
+
+    /// Set the bandwidth limits of an installed app, replacing any previous limits.
+    pub async fn put_bandwidth_limits(
+        &self,
+        app_id: &str,
+        limits: &BandwidthLimits,
+    ) -> StateMutationResult<()> {
+        Ok(self.db.put_bandwidth_limits(app_id, limits).await?)
+    }
+
+    /// Store the traffic counted towards the daily limit of an installed app.
+    pub async fn put_bandwidth_usage(
+        &self,
+        app_id: &str,
+        usage: &BandwidthUsage,
+    ) -> StateMutationResult<()> {
+        Ok(self.db.put_bandwidth_usage(app_id, usage).await?)
+    }
+
+    /// Delete the bandwidth limits of an installed app.
+    pub async fn delete_bandwidth_limits(&self, app_id: &str) -> StateMutationResult<()> {
+        Ok(self.db.delete_bandwidth_limits(app_id).await?)
+    }
+
+    /// Put a signal in the outbox, returning the ID assigned to it.
+    pub async fn insert_outbox_signal(&self, signal: &OutboxSignal) -> StateMutationResult<u64> {
+        Ok(self.db.insert_outbox_signal(signal).await?)
+    }
+
+    /// Record a failed attempt to send a signal from the outbox, and when to retry.
+    pub async fn reschedule_outbox_signal(
+        &self,
+        id: u64,
+        attempts: u32,
+        next_attempt_at: Timestamp,
+    ) -> StateMutationResult<()> {
+        Ok(self
+            .db
+            .reschedule_outbox_signal(id, attempts, next_attempt_at)
+            .await?)
+    }
+
+    /// Remove a signal from the outbox.
+    pub async fn delete_outbox_signal(&self, id: u64) -> StateMutationResult<()> {
+        Ok(self.db.delete_outbox_signal(id).await?)
+    }
+
+    /// Remove all signals that an agent sent on a DNA from the outbox.
+    pub async fn delete_outbox_signals_from(
+        &self,
+        dna_hash: &DnaHash,
+        from_agent: &AgentPubKey,
+    ) -> StateMutationResult<()> {
+        Ok(self
+            .db
+            .delete_outbox_signals_from(dna_hash, from_agent)
+            .await?)
+    }
+
+    /// Move the committed data out of the write-ahead log and into the
+    /// database file, so that the file can be copied on its own.
+    pub async fn checkpoint(&self) -> StateMutationResult<()> {
+        Ok(self.db.checkpoint().await?)
+    }
+
     /// Downgrade this writable store to a read-only store.
     pub fn as_read(&self) -> ConductorStoreRead {
         ConductorStore::new(self.db.as_ref().clone())
@@ -320,6 +442,7 @@ fn state_from_rows(rows: StateRows) -> StateQueryResult<ConductorState> {
             signal_subscriptions,
             installed_app_id: model.installed_app_id.clone(),
             driver,
+            zome_call_limits: zome_call_limits_from_model(&model)?,
         };
 
         let interface_id = if model.port == 0 {
@@ -371,6 +494,9 @@ fn state_to_rows(state: &ConductorState) -> Result<StateRows, StateMutationError
             config.installed_app_id.as_ref().map(|id| id.to_string()),
         )
         .map_err(StateMutationError::Other)?;
+        let zome_call_limits = config.zome_call_limits.unwrap_or_default();
+        model.max_concurrent_calls = zome_call_limits.max_concurrent_calls.map(i64::from);
+        model.max_calls_per_second = zome_call_limits.max_calls_per_second.map(i64::from);
 
         let mut subscriptions = Vec::with_capacity(config.signal_subscriptions.len());
         for (app_id, subscription) in &config.signal_subscriptions {
@@ -394,6 +520,26 @@ fn state_to_rows(state: &ConductorState) -> Result<StateRows, StateMutationError
     })
 }
 
+/// The zome call limits of an app interface from its persisted row, if any are set.
+fn zome_call_limits_from_model(
+    model: &AppInterfaceModel,
+) -> StateQueryResult<Option<ZomeCallLimits>> {
+    let limit = |value: Option<i64>| {
+        value
+            .map(|value| {
+                u32::try_from(value).map_err(|err| {
+                    StateQueryError::Other(format!("Invalid zome call limit {value}: {err}"))
+                })
+            })
+            .transpose()
+    };
+    let limits = ZomeCallLimits {
+        max_concurrent_calls: limit(model.max_concurrent_calls)?,
+        max_calls_per_second: limit(model.max_calls_per_second)?,
+    };
+    Ok((limits != ZomeCallLimits::default()).then_some(limits))
+}
+
 async fn load_rows_in_tx(tx: &mut TxRead<Conductor>) -> sqlx::Result<Option<StateRows>> {
     let tag = match tx.get_conductor_tag().await? {
         Some(tag) => tag,
@@ -7,7 +7,7 @@
 
 use holo_hash::{ActionHash, AgentPubKey, DhtOpHash, DnaHash, EntryHash, HasHash};
 use holochain_data::dht::{
-    InsertLimboChainOp, InsertLimboWarrant, InsertScheduledFunction,
+    AbandonedLimboOpFilter, InsertLimboChainOp, InsertLimboWarrant, InsertScheduledFunction,
     RemoveCountersigningSessionOutcome,
 };
 use holochain_data::kind::Dht;
@@ -211,6 +211,12 @@ impl DhtStore<DbWrite<Dht>> {
         Ok(self.db.delete_all_ephemeral_scheduled_functions().await?)
     }
 
+    /// Move the committed data out of the write-ahead log and into the
+    /// database file, so that the file can be copied on its own.
+    pub async fn checkpoint(&self) -> DhtStoreResult<()> {
+        Ok(self.db.checkpoint().await?)
+    }
+
     /// Re-evaluate every expired persisted scheduled function for `author` at
     /// `now`.
     ///
@@ -685,6 +691,30 @@ impl DhtStore<DbWrite<Dht>> {
         Ok(())
     }
 
+    /// Record why the last validation attempt of each op did not complete.
+    ///
+    /// For each (op_hash, error) pair, updates `last_validation_error` on the
+    /// matching `LimboChainOp` or `LimboWarrantOp` row.
+    pub async fn record_validation_errors(
+        &self,
+        errors: Vec<(DhtOpHash, String)>,
+    ) -> StateMutationResult<()> {
+        let mut tx = self.db.begin().await.map_err(StateMutationError::from)?;
+        for (hash, error) in errors {
+            let updated = tx
+                .set_limbo_chain_op_validation_error(&hash, &error)
+                .await
+                .map_err(StateMutationError::from)?;
+            if updated == 0 {
+                tx.set_limbo_warrant_validation_error(&hash, &error)
+                    .await
+                    .map_err(StateMutationError::from)?;
+            }
+        }
+        tx.commit().await.map_err(StateMutationError::from)?;
+        Ok(())
+    }
+
     /// Insert self-authored warrants into limbo (`Warrant` + `LimboWarrantOp`)
     /// already marked sys-validation accepted, so they are immediately ready
     /// for integration.
@@ -970,6 +1000,29 @@ impl DhtStore<DbWrite<Dht>> {
             .await?)
     }
 
+    /// Re-queue abandoned limbo ops for validation by clearing their
+    /// abandonment and attempt counters. Each `Some` argument narrows the set
+    /// of ops reset; warrants have no op type and are skipped when `op_type`
+    /// is set, and their basis is the warrantee.
+    ///
+    /// Returns the number of ops reset. Callers are responsible for
+    /// triggering the validation workflows afterwards.
+    pub async fn reset_abandoned_ops(
+        &self,
+        op_type: Option<holochain_zome_types::op::ChainOpType>,
+        author: Option<&AgentPubKey>,
+        basis: Option<&holo_hash::AnyLinkableHash>,
+    ) -> StateMutationResult<u64> {
+        Ok(self
+            .db
+            .reset_abandoned_limbo_ops(AbandonedLimboOpFilter {
+                op_type: op_type.map(i64::from),
+                author,
+                basis,
+            })
+            .await?)
+    }
+
     /// Try to acquire the source-chain lock for `author`.
     ///
     /// Returns `Ok(true)` when the caller holds the lock (no lock existed,
@@ -1094,6 +1147,16 @@ fn chain_op_basis_hash_from_row(op_type: i64, raw: Vec<u8>) -> holo_hash::AnyLin
     }
 }
 
+/// Reconstruct the basis hash of a limbo op summary row, which is a chain op
+/// with an `op_type` or a warrant without one. A warrant's basis is its
+/// warrantee.
+fn limbo_op_basis_hash_from_row(op_type: Option<i64>, raw: Vec<u8>) -> holo_hash::AnyLinkableHash {
+    match op_type {
+        Some(op_type) => chain_op_basis_hash_from_row(op_type, raw),
+        None => holo_hash::AgentPubKey::from_raw_36(raw).into(),
+    }
+}
+
 /// Compute the terminal [`OpValidity`](OpValidity)
 /// for a limbo chain op row.
 ///
@@ -1140,6 +1203,58 @@ impl DhtStore<DbWrite<Dht>> {
         Ok(row.map(|r| Timestamp::from_micros(r.when_integrated)))
     }
 
+    /// Test-only: move every integrated op of `action_hash` back into
+    /// `LimboChainOp`, abandoned after a failed sys validation attempt, as if
+    /// validation had given up on them. Their publish state and validation
+    /// receipts are dropped with them.
+    ///
+    /// Returns the hashes of the moved ops.
+    pub async fn test_abandon_integrated_chain_ops(
+        &self,
+        action_hash: &ActionHash,
+    ) -> StateMutationResult<Vec<DhtOpHash>> {
+        let now = Timestamp::now().as_micros();
+        let mut tx = self.db.pool().begin().await?;
+        let op_hashes: Vec<Vec<u8>> =
+            sqlx::query_scalar("SELECT hash FROM ChainOp WHERE action_hash = ?1")
+                .bind(action_hash.get_raw_36())
+                .fetch_all(&mut *tx)
+                .await?;
+        sqlx::query(
+            "INSERT INTO LimboChainOp
+                (hash, op_type, action_hash, basis_hash, storage_center_loc,
+                 require_receipt, when_received, serialized_size, abandoned_at,
+                 sys_validation_attempts, last_validation_attempt)
+             SELECT hash, op_type, action_hash, basis_hash, storage_center_loc,
+                    require_receipt, when_received, serialized_size, ?2, 1, ?2
+             FROM ChainOp WHERE action_hash = ?1",
+        )
+        .bind(action_hash.get_raw_36())
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "DELETE FROM ChainOpPublish
+             WHERE op_hash IN (SELECT hash FROM ChainOp WHERE action_hash = ?1)",
+        )
+        .bind(action_hash.get_raw_36())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "DELETE FROM ValidationReceipt
+             WHERE op_hash IN (SELECT hash FROM ChainOp WHERE action_hash = ?1)",
+        )
+        .bind(action_hash.get_raw_36())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM ChainOp WHERE action_hash = ?1")
+            .bind(action_hash.get_raw_36())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(op_hashes.into_iter().map(DhtOpHash::from_raw_36).collect())
+    }
+
     /// Test-only helper that writes a warrant op straight into the integrated
     /// `Warrant` + `WarrantOp` tables (with `when_integrated = now`), bypassing
     /// `LimboWarrantOp` and the integration workflow's block trigger.
@@ -1419,6 +1534,8 @@ impl DhtStore<DbWrite<Dht>> {
 
 pub(crate) mod action_indexes;
 mod cache;
+mod limbo_stats;
+mod op_trace;
 mod reads;
 mod sync_reads;
 
@@ -0,0 +1,102 @@
+//! `DhtStore` reads that back the workflow-state introspection admin API.
+//!
+//! These report on the validation limbo as a whole rather than on individual
+//! records: per-stage counts, the oldest pending op, attempt histograms and
+//! abandoned ops. Results are returned as `holochain_conductor_api` wire types.
+
+use super::{limbo_op_basis_hash_from_row, DhtStore};
+use crate::query::{StateQueryError, StateQueryResult};
+use holo_hash::{AgentPubKey, DhtOpHash};
+use holochain_conductor_api::{LimboOpInfo, LimboStage, LimboStageCounts};
+use holochain_data::kind::Dht;
+use holochain_data::models::dht::{LimboOpSummaryRow, ValidationAttemptsBucketRow};
+use holochain_data::DbRead;
+use holochain_types::prelude::{ChainOpType, Timestamp};
+use std::collections::BTreeMap;
+
+impl DhtStore<DbRead<Dht>> {
+    /// Number of ops in each limbo stage, across chain ops and warrants.
+    pub async fn limbo_stage_counts(&self) -> StateQueryResult<LimboStageCounts> {
+        let row = self.db().limbo_stage_counts().await?;
+        Ok(LimboStageCounts {
+            pending_sys_validation: row.pending_sys_validation.max(0) as u64,
+            pending_app_validation: row.pending_app_validation.max(0) as u64,
+            awaiting_integration: row.awaiting_integration.max(0) as u64,
+            abandoned: row.abandoned.max(0) as u64,
+        })
+    }
+
+    /// The longest-waiting op that is still pending sys or app validation.
+    pub async fn oldest_pending_limbo_op(&self) -> StateQueryResult<Option<LimboOpInfo>> {
+        self.db()
+            .oldest_pending_limbo_op()
+            .await?
+            .map(limbo_op_info_from_row)
+            .transpose()
+    }
+
+    /// Up to `limit` abandoned limbo ops, most recently abandoned first.
+    pub async fn abandoned_limbo_ops(&self, limit: u32) -> StateQueryResult<Vec<LimboOpInfo>> {
+        self.db()
+            .abandoned_limbo_ops(limit)
+            .await?
+            .into_iter()
+            .map(limbo_op_info_from_row)
+            .collect()
+    }
+
+    /// Histograms of validation attempts, as `(sys, app)`. Each maps a number
+    /// of attempts to the number of ops awaiting that validation stage which
+    /// have made exactly that many attempts.
+    pub async fn validation_attempts_histograms(
+        &self,
+    ) -> StateQueryResult<(BTreeMap<u32, u64>, BTreeMap<u32, u64>)> {
+        let sys = self.db().sys_validation_attempts_histogram().await?;
+        let app = self.db().app_validation_attempts_histogram().await?;
+        Ok((histogram_from_rows(sys), histogram_from_rows(app)))
+    }
+}
+
+fn histogram_from_rows(rows: Vec<ValidationAttemptsBucketRow>) -> BTreeMap<u32, u64> {
+    rows.into_iter()
+        .map(|r| (r.attempts.max(0) as u32, r.op_count.max(0) as u64))
+        .collect()
+}
+
+/// The limbo stage of a row with the given raw sys and app validation
+/// statuses. Warrants have no app validation stage.
+pub(super) fn limbo_stage(sys: Option<i64>, app: Option<i64>, is_chain_op: bool) -> LimboStage {
+    match (sys, app) {
+        (None, _) => LimboStage::PendingSysValidation,
+        (Some(1), None) if is_chain_op => LimboStage::PendingAppValidation,
+        _ => LimboStage::AwaitingIntegration,
+    }
+}
+
+/// Convert a [`LimboOpSummaryRow`] into its wire representation.
+pub(crate) fn limbo_op_info_from_row(row: LimboOpSummaryRow) -> StateQueryResult<LimboOpInfo> {
+    let op_type = row
+        .op_type
+        .map(ChainOpType::try_from)
+        .transpose()
+        .map_err(|v| StateQueryError::Other(format!("invalid op_type {v} in limbo op row")))?;
+    let stage = limbo_stage(
+        row.sys_validation_status,
+        row.app_validation_status,
+        op_type.is_some(),
+    );
+    let basis = limbo_op_basis_hash_from_row(row.op_type, row.basis_hash);
+    Ok(LimboOpInfo {
+        op_hash: DhtOpHash::from_raw_36(row.hash),
+        op_type,
+        basis,
+        author: AgentPubKey::from_raw_36(row.author),
+        stage,
+        when_received: Timestamp::from_micros(row.when_received),
+        sys_validation_attempts: row.sys_validation_attempts.max(0) as u32,
+        app_validation_attempts: row.app_validation_attempts.max(0) as u32,
+        last_validation_attempt: row.last_validation_attempt.map(Timestamp::from_micros),
+        abandoned_at: row.abandoned_at.map(Timestamp::from_micros),
+        last_validation_error: row.last_validation_error,
+    })
+}
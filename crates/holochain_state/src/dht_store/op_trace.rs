@@ -0,0 +1,174 @@
+//! `DhtStore` reads that back the per-op trace admin API.
+//!
+//! [`DhtStore::trace_op`] only fills in what the DNA's database knows about
+//! an op. Network-derived fields (arc coverage, authorities) and missing
+//! sys-validation dependencies are left empty for the conductor to add.
+
+use super::limbo_stats::limbo_stage;
+use super::{chain_op_basis_hash_from_row, DhtStore};
+use crate::query::{StateQueryError, StateQueryResult};
+use holo_hash::{ActionHash, AgentPubKey, DhtOpHash};
+use holochain_conductor_api::{OpLimboTrace, OpPublishState, OpState, OpTrace};
+use holochain_data::kind::Dht;
+use holochain_data::DbRead;
+use holochain_types::prelude::{ChainOpType, Timestamp, ValidationStatus};
+
+impl DhtStore<DbRead<Dht>> {
+    /// Trace a chain op or warrant through the limbo, integrated and
+    /// authored tables.
+    pub async fn trace_op(&self, op_hash: &DhtOpHash) -> StateQueryResult<OpTrace> {
+        let mut trace = OpTrace {
+            op_hash: op_hash.clone(),
+            state: OpState::Absent,
+            authored: false,
+            op_type: None,
+            action_hash: None,
+            author: None,
+            basis: None,
+            when_received: None,
+            when_integrated: None,
+            validation_status: None,
+            limbo: None,
+            missing_dependencies: Vec::new(),
+            publish: None,
+            validation_receipts: Vec::new(),
+            basis_in_local_arc: None,
+            authorities: Vec::new(),
+        };
+
+        if let Some(row) = self.db().get_limbo_chain_op(op_hash.clone()).await? {
+            let action_hash = ActionHash::from_raw_36(row.action_hash);
+            trace.state = OpState::Limbo;
+            trace.op_type = Some(op_type_from_raw(row.op_type)?);
+            trace.author = self.action_author(&action_hash).await?;
+            trace.action_hash = Some(action_hash);
+            trace.basis = Some(chain_op_basis_hash_from_row(row.op_type, row.basis_hash));
+            trace.when_received = Some(Timestamp::from_micros(row.when_received));
+            trace.validation_status = limbo_validation_status(
+                row.sys_validation_status,
+                row.app_validation_status,
+                row.abandoned_at,
+                true,
+            );
+            trace.limbo = Some(OpLimboTrace {
+                stage: limbo_stage(row.sys_validation_status, row.app_validation_status, true),
+                sys_validation_attempts: row.sys_validation_attempts.max(0) as u32,
+                app_validation_attempts: row.app_validation_attempts.max(0) as u32,
+                last_validation_attempt: row.last_validation_attempt.map(Timestamp::from_micros),
+                abandoned_at: row.abandoned_at.map(Timestamp::from_micros),
+            });
+        } else if let Some(row) = self.db().get_chain_op(op_hash.clone()).await? {
+            let action_hash = ActionHash::from_raw_36(row.action_hash);
+            trace.state = if row.locally_validated != 0 {
+                OpState::Integrated
+            } else {
+                OpState::Cached
+            };
+            trace.op_type = Some(op_type_from_raw(row.op_type)?);
+            trace.author = self.action_author(&action_hash).await?;
+            trace.action_hash = Some(action_hash);
+            trace.basis = Some(chain_op_basis_hash_from_row(row.op_type, row.basis_hash));
+            trace.when_received = Some(Timestamp::from_micros(row.when_received));
+            trace.when_integrated = Some(Timestamp::from_micros(row.when_integrated));
+            trace.validation_status = Some(integrated_validation_status(row.validation_status));
+        } else if let Some(row) = self.db().get_limbo_warrant(op_hash.clone()).await? {
+            trace.state = OpState::Limbo;
+            trace.author = Some(AgentPubKey::from_raw_36(row.author));
+            trace.basis = Some(AgentPubKey::from_raw_36(row.warrantee).into());
+            trace.when_received = Some(Timestamp::from_micros(row.when_received));
+            trace.validation_status =
+                limbo_validation_status(row.sys_validation_status, None, row.abandoned_at, false);
+            trace.limbo = Some(OpLimboTrace {
+                stage: limbo_stage(row.sys_validation_status, None, false),
+                sys_validation_attempts: row.sys_validation_attempts.max(0) as u32,
+                app_validation_attempts: 0,
+                last_validation_attempt: row.last_validation_attempt.map(Timestamp::from_micros),
+                abandoned_at: row.abandoned_at.map(Timestamp::from_micros),
+            });
+        } else if let Some(row) = self.db().get_warrant(op_hash.clone()).await? {
+            trace.state = OpState::Integrated;
+            trace.author = Some(AgentPubKey::from_raw_36(row.author));
+            trace.basis = Some(AgentPubKey::from_raw_36(row.warrantee).into());
+            trace.when_received = Some(Timestamp::from_micros(row.when_received));
+            trace.when_integrated = Some(Timestamp::from_micros(row.when_integrated));
+            trace.validation_status = self
+                .db()
+                .warrant_op_validation_status(op_hash)
+                .await?
+                .map(integrated_validation_status);
+        }
+
+        if let Some(row) = self.db().get_chain_op_publish(op_hash.clone()).await? {
+            trace.authored = true;
+            trace.publish = Some(OpPublishState {
+                last_publish_time: row.last_publish_time.map(Timestamp::from_micros),
+                receipts_complete: row.receipts_complete.unwrap_or(0) != 0,
+                withhold_publish: row.withhold_publish.unwrap_or(0) != 0,
+            });
+            trace.validation_receipts = self
+                .db()
+                .get_validation_receipts(op_hash.clone())
+                .await?
+                .into_iter()
+                .map(|row| {
+                    holochain_serialized_bytes::decode(&row.blob)
+                        .map_err(|e| StateQueryError::Other(format!("decode receipt: {e}")))
+                })
+                .collect::<StateQueryResult<_>>()?;
+        } else if let Some(row) = self.db().get_warrant_publish(op_hash.clone()).await? {
+            trace.authored = true;
+            trace.publish = Some(OpPublishState {
+                last_publish_time: row.last_publish_time.map(Timestamp::from_micros),
+                receipts_complete: false,
+                withhold_publish: false,
+            });
+        }
+
+        Ok(trace)
+    }
+
+    async fn action_author(
+        &self,
+        action_hash: &ActionHash,
+    ) -> StateQueryResult<Option<AgentPubKey>> {
+        Ok(self
+            .db()
+            .get_action(action_hash.clone())
+            .await?
+            .map(|action| action.action().author().clone()))
+    }
+}
+
+fn op_type_from_raw(op_type: i64) -> StateQueryResult<ChainOpType> {
+    ChainOpType::try_from(op_type)
+        .map_err(|v| StateQueryError::Other(format!("invalid op_type {v} in chain op row")))
+}
+
+/// The outcome of validation so far for a limbo row, using the
+/// `RecordValidity` encoding (1 = accepted, 2 = rejected). Warrants are
+/// final once sys validation accepts them; chain ops also need app
+/// validation.
+fn limbo_validation_status(
+    sys: Option<i64>,
+    app: Option<i64>,
+    abandoned_at: Option<i64>,
+    is_chain_op: bool,
+) -> Option<ValidationStatus> {
+    if abandoned_at.is_some() {
+        return Some(ValidationStatus::Abandoned);
+    }
+    match (sys, app) {
+        (Some(2), _) | (_, Some(2)) => Some(ValidationStatus::Rejected),
+        (Some(1), Some(1)) => Some(ValidationStatus::Valid),
+        (Some(1), None) if !is_chain_op => Some(ValidationStatus::Valid),
+        _ => None,
+    }
+}
+
+fn integrated_validation_status(status: i64) -> ValidationStatus {
+    if status == 2 {
+        ValidationStatus::Rejected
+    } else {
+        ValidationStatus::Valid
+    }
+}
@@ -3084,3 +3084,66 @@ mod publish_query {
         assert_eq!(ops_to_publish(&dht_store, &agent).await.len(), 0);
     }
 }
+
+#[tokio::test]
+async fn trace_op_follows_op_from_limbo_to_integrated() {
+    let store = DhtStore::new_test(dht_id()).await.unwrap();
+    let op = build_test_store_record_op_hashed(60);
+    let op_hash = op.0.as_hash().clone();
+    let basis = op.0.as_content().dht_basis();
+
+    let trace = store.as_read().trace_op(&op_hash).await.unwrap();
+    assert_eq!(trace.state, holochain_conductor_api::OpState::Absent);
+    assert!(trace.limbo.is_none());
+
+    store.record_incoming_ops(vec![op]).await.unwrap();
+    store
+        .record_chain_op_sys_validation_outcomes(vec![(op_hash.clone(), SysOutcome::Accepted)])
+        .await
+        .unwrap();
+
+    let trace = store.as_read().trace_op(&op_hash).await.unwrap();
+    assert_eq!(trace.state, holochain_conductor_api::OpState::Limbo);
+    assert_eq!(trace.op_type, Some(ChainOpType::CreateRecord));
+    assert_eq!(trace.author, Some(agent(60)));
+    assert_eq!(trace.basis, Some(basis.clone()));
+    assert_eq!(trace.validation_status, None);
+    assert_eq!(
+        trace.limbo.unwrap().stage,
+        holochain_conductor_api::LimboStage::PendingAppValidation
+    );
+    assert!(!trace.authored);
+
+    store
+        .record_app_validation_outcomes(vec![(op_hash.clone(), AppOutcome::Accepted)])
+        .await
+        .unwrap();
+    store
+        .integrate_ready_ops(Timestamp::from_micros(999))
+        .await
+        .unwrap();
+
+    let trace = store.as_read().trace_op(&op_hash).await.unwrap();
+    assert_eq!(trace.state, holochain_conductor_api::OpState::Integrated);
+    assert_eq!(trace.validation_status, Some(ValidationStatus::Valid));
+    assert_eq!(trace.when_integrated, Some(Timestamp::from_micros(999)));
+    assert_eq!(trace.basis, Some(basis));
+    assert!(trace.limbo.is_none());
+}
+
+#[tokio::test]
+async fn limbo_op_info_keeps_the_basis_hash_type() {
+    let store = DhtStore::new_test(dht_id()).await.unwrap();
+    let op = build_test_store_record_op_hashed(61);
+    let basis = op.0.as_content().dht_basis();
+    store.record_incoming_ops(vec![op]).await.unwrap();
+
+    let info = store
+        .as_read()
+        .oldest_pending_limbo_op()
+        .await
+        .unwrap()
+        .unwrap();
+    // Equal hashes also have the same hash type.
+    assert_eq!(info.basis, basis);
+}
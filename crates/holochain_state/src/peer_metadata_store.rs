@@ -89,6 +89,12 @@ impl PeerMetaStore<holochain_data::DbWrite<holochain_data::kind::PeerMetaStore>>
     pub async fn prune(&self) -> StateMutationResult<u64> {
         self.db.prune().await.map_err(StateMutationError::from)
     }
+
+    /// Move the committed data out of the write-ahead log and into the
+    /// database file, so that the file can be copied on its own.
+    pub async fn checkpoint(&self) -> StateMutationResult<()> {
+        self.db.checkpoint().await.map_err(StateMutationError::from)
+    }
 }
 
 impl From<PeerMetaStore<holochain_data::DbWrite<holochain_data::kind::PeerMetaStore>>>
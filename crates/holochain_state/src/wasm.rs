@@ -84,6 +84,12 @@ impl WasmStore<holochain_data::DbWrite<holochain_data::kind::Wasm>> {
             .map_err(StateMutationError::from)
     }
 
+    /// Move the committed data out of the write-ahead log and into the
+    /// database file, so that the file can be copied on its own.
+    pub async fn checkpoint(&self) -> StateMutationResult<()> {
+        self.db.checkpoint().await.map_err(StateMutationError::from)
+    }
+
     /// Downgrade this writable store to a read-only store.
     pub fn as_read(&self) -> WasmStoreRead {
         WasmStore::new(self.db.as_ref().clone())
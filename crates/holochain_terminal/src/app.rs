@@ -21,7 +21,6 @@ pub struct App {
     args: Arc<Args>,
 
     /// An admin client if the `admin_url` flag was provided
-    #[allow(dead_code)]
     admin_client: Option<Arc<Mutex<AdminClient>>>,
 
     /// An app client if the `admin_url` flag was provided
@@ -80,7 +79,6 @@ impl App {
         self.args.clone()
     }
 
-    #[allow(dead_code)]
     pub fn admin_client(&mut self) -> Option<Arc<Mutex<AdminClient>>> {
         self.admin_client.clone()
     }
@@ -1,9 +1,12 @@
 use anyhow::anyhow;
 use holo_hash::{AgentPubKey, DnaHash};
 use holochain_client::{AdminWebsocket, AppWebsocket, ClientAgentSigner, DynAgentSigner};
-use holochain_conductor_api::{AppAuthenticationToken, AppInterfaceInfo, CellInfo};
-use holochain_types::network::Kitsune2NetworkMetrics;
-use holochain_types::prelude::InstalledAppId;
+use holochain_conductor_api::{
+    AppAuthenticationToken, AppInfo, AppInterfaceInfo, CellInfo, DhtOpsCursor, FullStateDump,
+    JsonDump, SourceChainCursor, StorageInfo,
+};
+use holochain_types::network::{Kitsune2NetworkMetrics, PeerLatency};
+use holochain_types::prelude::{CellId, InstalledAppId};
 use holochain_types::websocket::AllowedOrigins;
 use std::collections::HashMap;
 
@@ -87,7 +90,7 @@ impl AdminClient {
             interface.port
         } else {
             self.client
-                .attach_app_interface(0, None, HC_TERM_ORIGIN.to_string().into(), None)
+                .attach_app_interface(0, None, HC_TERM_ORIGIN.to_string().into(), None, None)
                 .await?
         };
 
@@ -101,6 +104,42 @@ impl AdminClient {
         AppClient::connect(app_addr, token.token).await
     }
 
+    pub async fn list_apps(&mut self) -> anyhow::Result<Vec<AppInfo>> {
+        Ok(self.client.list_apps(None).await?)
+    }
+
+    /// Fetch a page of the source chain of a cell, starting after `cursor`
+    pub async fn dump_state(
+        &mut self,
+        cell_id: CellId,
+        cursor: Option<SourceChainCursor>,
+        limit: u32,
+    ) -> anyhow::Result<JsonDump> {
+        let dump = self.client.dump_state(cell_id, cursor, Some(limit)).await?;
+        Ok(serde_json::from_str(&dump)?)
+    }
+
+    /// Fetch a page of the DHT ops held by a cell, starting after `cursor`
+    pub async fn dump_full_state(
+        &mut self,
+        cell_id: CellId,
+        cursor: Option<DhtOpsCursor>,
+        limit: u32,
+    ) -> anyhow::Result<FullStateDump> {
+        Ok(self
+            .client
+            .dump_full_state(cell_id, cursor, Some(limit))
+            .await?)
+    }
+
+    pub async fn storage_info(&mut self) -> anyhow::Result<StorageInfo> {
+        Ok(self.client.storage_info().await?)
+    }
+
+    pub async fn peer_latency(&mut self) -> anyhow::Result<Vec<PeerLatency>> {
+        Ok(self.client.dump_peer_latency().await?)
+    }
+
     fn select_usable_app_interface(
         interfaces: impl IntoIterator<Item = AppInterfaceInfo>,
         installed_app_id: InstalledAppId,
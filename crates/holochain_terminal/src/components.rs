@@ -1,4 +1,8 @@
+pub mod apps;
 pub mod bootstrap;
 pub mod common;
+pub mod dht_ops;
 pub mod home;
 pub mod network_metrics;
+pub mod source_chain;
+pub mod storage;
@@ -0,0 +1,213 @@
+use crate::client::AdminClient;
+use crate::components::common::{fetch, show_detail, show_message, to_yaml};
+use crate::event::ScreenEvent;
+use holochain_conductor_api::{AppInfo, CellInfo};
+use holochain_types::prelude::CellId;
+use ratatui::{prelude::*, widgets::*};
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::sync::Mutex;
+
+fn get_apps() -> &'static RwLock<Option<Vec<AppInfo>>> {
+    static APPS: OnceLock<RwLock<Option<Vec<AppInfo>>>> = OnceLock::new();
+
+    APPS.get_or_init(|| RwLock::new(None))
+}
+
+fn get_selected() -> &'static RwLock<usize> {
+    static SELECTED: OnceLock<RwLock<usize>> = OnceLock::new();
+
+    SELECTED.get_or_init(|| RwLock::new(0))
+}
+
+fn get_selected_cell() -> &'static RwLock<Option<CellId>> {
+    static SELECTED_CELL: OnceLock<RwLock<Option<CellId>>> = OnceLock::new();
+
+    SELECTED_CELL.get_or_init(|| RwLock::new(None))
+}
+
+/// The cell chosen on the apps screen, which the source chain and DHT screens show
+pub fn selected_cell() -> Option<CellId> {
+    get_selected_cell().read().unwrap().clone()
+}
+
+/// A line in the app and cell tree
+enum Row<'a> {
+    App(&'a AppInfo),
+    Cell {
+        role_name: &'a str,
+        cell: &'a CellInfo,
+    },
+}
+
+impl Row<'_> {
+    fn cell_id(&self) -> Option<&CellId> {
+        match self {
+            Row::Cell {
+                cell: CellInfo::Provisioned(cell),
+                ..
+            } => Some(&cell.cell_id),
+            Row::Cell {
+                cell: CellInfo::Cloned(cell),
+                ..
+            } => Some(&cell.cell_id),
+            _ => None,
+        }
+    }
+}
+
+fn rows(apps: &[AppInfo]) -> Vec<Row<'_>> {
+    apps.iter()
+        .flat_map(|app| {
+            std::iter::once(Row::App(app)).chain(app.cell_info.iter().flat_map(
+                |(role_name, cells)| {
+                    cells.iter().map(move |cell| Row::Cell {
+                        role_name: role_name.as_str(),
+                        cell,
+                    })
+                },
+            ))
+        })
+        .collect()
+}
+
+pub struct AppsWidget {
+    admin_client: Option<Arc<Mutex<AdminClient>>>,
+    events: Vec<ScreenEvent>,
+}
+
+impl AppsWidget {
+    pub fn new(admin_client: Option<Arc<Mutex<AdminClient>>>, events: Vec<ScreenEvent>) -> Self {
+        Self {
+            admin_client,
+            events,
+        }
+    }
+}
+
+impl Widget for AppsWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let admin_client = match self.admin_client {
+            Some(c) => c,
+            None => {
+                show_message("No admin URL configured, to use this screen please re-run the terminal with `--admin-url <my-url>`", area, buf);
+                return;
+            }
+        };
+
+        let refresh = self
+            .events
+            .iter()
+            .any(|e| matches!(e, ScreenEvent::Refresh));
+        if refresh || get_apps().read().unwrap().is_none() {
+            match fetch("apps", async {
+                admin_client.lock().await.list_apps().await
+            }) {
+                Ok(apps) => *get_apps().write().unwrap() = Some(apps),
+                Err(e) => {
+                    show_message(format!("{e:?}").as_str(), area, buf);
+                    return;
+                }
+            }
+        }
+
+        let apps = get_apps().read().unwrap();
+        let rows = rows(apps.as_deref().unwrap_or_default());
+        if rows.is_empty() {
+            show_message("No apps installed", area, buf);
+            return;
+        }
+
+        let mut selected = get_selected().write().unwrap();
+        *selected = (*selected).min(rows.len() - 1);
+        for event in self.events {
+            match event {
+                ScreenEvent::NavDown => {
+                    if *selected < rows.len() - 1 {
+                        *selected += 1;
+                    }
+                }
+                ScreenEvent::NavUp => {
+                    if *selected > 0 {
+                        *selected -= 1;
+                    }
+                }
+                ScreenEvent::Select => {
+                    if let Some(cell_id) = rows[*selected].cell_id() {
+                        *get_selected_cell().write().unwrap() = Some(cell_id.clone());
+                    }
+                }
+                _ => {
+                    // Ignored
+                }
+            }
+        }
+
+        let content_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+
+        let selected_cell = selected_cell();
+        let list_items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| match row {
+                Row::App(app) => {
+                    ListItem::new(format!("{} [{:?}]", app.installed_app_id, app.status))
+                        .style(Style::default().bold())
+                }
+                Row::Cell { role_name, cell } => {
+                    let label = match cell {
+                        CellInfo::Provisioned(c) => format!("{role_name} - {}", c.name),
+                        CellInfo::Cloned(c) => format!(
+                            "{role_name} - {} ({}{})",
+                            c.name,
+                            c.clone_id,
+                            if c.enabled { "" } else { ", disabled" }
+                        ),
+                        CellInfo::Stem(c) => format!(
+                            "{role_name} - {} (stem)",
+                            c.name.as_deref().unwrap_or_default()
+                        ),
+                    };
+                    let marker =
+                        if selected_cell.is_some() && row.cell_id() == selected_cell.as_ref() {
+                            "*"
+                        } else {
+                            " "
+                        };
+                    ListItem::new(format!("  {marker} {label}"))
+                }
+            })
+            .collect();
+
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .title(" Apps (ENTER to choose the cell for the Source chain and DHT screens) ")
+                    .borders(Borders::ALL),
+            )
+            .style(Style::default().fg(Color::White))
+            .highlight_symbol(">> ");
+
+        let detail = match &rows[*selected] {
+            Row::App(app) => to_yaml(&serde_json::json!({
+                "installed_app_id": app.installed_app_id,
+                "status": app.status,
+                "agent_pub_key": app.agent_pub_key,
+                "installed_at": app.installed_at.to_string(),
+            })),
+            Row::Cell { role_name, cell } => to_yaml(&serde_json::json!({
+                "role_name": role_name,
+                "cell": cell,
+            })),
+        };
+        show_detail("Info", &detail, content_layout[1], buf);
+
+        StatefulWidget::render(
+            list,
+            content_layout[0],
+            buf,
+            &mut ListState::default().with_selected(Some(*selected)),
+        );
+    }
+}
@@ -93,6 +93,9 @@ impl Widget for BootstrapWidget {
                         *selected -= 1;
                     }
                 }
+                _ => {
+                    // Ignored
+                }
             }
         }
 
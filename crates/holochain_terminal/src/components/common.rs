@@ -1,6 +1,165 @@
+use anyhow::anyhow;
+use holo_hash::encode::holo_hash_encode;
+use holochain_types::prelude::{AppEntryBytes, Entry, ExternIO};
+use holochain_util::tokio_helper::block_on;
 use ratatui::{prelude::*, widgets::*};
+use serde_json::Value;
+use std::future::Future;
+use std::time::Duration;
+
+/// The number of items to fetch for each page of the paginated screens
+pub const PAGE_SIZE: u32 = 50;
 
 pub fn show_message(message: &str, area: Rect, buf: &mut Buffer) {
     let p = Paragraph::new(message).block(Block::default());
     p.render(area, buf);
 }
+
+/// Wait for a request to the conductor to complete, describing `what` was fetched on failure
+pub fn fetch<T>(what: &str, request: impl Future<Output = anyhow::Result<T>>) -> anyhow::Result<T> {
+    match block_on(request, Duration::from_secs(10)) {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(e)) => Err(anyhow!("Failed to fetch {what} - {e:?}")),
+        Err(_) => Err(anyhow!("Timeout while fetching {what}")),
+    }
+}
+
+/// Show a detail view with a title, such as an action rendered by [`to_yaml`]
+pub fn show_detail(title: &str, content: &str, area: Rect, buf: &mut Buffer) {
+    let p = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(format!(" {title} "))
+                .borders(Borders::ALL),
+        )
+        .style(Style::default().fg(Color::White))
+        .wrap(Wrap { trim: false });
+    p.render(area, buf);
+}
+
+/// Render a value as YAML, with hashes shown in their base64 form rather than as bytes
+pub fn to_yaml<T: serde::Serialize>(value: &T) -> String {
+    let value = match serde_json::to_value(value) {
+        Ok(value) => encode_hashes(value),
+        Err(e) => return format!("Failed to serialize: {e}"),
+    };
+    yaml_serde::to_string(&value).unwrap_or_else(|e| format!("Failed to serialize: {e}"))
+}
+
+/// Convert an entry to a value that can be passed to [`to_yaml`], decoding the
+/// content of app entries.
+pub fn entry_to_value(entry: &Entry) -> Value {
+    match entry {
+        Entry::App(bytes) => serde_json::json!({ "App": decode_app_entry(bytes) }),
+        Entry::CounterSign(session, bytes) => serde_json::json!({
+            "CounterSign": {
+                "session": serde_json::to_value(session).unwrap_or_default(),
+                "entry": decode_app_entry(bytes),
+            }
+        }),
+        entry => serde_json::to_value(entry).unwrap_or_default(),
+    }
+}
+
+/// Decode the msgpack content of an app entry, or fall back to its raw bytes
+fn decode_app_entry(bytes: &AppEntryBytes) -> Value {
+    ExternIO(bytes.0.bytes().to_vec())
+        .decode::<hc_serde_json::Value>()
+        .ok()
+        .and_then(|value| serde_json::to_value(value).ok())
+        .unwrap_or_else(|| bytes.0.bytes().clone().into())
+}
+
+fn encode_hashes(value: Value) -> Value {
+    match value {
+        Value::Array(items) => match as_hash_bytes(&items) {
+            Some(bytes) => Value::String(holo_hash_encode(&bytes)),
+            None => Value::Array(items.into_iter().map(encode_hashes).collect()),
+        },
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, encode_hashes(value)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// The bytes of a serialized HoloHash, which are 39 bytes starting with a hash type prefix
+fn as_hash_bytes(items: &[Value]) -> Option<Vec<u8>> {
+    if items.len() != 39 {
+        return None;
+    }
+    let bytes = items
+        .iter()
+        .map(|item| item.as_u64().and_then(|b| u8::try_from(b).ok()))
+        .collect::<Option<Vec<u8>>>()?;
+    // Every hash type prefix has the form [0x84, <type>, 0x24]
+    (bytes[0] == 0x84 && bytes[2] == 0x24).then_some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holo_hash::AgentPubKey;
+    use holochain_types::prelude::{SerializedBytes, UnsafeBytes};
+    use std::collections::BTreeMap;
+
+    fn app_entry(bytes: Vec<u8>) -> Entry {
+        Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
+            bytes,
+        ))))
+    }
+
+    #[test]
+    fn hashes_are_encoded_as_base64() {
+        let agent = AgentPubKey::from_raw_36(vec![1; 36]);
+        let value = serde_json::json!({
+            "author": serde_json::to_value(&agent).unwrap(),
+            "nested": [serde_json::to_value(&agent).unwrap()],
+        });
+
+        let expected = Value::String(holo_hash_encode(agent.get_raw_39()));
+        assert_eq!(
+            serde_json::json!({ "author": expected, "nested": [expected] }),
+            encode_hashes(value)
+        );
+    }
+
+    #[test]
+    fn arrays_without_a_hash_prefix_are_left_as_is() {
+        let bytes = Value::from(vec![7u8; 39]);
+        assert_eq!(bytes, encode_hashes(bytes.clone()));
+
+        // A hash prefix, but too short to be a hash
+        let short = Value::from(vec![0x84u8, 0x20, 0x24, 1, 2, 3]);
+        assert_eq!(short, encode_hashes(short.clone()));
+
+        // Values that are not bytes are never treated as a hash
+        let mut items = vec![Value::from(0x84), Value::from(0x20), Value::from(0x24)];
+        items.extend(std::iter::repeat_n(Value::from(1000), 36));
+        assert!(as_hash_bytes(&items).is_none());
+    }
+
+    #[test]
+    fn app_entries_are_decoded() {
+        let content = BTreeMap::from([("title".to_string(), "hello".to_string())]);
+        let entry = app_entry(ExternIO::encode(&content).unwrap().into_vec());
+
+        assert_eq!(
+            serde_json::json!({ "App": { "title": "hello" } }),
+            entry_to_value(&entry)
+        );
+    }
+
+    #[test]
+    fn undecodable_app_entries_fall_back_to_bytes() {
+        // 0xc1 is never used by msgpack
+        let entry = app_entry(vec![0xc1, 1, 2]);
+
+        assert_eq!(
+            serde_json::json!({ "App": [0xc1, 1, 2] }),
+            entry_to_value(&entry)
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use crate::client::AdminClient;
+use crate::components::apps::selected_cell;
+use crate::components::common::{
+    entry_to_value, fetch, show_detail, show_message, to_yaml, PAGE_SIZE,
+};
+use crate::event::ScreenEvent;
+use holochain_conductor_api::{DhtOpsCursor, FullIntegrationStateDump};
+use holochain_types::prelude::{CellId, DhtOp, OpEntry};
+use ratatui::{prelude::*, widgets::*};
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::sync::Mutex;
+
+/// The lifecycle stages that a cell's DHT ops are grouped by
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Bucket {
+    ValidationLimbo,
+    IntegrationLimbo,
+    Integrated,
+}
+
+impl Bucket {
+    fn title(&self) -> &'static str {
+        match self {
+            Bucket::ValidationLimbo => "Validation limbo",
+            Bucket::IntegrationLimbo => "Integration limbo",
+            Bucket::Integrated => "Integrated",
+        }
+    }
+}
+
+/// A page of DHT ops, with the cursor to fetch the page after it
+struct OpsPage {
+    ops: Vec<(Bucket, DhtOp)>,
+    next_cursor: Option<DhtOpsCursor>,
+}
+
+impl From<FullIntegrationStateDump> for OpsPage {
+    fn from(dump: FullIntegrationStateDump) -> Self {
+        let ops = [
+            (Bucket::ValidationLimbo, dump.validation_limbo),
+            (Bucket::IntegrationLimbo, dump.integration_limbo),
+            (Bucket::Integrated, dump.integrated),
+        ]
+        .into_iter()
+        .flat_map(|(bucket, ops)| ops.into_iter().map(move |op| (bucket, op)))
+        .collect();
+
+        Self {
+            ops,
+            next_cursor: dump.dht_ops_cursor,
+        }
+    }
+}
+
+#[derive(Default)]
+struct DhtOpsState {
+    /// The cell that the ops were fetched for
+    cell_id: Option<CellId>,
+    /// The cursor that each page up to the current one was fetched with
+    cursors: Vec<Option<DhtOpsCursor>>,
+    /// The ops on the current page, ordered by bucket
+    page: Option<OpsPage>,
+    selected: usize,
+    show_detail: bool,
+}
+
+fn get_state() -> &'static RwLock<DhtOpsState> {
+    static STATE: OnceLock<RwLock<DhtOpsState>> = OnceLock::new();
+
+    STATE.get_or_init(|| RwLock::new(DhtOpsState::default()))
+}
+
+pub struct DhtOpsWidget {
+    admin_client: Option<Arc<Mutex<AdminClient>>>,
+    events: Vec<ScreenEvent>,
+}
+
+impl DhtOpsWidget {
+    pub fn new(admin_client: Option<Arc<Mutex<AdminClient>>>, events: Vec<ScreenEvent>) -> Self {
+        Self {
+            admin_client,
+            events,
+        }
+    }
+}
+
+impl Widget for DhtOpsWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let admin_client = match self.admin_client {
+            Some(c) => c,
+            None => {
+                show_message("No admin URL configured, to use this screen please re-run the terminal with `--admin-url <my-url>`", area, buf);
+                return;
+            }
+        };
+
+        let cell_id = match selected_cell() {
+            Some(c) => c,
+            None => {
+                show_message(
+                    "No cell chosen, select a cell on the Apps screen and press ENTER",
+                    area,
+                    buf,
+                );
+                return;
+            }
+        };
+
+        let mut state = get_state().write().unwrap();
+
+        if state.cell_id.as_ref() != Some(&cell_id) {
+            match fetch_page(admin_client.clone(), cell_id.clone(), None) {
+                Ok(page) => {
+                    *state = DhtOpsState {
+                        cell_id: Some(cell_id.clone()),
+                        cursors: vec![None],
+                        page: Some(page),
+                        ..Default::default()
+                    };
+                }
+                Err(e) => {
+                    show_message(format!("{e:?}").as_str(), area, buf);
+                    return;
+                }
+            }
+        }
+
+        let op_count = |state: &DhtOpsState| state.page.as_ref().map_or(0, |p| p.ops.len());
+
+        for event in self.events {
+            let result = match event {
+                ScreenEvent::Refresh => {
+                    let cursor = state.cursors.last().cloned().flatten();
+                    fetch_page(admin_client.clone(), cell_id.clone(), cursor).map(|page| {
+                        state.page = Some(page);
+                    })
+                }
+                ScreenEvent::NextPage
+                    if state.page.as_ref().is_some_and(|p| p.next_cursor.is_some()) =>
+                {
+                    // Ops that fail to build are left out of a page, so a short page
+                    // isn't necessarily the last one
+                    let cursor = state.page.as_ref().and_then(|p| p.next_cursor.clone());
+                    fetch_page(admin_client.clone(), cell_id.clone(), cursor.clone()).map(|page| {
+                        // Stay on the current page if it was the last one
+                        if !page.ops.is_empty() {
+                            state.cursors.push(cursor);
+                            state.page = Some(page);
+                            state.selected = 0;
+                        }
+                    })
+                }
+                ScreenEvent::PrevPage if state.cursors.len() > 1 => {
+                    let cursor = state.cursors[state.cursors.len() - 2].clone();
+                    fetch_page(admin_client.clone(), cell_id.clone(), cursor).map(|page| {
+                        state.cursors.pop();
+                        state.page = Some(page);
+                        state.selected = 0;
+                    })
+                }
+                ScreenEvent::NavDown if !state.show_detail => {
+                    if state.selected + 1 < op_count(&state) {
+                        state.selected += 1;
+                    }
+                    Ok(())
+                }
+                ScreenEvent::NavUp if !state.show_detail => {
+                    state.selected = state.selected.saturating_sub(1);
+                    Ok(())
+                }
+                ScreenEvent::Select => {
+                    state.show_detail = op_count(&state) > 0;
+                    Ok(())
+                }
+                ScreenEvent::Back => {
+                    state.show_detail = false;
+                    Ok(())
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                show_message(format!("{e:?}").as_str(), area, buf);
+                return;
+            }
+        }
+        state.selected = state.selected.min(op_count(&state).saturating_sub(1));
+
+        let Some(page) = state.page.as_ref().filter(|p| !p.ops.is_empty()) else {
+            show_message("This cell holds no DHT ops", area, buf);
+            return;
+        };
+
+        if state.show_detail {
+            let (bucket, op) = &page.ops[state.selected];
+            show_detail(
+                &format!("{} op (BACKSPACE to go back)", bucket.title()),
+                &op_to_yaml(op),
+                area,
+                buf,
+            );
+            return;
+        }
+
+        // Show a heading before the ops of each bucket, and keep track of where
+        // the selected op ends up in the list.
+        let mut list_items = Vec::new();
+        let mut selected_item = 0;
+        let mut current_bucket = None;
+        for (index, (bucket, op)) in page.ops.iter().enumerate() {
+            if current_bucket != Some(*bucket) {
+                current_bucket = Some(*bucket);
+                let count = page.ops.iter().filter(|(b, _)| b == bucket).count();
+                list_items.push(
+                    ListItem::new(format!("{} ({count})", bucket.title()))
+                        .style(Style::default().bold()),
+                );
+            }
+            if index == state.selected {
+                selected_item = list_items.len();
+            }
+            list_items.push(ListItem::new(format!("  {}", op_summary(op))));
+        }
+
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .title(format!(
+                        " DHT ops held by {}, page {} (LEFT/RIGHT to change page, ENTER for details) ",
+                        cell_id.dna_hash(),
+                        state.cursors.len()
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .style(Style::default().fg(Color::White))
+            .highlight_symbol(">> ");
+
+        StatefulWidget::render(
+            list,
+            area,
+            buf,
+            &mut ListState::default().with_selected(Some(selected_item)),
+        );
+    }
+}
+
+fn fetch_page(
+    admin_client: Arc<Mutex<AdminClient>>,
+    cell_id: CellId,
+    cursor: Option<DhtOpsCursor>,
+) -> anyhow::Result<OpsPage> {
+    let dump = fetch("DHT ops", async {
+        admin_client
+            .lock()
+            .await
+            .dump_full_state(cell_id, cursor, PAGE_SIZE)
+            .await
+    })?;
+    Ok(dump.integration_dump.into())
+}
+
+fn op_summary(op: &DhtOp) -> String {
+    match op {
+        DhtOp::ChainOp(op) => {
+            let action = op.signed_action().data();
+            format!(
+                "{:<14}  {:<18}  {}  {}",
+                op.op_type().to_string(),
+                action.action_type().to_string(),
+                action.timestamp(),
+                action.author()
+            )
+        }
+        DhtOp::WarrantOp(op) => format!("{:?}  warrantee {}", op.get_type(), op.data().warrantee),
+    }
+}
+
+fn op_to_yaml(op: &DhtOp) -> String {
+    match op {
+        DhtOp::ChainOp(chain_op) => {
+            let action = chain_op.signed_action().data();
+            let entry = match chain_op.op_entry() {
+                Some(OpEntry::Present(entry)) => entry_to_value(entry),
+                Some(OpEntry::Hidden) => "hidden".into(),
+                Some(OpEntry::ActionOnly) | None => serde_json::Value::Null,
+            };
+            to_yaml(&serde_json::json!({
+                "op_hash": op.to_hash(),
+                "op_type": chain_op.op_type().to_string(),
+                "basis": op.dht_basis(),
+                "header": action.header,
+                "data": action.data,
+                "entry": entry,
+            }))
+        }
+        DhtOp::WarrantOp(warrant_op) => to_yaml(&serde_json::json!({
+            "op_hash": op.to_hash(),
+            "op_type": format!("{:?}", warrant_op.get_type()),
+            "basis": op.dht_basis(),
+            "warrant": warrant_op,
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::prelude::{
+        Action, ActionData, ActionHeader, AgentPubKey, ChainOp, DhtOpHash, DnaData, DnaHash,
+        Signature, SignedAction, Timestamp,
+    };
+
+    fn activity_op(seq: u32) -> DhtOp {
+        let action = Action {
+            header: ActionHeader {
+                author: AgentPubKey::from_raw_36(vec![1; 36]),
+                timestamp: Timestamp::from_micros(seq as i64),
+                action_seq: seq,
+                prev_action: None,
+            },
+            data: ActionData::Dna(DnaData {
+                dna_hash: DnaHash::from_raw_36(vec![2; 36]),
+            }),
+        };
+        ChainOp::AgentActivity(SignedAction::new(action, Signature([0; 64]))).into()
+    }
+
+    #[test]
+    fn page_lists_ops_by_lifecycle_stage() {
+        let cursor = DhtOpsCursor {
+            when_received: 42,
+            hash: DhtOpHash::from_raw_36(vec![3; 36]),
+        };
+        let dump = FullIntegrationStateDump {
+            validation_limbo: vec![activity_op(0)],
+            integration_limbo: vec![activity_op(1), activity_op(2)],
+            integrated: vec![activity_op(3)],
+            dht_ops_cursor: Some(cursor.clone()),
+        };
+
+        let page = OpsPage::from(dump);
+
+        assert_eq!(
+            vec![
+                (Bucket::ValidationLimbo, activity_op(0)),
+                (Bucket::IntegrationLimbo, activity_op(1)),
+                (Bucket::IntegrationLimbo, activity_op(2)),
+                (Bucket::Integrated, activity_op(3)),
+            ],
+            page.ops
+        );
+        assert_eq!(Some(cursor), page.next_cursor);
+    }
+
+    #[test]
+    fn empty_page_has_no_next_cursor() {
+        let dump = FullIntegrationStateDump {
+            validation_limbo: Vec::new(),
+            integration_limbo: Vec::new(),
+            integrated: Vec::new(),
+            dht_ops_cursor: None,
+        };
+
+        let page = OpsPage::from(dump);
+
+        assert!(page.ops.is_empty());
+        assert!(page.next_cursor.is_none());
+    }
+}
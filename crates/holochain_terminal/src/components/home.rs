@@ -34,7 +34,7 @@ impl Widget for HomeWidget {
         header.render(layout[0], buf);
 
         let help = Block::default()
-            .title("Use the TAB key to navigate between screens and press ESC to exit the terminal")
+            .title("Use the TAB key to navigate between screens, the arrow keys and ENTER to browse them, and press ESC to exit the terminal")
             .title_alignment(Alignment::Center);
 
         help.render(layout[1], buf);
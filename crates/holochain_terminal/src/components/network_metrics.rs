@@ -1,11 +1,11 @@
 use crate::cli::Args;
-use crate::client::AppClient;
-use crate::components::common::show_message;
+use crate::client::{AdminClient, AppClient};
+use crate::components::common::{fetch, show_message};
 use crate::event::ScreenEvent;
 use anyhow::anyhow;
 use chrono::{DateTime, Utc};
 use holo_hash::{AgentPubKey, DnaHash};
-use holochain_types::network::Kitsune2NetworkMetrics;
+use holochain_types::network::{Kitsune2NetworkMetrics, PeerLatency};
 use holochain_util::tokio_helper::block_on;
 use once_cell::sync::Lazy;
 use ratatui::{prelude::*, widgets::*};
@@ -38,9 +38,17 @@ fn get_network_metrics() -> &'static RwLock<HashMap<DnaHash, Kitsune2NetworkMetr
     NETWORK_INFO.get_or_init(|| RwLock::new(HashMap::with_capacity(0)))
 }
 
+/// The latency measured for each peer URL, keyed by URL
+fn get_peer_latency() -> &'static RwLock<HashMap<String, PeerLatency>> {
+    static PEER_LATENCY: OnceLock<RwLock<HashMap<String, PeerLatency>>> = OnceLock::new();
+
+    PEER_LATENCY.get_or_init(|| RwLock::new(HashMap::with_capacity(0)))
+}
+
 pub struct NetworkMetricsWidget {
     args: Arc<Args>,
     app_client: Option<Arc<Mutex<AppClient>>>,
+    admin_client: Option<Arc<Mutex<AdminClient>>>,
     events: Vec<ScreenEvent>,
 }
 
@@ -48,11 +56,13 @@ impl NetworkMetricsWidget {
     pub fn new(
         args: Arc<Args>,
         app_client: Option<Arc<Mutex<AppClient>>>,
+        admin_client: Option<Arc<Mutex<AdminClient>>>,
         events: Vec<ScreenEvent>,
     ) -> Self {
         Self {
             args,
             app_client,
+            admin_client,
             events,
         }
     }
@@ -110,6 +120,23 @@ impl Widget for NetworkMetricsWidget {
                         return;
                     }
                 }
+
+                if let Some(admin_client) = self.admin_client.clone() {
+                    match fetch("peer latency", async {
+                        admin_client.lock().await.peer_latency().await
+                    }) {
+                        Ok(latency) => {
+                            *get_peer_latency().write().unwrap() = latency
+                                .into_iter()
+                                .map(|peer| (peer.url.as_str().to_string(), peer))
+                                .collect();
+                        }
+                        Err(e) => {
+                            show_message(format!("{e:?}").as_str(), area, buf);
+                            return;
+                        }
+                    }
+                }
             };
         }
 
@@ -118,6 +145,7 @@ impl Widget for NetworkMetricsWidget {
             .as_ref()
             .expect("Should have network metrics params");
 
+        let have_admin_client = self.admin_client.is_some();
         for event in self.events {
             match event {
                 ScreenEvent::NavDown => {
@@ -250,11 +278,56 @@ impl Widget for NetworkMetricsWidget {
                         .filter_map(|meta| meta.1.peer_timeouts)
                         .sum::<u32>(),
                 )),
+                // Bandwidth
+                ListItem::new(format!(
+                    "Gossip bytes sent/recv : {} / {}",
+                    metrics.bandwidth.gossip.sent, metrics.bandwidth.gossip.received,
+                )),
+                ListItem::new(format!(
+                    "Fetch bytes sent/recv  : {} / {}",
+                    metrics.bandwidth.fetch.sent, metrics.bandwidth.fetch.received,
+                )),
+                ListItem::new(format!(
+                    "Gossip paused          : {:?}",
+                    metrics.bandwidth.gossip_paused,
+                )),
             ])
             .block(Block::default().title(" Info ").borders(Borders::ALL))
             .style(Style::default().fg(Color::White));
 
-            Widget::render(detail_line, content_layout[1], buf);
+            let detail_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(14), Constraint::Min(0)])
+                .split(content_layout[1]);
+
+            Widget::render(detail_line, detail_layout[0], buf);
+
+            let peer_latency = get_peer_latency().read().unwrap();
+            let mut peers: Vec<_> = metrics.gossip_state_summary.peer_meta.iter().collect();
+            peers.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+            let rows = peers.into_iter().map(|(url, meta)| {
+                Row::new(vec![
+                    url.as_str().to_string(),
+                    meta.last_gossip_timestamp
+                        .and_then(|ts| DateTime::<Utc>::from_timestamp_micros(ts.as_micros()))
+                        .map(|dt| dt.format("%H:%M:%S").to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    format_latency(peer_latency.get(url.as_str()), have_admin_client),
+                ])
+            });
+            let peers_table = Table::new(
+                rows,
+                [
+                    Constraint::Min(20),
+                    Constraint::Length(12),
+                    Constraint::Length(10),
+                ],
+            )
+            .header(Row::new(vec!["Peer", "Last gossip", "Latency"]).style(Style::default().bold()))
+            .block(Block::default().title(" Peers ").borders(Borders::ALL))
+            .style(Style::default().fg(Color::White));
+
+            Widget::render(peers_table, detail_layout[1], buf);
 
             Some(selected)
         } else {
@@ -270,6 +343,19 @@ impl Widget for NetworkMetricsWidget {
     }
 }
 
+/// Show the average round-trip time to a peer, or why there is none
+fn format_latency(latency: Option<&PeerLatency>, have_admin_client: bool) -> String {
+    match latency {
+        _ if !have_admin_client => "no admin".to_string(),
+        Some(latency) if latency.failed => "failed".to_string(),
+        Some(PeerLatency {
+            average_us: Some(average_us),
+            ..
+        }) => format!("{:.1} ms", *average_us as f64 / 1000.0),
+        _ => "-".to_string(),
+    }
+}
+
 fn get_network_metrics_params(
     app_client: Arc<Mutex<AppClient>>,
     app_id: String,
@@ -0,0 +1,220 @@
+use crate::client::AdminClient;
+use crate::components::apps::selected_cell;
+use crate::components::common::{
+    entry_to_value, fetch, show_detail, show_message, to_yaml, PAGE_SIZE,
+};
+use crate::event::ScreenEvent;
+use holochain_conductor_api::SourceChainCursor;
+use holochain_state_types::SourceChainDumpRecord;
+use holochain_types::prelude::CellId;
+use ratatui::{prelude::*, widgets::*};
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::sync::Mutex;
+
+#[derive(Default)]
+struct SourceChainState {
+    /// The cell that the records were fetched for
+    cell_id: Option<CellId>,
+    /// The cursor that each page up to the current one was fetched with
+    cursors: Vec<Option<SourceChainCursor>>,
+    /// The records on the current page
+    records: Vec<SourceChainDumpRecord>,
+    selected: usize,
+    show_detail: bool,
+}
+
+fn get_state() -> &'static RwLock<SourceChainState> {
+    static STATE: OnceLock<RwLock<SourceChainState>> = OnceLock::new();
+
+    STATE.get_or_init(|| RwLock::new(SourceChainState::default()))
+}
+
+pub struct SourceChainWidget {
+    admin_client: Option<Arc<Mutex<AdminClient>>>,
+    events: Vec<ScreenEvent>,
+}
+
+impl SourceChainWidget {
+    pub fn new(admin_client: Option<Arc<Mutex<AdminClient>>>, events: Vec<ScreenEvent>) -> Self {
+        Self {
+            admin_client,
+            events,
+        }
+    }
+}
+
+impl Widget for SourceChainWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let admin_client = match self.admin_client {
+            Some(c) => c,
+            None => {
+                show_message("No admin URL configured, to use this screen please re-run the terminal with `--admin-url <my-url>`", area, buf);
+                return;
+            }
+        };
+
+        let cell_id = match selected_cell() {
+            Some(c) => c,
+            None => {
+                show_message(
+                    "No cell chosen, select a cell on the Apps screen and press ENTER",
+                    area,
+                    buf,
+                );
+                return;
+            }
+        };
+
+        let mut state = get_state().write().unwrap();
+
+        if state.cell_id.as_ref() != Some(&cell_id) {
+            match fetch_page(admin_client.clone(), cell_id.clone(), None) {
+                Ok(records) => {
+                    *state = SourceChainState {
+                        cell_id: Some(cell_id.clone()),
+                        cursors: vec![None],
+                        records,
+                        ..Default::default()
+                    };
+                }
+                Err(e) => {
+                    show_message(format!("{e:?}").as_str(), area, buf);
+                    return;
+                }
+            }
+        }
+
+        for event in self.events {
+            let result = match event {
+                ScreenEvent::Refresh => {
+                    let cursor = state.cursors.last().cloned().flatten();
+                    fetch_page(admin_client.clone(), cell_id.clone(), cursor).map(|records| {
+                        state.records = records;
+                    })
+                }
+                ScreenEvent::NextPage if state.records.len() == PAGE_SIZE as usize => {
+                    let cursor = state
+                        .records
+                        .last()
+                        .map(|r| SourceChainCursor::ActionHash(r.action_address.clone()));
+                    fetch_page(admin_client.clone(), cell_id.clone(), cursor.clone()).map(
+                        |records| {
+                            // Stay on the current page if it was the last one
+                            if !records.is_empty() {
+                                state.cursors.push(cursor);
+                                state.records = records;
+                                state.selected = 0;
+                            }
+                        },
+                    )
+                }
+                ScreenEvent::PrevPage if state.cursors.len() > 1 => {
+                    let cursor = state.cursors[state.cursors.len() - 2].clone();
+                    fetch_page(admin_client.clone(), cell_id.clone(), cursor).map(|records| {
+                        state.cursors.pop();
+                        state.records = records;
+                        state.selected = 0;
+                    })
+                }
+                ScreenEvent::NavDown if !state.show_detail => {
+                    if state.selected + 1 < state.records.len() {
+                        state.selected += 1;
+                    }
+                    Ok(())
+                }
+                ScreenEvent::NavUp if !state.show_detail => {
+                    state.selected = state.selected.saturating_sub(1);
+                    Ok(())
+                }
+                ScreenEvent::Select => {
+                    state.show_detail = !state.records.is_empty();
+                    Ok(())
+                }
+                ScreenEvent::Back => {
+                    state.show_detail = false;
+                    Ok(())
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                show_message(format!("{e:?}").as_str(), area, buf);
+                return;
+            }
+        }
+        state.selected = state.selected.min(state.records.len().saturating_sub(1));
+
+        if state.records.is_empty() {
+            show_message("The source chain of this cell is empty", area, buf);
+            return;
+        }
+
+        if state.show_detail {
+            let record = &state.records[state.selected];
+            show_detail(
+                "Record (BACKSPACE to go back)",
+                &record_to_yaml(record),
+                area,
+                buf,
+            );
+            return;
+        }
+
+        let list_items: Vec<ListItem> = state
+            .records
+            .iter()
+            .map(|record| {
+                ListItem::new(format!(
+                    "{:>6}  {:<18}  {}  {}",
+                    record.action.action_seq(),
+                    record.action.action_type().to_string(),
+                    record.action.timestamp(),
+                    record.action_address
+                ))
+            })
+            .collect();
+
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .title(format!(
+                        " Source chain of {}, page {} (LEFT/RIGHT to change page, ENTER for details) ",
+                        cell_id.dna_hash(),
+                        state.cursors.len()
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .style(Style::default().fg(Color::White))
+            .highlight_symbol(">> ");
+
+        StatefulWidget::render(
+            list,
+            area,
+            buf,
+            &mut ListState::default().with_selected(Some(state.selected)),
+        );
+    }
+}
+
+fn fetch_page(
+    admin_client: Arc<Mutex<AdminClient>>,
+    cell_id: CellId,
+    cursor: Option<SourceChainCursor>,
+) -> anyhow::Result<Vec<SourceChainDumpRecord>> {
+    let dump = fetch("source chain", async {
+        admin_client
+            .lock()
+            .await
+            .dump_state(cell_id, cursor, PAGE_SIZE)
+            .await
+    })?;
+    Ok(dump.source_chain_dump.records)
+}
+
+fn record_to_yaml(record: &SourceChainDumpRecord) -> String {
+    to_yaml(&serde_json::json!({
+        "action_address": record.action_address,
+        "header": record.action.header,
+        "data": record.action.data,
+        "entry": record.entry.as_ref().map(entry_to_value),
+    }))
+}
@@ -0,0 +1,148 @@
+use crate::client::AdminClient;
+use crate::components::common::{fetch, show_detail, show_message, to_yaml};
+use crate::event::ScreenEvent;
+use holochain_conductor_api::{StorageBlob, StorageInfo};
+use ratatui::{prelude::*, widgets::*};
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::sync::Mutex;
+
+fn get_storage_info() -> &'static RwLock<Option<StorageInfo>> {
+    static STORAGE_INFO: OnceLock<RwLock<Option<StorageInfo>>> = OnceLock::new();
+
+    STORAGE_INFO.get_or_init(|| RwLock::new(None))
+}
+
+fn get_selected() -> &'static RwLock<usize> {
+    static SELECTED: OnceLock<RwLock<usize>> = OnceLock::new();
+
+    SELECTED.get_or_init(|| RwLock::new(0))
+}
+
+pub struct StorageWidget {
+    admin_client: Option<Arc<Mutex<AdminClient>>>,
+    events: Vec<ScreenEvent>,
+}
+
+impl StorageWidget {
+    pub fn new(admin_client: Option<Arc<Mutex<AdminClient>>>, events: Vec<ScreenEvent>) -> Self {
+        Self {
+            admin_client,
+            events,
+        }
+    }
+}
+
+impl Widget for StorageWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let admin_client = match self.admin_client {
+            Some(c) => c,
+            None => {
+                show_message("No admin URL configured, to use this screen please re-run the terminal with `--admin-url <my-url>`", area, buf);
+                return;
+            }
+        };
+
+        let refresh = self
+            .events
+            .iter()
+            .any(|e| matches!(e, ScreenEvent::Refresh));
+        if refresh || get_storage_info().read().unwrap().is_none() {
+            match fetch("storage info", async {
+                admin_client.lock().await.storage_info().await
+            }) {
+                Ok(info) => *get_storage_info().write().unwrap() = Some(info),
+                Err(e) => {
+                    show_message(format!("{e:?}").as_str(), area, buf);
+                    return;
+                }
+            }
+        }
+
+        let storage_info = get_storage_info().read().unwrap();
+        let blobs = storage_info
+            .as_ref()
+            .map(|info| info.blobs.as_slice())
+            .unwrap_or_default();
+        if blobs.is_empty() {
+            show_message("No storage in use", area, buf);
+            return;
+        }
+
+        let mut selected = get_selected().write().unwrap();
+        *selected = (*selected).min(blobs.len() - 1);
+        for event in self.events {
+            match event {
+                ScreenEvent::NavDown => {
+                    if *selected < blobs.len() - 1 {
+                        *selected += 1;
+                    }
+                }
+                ScreenEvent::NavUp => {
+                    if *selected > 0 {
+                        *selected -= 1;
+                    }
+                }
+                _ => {
+                    // Ignored
+                }
+            }
+        }
+
+        let content_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+
+        let total: usize = blobs
+            .iter()
+            .map(|blob| match blob {
+                StorageBlob::Dna(dna) => dna.dht_data_size_on_disk,
+            })
+            .sum();
+
+        let list_items: Vec<ListItem> = blobs
+            .iter()
+            .map(|blob| match blob {
+                StorageBlob::Dna(dna) => ListItem::new(format!(
+                    "{}  {:>10}  {}",
+                    dna.dna_hash,
+                    format_size(dna.dht_data_size_on_disk),
+                    dna.used_by.join(", ")
+                )),
+            })
+            .collect();
+
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .title(format!(" Storage (total on disk: {}) ", format_size(total)))
+                    .borders(Borders::ALL),
+            )
+            .style(Style::default().fg(Color::White))
+            .highlight_symbol(">> ");
+
+        show_detail("Info", &to_yaml(&blobs[*selected]), content_layout[1], buf);
+
+        StatefulWidget::render(
+            list,
+            content_layout[0],
+            buf,
+            &mut ListState::default().with_selected(Some(*selected)),
+        );
+    }
+}
+
+fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
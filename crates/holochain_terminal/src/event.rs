@@ -6,6 +6,10 @@ pub enum ScreenEvent {
     Refresh,
     NavDown,
     NavUp,
+    NextPage,
+    PrevPage,
+    Select,
+    Back,
 }
 
 pub fn handle_events(app: &mut App) -> anyhow::Result<()> {
@@ -24,6 +28,14 @@ pub fn handle_events(app: &mut App) -> anyhow::Result<()> {
                     app.push_event(ScreenEvent::NavDown)
                 } else if key.code == KeyCode::Up {
                     app.push_event(ScreenEvent::NavUp)
+                } else if key.code == KeyCode::Right || key.code == KeyCode::PageDown {
+                    app.push_event(ScreenEvent::NextPage)
+                } else if key.code == KeyCode::Left || key.code == KeyCode::PageUp {
+                    app.push_event(ScreenEvent::PrevPage)
+                } else if key.code == KeyCode::Enter {
+                    app.push_event(ScreenEvent::Select)
+                } else if key.code == KeyCode::Backspace {
+                    app.push_event(ScreenEvent::Back)
                 }
             }
         }
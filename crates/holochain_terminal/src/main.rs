@@ -9,7 +9,7 @@ use crate::app::App;
 use crate::cli::Args;
 use crate::client::AdminClient;
 use crate::event::handle_events;
-use crate::tui::Tui;
+use crate::tui::{Tui, TAB_TITLES};
 use anyhow::anyhow;
 use clap::Parser;
 use holochain_util::tokio_helper::block_on;
@@ -63,7 +63,7 @@ fn main() -> anyhow::Result<()> {
         (None, None)
     };
 
-    let mut app = App::new(args, admin_client, app_client, 3);
+    let mut app = App::new(args, admin_client, app_client, TAB_TITLES.len());
 
     let backend = CrosstermBackend::new(io::stdout());
     let terminal = Terminal::new(backend)?;
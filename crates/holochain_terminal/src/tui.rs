@@ -1,7 +1,11 @@
 use crate::app::App;
+use crate::components::apps::AppsWidget;
 use crate::components::bootstrap::BootstrapWidget;
+use crate::components::dht_ops::DhtOpsWidget;
 use crate::components::home::HomeWidget;
 use crate::components::network_metrics::NetworkMetricsWidget;
+use crate::components::source_chain::SourceChainWidget;
+use crate::components::storage::StorageWidget;
 use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::{terminal, ExecutableCommand};
 use ratatui::backend::Backend;
@@ -64,16 +68,24 @@ impl<B: Backend> Tui<B> {
     }
 }
 
+/// The titles of the screens, in the order they are switched between
+pub const TAB_TITLES: [&str; 7] = [
+    "Home",
+    "Network",
+    "Bootstrap",
+    "Apps",
+    "Source chain",
+    "DHT ops",
+    "Storage",
+];
+
 fn render(app: &mut App, frame: &mut Frame) {
     let root_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(2), Constraint::Min(0)])
         .split(frame.area());
 
-    let titles = ["Home", "Network", "Bootstrap"]
-        .iter()
-        .cloned()
-        .map(Line::from);
+    let titles = TAB_TITLES.iter().cloned().map(Line::from);
     let tabs = Tabs::new(titles)
         .select(app.tab_index())
         .block(
@@ -96,13 +108,30 @@ fn render(app: &mut App, frame: &mut Frame) {
         }
         1 => {
             let app_client = app.app_client();
-            let network_info_widget = NetworkMetricsWidget::new(app.args(), app_client, events);
+            let network_info_widget =
+                NetworkMetricsWidget::new(app.args(), app_client, app.admin_client(), events);
             frame.render_widget(network_info_widget, root_layout[1]);
         }
         2 => {
             let bootstrap_widget = BootstrapWidget::new(app.args(), events);
             frame.render_widget(bootstrap_widget, root_layout[1]);
         }
+        3 => {
+            let apps_widget = AppsWidget::new(app.admin_client(), events);
+            frame.render_widget(apps_widget, root_layout[1]);
+        }
+        4 => {
+            let source_chain_widget = SourceChainWidget::new(app.admin_client(), events);
+            frame.render_widget(source_chain_widget, root_layout[1]);
+        }
+        5 => {
+            let dht_ops_widget = DhtOpsWidget::new(app.admin_client(), events);
+            frame.render_widget(dht_ops_widget, root_layout[1]);
+        }
+        6 => {
+            let storage_widget = StorageWidget::new(app.admin_client(), events);
+            frame.render_widget(storage_widget, root_layout[1]);
+        }
         _ => {
             panic!("Page not implemented");
         }
@@ -13,7 +13,9 @@
 mod app_bundle;
 mod app_manifest;
 mod error;
+mod wasm_metering;
 
+use crate::web_app::WebAppManifest;
 use crate::{dna::DnaBundle, prelude::*};
 pub use app_bundle::*;
 pub use app_manifest::app_manifest_validated::*;
@@ -23,12 +25,15 @@ use derive_more::Into;
 pub use error::*;
 use holo_hash::{AgentPubKey, DnaHash};
 use holochain_serialized_bytes::prelude::*;
-use holochain_util::ffs;
 use holochain_zome_types::cell::CloneId;
 use holochain_zome_types::prelude::*;
 use indexmap::IndexMap;
 use itertools::Itertools;
+use mr_bundle::error::MrBundleError;
+use mr_bundle::stream::BundleReader;
+use mr_bundle::FileSystemBundler;
 use std::{collections::HashMap, path::PathBuf};
+pub use wasm_metering::*;
 
 /// The unique identifier for an installed app in this conductor
 pub type InstalledAppId = String;
@@ -62,6 +67,12 @@ pub struct UpdateCoordinatorsPayload {
     pub cell_id: CellId,
     /// Where to find the coordinators.
     pub source: CoordinatorSource,
+    /// Re-queue all abandoned ops of the DNA for validation once the
+    /// coordinators have been updated, as with
+    /// `AdminRequest::RetryAbandonedOps`. A failed retry is logged and does
+    /// not fail the update.
+    #[serde(default)]
+    pub retry_abandoned_ops: bool,
 }
 
 /// The parameters to create a clone of an existing cell.
@@ -141,6 +152,18 @@ pub struct InstallAppPayload {
     /// Requires `agent_key` to be `Some`.
     #[serde(default)]
     pub restore_from_dht: bool,
+
+    /// Optional: Wasm metering budgets for the zome calls and callbacks of this app.
+    ///
+    /// These can only lower the budgets in the conductor config, which are the
+    /// ceiling for every app. They also apply to the genesis and `init` calls
+    /// made while installing the app.
+    ///
+    /// Cells that the app uses from other apps run with the budgets of the app
+    /// that created them, so the app can't be installed if its budgets differ
+    /// from theirs.
+    #[serde(default)]
+    pub wasm_metering: Option<WasmMeteringBudgets>,
 }
 
 /// Alias
@@ -148,6 +171,8 @@ pub type MemproofMap = HashMap<RoleName, MembraneProof>;
 /// Alias
 pub type ModifiersMap = HashMap<RoleName, DnaModifiersOpt<YamlProperties>>;
 /// Alias
+pub type RoleNetworkMap = HashMap<RoleName, RoleNetworkConfig>;
+/// Alias
 pub type ExistingCellsMap = HashMap<RoleName, CellId>;
 /// Alias
 pub type InitPropertiesMap = HashMap<RoleName, InitProperties>;
@@ -187,6 +212,9 @@ pub enum RoleSettings {
         /// by the conductor alongside the app at install time and read back during `init` via the
         /// `hdk::migrate::get_init_properties` host function.
         init_properties: Option<InitProperties>,
+        /// Overrides the network settings of the role from the app manifest. Only the
+        /// settings that are provided will be overwritten.
+        network: Option<RoleNetworkConfig>,
     },
 }
 
@@ -196,6 +224,7 @@ impl Default for RoleSettings {
             membrane_proof: None,
             modifiers: None,
             init_properties: None,
+            network: None,
         }
     }
 }
@@ -207,10 +236,12 @@ impl From<RoleSettingsYaml> for RoleSettings {
                 membrane_proof,
                 modifiers,
                 init_properties,
+                network,
             } => Self::Provisioned {
                 membrane_proof,
                 modifiers,
                 init_properties,
+                network,
             },
             #[allow(deprecated)]
             RoleSettingsYaml::UseExisting { cell_id } => Self::UseExisting { cell_id },
@@ -249,6 +280,9 @@ pub enum RoleSettingsYaml {
         /// conductor-side at install time and read back during `init` via the
         /// `hdk::migrate::get_init_properties` host function.
         init_properties: Option<InitProperties>,
+        /// Overrides the network settings of the role from the app manifest. Only the
+        /// settings that are provided will be overwritten.
+        network: Option<RoleNetworkConfig>,
     },
 }
 
@@ -267,12 +301,104 @@ impl AppBundleSource {
     pub async fn resolve(self) -> Result<AppBundle, AppBundleError> {
         Ok(match self {
             Self::Bytes(bytes) => AppBundle::unpack(bytes.reader())?,
+            Self::Path(path) => FileSystemBundler::load_from::<AppManifest>(&path)
+                .await?
+                .into(),
+        })
+    }
+
+    /// Open the bundle from the source, which may also be a web app bundle.
+    ///
+    /// For a web app bundle, the hApp bundle inside it is opened and returned
+    /// along with its web UI. Only the manifest of the hApp bundle
+    /// is read, its DNAs are read one at a time as they are resolved, see
+    /// [`AppBundleReader`]. A bundle file is read through a file handle rather
+    /// than into memory. Consumes the source.
+    pub async fn open_web_app(
+        self,
+    ) -> Result<(AppBundleReader<BundleSourceReader>, Option<BundledWebUi>), AppBundleError> {
+        tokio::task::spawn_blocking(move || self.open_web_app_blocking())
+            .await
+            .map_err(|e| {
+                MrBundleError::IoError(
+                    "Failed to open bundle".to_string(),
+                    std::io::Error::other(e),
+                )
+            })?
+    }
+
+    /// Open a reader over the source. A file is opened anew on every call.
+    ///
+    /// This blocks, so it must be called from a blocking task.
+    fn reader(&self) -> Result<BundleSourceReader, AppBundleError> {
+        Ok(match self {
+            Self::Bytes(bytes) => Box::new(bytes.clone().reader()),
             Self::Path(path) => {
-                let content = ffs::read(&path).await?;
-                AppBundle::unpack(content.as_slice())?
+                Box::new(std::io::BufReader::new(std::fs::File::open(path).map_err(
+                    |e| MrBundleError::IoError(format!("Failed to read bundle file: {path:?}"), e),
+                )?))
             }
         })
     }
+
+    fn open_web_app_blocking(
+        self,
+    ) -> Result<(AppBundleReader<BundleSourceReader>, Option<BundledWebUi>), AppBundleError> {
+        match AppBundleReader::open(self.reader()?) {
+            Err(AppBundleError::MrBundleError(MrBundleError::MsgpackDecodeError(..))) => {}
+            app_bundle => return Ok((app_bundle?, None)),
+        }
+
+        let mut web_app_bundle = BundleReader::<WebAppManifest, _>::open(self.reader()?)?;
+        let happ_location = web_app_bundle.manifest().happ_bundle_location();
+        let ui_location = web_app_bundle.manifest().web_ui_location();
+        let (mut happ, mut ui) = (None, None);
+        let mut resource_hashes = mr_bundle::ResourceHashes::new();
+        while let Some((resource_id, resource)) = web_app_bundle.next_resource()? {
+            resource_hashes.insert(
+                resource_id.clone(),
+                mr_bundle::resource_hash(resource.as_ref()),
+            );
+            if resource_id == happ_location {
+                happ = Some(resource);
+            } else if resource_id == ui_location {
+                ui = Some(resource);
+            }
+        }
+        let happ: bytes::Bytes = happ
+            .ok_or(MrBundleError::MissingResources(vec![happ_location]))?
+            .into();
+        let ui = ui.ok_or(MrBundleError::MissingResources(vec![ui_location]))?;
+        let (manifest, signature) = web_app_bundle.into_parts();
+        let web_ui = BundledWebUi {
+            zip: ui.into(),
+            publisher_signature: PublisherSignature::new(
+                signature.as_ref(),
+                &manifest,
+                &resource_hashes,
+            )?,
+        };
+
+        Ok((
+            AppBundleReader::open(Box::new(happ.reader()))?,
+            Some(web_ui),
+        ))
+    }
+}
+
+/// A reader over the bytes or the file of an [`AppBundleSource`].
+pub type BundleSourceReader = Box<dyn std::io::Read + Send>;
+
+/// The web UI of a web app bundle.
+#[derive(Debug, Clone)]
+pub struct BundledWebUi {
+    /// The zip file of the UI.
+    pub zip: bytes::Bytes,
+    /// The publisher signature of the web app bundle, if it is signed.
+    ///
+    /// It covers both the UI and the hApp bundle, which includes any signature
+    /// of the hApp bundle itself.
+    pub publisher_signature: Option<PublisherSignature>,
 }
 
 /// Information needed to specify a DNA as part of an App
@@ -385,6 +511,12 @@ pub struct InstalledAppCommon {
 
     /// The timestamp when this app was installed
     pub installed_at: Timestamp,
+
+    /// Wasm metering budgets set when the app was installed.
+    ///
+    /// These are limited by the budgets in the conductor config.
+    #[serde(default)]
+    pub wasm_metering: Option<WasmMeteringBudgets>,
 }
 
 impl InstalledAppCommon {
@@ -427,6 +559,7 @@ impl InstalledAppCommon {
             role_assignments,
             manifest,
             installed_at,
+            wasm_metering: None,
         })
     }
 
@@ -545,6 +678,22 @@ impl InstalledAppCommon {
         self.provisioned_cells().map(|(_, c)| c)
     }
 
+    /// The name of the role that a cell of this app belongs to, either as its
+    /// provisioned cell or as one of its clone cells.
+    pub fn role_name_for_cell(&self, cell_id: &CellId) -> Option<&RoleName> {
+        if cell_id.agent_pubkey() != &self.agent_key {
+            return None;
+        }
+        let dna_hash = cell_id.dna_hash();
+        self.role_assignments.iter().find_map(|(role_name, role)| {
+            let primary = role.as_primary()?;
+            (primary.dna_hash() == dna_hash
+                || primary.clones.values().any(|d| d == dna_hash)
+                || primary.disabled_clones.values().any(|d| d == dna_hash))
+            .then_some(role_name)
+        })
+    }
+
     /// Accessor for particular role
     pub fn role(&self, role_name: &RoleName) -> AppResult<&AppRoleAssignment> {
         self.role_assignments
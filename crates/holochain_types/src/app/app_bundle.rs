@@ -2,9 +2,11 @@
 
 use super::{AppManifest, AppManifestValidated};
 use crate::prelude::*;
-use bytes::Buf;
 use mr_bundle::error::MrBundleError;
-use mr_bundle::{Bundle, ResourceIdentifier};
+use mr_bundle::stream::BundleReader;
+use mr_bundle::{Bundle, BundleSignature, ResourceBytes, ResourceHashes, ResourceIdentifier};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
 use std::io::Read;
 use std::sync::Arc;
 
@@ -45,6 +47,32 @@ impl AppBundle {
         self.0
     }
 
+    /// The bytes a publisher signs to sign this bundle.
+    ///
+    /// See [`Bundle::signing_payload`], any change to the manifest or to a DNA
+    /// invalidates the signature.
+    pub fn signing_payload(&self) -> AppBundleResult<Vec<u8>> {
+        Ok(self.0.signing_payload()?.1)
+    }
+
+    /// The publisher that signed this bundle and their signature, if it is signed.
+    ///
+    /// This does not check the signature, which is left to the conductor.
+    pub fn publisher_signature(&self) -> AppBundleResult<Option<PublisherSignature>> {
+        PublisherSignature::of_bundle(&self.0)
+    }
+
+    /// Return this bundle signed by `publisher`.
+    ///
+    /// The signature must be over [`AppBundle::signing_payload`].
+    pub fn with_publisher_signature(
+        self,
+        publisher: AgentPubKey,
+        signature: Signature,
+    ) -> AppBundleResult<Self> {
+        Ok(with_publisher_signature(self.0, publisher, signature)?.into())
+    }
+
     /// Given a partial list of already available DnaFiles, fetch the missing others via
     /// mr_bundle::Location resolution
     pub async fn resolve_cells(
@@ -56,115 +84,288 @@ impl AppBundle {
         let bundle = Arc::new(self);
         let tasks = roles.into_iter().map(|(role_name, role)| async {
             let bundle = bundle.clone();
+            let dna_bundle = role_dna_path(&role)
+                .map(|resource_id| {
+                    bundle
+                        .get_resource(resource_id)
+                        .ok_or_else(|| MrBundleError::MissingResources(vec![resource_id.clone()]))
+                        .map_err(AppBundleError::from)
+                        .and_then(unpack_dna_bundle)
+                })
+                .transpose()?;
             Ok((
                 role_name.clone(),
-                bundle
-                    .resolve_cell(role_name, role, &existing_cells)
-                    .await?,
+                resolve_cell(role_name, role, dna_bundle, &existing_cells).await?,
             ))
         });
 
-        futures::future::join_all(tasks)
+        let ops = futures::future::join_all(tasks)
             .await
             .into_iter()
-            .collect::<AppBundleResult<Vec<_>>>()?
-            .into_iter()
-            .try_fold(
-                AppRoleResolution::default(),
-                |mut resolution: AppRoleResolution, (role_name, op)| {
-                    match op {
-                        CellProvisioningOp::CreateFromDnaFile(dna, clone_limit) => {
-                            let dna_hash = dna.dna_hash().clone();
-                            let role = AppRolePrimary::new(dna_hash, true, clone_limit).into();
-                            // TODO: could sequentialize this to remove the clone
-                            let proof = membrane_proofs.get(&role_name).cloned();
-                            resolution.dnas_to_register.push((dna, proof));
-                            resolution.role_assignments.push((role_name, role));
-                        }
-
-                        CellProvisioningOp::Existing(cell_id, protected) => {
-                            let role = AppRoleDependency { cell_id, protected }.into();
-                            resolution.role_assignments.push((role_name, role));
-                        }
-
-                        CellProvisioningOp::ProvisionOnly(dna, clone_limit) => {
-                            let dna_hash = dna.dna_hash().clone();
-
-                            // TODO: could sequentialize this to remove the clone
-                            let proof = membrane_proofs.get(&role_name).cloned();
-                            resolution.dnas_to_register.push((dna, proof));
-                            resolution.role_assignments.push((
-                                role_name,
-                                AppRolePrimary::new(dna_hash, false, clone_limit).into(),
-                            ));
-                        }
-                    }
-
-                    Ok(resolution)
-                },
-            )
+            .collect::<AppBundleResult<Vec<_>>>()?;
+        Ok(resolution_from_ops(ops, &membrane_proofs))
     }
+}
 
-    async fn resolve_cell(
-        &self,
-        role_name: RoleName,
-        role: AppRoleManifestValidated,
-        existing_cells: &ExistingCellsMap,
-    ) -> AppBundleResult<CellProvisioningOp> {
-        match role {
-            AppRoleManifestValidated::Create {
-                path: resource_id,
-                clone_limit,
-                modifiers,
-                ..
-            } => {
-                let dna = self.get_modified_dna_file(&resource_id, modifiers).await?.0;
-                Ok(CellProvisioningOp::CreateFromDnaFile(dna, clone_limit))
-            }
+/// An [`AppBundle`] that is read one DNA at a time.
+///
+/// Only the manifest is read when the reader is opened. The DNAs are read by
+/// [`resolve_cells`](AppBundleReader::resolve_cells), which turns each of them into the DNA
+/// files of its roles as soon as it is read, so the bundle is never held in memory as a whole.
+pub struct AppBundleReader<R: Read>(BundleReader<AppManifest, R>);
 
-            #[allow(deprecated)]
-            AppRoleManifestValidated::UseExisting {
-                compatible_hash,
-                protected,
-            } => {
-                if let Some(cell_id) = existing_cells.get(&role_name) {
-                    Ok(CellProvisioningOp::Existing(cell_id.clone(), protected))
-                } else {
-                    Err(AppBundleError::CellResolutionFailure(
-                        role_name,
-                        format!("No existing cell was specified for the role with DNA {compatible_hash}"),
-                    ))
-                }
-            }
+impl<R: Read> AppBundleReader<R> {
+    /// Open an app bundle in either bundle format, see [`BundleReader::open`].
+    pub fn open(source: R) -> AppBundleResult<Self> {
+        Ok(Self(BundleReader::open(source)?))
+    }
 
-            AppRoleManifestValidated::CloneOnly {
-                clone_limit,
-                path: resource_id,
-                modifiers,
-                installed_hash: _,
-            } => {
-                let dna = self.get_modified_dna_file(&resource_id, modifiers).await?.0;
-                Ok(CellProvisioningOp::ProvisionOnly(dna, clone_limit))
+    /// Accessor for the manifest
+    pub fn manifest(&self) -> &AppManifest {
+        self.0.manifest()
+    }
+
+    /// Read the DNAs of this bundle to resolve the cells of `manifest`, which is the manifest of
+    /// this bundle with any overrides applied.
+    ///
+    /// Returns the resolution along with the [publisher signature](AppBundle::publisher_signature)
+    /// of the bundle, which can only be checked once every resource has been read.
+    pub async fn resolve_cells(
+        mut self,
+        manifest: AppManifest,
+        membrane_proofs: MemproofMap,
+        existing_cells: ExistingCellsMap,
+    ) -> AppBundleResult<(AppRoleResolution, Option<PublisherSignature>)> {
+        let AppManifestValidated {
+            name: _,
+            roles: mut unresolved,
+        } = manifest.validate()?;
+        let mut ops = Vec::with_capacity(unresolved.len());
+        let mut resource_hashes = ResourceHashes::new();
+        while let Some((resource_id, resource)) = self.0.next_resource()? {
+            resource_hashes.insert(
+                resource_id.clone(),
+                mr_bundle::resource_hash(resource.as_ref()),
+            );
+
+            let role_names = unresolved
+                .iter()
+                .filter(|(_, role)| role_dna_path(role) == Some(&resource_id))
+                .map(|(role_name, _)| role_name.clone())
+                .collect::<Vec<_>>();
+            if role_names.is_empty() {
+                continue;
+            }
+            let dna_bundle = unpack_dna_bundle(&resource)?;
+            for role_name in role_names {
+                let role = unresolved
+                    .remove(&role_name)
+                    .expect("the role was found above");
+                let op = resolve_cell(
+                    role_name.clone(),
+                    role,
+                    Some(dna_bundle.clone()),
+                    &existing_cells,
+                )
+                .await?;
+                ops.push((role_name, op));
             }
         }
+
+        // The remaining roles either don't use a DNA from the bundle, or their DNA is missing.
+        for (role_name, role) in unresolved {
+            let op = resolve_cell(role_name.clone(), role, None, &existing_cells).await?;
+            ops.push((role_name, op));
+        }
+
+        let (original_manifest, signature) = self.0.into_parts();
+        Ok((
+            resolution_from_ops(ops, &membrane_proofs),
+            PublisherSignature::new(signature.as_ref(), &original_manifest, &resource_hashes)?,
+        ))
+    }
+}
+
+/// The signature of a bundle by its publisher, along with the payload that it must be over.
+///
+/// This does not check the signature, which is left to the conductor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublisherSignature {
+    /// The publisher that signed the bundle.
+    pub publisher: AgentPubKey,
+    /// The signature.
+    pub signature: Signature,
+    /// The bytes the signature must be over, see [`mr_bundle::signing_payload`].
+    pub payload: Vec<u8>,
+}
+
+impl PublisherSignature {
+    /// The publisher signature of a bundle, if it is signed.
+    pub fn of_bundle<M: Debug + Serialize + DeserializeOwned + PartialEq>(
+        bundle: &Bundle<M>,
+    ) -> AppBundleResult<Option<Self>> {
+        Self::new(
+            bundle.signature(),
+            bundle.manifest(),
+            &bundle.resource_hashes(),
+        )
+    }
+
+    /// The publisher signature of a bundle with this manifest and these resources, if it is
+    /// signed.
+    ///
+    /// Fails if the manifest that was signed is not `manifest`.
+    pub fn new<M: DeserializeOwned + PartialEq>(
+        signature: Option<&BundleSignature>,
+        manifest: &M,
+        resource_hashes: &ResourceHashes,
+    ) -> AppBundleResult<Option<Self>> {
+        let Some(bundle_signature) = signature else {
+            return Ok(None);
+        };
+        let publisher = AgentPubKey::try_from_raw_39(bundle_signature.signer.to_vec())
+            .map_err(|e| AppBundleError::InvalidPublisherSignature(e.to_string()))?;
+        let signature = Signature::try_from(bundle_signature.signature.as_ref())
+            .map_err(|e| AppBundleError::InvalidPublisherSignature(e.to_string()))?;
+        if bundle_signature.decode_manifest::<M>()? != *manifest {
+            return Err(AppBundleError::InvalidPublisherSignature(
+                "the signed manifest is not the manifest of the bundle".to_string(),
+            ));
+        }
+        Ok(Some(Self {
+            publisher,
+            signature,
+            payload: mr_bundle::signing_payload(&bundle_signature.manifest, resource_hashes),
+        }))
     }
+}
+
+/// Return `bundle` signed by `publisher`, whose signature must be over its
+/// [signing payload](Bundle::signing_payload).
+pub fn with_publisher_signature<M: Debug + Serialize + DeserializeOwned>(
+    bundle: Bundle<M>,
+    publisher: AgentPubKey,
+    signature: Signature,
+) -> AppBundleResult<Bundle<M>> {
+    let manifest = bundle.encode_manifest()?;
+    Ok(bundle.with_signature(Some(BundleSignature {
+        signer: publisher.get_raw_39().to_vec().into(),
+        signature: signature.0.to_vec().into(),
+        manifest,
+    })))
+}
 
-    async fn get_modified_dna_file(
-        &self,
-        resource_id: &ResourceIdentifier,
-        modifiers: DnaModifiersOpt,
-    ) -> AppBundleResult<(DnaFile, DnaHash)> {
-        let bytes: bytes::Bytes = self
-            .get_resource(resource_id)
-            .ok_or_else(|| MrBundleError::MissingResources(vec![resource_id.clone()]))?
-            .clone()
-            .into();
-        let dna_bundle: DnaBundle = Bundle::unpack(bytes.reader())?.into();
-        let (dna_file, original_hash) = dna_bundle.into_dna_file(modifiers).await?;
-        Ok((dna_file, original_hash))
+/// The resource of the DNA that a role is provisioned from, if it has one.
+fn role_dna_path(role: &AppRoleManifestValidated) -> Option<&ResourceIdentifier> {
+    match role {
+        AppRoleManifestValidated::Create { path, .. }
+        | AppRoleManifestValidated::CloneOnly { path, .. } => Some(path),
+        #[allow(deprecated)]
+        AppRoleManifestValidated::UseExisting { .. } => None,
     }
 }
 
+fn unpack_dna_bundle(resource: &ResourceBytes) -> AppBundleResult<DnaBundle> {
+    Ok(Bundle::unpack(resource.as_ref())?.into())
+}
+
+/// Resolve how to provision the cell of a role, given the DNA bundle that the role uses, if it
+/// was found.
+async fn resolve_cell(
+    role_name: RoleName,
+    role: AppRoleManifestValidated,
+    dna_bundle: Option<DnaBundle>,
+    existing_cells: &ExistingCellsMap,
+) -> AppBundleResult<CellProvisioningOp> {
+    match role {
+        AppRoleManifestValidated::Create {
+            path: resource_id,
+            clone_limit,
+            modifiers,
+            ..
+        } => {
+            let dna = bundled_dna_file(resource_id, dna_bundle, modifiers).await?;
+            Ok(CellProvisioningOp::CreateFromDnaFile(dna, clone_limit))
+        }
+
+        #[allow(deprecated)]
+        AppRoleManifestValidated::UseExisting {
+            compatible_hash,
+            protected,
+        } => {
+            if let Some(cell_id) = existing_cells.get(&role_name) {
+                Ok(CellProvisioningOp::Existing(cell_id.clone(), protected))
+            } else {
+                Err(AppBundleError::CellResolutionFailure(
+                    role_name,
+                    format!(
+                        "No existing cell was specified for the role with DNA {compatible_hash}"
+                    ),
+                ))
+            }
+        }
+
+        AppRoleManifestValidated::CloneOnly {
+            clone_limit,
+            path: resource_id,
+            modifiers,
+            installed_hash: _,
+        } => {
+            let dna = bundled_dna_file(resource_id, dna_bundle, modifiers).await?;
+            Ok(CellProvisioningOp::ProvisionOnly(dna, clone_limit))
+        }
+    }
+}
+
+async fn bundled_dna_file(
+    resource_id: ResourceIdentifier,
+    dna_bundle: Option<DnaBundle>,
+    modifiers: DnaModifiersOpt,
+) -> AppBundleResult<DnaFile> {
+    let dna_bundle = dna_bundle.ok_or(MrBundleError::MissingResources(vec![resource_id]))?;
+    Ok(dna_bundle.into_dna_file(modifiers).await?.0)
+}
+
+fn resolution_from_ops(
+    ops: Vec<(RoleName, CellProvisioningOp)>,
+    membrane_proofs: &MemproofMap,
+) -> AppRoleResolution {
+    ops.into_iter().fold(
+        AppRoleResolution::default(),
+        |mut resolution: AppRoleResolution, (role_name, op)| {
+            match op {
+                CellProvisioningOp::CreateFromDnaFile(dna, clone_limit) => {
+                    let dna_hash = dna.dna_hash().clone();
+                    let role = AppRolePrimary::new(dna_hash, true, clone_limit).into();
+                    // TODO: could sequentialize this to remove the clone
+                    let proof = membrane_proofs.get(&role_name).cloned();
+                    resolution.dnas_to_register.push((dna, proof));
+                    resolution.role_assignments.push((role_name, role));
+                }
+
+                CellProvisioningOp::Existing(cell_id, protected) => {
+                    let role = AppRoleDependency { cell_id, protected }.into();
+                    resolution.role_assignments.push((role_name, role));
+                }
+
+                CellProvisioningOp::ProvisionOnly(dna, clone_limit) => {
+                    let dna_hash = dna.dna_hash().clone();
+
+                    // TODO: could sequentialize this to remove the clone
+                    let proof = membrane_proofs.get(&role_name).cloned();
+                    resolution.dnas_to_register.push((dna, proof));
+                    resolution.role_assignments.push((
+                        role_name,
+                        AppRolePrimary::new(dna_hash, false, clone_limit).into(),
+                    ));
+                }
+            }
+
+            resolution
+        },
+    )
+}
+
 /// The answer to the question:
 /// "how do we concretely assign DNAs to the open roles of this App?"
 /// Includes the DNAs selected to fill the roles and the details of the role assignments.
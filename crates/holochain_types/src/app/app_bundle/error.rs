@@ -19,6 +19,9 @@ pub enum AppBundleError {
 
     #[error(transparent)]
     FfsIoError(#[from] ffs::IoError),
+
+    #[error("The publisher signature of the app bundle is malformed: {0}")]
+    InvalidPublisherSignature(String),
 }
 
 pub type AppBundleResult<T> = Result<T, AppBundleError>;
@@ -1,4 +1,4 @@
-use super::AppBundle;
+use super::{AppBundle, AppBundleReader};
 use crate::prelude::*;
 use app_manifest_v0::tests::{app_manifest_fixture, app_manifest_properties_fixture};
 
@@ -62,3 +62,79 @@ async fn provisioning_1_create() {
     };
     assert_eq!(resolution, expected);
 }
+
+#[tokio::test]
+async fn publisher_signature_round_trip() {
+    let (bundle, _) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+    assert!(bundle.publisher_signature().unwrap().is_none());
+
+    let payload = bundle.signing_payload().unwrap();
+    let publisher = AgentPubKey::from_raw_32(vec![1; 32]);
+    let signature = Signature::from([2; 64]);
+    let signed = bundle
+        .clone()
+        .with_publisher_signature(publisher.clone(), signature.clone())
+        .unwrap();
+
+    // The signature itself is not part of what is signed.
+    assert_eq!(payload, signed.signing_payload().unwrap());
+
+    let expected = PublisherSignature {
+        publisher,
+        signature,
+        payload,
+    };
+    for packed in [signed.pack().unwrap(), signed.pack_streaming().unwrap()] {
+        let unpacked = AppBundle::unpack(bytes::Buf::reader(packed)).unwrap();
+        assert_eq!(
+            Some(&expected),
+            unpacked.publisher_signature().unwrap().as_ref()
+        );
+    }
+
+    // A signature moved to a bundle with another manifest is rejected.
+    let mut manifest = bundle.manifest().clone();
+    manifest.set_network_seed("other".to_string());
+    let other = AppBundle::from(
+        bundle
+            .into_inner()
+            .update_manifest(manifest)
+            .unwrap()
+            .with_signature(signed.signature().cloned()),
+    );
+    assert!(matches!(
+        other.publisher_signature(),
+        Err(AppBundleError::InvalidPublisherSignature(_))
+    ));
+}
+
+/// Test that reading a bundle one DNA at a time resolves the same cells and publisher signature
+/// as the whole bundle, in either bundle format.
+#[tokio::test]
+async fn reader_resolves_like_bundle() {
+    let (bundle, _) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+    let bundle = bundle
+        .with_publisher_signature(
+            AgentPubKey::from_raw_32(vec![1; 32]),
+            Signature::from([2; 64]),
+        )
+        .unwrap();
+    let publisher_signature = bundle.publisher_signature().unwrap();
+    assert!(publisher_signature.is_some());
+    let packed = [bundle.pack().unwrap(), bundle.pack_streaming().unwrap()];
+    let expected = bundle
+        .resolve_cells(Default::default(), Default::default())
+        .await
+        .unwrap();
+
+    for bytes in packed {
+        let reader = AppBundleReader::open(bytes::Buf::reader(bytes)).unwrap();
+        let manifest = reader.manifest().clone();
+        let (resolution, reader_publisher_signature) = reader
+            .resolve_cells(manifest, Default::default(), Default::default())
+            .await
+            .unwrap();
+        assert_eq!(expected, resolution);
+        assert_eq!(publisher_signature, reader_publisher_signature);
+    }
+}
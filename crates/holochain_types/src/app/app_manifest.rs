@@ -47,8 +47,8 @@ mod current;
 mod error;
 
 use self::app_manifest_validated::AppManifestValidated;
-use super::ModifiersMap;
-pub use app_manifest_v0::{AppRoleDnaManifest, CellProvisioning};
+use super::{ModifiersMap, RoleNetworkMap};
+pub use app_manifest_v0::{AppRoleDnaManifest, CellProvisioning, RoleNetworkConfig};
 pub use current::*;
 pub use error::*;
 
@@ -137,6 +137,14 @@ impl AppManifest {
         }
     }
 
+    /// Selectively override the network settings of the specified roles. Only settings that
+    /// are provided will override the corresponding setting in the manifest.
+    pub fn override_network(&mut self, network: RoleNetworkMap) -> AppManifestResult<()> {
+        match self {
+            Self::V0(manifest) => manifest.override_network(network),
+        }
+    }
+
     /// Returns the list of app roles that this manifest declares
     pub fn app_roles(&self) -> Vec<AppRoleManifest> {
         match self {
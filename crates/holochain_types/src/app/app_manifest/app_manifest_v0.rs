@@ -88,6 +88,12 @@ pub struct AppRoleManifest {
     /// Declares where to find the DNA, and options to modify it before
     /// inclusion in a Cell
     pub dna: AppRoleDnaManifest,
+
+    /// Optional network settings for the Cells of this role.
+    ///
+    /// These are merged over the network settings in the conductor config.
+    #[serde(default)]
+    pub network: Option<RoleNetworkConfig>,
 }
 
 impl AppRoleManifest {
@@ -97,6 +103,7 @@ impl AppRoleManifest {
             name,
             provisioning: Some(CellProvisioning::default()),
             dna: AppRoleDnaManifest::sample(),
+            network: None,
         }
     }
 }
@@ -148,6 +155,71 @@ impl AppRoleDnaManifest {
     }
 }
 
+/// Network settings for the Cells of an app role.
+///
+/// Each setting that is provided takes precedence over the corresponding
+/// setting in the conductor config, for the network space of the role's DNA.
+/// Cells of the same DNA share a network space, so Cells of the same DNA in
+/// different roles or apps must use the same settings. Otherwise the space
+/// keeps the settings of the first Cell that joined it.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RoleNetworkConfig {
+    /// Don't gossip with peers in the network space of this role.
+    #[serde(default)]
+    pub disable_gossip: Option<bool>,
+
+    /// Don't publish the data authored by the Cells of this role. The data
+    /// still reaches other peers through gossip.
+    #[serde(default)]
+    pub disable_publish: Option<bool>,
+
+    /// The target arc factor of the agents of this role.
+    #[serde(default)]
+    pub target_arc_factor: Option<u32>,
+
+    /// The timeout in seconds for network requests made by the Cells of this role.
+    #[serde(default)]
+    pub request_timeout_s: Option<u64>,
+
+    /// Advanced Kitsune2 module config.
+    ///
+    /// This has the same format as the `advanced` network config of the
+    /// conductor, and is merged over it field by field.
+    #[serde(default)]
+    pub advanced: Option<serde_json::Value>,
+}
+
+impl RoleNetworkConfig {
+    /// Override these settings with the settings that are provided in `other`.
+    pub fn override_with(&mut self, other: RoleNetworkConfig) {
+        let RoleNetworkConfig {
+            disable_gossip,
+            disable_publish,
+            target_arc_factor,
+            request_timeout_s,
+            advanced,
+        } = other;
+        if disable_gossip.is_some() {
+            self.disable_gossip = disable_gossip;
+        }
+        if disable_publish.is_some() {
+            self.disable_publish = disable_publish;
+        }
+        if target_arc_factor.is_some() {
+            self.target_arc_factor = target_arc_factor;
+        }
+        if request_timeout_s.is_some() {
+            self.request_timeout_s = request_timeout_s;
+        }
+        if advanced.is_some() {
+            self.advanced = advanced;
+        }
+    }
+}
+
 /// Rules to determine if and how a Cell will be created for this Dna
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
@@ -232,6 +304,29 @@ impl AppManifestV0 {
         Ok(())
     }
 
+    /// Selectively overrides the network settings of the given roles. Only settings that are
+    /// provided will override the corresponding setting in the manifest.
+    pub fn override_network(
+        &mut self,
+        network: HashMap<RoleName, RoleNetworkConfig>,
+    ) -> AppManifestResult<()> {
+        for role_name in network.keys() {
+            if !self.roles.iter().any(|role| &role.name == role_name) {
+                return Err(AppManifestError::InvalidRoleName(format!(
+                    "Tried to set network settings for a role name that does not exist in the app manifest: {role_name}"
+                )));
+            }
+        }
+        for role in self.roles.iter_mut() {
+            if let Some(role_network) = network.get(&role.name) {
+                role.network
+                    .get_or_insert_with(Default::default)
+                    .override_with(role_network.clone());
+            }
+        }
+        Ok(())
+    }
+
     /// Convert this human-focused manifest into a validated, concise representation
     pub fn validate(self) -> AppManifestResult<AppManifestValidated> {
         let AppManifestV0 {
@@ -249,6 +344,7 @@ impl AppManifestV0 {
                      name,
                      provisioning,
                      dna,
+                     network: _,
                  }| {
                     let AppRoleDnaManifest {
                         path,
@@ -331,6 +427,7 @@ pub mod tests {
                 clone_limit: 50,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
+            network: None,
         }];
         AppManifestV0 {
             name: "Test app".to_string(),
@@ -418,6 +515,7 @@ roles:
                     installed_hash: None,
                     clone_limit: 0,
                 },
+                network: None,
             },
             AppRoleManifest {
                 name: "test-role-2".to_string(),
@@ -428,6 +526,7 @@ roles:
                     installed_hash: None,
                     clone_limit: 0,
                 },
+                network: None,
             },
         ];
         manifest.roles[0].provisioning = Some(CellProvisioning::Create { deferred: false });
@@ -446,4 +545,81 @@ roles:
             Some(&network_seed)
         );
     }
+    #[test]
+    fn manifest_v0_override_network() {
+        let manifest_yaml = r#"
+manifest_version: "0"
+name: "test"
+roles:
+  - name: "chat"
+    dna:
+      path: ./chat.dna
+    network:
+      disable_gossip: true
+      target_arc_factor: 0
+      advanced:
+        k2Gossip:
+          initiateIntervalMs: 10000
+  - name: "wiki"
+    dna:
+      path: ./wiki.dna
+"#;
+        let manifest: AppManifest = yaml_serde::from_str(manifest_yaml).unwrap();
+        let AppManifest::V0(mut manifest) = manifest;
+
+        assert_eq!(
+            manifest.roles[0].network,
+            Some(RoleNetworkConfig {
+                disable_gossip: Some(true),
+                target_arc_factor: Some(0),
+                advanced: Some(serde_json::json!({
+                    "k2Gossip": { "initiateIntervalMs": 10000 }
+                })),
+                ..Default::default()
+            })
+        );
+        assert_eq!(manifest.roles[1].network, None);
+
+        manifest
+            .override_network(HashMap::from([
+                (
+                    "chat".to_string(),
+                    RoleNetworkConfig {
+                        target_arc_factor: Some(1),
+                        request_timeout_s: Some(10),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "wiki".to_string(),
+                    RoleNetworkConfig {
+                        disable_publish: Some(true),
+                        ..Default::default()
+                    },
+                ),
+            ]))
+            .unwrap();
+
+        // - Only the provided settings are overridden.
+        let chat_network = manifest.roles[0].network.clone().unwrap();
+        assert_eq!(chat_network.disable_gossip, Some(true));
+        assert_eq!(chat_network.target_arc_factor, Some(1));
+        assert_eq!(chat_network.request_timeout_s, Some(10));
+        assert!(chat_network.advanced.is_some());
+        assert_eq!(
+            manifest.roles[1].network,
+            Some(RoleNetworkConfig {
+                disable_publish: Some(true),
+                ..Default::default()
+            })
+        );
+
+        // - Unknown roles are rejected.
+        assert!(manifest
+            .override_network(HashMap::from([(
+                "unknown".to_string(),
+                RoleNetworkConfig::default()
+            )]))
+            .is_err());
+    }
 }
@@ -0,0 +1,157 @@
+//! Wasm metering budgets for the zome calls and callbacks of an app.
+
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+
+/// Limits on the wasm metering points that calls into an app's zomes may
+/// consume before they are trapped.
+///
+/// Budgets can be set in the conductor config or when installing an app.
+/// Any budget that is not set falls back to the next, less specific source,
+/// and finally to the limit built into the wasm host. Budgets set when
+/// installing an app can only lower the ones from the conductor config.
+///
+/// Budgets only apply to wasm backends that meter execution; the wasmi
+/// backend does not.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, JsonSchema,
+)]
+pub struct WasmMeteringBudgets {
+    /// Points available to each zome call, and to the `init` and
+    /// `post_commit` callbacks.
+    #[serde(default)]
+    pub zome_call: Option<u64>,
+
+    /// Points available to calls of specific zome functions, keyed by zome
+    /// name and then function name. Takes precedence over `zome_call`.
+    #[serde(default)]
+    pub zome_fns: BTreeMap<String, BTreeMap<String, u64>>,
+
+    /// Points available to each validation callback, that is `validate` and
+    /// `genesis_self_check`.
+    #[serde(default)]
+    pub validation: Option<u64>,
+}
+
+impl WasmMeteringBudgets {
+    /// Fill in any budget that is not set here from `fallback`.
+    ///
+    /// Function budgets are merged, with the ones set here taking precedence.
+    pub fn or(mut self, fallback: &WasmMeteringBudgets) -> Self {
+        self.zome_call = self.zome_call.or(fallback.zome_call);
+        self.validation = self.validation.or(fallback.validation);
+        for (zome_name, fns) in &fallback.zome_fns {
+            let zome_fns = self.zome_fns.entry(zome_name.clone()).or_default();
+            for (fn_name, budget) in fns {
+                zome_fns.entry(fn_name.clone()).or_insert(*budget);
+            }
+        }
+        self
+    }
+
+    /// Limit these budgets to the ones in `ceiling`.
+    ///
+    /// Any budget that is not set here is taken from `ceiling`, and a budget
+    /// that is not set in `ceiling` does not limit the one set here.
+    pub fn clamp_to(&self, ceiling: &WasmMeteringBudgets) -> Self {
+        let min = |own: Option<u64>, max: Option<u64>| match (own, max) {
+            (Some(own), Some(max)) => Some(own.min(max)),
+            (own, max) => own.or(max),
+        };
+        let mut zome_fns: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+        for (zome_name, fns) in self.zome_fns.iter().chain(&ceiling.zome_fns) {
+            for fn_name in fns.keys() {
+                if let Some(budget) = min(
+                    self.zome_fn_budget(zome_name, fn_name),
+                    ceiling.zome_fn_budget(zome_name, fn_name),
+                ) {
+                    zome_fns
+                        .entry(zome_name.clone())
+                        .or_default()
+                        .insert(fn_name.clone(), budget);
+                }
+            }
+        }
+        WasmMeteringBudgets {
+            zome_call: min(self.zome_call, ceiling.zome_call),
+            zome_fns,
+            validation: min(self.validation, ceiling.validation),
+        }
+    }
+
+    /// The budget for a call to a function of a zome, if one is set.
+    pub fn zome_fn_budget(&self, zome_name: &str, fn_name: &str) -> Option<u64> {
+        self.zome_fns
+            .get(zome_name)
+            .and_then(|fns| fns.get(fn_name))
+            .copied()
+            .or(self.zome_call)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn or_prefers_own_budgets() {
+        let app = WasmMeteringBudgets {
+            zome_call: Some(10),
+            zome_fns: [("z".to_string(), [("a".to_string(), 1)].into())].into(),
+            validation: None,
+        };
+        let fallback = WasmMeteringBudgets {
+            zome_call: Some(20),
+            zome_fns: [(
+                "z".to_string(),
+                [("a".to_string(), 2), ("b".to_string(), 3)].into(),
+            )]
+            .into(),
+            validation: Some(30),
+        };
+
+        let merged = app.or(&fallback);
+
+        assert_eq!(merged.zome_fn_budget("z", "a"), Some(1));
+        assert_eq!(merged.zome_fn_budget("z", "b"), Some(3));
+        assert_eq!(merged.zome_fn_budget("z", "c"), Some(10));
+        assert_eq!(merged.zome_fn_budget("y", "a"), Some(10));
+        assert_eq!(merged.validation, Some(30));
+    }
+
+    #[test]
+    fn clamp_to_never_raises_budgets() {
+        let installed = WasmMeteringBudgets {
+            zome_call: Some(50),
+            zome_fns: [(
+                "z".to_string(),
+                [("a".to_string(), 1), ("b".to_string(), 100)].into(),
+            )]
+            .into(),
+            validation: None,
+        };
+        let ceiling = WasmMeteringBudgets {
+            zome_call: Some(20),
+            zome_fns: [(
+                "z".to_string(),
+                [("b".to_string(), 5), ("c".to_string(), 40)].into(),
+            )]
+            .into(),
+            validation: Some(30),
+        };
+
+        let clamped = installed.clamp_to(&ceiling);
+
+        assert_eq!(clamped.zome_fn_budget("z", "a"), Some(1));
+        assert_eq!(clamped.zome_fn_budget("z", "b"), Some(5));
+        assert_eq!(clamped.zome_fn_budget("z", "c"), Some(40));
+        assert_eq!(clamped.zome_fn_budget("y", "a"), Some(20));
+        assert_eq!(clamped.validation, Some(30));
+
+        // Without a ceiling the installed budgets are kept.
+        assert_eq!(
+            installed.clamp_to(&WasmMeteringBudgets::default()),
+            installed
+        );
+    }
+}
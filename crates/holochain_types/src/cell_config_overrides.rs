@@ -3,10 +3,13 @@
 //! This module defines structures and functions for overriding
 //! Cell configuration settings in Holochain applications.
 
+use crate::app::RoleNetworkConfig;
+
 /// Overrides for Cell configuration settings.
 ///
 /// This struct holds optional override values for Cell configurations
-/// such as bootstrap URLs and relay server URLs.
+/// such as bootstrap URLs, relay server URLs and the network settings of
+/// the Cell's role.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct CellConfigOverrides {
     /// URL of the bootstrap server to use for all Cells created
@@ -17,6 +20,9 @@ pub struct CellConfigOverrides {
     /// for an app. If not overridden, the relay server
     /// specified in the conductor config file will be used.
     pub relay_url: Option<String>,
+    /// Network settings of the role that the Cell belongs to. Settings
+    /// that are not provided are taken from the conductor config.
+    pub network: RoleNetworkConfig,
 }
 
 impl CellConfigOverrides {
@@ -24,7 +30,9 @@ impl CellConfigOverrides {
     ///
     /// Returns `true` if at least one override field is [`Some`], otherwise returns `false`.
     pub fn is_overriding(&self) -> bool {
-        self.bootstrap_url.is_some() || self.relay_url.is_some()
+        self.bootstrap_url.is_some()
+            || self.relay_url.is_some()
+            || self.network != RoleNetworkConfig::default()
     }
 }
 
@@ -37,24 +45,38 @@ mod tests {
         let overrides = CellConfigOverrides {
             bootstrap_url: None,
             relay_url: None,
+            network: RoleNetworkConfig::default(),
         };
         assert!(!overrides.is_overriding());
 
         let overrides = CellConfigOverrides {
             bootstrap_url: Some("http://localhost:1234".to_string()),
             relay_url: None,
+            network: RoleNetworkConfig::default(),
         };
         assert!(overrides.is_overriding());
 
         let overrides = CellConfigOverrides {
             bootstrap_url: None,
             relay_url: Some("ws://localhost:5678".to_string()),
+            network: RoleNetworkConfig::default(),
         };
         assert!(overrides.is_overriding());
 
         let overrides = CellConfigOverrides {
             bootstrap_url: Some("http://localhost:1234".to_string()),
             relay_url: Some("ws://localhost:5678".to_string()),
+            network: RoleNetworkConfig::default(),
+        };
+        assert!(overrides.is_overriding());
+
+        let overrides = CellConfigOverrides {
+            bootstrap_url: None,
+            relay_url: None,
+            network: RoleNetworkConfig {
+                disable_gossip: Some(true),
+                ..Default::default()
+            },
         };
         assert!(overrides.is_overriding());
     }
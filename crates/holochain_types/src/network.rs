@@ -1,6 +1,7 @@
 //! Types for interacting with Holochain's network layer.
 
 use holo_hash::DnaHash;
+use holochain_timestamp::Timestamp;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -32,6 +33,10 @@ pub struct Kitsune2NetworkMetrics {
 
     /// A summary of the state of each local agent.
     pub local_agents: Vec<LocalAgentSummary>,
+
+    /// The bytes sent and received in this space since the conductor started.
+    #[serde(default)]
+    pub bandwidth: SpaceBandwidth,
 }
 
 /// Summary of a local agent's network state.
@@ -65,3 +70,114 @@ pub struct HolochainTransportStats {
     pub blocked_message_counts:
         HashMap<kitsune2_api::Url, HashMap<DnaHash, kitsune2_api::MessageBlockCount>>,
 }
+
+/// The round-trip latency measured by pinging a peer URL.
+///
+/// Peer URLs are pinged when they are first encountered, and again before their estimate expires.
+/// The estimate is used to prefer lower latency peers when selecting authorities to make
+/// requests to, and peers whose pings keep failing are not selected at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerLatency {
+    /// The URL of the peer.
+    pub url: kitsune2_api::Url,
+
+    /// The round-trip times of the most recent pings in microseconds, oldest first.
+    pub samples_us: Vec<u64>,
+
+    /// The average of the samples in microseconds, or `None` if no ping has succeeded.
+    pub average_us: Option<u64>,
+
+    /// The number of ping rounds in a row in which every ping failed.
+    pub consecutive_failures: u32,
+
+    /// Whether so many ping rounds have failed that the peer is no longer pinged or
+    /// selected for requests, until it is encountered again.
+    pub failed: bool,
+
+    /// How long ago the last sample was recorded, in milliseconds.
+    pub sampled_ms_ago: Option<u64>,
+
+    /// How long until the estimate expires, in milliseconds. This is `0` if it already has.
+    pub expires_in_ms: Option<u64>,
+}
+
+/// How much of a space's DHT the local agents of a conductor hold.
+///
+/// By default every space uses the target arc factor from the conductor's network config.
+/// A policy can be set per DNA at runtime to change that without the agents rejoining.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ArcPolicy {
+    /// Apply this factor to the target arc hints from Kitsune2.
+    ///
+    /// A factor of 0 holds nothing and 1 holds what Kitsune2 suggests. Larger factors are not
+    /// supported yet.
+    TargetArcFactor(u32),
+
+    /// Pin the target arc to the full DHT, regardless of the hints from Kitsune2.
+    Full,
+
+    /// Pin the target arc to nothing, and stop claiming authority for any part of the DHT.
+    Empty,
+}
+
+/// The bytes sent to and received from peers for one kind of traffic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficBytes {
+    /// The number of bytes sent to peers.
+    pub sent: u64,
+
+    /// The number of bytes received from peers.
+    pub received: u64,
+}
+
+/// The network traffic of a space, counted by the kind of traffic.
+///
+/// Only message payloads are counted, not the framing and encryption overhead of the transport.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpaceBandwidth {
+    /// Gossip with peers to sync the DHT.
+    pub gossip: TrafficBytes,
+
+    /// Fetching ops that were discovered through gossip or publish, and serving them to peers.
+    pub fetch: TrafficBytes,
+
+    /// Publishing ops to their authorities.
+    pub publish: TrafficBytes,
+
+    /// All other traffic, such as gets, remote calls and signals.
+    pub other: TrafficBytes,
+
+    /// Whether gossip is paused because a bandwidth limit of an app that uses the space has been
+    /// reached.
+    pub gossip_paused: bool,
+}
+
+/// Limits on the background network traffic of an app.
+///
+/// The gossip and fetch traffic of all the spaces that an app's cells are in counts towards its
+/// limits. When a limit is reached, gossip in those spaces is paused until the limit period is
+/// over. While the app has a rate limit, gossip messages in those spaces are also held back to
+/// that rate. Gets, publishes and other traffic that the app asks for
+/// directly are not paused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthLimits {
+    /// The number of bytes that may be sent and received in a day.
+    ///
+    /// The day starts when the limits are applied, and a new one starts every 24 hours. The
+    /// traffic counted in a day is persisted, so restarting the conductor doesn't start a new day.
+    pub daily_bytes: Option<u64>,
+
+    /// The number of bytes per second that may be sent and received, on average over a minute.
+    pub bytes_per_second: Option<u64>,
+}
+
+/// The traffic counted towards the daily limit of an app, as persisted between restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthUsage {
+    /// When the current day of the daily limit started.
+    pub day_start: Timestamp,
+
+    /// The number of bytes sent and received since the day started.
+    pub day_bytes: u64,
+}
@@ -68,6 +68,35 @@ pub enum SystemSignal {
         /// Why the restore is unrecoverable.
         reason: UnrecoverableCellReason,
     },
+    /// A durable remote signal could not be sent to an agent, and was put in the
+    /// outbox to be retried.
+    RemoteSignalQueued {
+        /// The cell that sent the signal.
+        cell_id: CellId,
+        /// The agent that the signal is for.
+        to_agent: AgentPubKey,
+        /// The ID of the signal in the outbox.
+        outbox_id: u64,
+    },
+    /// A durable remote signal from the outbox has been sent to its agent.
+    RemoteSignalDelivered {
+        /// The cell that sent the signal.
+        cell_id: CellId,
+        /// The agent that the signal is for.
+        to_agent: AgentPubKey,
+        /// The ID of the signal in the outbox.
+        outbox_id: u64,
+    },
+    /// A durable remote signal could not be sent to its agent before its TTL
+    /// passed, and was removed from the outbox.
+    RemoteSignalExpired {
+        /// The cell that sent the signal.
+        cell_id: CellId,
+        /// The agent that the signal is for.
+        to_agent: AgentPubKey,
+        /// The ID of the signal in the outbox.
+        outbox_id: u64,
+    },
 }
 
 impl_from! {
@@ -81,6 +110,99 @@ pub const DIRECT_SIGNAL_MAX_SIZE: usize = 1024 * 1024;
 #[derive(Debug, Clone, Serialize, Deserialize, SerializedBytes)]
 pub struct DirectSignal(pub Vec<u8>);
 
+/// What is signed for a direct signal that is sent with acknowledgement.
+///
+/// The idempotency key is signed along with the signal, so that the signal
+/// can't be replayed with a new key to have it passed on again.
+#[derive(Debug, Clone, Serialize, Deserialize, SerializedBytes)]
+pub struct AckedDirectSignal {
+    /// The signal, as in [`DirectSignal`].
+    pub signal: Vec<u8>,
+    /// The idempotency key that the signal is sent with.
+    pub idempotency_key: u64,
+}
+
+/// What happened to a direct signal that was sent with acknowledgement.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectSignalOutcome {
+    /// The receiving conductor passed the signal to at least one subscribed app interface.
+    Delivered,
+    /// The receiving conductor accepted the signal, but no app interface was subscribed
+    /// to the signals of the receiving app.
+    NoSubscriber,
+    /// The receiving conductor refused the signal, because the sending agent is on its
+    /// block list.
+    Blocked,
+    /// The receiving conductor did not answer, even after retrying.
+    Unreachable,
+}
+
+/// The acknowledgement of a direct signal by one of the agents it was sent to.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DirectSignalAck {
+    /// The agent that the signal was sent to.
+    pub agent: AgentPubKey,
+    /// What happened to the signal.
+    pub outcome: DirectSignalOutcome,
+}
+
+/// The longest time that Holochain will keep a durable remote signal in the outbox.
+pub const DURABLE_SIGNAL_MAX_TTL: std::time::Duration =
+    std::time::Duration::from_secs(7 * 24 * 60 * 60);
+
+/// A remote signal in the outbox of the sending conductor, waiting to be sent
+/// to an agent that could not be reached.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutboxSignal {
+    /// The ID of the signal in the outbox.
+    pub id: u64,
+    /// The network that the signal is sent on.
+    pub dna_hash: DnaHash,
+    /// The agent that sent the signal.
+    pub from_agent: AgentPubKey,
+    /// The agent that the signal is for.
+    pub to_agent: AgentPubKey,
+    /// The signal.
+    pub payload: OutboxSignalPayload,
+    /// When the signal was first sent.
+    pub created_at: Timestamp,
+    /// When the signal is dropped if it has not been sent.
+    pub expires_at: Timestamp,
+    /// How many times sending the signal has been retried.
+    pub attempts: u32,
+    /// When sending the signal is retried next.
+    pub next_attempt_at: Timestamp,
+}
+
+impl OutboxSignal {
+    /// The cell that sent the signal.
+    pub fn cell_id(&self) -> CellId {
+        CellId::new(self.dna_hash.clone(), self.from_agent.clone())
+    }
+}
+
+/// The signal of an [`OutboxSignal`].
+///
+/// Signals are stored unsigned, and signed again each time they are sent.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum OutboxSignalPayload {
+    /// A signal sent by a zome with `send_remote_signal`, to be received by
+    /// the `recv_remote_signal` callback of the same zome.
+    Zome {
+        /// The zome that sent the signal.
+        zome_name: ZomeName,
+        /// The signal.
+        signal: ExternIO,
+    },
+    /// A signal sent with the app request `SendDirectSignal`.
+    Direct {
+        /// The signal.
+        signal: Vec<u8>,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,12 +1,9 @@
 //! Web App manifest describing how to bind a Web UI and a happ bundle together
 //!
-//! This will not be used inside Holochain as the bundle to install, but rather as a
-//! unique package that both Holo and the launcher know how to install, in slightly
-//! different ways.
-//!
-//! Eg: when the launcher installs a web-happ bundle, it will extract the WebUI and
-//! install it in the file system. Also, it will extract the happ bundle and call
-//! `InstallApp` with it.
+//! A web-happ bundle can be installed with `InstallApp`, which installs the happ
+//! bundle inside it and keeps the Web UI so that the conductor can serve it from a
+//! UI interface. Launchers can also unpack it themselves: extract the WebUI and
+//! serve it, and call `InstallApp` with the happ bundle.
 
 mod web_app_bundle;
 mod web_app_manifest;
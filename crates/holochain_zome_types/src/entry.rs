@@ -74,10 +74,17 @@ pub struct GetOptions {
     ///
     /// Only used when strategy is [`GetStrategy::Network`] and the remote agent count is >= 2.
     ///
-    /// Note: Setting this to false is not yet implemented.
+    /// When this is false, the responses of all queried agents are merged instead, see
+    /// [`GetOptions::with_as_race`].
     ///
     /// None means use the network's default (race).
     as_race: Option<bool>,
+
+    /// Whether to fail a merged read when the queried agents disagree about whether an action
+    /// is valid, see [`GetOptions::with_fail_on_disagreement`].
+    ///
+    /// None means the merged result is returned regardless.
+    fail_on_disagreement: Option<bool>,
 }
 
 impl GetOptions {
@@ -101,6 +108,11 @@ impl GetOptions {
         self.as_race
     }
 
+    /// Get whether to fail the request when agents disagree about the validity of an action.
+    pub fn fail_on_disagreement(&self) -> Option<bool> {
+        self.fail_on_disagreement
+    }
+
     /// Set the strategy while preserving other options.
     pub fn with_strategy(mut self, strategy: GetStrategy) -> Self {
         self.strategy = strategy;
@@ -118,6 +130,7 @@ impl GetOptions {
             remote_agent_count: None,
             timeout_ms: None,
             as_race: None,
+            fail_on_disagreement: None,
         }
     }
 
@@ -129,6 +142,7 @@ impl GetOptions {
             remote_agent_count: None,
             timeout_ms: None,
             as_race: None,
+            fail_on_disagreement: None,
         }
     }
 
@@ -146,6 +160,33 @@ impl GetOptions {
         self.timeout_ms = Some(timeout);
         self
     }
+
+    /// Set whether to race the remote agents or to aggregate their responses.
+    ///
+    /// When racing, the first response with data is used. Otherwise every one of the
+    /// remote agents is queried and the responses received before the timeout are merged,
+    /// which is slower but suited to reads that must not miss data held by only some agents.
+    /// The merged result contains all of the records, links and deletes that any agent holds.
+    /// If agents disagree about whether an action is valid, the merged result is still
+    /// returned unless [`GetOptions::with_fail_on_disagreement`] is set, and the hashes of
+    /// the disputed actions can be read with the `get_authority_disagreements` host fn.
+    ///
+    /// Applies to `get`, `get_details`, `get_links` and `get_agent_activity`.
+    pub fn with_as_race(mut self, as_race: bool) -> Self {
+        self.as_race = Some(as_race);
+        self
+    }
+
+    /// Set whether to fail the request when the remote agents disagree about whether an
+    /// action is valid.
+    ///
+    /// Only has an effect when responses are aggregated, see [`GetOptions::with_as_race`].
+    /// The request then fails with an error that lists the hashes of the actions the agents
+    /// disagree on, so that the zome can decide how to treat them.
+    pub fn with_fail_on_disagreement(mut self, fail_on_disagreement: bool) -> Self {
+        self.fail_on_disagreement = Some(fail_on_disagreement);
+        self
+    }
 }
 
 impl Default for GetOptions {
@@ -161,6 +202,7 @@ impl From<GetStrategy> for GetOptions {
             remote_agent_count: None,
             timeout_ms: None,
             as_race: None,
+            fail_on_disagreement: None,
         }
     }
 }
@@ -354,6 +396,9 @@ mod tests {
         assert_eq!(options.timeout_ms(), Some(2000));
         assert_eq!(options.as_race(), None);
 
+        let options = GetOptions::network().with_as_race(false);
+        assert_eq!(options.as_race(), Some(false));
+
         // Test capping of remote_agent_count
         let options = GetOptions::network().with_remote_agent_count(20);
         assert_eq!(options.remote_agent_count(), Some(5)); // Capped at MAX_REMOTE_AGENT_COUNT
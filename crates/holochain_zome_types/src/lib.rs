@@ -36,6 +36,7 @@ pub mod link;
 pub mod metadata;
 pub mod op;
 pub mod prelude;
+pub mod presence;
 #[cfg(feature = "properties")]
 pub mod properties;
 pub mod query;
@@ -18,6 +18,7 @@ pub use crate::judged::*;
 pub use crate::link::*;
 pub use crate::metadata::*;
 pub use crate::op::*;
+pub use crate::presence::*;
 #[cfg(feature = "properties")]
 pub use crate::properties::*;
 pub use crate::query::ChainQueryFilter as QueryFilter;
@@ -0,0 +1,68 @@
+//! Types for querying the presence of agents on the network.
+
+use holo_hash::AgentPubKey;
+use holochain_timestamp::Timestamp;
+
+/// Input for the `get_agent_presence` host function.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GetAgentPresenceInput {
+    /// The agents to look up.
+    pub agents: Vec<AgentPubKey>,
+}
+
+impl GetAgentPresenceInput {
+    /// Create a new input to get the presence of the given agents.
+    pub fn new(agents: Vec<AgentPubKey>) -> Self {
+        Self { agents }
+    }
+}
+
+/// What the local conductor knows about the reachability of an agent.
+///
+/// This is read from the local peer store without contacting the agent, so it
+/// reflects the last information that was received about the agent, not its
+/// current state.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AgentPresence {
+    /// The agent this presence information is about.
+    pub agent: AgentPubKey,
+
+    /// When the latest agent info of the agent in the peer store was created.
+    ///
+    /// `None` if there is no agent info for the agent.
+    pub agent_info_created_at: Option<Timestamp>,
+
+    /// When the latest agent info of the agent in the peer store expires.
+    ///
+    /// `None` if there is no agent info for the agent.
+    pub agent_info_expires_at: Option<Timestamp>,
+
+    /// When this conductor last had a successful latency ping to the agent's
+    /// peer URL. Other requests to the agent don't update it.
+    ///
+    /// `None` if there has been no recent successful ping.
+    pub last_contact_at: Option<Timestamp>,
+
+    /// Whether the agent's peer URL has been marked as unresponsive.
+    pub unresponsive: bool,
+
+    /// Whether the agent is likely to accept a connection.
+    ///
+    /// This is `true` if the agent has a known peer URL that has not been
+    /// marked as unresponsive.
+    pub recently_online: bool,
+}
+
+impl AgentPresence {
+    /// Presence of an agent that there is no information about.
+    pub fn unknown(agent: AgentPubKey) -> Self {
+        Self {
+            agent,
+            agent_info_created_at: None,
+            agent_info_expires_at: None,
+            last_contact_at: None,
+            unresponsive: false,
+            recently_online: false,
+        }
+    }
+}
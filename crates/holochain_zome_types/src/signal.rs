@@ -30,3 +30,16 @@ pub struct RemoteSignal {
     /// The signal to send.
     pub signal: ExternIO,
 }
+
+/// Remote signal many agents, keeping the signal for any agent it can't be
+/// sent to.
+///
+/// Signals that can't be sent are kept in the outbox of the sending conductor
+/// and retried for up to `ttl`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct DurableRemoteSignal {
+    /// The signal and the agents to send it to.
+    pub remote_signal: RemoteSignal,
+    /// How long to keep retrying the signal for agents it could not be sent to.
+    pub ttl: std::time::Duration,
+}
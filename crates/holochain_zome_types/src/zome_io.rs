@@ -122,6 +122,9 @@ wasm_io_types! {
     // Remotely signal many agents without waiting for responses
     fn send_remote_signal (zt::prelude::RemoteSignal) -> ();
 
+    // Remotely signal many agents, keeping the signal for agents that can't be reached
+    fn send_remote_signal_durable (zt::prelude::DurableRemoteSignal) -> ();
+
     // Schedule a schedulable function if it is not already.
     fn schedule (String) -> ();
 
@@ -203,6 +206,12 @@ wasm_io_types! {
 
     // Get validation receipts for an action
     fn get_validation_receipts(zt::prelude::GetValidationReceiptsInput) -> Vec<zt::prelude::ValidationReceiptSet>;
+
+    // Get what is locally known about the reachability of agents
+    fn get_agent_presence(zt::prelude::GetAgentPresenceInput) -> Vec<zt::prelude::AgentPresence>;
+
+    // Get the actions that the authorities of aggregated reads in this call disagreed on
+    fn get_authority_disagreements(()) -> Vec<holo_hash::ActionHash>;
 }
 
 /// Anything that can go wrong while calling a HostFnApi method
@@ -1,5 +1,6 @@
 use crate::error::MrBundleError;
 use crate::manifest::ResourceIdentifier;
+use crate::stream::{BundleReader, BundleWriter};
 use crate::{error::MrBundleResult, manifest::Manifest};
 use resource::ResourceBytes;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -29,8 +30,79 @@ where
     /// of the Bundled Locations specified by the Manifest. Bundled Locations
     /// are always relative paths (relative to the root_dir).
     resources: ResourceMap,
+
+    /// The publisher's signature over this bundle, if it has been signed.
+    ///
+    /// Unsigned bundles are serialized without this field, so they are
+    /// unchanged from bundles packed before signatures existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<BundleSignature>,
+}
+
+/// A signature over a [`Bundle`] by its publisher.
+///
+/// The signature is over the [`signing_payload`] of the bundle. Mr. Bundle
+/// does not know how it is made, this is left to the users of a bundle. Both
+/// the publisher key and the signature are opaque bytes here.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleSignature {
+    /// The public key of the publisher that signed the bundle.
+    pub signer: bytes::Bytes,
+
+    /// The signature.
+    pub signature: bytes::Bytes,
+
+    /// The manifest that was signed, encoded with [`Bundle::encode_manifest`]
+    /// when the bundle was signed.
+    ///
+    /// The signature covers these bytes rather than the manifest of the
+    /// bundle, so that it doesn't depend on how the manifest is encoded by
+    /// the version that checks it.
+    pub manifest: bytes::Bytes,
+}
+
+impl BundleSignature {
+    /// Decode the manifest that was signed.
+    pub fn decode_manifest<M: DeserializeOwned>(&self) -> MrBundleResult<M> {
+        rmp_serde::from_slice(&self.manifest).map_err(|e| {
+            MrBundleError::MsgpackDecodeError(std::any::type_name::<M>().to_string(), e)
+        })
+    }
+}
+
+/// The hashes of the resources of a bundle, see [`resource_hash`].
+pub type ResourceHashes = BTreeMap<ResourceIdentifier, [u8; 32]>;
+
+/// The 32 byte blake2b hash of the bytes of a resource.
+pub fn resource_hash(bytes: &[u8]) -> [u8; 32] {
+    let hash = blake2b_simd::Params::new().hash_length(32).hash(bytes);
+    let mut out = [0; 32];
+    out.copy_from_slice(hash.as_bytes());
+    out
 }
 
+/// The bytes a publisher signs to sign a bundle.
+///
+/// This is a hash of the manifest as it is stored with the signature, see
+/// [`BundleSignature::manifest`], and of the hash of every resource, so any
+/// change to the manifest or to a resource invalidates the signature.
+pub fn signing_payload(encoded_manifest: &[u8], resource_hashes: &ResourceHashes) -> Vec<u8> {
+    let mut state = blake2b_simd::Params::new().hash_length(32).to_state();
+    state.update(SIGNING_CONTEXT);
+    state.update(&(encoded_manifest.len() as u64).to_be_bytes());
+    state.update(encoded_manifest);
+    state.update(&(resource_hashes.len() as u64).to_be_bytes());
+    for (resource_id, hash) in resource_hashes {
+        state.update(&(resource_id.len() as u64).to_be_bytes());
+        state.update(resource_id.as_bytes());
+        state.update(hash);
+    }
+    state.finalize().as_bytes().to_vec()
+}
+
+/// Keeps signatures over bundles from being valid for anything else.
+const SIGNING_CONTEXT: &[u8] = b"mr_bundle signature v1";
+
 impl<M> Bundle<M>
 where
     M: Debug + Serialize + DeserializeOwned,
@@ -50,6 +122,43 @@ where
         self.resources.get(resource_identifier)
     }
 
+    /// Accessor for the publisher's signature, if the bundle has been signed.
+    pub fn signature(&self) -> Option<&BundleSignature> {
+        self.signature.as_ref()
+    }
+
+    /// Encode the manifest as it is signed, see [`BundleSignature::manifest`].
+    pub fn encode_manifest(&self) -> MrBundleResult<bytes::Bytes> {
+        Ok(rmp_serde::to_vec_named(&self.manifest)?.into())
+    }
+
+    /// The hashes of the resources of this bundle.
+    pub fn resource_hashes(&self) -> ResourceHashes {
+        self.resources
+            .iter()
+            .map(|(resource_id, bytes)| (resource_id.clone(), resource_hash(bytes.as_ref())))
+            .collect()
+    }
+
+    /// The bytes a publisher signs to sign this bundle, along with the
+    /// encoded manifest that the signature must be stored with.
+    ///
+    /// See [`signing_payload`].
+    pub fn signing_payload(&self) -> MrBundleResult<(bytes::Bytes, Vec<u8>)> {
+        let manifest = self.encode_manifest()?;
+        let payload = signing_payload(&manifest, &self.resource_hashes());
+        Ok((manifest, payload))
+    }
+
+    /// Return this bundle with its signature replaced.
+    ///
+    /// Any change to the manifest or resources produces a new, unsigned
+    /// bundle, so a signature has to be added last.
+    pub fn with_signature(mut self, signature: Option<BundleSignature>) -> Self {
+        self.signature = signature;
+        self
+    }
+
     /// Pack this bundle into a byte array.
     ///
     /// Uses [`pack`](fn@crate::pack) to produce the byte array.
@@ -57,11 +166,44 @@ where
         crate::pack(self)
     }
 
-    /// Unpack bytes produced by [`pack`](Bundle::pack) into a new [Bundle].
+    /// Pack this bundle into a byte array in the streaming format.
+    ///
+    /// Uses a [`BundleWriter`] to produce the byte array. Only readers that
+    /// support the streaming format can unpack it, so bundles that may be
+    /// read by older versions should be packed with [`pack`](Bundle::pack).
+    pub fn pack_streaming(&self) -> MrBundleResult<bytes::Bytes> {
+        let mut writer = BundleWriter::new(Vec::new(), &self.manifest, self.signature.as_ref())?;
+        for (resource_id, resource) in &self.resources {
+            writer.write_resource(resource_id, resource.as_ref())?;
+        }
+        Ok(writer.finish()?.into())
+    }
+
+    /// Unpack bytes produced by [`pack`](Bundle::pack) or
+    /// [`pack_streaming`](Bundle::pack_streaming) into a new [Bundle].
     ///
-    /// Uses [`unpack`](crate::unpack) to produce the new Bundle.
+    /// The format is detected from the first bytes, see [`BundleReader::open`].
     pub fn unpack(source: impl Read) -> MrBundleResult<Self> {
-        crate::unpack(source)
+        Self::read_from(BundleReader::open(source)?)
+    }
+
+    /// Read the remaining resources of a [`BundleReader`] into a new [Bundle].
+    pub(crate) fn read_from<R: Read>(mut reader: BundleReader<M, R>) -> MrBundleResult<Self> {
+        let mut resources = ResourceMap::new();
+        while let Some((resource_id, resource)) = reader.next_resource()? {
+            resources.insert(resource_id, resource);
+        }
+        let (manifest, signature) = reader.into_parts();
+        Ok(Self {
+            manifest,
+            resources,
+            signature,
+        })
+    }
+
+    /// Split this bundle into its manifest, resources and signature.
+    pub(crate) fn into_parts(self) -> (M, ResourceMap, Option<BundleSignature>) {
+        (self.manifest, self.resources, self.signature)
     }
 }
 
@@ -116,6 +258,7 @@ where
         Ok(Self {
             manifest,
             resources,
+            signature: None,
         })
     }
 
@@ -130,6 +273,7 @@ where
 mod tests {
     use super::*;
     use crate::error::MrBundleError;
+    use crate::stream::MAGIC;
     use bytes::Buf;
 
     #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -217,6 +361,150 @@ mod tests {
         assert_eq!(bundle, unpacked);
     }
 
+    #[test]
+    fn unpack_streaming_format() {
+        let manifest = TestManifest(vec!["1.thing".into(), "2.thing".into()]);
+
+        let bundle = Bundle::new(
+            manifest.clone(),
+            vec![
+                ("1.thing".into(), vec![1].into()),
+                ("2.thing".into(), vec![2].into()),
+            ],
+        )
+        .unwrap();
+
+        let packed = bundle.pack().unwrap();
+        let packed_streaming = bundle.pack_streaming().unwrap();
+        assert_ne!(packed, packed_streaming);
+        assert_ne!(MAGIC[..], packed[..MAGIC.len()]);
+        assert_eq!(MAGIC[..], packed_streaming[..MAGIC.len()]);
+
+        let unpacked = Bundle::unpack(packed_streaming.reader()).unwrap();
+        assert_eq!(bundle, unpacked);
+    }
+
+    #[test]
+    fn read_either_format_one_resource_at_a_time() {
+        let manifest = TestManifest(vec!["1.thing".into(), "2.thing".into()]);
+
+        let bundle = Bundle::new(
+            manifest.clone(),
+            vec![
+                ("1.thing".into(), vec![1].into()),
+                ("2.thing".into(), vec![2].into()),
+            ],
+        )
+        .unwrap();
+
+        for packed in [bundle.pack().unwrap(), bundle.pack_streaming().unwrap()] {
+            let mut reader = BundleReader::<TestManifest, _>::open(packed.reader()).unwrap();
+            assert_eq!(&manifest, reader.manifest());
+            let mut resources = ResourceMap::new();
+            while let Some((resource_id, resource)) = reader.next_resource().unwrap() {
+                resources.insert(resource_id, resource);
+            }
+            assert_eq!(bundle.resources, resources);
+        }
+    }
+
+    #[test]
+    fn signature_round_trip() {
+        #[derive(Serialize)]
+        struct UnsignedBundle<'a> {
+            manifest: &'a TestManifest,
+            resources: &'a ResourceMap,
+        }
+
+        let manifest = TestManifest(vec!["1.thing".into()]);
+        let bundle = Bundle::new(manifest, vec![("1.thing".into(), vec![1].into())]).unwrap();
+
+        // An unsigned bundle packs the same as a bundle without a signature field.
+        let unsigned = crate::pack(&UnsignedBundle {
+            manifest: &bundle.manifest,
+            resources: &bundle.resources,
+        })
+        .unwrap();
+        assert_eq!(unsigned, bundle.pack().unwrap());
+        assert_eq!(
+            bundle,
+            Bundle::<TestManifest>::unpack(unsigned.reader()).unwrap()
+        );
+
+        let signature = BundleSignature {
+            signer: vec![1; 32].into(),
+            signature: vec![2; 64].into(),
+            manifest: bundle.encode_manifest().unwrap(),
+        };
+        let signed = bundle.with_signature(Some(signature.clone()));
+        let unpacked = Bundle::<TestManifest>::unpack(signed.pack().unwrap().reader()).unwrap();
+        assert_eq!(Some(&signature), unpacked.signature());
+        let unpacked =
+            Bundle::<TestManifest>::unpack(signed.pack_streaming().unwrap().reader()).unwrap();
+        assert_eq!(Some(&signature), unpacked.signature());
+
+        // Changing the manifest drops the signature.
+        let manifest = unpacked.manifest().clone();
+        assert!(unpacked
+            .update_manifest(manifest)
+            .unwrap()
+            .signature()
+            .is_none());
+    }
+
+    #[test]
+    fn signing_payload_covers_manifest_and_resources() {
+        let manifest = TestManifest(vec!["1.thing".into(), "2.thing".into()]);
+        let bundle = Bundle::new(
+            manifest.clone(),
+            vec![
+                ("1.thing".into(), vec![1].into()),
+                ("2.thing".into(), vec![2].into()),
+            ],
+        )
+        .unwrap();
+        let (encoded_manifest, payload) = bundle.signing_payload().unwrap();
+        assert_eq!(
+            manifest,
+            BundleSignature {
+                signer: Default::default(),
+                signature: Default::default(),
+                manifest: encoded_manifest.clone(),
+            }
+            .decode_manifest::<TestManifest>()
+            .unwrap()
+        );
+
+        // The payload is the same for the unpacked bundle in either format.
+        for packed in [bundle.pack().unwrap(), bundle.pack_streaming().unwrap()] {
+            let unpacked = Bundle::<TestManifest>::unpack(packed.reader()).unwrap();
+            assert_eq!(
+                payload,
+                signing_payload(&encoded_manifest, &unpacked.resource_hashes())
+            );
+        }
+
+        let changed_resource = Bundle::new(
+            manifest,
+            vec![
+                ("1.thing".into(), vec![1].into()),
+                ("2.thing".into(), vec![3].into()),
+            ],
+        )
+        .unwrap();
+        assert_ne!(payload, changed_resource.signing_payload().unwrap().1);
+
+        let changed_manifest = Bundle::new(
+            TestManifest(vec!["2.thing".into(), "1.thing".into()]),
+            vec![
+                ("1.thing".into(), vec![1].into()),
+                ("2.thing".into(), vec![2].into()),
+            ],
+        )
+        .unwrap();
+        assert_ne!(payload, changed_manifest.signing_payload().unwrap().1);
+    }
+
     #[test]
     fn consistent_id_generation_in_mem() {
         #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,6 +26,14 @@ pub enum MrBundleError {
     #[error("Failed to decode bundle to [{0}] due to a deserialization error: {1}")]
     MsgpackDecodeError(String, rmp_serde::decode::Error),
 
+    /// A bundle could not be read or written in the streaming format
+    #[error("Invalid bundle: {0}")]
+    InvalidBundleFormat(String),
+
+    /// A resource in a bundle does not match the hash it was packed with
+    #[error("The resource '{0}' in the bundle does not match its hash")]
+    ResourceHashMismatch(ResourceIdentifier),
+
     /// A YAML error
     #[cfg(feature = "fs")]
     #[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
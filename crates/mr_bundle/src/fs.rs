@@ -1,10 +1,17 @@
 use super::{Bundle, ResourceIdentifier};
 use crate::error::MrBundleError;
-use crate::{error::MrBundleResult, Manifest};
+use crate::stream::{BundleReader, BundleWriter};
+use crate::{error::MrBundleResult, Manifest, ResourceBytes};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
-use std::path::Path;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// The size of the chunks that resource files are streamed into a bundle in.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
 
 /// A recommended conversion from a path to a resource identifier.
 ///
@@ -43,103 +50,217 @@ impl FileSystemBundler {
     ///
     /// The resulting [`Bundle`] will contain the manifest and its resources.
     pub async fn bundle<M: Manifest>(manifest_path: impl AsRef<Path>) -> MrBundleResult<Bundle<M>> {
-        let manifest_path = dunce::canonicalize(manifest_path).map_err(|e| {
-            MrBundleError::IoError("Failed to canonicalize manifest path".to_string(), e)
-        })?;
-        let manifest_yaml = tokio::fs::read_to_string(&manifest_path)
-            .await
-            .map_err(|e| {
-                MrBundleError::IoError(
-                    format!("Failed to read manifest file: {manifest_path:?}"),
-                    e,
-                )
-            })?;
-        let mut manifest: M = yaml_serde::from_str(&manifest_yaml)?;
-
-        let manifest_dir = manifest_path
-            .parent()
-            .ok_or_else(|| MrBundleError::ParentlessPath(manifest_path.to_path_buf()))?;
-        let resources =
-            futures::future::join_all(manifest.generate_resource_ids().into_iter().map(
-                |(resource_id, relative_path)| async move {
-                    let resource_path = manifest_dir.join(&relative_path);
-                    let resource_path = dunce::canonicalize(&resource_path).map_err(|e| {
+        let (manifest, resource_paths) =
+            FileSystemBundler::read_manifest::<M>(manifest_path).await?;
+        let resources = futures::future::join_all(resource_paths.into_iter().map(
+            |(resource_id, resource_path)| async move {
+                tokio::fs::read(&resource_path)
+                    .await
+                    .map(|resource| (resource_id, resource.into()))
+                    .map_err(|e| {
                         MrBundleError::IoError(
-                            format!(
-                                "Failed to canonicalize resource path: {}",
-                                resource_path.display()
-                            ),
+                            format!("Failed to read resource at path: {resource_path:?}"),
                             e,
                         )
-                    })?;
-                    tokio::fs::read(&resource_path)
-                        .await
-                        .map(|resource| (resource_id, resource.into()))
-                        .map_err(|e| {
-                            MrBundleError::IoError(
-                                format!("Failed to read resource at path: {resource_path:?}"),
-                                e,
-                            )
-                        })
-                },
-            ))
-            .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?;
+                    })
+            },
+        ))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
         Bundle::new(manifest, resources)
     }
 
     /// A convenience function that creates a bundle and writes it to the filesystem.
     ///
     /// Uses [`bundle`](FileSystemBundler::bundle) to create the bundle and then writes it to the
-    /// provided `bundle_path`.
+    /// provided `bundle_path`, in the original format that every reader supports.
     pub async fn bundle_to<M: Manifest>(
         manifest_path: impl AsRef<Path>,
         bundle_path: impl AsRef<Path>,
     ) -> MrBundleResult<()> {
         let bundle = FileSystemBundler::bundle::<M>(manifest_path).await?;
 
-        tokio::fs::create_dir_all(
-            bundle_path
-                .as_ref()
-                .parent()
-                .ok_or_else(|| MrBundleError::ParentlessPath(bundle_path.as_ref().to_path_buf()))?,
-        )
-        .await
-        .map_err(|e| {
+        let bundle_path = bundle_path.as_ref();
+        FileSystemBundler::create_bundle_dir(bundle_path).await?;
+        tokio::fs::write(bundle_path, bundle.pack()?)
+            .await
+            .map_err(|e| {
+                MrBundleError::IoError(
+                    format!("Failed to write bundle to path: {}", bundle_path.display()),
+                    e,
+                )
+            })?;
+
+        Ok(())
+    }
+
+    /// Create a bundle from a manifest file and write it to the filesystem in the streaming
+    /// format.
+    ///
+    /// This produces the same bundle as [`bundle`](FileSystemBundler::bundle) packed with
+    /// [`pack_streaming`](Bundle::pack_streaming), but the resources are streamed from their files
+    /// into the bundle file one chunk at a time, so the bundle is never held in memory. Only
+    /// readers that support the streaming format can read the bundle.
+    pub async fn bundle_streaming_to<M: Manifest>(
+        manifest_path: impl AsRef<Path>,
+        bundle_path: impl AsRef<Path>,
+    ) -> MrBundleResult<()> {
+        let (manifest, resource_paths) =
+            FileSystemBundler::read_manifest::<M>(manifest_path).await?;
+
+        let bundle_path = bundle_path.as_ref();
+        FileSystemBundler::create_bundle_dir(bundle_path).await?;
+        let write_err = |e| {
+            MrBundleError::IoError(
+                format!("Failed to write bundle to path: {}", bundle_path.display()),
+                e,
+            )
+        };
+        let mut file = tokio::fs::File::create(bundle_path)
+            .await
+            .map_err(write_err)?;
+
+        // The writer compresses into a buffer, which is drained to the file after every chunk.
+        let mut writer = BundleWriter::new(Vec::new(), &manifest, None)?;
+        let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+        for (resource_id, resource_path) in resource_paths {
+            let read_err = |e| {
+                MrBundleError::IoError(
+                    format!("Failed to read resource at path: {resource_path:?}"),
+                    e,
+                )
+            };
+            let mut resource = tokio::fs::File::open(&resource_path)
+                .await
+                .map_err(read_err)?;
+            let len = resource.metadata().await.map_err(read_err)?.len();
+
+            writer.begin_resource(&resource_id, len)?;
+            loop {
+                let n = resource.read(&mut chunk).await.map_err(read_err)?;
+                if n == 0 {
+                    break;
+                }
+                writer.write_resource_data(&chunk[..n])?;
+                file.write_all(writer.get_mut()).await.map_err(write_err)?;
+                writer.get_mut().clear();
+            }
+            writer.end_resource()?;
+        }
+        file.write_all(&writer.finish()?).await.map_err(write_err)?;
+        file.flush().await.map_err(write_err)?;
+
+        Ok(())
+    }
+
+    /// Unpack a bundle file directly to a directory, one resource at a time.
+    ///
+    /// This is equivalent to [`load_from`](FileSystemBundler::load_from) followed by
+    /// [`expand_to`](FileSystemBundler::expand_to), but bundles in the streaming format are read
+    /// from the file as they are written out, so only one decompressed resource is held in memory
+    /// at a time. Bundles in the original format are still supported, but are unpacked in full
+    /// first, see [`BundleReader::open`].
+    pub async fn unpack_to<M: Manifest + Send + 'static>(
+        bundle_path: impl AsRef<Path>,
+        target_dir: impl AsRef<Path>,
+        force: bool,
+    ) -> MrBundleResult<()> {
+        let bundle_path = bundle_path.as_ref().to_path_buf();
+        let target_dir = target_dir.as_ref();
+
+        // The file is read on a blocking thread, which sends each resource on as it is read.
+        let (resource_tx, mut resource_rx) = tokio::sync::mpsc::channel(1);
+        let read = tokio::task::spawn_blocking(move || {
+            let mut reader = open_bundle_file::<M>(&bundle_path)?;
+            while let Some(resource) = reader.next_resource()? {
+                if resource_tx.blocking_send(resource).is_err() {
+                    // Writing failed, which is reported below.
+                    break;
+                }
+            }
+            Ok(reader.into_parts().0)
+        });
+
+        FileSystemBundler::create_target_dir(target_dir, force).await?;
+        while let Some((resource_id, resource)) = resource_rx.recv().await {
+            FileSystemBundler::write_resource_to(target_dir, &resource_id, &resource).await?;
+        }
+        let manifest = join_blocking(read).await?;
+        FileSystemBundler::write_manifest_to(&manifest, M::file_name(), target_dir).await
+    }
+
+    /// Create the directory a bundle file is written to.
+    async fn create_bundle_dir(bundle_path: &Path) -> MrBundleResult<()> {
+        let bundle_dir = bundle_path
+            .parent()
+            .ok_or_else(|| MrBundleError::ParentlessPath(bundle_path.to_path_buf()))?;
+        tokio::fs::create_dir_all(bundle_dir).await.map_err(|e| {
             MrBundleError::IoError(
                 format!(
                     "Failed to create bundle directory: {}",
-                    bundle_path.as_ref().display()
+                    bundle_path.display()
                 ),
                 e,
             )
-        })?;
+        })
+    }
 
-        let bundle_path = bundle_path.as_ref();
-        tokio::fs::write(bundle_path, bundle.pack()?)
+    /// Read a manifest file, returning the manifest with its resource identifiers generated and
+    /// the path of each resource, sorted by resource identifier.
+    async fn read_manifest<M: Manifest>(
+        manifest_path: impl AsRef<Path>,
+    ) -> MrBundleResult<(M, BTreeMap<ResourceIdentifier, PathBuf>)> {
+        let manifest_path = dunce::canonicalize(manifest_path).map_err(|e| {
+            MrBundleError::IoError("Failed to canonicalize manifest path".to_string(), e)
+        })?;
+        let manifest_yaml = tokio::fs::read_to_string(&manifest_path)
             .await
             .map_err(|e| {
                 MrBundleError::IoError(
-                    format!("Failed to write bundle to path: {}", bundle_path.display()),
+                    format!("Failed to read manifest file: {manifest_path:?}"),
                     e,
                 )
             })?;
+        let mut manifest: M = yaml_serde::from_str(&manifest_yaml)?;
 
-        Ok(())
+        let manifest_dir = manifest_path
+            .parent()
+            .ok_or_else(|| MrBundleError::ParentlessPath(manifest_path.to_path_buf()))?;
+        let resource_paths = manifest
+            .generate_resource_ids()
+            .into_iter()
+            .map(|(resource_id, relative_path)| {
+                let resource_path = manifest_dir.join(&relative_path);
+                let resource_path = dunce::canonicalize(&resource_path).map_err(|e| {
+                    MrBundleError::IoError(
+                        format!(
+                            "Failed to canonicalize resource path: {}",
+                            resource_path.display()
+                        ),
+                        e,
+                    )
+                })?;
+                Ok((resource_id, resource_path))
+            })
+            .collect::<MrBundleResult<_>>()?;
+
+        Ok((manifest, resource_paths))
     }
 
     /// Load a bundle from the filesystem.
     ///
-    /// The bundle is automatically unpacked into a [`Bundle`] object.
-    pub async fn load_from<M: Debug + Serialize + DeserializeOwned>(
+    /// The bundle is automatically unpacked into a [`Bundle`] object. Bundles in the streaming
+    /// format are decompressed and read one resource at a time as the file is read, so only the
+    /// unpacked bundle is held in memory.
+    pub async fn load_from<M: Debug + Serialize + DeserializeOwned + Send + 'static>(
         bundle_path: impl AsRef<Path>,
     ) -> MrBundleResult<Bundle<M>> {
-        let bundle_path = bundle_path.as_ref();
-        let bundle_bytes = tokio::fs::read(bundle_path).await.map_err(|e| {
-            MrBundleError::IoError(format!("Failed to read bundle file: {bundle_path:?}"), e)
-        })?;
-        Bundle::unpack(&bundle_bytes[..])
+        let bundle_path = bundle_path.as_ref().to_path_buf();
+        join_blocking(tokio::task::spawn_blocking(move || {
+            Bundle::read_from(open_bundle_file(&bundle_path)?)
+        }))
+        .await
     }
 
     /// Write the contents of the bundle to the filesystem.
@@ -167,7 +288,18 @@ impl FileSystemBundler {
         force: bool,
     ) -> MrBundleResult<()> {
         let target_dir = target_dir.as_ref();
+        FileSystemBundler::create_target_dir(target_dir, force).await?;
+        FileSystemBundler::write_manifest_to(bundle.manifest(), manifest_file_name, target_dir)
+            .await?;
+        for (resource_id, resource) in bundle.get_all_resources() {
+            FileSystemBundler::write_resource_to(target_dir, resource_id, resource).await?;
+        }
+
+        Ok(())
+    }
 
+    /// Create the directory a bundle is expanded into.
+    async fn create_target_dir(target_dir: &Path, force: bool) -> MrBundleResult<()> {
         // If the directory already exists, and we're not forcing, then we can't continue.
         if !force && target_dir.exists() {
             return Err(MrBundleError::DirectoryExists(target_dir.to_owned()));
@@ -181,8 +313,16 @@ impl FileSystemBundler {
             )
         })?;
 
-        // Write the manifest to the target directory.
-        let yaml_str = yaml_serde::to_string(bundle.manifest())?;
+        Ok(())
+    }
+
+    /// Write the manifest to the target directory.
+    async fn write_manifest_to<M: Serialize>(
+        manifest: &M,
+        manifest_file_name: &str,
+        target_dir: &Path,
+    ) -> MrBundleResult<()> {
+        let yaml_str = yaml_serde::to_string(manifest)?;
         let manifest_path = target_dir.join(manifest_file_name);
         tokio::fs::write(&manifest_path, yaml_str.as_bytes())
             .await
@@ -193,28 +333,56 @@ impl FileSystemBundler {
                 )
             })?;
 
-        // Write the resources to the target directory.
-        for (resource_id, resource) in bundle.get_all_resources() {
-            let path = target_dir.join(resource_id);
-            let path_clone = path.clone();
-            let parent = path_clone
-                .parent()
-                .ok_or_else(|| MrBundleError::ParentlessPath(path.clone()))?;
-            tokio::fs::create_dir_all(&parent).await.map_err(|e| {
-                MrBundleError::IoError(
-                    format!("Failed to create resource directory: {parent:?}"),
-                    e,
-                )
-            })?;
-            tokio::fs::write(&path, resource).await.map_err(|e| {
-                MrBundleError::IoError(format!("Failed to write resource to path: {path:?}"), e)
-            })?;
-        }
+        Ok(())
+    }
+
+    /// Write a single resource to the target directory.
+    async fn write_resource_to(
+        target_dir: &Path,
+        resource_id: &ResourceIdentifier,
+        resource: &ResourceBytes,
+    ) -> MrBundleResult<()> {
+        let path = target_dir.join(resource_id);
+        let path_clone = path.clone();
+        let parent = path_clone
+            .parent()
+            .ok_or_else(|| MrBundleError::ParentlessPath(path.clone()))?;
+        tokio::fs::create_dir_all(&parent).await.map_err(|e| {
+            MrBundleError::IoError(
+                format!("Failed to create resource directory: {parent:?}"),
+                e,
+            )
+        })?;
+        tokio::fs::write(&path, resource).await.map_err(|e| {
+            MrBundleError::IoError(format!("Failed to write resource to path: {path:?}"), e)
+        })?;
 
         Ok(())
     }
 }
 
+/// Open a bundle file in either format, see [`BundleReader::open`].
+///
+/// This blocks, so it must be called from a blocking task.
+fn open_bundle_file<M: Debug + Serialize + DeserializeOwned>(
+    bundle_path: &Path,
+) -> MrBundleResult<BundleReader<M, BufReader<std::fs::File>>> {
+    let file = std::fs::File::open(bundle_path).map_err(|e| {
+        MrBundleError::IoError(format!("Failed to read bundle file: {bundle_path:?}"), e)
+    })?;
+    BundleReader::open(BufReader::new(file))
+}
+
+/// Wait for a blocking task that reads a bundle file.
+async fn join_blocking<T>(task: tokio::task::JoinHandle<MrBundleResult<T>>) -> MrBundleResult<T> {
+    task.await.map_err(|e| {
+        MrBundleError::IoError(
+            "Failed to load bundle".to_string(),
+            std::io::Error::other(e),
+        )
+    })?
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,8 +144,12 @@ pub mod error;
 mod fs;
 mod manifest;
 mod pack;
+pub mod stream;
 
-pub use bundle::{resource::ResourceBytes, Bundle, ResourceMap};
+pub use bundle::{
+    resource::ResourceBytes, resource_hash, signing_payload, Bundle, BundleSignature,
+    ResourceHashes, ResourceMap,
+};
 #[cfg(feature = "fs")]
 pub use fs::{resource_id_for_path, FileSystemBundler};
 pub use manifest::{Manifest, ResourceIdentifier};
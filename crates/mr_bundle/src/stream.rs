@@ -0,0 +1,457 @@
+//! A streaming container format for bundles.
+//!
+//! The original bundle format is a single msgpack encoding of the whole
+//! [`Bundle`](crate::Bundle), compressed with gzip. Packing or unpacking it
+//! needs the complete bundle in memory, several times over. This format
+//! frames each resource separately, so a bundle can be written and read one
+//! resource at a time.
+//!
+//! The layout is:
+//!
+//! - the 4 byte [`MAGIC`] header, uncompressed,
+//! - followed by a gzip stream containing:
+//!   - the length of the header as a big endian `u32`, then the msgpack
+//!     encoded header with the manifest and the optional signature,
+//!   - for each resource, a `1` byte, the length of the resource identifier
+//!     as a big endian `u32`, the identifier as UTF-8, the length of the
+//!     resource as a big endian `u64`, the resource bytes and then the 32 byte
+//!     blake2b hash of the resource bytes,
+//!   - a `0` byte to mark the end of the bundle.
+//!
+//! The hash is written after the resource so that it can be computed while
+//! the resource is streamed in.
+
+use crate::bundle::resource::ResourceBytes;
+use crate::error::{MrBundleError, MrBundleResult};
+use crate::{Bundle, BundleSignature, ResourceIdentifier};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{btree_map, HashSet};
+use std::fmt::Debug;
+use std::io::{Read, Write};
+
+/// The bytes that every bundle in the streaming format starts with.
+///
+/// Bundles in the original format are plain gzip streams, which start with
+/// `0x1f 0x8b`, so the two formats can be told apart by their first bytes.
+pub const MAGIC: [u8; 4] = *b"MRB\x02";
+
+const RESOURCE_TAG: u8 = 1;
+const END_TAG: u8 = 0;
+const HASH_LEN: usize = 32;
+
+#[derive(Serialize)]
+struct StreamHeaderRef<'a, M> {
+    manifest: &'a M,
+    signature: Option<&'a BundleSignature>,
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "M: DeserializeOwned"))]
+struct StreamHeader<M> {
+    manifest: M,
+    signature: Option<BundleSignature>,
+}
+
+/// Writes a bundle in the streaming format, one resource at a time.
+///
+/// Resources are written either in one go with
+/// [`write_resource`](BundleWriter::write_resource), or in chunks by calling
+/// [`begin_resource`](BundleWriter::begin_resource), then
+/// [`write_resource_data`](BundleWriter::write_resource_data) as many times as
+/// needed and finally [`end_resource`](BundleWriter::end_resource).
+pub struct BundleWriter<W: Write> {
+    encoder: flate2::write::GzEncoder<W>,
+    current: Option<PendingResource>,
+    written: HashSet<ResourceIdentifier>,
+}
+
+struct PendingResource {
+    id: ResourceIdentifier,
+    remaining: u64,
+    hasher: blake2b_simd::State,
+}
+
+impl<W: Write> BundleWriter<W> {
+    /// Start a bundle by writing its manifest and signature to `writer`.
+    pub fn new<M: Serialize>(
+        mut writer: W,
+        manifest: &M,
+        signature: Option<&BundleSignature>,
+    ) -> MrBundleResult<Self> {
+        writer
+            .write_all(&MAGIC)
+            .map_err(|e| MrBundleError::IoError("Failed to write bundle header".to_string(), e))?;
+
+        let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+        let header = rmp_serde::to_vec_named(&StreamHeaderRef {
+            manifest,
+            signature,
+        })?;
+        let header_len = u32::try_from(header.len()).map_err(|_| {
+            MrBundleError::InvalidBundleFormat("the manifest is too large".to_string())
+        })?;
+        write_all(&mut encoder, &header_len.to_be_bytes())?;
+        write_all(&mut encoder, &header)?;
+
+        Ok(Self {
+            encoder,
+            current: None,
+            written: HashSet::new(),
+        })
+    }
+
+    /// Write a whole resource.
+    pub fn write_resource(&mut self, id: &ResourceIdentifier, bytes: &[u8]) -> MrBundleResult<()> {
+        self.begin_resource(id, bytes.len() as u64)?;
+        self.write_resource_data(bytes)?;
+        self.end_resource()
+    }
+
+    /// Start a resource of `len` bytes, which must then be provided with
+    /// [`write_resource_data`](BundleWriter::write_resource_data).
+    pub fn begin_resource(&mut self, id: &ResourceIdentifier, len: u64) -> MrBundleResult<()> {
+        if let Some(current) = &self.current {
+            return Err(MrBundleError::InvalidBundleFormat(format!(
+                "resource '{}' was not finished before starting '{id}'",
+                current.id
+            )));
+        }
+        if !self.written.insert(id.clone()) {
+            return Err(MrBundleError::InvalidBundleFormat(format!(
+                "resource '{id}' was written twice"
+            )));
+        }
+        let id_len = u32::try_from(id.len()).map_err(|_| {
+            MrBundleError::InvalidBundleFormat("a resource identifier is too long".to_string())
+        })?;
+
+        write_all(&mut self.encoder, &[RESOURCE_TAG])?;
+        write_all(&mut self.encoder, &id_len.to_be_bytes())?;
+        write_all(&mut self.encoder, id.as_bytes())?;
+        write_all(&mut self.encoder, &len.to_be_bytes())?;
+
+        self.current = Some(PendingResource {
+            id: id.clone(),
+            remaining: len,
+            hasher: hasher(),
+        });
+        Ok(())
+    }
+
+    /// Write the next chunk of the current resource.
+    pub fn write_resource_data(&mut self, data: &[u8]) -> MrBundleResult<()> {
+        let current = self.current.as_mut().ok_or_else(|| {
+            MrBundleError::InvalidBundleFormat("no resource has been started".to_string())
+        })?;
+        if data.len() as u64 > current.remaining {
+            return Err(MrBundleError::InvalidBundleFormat(format!(
+                "resource '{}' is longer than its declared length",
+                current.id
+            )));
+        }
+        current.remaining -= data.len() as u64;
+        current.hasher.update(data);
+        write_all(&mut self.encoder, data)
+    }
+
+    /// Finish the current resource by writing its hash.
+    pub fn end_resource(&mut self) -> MrBundleResult<()> {
+        let current = self.current.take().ok_or_else(|| {
+            MrBundleError::InvalidBundleFormat("no resource has been started".to_string())
+        })?;
+        if current.remaining != 0 {
+            return Err(MrBundleError::InvalidBundleFormat(format!(
+                "resource '{}' is shorter than its declared length",
+                current.id
+            )));
+        }
+        write_all(&mut self.encoder, current.hasher.finalize().as_bytes())
+    }
+
+    /// Access the underlying writer, for example to drain compressed output
+    /// that has been written to a buffer.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.encoder.get_mut()
+    }
+
+    /// Mark the end of the bundle and return the underlying writer.
+    pub fn finish(mut self) -> MrBundleResult<W> {
+        if let Some(current) = &self.current {
+            return Err(MrBundleError::InvalidBundleFormat(format!(
+                "resource '{}' was not finished",
+                current.id
+            )));
+        }
+        write_all(&mut self.encoder, &[END_TAG])?;
+        self.encoder.finish().map_err(|e| {
+            MrBundleError::IoError("Failed to finish compressing bundle".to_string(), e)
+        })
+    }
+}
+
+/// Reads a bundle one resource at a time.
+///
+/// The manifest is read when the reader is created. Resources are then read
+/// in the order they were written, and each one is checked against its hash.
+pub struct BundleReader<M, R: Read> {
+    resources: Resources<R>,
+    manifest: M,
+    signature: Option<BundleSignature>,
+    seen: HashSet<ResourceIdentifier>,
+    done: bool,
+}
+
+/// Where a [`BundleReader`] reads its resources from.
+enum Resources<R: Read> {
+    /// The frames of a bundle in the streaming format.
+    Frames(Box<flate2::read::GzDecoder<R>>),
+    /// The resources of a bundle in the original format, which had to be
+    /// unpacked in full.
+    Unpacked(btree_map::IntoIter<ResourceIdentifier, ResourceBytes>),
+}
+
+impl<M: DeserializeOwned, R: Read> BundleReader<M, R> {
+    /// Read the header of a bundle, including its manifest.
+    ///
+    /// Fails if the source is not in the streaming format.
+    pub fn new(mut source: R) -> MrBundleResult<Self> {
+        let mut magic = [0; MAGIC.len()];
+        source
+            .read_exact(&mut magic)
+            .map_err(|e| MrBundleError::IoError("Failed to read bundle header".to_string(), e))?;
+        if magic != MAGIC {
+            return Err(MrBundleError::InvalidBundleFormat(
+                "this is not a streaming bundle".to_string(),
+            ));
+        }
+
+        Self::read_header(source)
+    }
+
+    /// Read the header that follows the [`MAGIC`] bytes.
+    fn read_header(source: R) -> MrBundleResult<Self> {
+        let mut decoder = flate2::read::GzDecoder::new(source);
+        let header_len = u32::from_be_bytes(read_array(&mut decoder)?);
+        let header = read_vec(&mut decoder, header_len as u64)?;
+        let header: StreamHeader<M> = rmp_serde::from_slice(&header).map_err(|e| {
+            MrBundleError::MsgpackDecodeError(std::any::type_name::<M>().to_string(), e)
+        })?;
+
+        Ok(Self {
+            resources: Resources::Frames(Box::new(decoder)),
+            manifest: header.manifest,
+            signature: header.signature,
+            seen: HashSet::new(),
+            done: false,
+        })
+    }
+
+    /// Accessor for the manifest.
+    pub fn manifest(&self) -> &M {
+        &self.manifest
+    }
+
+    /// Accessor for the publisher's signature, if the bundle has been signed.
+    pub fn signature(&self) -> Option<&BundleSignature> {
+        self.signature.as_ref()
+    }
+
+    /// Read the next resource, or `None` once all resources have been read.
+    ///
+    /// Only this resource is held in memory.
+    pub fn next_resource(&mut self) -> MrBundleResult<Option<(ResourceIdentifier, ResourceBytes)>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let decoder = match &mut self.resources {
+            Resources::Frames(decoder) => decoder,
+            Resources::Unpacked(resources) => return Ok(resources.next()),
+        };
+
+        let [tag] = read_array(decoder)?;
+        match tag {
+            END_TAG => {
+                self.done = true;
+                return Ok(None);
+            }
+            RESOURCE_TAG => {}
+            _ => {
+                return Err(MrBundleError::InvalidBundleFormat(format!(
+                    "unexpected frame tag {tag}"
+                )))
+            }
+        }
+
+        let id_len = u32::from_be_bytes(read_array(decoder)?);
+        let id = String::from_utf8(read_vec(decoder, id_len as u64)?).map_err(|_| {
+            MrBundleError::InvalidBundleFormat("a resource identifier is not UTF-8".to_string())
+        })?;
+        if !self.seen.insert(id.clone()) {
+            return Err(MrBundleError::InvalidBundleFormat(format!(
+                "resource '{id}' appears twice"
+            )));
+        }
+
+        let len = u64::from_be_bytes(read_array(decoder)?);
+        let bytes = read_vec(decoder, len)?;
+        let hash: [u8; HASH_LEN] = read_array(decoder)?;
+        if hasher().update(&bytes).finalize().as_bytes() != hash {
+            return Err(MrBundleError::ResourceHashMismatch(id));
+        }
+
+        Ok(Some((id, bytes.into())))
+    }
+
+    /// Consume the reader, returning the manifest and signature.
+    pub fn into_parts(self) -> (M, Option<BundleSignature>) {
+        (self.manifest, self.signature)
+    }
+}
+
+impl<M: Debug + Serialize + DeserializeOwned, R: Read> BundleReader<M, R> {
+    /// Open a bundle in either format, detected from its first bytes.
+    ///
+    /// Bundles in the streaming format are read like with
+    /// [`new`](BundleReader::new). Bundles in the original format can only be
+    /// unpacked in full, so all of their resources are in memory once this
+    /// returns, and are then handed out one at a time.
+    pub fn open(mut source: R) -> MrBundleResult<Self> {
+        let mut magic = Vec::with_capacity(MAGIC.len());
+        (&mut source)
+            .take(MAGIC.len() as u64)
+            .read_to_end(&mut magic)
+            .map_err(|e| MrBundleError::IoError("Failed to read bundle header".to_string(), e))?;
+        if magic[..] == MAGIC[..] {
+            return Self::read_header(source);
+        }
+
+        let bundle: Bundle<M> = crate::unpack(std::io::Cursor::new(magic).chain(source))?;
+        let (manifest, resources, signature) = bundle.into_parts();
+        Ok(Self {
+            resources: Resources::Unpacked(resources.into_iter()),
+            manifest,
+            signature,
+            seen: HashSet::new(),
+            done: false,
+        })
+    }
+}
+
+fn hasher() -> blake2b_simd::State {
+    blake2b_simd::Params::new().hash_length(HASH_LEN).to_state()
+}
+
+fn write_all(writer: &mut impl Write, data: &[u8]) -> MrBundleResult<()> {
+    writer
+        .write_all(data)
+        .map_err(|e| MrBundleError::IoError("Failed to write bundle".to_string(), e))
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> MrBundleResult<[u8; N]> {
+    let mut out = [0; N];
+    reader
+        .read_exact(&mut out)
+        .map_err(|e| MrBundleError::IoError("Failed to read bundle".to_string(), e))?;
+    Ok(out)
+}
+
+fn read_vec(reader: &mut impl Read, len: u64) -> MrBundleResult<Vec<u8>> {
+    // Don't trust the length for the allocation, a corrupt bundle could
+    // claim any size.
+    let mut out = Vec::new();
+    reader
+        .take(len)
+        .read_to_end(&mut out)
+        .map_err(|e| MrBundleError::IoError("Failed to read bundle".to_string(), e))?;
+    if out.len() as u64 != len {
+        return Err(MrBundleError::InvalidBundleFormat(
+            "the bundle ended unexpectedly".to_string(),
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_round_trip() {
+        let signature = BundleSignature {
+            signer: vec![1; 32].into(),
+            signature: vec![2; 64].into(),
+            manifest: vec![3; 8].into(),
+        };
+        let mut writer =
+            BundleWriter::new(Vec::new(), &"manifest".to_string(), Some(&signature)).unwrap();
+        writer.write_resource(&"a".to_string(), &[1, 2, 3]).unwrap();
+        writer.begin_resource(&"b".to_string(), 4).unwrap();
+        writer.write_resource_data(&[4, 5]).unwrap();
+        writer.write_resource_data(&[6, 7]).unwrap();
+        writer.end_resource().unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = BundleReader::<String, _>::new(&bytes[..]).unwrap();
+        assert_eq!("manifest", reader.manifest());
+        assert_eq!(Some(&signature), reader.signature());
+        assert_eq!(
+            Some(("a".to_string(), vec![1, 2, 3].into())),
+            reader.next_resource().unwrap()
+        );
+        assert_eq!(
+            Some(("b".to_string(), vec![4, 5, 6, 7].into())),
+            reader.next_resource().unwrap()
+        );
+        assert_eq!(None, reader.next_resource().unwrap());
+        assert_eq!(None, reader.next_resource().unwrap());
+    }
+
+    #[test]
+    fn writer_checks_resource_lengths() {
+        let mut writer = BundleWriter::new(Vec::new(), &(), None).unwrap();
+        writer.begin_resource(&"a".to_string(), 2).unwrap();
+        assert!(matches!(
+            writer.write_resource_data(&[1, 2, 3]),
+            Err(MrBundleError::InvalidBundleFormat(_))
+        ));
+        writer.write_resource_data(&[1]).unwrap();
+        assert!(matches!(
+            writer.end_resource(),
+            Err(MrBundleError::InvalidBundleFormat(_))
+        ));
+
+        let mut writer = BundleWriter::new(Vec::new(), &(), None).unwrap();
+        writer.write_resource(&"a".to_string(), &[1]).unwrap();
+        assert!(matches!(
+            writer.write_resource(&"a".to_string(), &[1]),
+            Err(MrBundleError::InvalidBundleFormat(_))
+        ));
+    }
+
+    #[test]
+    fn reader_detects_corrupt_resources() {
+        let mut writer = BundleWriter::new(Vec::new(), &(), None).unwrap();
+        writer.write_resource(&"a".to_string(), &[1, 2, 3]).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        // Re-compress the frames with one byte of the resource changed
+        let mut frames = Vec::new();
+        flate2::read::GzDecoder::new(&bytes[MAGIC.len()..])
+            .read_to_end(&mut frames)
+            .unwrap();
+        let pos = frames.windows(3).position(|w| w == [1, 2, 3]).unwrap();
+        frames[pos] = 9;
+        let mut corrupt = MAGIC.to_vec();
+        let mut encoder = flate2::write::GzEncoder::new(&mut corrupt, Default::default());
+        encoder.write_all(&frames).unwrap();
+        encoder.finish().unwrap();
+
+        let mut reader = BundleReader::<(), _>::new(&corrupt[..]).unwrap();
+        assert!(matches!(
+            reader.next_resource(),
+            Err(MrBundleError::ResourceHashMismatch(id)) if id == "a"
+        ));
+    }
+}
@@ -209,3 +209,82 @@ async fn file_system_bundler_with_raw_bundle() {
         std::fs::read_to_string(unpacked_dir.join("bundled.thing")).unwrap()
     );
 }
+
+#[tokio::test]
+async fn file_system_bundler_streams_large_resources() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // A resource that spans several chunks when streamed
+    let content = (0..200_000u32)
+        .flat_map(|i| i.to_le_bytes())
+        .collect::<Vec<_>>();
+    tokio::fs::write(dir.path().join("large.thing"), &content)
+        .await
+        .unwrap();
+
+    let manifest = TestManifest::V0(ManifestV0 {
+        name: "name".to_string(),
+        things: vec![ThingManifest {
+            location: "large.thing".to_string(),
+        }],
+    });
+    let manifest_path = dir.path().join(TestManifest::file_name());
+    tokio::fs::write(&manifest_path, yaml_serde::to_string(&manifest).unwrap())
+        .await
+        .unwrap();
+
+    let bundle_path = dir
+        .path()
+        .join(format!("test-bundle.{}", TestManifest::bundle_extension()));
+    FileSystemBundler::bundle_streaming_to::<TestManifest>(&manifest_path, &bundle_path)
+        .await
+        .unwrap();
+
+    // The streamed bundle is the same as one packed in memory
+    let bundle = FileSystemBundler::bundle::<TestManifest>(&manifest_path)
+        .await
+        .unwrap();
+    assert_eq!(
+        bundle.pack_streaming().unwrap(),
+        tokio::fs::read(&bundle_path).await.unwrap()
+    );
+    assert_eq!(
+        bundle,
+        FileSystemBundler::load_from::<TestManifest>(&bundle_path)
+            .await
+            .unwrap()
+    );
+
+    let unpacked_dir = dir.path().join("unpacked");
+    FileSystemBundler::unpack_to::<TestManifest>(&bundle_path, &unpacked_dir, false)
+        .await
+        .unwrap();
+    assert_eq!(
+        content,
+        std::fs::read(unpacked_dir.join("large.thing")).unwrap()
+    );
+
+    // Bundles are written in the original format by default, and can still be unpacked
+    let legacy_bundle_path = dir.path().join("legacy.bundle");
+    FileSystemBundler::bundle_to::<TestManifest>(&manifest_path, &legacy_bundle_path)
+        .await
+        .unwrap();
+    assert_eq!(
+        bundle.pack().unwrap(),
+        tokio::fs::read(&legacy_bundle_path).await.unwrap()
+    );
+    let legacy_unpacked_dir = dir.path().join("legacy-unpacked");
+    FileSystemBundler::unpack_to::<TestManifest>(&legacy_bundle_path, &legacy_unpacked_dir, false)
+        .await
+        .unwrap();
+    assert_eq!(
+        content,
+        std::fs::read(legacy_unpacked_dir.join("large.thing")).unwrap()
+    );
+    assert_eq!(
+        bundle,
+        FileSystemBundler::load_from::<TestManifest>(&legacy_bundle_path)
+            .await
+            .unwrap()
+    );
+}
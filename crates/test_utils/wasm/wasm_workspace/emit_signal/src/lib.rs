@@ -12,6 +12,15 @@ fn signal_others(signal: RemoteSignal) -> ExternResult<()> {
     send_remote_signal(&signal.signal, signal.agents)
 }
 
+#[hdk_extern]
+fn signal_others_durable(input: DurableRemoteSignal) -> ExternResult<()> {
+    send_remote_signal_durable(
+        &input.remote_signal.signal,
+        input.remote_signal.agents,
+        input.ttl,
+    )
+}
+
 #[hdk_extern]
 fn recv_remote_signal(signal: ExternIO) -> ExternResult<()> {
     HDK.with(|h| h.borrow().emit_signal(AppSignal::new(signal)))